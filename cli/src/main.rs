@@ -0,0 +1,247 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Command line tool for RISC-V E-Traces
+//!
+//! This tool decodes and traces RISC-V E-Trace data using the `riscv-etrace`
+//! library. It supports the following subcommands:
+//!
+//! * `decode`: decode all packets in a trace file and print them
+//! * `trace`: trace all harts and print the trace items in a format similar to
+//!   `objdump`'s disassembly
+//! * `export`: trace all harts and export packets and items in JSON Lines
+//!   format, or items with their packets' timestamps in Perfetto format
+//! * `diff`: trace a single hart and compare the traced PCs against a
+//!   reference file containing one hexadecimal PC per line
+//!
+//! Trace files compressed with Zstandard or LZ4 are decompressed transparently
+//! if the tool is built with the `zstd` or `lz4` feature respectively.
+//!
+//! All processing is done by the library. This tool only ties together the
+//! necessary components based on the command line arguments.
+
+use std::error::Error;
+use std::fs;
+use std::io::{self, BufRead, Read};
+use std::path::{Path, PathBuf};
+
+use riscv_etrace::analysis::reference::Comparator;
+use riscv_etrace::binary::{self, elf::Elf};
+use riscv_etrace::capture;
+use riscv_etrace::config::Parameters;
+use riscv_etrace::export::{jsonl, perfetto, text};
+use riscv_etrace::packet::{self, unit};
+use riscv_etrace::session::{self, Framing};
+use riscv_etrace::types::stack::VecStack;
+
+type Result<T> = core::result::Result<T, Box<dyn Error>>;
+
+type ElfBytes<'d> = elf::ElfBytes<'d, elf::endian::LittleEndian>;
+
+type Binary<'d> = binary::Multi<
+    Vec<Elf<'d, &'d ElfBytes<'d>, elf::endian::LittleEndian>>,
+    Elf<'d, &'d ElfBytes<'d>, elf::endian::LittleEndian>,
+>;
+
+type Session<'d> = session::Session<unit::Plug, Binary<'d>, VecStack>;
+
+#[cfg(test)]
+mod tests;
+
+fn main() -> std::process::ExitCode {
+    run(&command().get_matches()).map_or_else(
+        |e| {
+            eprintln!("Error: {e}");
+            std::process::ExitCode::FAILURE
+        },
+        |_| std::process::ExitCode::SUCCESS,
+    )
+}
+
+/// Run the subcommand selected via the given command line arguments
+fn run(matches: &clap::ArgMatches) -> Result<()> {
+    let (name, matches) = matches.subcommand().ok_or("No subcommand given")?;
+
+    let mut trace = Vec::new();
+    capture::Reader::open(matches.get_one::<PathBuf>("trace").ok_or("No trace file")?)?
+        .read_to_end(&mut trace)?;
+    let elf_data = matches
+        .try_get_many::<PathBuf>("elf")
+        .ok()
+        .flatten()
+        .into_iter()
+        .flatten()
+        .map(fs::read)
+        .collect::<io::Result<Vec<_>>>()?;
+    let elfs = elf_data
+        .iter()
+        .map(|d| ElfBytes::minimal_parse(d))
+        .collect::<core::result::Result<Vec<_>, _>>()?;
+    let binary = elfs
+        .iter()
+        .map(Elf::new)
+        .collect::<core::result::Result<Vec<_>, _>>()?;
+    let mut session = build_session(matches, binary.into())?;
+
+    let mut output: Box<dyn io::Write> = match matches.get_one::<PathBuf>("output") {
+        Some(path) => Box::new(io::BufWriter::new(fs::File::create(path)?)),
+        None => Box::new(io::stdout().lock()),
+    };
+
+    match name {
+        "decode" => {
+            jsonl::export_packets(&mut session, &trace, output)?;
+        }
+        "trace" => {
+            text::export(&mut session, &trace, output)?;
+        }
+        "export" if matches.get_flag("perfetto") => {
+            perfetto::export(&mut session, &trace, output)?;
+        }
+        "export" => {
+            jsonl::export(&mut session, &trace, output)?;
+        }
+        "diff" => {
+            let reference = read_reference(
+                matches
+                    .get_one::<PathBuf>("reference")
+                    .ok_or("No reference")?,
+            )?;
+            let target = matches.get_one::<u64>("hart").copied().unwrap_or_default();
+            let mut comparator = Comparator::new(reference);
+            let mut res = Ok(());
+            session.for_each_item(&trace, |hart, item| {
+                if hart == target && res.is_ok() {
+                    res = comparator.compare(&item);
+                }
+            })?;
+            res?;
+            let count = comparator.finish()?;
+            writeln!(output, "{count} instructions match the reference")?;
+        }
+        _ => return Err(format!("Unknown subcommand {name}").into()),
+    }
+    session
+        .take_isa_mismatches()
+        .iter()
+        .for_each(|m| eprintln!("Warning: {m}"));
+    Ok(())
+}
+
+/// Build the [`Session`] according to the common arguments
+fn build_session<'d>(matches: &clap::ArgMatches, binary: Binary<'d>) -> Result<Session<'d>> {
+    let params: Parameters = matches
+        .get_one::<PathBuf>("params")
+        .map(|p| fs::read_to_string(p).map(|s| toml::from_str(&s)))
+        .transpose()?
+        .transpose()?
+        .unwrap_or_default();
+    let unit = matches
+        .get_one::<String>("unit")
+        .map(|u| unit::PlugsEntry::by_name(u).ok_or_else(|| format!("Unknown unit {u}")))
+        .transpose()?
+        .unwrap_or_default()
+        .plug();
+    let framing = matches
+        .get_one::<String>("framing")
+        .map(|f| Framing::from_name(f).ok_or_else(|| format!("Unknown framing {f}")))
+        .transpose()?
+        .unwrap_or_default();
+    let width = |name| matches.get_one::<u8>(name).copied().unwrap_or_default();
+    let decoder = packet::builder()
+        .with_hart_index_width(width("hart-index-width"))
+        .with_timestamp_width(width("timestamp-width"))
+        .with_address_shift(matches.get_one::<u8>("address-shift").copied())
+        .for_unit(unit);
+
+    Ok(session::builder()
+        .with_decoder(decoder)
+        .with_binary(binary)
+        .with_params(&params)
+        .with_framing(framing)
+        .build())
+}
+
+/// Read a reference file containing one hexadecimal PC per line
+fn read_reference(path: &Path) -> Result<Vec<u64>> {
+    io::BufReader::new(fs::File::open(path)?)
+        .lines()
+        .filter(|l| l.as_ref().map(|l| !l.trim().is_empty()).unwrap_or(true))
+        .map(|l| {
+            let l = l?;
+            let pc = l.trim().trim_start_matches("0x");
+            Ok(u64::from_str_radix(pc, 16)?)
+        })
+        .collect()
+}
+
+/// Construct the [`clap::Command`]
+fn command() -> clap::Command {
+    use clap::{Arg, ArgAction, Command, arg, value_parser};
+
+    let common = [
+        arg!(<trace> "Path to the trace file").value_parser(value_parser!(PathBuf)),
+        arg!(-p --params <FILE> "Trace encoder parameters in TOML format")
+            .value_parser(value_parser!(PathBuf)),
+        Arg::new("unit")
+            .short('u')
+            .long("unit")
+            .help("Trace encoder implementation that produced the trace")
+            .value_parser(unit::PLUGS.iter().map(|p| p.name()).collect::<Vec<_>>()),
+        Arg::new("framing")
+            .short('f')
+            .long("framing")
+            .help("Framing of packets within the trace file")
+            .value_parser(Framing::ALL.map(Framing::name)),
+        arg!(--"hart-index-width" <WIDTH> "Width of the hart index field")
+            .value_parser(value_parser!(u8)),
+        arg!(--"timestamp-width" <BYTES> "Width of the timestamp field in bytes")
+            .value_parser(value_parser!(u8)),
+        arg!(--"address-shift" <BITS> "Shift applied to addresses, overriding iaddress_lsb_p")
+            .value_parser(value_parser!(u8)),
+        arg!(-o --output <FILE> "Write output to the given file instead of stdout")
+            .value_parser(value_parser!(PathBuf)),
+    ];
+    let elf = arg!(<elf>... "ELF files containing the code being traced")
+        .value_parser(value_parser!(PathBuf));
+
+    Command::new("riscv-etrace")
+        .about("Decode and trace RISC-V E-Traces")
+        .subcommand_required(true)
+        .subcommand(
+            Command::new("decode")
+                .about("Decode packets and print them in JSON Lines format")
+                .args(common.clone()),
+        )
+        .subcommand(
+            Command::new("trace")
+                .about("Trace all harts and print the trace items")
+                .args(common.clone())
+                .arg(elf.clone()),
+        )
+        .subcommand(
+            Command::new("export")
+                .about("Trace all harts and export the packets and trace items")
+                .args(common.clone())
+                .arg(elf.clone())
+                .arg(
+                    arg!(--perfetto "Export in Perfetto instead of JSON Lines format")
+                        .action(ArgAction::SetTrue),
+                ),
+        )
+        .subcommand(
+            Command::new("diff")
+                .about("Trace a single hart and compare it against a reference")
+                .args(common)
+                .arg(elf)
+                .arg(
+                    arg!(-r --reference <FILE> "File containing one hexadecimal PC per line")
+                        .required(true)
+                        .value_parser(value_parser!(PathBuf)),
+                )
+                .arg(
+                    arg!(--hart <NUM> "Hart to compare")
+                        .value_parser(value_parser!(u64))
+                        .default_value("0"),
+                ),
+        )
+}
@@ -0,0 +1,116 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+use riscv_etrace::packet::{encap, payload, sync};
+
+/// Temporary directory holding a trace file and an ELF file
+struct Fixture {
+    dir: PathBuf,
+}
+
+impl Fixture {
+    /// Create a trace with start packets for two harts in a fresh directory
+    fn new(name: &str) -> Self {
+        let dir =
+            std::env::temp_dir().join(format!("riscv-etrace-cli-{name}-{}", std::process::id()));
+        fs::create_dir_all(&dir).expect("Could not create directory");
+
+        let mut data = vec![0; 64];
+        let mut encoder = packet::builder()
+            .with_hart_index_width(8)
+            .with_timestamp_width(1)
+            .encoder(data.as_mut());
+        [(1, 20, 0xa0000000), (0, 10, 0xa0000010)]
+            .into_iter()
+            .for_each(|(hart, timestamp, address)| {
+                let payload: payload::InstructionTrace = sync::Start::new(address).into();
+                let packet = encap::Normal::new(0, hart, payload::Payload::from(payload))
+                    .with_timestamp(timestamp);
+                encoder
+                    .encode(&encap::Packet::from(packet))
+                    .expect("Could not encode packet");
+            });
+        let uncommitted = encoder.uncommitted();
+        let len = data.len() - uncommitted;
+        fs::write(dir.join("trace.bin"), &data[..len]).expect("Could not write trace");
+        let elf = include_bytes!("../../src/binary/testfile.elf");
+        fs::write(dir.join("test.elf"), elf).expect("Could not write ELF file");
+        Self { dir }
+    }
+
+    /// Run the given subcommand with the given extra arguments
+    ///
+    /// Returns the output written by the subcommand.
+    fn run(&self, subcommand: &str, args: &[&str]) -> Vec<u8> {
+        let output = self.dir.join(format!("{subcommand}.out"));
+        let matches = command()
+            .try_get_matches_from(
+                ["riscv-etrace", subcommand, "--hart-index-width", "8"]
+                    .into_iter()
+                    .map(Into::into)
+                    .chain([
+                        "--timestamp-width".into(),
+                        "1".into(),
+                        "-o".into(),
+                        output.clone().into_os_string(),
+                        self.dir.join("trace.bin").into_os_string(),
+                    ])
+                    .chain(args.iter().map(Into::into)),
+            )
+            .expect("Could not parse arguments");
+        run(&matches).expect("Could not run subcommand");
+        fs::read(output).expect("Could not read output")
+    }
+
+    /// Retrieve the path to the ELF file as argument
+    fn elf(&self) -> String {
+        self.dir.join("test.elf").to_string_lossy().into_owned()
+    }
+}
+
+impl Drop for Fixture {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+#[test]
+fn decode() {
+    let fixture = Fixture::new("decode");
+    let output = String::from_utf8(fixture.run("decode", &[])).unwrap();
+    let lines: Vec<_> = output.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with(r#"{"type":"packet","id":0,"hart":1,"timestamp":20,"#));
+}
+
+#[test]
+fn export_jsonl() {
+    let fixture = Fixture::new("jsonl");
+    let output = String::from_utf8(fixture.run("export", &[&fixture.elf()])).unwrap();
+    let lines: Vec<_> = output.lines().collect();
+    assert!(lines[0].starts_with(r#"{"type":"packet","id":0,"hart":0,"timestamp":10,"#));
+    assert!(lines[1].starts_with(r#"{"type":"item","hart":0,"packet":0,"#));
+    assert!(
+        lines
+            .iter()
+            .any(|l| l.starts_with(r#"{"type":"packet","id":1,"hart":1,"#))
+    );
+    assert!(!output.contains(r#""packet":null"#));
+}
+
+#[test]
+fn export_perfetto() {
+    let fixture = Fixture::new("perfetto");
+    let output = fixture.run("export", &["--perfetto", &fixture.elf()]);
+    assert!(!output.is_empty());
+}
+
+#[test]
+fn trace() {
+    let fixture = Fixture::new("trace");
+    let output = String::from_utf8(fixture.run("trace", &[&fixture.elf()])).unwrap();
+    assert!(output.lines().next().unwrap().starts_with("[0]"));
+    assert!(output.contains("wfi"));
+}
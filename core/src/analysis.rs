@@ -0,0 +1,50 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Analysis of tracing items
+//!
+//! This module provides utilities for analysing streams of tracing [`Item`]s
+//! generated by a [`Tracer`], such as extracting the [invocation]s of specific
+//! functions or building a control flow graph. It also provides utilities for
+//! auditing the trace itself, such as the detection of [gap]s or misconfigured
+//! [address] parameters, for assessing its compression [efficiency] and the
+//! overhead per sync [interval], for retaining the recent [history] of harts
+//! and for comparing it against a [reference][mod@reference], which may also
+//! be streamed live and [correlate]d with online. Execution [profile]s show
+//! where the traced program spends its time. Ahead of tracing,
+//! the code reachable in a [`Binary`] may be [validate]d. A [replay] of
+//! register values allows resolving jump targets locally. A [watchdog] flags
+//! harts which appear to hang. Items may be attributed to operating system
+//! [process]es based on the execution context. The coverage of trap
+//! [handler]s shows which traps were exercised. A [protection] check flags
+//! execution outside of the regions expected to hold code.
+//!
+//! [`Item`]: crate::tracer::item::Item
+//! [`Tracer`]: crate::tracer::Tracer
+//! [`Binary`]: crate::binary::Binary
+
+pub mod address;
+#[cfg(feature = "alloc")]
+pub mod cfg;
+#[cfg(feature = "alloc")]
+pub mod correlate;
+pub mod efficiency;
+pub mod gap;
+#[cfg(feature = "alloc")]
+pub mod handler;
+pub mod history;
+pub mod interval;
+pub mod invocation;
+#[cfg(feature = "alloc")]
+pub mod process;
+pub mod profile;
+#[cfg(feature = "alloc")]
+pub mod protection;
+pub mod reference;
+#[cfg(feature = "riscv-isa")]
+pub mod replay;
+#[cfg(feature = "alloc")]
+pub mod validate;
+pub mod watchdog;
+
+#[cfg(test)]
+mod tests;
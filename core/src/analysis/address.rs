@@ -0,0 +1,427 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Diagnosis of misconfigured address parameters
+//!
+//! If a trace is decoded with parameters differing from those of the encoder,
+//! the addresses reported will usually point outside of the code being traced.
+//! This is typically only noticed when a [`Tracer`] fails to fetch some
+//! instruction, deep into the trace and with a rather generic error.
+//!
+//! This module provides [`Diagnostics`] which check reported addresses
+//! against a [`Binary`] under the configured parameters as well as under a set
+//! of alternative interpretations. If addresses systematically miss the
+//! [`Binary`] while one of the alternatives matches, a [`Diagnosis`] naming
+//! the likely misconfigured parameter is produced. Independently of the
+//! [`Binary`], addresses which consistently land on misaligned locations hint
+//! at a wrong address shift.
+//!
+//! [`Tracer`]: crate::tracer::Tracer
+
+use core::fmt;
+
+use crate::binary::Binary;
+use crate::config::{self, AddressMode};
+use crate::instruction::info::Info;
+use crate::packet::payload::InstructionTrace;
+use crate::packet::sync::Synchronization;
+use crate::packet::unit::IOptions;
+
+/// Address widths considered as alternatives to the configured one
+const WIDTHS: [u8; 4] = [32, 39, 48, 56];
+
+/// Maximum `iaddress_lsb_p` considered
+const MAX_LSB: u8 = 3;
+
+/// Number of alternative [`Hypothesis`]es
+const ALTERNATIVES: usize = MAX_LSB as usize + 1 + WIDTHS.len() + 1;
+
+/// Diagnostics for addresses reported by a single hart
+///
+/// Diagnostics process all [`InstructionTrace`] payloads of a single hart and
+/// check the addresses reported in those payloads against a [`Binary`]. In
+/// addition to the configured parameters, the following alternatives are
+/// considered:
+///
+/// * a different `iaddress_lsb_p`,
+/// * the respective other [`AddressMode`] and
+/// * a smaller `iaddress_width_p`, with the upper bits being discarded.
+///
+/// For [delta addresses][AddressMode::Delta], every alternative tracks its own
+/// reference address.
+///
+/// If the address shift of the decoder was
+/// [overridden][crate::packet::Builder::with_address_shift], the parameters
+/// passed need to reflect that shift as `iaddress_lsb_p`.
+///
+/// # Example
+///
+/// ```
+/// use riscv_etrace_core::analysis::address::{Diagnosis, Diagnostics};
+/// use riscv_etrace_core::binary;
+/// use riscv_etrace_core::config::{AddressMode, Parameters};
+/// use riscv_etrace_core::instruction::COMPRESSED;
+/// use riscv_etrace_core::packet::{payload, sync};
+///
+/// let mut code = binary::from_sorted_map([(0x80000000, COMPRESSED), (0x80000100, COMPRESSED)]);
+/// let params = Parameters {
+///     iaddress_lsb_p: 0,
+///     ..Default::default()
+/// };
+/// let mut diagnostics = Diagnostics::new(&params, AddressMode::Delta).with_threshold(4);
+///
+/// // The encoder actually shifts addresses by one bit
+/// let start: payload::InstructionTrace = sync::Start::new(0x40000000).into();
+/// let forth: payload::InstructionTrace = payload::AddressInfo::new(0x80).into();
+/// let back: payload::InstructionTrace = payload::AddressInfo::new(-0x80).into();
+/// for payload in [&start, &forth, &back, &forth] {
+///     diagnostics.process_payload(&mut code, payload);
+/// }
+/// assert_eq!(
+///     diagnostics.diagnosis(),
+///     Some(Diagnosis::IaddressLsb { configured: 0, suggested: 1 }),
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct Diagnostics {
+    lsb: u8,
+    width: u8,
+    mode: AddressMode,
+    threshold: u64,
+    alignment: u64,
+    observations: u64,
+    configured: Candidate,
+    alternatives: [Option<(Hypothesis, Candidate)>; ALTERNATIVES],
+}
+
+impl Diagnostics {
+    /// Create new diagnostics for the given parameters and [`AddressMode`]
+    ///
+    /// The [`AddressMode`] is updated from [`sync::Support`] payloads
+    /// processed.
+    ///
+    /// [`sync::Support`]: crate::packet::sync::Support
+    pub fn new(params: &config::Parameters, mode: AddressMode) -> Self {
+        let lsb = params.iaddress_lsb_p;
+        let width = params.iaddress_width_p.get();
+        let mut alternatives = [None; ALTERNATIVES];
+        let lsbs = (0..=MAX_LSB).filter(|l| *l != lsb).map(Hypothesis::Lsb);
+        let widths = WIDTHS
+            .into_iter()
+            .filter(|w| *w < width)
+            .map(Hypothesis::Width);
+        lsbs.chain(widths)
+            .chain([Hypothesis::Mode])
+            .zip(alternatives.iter_mut())
+            .for_each(|(h, a)| *a = Some((h, Default::default())));
+
+        Self {
+            lsb,
+            width,
+            mode,
+            threshold: 16,
+            alignment: 2,
+            observations: 0,
+            configured: Default::default(),
+            alternatives,
+        }
+    }
+
+    /// Set the number of addresses required for a [`Diagnosis`]
+    ///
+    /// By default, no [`Diagnosis`] is produced before 16 addresses were
+    /// observed.
+    pub fn with_threshold(self, threshold: u64) -> Self {
+        Self { threshold, ..self }
+    }
+
+    /// Set the alignment of instruction addresses in bytes
+    ///
+    /// By default, addresses are expected to be aligned to 2 bytes. Harts not
+    /// supporting compressed instructions require an alignment of 4 bytes.
+    pub fn with_alignment(self, alignment: u64) -> Self {
+        Self { alignment, ..self }
+    }
+
+    /// Process an [`InstructionTrace`] payload
+    ///
+    /// Addresses reported in the payload are checked against the given
+    /// [`Binary`].
+    pub fn process_payload<B, I, O, D>(&mut self, binary: &mut B, payload: &InstructionTrace<O, D>)
+    where
+        B: Binary<I>,
+        I: Info,
+        O: IOptions,
+    {
+        let reported = match payload {
+            InstructionTrace::Synchronization(Synchronization::Start(start)) => {
+                Reported::Sync(start.address)
+            }
+            InstructionTrace::Synchronization(Synchronization::Trap(trap)) => {
+                Reported::Sync(trap.address)
+            }
+            InstructionTrace::Synchronization(Synchronization::Support(support)) => {
+                if let Some(mode) = support.ioptions.address_mode() {
+                    self.mode = mode;
+                }
+                return;
+            }
+            _ => match payload.get_address_info() {
+                Some(info) => Reported::Address(info.address),
+                None => return,
+            },
+        };
+
+        self.observations += 1;
+        let (lsb, width, mode) = (self.lsb, self.width, self.mode);
+        let alignment = self.alignment;
+        self.configured
+            .observe(binary, alignment, reported.resolve(lsb, lsb, width, mode));
+        self.alternatives.iter_mut().flatten().for_each(|(h, c)| {
+            let address = match *h {
+                Hypothesis::Lsb(l) => reported.resolve(lsb, l, width, mode),
+                Hypothesis::Width(w) => reported.resolve(lsb, lsb, w, mode),
+                Hypothesis::Mode => reported.resolve(lsb, lsb, width, other_mode(mode)),
+            };
+            c.observe(binary, alignment, address)
+        });
+    }
+
+    /// Retrieve the number of addresses observed
+    pub fn observations(&self) -> u64 {
+        self.observations
+    }
+
+    /// Retrieve the number of addresses missing the [`Binary`]
+    ///
+    /// Only addresses interpreted according to the configured parameters are
+    /// considered.
+    pub fn misses(&self) -> u64 {
+        self.observations - self.configured.hits
+    }
+
+    /// Retrieve the number of misaligned addresses
+    ///
+    /// Only addresses interpreted according to the configured parameters are
+    /// considered.
+    pub fn misaligned(&self) -> u64 {
+        self.configured.misaligned
+    }
+
+    /// Retrieve a [`Diagnosis`] if the addresses observed suggest one
+    ///
+    /// A [`Diagnosis`] is only produced after reaching the threshold of
+    /// observations and if the majority of addresses miss the [`Binary`]. If
+    /// the majority of addresses under some alternative interpretation do not,
+    /// that alternative is diagnosed. Otherwise, if the majority of addresses
+    /// is misaligned, [`Diagnosis::Misaligned`] is produced.
+    pub fn diagnosis(&self) -> Option<Diagnosis> {
+        let majority = self.observations / 2;
+        if self.observations < self.threshold || self.configured.hits > majority {
+            return None;
+        }
+
+        self.alternatives
+            .iter()
+            .flatten()
+            .filter(|(_, c)| c.hits > majority)
+            .max_by_key(|(_, c)| c.hits)
+            .map(|(h, _)| match *h {
+                Hypothesis::Lsb(suggested) => Diagnosis::IaddressLsb {
+                    configured: self.lsb,
+                    suggested,
+                },
+                Hypothesis::Width(suggested) => Diagnosis::IaddressWidth {
+                    configured: self.width,
+                    suggested,
+                },
+                Hypothesis::Mode => Diagnosis::AddressMode {
+                    configured: self.mode,
+                    suggested: other_mode(self.mode),
+                },
+            })
+            .or_else(|| self.misalignment(majority))
+    }
+
+    /// Diagnose a misaligned address shift if the majority of addresses is
+    /// misaligned
+    ///
+    /// The smallest larger shift under which no address is misaligned is
+    /// suggested.
+    fn misalignment(&self, majority: u64) -> Option<Diagnosis> {
+        if self.configured.misaligned <= majority {
+            return None;
+        }
+        let suggested = self
+            .alternatives
+            .iter()
+            .flatten()
+            .filter_map(|(h, c)| match *h {
+                Hypothesis::Lsb(l) if l > self.lsb && c.misaligned == 0 => Some(l),
+                _ => None,
+            })
+            .min();
+        Some(Diagnosis::Misaligned {
+            configured: self.lsb,
+            suggested,
+        })
+    }
+}
+
+/// Likely misconfiguration of address parameters
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Diagnosis {
+    /// The `iaddress_lsb_p` parameter is likely misconfigured
+    IaddressLsb { configured: u8, suggested: u8 },
+    /// The `iaddress_width_p` parameter is likely too large
+    IaddressWidth { configured: u8, suggested: u8 },
+    /// The [`AddressMode`] is likely misconfigured
+    AddressMode {
+        configured: AddressMode,
+        suggested: AddressMode,
+    },
+    /// Addresses consistently land on misaligned locations
+    ///
+    /// The address shift, i.e. `iaddress_lsb_p`, is likely too small. A shift
+    /// under which addresses are aligned is suggested, if any.
+    Misaligned {
+        configured: u8,
+        suggested: Option<u8>,
+    },
+}
+
+impl fmt::Display for Diagnosis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::IaddressLsb {
+                configured,
+                suggested,
+            } => write!(
+                f,
+                "addresses match the binary with iaddress_lsb_p = {suggested} \
+                (configured: {configured})"
+            ),
+            Self::IaddressWidth {
+                configured,
+                suggested,
+            } => write!(
+                f,
+                "addresses match the binary with iaddress_width_p = {suggested} \
+                (configured: {configured})"
+            ),
+            Self::AddressMode {
+                configured,
+                suggested,
+            } => write!(
+                f,
+                "addresses match the binary in {suggested:?} address mode \
+                (configured: {configured:?})"
+            ),
+            Self::Misaligned {
+                configured,
+                suggested: Some(suggested),
+            } => write!(
+                f,
+                "addresses are misaligned, but aligned with an address shift of \
+                {suggested} (configured: {configured})"
+            ),
+            Self::Misaligned {
+                configured,
+                suggested: None,
+            } => write!(
+                f,
+                "addresses are misaligned with an address shift of {configured}"
+            ),
+        }
+    }
+}
+
+/// Alternative interpretation of reported addresses
+#[derive(Copy, Clone, Debug)]
+enum Hypothesis {
+    /// Addresses are shifted by the given number of bits
+    Lsb(u8),
+    /// Addresses are of the given width
+    Width(u8),
+    /// Addresses are reported in the other [`AddressMode`]
+    Mode,
+}
+
+/// State of an interpretation of reported addresses
+#[derive(Copy, Clone, Debug, Default)]
+struct Candidate {
+    reference: u64,
+    hits: u64,
+    misaligned: u64,
+}
+
+impl Candidate {
+    /// Observe an address resolved under this candidate's interpretation
+    fn observe<B: Binary<I>, I: Info>(
+        &mut self,
+        binary: &mut B,
+        alignment: u64,
+        address: Resolved,
+    ) {
+        let address = match address {
+            Resolved::Absolute(address) => address,
+            Resolved::Relative(offset, mask) => self.reference.wrapping_add_signed(offset) & mask,
+        };
+        self.reference = address;
+        if address % alignment.max(1) != 0 {
+            self.misaligned += 1;
+        }
+        if binary.get_insn(address).is_ok() {
+            self.hits += 1;
+        }
+    }
+}
+
+/// Address as reported in a payload
+#[derive(Copy, Clone, Debug)]
+enum Reported {
+    /// Full address reported in a [`Synchronization`]
+    Sync(u64),
+    /// Address reported in an [`AddressInfo`][info]
+    ///
+    /// [info]: crate::packet::payload::AddressInfo
+    Address(i64),
+}
+
+impl Reported {
+    /// Resolve this address under the given interpretation
+    ///
+    /// The `decoded_lsb` is the `iaddress_lsb_p` the address was decoded with.
+    fn resolve(self, decoded_lsb: u8, lsb: u8, width: u8, mode: AddressMode) -> Resolved {
+        let mask = u64::MAX
+            .checked_shl(width.into())
+            .map(|m| !m)
+            .unwrap_or(u64::MAX);
+        match self {
+            Self::Sync(address) => Resolved::Absolute(((address >> decoded_lsb) << lsb) & mask),
+            Self::Address(address) => {
+                let address = (address >> decoded_lsb) << lsb;
+                match mode {
+                    AddressMode::Full => Resolved::Absolute((address as u64) & mask),
+                    AddressMode::Delta => Resolved::Relative(address, mask),
+                }
+            }
+        }
+    }
+}
+
+/// Address resolved under some interpretation
+#[derive(Copy, Clone, Debug)]
+enum Resolved {
+    /// An absolute address
+    Absolute(u64),
+    /// An offset to the previous address and a mask to apply after addition
+    Relative(i64, u64),
+}
+
+/// Retrieve the respective other [`AddressMode`]
+fn other_mode(mode: AddressMode) -> AddressMode {
+    match mode {
+        AddressMode::Full => AddressMode::Delta,
+        AddressMode::Delta => AddressMode::Full,
+    }
+}
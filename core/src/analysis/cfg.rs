@@ -0,0 +1,274 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Trace-derived control flow graphs
+//!
+//! This module provides [`Graph`], a control flow graph which is built
+//! incrementally from tracing [`Item`]s. Its nodes are basic [`Block`]s
+//! discovered via a [`Binary`] and its edges are weighted by the number of
+//! times they were traversed. A [`Graph`] may be exported in the DOT format
+//! via [`Graph::dot`].
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+
+use crate::binary::Binary;
+use crate::instruction::Instruction;
+use crate::instruction::info::Info;
+use crate::tracer::item::Item;
+
+/// Control flow graph built from tracing [`Item`]s
+///
+/// [`Item`]s are fed to the graph via [`process_item`][Self::process_item] in
+/// the order they were generated by the [`Tracer`][crate::tracer::Tracer].
+/// Whenever execution reaches an address not yet known as the start of a
+/// [`Block`], the graph discovers the [`Block`]'s extent by scanning the
+/// [`Binary`] for the next control transfer instruction. Existing [`Block`]s
+/// are split if execution enters them at some address other than their start.
+///
+/// Edges are recorded between consecutively executed [`Block`]s, including
+/// transitions caused by traps. [`Item`]s not signalling a retired
+/// [`Instruction`] are otherwise ignored.
+///
+/// # Example
+///
+/// ```
+/// use riscv_etrace_core::analysis::cfg::Graph;
+/// use riscv_etrace_core::binary::{self, Binary};
+/// use riscv_etrace_core::instruction::{Kind, UNCOMPRESSED};
+/// use riscv_etrace_core::tracer::item::Item;
+///
+/// let mut binary = binary::from_sorted_map([
+///     (0x10, UNCOMPRESSED),
+///     (0x14, Kind::new_bne(10, 11, -4).into()),
+///     (0x18, Kind::mret.into()),
+/// ])
+/// .unwrap();
+///
+/// let mut graph = Graph::default();
+/// [0x10, 0x14, 0x10, 0x14, 0x18].into_iter().for_each(|pc| {
+///     let item = Item::new(pc, binary.get_insn(pc).unwrap().into());
+///     graph.process_item(&mut binary, &item);
+/// });
+///
+/// assert_eq!(graph.blocks().count(), 2);
+/// assert_eq!(graph.edge(0x10, 0x10), Some(1));
+/// assert_eq!(graph.edge(0x10, 0x18), Some(1));
+/// println!("{}", graph.dot());
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Graph {
+    blocks: BTreeMap<u64, Block>,
+    edges: BTreeMap<(u64, u64), u64>,
+    current: Option<Position>,
+}
+
+impl Graph {
+    /// Process a single [`Item`]
+    ///
+    /// The [`Binary`] is used for discovering new [`Block`]s and should be the
+    /// [`Binary`] used for tracing.
+    pub fn process_item<B: Binary<I>, I: Info>(&mut self, binary: &mut B, item: &Item<I>) {
+        let Some(insn) = item.instruction() else {
+            return;
+        };
+        let pc = item.pc();
+
+        let block = self
+            .current
+            .as_ref()
+            .filter(|p| p.next == Some(pc) && pc < p.end && !self.blocks.contains_key(&pc))
+            .map(|p| p.block);
+        let (block, end) = if let Some(block) = block {
+            (block, self.current.as_ref().map(|p| p.end).unwrap_or(pc))
+        } else {
+            let end = self.enter_block(binary, pc, insn);
+            if let Some(from) = self.current.as_ref().map(|p| p.block) {
+                *self.edges.entry((from, pc)).or_default() += 1;
+            }
+            (pc, end)
+        };
+
+        let next = if is_control_transfer(insn) {
+            None
+        } else {
+            pc.checked_add(insn.size.into())
+        };
+        self.current = Some(Position {
+            block,
+            end,
+            pc,
+            next,
+        });
+    }
+
+    /// Retrieve all [`Block`]s, ordered by their start address
+    pub fn blocks(&self) -> impl Iterator<Item = (u64, &Block)> + '_ {
+        self.blocks.iter().map(|(s, b)| (*s, b))
+    }
+
+    /// Retrieve the [`Block`] starting at the given address
+    pub fn block(&self, start: u64) -> Option<&Block> {
+        self.blocks.get(&start)
+    }
+
+    /// Retrieve all edges along with their traversal counts
+    ///
+    /// Edges are represented by the start addresses of the [`Block`]s they
+    /// connect. They are ordered by the source, then the destination.
+    pub fn edges(&self) -> impl Iterator<Item = ((u64, u64), u64)> + '_ {
+        self.edges.iter().map(|(e, c)| (*e, *c))
+    }
+
+    /// Retrieve the traversal count of the edge between the given [`Block`]s
+    pub fn edge(&self, from: u64, to: u64) -> Option<u64> {
+        self.edges.get(&(from, to)).copied()
+    }
+
+    /// Create a [`Dot`] for exporting this graph in the DOT format
+    pub fn dot(&self) -> Dot<'_> {
+        Dot { graph: self }
+    }
+
+    /// Enter the block starting at `start`, discovering or splitting if needed
+    ///
+    /// Returns the end of the [`Block`].
+    fn enter_block<B: Binary<I>, I: Info>(
+        &mut self,
+        binary: &mut B,
+        start: u64,
+        insn: &Instruction<I>,
+    ) -> u64 {
+        if let Some(block) = self.blocks.get_mut(&start) {
+            block.count += 1;
+            return block.end;
+        }
+
+        let containing = self
+            .blocks
+            .range_mut(..start)
+            .next_back()
+            .filter(|(_, b)| b.end > start);
+        if let Some((outer, block)) = containing {
+            let outer = *outer;
+            let end = block.end;
+            let count = block.count;
+            block.end = start;
+
+            let moved: Vec<_> = self
+                .edges
+                .range((outer, 0)..=(outer, u64::MAX))
+                .map(|(e, c)| (e.1, *c))
+                .collect();
+            moved.into_iter().for_each(|(to, c)| {
+                self.edges.remove(&(outer, to));
+                self.edges.insert((start, to), c);
+            });
+            if count > 0 {
+                self.edges.insert((outer, start), count);
+            }
+
+            self.blocks.insert(
+                start,
+                Block {
+                    end,
+                    count: count + 1,
+                },
+            );
+            if let Some(current) = self.current.as_mut().filter(|p| p.block == outer) {
+                if current.pc >= start {
+                    current.block = start;
+                } else {
+                    current.end = start;
+                }
+            }
+            return end;
+        }
+
+        let limit = self
+            .blocks
+            .range(start..)
+            .next()
+            .map(|(s, _)| *s)
+            .unwrap_or(u64::MAX);
+        let mut end = start.saturating_add(insn.size.into());
+        let mut last = is_control_transfer(insn);
+        while !last && end < limit {
+            let Ok(insn) = binary.get_insn(end) else {
+                break;
+            };
+            last = is_control_transfer(&insn);
+            end = end.saturating_add(insn.size.into());
+        }
+        let end = end.min(limit);
+        self.blocks.insert(start, Block { end, count: 1 });
+        end
+    }
+}
+
+/// A basic block within a [`Graph`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Block {
+    end: u64,
+    count: u64,
+}
+
+impl Block {
+    /// Retrieve the address following the last [`Instruction`] of this block
+    pub fn end(&self) -> u64 {
+        self.end
+    }
+
+    /// Retrieve the number of times this block was entered
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Retrieve the address range covered by this block
+    pub fn range(&self, start: u64) -> Range<u64> {
+        start..self.end
+    }
+}
+
+/// Helper for exporting a [`Graph`] in the DOT format
+///
+/// The [`fmt::Display`] impl of this type emits a directed graph in the DOT
+/// format. Each [`Block`] is labelled with its address range and execution
+/// count. Edges are labelled with their traversal count.
+#[derive(Copy, Clone, Debug)]
+pub struct Dot<'g> {
+    graph: &'g Graph,
+}
+
+impl fmt::Display for Dot<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "digraph cfg {{")?;
+        writeln!(f, "    node [shape=box];")?;
+        self.graph.blocks().try_for_each(|(start, block)| {
+            writeln!(
+                f,
+                "    b{start:x} [label=\"{start:#x}..{:#x}\\n{}\"];",
+                block.end, block.count,
+            )
+        })?;
+        self.graph.edges().try_for_each(|((from, to), count)| {
+            writeln!(f, "    b{from:x} -> b{to:x} [label=\"{count}\"];")
+        })?;
+        writeln!(f, "}}")
+    }
+}
+
+/// Position within the [`Graph`] of the last [`Item`] processed
+#[derive(Copy, Clone, Debug)]
+struct Position {
+    block: u64,
+    end: u64,
+    pc: u64,
+    next: Option<u64>,
+}
+
+/// Determine whether the given [`Instruction`] ends a basic block
+fn is_control_transfer<I: Info>(insn: &Instruction<I>) -> bool {
+    insn.is_branch() || insn.is_inferable_jump() || insn.is_uninferable_discon()
+}
@@ -0,0 +1,254 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Online correlation with a live reference
+//!
+//! The [`Comparator`][super::reference::Comparator] checks [`Item`]s against a
+//! reference which is available in its entirety. When verifying a system
+//! online, however, the reference is usually a stream of its own, e.g. the
+//! commit log of a simulator or of the core itself delivered via a socket,
+//! which arrives independently of the trace data. This module provides a
+//! [`Correlator`] consuming both streams as they arrive. PCs not yet matched
+//! are held in a bounded window until the other stream catches up.
+
+use alloc::collections::VecDeque;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::instruction::info::Info;
+use crate::tracer::item::Item;
+
+use super::reference::Mismatch;
+
+/// Correlator of [`Item`]s of a single hart with a live reference
+///
+/// [`Item`]s and reference PCs are fed via [`push_item`][Self::push_item] and
+/// [`push_reference`][Self::push_reference] in any interleaving. Like for the
+/// [`Comparator`][super::reference::Comparator], only [`Item`]s carrying an
+/// [`Instruction`] are considered. Each stream may run ahead of the other by
+/// up to `window` PCs. Exceeding the window is reported as an
+/// [`Error::Overflow`].
+///
+/// The first [`Divergence`] is reported along with the last PCs on which both
+/// streams agreed and the PCs pending in either stream. After an error, the
+/// correlator reports the same error for all further input.
+///
+/// # Example
+///
+/// ```
+/// use riscv_etrace_core::analysis::correlate::{Correlator, Error};
+/// use riscv_etrace_core::instruction::COMPRESSED;
+/// use riscv_etrace_core::tracer::item::Item;
+///
+/// let mut correlator = Correlator::new(16).with_context(2);
+/// for pc in [0x1000, 0x1002, 0x1004, 0x1006] {
+///     correlator.push_reference(pc).unwrap();
+/// }
+/// for pc in [0x1000, 0x1002, 0x1004] {
+///     correlator.push_item(&Item::new(pc, COMPRESSED.into())).unwrap();
+/// }
+/// let Err(Error::Diverged(divergence)) = correlator.push_item(&Item::new(0x1010, COMPRESSED.into()))
+/// else {
+///     panic!("Expected divergence")
+/// };
+/// assert_eq!(divergence.mismatch.index, 3);
+/// assert_eq!(divergence.history, [0x1002, 0x1004]);
+/// assert_eq!(divergence.reference, [0x1006]);
+/// ```
+///
+/// [`Instruction`]: crate::instruction::Instruction
+#[derive(Clone, Debug)]
+pub struct Correlator {
+    window: usize,
+    context: usize,
+    traced: VecDeque<u64>,
+    reference: VecDeque<u64>,
+    history: VecDeque<u64>,
+    index: u64,
+    error: Option<Error>,
+}
+
+impl Correlator {
+    /// Create a new correlator with the given window
+    ///
+    /// The resulting correlator retains `8` PCs of context.
+    pub fn new(window: usize) -> Self {
+        Self {
+            window,
+            context: 8,
+            traced: Default::default(),
+            reference: Default::default(),
+            history: Default::default(),
+            index: 0,
+            error: None,
+        }
+    }
+
+    /// Set the number of PCs of context reported with a [`Divergence`]
+    pub fn with_context(self, context: usize) -> Self {
+        Self { context, ..self }
+    }
+
+    /// Push a single traced [`Item`]
+    pub fn push_item<I: Info>(&mut self, item: &Item<I>) -> Result<(), Error> {
+        if item.instruction().is_none() {
+            return self.error.clone().map_or(Ok(()), Err);
+        }
+        self.push(Stream::Traced, item.pc())
+    }
+
+    /// Push a single reference PC
+    pub fn push_reference(&mut self, pc: u64) -> Result<(), Error> {
+        self.push(Stream::Reference, pc)
+    }
+
+    /// Retrieve the number of PCs matched so far
+    pub fn compared(&self) -> u64 {
+        self.index
+    }
+
+    /// Retrieve the number of PCs of the given [`Stream`] not yet matched
+    pub fn pending(&self, stream: Stream) -> usize {
+        match stream {
+            Stream::Traced => self.traced.len(),
+            Stream::Reference => self.reference.len(),
+        }
+    }
+
+    /// Retrieve the first error encountered, if any
+    pub fn error(&self) -> Option<&Error> {
+        self.error.as_ref()
+    }
+
+    /// Finish the correlation after both streams ended
+    ///
+    /// Returns the number of PCs matched or an [`Error`]. PCs still pending
+    /// in either stream are reported as a [`Divergence`].
+    pub fn finish(self) -> Result<u64, Error> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+        let expected = self.reference.front().copied();
+        let actual = self.traced.front().copied();
+        if expected.is_some() || actual.is_some() {
+            return Err(Error::Diverged(self.divergence(expected, actual)));
+        }
+        Ok(self.index)
+    }
+
+    /// Push a PC to the given [`Stream`] and match pending PCs
+    fn push(&mut self, stream: Stream, pc: u64) -> Result<(), Error> {
+        if let Some(error) = self.error.as_ref() {
+            return Err(error.clone());
+        }
+        match stream {
+            Stream::Traced => self.traced.push_back(pc),
+            Stream::Reference => self.reference.push_back(pc),
+        }
+
+        while let (Some(actual), Some(expected)) = (self.traced.front(), self.reference.front()) {
+            let (actual, expected) = (*actual, *expected);
+            if actual != expected {
+                let divergence = self.divergence(Some(expected), Some(actual));
+                return Err(self.fail(Error::Diverged(divergence)));
+            }
+            self.traced.pop_front();
+            self.reference.pop_front();
+            self.index += 1;
+            if self.history.len() >= self.context {
+                self.history.pop_front();
+            }
+            if self.context > 0 {
+                self.history.push_back(actual);
+            }
+        }
+
+        if self.pending(stream) > self.window {
+            return Err(self.fail(Error::Overflow(stream)));
+        }
+        Ok(())
+    }
+
+    /// Record the given error, returning it
+    fn fail(&mut self, error: Error) -> Error {
+        self.error = Some(error.clone());
+        error
+    }
+
+    /// Build a [`Divergence`] at the current position
+    fn divergence(&self, expected: Option<u64>, actual: Option<u64>) -> Divergence {
+        Divergence {
+            mismatch: Mismatch {
+                index: self.index,
+                expected,
+                actual,
+            },
+            history: self.history.iter().copied().collect(),
+            traced: self.traced.iter().copied().take(self.context).collect(),
+            reference: self.reference.iter().copied().take(self.context).collect(),
+        }
+    }
+}
+
+/// One of the streams correlated by a [`Correlator`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Stream {
+    /// The traced [`Item`]s
+    Traced,
+    /// The reference
+    Reference,
+}
+
+impl fmt::Display for Stream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Traced => write!(f, "traced"),
+            Self::Reference => write!(f, "reference"),
+        }
+    }
+}
+
+/// First divergence of the traced [`Item`]s from the reference
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Divergence {
+    /// The [`Mismatch`] at which the streams diverge
+    pub mismatch: Mismatch,
+    /// Last PCs on which both streams agreed, oldest first
+    pub history: Vec<u64>,
+    /// Traced PCs pending, starting with the mismatching one
+    pub traced: Vec<u64>,
+    /// Reference PCs pending, starting with the mismatching one
+    pub reference: Vec<u64>,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.mismatch)?;
+        let mut list = |name, pcs: &[u64]| {
+            write!(f, "; {name}:")?;
+            pcs.iter().try_for_each(|pc| write!(f, " {pc:#x}"))
+        };
+        list("agreed", &self.history)?;
+        list("traced", &self.traced)?;
+        list("reference", &self.reference)
+    }
+}
+
+/// Errors reported by a [`Correlator`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The traced [`Item`]s diverged from the reference
+    Diverged(Divergence),
+    /// The given [`Stream`] ran ahead of the other by more than the window
+    Overflow(Stream),
+}
+
+impl core::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Diverged(divergence) => write!(f, "{divergence}"),
+            Self::Overflow(stream) => write!(f, "{stream} stream exceeded the window"),
+        }
+    }
+}
@@ -0,0 +1,371 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Trace compression efficiency
+//!
+//! This module provides utilities for assessing how efficiently a trace
+//! encodes the control flow of a program. [`Stats`] collect the number of
+//! bits spent on packets of each payload [`Format`] as well as the number of
+//! retired instructions, from which the number of bits per instruction is
+//! derived.
+//!
+//! A [`Simulator`] re-encodes a stream of reconstructed [`Item`]s using the
+//! encoder [`Model`] with alternative [`Options`]. Comparing the resulting
+//! [`Stats`] with those of the original trace shows how those options would
+//! have affected the trace size, which may guide the configuration of trace
+//! encoders.
+//!
+//! # Example
+//!
+//! ```
+//! use riscv_etrace_core::analysis::efficiency::{Options, Simulator};
+//! use riscv_etrace_core::config::{self, AddressMode};
+//! use riscv_etrace_core::instruction;
+//! use riscv_etrace_core::tracer::item::Item;
+//!
+//! let params = config::Parameters::default();
+//! let options = Options {
+//!     address_mode: AddressMode::Full,
+//!     ..Default::default()
+//! };
+//! let mut simulator: Simulator = Simulator::new(&params, options).unwrap();
+//! let insn = instruction::Instruction::from(instruction::Kind::c_nop);
+//! for i in 0..8 {
+//!     simulator.process_item(&Item::new(0x80000000 + 2 * i, insn.into())).unwrap();
+//! }
+//! let stats = simulator.finish().unwrap();
+//! assert_eq!(stats.instructions(), 8);
+//! assert!(stats.bits_per_instruction().is_some());
+//! ```
+//!
+//! [`Item`]: crate::tracer::item::Item
+
+use crate::config::{self, AddressMode};
+use crate::generator::{self, error::Error, hart2enc::CType, model::Model, step};
+use crate::instruction::{self, Instruction, info::Info};
+use crate::packet::{self, payload::InstructionTrace, unit};
+use crate::tracer::item::{self, Item};
+use crate::types::{Context, trap};
+
+/// Format of an [`InstructionTrace`] payload
+///
+/// The format corresponds to the two bit `format` field preceding all
+/// instruction trace payloads.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Format {
+    /// Format 0: [`Extension`][packet::ext::Extension]
+    Extension,
+    /// Format 1: [`Branch`][packet::payload::Branch]
+    Branch,
+    /// Format 2: [`AddressInfo`][packet::payload::AddressInfo]
+    Address,
+    /// Format 3: [`Synchronization`][packet::sync::Synchronization]
+    Synchronization,
+}
+
+impl Format {
+    /// All formats in order of their numerical value
+    pub const ALL: [Self; 4] = [
+        Self::Extension,
+        Self::Branch,
+        Self::Address,
+        Self::Synchronization,
+    ];
+}
+
+impl<I, D> From<&InstructionTrace<I, D>> for Format {
+    fn from(payload: &InstructionTrace<I, D>) -> Self {
+        match payload {
+            InstructionTrace::Extension(_) => Self::Extension,
+            InstructionTrace::Branch(_) => Self::Branch,
+            InstructionTrace::Address(_) => Self::Address,
+            InstructionTrace::Synchronization(_) => Self::Synchronization,
+        }
+    }
+}
+
+/// Number of packets and bits spent on them
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Counter {
+    /// Number of packets
+    pub packets: u64,
+    /// Number of bits of those packets
+    pub bits: u64,
+}
+
+/// Statistics about the size of a trace
+///
+/// Stats record the size of packets, broken down by payload [`Format`], and
+/// the number of instructions retired.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    formats: [Counter; 4],
+    instructions: u64,
+}
+
+impl Stats {
+    /// Record a packet of the given size carrying the given payload
+    ///
+    /// The `bits` should include any packet headers and timestamps.
+    pub fn add_packet<I, D>(&mut self, payload: &InstructionTrace<I, D>, bits: u64) {
+        let counter = &mut self.formats[Format::from(payload) as usize];
+        counter.packets += 1;
+        counter.bits += bits;
+    }
+
+    /// Record a reconstructed [`Item`]
+    ///
+    /// Only [`Item`]s signalling a retired instruction are counted.
+    pub fn add_item<I: Info>(&mut self, item: &Item<I>) {
+        if item.instruction().is_some() {
+            self.instructions += 1;
+        }
+    }
+
+    /// Retrieve the [`Counter`] for the given [`Format`]
+    pub fn format(&self, format: Format) -> Counter {
+        self.formats[format as usize]
+    }
+
+    /// Retrieve the total number of packets recorded
+    pub fn packets(&self) -> u64 {
+        self.formats.iter().map(|c| c.packets).sum()
+    }
+
+    /// Retrieve the total number of bits recorded
+    pub fn bits(&self) -> u64 {
+        self.formats.iter().map(|c| c.bits).sum()
+    }
+
+    /// Retrieve the number of retired instructions recorded
+    pub fn instructions(&self) -> u64 {
+        self.instructions
+    }
+
+    /// Compute the number of bits per retired instruction
+    ///
+    /// Returns `None` if no instructions were recorded.
+    pub fn bits_per_instruction(&self) -> Option<f64> {
+        self.per_instruction(self.bits())
+    }
+
+    /// Compute the number of bits per retired instruction for a [`Format`]
+    ///
+    /// Only packets of the given [`Format`] are considered. Returns `None` if
+    /// no instructions were recorded.
+    pub fn format_bits_per_instruction(&self, format: Format) -> Option<f64> {
+        self.per_instruction(self.format(format).bits)
+    }
+
+    fn per_instruction(&self, bits: u64) -> Option<f64> {
+        (self.instructions != 0).then(|| bits as f64 / self.instructions as f64)
+    }
+}
+
+/// Encoder options to simulate
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Options {
+    /// [`AddressMode`] of the encoder
+    pub address_mode: AddressMode,
+    /// Whether sequentially inferable jumps are inferred
+    pub sijump: bool,
+    /// Whether implicit returns are enabled
+    ///
+    /// The [`Generator`][generator::Generator] currently does not support
+    /// implicit returns. Enabling this option will cause [`Simulator::new`] to
+    /// fail.
+    pub implicit_return: bool,
+}
+
+/// Simulator for traces generated with alternative [`Options`]
+///
+/// A simulator converts reconstructed [`Item`]s of a single hart into
+/// [`Step`][step::Step]s and feeds them to a [`Model`]. The packets emitted are
+/// recorded in [`Stats`], which are returned by [`finish`][Self::finish].
+///
+/// The [`Model`] emits [`encap::Packet`][packet::encap::Packet]s without
+/// timestamps, which should be considered when comparing the [`Stats`] to
+/// those of the original trace.
+pub struct Simulator<I = Option<instruction::Kind>>
+where
+    I: Info + Clone,
+{
+    model: Model<ItemStep<I>>,
+    stats: Stats,
+    held: Option<(Instruction<I>, u64)>,
+    trap: Option<trap::Info>,
+    context: Context,
+    ctype: CType,
+    upper_immediate: Option<I::Register>,
+}
+
+impl<I> Simulator<I>
+where
+    I: Info + Clone,
+    I::Register: Clone,
+{
+    /// Create a new simulator for the given parameters and [`Options`]
+    pub fn new(params: &config::Parameters, options: Options) -> Result<Self, Error> {
+        let generator = generator::builder()
+            .with_params(&config::Parameters {
+                sijump_p: options.sijump,
+                ..*params
+            })
+            .with_address_mode(options.address_mode)
+            .with_implicit_return(options.implicit_return)
+            .build()?;
+        let mut model = Model::new(generator, packet::builder().with_params(params));
+
+        let ioptions = unit::ReferenceIOptions {
+            implicit_return: options.implicit_return,
+            full_address: options.address_mode == AddressMode::Full,
+            ..Default::default()
+        };
+        let mut stats = Stats::default();
+        model.begin_qualification(ioptions, Default::default(), |p, d| {
+            stats.add_packet(p, 8 * d.len() as u64)
+        })?;
+
+        Ok(Self {
+            model,
+            stats,
+            held: None,
+            trap: None,
+            context: Default::default(),
+            ctype: Default::default(),
+            upper_immediate: None,
+        })
+    }
+
+    /// Process a single reconstructed [`Item`]
+    pub fn process_item(&mut self, item: &Item<I>) -> Result<(), Error> {
+        self.stats.add_item(item);
+
+        let pc = item.pc();
+        let step = match item.kind() {
+            item::Kind::Regular(insn) if insn.is_ecall_or_ebreak() => {
+                self.held = Some((insn.clone(), pc));
+                None
+            }
+            item::Kind::Regular(insn) => {
+                let prev_upper_immediate = self.upper_immediate.take();
+                self.upper_immediate = insn.upper_immediate(pc).map(|(r, _)| r);
+                Some(ItemStep {
+                    address: pc,
+                    retired: Retired::Instruction(insn.clone(), self.trap.take()),
+                    ctype: core::mem::take(&mut self.ctype),
+                    context: self.context,
+                    prev_upper_immediate,
+                    branch_taken: false,
+                })
+            }
+            item::Kind::Trap(info) => match self.held.take() {
+                Some((insn, address)) => Some(ItemStep {
+                    address,
+                    retired: Retired::Instruction(insn, Some(*info)),
+                    ctype: core::mem::take(&mut self.ctype),
+                    context: self.context,
+                    prev_upper_immediate: self.upper_immediate.take(),
+                    branch_taken: false,
+                }),
+                None => {
+                    self.trap = Some(*info);
+                    None
+                }
+            },
+            item::Kind::Notify => None,
+            item::Kind::Context(context) => {
+                let previous = core::mem::replace(&mut self.context, *context);
+                self.ctype = CType::Precisely;
+                self.trap.take().map(|trap| ItemStep {
+                    address: pc,
+                    retired: Retired::Trap(trap),
+                    ctype: CType::Unreported,
+                    context: previous,
+                    prev_upper_immediate: None,
+                    branch_taken: false,
+                })
+            }
+        };
+
+        if let Some(step) = step {
+            let stats = &mut self.stats;
+            self.model
+                .process_step(step, false, |p, d| stats.add_packet(p, 8 * d.len() as u64))?;
+        }
+        Ok(())
+    }
+
+    /// Finish the simulation, returning the [`Stats`] of the simulated trace
+    pub fn finish(mut self) -> Result<Stats, Error> {
+        let stats = &mut self.stats;
+        self.model
+            .end_qualification(true, |p, d| stats.add_packet(p, 8 * d.len() as u64))?;
+        Ok(self.stats)
+    }
+}
+
+/// [`step::Step`] derived from [`Item`]s
+#[derive(Clone, Debug)]
+struct ItemStep<I: Info> {
+    address: u64,
+    retired: Retired<I>,
+    ctype: CType,
+    context: Context,
+    prev_upper_immediate: Option<I::Register>,
+    branch_taken: bool,
+}
+
+impl<I> step::Step for ItemStep<I>
+where
+    I: Info + Clone,
+    I::Register: Clone,
+{
+    fn address(&self) -> u64 {
+        self.address
+    }
+
+    fn kind(&self) -> step::Kind {
+        match &self.retired {
+            Retired::Instruction(insn, Some(info)) => step::Kind::Trap {
+                insn_size: Some(insn.size),
+                info: *info,
+            },
+            Retired::Instruction(insn, None) => step::Kind::from_instruction(
+                insn.clone(),
+                self.branch_taken,
+                self.prev_upper_immediate.clone(),
+            ),
+            Retired::Trap(info) => step::Kind::Trap {
+                insn_size: None,
+                info: *info,
+            },
+        }
+    }
+
+    fn ctype(&self) -> CType {
+        self.ctype
+    }
+
+    fn context(&self) -> Context {
+        self.context
+    }
+
+    fn refine(&mut self, next: &Self) {
+        let Retired::Instruction(insn, _) = &self.retired else {
+            return;
+        };
+        let next_retires = matches!(next.retired, Retired::Instruction(..));
+        if let Some(target) = insn.branch_target().filter(|_| next_retires) {
+            self.branch_taken = self.address.wrapping_add_signed(target.into()) == next.address;
+        }
+    }
+}
+
+/// Retirement and trap information of an [`ItemStep`]
+#[derive(Clone, Debug)]
+enum Retired<I: Info> {
+    /// Retirement of an [`Instruction`], optionally with a trap
+    Instruction(Instruction<I>, Option<trap::Info>),
+    /// Trap without retirement
+    Trap(trap::Info),
+}
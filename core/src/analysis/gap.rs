@@ -0,0 +1,135 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Trace gap detection
+//!
+//! This module provides a [`Detector`] for intervals in which a single hart
+//! was not traced, e.g. because tracing was disabled, filtered or trace data
+//! was lost. Such intervals are reported as [`Gap`]s.
+
+use crate::packet::payload::InstructionTrace;
+use crate::packet::sync::{self, QualStatus};
+
+/// Detector for [`Gap`]s in the trace of a single hart
+///
+/// A detector processes all [`InstructionTrace`] payloads for a single hart,
+/// optionally accompanied by timestamps such as those carried by
+/// [`encap::Normal`][crate::packet::encap::Normal] packets. A [`Gap`] starts
+/// with a [`sync::Support`] payload signalling that tracing was disabled, or
+/// that qualification ended or trace data was lost. It ends with the next
+/// [`sync::Start`] or [`sync::Trap`].
+///
+/// If multiple harts are traced, one detector is needed per hart.
+///
+/// # Example
+///
+/// ```
+/// use riscv_etrace_core::analysis::gap::{Cause, Detector};
+/// use riscv_etrace_core::packet::payload::InstructionTrace;
+/// use riscv_etrace_core::packet::sync;
+///
+/// let end = sync::Support::default()
+///     .with_ienable(true)
+///     .with_qual_status(sync::QualStatus::TraceLost);
+/// let start = sync::Start::new(0x80000000).with_branch(false);
+///
+/// let mut detector = Detector::default();
+/// let payload: InstructionTrace = InstructionTrace::from(end);
+/// assert_eq!(detector.process_payload(Some(100), &payload), None);
+/// let payload: InstructionTrace = InstructionTrace::from(start);
+/// let gap = detector.process_payload(Some(150), &payload).unwrap();
+/// assert_eq!(gap.cause, Cause::Lost);
+/// assert_eq!(gap.duration(), Some(50));
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Detector {
+    last_time: Option<u64>,
+    open: Option<(Option<u64>, Cause)>,
+}
+
+impl Detector {
+    /// Process an [`InstructionTrace`] payload
+    ///
+    /// The `timestamp`, if present, should be the time associated with the
+    /// packet carrying the payload. Returns a [`Gap`] if the payload ended one.
+    pub fn process_payload<I, D>(
+        &mut self,
+        timestamp: Option<u64>,
+        payload: &InstructionTrace<I, D>,
+    ) -> Option<Gap> {
+        use sync::Synchronization;
+
+        if timestamp.is_some() {
+            self.last_time = timestamp;
+        }
+
+        match payload {
+            InstructionTrace::Synchronization(Synchronization::Support(support)) => {
+                if self.open.is_none() {
+                    let cause = if !support.ienable {
+                        Some(Cause::Disabled)
+                    } else {
+                        match support.qual_status {
+                            QualStatus::NoChange => None,
+                            QualStatus::EndedRep | QualStatus::EndedNtr => Some(Cause::Filtered),
+                            QualStatus::TraceLost => Some(Cause::Lost),
+                        }
+                    };
+                    self.open = cause.map(|c| (self.last_time, c));
+                }
+                None
+            }
+            InstructionTrace::Synchronization(Synchronization::Start(_))
+            | InstructionTrace::Synchronization(Synchronization::Trap(_)) => self.finish(timestamp),
+            _ => None,
+        }
+    }
+
+    /// Determine whether a [`Gap`] is currently open
+    pub fn in_gap(&self) -> bool {
+        self.open.is_some()
+    }
+
+    /// Finish the currently open [`Gap`], if any
+    ///
+    /// This fn may be used for retrieving a [`Gap`] which did not end before
+    /// the end of a capture. The `timestamp` marks the end of the [`Gap`].
+    pub fn finish(&mut self, timestamp: Option<u64>) -> Option<Gap> {
+        self.open.take().map(|(start, cause)| Gap {
+            start,
+            end: timestamp,
+            cause,
+        })
+    }
+}
+
+/// An interval in which a hart was not traced
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Gap {
+    /// Time of the last packet before or at the start of the gap
+    pub start: Option<u64>,
+    /// Time of the packet ending the gap
+    pub end: Option<u64>,
+    /// [`Cause`] of the gap
+    pub cause: Cause,
+}
+
+impl Gap {
+    /// Retrieve the duration of this gap
+    ///
+    /// Returns `None` if either the start or end time is unknown or if the end
+    /// lies before the start.
+    pub fn duration(&self) -> Option<u64> {
+        self.end?.checked_sub(self.start?)
+    }
+}
+
+/// Cause of a [`Gap`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Cause {
+    /// Instruction tracing was disabled
+    Disabled,
+    /// Qualification ended, e.g. due to filtering
+    Filtered,
+    /// Trace data was lost
+    Lost,
+}
@@ -0,0 +1,217 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Coverage of trap handlers
+//!
+//! This module provides [`Coverage`], which records the trap handlers
+//! exercised in a stream of [`Item`]s. Handlers are identified by the
+//! [`Vector`] consisting of the trap's cause and the PC of the handler's first
+//! instruction. For each handler, the number of invocations and the minimum and
+//! maximum durations, in instructions retired up to and including the return
+//! from the trap, are reported. The coverage may be exported as
+//! [CSV][Coverage::csv] or audited for causes which were never exercised via
+//! [`missing`][Coverage::missing].
+//!
+//! # Example
+//!
+//! ```
+//! use riscv_etrace_core::analysis::handler::{Coverage, Vector};
+//! use riscv_etrace_core::instruction::Kind;
+//! use riscv_etrace_core::tracer::item::Item;
+//! use riscv_etrace_core::types::trap;
+//!
+//! let ecall = trap::Info { ecause: 8, tval: Some(0) };
+//! let items = [
+//!     Item::new(0x1000, ecall.into()),
+//!     Item::new(0x100, Kind::nop.into()),
+//!     Item::new(0x104, Kind::mret.into()),
+//!     Item::new(0x1004, Kind::nop.into()),
+//! ];
+//!
+//! let mut coverage = Coverage::default();
+//! items.iter().for_each(|i| coverage.process_item(i));
+//! let vector = Vector { interrupt: false, ecause: 8, entry: 0x100 };
+//! let handler = coverage.handler(&vector).unwrap();
+//! assert_eq!(handler.count, 1);
+//! assert_eq!(handler.max, Some(2));
+//! assert_eq!(
+//!     coverage.csv().to_string(),
+//!     "kind,ecause,entry,count,min,max\nexception,8,0x100,1,2,2\n",
+//! );
+//! ```
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::instruction::info::Info;
+use crate::tracer::item::{Confidence, Item, Kind};
+use crate::types::trap;
+
+/// Coverage of trap handlers
+///
+/// [`Item`]s are fed via [`process_item`][Self::process_item] in the order
+/// they were generated for a single hart. A trap is attributed to the handler
+/// at the PC of the next instruction retired. The handler is considered active
+/// until an instruction [returning from the trap][Info::is_return_from_trap]
+/// is retired. Nested traps are supported, with the duration of an inner
+/// handler counting towards the outer one.
+///
+/// An [`Item`] with [`Confidence::Gap`] discards all active handlers, as their
+/// durations cannot be determined.
+#[derive(Clone, Debug, Default)]
+pub struct Coverage {
+    handlers: BTreeMap<Vector, Handler>,
+    pending: Option<trap::Info>,
+    active: Vec<(Vector, u64)>,
+}
+
+impl Coverage {
+    /// Process a reconstructed [`Item`]
+    pub fn process_item<I: Info>(&mut self, item: &Item<I>) {
+        if item.confidence() == Confidence::Gap {
+            self.pending = None;
+            self.active.clear();
+        }
+        match item.kind() {
+            Kind::Regular(insn) => {
+                if let Some(info) = self.pending.take() {
+                    let vector = Vector {
+                        interrupt: info.is_interrupt(),
+                        ecause: info.ecause,
+                        entry: item.pc(),
+                    };
+                    let handler = self.handlers.entry(vector).or_default();
+                    handler.count = handler.count.saturating_add(1);
+                    self.active.push((vector, 0));
+                }
+                if let Some((_, duration)) = self.active.last_mut() {
+                    *duration = duration.saturating_add(1);
+                }
+                if insn.info.is_return_from_trap() {
+                    self.finish();
+                }
+            }
+            Kind::Trap(info) => self.pending = Some(*info),
+            Kind::Context(_) | Kind::Notify => (),
+        }
+    }
+
+    /// Retrieve the [`Handler`] for the given [`Vector`], if exercised
+    pub fn handler(&self, vector: &Vector) -> Option<&Handler> {
+        self.handlers.get(vector)
+    }
+
+    /// Iterate over all exercised [`Vector`]s and their [`Handler`]s
+    ///
+    /// The iterator yields exceptions before interrupts, in ascending order of
+    /// their cause and entry PC.
+    pub fn iter(&self) -> impl Iterator<Item = (&Vector, &Handler)> + '_ {
+        self.handlers.iter()
+    }
+
+    /// Retrieve the number of handlers currently active
+    pub fn depth(&self) -> usize {
+        self.active.len()
+    }
+
+    /// Determine whether any handler was entered for the given cause
+    pub fn covers(&self, cause: trap::Cause) -> bool {
+        self.handlers.keys().any(|v| v.cause() == cause)
+    }
+
+    /// Determine which of the given causes were never exercised
+    pub fn missing<'a, C>(&'a self, causes: C) -> impl Iterator<Item = trap::Cause> + 'a
+    where
+        C: IntoIterator<Item = trap::Cause>,
+        C::IntoIter: 'a,
+    {
+        causes.into_iter().filter(|c| !self.covers(*c))
+    }
+
+    /// Create a [`fmt::Display`] rendering this coverage as CSV
+    ///
+    /// The CSV consists of a header followed by one line per [`Vector`].
+    /// Durations are empty for handlers which never returned.
+    pub fn csv(&self) -> Csv<'_> {
+        Csv(self)
+    }
+
+    /// Finish the innermost active handler
+    fn finish(&mut self) {
+        let Some((vector, duration)) = self.active.pop() else {
+            return;
+        };
+        if let Some(handler) = self.handlers.get_mut(&vector) {
+            handler.min = Some(handler.min.map_or(duration, |m| m.min(duration)));
+            handler.max = Some(handler.max.map_or(duration, |m| m.max(duration)));
+        }
+        if let Some((_, outer)) = self.active.last_mut() {
+            *outer = outer.saturating_add(duration);
+        }
+    }
+}
+
+/// Identification of a trap handler
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vector {
+    /// Whether the handler was entered due to an interrupt
+    pub interrupt: bool,
+    /// Exception or interrupt cause
+    pub ecause: u16,
+    /// PC of the handler's first instruction
+    pub entry: u64,
+}
+
+impl Vector {
+    /// Decode the [`ecause`][Self::ecause] into a [`trap::Cause`]
+    pub fn cause(&self) -> trap::Cause {
+        if self.interrupt {
+            trap::Cause::Interrupt(self.ecause.into())
+        } else {
+            trap::Cause::Exception(self.ecause.into())
+        }
+    }
+}
+
+/// Statistics of a single trap handler
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Handler {
+    /// Number of times the handler was entered
+    pub count: u64,
+    /// Shortest duration in instructions, if the handler ever returned
+    pub min: Option<u64>,
+    /// Longest duration in instructions, if the handler ever returned
+    pub max: Option<u64>,
+}
+
+/// CSV rendering of a [`Coverage`]
+///
+/// Created via [`Coverage::csv`].
+#[derive(Copy, Clone, Debug)]
+pub struct Csv<'a>(&'a Coverage);
+
+impl fmt::Display for Csv<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "kind,ecause,entry,count,min,max")?;
+        self.0.iter().try_for_each(|(vector, handler)| {
+            let kind = if vector.interrupt {
+                "interrupt"
+            } else {
+                "exception"
+            };
+            write!(
+                f,
+                "{kind},{},{:#x},{},",
+                vector.ecause, vector.entry, handler.count
+            )?;
+            if let Some(min) = handler.min {
+                write!(f, "{min}")?;
+            }
+            write!(f, ",")?;
+            if let Some(max) = handler.max {
+                write!(f, "{max}")?;
+            }
+            writeln!(f)
+        })
+    }
+}
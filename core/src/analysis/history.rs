@@ -0,0 +1,399 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Retention of the most recent tracing items
+//!
+//! This module provides a [`History`] retaining the last `N` [`Item`]s of a
+//! single hart and [`Harts`], which retains histories for multiple harts. Both
+//! do not require any allocation and are intended for self-hosted decoders,
+//! e.g. on embedded targets, which want to report the recent control flow
+//! after a fault or watchdog reset.
+//!
+//! Histories can be written into a compact binary [dump][History::dump] which
+//! may be read back via [`read_dump`].
+//!
+//! # Dump format
+//!
+//! A dump consists of a sequence of records, one per hart. Each record starts
+//! with the hart's index and the number of items, followed by the items from
+//! oldest to newest. Each item starts with a tag byte. Its lowest two bits
+//! encode the [`Kind`]: `0` for [`Kind::Regular`], `1` for [`Kind::Trap`], `2`
+//! for [`Kind::Context`] and `3` for [`Kind::Notify`]. For regular items, the
+//! next three bits encode the instruction's [`Size`] in units of 16 bit. For
+//! traps, bit `2` is set for exceptions. The tag is followed by the difference
+//! between the item's PC and the PC of the previous item in the record (or
+//! `0`), zigzag-encoded. Traps are followed by the `ecause` and, for
+//! exceptions, the `tval`. Contexts are followed by the [`Privilege`] as a
+//! single byte and the context. All numbers except the tag and privilege are
+//! encoded as unsigned LEB128.
+//!
+//! [`Privilege`]: crate::types::Privilege
+
+use core::fmt;
+
+use crate::instruction::{self, Instruction, Size, info::Info};
+use crate::tracer::item::{Item, Kind};
+use crate::types::{Context, trap};
+
+/// Ring buffer retaining the last `N` [`Item`]s of a single hart
+///
+/// # Example
+///
+/// ```
+/// use riscv_etrace_core::analysis::history::History;
+/// use riscv_etrace_core::instruction::COMPRESSED;
+/// use riscv_etrace_core::tracer::item::Item;
+///
+/// let mut history: History<_, 2> = Default::default();
+/// (0..3).for_each(|i| history.push(Item::new(0x100 + 2 * i, COMPRESSED.into())));
+/// assert_eq!(history.len(), 2);
+/// assert_eq!(history.total(), 3);
+/// assert!(history.iter().map(Item::pc).eq([0x102, 0x104]));
+/// ```
+#[derive(Clone, Debug)]
+pub struct History<I: Info = Option<instruction::Kind>, const N: usize = 64> {
+    items: [Option<Item<I>>; N],
+    next: usize,
+    total: u64,
+}
+
+impl<I: Info, const N: usize> History<I, N> {
+    /// Push a new [`Item`], evicting the oldest one if the history is full
+    pub fn push(&mut self, item: Item<I>) {
+        if let Some(slot) = self.items.get_mut(self.next) {
+            *slot = Some(item);
+            self.next = (self.next + 1) % N;
+        }
+        self.total = self.total.saturating_add(1);
+    }
+
+    /// Retrieve the number of [`Item`]s currently retained
+    pub fn len(&self) -> usize {
+        self.total.try_into().unwrap_or(usize::MAX).min(N)
+    }
+
+    /// Determine whether no [`Item`]s are retained
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Retrieve the total number of [`Item`]s pushed since the last clear
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Iterate over the retained [`Item`]s, from oldest to newest
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Item<I>> + '_ {
+        let (newer, older) = self.items.split_at(self.next);
+        older.iter().chain(newer).filter_map(Option::as_ref)
+    }
+
+    /// Retrieve the most recent [`Item`]
+    pub fn latest(&self) -> Option<&Item<I>> {
+        self.iter().next_back()
+    }
+
+    /// Remove all [`Item`]s
+    pub fn clear(&mut self) {
+        self.items.iter_mut().for_each(|i| *i = None);
+        self.next = 0;
+        self.total = 0;
+    }
+
+    /// Write a compact dump of this history for the given hart
+    ///
+    /// Returns the number of bytes written to `buffer`. See the
+    /// [module level documentation][self] for a description of the format.
+    pub fn dump(&self, hart: u64, buffer: &mut [u8]) -> Result<usize, Error> {
+        let mut writer = Writer { buffer, pos: 0 };
+        writer.write_uint(hart)?;
+        writer.write_uint(self.len() as u64)?;
+        self.iter().try_fold(0, |pc, item| {
+            writer.write_item(pc, item)?;
+            Ok(item.pc())
+        })?;
+        Ok(writer.pos)
+    }
+}
+
+impl<I: Info, const N: usize> Default for History<I, N> {
+    fn default() -> Self {
+        Self {
+            items: [const { None }; N],
+            next: 0,
+            total: 0,
+        }
+    }
+}
+
+/// [`History`]s for up to `H` harts
+///
+/// A [`History`] is assigned to a hart when an [`Item`] is first pushed for
+/// it. If histories were already assigned to `H` harts, items for any other
+/// hart are rejected.
+#[derive(Clone, Debug)]
+pub struct Harts<I: Info = Option<instruction::Kind>, const N: usize = 64, const H: usize = 4> {
+    harts: [Option<(u64, History<I, N>)>; H],
+}
+
+impl<I: Info, const N: usize, const H: usize> Harts<I, N, H> {
+    /// Push a new [`Item`] for the given hart
+    ///
+    /// Returns the [`Item`] if no [`History`] could be assigned to the hart.
+    pub fn push(&mut self, hart: u64, item: Item<I>) -> Result<(), Item<I>> {
+        let pos = self
+            .harts
+            .iter()
+            .position(|h| h.as_ref().is_some_and(|(h, _)| *h == hart))
+            .or_else(|| self.harts.iter().position(Option::is_none));
+        match pos.and_then(|p| self.harts.get_mut(p)) {
+            Some(slot) => {
+                slot.get_or_insert_with(|| (hart, Default::default()))
+                    .1
+                    .push(item);
+                Ok(())
+            }
+            None => Err(item),
+        }
+    }
+
+    /// Retrieve the [`History`] for the given hart
+    pub fn get(&self, hart: u64) -> Option<&History<I, N>> {
+        self.iter()
+            .find_map(|(h, history)| (h == hart).then_some(history))
+    }
+
+    /// Iterate over all harts and their [`History`]s
+    pub fn iter(&self) -> impl Iterator<Item = (u64, &History<I, N>)> + '_ {
+        self.harts
+            .iter()
+            .flatten()
+            .map(|(h, history)| (*h, history))
+    }
+
+    /// Write a compact dump of the [`History`]s of all harts
+    ///
+    /// Returns the number of bytes written to `buffer`.
+    pub fn dump(&self, buffer: &mut [u8]) -> Result<usize, Error> {
+        self.iter().try_fold(0, |pos, (hart, history)| {
+            let buffer = buffer.get_mut(pos..).ok_or(Error::BufferTooSmall)?;
+            history.dump(hart, buffer).map(|len| pos + len)
+        })
+    }
+}
+
+impl<I: Info, const N: usize, const H: usize> Default for Harts<I, N, H> {
+    fn default() -> Self {
+        Self {
+            harts: [const { None }; H],
+        }
+    }
+}
+
+/// Read a dump written by [`History::dump`] or [`Harts::dump`]
+///
+/// The returned iterator yields the hart index and [`Item`] for every item in
+/// the dump. Since dumps do not contain any [`Info`], it is not restored.
+///
+/// # Example
+///
+/// ```
+/// use riscv_etrace_core::analysis::history::{History, read_dump};
+/// use riscv_etrace_core::instruction::UNCOMPRESSED;
+/// use riscv_etrace_core::tracer::item::Item;
+///
+/// let mut history: History = Default::default();
+/// history.push(Item::new(0x80000000, UNCOMPRESSED.into()));
+/// let mut buffer = [0u8; 16];
+/// let len = history.dump(1, &mut buffer).unwrap();
+/// let (hart, item) = read_dump(&buffer[..len]).next().unwrap().unwrap();
+/// assert_eq!(hart, 1);
+/// assert_eq!(item.pc(), 0x80000000);
+/// ```
+pub fn read_dump(data: &[u8]) -> Dump<'_> {
+    Dump {
+        data,
+        hart: 0,
+        left: 0,
+        pc: 0,
+    }
+}
+
+/// Reader for dumps created by [`History::dump`] or [`Harts::dump`]
+///
+/// Created via [`read_dump`].
+#[derive(Clone, Debug)]
+pub struct Dump<'d> {
+    data: &'d [u8],
+    hart: u64,
+    left: u64,
+    pc: u64,
+}
+
+impl Dump<'_> {
+    /// Read the next [`Item`]
+    fn read_item(&mut self) -> Result<(u64, Item), Error> {
+        while self.left == 0 {
+            self.hart = self.read_uint()?;
+            self.left = self.read_uint()?;
+            self.pc = 0;
+        }
+
+        let tag = self.read_byte()?;
+        let pc = self.pc.wrapping_add_signed(unzigzag(self.read_uint()?));
+        let kind = match tag & 0x3 {
+            0 => {
+                let size = match tag >> 2 {
+                    1 => Size::Compressed,
+                    2 => Size::Normal,
+                    3 => Size::Wide,
+                    4 => Size::ExtraWide,
+                    _ => return Err(Error::InvalidData),
+                };
+                Kind::Regular(Instruction { size, info: None })
+            }
+            1 => {
+                let ecause = self
+                    .read_uint()?
+                    .try_into()
+                    .map_err(|_| Error::InvalidData)?;
+                let tval = if tag & 0x4 != 0 {
+                    Some(self.read_uint()?)
+                } else {
+                    None
+                };
+                Kind::Trap(trap::Info { ecause, tval })
+            }
+            2 => {
+                let privilege = self
+                    .read_byte()?
+                    .try_into()
+                    .map_err(|_| Error::InvalidData)?;
+                let context = self.read_uint()?;
+                Kind::Context(Context { privilege, context })
+            }
+            _ => Kind::Notify,
+        };
+
+        self.left -= 1;
+        self.pc = pc;
+        Ok((self.hart, Item::new(pc, kind)))
+    }
+
+    /// Read a single byte
+    fn read_byte(&mut self) -> Result<u8, Error> {
+        let (byte, rest) = self.data.split_first().ok_or(Error::InvalidData)?;
+        self.data = rest;
+        Ok(*byte)
+    }
+
+    /// Read an unsigned LEB128 number
+    fn read_uint(&mut self) -> Result<u64, Error> {
+        let mut res = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.read_byte()?;
+            res |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(res);
+            }
+        }
+        Err(Error::InvalidData)
+    }
+}
+
+impl Iterator for Dump<'_> {
+    type Item = Result<(u64, Item), Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.left == 0 && self.data.is_empty() {
+            return None;
+        }
+        let res = self.read_item();
+        if res.is_err() {
+            self.data = &[];
+            self.left = 0;
+        }
+        Some(res)
+    }
+}
+
+/// Errors related to dumps of [`History`]s
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The buffer is too small for the dump
+    BufferTooSmall,
+    /// The dump is malformed or truncated
+    InvalidData,
+}
+
+impl core::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BufferTooSmall => write!(f, "buffer too small for dump"),
+            Self::InvalidData => write!(f, "malformed dump"),
+        }
+    }
+}
+
+/// Writer for dumps
+struct Writer<'b> {
+    buffer: &'b mut [u8],
+    pos: usize,
+}
+
+impl Writer<'_> {
+    /// Write a single [`Item`] following an item with the given PC
+    fn write_item<I: Info>(&mut self, pc: u64, item: &Item<I>) -> Result<(), Error> {
+        let delta = zigzag(item.pc().wrapping_sub(pc) as i64);
+        match item.kind() {
+            Kind::Regular(insn) => {
+                self.write_byte((insn.size as u8 / 2) << 2)?;
+                self.write_uint(delta)
+            }
+            Kind::Trap(info) => {
+                self.write_byte(if info.is_exception() { 0x5 } else { 0x1 })?;
+                self.write_uint(delta)?;
+                self.write_uint(info.ecause.into())?;
+                info.tval.map_or(Ok(()), |t| self.write_uint(t))
+            }
+            Kind::Context(ctx) => {
+                self.write_byte(0x2)?;
+                self.write_uint(delta)?;
+                self.write_byte(u8::from(ctx.privilege))?;
+                self.write_uint(ctx.context)
+            }
+            Kind::Notify => {
+                self.write_byte(0x3)?;
+                self.write_uint(delta)
+            }
+        }
+    }
+
+    /// Write a single byte
+    fn write_byte(&mut self, byte: u8) -> Result<(), Error> {
+        let slot = self.buffer.get_mut(self.pos).ok_or(Error::BufferTooSmall)?;
+        *slot = byte;
+        self.pos += 1;
+        Ok(())
+    }
+
+    /// Write an unsigned LEB128 number
+    fn write_uint(&mut self, mut value: u64) -> Result<(), Error> {
+        while value >= 0x80 {
+            self.write_byte((value as u8) | 0x80)?;
+            value >>= 7;
+        }
+        self.write_byte(value as u8)
+    }
+}
+
+/// Zigzag-encode a signed number
+fn zigzag(value: i64) -> u64 {
+    ((value << 1) ^ (value >> 63)) as u64
+}
+
+/// Decode a zigzag-encoded number
+fn unzigzag(value: u64) -> i64 {
+    ((value >> 1) as i64) ^ -((value & 1) as i64)
+}
@@ -0,0 +1,135 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Per sync interval metrics
+//!
+//! Encoders emit synchronization packets periodically, e.g. after a number of
+//! packets or cycles, bounding the amount of trace data that needs to be
+//! processed before tracing may be (re)started. This module provides a
+//! [`Meter`] collecting metrics for the [`Interval`]s between those packets,
+//! which allow verifying that an encoder's resync settings result in the
+//! expected overhead on real workloads.
+
+use crate::instruction::info::Info;
+use crate::packet::payload::InstructionTrace;
+use crate::packet::sync::Synchronization;
+use crate::tracer::item::Item;
+
+/// Meter for [`Interval`]s between sync packets of a single hart
+///
+/// A meter processes all [`InstructionTrace`] payloads for a single hart
+/// together with their size, as well as the [`Item`]s generated from them. An
+/// [`Interval`] starts with a [`sync::Start`][crate::packet::sync::Start] or
+/// [`sync::Trap`][crate::packet::sync::Trap] payload, which is counted towards
+/// it, and ends with the next one.
+///
+/// If multiple harts are traced, one meter is needed per hart.
+///
+/// # Example
+///
+/// ```
+/// use riscv_etrace_core::analysis::interval::Meter;
+/// use riscv_etrace_core::instruction::Kind;
+/// use riscv_etrace_core::packet::{payload, sync};
+/// use riscv_etrace_core::tracer::item::Item;
+///
+/// let start: payload::InstructionTrace = sync::Start::new(0x80000000).into();
+///
+/// let mut meter = Meter::default();
+/// assert_eq!(meter.process_payload(&start, 10), None);
+/// for pc in [0x80000000, 0x80000002] {
+///     meter.process_item(&Item::<Option<Kind>>::new(pc, Kind::c_nop.into()));
+/// }
+/// let interval = meter.process_payload(&start, 10).unwrap();
+/// assert_eq!(interval.start, Some(0x80000000));
+/// assert_eq!(interval.packets, 1);
+/// assert_eq!(interval.bytes, 10);
+/// assert_eq!(interval.instructions, 2);
+/// assert_eq!(interval.bytes_per_instruction(), Some(5.0));
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Meter {
+    current: Interval,
+}
+
+impl Meter {
+    /// Process an [`InstructionTrace`] payload
+    ///
+    /// The `bytes` should be the size of the packet carrying the payload,
+    /// including any headers. Returns an [`Interval`] if the payload ended one.
+    /// Payloads preceeding the first sync are accounted to an [`Interval`]
+    /// without a start address, which is only reported if it is not empty.
+    pub fn process_payload<I, D>(
+        &mut self,
+        payload: &InstructionTrace<I, D>,
+        bytes: u64,
+    ) -> Option<Interval> {
+        let start = match payload {
+            InstructionTrace::Synchronization(Synchronization::Start(start)) => Some(start.address),
+            InstructionTrace::Synchronization(Synchronization::Trap(trap)) => Some(trap.address),
+            _ => None,
+        };
+        let res = start.and_then(|start| {
+            let next = Interval {
+                start: Some(start),
+                ..Default::default()
+            };
+            Some(core::mem::replace(&mut self.current, next)).filter(|i| !i.is_empty())
+        });
+        self.current.packets += 1;
+        self.current.bytes += bytes;
+        res
+    }
+
+    /// Process an [`Item`] generated from the payloads processed
+    pub fn process_item<I: Info>(&mut self, item: &Item<I>) {
+        self.current.items += 1;
+        if item.instruction().is_some() {
+            self.current.instructions += 1;
+        }
+    }
+
+    /// Retrieve the current, unfinished [`Interval`]
+    pub fn current(&self) -> &Interval {
+        &self.current
+    }
+
+    /// Finish the current [`Interval`]
+    ///
+    /// This fn may be used for retrieving the last [`Interval`] at the end of
+    /// a capture. Returns `None` if the current [`Interval`] is empty.
+    pub fn finish(&mut self) -> Option<Interval> {
+        Some(core::mem::take(&mut self.current)).filter(|i| !i.is_empty())
+    }
+}
+
+/// Metrics of the trace between two sync packets
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Interval {
+    /// Address reported by the sync packet starting the interval
+    pub start: Option<u64>,
+    /// Number of packets in the interval, including the starting sync
+    pub packets: u64,
+    /// Number of bytes of the packets in the interval
+    pub bytes: u64,
+    /// Number of [`Item`]s generated
+    pub items: u64,
+    /// Number of retired instructions
+    pub instructions: u64,
+}
+
+impl Interval {
+    /// Determine whether this interval is empty
+    ///
+    /// An interval is considered empty if neither packets nor [`Item`]s were
+    /// recorded.
+    pub fn is_empty(&self) -> bool {
+        self.packets == 0 && self.items == 0
+    }
+
+    /// Compute the number of bytes per retired instruction
+    ///
+    /// Returns `None` if no instructions were retired.
+    pub fn bytes_per_instruction(&self) -> Option<f64> {
+        (self.instructions != 0).then(|| self.bytes as f64 / self.instructions as f64)
+    }
+}
@@ -0,0 +1,205 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Function invocation tracing
+//!
+//! This module provides [`Invocations`], an adapter for streams of [`Item`]s
+//! that extracts individual invocations of a single function. An invocation
+//! spans all [`Item`]s from the entry of the function up to and including the
+//! matching return.
+
+use core::ops::Range;
+
+use crate::instruction::info::Info;
+use crate::tracer::item::Item;
+
+/// Adapter extracting invocations of a single function
+///
+/// This adapter wraps a stream of [`Item`]s and allows extracting the
+/// [`Invocation`]s of a function identified by its address range. Invocations
+/// are extracted one after another via
+/// [`next_invocation`][Self::next_invocation].
+///
+/// An invocation starts with a retired [`Instruction`] at the function's entry
+/// point, i.e. the start of the range. It ends with the
+/// [return][Info::is_return] matching that entry. Calls and returns within the
+/// invocation are tracked via a call depth, which covers both calls to other
+/// functions and recursive calls to the function itself. Recursive invocations
+/// are thus part of the outermost invocation rather than being reported
+/// separately.
+///
+/// The end of the range is used for attributing [`Item`]s to the function
+/// itself rather than to its callees, see [`Invocation::is_own`]. A matching
+/// return outside the range, e.g. of a function tail-called by the function,
+/// also ends the invocation, but is reported via
+/// [`Invocation::is_tail_return`]. Other ways of leaving a function without a
+/// return are not detected.
+///
+/// [`Instruction`]: crate::instruction::Instruction
+///
+/// # Example
+///
+/// ```
+/// use riscv_etrace_core::analysis::invocation::Invocations;
+/// use riscv_etrace_core::instruction::Kind;
+/// use riscv_etrace_core::tracer::item::Item;
+///
+/// let items = [
+///     Item::new(0x10, Kind::new_jal(1, 0x10).into()),
+///     Item::new(0x20, Kind::nop.into()),
+///     Item::new(0x24, Kind::new_c_jr(1).into()),
+///     Item::new(0x14, Kind::nop.into()),
+/// ];
+/// let mut invocations = Invocations::new(items.into_iter(), 0x20..0x30);
+/// let mut invocation = invocations.next_invocation().unwrap();
+/// assert_eq!(invocation.by_ref().map(|i| i.pc()).sum::<u64>(), 0x44);
+/// assert!(invocation.is_complete());
+/// assert!(invocations.next_invocation().is_none());
+/// ```
+#[derive(Clone, Debug)]
+pub struct Invocations<T, I: Info> {
+    items: T,
+    function: Range<u64>,
+    state: Option<State<I>>,
+}
+
+impl<T: Iterator<Item = Item<I>>, I: Info> Invocations<T, I> {
+    /// Create a new adapter for the function occupying the given range
+    pub fn new(items: T, function: Range<u64>) -> Self {
+        Self {
+            items,
+            function,
+            state: None,
+        }
+    }
+
+    /// Retrieve the address range of the function
+    pub fn function(&self) -> &Range<u64> {
+        &self.function
+    }
+
+    /// Retrieve the next [`Invocation`]
+    ///
+    /// Any remaining [`Item`]s of the previous [`Invocation`] are skipped.
+    /// Returns `None` if the underlying stream of [`Item`]s is exhausted before
+    /// encountering the function's entry.
+    pub fn next_invocation(&mut self) -> Option<Invocation<'_, T, I>> {
+        if self.state.as_ref().is_some_and(|s| !s.done) {
+            Invocation { adapter: self }.for_each(drop);
+        }
+
+        let entry = self.function.start;
+        let first = self
+            .items
+            .find(|i| i.pc() == entry && i.instruction().is_some())?;
+        self.state = Some(State {
+            first: Some(first),
+            depth: 0,
+            own: false,
+            done: false,
+            complete: false,
+        });
+        Some(Invocation { adapter: self })
+    }
+
+    /// Retrieve the inner stream of [`Item`]s
+    pub fn into_inner(self) -> T {
+        self.items
+    }
+}
+
+/// A single invocation of a function
+///
+/// This [`Iterator`] yields the [`Item`]s of a single invocation extracted by
+/// [`Invocations`], starting with the [`Item`] for the function's entry. It
+/// ends after yielding the [`Item`] for the matching return or when the
+/// underlying stream is exhausted.
+pub struct Invocation<'a, T, I: Info> {
+    adapter: &'a mut Invocations<T, I>,
+}
+
+impl<T: Iterator<Item = Item<I>>, I: Info> Invocation<'_, T, I> {
+    /// Retrieve the current call depth relative to the function's entry
+    ///
+    /// The depth is incremented for every call and decremented for every
+    /// return encountered within the invocation.
+    pub fn depth(&self) -> usize {
+        self.state().depth
+    }
+
+    /// Determine whether the invocation ended with its matching return
+    ///
+    /// Returns `false` if the invocation was not yet exhausted or if the
+    /// underlying stream ended before the matching return was encountered.
+    pub fn is_complete(&self) -> bool {
+        self.state().complete
+    }
+
+    /// Determine whether the last [`Item`] yielded belongs to the function
+    ///
+    /// Returns `true` if the last [`Item`] lies within the function's range
+    /// and was not yielded for one of its callees, including recursive
+    /// invocations. Returns `false` if no [`Item`] was yielded yet.
+    pub fn is_own(&self) -> bool {
+        self.state().own
+    }
+
+    /// Determine whether the invocation ended with a return outside the range
+    ///
+    /// Returns `true` if the matching return lies outside the function's
+    /// range, e.g. if the function was left via a tail call and the invocation
+    /// ended with the tail-called function's return.
+    pub fn is_tail_return(&self) -> bool {
+        let state = self.state();
+        state.complete && !state.own
+    }
+
+    /// Retrieve the invocation's [`State`]
+    fn state(&self) -> &State<I> {
+        self.adapter
+            .state
+            .as_ref()
+            .expect("Invocation without state")
+    }
+}
+
+impl<T: Iterator<Item = Item<I>>, I: Info> Iterator for Invocation<'_, T, I> {
+    type Item = Item<I>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let adapter = &mut *self.adapter;
+        let state = adapter.state.as_mut()?;
+        if state.done {
+            return None;
+        }
+
+        let Some(item) = state.first.take().or_else(|| adapter.items.next()) else {
+            state.done = true;
+            return None;
+        };
+
+        state.own = state.depth == 0 && adapter.function.contains(&item.pc());
+        if let Some(insn) = item.instruction() {
+            if insn.is_call() {
+                state.depth += 1;
+            } else if insn.is_return() {
+                if let Some(depth) = state.depth.checked_sub(1) {
+                    state.depth = depth;
+                } else {
+                    state.done = true;
+                    state.complete = true;
+                }
+            }
+        }
+        Some(item)
+    }
+}
+
+/// State of the current [`Invocation`]
+#[derive(Clone, Debug)]
+struct State<I: Info> {
+    first: Option<Item<I>>,
+    depth: usize,
+    own: bool,
+    done: bool,
+    complete: bool,
+}
@@ -0,0 +1,269 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Attribution of tracing items to processes
+//!
+//! Operating systems usually communicate the currently running process to the
+//! trace encoder via the execution context, e.g. by writing an ASID or PID to
+//! the `scontext` CSR. This module provides an [`Attribution`] which maps
+//! contexts reported in [`Kind::Context`] items to [`Process`]es registered by
+//! the user, attributes all following [`Item`]s to the current process and
+//! reports [`Switch`]es between processes. Per-process [`Stats`] are collected
+//! along the way.
+//!
+//! [`Kind::Context`]: crate::tracer::item::Kind::Context
+
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use core::fmt;
+
+use crate::instruction::info::Info;
+use crate::tracer::item::{Item, Kind};
+
+/// Attribution of [`Item`]s to [`Process`]es
+///
+/// [`Item`]s are fed via [`process_item`][Self::process_item] in the order
+/// they were generated for a single hart. The context of each [`Kind::Context`]
+/// item is masked with the configured [mask][Self::with_mask] and looked up
+/// among the registered [`Process`]es. Items following a context for which no
+/// process is registered, or preceding the first context, are attributed to no
+/// process and accounted for in [`unknown`][Self::unknown].
+///
+/// # Example
+///
+/// ```
+/// use riscv_etrace_core::analysis::process::{Attribution, Process, Switch};
+/// use riscv_etrace_core::instruction::{Instruction, Kind};
+/// use riscv_etrace_core::tracer::item::Item;
+/// use riscv_etrace_core::types::{Context, Privilege};
+///
+/// let mut attribution = Attribution::new()
+///     .with_mask(0xffff)
+///     .with_process(1, Process::new(100, "init"))
+///     .with_process(2, Process::new(200, "shell"));
+///
+/// let nop = Instruction::from(Kind::c_nop);
+/// let context = |context| Context { privilege: Privilege::User, context };
+/// let items = [
+///     Item::new(0x1000, context(0x1_0001).into()),
+///     Item::new(0x1000, nop.into()),
+///     Item::new(0x2000, context(0x2_0002).into()),
+///     Item::new(0x2000, nop.into()),
+///     Item::new(0x2002, nop.into()),
+/// ];
+/// let switches: Vec<_> = items
+///     .iter()
+///     .filter_map(|i| attribution.process_item(i))
+///     .collect();
+/// assert_eq!(switches[1], Switch { pc: 0x2000, from: Some(1), to: Some(2) });
+/// assert_eq!(attribution.current().map(|p| p.name.as_str()), Some("shell"));
+/// assert_eq!(attribution.stats(2).map(|s| s.instructions), Some(2));
+/// ```
+#[derive(Clone, Debug)]
+pub struct Attribution {
+    processes: BTreeMap<u64, (Process, Stats)>,
+    mask: u64,
+    current: Option<u64>,
+    unknown: Stats,
+}
+
+impl Attribution {
+    /// Create a new attribution without any registered [`Process`]es
+    ///
+    /// Contexts are not masked.
+    pub fn new() -> Self {
+        Self {
+            processes: Default::default(),
+            mask: u64::MAX,
+            current: None,
+            unknown: Default::default(),
+        }
+    }
+
+    /// Set the mask applied to contexts before looking up [`Process`]es
+    ///
+    /// This allows extracting e.g. an ASID from a context which also carries
+    /// other information.
+    pub fn with_mask(self, mask: u64) -> Self {
+        Self { mask, ..self }
+    }
+
+    /// Register a [`Process`] for the given (masked) context
+    pub fn with_process(mut self, context: u64, process: Process) -> Self {
+        self.insert(context, process);
+        self
+    }
+
+    /// Register a [`Process`] for the given (masked) context
+    ///
+    /// Replaces any process previously registered for the context, including
+    /// its [`Stats`].
+    pub fn insert(&mut self, context: u64, process: Process) {
+        self.processes
+            .insert(context, (process, Default::default()));
+    }
+
+    /// Process a reconstructed [`Item`]
+    ///
+    /// Returns a [`Switch`] if the item signals a context different from the
+    /// current one.
+    pub fn process_item<I: Info>(&mut self, item: &Item<I>) -> Option<Switch> {
+        match item.kind() {
+            Kind::Regular(_) => {
+                let stats = self.current_stats_mut();
+                stats.instructions = stats.instructions.saturating_add(1);
+                None
+            }
+            Kind::Trap(_) => {
+                let stats = self.current_stats_mut();
+                stats.traps = stats.traps.saturating_add(1);
+                None
+            }
+            Kind::Notify => None,
+            Kind::Context(ctx) => {
+                let to = self
+                    .processes
+                    .contains_key(&(ctx.context & self.mask))
+                    .then_some(ctx.context & self.mask);
+                let from = core::mem::replace(&mut self.current, to);
+                if from == to {
+                    return None;
+                }
+                let stats = self.current_stats_mut();
+                stats.switches = stats.switches.saturating_add(1);
+                Some(Switch {
+                    pc: item.pc(),
+                    from,
+                    to,
+                })
+            }
+        }
+    }
+
+    /// Filter [`Item`]s, retaining only those attributed to the given context
+    ///
+    /// Items are [processed][Self::process_item] as they are yielded by the
+    /// returned iterator. [`Kind::Context`] items switching to the process are
+    /// retained.
+    pub fn filter<'a, I: Info + 'a>(
+        &'a mut self,
+        items: impl IntoIterator<Item = Item<I>> + 'a,
+        context: u64,
+    ) -> impl Iterator<Item = Item<I>> + 'a {
+        items.into_iter().filter(move |item| {
+            self.process_item(item);
+            self.current == Some(context)
+        })
+    }
+
+    /// Retrieve the (masked) context of the current [`Process`], if any
+    pub fn current_context(&self) -> Option<u64> {
+        self.current
+    }
+
+    /// Retrieve the current [`Process`], if any
+    pub fn current(&self) -> Option<&Process> {
+        self.current
+            .and_then(|c| self.processes.get(&c))
+            .map(|(p, _)| p)
+    }
+
+    /// Retrieve the [`Process`] registered for the given (masked) context
+    pub fn process(&self, context: u64) -> Option<&Process> {
+        self.processes.get(&context).map(|(p, _)| p)
+    }
+
+    /// Retrieve the [`Stats`] of the process registered for the given context
+    pub fn stats(&self, context: u64) -> Option<&Stats> {
+        self.processes.get(&context).map(|(_, s)| s)
+    }
+
+    /// Retrieve the [`Stats`] for [`Item`]s not attributed to any [`Process`]
+    pub fn unknown(&self) -> &Stats {
+        &self.unknown
+    }
+
+    /// Iterate over all registered [`Process`]es and their [`Stats`]
+    ///
+    /// The iterator yields the processes in ascending order of their context.
+    pub fn iter(&self) -> impl Iterator<Item = (u64, &Process, &Stats)> + '_ {
+        self.processes.iter().map(|(c, (p, s))| (*c, p, s))
+    }
+
+    /// Retrieve the [`Stats`] of the current [`Process`] mutably
+    fn current_stats_mut(&mut self) -> &mut Stats {
+        match self.current.and_then(|c| self.processes.get_mut(&c)) {
+            Some((_, stats)) => stats,
+            None => &mut self.unknown,
+        }
+    }
+}
+
+impl Default for Attribution {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A process items may be attributed to
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Process {
+    /// Id of the process, e.g. its PID
+    pub id: u64,
+    /// Name of the process
+    pub name: String,
+}
+
+impl Process {
+    /// Create a new process with the given id and name
+    pub fn new(id: u64, name: impl Into<String>) -> Self {
+        Self {
+            id,
+            name: name.into(),
+        }
+    }
+}
+
+impl fmt::Display for Process {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({})", self.name, self.id)
+    }
+}
+
+/// Statistics of a single [`Process`]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// Number of instructions retired
+    pub instructions: u64,
+    /// Number of traps taken
+    pub traps: u64,
+    /// Number of times execution switched to the process
+    pub switches: u64,
+}
+
+/// Switch between [`Process`]es reported by an [`Attribution`]
+///
+/// Processes are identified by their (masked) context. [`None`] denotes that
+/// execution is not attributed to any process.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Switch {
+    /// PC of the first instruction executed after the switch
+    pub pc: u64,
+    /// Context of the process switched from
+    pub from: Option<u64>,
+    /// Context of the process switched to
+    pub to: Option<u64>,
+}
+
+impl fmt::Display for Switch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let context = |f: &mut fmt::Formatter<'_>, context| match context {
+            Some(c) => write!(f, "{c:#x}"),
+            None => write!(f, "unknown"),
+        };
+        write!(f, "switch from ")?;
+        context(f, self.from)?;
+        write!(f, " to ")?;
+        context(f, self.to)?;
+        write!(f, " at {:#x}", self.pc)
+    }
+}
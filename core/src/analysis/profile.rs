@@ -0,0 +1,170 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Per-address execution profiles
+//!
+//! This module provides a [`Histogram`] counting the number of times the
+//! instructions within an address range were retired. Counters are kept in a
+//! flat array with one slot per [`GRANULE`] bytes, starting at the base address
+//! of the range, e.g. the start of a program's text section. The resulting
+//! heatmap may be exported as [CSV][Histogram::csv] for quick analyses of
+//! where the execution time of a program goes.
+//!
+//! # Example
+//!
+//! ```
+//! use riscv_etrace_core::analysis::profile::Histogram;
+//! use riscv_etrace_core::instruction::{Instruction, Kind};
+//! use riscv_etrace_core::tracer::item::Item;
+//!
+//! let mut histogram = Histogram::new(0x80000000, [0u64; 16]);
+//! let insn = Instruction::from(Kind::c_nop);
+//! for pc in [0x80000000, 0x80000002, 0x80000000] {
+//!     histogram.process_item(&Item::new(pc, insn.into()));
+//! }
+//! assert_eq!(histogram.count(0x80000000), Some(2));
+//! assert_eq!(
+//!     histogram.csv().to_string(),
+//!     "address,count\n0x80000000,2\n0x80000002,1\n",
+//! );
+//! ```
+
+use core::fmt;
+
+use crate::instruction::info::Info;
+use crate::tracer::item::Item;
+
+/// Number of bytes covered by a single counter
+///
+/// This corresponds to the minimum alignment of instructions.
+pub const GRANULE: u64 = 2;
+
+/// Histogram of retired instructions per address
+///
+/// The counters are stored in a `C`, which may be an array, a slice or, with
+/// the `alloc` feature, a [`Vec`][alloc::vec::Vec].
+#[derive(Clone, Debug)]
+pub struct Histogram<C> {
+    base: u64,
+    counts: C,
+    outside: u64,
+}
+
+impl<C: AsRef<[u64]> + AsMut<[u64]>> Histogram<C> {
+    /// Create a new histogram for the range starting at the given `base`
+    ///
+    /// The range covers [`GRANULE`] bytes for each element in `counts`, which
+    /// are expected to be initialized with zero.
+    pub fn new(base: u64, counts: C) -> Self {
+        Self {
+            base,
+            counts,
+            outside: 0,
+        }
+    }
+
+    /// Record a reconstructed [`Item`]
+    ///
+    /// Only [`Item`]s signalling a retired instruction are counted.
+    pub fn process_item<I: Info>(&mut self, item: &Item<I>) {
+        if item.instruction().is_some() {
+            self.add(item.pc(), 1);
+        }
+    }
+
+    /// Add the given value to the counter for the given address
+    ///
+    /// This allows weighting addresses, e.g. by cycles spent, rather than
+    /// counting retired instructions. Values for addresses outside the range
+    /// are accumulated in a separate [counter][Self::outside].
+    pub fn add(&mut self, pc: u64, value: u64) {
+        match self.index(pc).and_then(|i| self.counts.as_mut().get_mut(i)) {
+            Some(count) => *count = count.saturating_add(value),
+            None => self.outside = self.outside.saturating_add(value),
+        }
+    }
+
+    /// Retrieve the count for the given address
+    ///
+    /// Returns [`None`] if the address is outside the range covered.
+    pub fn count(&self, pc: u64) -> Option<u64> {
+        self.index(pc)
+            .and_then(|i| self.counts.as_ref().get(i))
+            .copied()
+    }
+
+    /// Retrieve the accumulated count for addresses outside the range
+    pub fn outside(&self) -> u64 {
+        self.outside
+    }
+
+    /// Retrieve the sum of all counts, including those outside the range
+    pub fn total(&self) -> u64 {
+        self.counts
+            .as_ref()
+            .iter()
+            .fold(self.outside, |a, c| a.saturating_add(*c))
+    }
+
+    /// Iterate over all addresses with a non-zero count
+    ///
+    /// The iterator yields pairs of addresses and counts in ascending order of
+    /// addresses.
+    pub fn iter(&self) -> impl Iterator<Item = (u64, u64)> + '_ {
+        self.counts
+            .as_ref()
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| **c != 0)
+            .map(|(i, c)| (self.base.wrapping_add(i as u64 * GRANULE), *c))
+    }
+
+    /// Retrieve the address with the highest count
+    pub fn hottest(&self) -> Option<(u64, u64)> {
+        self.iter().reduce(|a, b| if b.1 > a.1 { b } else { a })
+    }
+
+    /// Reset all counters to zero
+    pub fn clear(&mut self) {
+        self.counts.as_mut().fill(0);
+        self.outside = 0;
+    }
+
+    /// Create a [`fmt::Display`] rendering this histogram as CSV
+    ///
+    /// The CSV consists of a header followed by one line per address with a
+    /// non-zero count. The count for addresses outside the range is not
+    /// included.
+    pub fn csv(&self) -> Csv<'_, C> {
+        Csv(self)
+    }
+
+    /// Retrieve the index of the counter for the given address
+    fn index(&self, pc: u64) -> Option<usize> {
+        pc.checked_sub(self.base)
+            .and_then(|o| usize::try_from(o / GRANULE).ok())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Histogram<alloc::vec::Vec<u64>> {
+    /// Create a new histogram covering `size` bytes starting at `base`
+    pub fn with_size(base: u64, size: u64) -> Self {
+        let len = usize::try_from(size.div_ceil(GRANULE)).unwrap_or(usize::MAX);
+        Self::new(base, alloc::vec![0; len])
+    }
+}
+
+/// CSV rendering of a [`Histogram`]
+///
+/// Created via [`Histogram::csv`].
+#[derive(Copy, Clone, Debug)]
+pub struct Csv<'h, C>(&'h Histogram<C>);
+
+impl<C: AsRef<[u64]> + AsMut<[u64]>> fmt::Display for Csv<'_, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "address,count")?;
+        self.0
+            .iter()
+            .try_for_each(|(pc, count)| writeln!(f, "{pc:#x},{count}"))
+    }
+}
@@ -0,0 +1,192 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Detection of execution outside of expected regions
+//!
+//! Executing instructions outside the regions expected to hold code, e.g. the
+//! executable segments of an ELF file, hints at a hijacked control flow or a
+//! misconfiguration such as a wrong load address. This module provides a
+//! [`Checker`] which flags such execution in a stream of [`Item`]s.
+//!
+//! Consecutive instructions of a hart executed outside the expected regions
+//! form an excursion. Excursions are summarized by their [`Escape`], i.e. the
+//! last instruction within and the first instruction outside the regions.
+//!
+//! # Example
+//!
+//! ```
+//! use riscv_etrace_core::analysis::protection::{Checker, Escape};
+//! use riscv_etrace_core::instruction::Kind;
+//! use riscv_etrace_core::tracer::item::Item;
+//!
+//! let mut checker = Checker::new([0x80000000..0x80001000]);
+//! let items = [
+//!     Item::new(0x80000000, Kind::new_jalr(0, 10, 0).into()),
+//!     Item::new(0x10000, Kind::nop.into()),
+//!     Item::new(0x10004, Kind::nop.into()),
+//! ];
+//! items.iter().for_each(|i| checker.process_item(0, i));
+//!
+//! let escape = Escape { from: Some(0x80000000), to: 0x10000 };
+//! assert_eq!(checker.finding(&escape).map(|f| f.instructions), Some(2));
+//! assert_eq!(checker.summary().outside, 2);
+//! ```
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+
+use crate::instruction::info::Info;
+use crate::tracer::item::{Confidence, Item, Kind};
+
+/// Checker for execution outside of expected regions
+///
+/// [`Item`]s are fed via [`process_item`][Self::process_item] in the order in
+/// which they were generated. Items of different harts may be interleaved.
+/// Only instructions are checked. An [`Item`] with [`Confidence::Gap`] resets
+/// the hart's last instruction within the regions, as the control flow leading
+/// to it is unknown.
+#[derive(Clone, Debug, Default)]
+pub struct Checker {
+    regions: Vec<Range<u64>>,
+    harts: BTreeMap<u64, Hart>,
+    findings: BTreeMap<Escape, Finding>,
+    instructions: u64,
+}
+
+impl Checker {
+    /// Create a new checker for the given expected-executable regions
+    ///
+    /// Regions may overlap and need not be sorted.
+    pub fn new(regions: impl IntoIterator<Item = Range<u64>>) -> Self {
+        let mut sorted: Vec<_> = regions.into_iter().filter(|r| !r.is_empty()).collect();
+        sorted.sort_by_key(|r| r.start);
+
+        let mut regions: Vec<Range<u64>> = Vec::new();
+        for region in sorted {
+            match regions.last_mut() {
+                Some(last) if last.end >= region.start => last.end = last.end.max(region.end),
+                _ => regions.push(region),
+            }
+        }
+        Self {
+            regions,
+            ..Default::default()
+        }
+    }
+
+    /// Determine whether the given address lies within the expected regions
+    pub fn is_expected(&self, address: u64) -> bool {
+        let index = self.regions.partition_point(|r| r.end <= address);
+        self.regions
+            .get(index)
+            .is_some_and(|r| r.contains(&address))
+    }
+
+    /// Process a reconstructed [`Item`] of the given hart
+    pub fn process_item<I: Info>(&mut self, hart: u64, item: &Item<I>) {
+        let expected = self.is_expected(item.pc());
+        let state = self.harts.entry(hart).or_default();
+        if item.confidence() == Confidence::Gap {
+            *state = Default::default();
+        }
+        let Kind::Regular(_) = item.kind() else {
+            return;
+        };
+        self.instructions = self.instructions.saturating_add(1);
+
+        if expected {
+            state.excursion = None;
+            state.inside = Some(item.pc());
+            return;
+        }
+
+        let escape = state.excursion.unwrap_or(Escape {
+            from: state.inside,
+            to: item.pc(),
+        });
+        let finding = self.findings.entry(escape).or_default();
+        if state.excursion.replace(escape).is_none() {
+            finding.count = finding.count.saturating_add(1);
+        }
+        finding.instructions = finding.instructions.saturating_add(1);
+    }
+
+    /// Retrieve the [`Finding`] for the given [`Escape`], if any
+    pub fn finding(&self, escape: &Escape) -> Option<&Finding> {
+        self.findings.get(escape)
+    }
+
+    /// Iterate over all [`Escape`]s and their [`Finding`]s
+    pub fn findings(&self) -> impl Iterator<Item = (&Escape, &Finding)> + '_ {
+        self.findings.iter()
+    }
+
+    /// Determine whether no execution outside the expected regions was found
+    pub fn is_clean(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    /// Summarize the findings
+    pub fn summary(&self) -> Summary {
+        let (excursions, outside) = self.findings.values().fold((0u64, 0u64), |(e, o), f| {
+            (e.saturating_add(f.count), o.saturating_add(f.instructions))
+        });
+        Summary {
+            instructions: self.instructions,
+            outside,
+            excursions,
+            escapes: self.findings.len(),
+        }
+    }
+}
+
+/// Protection related state of a single hart
+#[derive(Copy, Clone, Debug, Default)]
+struct Hart {
+    /// PC of the last instruction within the expected regions
+    inside: Option<u64>,
+    /// The current excursion, if any
+    excursion: Option<Escape>,
+}
+
+/// Transition from expected to unexpected execution
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Escape {
+    /// PC of the last instruction within the expected regions, if known
+    pub from: Option<u64>,
+    /// PC of the first instruction outside the expected regions
+    pub to: u64,
+}
+
+/// Statistics of all excursions sharing an [`Escape`]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Finding {
+    /// Number of excursions
+    pub count: u64,
+    /// Number of instructions executed outside the expected regions
+    pub instructions: u64,
+}
+
+/// Summary of a [`Checker`]'s findings
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Summary {
+    /// Number of instructions checked
+    pub instructions: u64,
+    /// Number of instructions executed outside the expected regions
+    pub outside: u64,
+    /// Number of excursions
+    pub excursions: u64,
+    /// Number of distinct [`Escape`]s
+    pub escapes: usize,
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} of {} instructions outside expected regions in {} excursion(s) via {} escape(s)",
+            self.outside, self.instructions, self.excursions, self.escapes,
+        )
+    }
+}
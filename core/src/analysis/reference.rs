@@ -0,0 +1,122 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Comparison against reference traces
+//!
+//! This module provides a [`Comparator`] checking the PCs of the [`Item`]s
+//! generated by a [`Tracer`] against a reference, e.g. an instruction log
+//! produced by a simulator executing the same program.
+//!
+//! [`Tracer`]: crate::tracer::Tracer
+
+use core::fmt;
+
+use crate::instruction::info::Info;
+use crate::tracer::item::Item;
+
+/// Comparator for [`Item`]s of a single hart against reference PCs
+///
+/// Only [`Item`]s carrying an [`Instruction`] are compared. Traps and context
+/// changes are ignored.
+///
+/// # Example
+///
+/// ```
+/// use riscv_etrace_core::analysis::reference::{Comparator, Mismatch};
+/// use riscv_etrace_core::instruction::COMPRESSED;
+/// use riscv_etrace_core::tracer::item::Item;
+///
+/// let mut comparator = Comparator::new([0x1000, 0x1002, 0x1004]);
+/// let items: [Item; 2] = [
+///     Item::new(0x1000, COMPRESSED.into()),
+///     Item::new(0x1002, COMPRESSED.into()),
+/// ];
+/// for item in &items {
+///     comparator.compare(item).unwrap();
+/// }
+/// assert_eq!(
+///     comparator.finish(),
+///     Err(Mismatch { index: 2, expected: Some(0x1004), actual: None }),
+/// );
+/// ```
+///
+/// [`Instruction`]: crate::instruction::Instruction
+#[derive(Clone, Debug)]
+pub struct Comparator<R> {
+    reference: R,
+    index: u64,
+}
+
+impl<R: Iterator<Item = u64>> Comparator<R> {
+    /// Create a new comparator for the given reference PCs
+    pub fn new(reference: impl IntoIterator<IntoIter = R>) -> Self {
+        Self {
+            reference: reference.into_iter(),
+            index: 0,
+        }
+    }
+
+    /// Compare a single [`Item`] against the next reference PC
+    pub fn compare<I: Info>(&mut self, item: &Item<I>) -> Result<(), Mismatch> {
+        if item.instruction().is_none() {
+            return Ok(());
+        }
+
+        let index = self.index;
+        self.index += 1;
+        let expected = self.reference.next();
+        if expected == Some(item.pc()) {
+            Ok(())
+        } else {
+            Err(Mismatch {
+                index,
+                expected,
+                actual: Some(item.pc()),
+            })
+        }
+    }
+
+    /// Retrieve the number of [`Item`]s compared so far
+    pub fn compared(&self) -> u64 {
+        self.index
+    }
+
+    /// Finish the comparison
+    ///
+    /// Returns the number of [`Item`]s compared or a [`Mismatch`] if the
+    /// reference contains PCs for which no [`Item`] was compared.
+    pub fn finish(mut self) -> Result<u64, Mismatch> {
+        match self.reference.next() {
+            Some(expected) => Err(Mismatch {
+                index: self.index,
+                expected: Some(expected),
+                actual: None,
+            }),
+            None => Ok(self.index),
+        }
+    }
+}
+
+/// Mismatch between an [`Item`] and the reference
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Mismatch {
+    /// Index of the instruction at which the mismatch occured
+    pub index: u64,
+    /// The PC expected according to the reference, if any
+    pub expected: Option<u64>,
+    /// The PC of the [`Item`], if any
+    pub actual: Option<u64>,
+}
+
+impl core::error::Error for Mismatch {}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let index = self.index;
+        match (self.expected, self.actual) {
+            (Some(e), Some(a)) => write!(f, "instruction {index}: expected {e:#x}, traced {a:#x}"),
+            (Some(e), None) => write!(f, "instruction {index}: expected {e:#x}, traced nothing"),
+            (None, Some(a)) => write!(f, "instruction {index}: traced {a:#x} past reference"),
+            (None, None) => write!(f, "instruction {index}: no mismatch"),
+        }
+    }
+}
@@ -0,0 +1,236 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Replay of register values
+//!
+//! The targets of uninferable jumps, such as a `jalr` following an `auipc`,
+//! are reported in the trace. However, many of them are determined by short
+//! chains of instructions computing constants. This module provides a
+//! [`Replay`] which follows [`Item`]s carrying full [`riscv_isa`] information
+//! and tracks a best-effort sketch of the integer registers' values. This
+//! allows resolving the [target][Replay::target] of uninferable jumps locally
+//! and flagging [`Mismatch`]es between the values computed and the addresses
+//! reported by the trace, which usually hint at misconfigured binaries.
+//!
+//! Only the results of a small set of instructions, such as `lui`, `auipc`,
+//! `addi` and jumps, are computed. The destination of any other instruction,
+//! e.g. a load, is considered unknown.
+//!
+//! # Example
+//!
+//! ```
+//! use riscv_etrace_core::analysis::replay::Replay;
+//! use riscv_etrace_core::instruction::decode::{Decode, MakeDecode};
+//! use riscv_etrace_core::instruction::{Instruction, Size, base, isa::Adapter};
+//! use riscv_etrace_core::tracer::item::Item;
+//!
+//! let target = riscv_isa::Target::rv64i_full();
+//! let insn = |bits| Instruction::<Adapter> {
+//!     size: Size::Normal,
+//!     info: target.decode_32(bits),
+//! };
+//!
+//! let mut replay = Replay::new(base::Set::Rv64I);
+//! // auipc t0, 0x1
+//! replay.process_item(&Item::new(0x1000, insn(0x00001297).into())).unwrap();
+//! // addi t0, t0, 0x10
+//! replay.process_item(&Item::new(0x1004, insn(0x01028293).into())).unwrap();
+//! assert_eq!(replay.register(5), Some(0x2010));
+//! // jalr ra, 0(t0)
+//! let jalr = insn(0x000280e7);
+//! assert_eq!(replay.target(&jalr.info), Some(0x2010));
+//! replay.process_item(&Item::new(0x1008, jalr.into())).unwrap();
+//! assert_eq!(replay.register(1), Some(0x100c));
+//! assert!(replay.process_item(&Item::new(0x2020, insn(0x00000013).into())).is_err());
+//! ```
+
+use core::fmt;
+
+use riscv_isa::Instruction as Isa;
+
+use crate::instruction::base;
+use crate::instruction::info::Info;
+use crate::instruction::isa::Adapter;
+use crate::tracer::item::{Confidence, Item};
+
+/// Best-effort replay of integer register values for a single hart
+#[derive(Clone, Debug)]
+pub struct Replay {
+    registers: [Option<u64>; 32],
+    base: base::Set,
+    pending: Option<(u64, u64)>,
+    resolved: u64,
+    unresolved: u64,
+}
+
+impl Replay {
+    /// Create a new replay for the given base instruction set
+    ///
+    /// The values of all registers other than `x0` are initially unknown.
+    pub fn new(base: base::Set) -> Self {
+        Self {
+            registers: [None; 32],
+            base,
+            pending: None,
+            resolved: 0,
+            unresolved: 0,
+        }
+    }
+
+    /// Process a single [`Item`]
+    ///
+    /// If the preceeding [`Item`] was an uninferable jump with a known target,
+    /// that target is compared against the PC of this [`Item`]. A [`Mismatch`]
+    /// is returned if they differ. Traps cancel this comparison. All register
+    /// values are forgotten on [`Item`]s with a [`Confidence::Gap`].
+    pub fn process_item<I>(&mut self, item: &Item<I>) -> Result<(), Mismatch>
+    where
+        I: Info + Clone + Into<Adapter>,
+    {
+        if item.confidence() == Confidence::Gap {
+            self.reset();
+        }
+        let Some(insn) = item.instruction() else {
+            if item.trap().is_some() {
+                self.pending = None;
+            }
+            return Ok(());
+        };
+
+        let pc = item.pc();
+        let pending = self.pending.take();
+
+        let info: Adapter = insn.info.clone().into();
+        let link = pc.wrapping_add(insn.size.into());
+        if info.is_uninferable_jump() {
+            match self.target(&info) {
+                Some(target) => self.pending = Some((pc, target)),
+                None => self.unresolved += 1,
+            }
+        }
+        if let Some(rd) = info.rd() {
+            let value = self.evaluate(info.inner(), pc, link);
+            self.set(rd, value);
+        }
+
+        match pending {
+            Some((jump, expected)) if expected != pc => Err(Mismatch {
+                pc: jump,
+                expected,
+                actual: pc,
+            }),
+            Some(_) => {
+                self.resolved += 1;
+                Ok(())
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Determine the target of the given uninferable jump
+    ///
+    /// Returns the target based on the current register values or [`None`] if
+    /// the given instruction is not an uninferable jump or the value of its
+    /// source register is not known.
+    pub fn target(&self, insn: &Adapter) -> Option<u64> {
+        let (reg, offset) = insn.uninferable_jump_target()?;
+        let base = self.register(reg)?;
+        Some(self.truncate(base.wrapping_add_signed(offset.into())) & !1)
+    }
+
+    /// Retrieve the value of the given register, if known
+    pub fn register(&self, reg: u32) -> Option<u64> {
+        match reg {
+            0 => Some(0),
+            r => self.registers.get(r as usize).copied().flatten(),
+        }
+    }
+
+    /// Retrieve the number of jumps whose target was resolved correctly
+    pub fn resolved(&self) -> u64 {
+        self.resolved
+    }
+
+    /// Retrieve the number of uninferable jumps whose target was not resolved
+    pub fn unresolved(&self) -> u64 {
+        self.unresolved
+    }
+
+    /// Forget all register values
+    pub fn reset(&mut self) {
+        self.registers = [None; 32];
+        self.pending = None;
+    }
+
+    /// Set the value of a register
+    fn set(&mut self, reg: u32, value: Option<u64>) {
+        let value = value.map(|v| self.base_value(v));
+        if let Some(r) = self.registers.get_mut(reg as usize).filter(|_| reg != 0) {
+            *r = value;
+        }
+    }
+
+    /// Compute the value written to the destination register of `insn`
+    fn evaluate(&self, insn: &Isa, pc: u64, link: u64) -> Option<u64> {
+        let reg = |r: &u32| self.register(*r);
+        match insn {
+            Isa::LUI { .. } | Isa::AUIPC { .. } => insn.upper_immediate(pc).map(|(_, v)| v),
+            Isa::JAL { .. } | Isa::JALR { .. } => Some(link),
+            Isa::ADDI { rs1, imm, .. } => reg(rs1).map(|v| v.wrapping_add_signed((*imm).into())),
+            Isa::ADDIW { rs1, imm, .. } => {
+                reg(rs1).map(|v| sign_extend_word(v.wrapping_add_signed((*imm).into())))
+            }
+            Isa::XORI { rs1, imm, .. } => reg(rs1).map(|v| v ^ (i64::from(*imm) as u64)),
+            Isa::ORI { rs1, imm, .. } => reg(rs1).map(|v| v | (i64::from(*imm) as u64)),
+            Isa::ANDI { rs1, imm, .. } => reg(rs1).map(|v| v & (i64::from(*imm) as u64)),
+            Isa::SLLI { rs1, shamt, .. } => reg(rs1).map(|v| v << (shamt & 0x3f)),
+            Isa::SRLI { rs1, shamt, .. } => reg(rs1).map(|v| self.truncate(v) >> (shamt & 0x3f)),
+            Isa::ADD { rs1, rs2, .. } => Some(reg(rs1)?.wrapping_add(reg(rs2)?)),
+            Isa::SUB { rs1, rs2, .. } => Some(reg(rs1)?.wrapping_sub(reg(rs2)?)),
+            _ => None,
+        }
+    }
+
+    /// Truncate a value to the width of registers, zero-extending it
+    fn truncate(&self, value: u64) -> u64 {
+        match self.base {
+            base::Set::Rv32I => value & 0xffff_ffff,
+            base::Set::Rv64I => value,
+        }
+    }
+
+    /// Bring a value into the canonical form for the base instruction set
+    fn base_value(&self, value: u64) -> u64 {
+        match self.base {
+            base::Set::Rv32I => sign_extend_word(value),
+            base::Set::Rv64I => value,
+        }
+    }
+}
+
+/// Sign-extend the lower 32 bits of a value
+fn sign_extend_word(value: u64) -> u64 {
+    value as i32 as u64
+}
+
+/// Mismatch between a computed jump target and the traced one
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Mismatch {
+    /// Address of the uninferable jump
+    pub pc: u64,
+    /// The target computed from the replayed register values
+    pub expected: u64,
+    /// The target reported by the trace
+    pub actual: u64,
+}
+
+impl core::error::Error for Mismatch {}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "jump at {:#x}: computed target {:#x}, traced {:#x}",
+            self.pc, self.expected, self.actual
+        )
+    }
+}
@@ -0,0 +1,756 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+
+use crate::instruction::{Instruction, Kind};
+use crate::tracer::item::{self, Item};
+use crate::types::{Context, trap};
+
+use super::*;
+
+fn call_and_recursion() -> [Item; 10] {
+    [
+        Item::new(0x10, Kind::new_jal(1, 0x10).into()),
+        // Invocation of function at 0x20
+        Item::new(0x20, Kind::new_c_beqz(10, 0x8).into()),
+        Item::new(0x22, Kind::new_jal(1, -2).into()),
+        // Recursive invocation
+        Item::new(0x20, Kind::new_c_beqz(10, 0x8).into()),
+        Item::new(0x28, Kind::new_c_jr(1).into()),
+        Item::new(0x26, Kind::new_c_jr(1).into()),
+        // Back in the caller
+        Item::new(0x14, Kind::new_jal(1, 0xc).into()),
+        // Second invocation, which is interrupted by a trap
+        Item::new(0x20, Kind::nop.into()),
+        Item::new(
+            0x20,
+            trap::Info {
+                ecause: 7,
+                tval: None,
+            }
+            .into(),
+        ),
+        Item::new(0x100, Kind::nop.into()),
+    ]
+}
+
+#[test]
+fn invocation_recursion() {
+    let mut invocations =
+        invocation::Invocations::new(call_and_recursion().into_iter(), 0x20..0x2a);
+
+    let mut invocation = invocations.next_invocation().expect("No invocation");
+    let expected = [
+        (0x20, true),
+        (0x22, true),
+        (0x20, false),
+        (0x28, false),
+        (0x26, true),
+    ];
+    expected.into_iter().for_each(|(pc, own)| {
+        assert_eq!(invocation.next().map(|i| i.pc()), Some(pc));
+        assert_eq!(invocation.is_own(), own);
+    });
+    assert_eq!(invocation.next(), None);
+    assert!(invocation.is_complete());
+    assert!(!invocation.is_tail_return());
+    assert_eq!(invocation.depth(), 0);
+
+    let mut invocation = invocations.next_invocation().expect("No invocation");
+    assert_eq!(invocation.by_ref().count(), 3);
+    assert!(!invocation.is_complete());
+
+    assert!(invocations.next_invocation().is_none());
+}
+
+#[test]
+fn invocation_tail_call() {
+    let items = [
+        Item::new(0x10, Kind::new_jal(1, 0x10).into()),
+        Item::new(0x20, Kind::new_jal(0, 0x20).into()),
+        Item::new(0x40, Kind::new_c_jr(1).into()),
+        Item::new(0x14, Kind::nop.into()),
+    ];
+    let mut invocations = invocation::Invocations::new(items.into_iter(), 0x20..0x30);
+
+    let mut invocation = invocations.next_invocation().expect("No invocation");
+    assert_eq!(invocation.next().map(|i| i.pc()), Some(0x20));
+    assert!(invocation.is_own());
+    assert_eq!(invocation.next().map(|i| i.pc()), Some(0x40));
+    assert!(!invocation.is_own());
+    assert_eq!(invocation.next(), None);
+    assert!(invocation.is_complete());
+    assert!(invocation.is_tail_return());
+}
+
+#[test]
+fn invocation_skip_remaining() {
+    let mut invocations =
+        invocation::Invocations::new(call_and_recursion().into_iter(), 0x20..0x2a);
+
+    let mut invocation = invocations.next_invocation().expect("No invocation");
+    assert_eq!(invocation.next().map(|i| i.pc()), Some(0x20));
+    assert_eq!(invocation.next().map(|i| i.pc()), Some(0x22));
+    assert_eq!(invocation.depth(), 1);
+
+    // The recursive invocation must not be reported separately
+    let mut invocation = invocations.next_invocation().expect("No invocation");
+    assert_eq!(invocation.next(), Some(Item::new(0x20, Kind::nop.into())));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn cfg_split_block() {
+    use crate::binary::{self, Binary};
+    use crate::instruction::UNCOMPRESSED;
+
+    let mut binary = binary::from_sorted_map([
+        (0x10, UNCOMPRESSED),
+        (0x14, UNCOMPRESSED),
+        (0x18, Kind::new_bne(10, 11, -4).into()),
+        (0x1c, Kind::mret.into()),
+    ])
+    .expect("Could not construct binary");
+
+    let mut graph = cfg::Graph::default();
+    [0x10, 0x14, 0x18, 0x14, 0x18, 0x1c]
+        .into_iter()
+        .for_each(|pc| {
+            let insn = binary.get_insn(pc).expect("Could not fetch instruction");
+            graph.process_item(&mut binary, &Item::new(pc, insn.into()));
+        });
+
+    let blocks = graph.blocks().map(|(s, b)| (s, b.end(), b.count()));
+    assert!(blocks.eq([(0x10, 0x14, 1), (0x14, 0x1c, 2), (0x1c, 0x20, 1)]));
+    let edges = graph.edges();
+    assert!(edges.eq([((0x10, 0x14), 1), ((0x14, 0x14), 1), ((0x14, 0x1c), 1)]));
+
+    let dot = alloc::format!("{}", graph.dot());
+    assert_eq!(
+        dot,
+        "digraph cfg {\n    node [shape=box];\n    \
+        b10 [label=\"0x10..0x14\\n1\"];\n    \
+        b14 [label=\"0x14..0x1c\\n2\"];\n    \
+        b1c [label=\"0x1c..0x20\\n1\"];\n    \
+        b10 -> b14 [label=\"1\"];\n    \
+        b14 -> b14 [label=\"1\"];\n    \
+        b14 -> b1c [label=\"1\"];\n}\n",
+    );
+}
+
+#[test]
+fn gap_disabled_and_filtered() {
+    use crate::packet::payload::{AddressInfo, InstructionTrace};
+    use crate::packet::sync;
+
+    let start: InstructionTrace = sync::Start {
+        branch: false,
+        ctx: Default::default(),
+        address: 0x80000000,
+    }
+    .into();
+    let address: InstructionTrace = AddressInfo {
+        address: 0x10,
+        notify: false,
+        updiscon: false,
+        irdepth: None,
+    }
+    .into();
+    let filtered: InstructionTrace = sync::Support {
+        ienable: true,
+        qual_status: sync::QualStatus::EndedNtr,
+        ..Default::default()
+    }
+    .into();
+    let disabled: InstructionTrace = sync::Support::default().into();
+
+    let mut detector = gap::Detector::default();
+    assert_eq!(detector.process_payload(Some(10), &start), None);
+    assert_eq!(detector.process_payload(Some(20), &filtered), None);
+    assert!(detector.in_gap());
+    // Packets without timestamp or subsequent support packets do not alter
+    // the gap
+    assert_eq!(detector.process_payload(None, &disabled), None);
+    assert_eq!(detector.process_payload(Some(25), &address), None);
+    assert_eq!(
+        detector.process_payload(Some(30), &start),
+        Some(gap::Gap {
+            start: Some(20),
+            end: Some(30),
+            cause: gap::Cause::Filtered,
+        }),
+    );
+    assert!(!detector.in_gap());
+
+    assert_eq!(detector.process_payload(None, &disabled), None);
+    let gap = detector.finish(None).expect("No open gap");
+    assert_eq!(gap.cause, gap::Cause::Disabled);
+    assert_eq!(gap.start, Some(30));
+    assert_eq!(gap.duration(), None);
+}
+
+#[test]
+fn history_dump_roundtrip() {
+    use crate::types::{Context, Privilege};
+
+    let items: [Item; 5] = [
+        Item::new(0x80000010, Kind::new_c_j(0, -0x10).into()),
+        Item::new(0x80000000, Kind::new_jalr(0, 1, 0).into()),
+        Item::new(
+            0x80000020,
+            trap::Info {
+                ecause: 3,
+                tval: None,
+            }
+            .into(),
+        ),
+        Item::new(
+            0x80000100,
+            trap::Info {
+                ecause: 2,
+                tval: Some(0xdeadbeef),
+            }
+            .into(),
+        ),
+        Item::new(
+            0x80000200,
+            Context {
+                privilege: Privilege::Machine,
+                context: 42,
+            }
+            .into(),
+        ),
+    ];
+
+    let mut harts: history::Harts<_, 4, 2> = Default::default();
+    items
+        .iter()
+        .for_each(|i| harts.push(0, *i).expect("Could not push"));
+    assert_eq!(harts.push(3, items[0]), Ok(()));
+    assert_eq!(harts.push(5, items[0]), Err(items[0]));
+    assert_eq!(harts.get(0).map(history::History::total), Some(5));
+
+    let mut buffer = [0u8; 64];
+    let len = harts.dump(&mut buffer).expect("Could not dump");
+    assert_eq!(
+        harts.dump(&mut buffer[..len - 1]),
+        Err(history::Error::BufferTooSmall)
+    );
+    let expected = items[1..]
+        .iter()
+        .chain(&items[..1])
+        .map(|i| match i.kind() {
+            item::Kind::Regular(insn) => {
+                let insn = Instruction {
+                    size: insn.size,
+                    info: None,
+                };
+                Item::new(i.pc(), insn.into())
+            }
+            _ => *i,
+        })
+        .zip([0, 0, 0, 0, 3])
+        .map(|(i, h)| Ok((h, i)));
+    assert!(history::read_dump(&buffer[..len]).eq(expected));
+
+    assert_eq!(
+        history::read_dump(&buffer[..len - 1]).last(),
+        Some(Err(history::Error::InvalidData)),
+    );
+}
+
+fn simulate(options: efficiency::Options) -> efficiency::Stats {
+    let params = Default::default();
+    let mut simulator: efficiency::Simulator =
+        efficiency::Simulator::new(&params, options).expect("Could not create simulator");
+    call_and_recursion()
+        .iter()
+        .try_for_each(|i| simulator.process_item(i))
+        .expect("Could not process item");
+    simulator.finish().expect("Could not finish simulation")
+}
+
+#[test]
+fn efficiency_address_mode() {
+    use crate::config::AddressMode;
+    use efficiency::Format;
+
+    let delta = simulate(Default::default());
+    let full = simulate(efficiency::Options {
+        address_mode: AddressMode::Full,
+        ..Default::default()
+    });
+    assert_eq!(delta.instructions(), 9);
+    assert_eq!(full.instructions(), 9);
+    assert_eq!(delta.packets(), full.packets());
+    for format in Format::ALL {
+        assert_eq!(
+            delta.format(format).packets,
+            full.format(format).packets,
+            "{format:?}"
+        );
+    }
+    assert!(full.bits() > delta.bits());
+    assert!(full.bits_per_instruction() > delta.bits_per_instruction());
+}
+
+#[test]
+fn efficiency_implicit_return() {
+    let options = efficiency::Options {
+        implicit_return: true,
+        ..Default::default()
+    };
+    assert!(efficiency::Simulator::<Option<Kind>>::new(&Default::default(), options).is_err());
+}
+
+#[test]
+fn address_diagnostics() {
+    use crate::binary;
+    use crate::config::{AddressMode, Parameters};
+    use crate::instruction::COMPRESSED;
+    use crate::packet::{payload, sync};
+
+    let mut code = binary::from_sorted_map([
+        (0x80000000, COMPRESSED),
+        (0x80000010, COMPRESSED),
+        (0x80000100, COMPRESSED),
+    ]);
+    let start: payload::InstructionTrace = sync::Start {
+        branch: true,
+        ctx: Default::default(),
+        address: 0x80000000,
+    }
+    .into();
+    let payloads: [payload::InstructionTrace; 4] = [0x80000010, 0x80000100, 0x80000000, 0x80000100]
+        .map(|address| {
+            payload::AddressInfo {
+                address,
+                notify: false,
+                updiscon: false,
+                irdepth: None,
+            }
+            .into()
+        });
+
+    let params = Parameters::default();
+    let mut full = address::Diagnostics::new(&params, AddressMode::Full).with_threshold(5);
+    let mut delta = address::Diagnostics::new(&params, AddressMode::Delta).with_threshold(5);
+    for payload in core::iter::once(&start).chain(payloads.iter()) {
+        full.process_payload(&mut code, payload);
+        delta.process_payload(&mut code, payload);
+    }
+    assert_eq!(full.misses(), 0);
+    assert_eq!(full.diagnosis(), None);
+    assert_eq!(delta.observations(), 5);
+    assert_eq!(delta.misses(), 4);
+    assert_eq!(
+        delta.diagnosis(),
+        Some(address::Diagnosis::AddressMode {
+            configured: AddressMode::Delta,
+            suggested: AddressMode::Full,
+        })
+    );
+}
+
+#[test]
+fn address_misalignment() {
+    use crate::binary::basic::Empty;
+    use crate::config::{AddressMode, Parameters};
+    use crate::instruction::Kind;
+    use crate::packet::{payload, sync};
+
+    let params = Parameters {
+        iaddress_lsb_p: 0,
+        ..Default::default()
+    };
+    let mut diagnostics = address::Diagnostics::new(&params, AddressMode::Full)
+        .with_threshold(4)
+        .with_alignment(4);
+    // The encoder actually shifts addresses by two bits
+    for address in [0x80000004u64, 0x8000000c, 0x80000014, 0x80000104] {
+        let payload: payload::InstructionTrace = sync::Start {
+            branch: true,
+            ctx: Default::default(),
+            address: address >> 2,
+        }
+        .into();
+        diagnostics.process_payload::<_, Option<Kind>, _, _>(&mut Empty, &payload);
+    }
+    assert_eq!(diagnostics.misaligned(), 4);
+    assert_eq!(
+        diagnostics.diagnosis(),
+        Some(address::Diagnosis::Misaligned {
+            configured: 0,
+            suggested: Some(2),
+        })
+    );
+
+    let mut aligned = address::Diagnostics::new(&params, AddressMode::Full).with_threshold(1);
+    let payload: payload::InstructionTrace = sync::Start {
+        branch: true,
+        ctx: Default::default(),
+        address: 0x80000004,
+    }
+    .into();
+    aligned.process_payload::<_, Option<Kind>, _, _>(&mut Empty, &payload);
+    assert_eq!(aligned.misaligned(), 0);
+    assert_eq!(aligned.diagnosis(), None);
+}
+
+#[test]
+fn sync_intervals() {
+    use crate::packet::{payload, sync};
+
+    let start = |address| -> payload::InstructionTrace {
+        sync::Start {
+            branch: true,
+            ctx: Default::default(),
+            address,
+        }
+        .into()
+    };
+    let branch: payload::InstructionTrace = payload::Branch {
+        branch_map: Default::default(),
+        address: None,
+    }
+    .into();
+    let nop: Item = Item::new(0x10, Kind::c_nop.into());
+
+    let mut meter = interval::Meter::default();
+    assert_eq!(meter.process_payload(&branch, 3), None);
+    let leading = meter.process_payload(&start(0x10), 8).unwrap();
+    assert_eq!(leading.start, None);
+    assert_eq!(leading.packets, 1);
+    meter.process_item(&Item::<Option<Kind>>::new(0x10, Context::default().into()));
+    meter.process_item(&nop);
+    assert_eq!(meter.process_payload(&branch, 3), None);
+    meter.process_item(&nop);
+    meter.process_item(&nop);
+    let interval = meter.process_payload(&start(0x20), 8).unwrap();
+    assert_eq!(
+        interval,
+        interval::Interval {
+            start: Some(0x10),
+            packets: 2,
+            bytes: 11,
+            items: 4,
+            instructions: 3,
+        }
+    );
+    let last = meter.finish().unwrap();
+    assert_eq!(last.start, Some(0x20));
+    assert_eq!(last.bytes_per_instruction(), None);
+    assert_eq!(meter.finish(), None);
+}
+
+#[test]
+fn profile_histogram() {
+    let mut histogram = profile::Histogram::new(0x10, [0u64; 16]);
+    call_and_recursion()
+        .iter()
+        .for_each(|i| histogram.process_item(i));
+    histogram.add(0x100, 3);
+
+    assert_eq!(histogram.count(0x20), Some(3));
+    assert_eq!(histogram.count(0x22), Some(1));
+    assert_eq!(histogram.count(0x08), None);
+    assert_eq!(histogram.outside(), 4);
+    assert_eq!(histogram.hottest(), Some((0x20, 3)));
+    assert!(histogram.iter().eq([
+        (0x10, 1),
+        (0x14, 1),
+        (0x20, 3),
+        (0x22, 1),
+        (0x26, 1),
+        (0x28, 1)
+    ]));
+    assert_eq!(histogram.total(), 12);
+
+    histogram.clear();
+    assert_eq!(histogram.iter().next(), None);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn validate_reachable() {
+    use crate::binary::{self, error::NoInstruction};
+    use crate::instruction::UNCOMPRESSED;
+
+    let mut binary = binary::from_sorted_map([
+        (0x10, Kind::new_jal(1, 0x10).into()),
+        (0x14, Kind::new_c_jalr(10).into()),
+        (0x16, Kind::new_c_bnez(10, 0x2a).into()),
+        (0x18, UNCOMPRESSED),
+        (0x20, Kind::new_c_jr(1).into()),
+        (0x30, UNCOMPRESSED),
+    ])
+    .unwrap();
+
+    let report = validate::Validator::default().validate(&mut binary, [0x10]);
+    assert_eq!(report.visited, 6);
+    assert_eq!(
+        report.issues,
+        [
+            validate::Issue::Unknown(0x18),
+            validate::Issue::Fetch(0x40, NoInstruction),
+        ]
+    );
+    assert_eq!(report.unresolved, [0x14]);
+    assert!(!report.truncated);
+
+    let report = validate::Validator::default()
+        .with_max_instructions(2)
+        .validate(&mut binary, [0x10]);
+    assert_eq!(report.visited, 2);
+    assert!(report.truncated);
+}
+
+#[cfg(feature = "riscv-isa")]
+#[test]
+fn replay_rv32() {
+    use crate::instruction::decode::{Decode, MakeDecode};
+    use crate::instruction::{Size, base, isa::Adapter};
+
+    let target = riscv_isa::Target::rv32i_full();
+    let insn = |bits| Instruction::<Adapter> {
+        size: Size::Normal,
+        info: target.decode_32(bits),
+    };
+
+    let mut replay = replay::Replay::new(base::Set::Rv32I);
+    // lui a0, 0x80000
+    replay
+        .process_item(&Item::new(0x100, insn(0x80000537).into()))
+        .unwrap();
+    assert_eq!(replay.register(10), Some(0xffffffff80000000));
+    // addi a0, a0, 0x24
+    replay
+        .process_item(&Item::new(0x104, insn(0x02450513).into()))
+        .unwrap();
+    // jalr zero, 0(a0)
+    replay
+        .process_item(&Item::new(0x108, insn(0x00050067).into()))
+        .unwrap();
+    replay
+        .process_item(&Item::new(0x80000024, insn(0x00000013).into()))
+        .unwrap();
+    assert_eq!((replay.resolved(), replay.unresolved()), (1, 0));
+
+    // lw a0, 0(sp) is not replayed
+    let item = Item::new(0x80000028, insn(0x00012503).into());
+    replay.process_item(&item).unwrap();
+    assert_eq!(replay.register(10), None);
+
+    // lui a0, 0x80000 after a gap
+    replay
+        .process_item(&Item::new(0x100, insn(0x80000537).into()))
+        .unwrap();
+    let item = Item::new(0x104, insn(0x00000013).into()).with_confidence(item::Confidence::Gap);
+    replay.process_item(&item).unwrap();
+    assert_eq!(replay.register(10), None);
+}
+
+#[test]
+fn watchdog_leaves_loop() {
+    use watchdog::{Event, Watchdog};
+
+    let mut watchdog: Watchdog<1> = Watchdog::new(3);
+    let nop = Instruction::from(Kind::c_nop);
+    let events: [_; 4] =
+        core::array::from_fn(|_| watchdog.process_item(&Item::new(0x10, nop.into())));
+    let expected = Event::Loop {
+        start: 0x10,
+        end: 0x10,
+        items: 3,
+    };
+    assert_eq!(events, [None, None, Some(expected), None]);
+    assert!(watchdog.is_looping());
+
+    assert_eq!(watchdog.process_item(&Item::new(0x12, nop.into())), None);
+    assert!(!watchdog.is_looping());
+    assert_eq!(watchdog.pcs(), [0x12]);
+
+    let item = Item::new(0x12, nop.into()).with_confidence(item::Confidence::Gap);
+    assert_eq!(watchdog.process_item(&item), None);
+    assert_eq!(watchdog.check(1000), None);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn process_filter() {
+    use process::{Attribution, Process};
+
+    let mut attribution = Attribution::new().with_process(7, Process::new(1, "init"));
+    let nop = Instruction::from(Kind::c_nop);
+    let context = |context| {
+        Context {
+            context,
+            ..Default::default()
+        }
+        .into()
+    };
+    let items = [
+        Item::new(0x10, nop.into()),
+        Item::new(0x20, context(7)),
+        Item::new(0x20, nop.into()),
+        Item::new(0x30, context(9)),
+        Item::new(0x30, nop.into()),
+        Item::new(0x32, nop.into()),
+    ];
+    let retained: alloc::vec::Vec<_> = attribution.filter(items, 7).map(|i| i.pc()).collect();
+    assert_eq!(retained, [0x20, 0x20]);
+    assert_eq!(attribution.current(), None);
+    assert_eq!(attribution.stats(7).map(|s| s.instructions), Some(1));
+    assert_eq!(attribution.unknown().instructions, 3);
+    assert_eq!(attribution.unknown().switches, 1);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn handler_nested() {
+    use handler::{Coverage, Vector};
+    use trap::{Cause, Exception, Interrupt};
+
+    let timer = trap::Info {
+        ecause: 7,
+        tval: None,
+    };
+    let ecall = trap::Info {
+        ecause: 8,
+        tval: Some(0),
+    };
+    let items = [
+        Item::new(0x1000, ecall.into()),
+        Item::new(0x100, Kind::nop.into()),
+        Item::new(0x104, timer.into()),
+        Item::new(0x200, Kind::nop.into()),
+        Item::new(0x204, Kind::mret.into()),
+        Item::new(0x104, Kind::mret.into()),
+        Item::new(0x1004, ecall.into()),
+        Item::new(0x100, Kind::mret.into()),
+    ];
+    let mut coverage = Coverage::default();
+    items.iter().for_each(|i| coverage.process_item(i));
+    assert_eq!(coverage.depth(), 0);
+
+    let vector = Vector {
+        interrupt: false,
+        ecause: 8,
+        entry: 0x100,
+    };
+    let handler = coverage.handler(&vector).unwrap();
+    assert_eq!(
+        (handler.count, handler.min, handler.max),
+        (2, Some(1), Some(4))
+    );
+    let vector = Vector {
+        interrupt: true,
+        ecause: 7,
+        entry: 0x200,
+    };
+    assert_eq!(coverage.handler(&vector).and_then(|h| h.max), Some(2));
+
+    let causes = [
+        Cause::Exception(Exception::from(8)),
+        Cause::Exception(Exception::from(2)),
+        Cause::Interrupt(Interrupt::from(7)),
+    ];
+    assert!(
+        coverage
+            .missing(causes)
+            .eq([Cause::Exception(Exception::from(2))])
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn protection() {
+    use protection::{Checker, Escape, Finding};
+
+    let items = [
+        Item::new(0x100, Kind::new_jalr(0, 10, 0).into()),
+        Item::new(0x4000, Kind::nop.into()),
+        Item::new(0x4004, Kind::new_jal(0, -0x3f00).into()),
+        Item::new(0x104, Kind::new_jalr(0, 10, 0).into()),
+        Item::new(0x4000, Kind::nop.into()),
+        Item::new(0x4004, Kind::new_jal(0, -0x3f00).into()),
+        Item::new(0x8000, Kind::nop.into()).with_confidence(item::Confidence::Gap),
+        Item::new(0x1000, Kind::nop.into()),
+    ];
+    let mut checker = Checker::new([0x1000..0x2000, 0x100..0x200, 0x180..0x400]);
+    assert!(checker.is_expected(0x3ff));
+    assert!(!checker.is_expected(0x400));
+    items.iter().for_each(|i| checker.process_item(0, i));
+    assert!(!checker.is_clean());
+
+    let escape = Escape {
+        from: Some(0x100),
+        to: 0x4000,
+    };
+    let finding = Finding {
+        count: 1,
+        instructions: 2,
+    };
+    assert_eq!(checker.finding(&escape), Some(&finding));
+    let escape = Escape {
+        from: None,
+        to: 0x8000,
+    };
+    assert_eq!(checker.finding(&escape).map(|f| f.count), Some(1));
+
+    let summary = checker.summary();
+    assert_eq!(
+        (summary.instructions, summary.outside, summary.excursions),
+        (8, 5, 3)
+    );
+    assert_eq!(summary.escapes, 3);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn correlate_streams() {
+    use correlate::{Correlator, Error, Stream};
+
+    let item = |pc| Item::new(pc, Kind::c_nop.into());
+    let mut correlator = Correlator::new(2).with_context(2);
+    correlator.push_item(&item(0x10)).unwrap();
+    correlator.push_item(&item(0x12)).unwrap();
+    correlator.push_reference(0x10).unwrap();
+    assert_eq!(correlator.pending(Stream::Traced), 1);
+    correlator.push_reference(0x12).unwrap();
+    correlator.push_reference(0x14).unwrap();
+    correlator
+        .push_item(&Item::<Option<Kind>>::new(0x14, Context::default().into()))
+        .unwrap();
+    correlator.push_item(&item(0x14)).unwrap();
+    assert_eq!(correlator.compared(), 3);
+    assert_eq!(correlator.clone().finish(), Ok(3));
+
+    let mut overflowing = correlator.clone();
+    overflowing.push_reference(0x16).unwrap();
+    overflowing.push_reference(0x18).unwrap();
+    assert_eq!(
+        overflowing.push_reference(0x1a),
+        Err(Error::Overflow(Stream::Reference))
+    );
+    assert_eq!(
+        overflowing.push_item(&item(0x16)),
+        Err(Error::Overflow(Stream::Reference))
+    );
+
+    correlator.push_reference(0x16).unwrap();
+    correlator.push_reference(0x18).unwrap();
+    let Err(Error::Diverged(divergence)) = correlator.push_item(&item(0x20)) else {
+        panic!("Expected divergence");
+    };
+    assert_eq!(divergence.mismatch.index, 3);
+    assert_eq!(divergence.mismatch.expected, Some(0x16));
+    assert_eq!(divergence.mismatch.actual, Some(0x20));
+    assert_eq!(divergence.history, [0x12, 0x14]);
+    assert_eq!(divergence.traced, [0x20]);
+    assert_eq!(divergence.reference, [0x16, 0x18]);
+    assert_eq!(
+        alloc::string::ToString::to_string(&divergence),
+        "instruction 3: expected 0x16, traced 0x20; agreed: 0x12 0x14; traced: 0x20; reference: 0x16 0x18"
+    );
+    assert_eq!(correlator.finish(), Err(Error::Diverged(divergence)));
+}
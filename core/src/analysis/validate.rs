@@ -0,0 +1,177 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Static pre-validation of [`Binary`]s
+//!
+//! A [`Tracer`] needs to fetch and classify every instruction it reconstructs.
+//! Instructions it can not classify, e.g. because the [`Instruction`] decoder
+//! does not support a given extension, may only surface mid-trace as mismatches
+//! between the reconstructed and the reported control flow. This module
+//! provides a [`Validator`] which walks a [`Binary`] from one or more start
+//! addresses, following only the statically known control flow, and reports
+//! [`Issue`]s such as [unknown][Info::is_unknown] instructions in reachable
+//! code ahead of tracing.
+//!
+//! # Example
+//!
+//! ```
+//! use riscv_etrace_core::analysis::validate::{Issue, Validator};
+//! use riscv_etrace_core::binary;
+//! use riscv_etrace_core::instruction::{Kind, UNCOMPRESSED};
+//!
+//! let mut binary = binary::from_sorted_map([
+//!     (0x10, Kind::new_c_beqz(10, 0x8).into()),
+//!     (0x12, Kind::new_c_jr(1).into()),
+//!     (0x18, UNCOMPRESSED),
+//! ])
+//! .unwrap();
+//!
+//! let report = Validator::default().validate(&mut binary, [0x10]);
+//! assert_eq!(report.visited, 3);
+//! assert!(matches!(report.issues[..], [Issue::Unknown(0x18)]));
+//! ```
+//!
+//! [`Tracer`]: crate::tracer::Tracer
+//! [`Instruction`]: crate::instruction::Instruction
+
+use alloc::collections::BTreeSet;
+use alloc::vec::Vec;
+
+use crate::binary::Binary;
+use crate::instruction::info::Info;
+
+/// Walker validating the statically reachable code of a [`Binary`]
+///
+/// Starting from the given addresses, the validator follows the fall-through
+/// path of every [`Instruction`] as well as the targets of branches and
+/// inferable jumps. Function calls are assumed to return, i.e. the fall-through
+/// path of calls is followed. Traps are assumed to return to the instruction
+/// following an `ecall` or `ebreak`. Targets of uninferable jumps other than
+/// returns can not be determined statically and are reported as
+/// [unresolved][Report::unresolved].
+///
+/// [`Instruction`]: crate::instruction::Instruction
+#[derive(Copy, Clone, Debug)]
+pub struct Validator {
+    max_instructions: usize,
+}
+
+impl Validator {
+    /// Set the maximum number of instructions to visit
+    pub fn with_max_instructions(self, max_instructions: usize) -> Self {
+        Self { max_instructions }
+    }
+
+    /// Validate the code reachable from the given start addresses
+    pub fn validate<B, I>(
+        &self,
+        binary: &mut B,
+        start: impl IntoIterator<Item = u64>,
+    ) -> Report<B::Error>
+    where
+        B: Binary<I>,
+        I: Info,
+    {
+        let mut visited = BTreeSet::new();
+        let mut pending: Vec<u64> = start.into_iter().collect();
+        let mut report = Report {
+            visited: 0,
+            issues: Vec::new(),
+            unresolved: Vec::new(),
+            truncated: false,
+        };
+
+        while let Some(pc) = pending.pop() {
+            if !visited.insert(pc) {
+                continue;
+            }
+            if report.visited >= self.max_instructions {
+                report.truncated = true;
+                break;
+            }
+            report.visited += 1;
+
+            let insn = match binary.get_insn(pc) {
+                Ok(insn) => insn,
+                Err(err) => {
+                    report.issues.push(Issue::Fetch(pc, err));
+                    continue;
+                }
+            };
+            if insn.is_unknown() {
+                report.issues.push(Issue::Unknown(pc));
+                continue;
+            }
+
+            let next = pc.wrapping_add(insn.size.into());
+            if let Some(target) = insn.branch_target() {
+                pending.push(pc.wrapping_add_signed(target.into()));
+                pending.push(next);
+            } else if let Some(target) = insn.inferable_jump_target() {
+                pending.push(pc.wrapping_add_signed(target.into()));
+                if insn.is_call() {
+                    pending.push(next);
+                }
+            } else if insn.is_uninferable_jump() {
+                if insn.is_call() {
+                    pending.push(next);
+                }
+                if !insn.is_return() {
+                    report.unresolved.push(pc);
+                }
+            } else if !insn.is_return_from_trap() {
+                pending.push(next);
+            }
+        }
+
+        report.issues.sort_by_key(Issue::address);
+        report.unresolved.sort_unstable();
+        report
+    }
+}
+
+impl Default for Validator {
+    fn default() -> Self {
+        Self {
+            max_instructions: 1 << 20,
+        }
+    }
+}
+
+/// Result of a [`Validator`] run
+#[derive(Clone, Debug)]
+pub struct Report<E> {
+    /// Number of distinct addresses visited
+    pub visited: usize,
+    /// [`Issue`]s found, ordered by address
+    pub issues: Vec<Issue<E>>,
+    /// Addresses of uninferable jumps whose targets were not followed
+    pub unresolved: Vec<u64>,
+    /// Whether the walk was stopped due to the instruction limit
+    pub truncated: bool,
+}
+
+impl<E> Report<E> {
+    /// Check whether the validation did not find any [`Issue`]
+    pub fn is_clean(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// An issue found in reachable code
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Issue<E> {
+    /// The instruction at the given address is unknown
+    Unknown(u64),
+    /// The instruction at the given address could not be fetched
+    Fetch(u64, E),
+}
+
+impl<E> Issue<E> {
+    /// Retrieve the address of the affected instruction
+    pub fn address(&self) -> u64 {
+        match self {
+            Self::Unknown(address) => *address,
+            Self::Fetch(address, _) => *address,
+        }
+    }
+}
@@ -0,0 +1,215 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Detection of hangs in live traces
+//!
+//! This module provides a [`Watchdog`] for triaging hangs while tracing a
+//! system on the bench. It flags harts whose reconstructed execution remains
+//! within a small set of PCs, e.g. a polling or an infinite loop, as well as
+//! harts for which no trace data arrives for some time. Both conditions are
+//! reported as [`Event`]s.
+
+use core::fmt;
+
+use crate::instruction::info::Info;
+use crate::tracer::item::{Confidence, Item};
+
+/// Watchdog for a single hart
+///
+/// The watchdog tracks the distinct PCs of the [`Item`]s retiring an
+/// instruction. If the last `threshold` of those items all share at most `N`
+/// distinct PCs, the hart is considered to be stuck in a loop. Such a loop is
+/// reported only once, until execution leaves the set of PCs.
+///
+/// In addition, a timeout may be configured. The time of arrival of packets
+/// is communicated via [`process_packet`][Self::process_packet] and
+/// [`check`][Self::check] is expected to be called periodically. Times may be
+/// in any unit, e.g. timestamps or host clock ticks, as long as the same unit
+/// is used for the timeout.
+///
+/// If multiple harts are traced, one watchdog is needed per hart.
+///
+/// # Example
+///
+/// ```
+/// use riscv_etrace_core::analysis::watchdog::{Event, Watchdog};
+/// use riscv_etrace_core::instruction::{Instruction, Kind};
+/// use riscv_etrace_core::tracer::item::Item;
+///
+/// let mut watchdog: Watchdog<2> = Watchdog::new(6).with_timeout(100);
+/// let nop = Instruction::from(Kind::c_nop);
+/// let jump = Instruction::from(Kind::new_c_j(0, -2));
+/// let mut events = Vec::new();
+/// for _ in 0..4 {
+///     events.extend(watchdog.process_item(&Item::new(0x1000, nop.into())));
+///     events.extend(watchdog.process_item(&Item::new(0x1002, jump.into())));
+/// }
+/// assert_eq!(events, [Event::Loop { start: 0x1000, end: 0x1002, items: 6 }]);
+///
+/// assert_eq!(watchdog.process_packet(10), None);
+/// assert_eq!(watchdog.check(50), None);
+/// assert_eq!(watchdog.check(150), Some(Event::Stall { since: 10, now: 150 }));
+/// assert_eq!(watchdog.check(200), None);
+/// assert_eq!(watchdog.process_packet(220), Some(Event::Resumed { since: 10, now: 220 }));
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Watchdog<const N: usize = 8> {
+    pcs: [u64; N],
+    len: usize,
+    items: u64,
+    threshold: u64,
+    looping: bool,
+    timeout: Option<u64>,
+    last_packet: Option<u64>,
+    stalled: bool,
+}
+
+impl<const N: usize> Watchdog<N> {
+    /// Create a new watchdog flagging loops after `threshold` items
+    ///
+    /// No timeout is configured.
+    pub fn new(threshold: u64) -> Self {
+        Self {
+            pcs: [0; N],
+            len: 0,
+            items: 0,
+            threshold,
+            looping: false,
+            timeout: None,
+            last_packet: None,
+            stalled: false,
+        }
+    }
+
+    /// Set the time after which a hart without packets is considered stalled
+    pub fn with_timeout(self, timeout: u64) -> Self {
+        Self {
+            timeout: Some(timeout),
+            ..self
+        }
+    }
+
+    /// Process a reconstructed [`Item`]
+    ///
+    /// Returns an [`Event::Loop`] if the hart is found to be stuck in a loop.
+    /// [`Item`]s not retiring an instruction are ignored, except that an
+    /// [`Item`] with [`Confidence::Gap`] restarts the detection.
+    pub fn process_item<I: Info>(&mut self, item: &Item<I>) -> Option<Event> {
+        if item.confidence() == Confidence::Gap {
+            self.reset();
+        }
+        item.instruction()?;
+
+        let pc = item.pc();
+        if !self.pcs().contains(&pc) {
+            if self.len >= N {
+                self.reset();
+            }
+            if let Some(slot) = self.pcs.get_mut(self.len) {
+                *slot = pc;
+                self.len += 1;
+            }
+        }
+        self.items = self.items.saturating_add(1);
+
+        if self.looping || self.len == 0 || self.items < self.threshold {
+            return None;
+        }
+        self.looping = true;
+        let pcs = self.pcs();
+        Some(Event::Loop {
+            start: pcs.iter().copied().min().unwrap_or(pc),
+            end: pcs.iter().copied().max().unwrap_or(pc),
+            items: self.items,
+        })
+    }
+
+    /// Record the arrival of a packet at the given time
+    ///
+    /// Returns an [`Event::Resumed`] if the hart was previously reported as
+    /// stalled.
+    pub fn process_packet(&mut self, time: u64) -> Option<Event> {
+        let since = self.last_packet.replace(time);
+        if core::mem::take(&mut self.stalled) {
+            since.map(|since| Event::Resumed { since, now: time })
+        } else {
+            None
+        }
+    }
+
+    /// Check for a stall at the given time
+    ///
+    /// Returns an [`Event::Stall`] if no packet arrived within the timeout
+    /// before `now`. A stall is reported only once, until the next packet
+    /// arrives. No stall is reported before the first packet arrived.
+    pub fn check(&mut self, now: u64) -> Option<Event> {
+        let since = self.last_packet?;
+        if self.stalled || now.saturating_sub(since) <= self.timeout? {
+            return None;
+        }
+        self.stalled = true;
+        Some(Event::Stall { since, now })
+    }
+
+    /// Retrieve the distinct PCs executed since execution entered the current
+    /// set of PCs
+    pub fn pcs(&self) -> &[u64] {
+        &self.pcs[..self.len]
+    }
+
+    /// Determine whether the hart is currently considered stuck in a loop
+    pub fn is_looping(&self) -> bool {
+        self.looping
+    }
+
+    /// Determine whether the hart is currently considered stalled
+    pub fn is_stalled(&self) -> bool {
+        self.stalled
+    }
+
+    /// Restart loop detection
+    pub fn reset(&mut self) {
+        self.len = 0;
+        self.items = 0;
+        self.looping = false;
+    }
+}
+
+/// Diagnostic event emitted by a [`Watchdog`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Event {
+    /// Execution remained within a small set of PCs
+    Loop {
+        /// Lowest PC in the set
+        start: u64,
+        /// Highest PC in the set
+        end: u64,
+        /// Number of instructions retired within the set so far
+        items: u64,
+    },
+    /// No packet arrived within the timeout
+    Stall {
+        /// Time at which the last packet arrived
+        since: u64,
+        /// Time at which the stall was detected
+        now: u64,
+    },
+    /// A packet arrived after a stall was reported
+    Resumed {
+        /// Time at which the last packet before the stall arrived
+        since: u64,
+        /// Time at which the packet arrived
+        now: u64,
+    },
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Loop { start, end, items } => {
+                write!(f, "stuck in {start:#x}..={end:#x} for {items} instructions")
+            }
+            Self::Stall { since, now } => write!(f, "no trace data since {since} (now {now})"),
+            Self::Resumed { since, now } => write!(f, "trace data resumed at {now} after {since}"),
+        }
+    }
+}
@@ -2,7 +2,7 @@
 // SPDX-License-Identifier: Apache-2.0
 //! Basic [`Binary`]s and adapters
 
-use crate::instruction::{Instruction, decode, info};
+use crate::instruction::{Instruction, base, decode, info};
 
 use super::Binary;
 use super::error;
@@ -53,8 +53,8 @@ where
 /// The following example builds a segment at a specifig offset:
 ///
 /// ```
-/// use riscv_etrace::binary::{self, Adaptable, Binary};
-/// use riscv_etrace::instruction::{self, base};
+/// use riscv_etrace_core::binary::{self, Adaptable, Binary};
+/// use riscv_etrace_core::instruction::{self, base};
 ///
 /// let bootrom = b"\x97\x02\x00\x00\x93\x85\x02\x02\x73\x25\x40\xf1\x83\xb2\x82\x01\x67\x80\x02\x00";
 /// let mut bootrom = binary::from_segment(bootrom, base::Set::Rv64I)
@@ -93,6 +93,9 @@ impl<T: AsRef<[u8]>, B: decode::Decode<I>, I: info::Info> Binary<I> for Segment<
             .map(|(i, _)| i)
             .ok_or(Self::Error::InvalidInstruction)
     }
+    fn base_set(&self) -> Option<base::Set> {
+        decode::Decode::<I>::base_set(&self.base)
+    }
 }
 
 /// Create a new [`Binary`] for a segment of (raw) code
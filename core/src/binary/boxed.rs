@@ -50,6 +50,9 @@ where
     fn get_insn(&mut self, address: u64) -> Result<Instruction<I>, Self::Error> {
         self.inner.get_insn(address).map_err(|e| Box::new(e).into())
     }
+    fn switch_context(&mut self, context: &crate::types::Context) {
+        self.inner.switch_context(context)
+    }
 }
 
 /// Dynamically dispatched error
@@ -0,0 +1,356 @@
+// Copyright (C) 2025, 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Combination of multiple [`Binary`]
+
+use core::borrow::{Borrow, BorrowMut};
+
+use crate::instruction::{Instruction, base, info};
+use crate::types::{Context, Privilege};
+
+use super::Binary;
+use super::error::{MaybeMiss, Miss};
+
+/// Set of [`Binary`] acting as a single [`Binary`]
+#[derive(Copy, Clone, Debug)]
+pub struct Multi<C: BorrowMut<[B]>, B> {
+    bins: C,
+    last: usize,
+    phantom: core::marker::PhantomData<B>,
+}
+
+impl<C: BorrowMut<[B]>, B> Multi<C, B> {
+    /// Create a new [`Binary`] combining all `bins`
+    pub fn new(bins: C) -> Self {
+        Self {
+            bins,
+            last: 0,
+            phantom: Default::default(),
+        }
+    }
+}
+
+/// Accessors
+impl<C: Borrow<[B]> + BorrowMut<[B]>, B> Multi<C, B> {
+    /// Retrieve the inner [`Binary`]s
+    pub fn inner(&self) -> &[B] {
+        self.bins.borrow()
+    }
+
+    /// Retrieve an iterator over all inner [`Binary`]s
+    pub fn iter(&self) -> core::slice::Iter<'_, B> {
+        self.inner().iter()
+    }
+}
+
+impl<C: BorrowMut<[B]> + Default, B> Default for Multi<C, B> {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+impl<C: BorrowMut<[B]>, B> From<C> for Multi<C, B> {
+    fn from(bins: C) -> Self {
+        Self::new(bins)
+    }
+}
+
+impl<C: BorrowMut<[B]> + FromIterator<B>, B> FromIterator<B> for Multi<C, B> {
+    fn from_iter<T: IntoIterator<Item = B>>(iter: T) -> Self {
+        C::from_iter(iter).into()
+    }
+}
+
+impl<C: BorrowMut<[B]> + Extend<B>, B> Extend<B> for Multi<C, B> {
+    fn extend<T: IntoIterator<Item = B>>(&mut self, iter: T) {
+        self.bins.extend(iter)
+    }
+}
+
+impl<C, B, I> Binary<I> for Multi<C, B>
+where
+    C: BorrowMut<[B]>,
+    B: Binary<I>,
+    B::Error: Miss,
+    I: info::Info,
+{
+    type Error = B::Error;
+
+    fn get_insn(&mut self, address: u64) -> Result<Instruction<I>, Self::Error> {
+        let bins = self.bins.borrow_mut();
+        let res = bins
+            .get_mut(self.last)
+            .map(|b| b.get_insn(address))
+            .filter(|r| !r.is_miss());
+        if let Some(res) = res {
+            return res;
+        }
+
+        let res = bins
+            .iter_mut()
+            .enumerate()
+            .filter(|(n, _)| *n != self.last)
+            .map(|(n, b)| (n, b.get_insn(address)))
+            .find(|(_, r)| !r.is_miss());
+        if let Some((current, res)) = res {
+            self.last = current;
+            res
+        } else {
+            Miss::miss(address)
+        }
+    }
+    fn switch_context(&mut self, context: &Context) {
+        self.bins
+            .borrow_mut()
+            .iter_mut()
+            .for_each(|b| b.switch_context(context))
+    }
+
+    fn base_set(&self) -> Option<base::Set> {
+        self.bins.borrow().iter().find_map(B::base_set)
+    }
+}
+
+/// Overlay of [`Binary`]s backing the same address range
+///
+/// Some targets use code overlays or bank switching, i.e. the same address
+/// range is backed by different code at different times. An overlay holds one
+/// [`Binary`] per bank and forwards accesses to the currently selected one.
+///
+/// Banks may be selected explicitly via [`select`][Self::select], e.g. in
+/// reaction to some [`Item`][crate::tracer::item::Item] or user defined event.
+/// Alternatively, an overlay may follow a schedule of `(key, bank)` pairs,
+/// sorted by key. Keys may be timestamps or any other monotonic event index.
+/// Calling [`advance`][Self::advance] selects the bank of the last entry with
+/// a key lower or equal to the given one.
+///
+/// As a [`Tracer`][crate::tracer::Tracer] may fetch [`Instruction`]s while
+/// processing a payload, banks should be switched before the payload following
+/// the switch is fed to the tracer. The overlay is accessible via
+/// [`Tracer::binary_mut`][crate::tracer::Tracer::binary_mut].
+///
+/// # Example
+///
+/// ```
+/// use riscv_etrace_core::binary::{self, Binary, combinators::Overlay};
+/// use riscv_etrace_core::instruction::{self, Instruction};
+///
+/// let banks = [
+///     binary::from_sorted_map([(0x1000, instruction::UNCOMPRESSED)]),
+///     binary::from_sorted_map([(0x1000, instruction::COMPRESSED)]),
+/// ];
+/// let mut overlay = Overlay::new(banks).with_schedule([(0, 0), (100, 1), (200, 0)]);
+/// overlay.advance(150);
+/// assert_eq!(overlay.selected(), 1);
+/// let insn: Instruction = overlay.get_insn(0x1000).unwrap();
+/// assert_eq!(insn, instruction::COMPRESSED);
+/// overlay.advance(200);
+/// assert_eq!(overlay.selected(), 0);
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Overlay<C: BorrowMut<[B]>, B, S = [(u64, usize); 0]> {
+    banks: C,
+    selected: usize,
+    schedule: S,
+    phantom: core::marker::PhantomData<B>,
+}
+
+impl<C: BorrowMut<[B]>, B> Overlay<C, B> {
+    /// Create a new overlay of the given banks
+    ///
+    /// Initially, the first bank is selected.
+    pub fn new(banks: C) -> Self {
+        Self {
+            banks,
+            selected: 0,
+            schedule: [],
+            phantom: Default::default(),
+        }
+    }
+}
+
+impl<C: BorrowMut<[B]>, B, S> Overlay<C, B, S> {
+    /// Follow the given schedule of `(key, bank)` pairs
+    ///
+    /// The schedule must be sorted by key. See [`advance`][Self::advance].
+    pub fn with_schedule<T: Borrow<[(u64, usize)]>>(self, schedule: T) -> Overlay<C, B, T> {
+        Overlay {
+            banks: self.banks,
+            selected: self.selected,
+            schedule,
+            phantom: Default::default(),
+        }
+    }
+
+    /// Select the bank with the given index
+    ///
+    /// Returns `false` if there is no such bank, in which case the selection
+    /// is not changed.
+    pub fn select(&mut self, bank: usize) -> bool {
+        let exists = bank < self.banks.borrow_mut().len();
+        if exists {
+            self.selected = bank;
+        }
+        exists
+    }
+
+    /// Retrieve the index of the currently selected bank
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// Retrieve the [`Binary`] of the currently selected bank
+    ///
+    /// Returns `None` if the overlay does not have any banks.
+    pub fn current_mut(&mut self) -> Option<&mut B> {
+        self.banks.borrow_mut().get_mut(self.selected)
+    }
+
+    /// Advance to the given key, selecting a bank according to the schedule
+    ///
+    /// Selects the bank of the last entry in the schedule with a key lower or
+    /// equal to the given `key`. If there is no such entry, the selection is
+    /// not changed. Returns the index of the bank selected.
+    pub fn advance(&mut self, key: u64) -> usize
+    where
+        S: Borrow<[(u64, usize)]>,
+    {
+        let schedule = self.schedule.borrow();
+        let pos = schedule.partition_point(|(k, _)| *k <= key);
+        if let Some((_, bank)) = pos.checked_sub(1).and_then(|p| schedule.get(p)) {
+            self.select(*bank);
+        }
+        self.selected
+    }
+}
+
+impl<C, B, S, I> Binary<I> for Overlay<C, B, S>
+where
+    C: BorrowMut<[B]>,
+    B: Binary<I>,
+    B::Error: Miss,
+    I: info::Info,
+{
+    type Error = B::Error;
+
+    fn get_insn(&mut self, address: u64) -> Result<Instruction<I>, Self::Error> {
+        self.current_mut()
+            .map(|b| b.get_insn(address))
+            .unwrap_or_else(|| Miss::miss(address))
+    }
+    fn switch_context(&mut self, context: &Context) {
+        self.banks
+            .borrow_mut()
+            .iter_mut()
+            .for_each(|b| b.switch_context(context))
+    }
+
+    fn base_set(&self) -> Option<base::Set> {
+        self.banks.borrow().get(self.selected).and_then(B::base_set)
+    }
+}
+
+/// [`Binary`]s for hypervisor host and guest
+///
+/// In systems implementing the hypervisor extension, code executed in virtual
+/// privilege levels (i.e. with `V=1`) is usually found in a different binary
+/// than code executed by the host (i.e. with `V=0`). This [`Binary`] forwards
+/// accesses to either the host's or the guest's [`Binary`] depending on the
+/// [`Privilege`] of the [`Context`] it was last
+/// [switched to][Binary::switch_context]. Initially, the host's [`Binary`] is
+/// selected.
+///
+/// # Example
+///
+/// ```
+/// use riscv_etrace_core::binary::{self, Binary, Virtualized};
+/// use riscv_etrace_core::instruction::{self, Instruction};
+/// use riscv_etrace_core::types::{Context, Privilege};
+///
+/// let host = binary::from_sorted_map([(0x1000, instruction::UNCOMPRESSED)]).unwrap();
+/// let guest = binary::from_sorted_map([(0x1000, instruction::COMPRESSED)]).unwrap();
+/// let mut binary = Virtualized::new(host, guest);
+///
+/// let insn: Instruction = binary.get_insn(0x1000).unwrap();
+/// assert_eq!(insn, instruction::UNCOMPRESSED);
+///
+/// binary.switch_context(&Context {
+///     privilege: Privilege::VirtSupervisor,
+///     context: 0,
+/// });
+/// let insn: Instruction = binary.get_insn(0x1000).unwrap();
+/// assert_eq!(insn, instruction::COMPRESSED);
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Virtualized<H, G> {
+    host: H,
+    guest: G,
+    virt: bool,
+}
+
+impl<H, G> Virtualized<H, G> {
+    /// Create a new [`Binary`] for the given host and guest [`Binary`]s
+    pub fn new(host: H, guest: G) -> Self {
+        Self {
+            host,
+            guest,
+            virt: false,
+        }
+    }
+
+    /// Retrieve the host's [`Binary`]
+    pub fn host(&self) -> &H {
+        &self.host
+    }
+
+    /// Retrieve the guest's [`Binary`]
+    pub fn guest(&self) -> &G {
+        &self.guest
+    }
+
+    /// Retrieve a mutable reference to the guest's [`Binary`]
+    ///
+    /// This allows e.g. [selecting][Overlay::select] a specific guest's code
+    /// if the guest [`Binary`] is an [`Overlay`].
+    pub fn guest_mut(&mut self) -> &mut G {
+        &mut self.guest
+    }
+
+    /// Determine whether the guest's [`Binary`] is currently selected
+    pub fn is_virtualized(&self) -> bool {
+        self.virt
+    }
+}
+
+impl<H, G, I> Binary<I> for Virtualized<H, G>
+where
+    H: Binary<I>,
+    G: Binary<I, Error = H::Error>,
+    I: info::Info,
+{
+    type Error = H::Error;
+
+    fn get_insn(&mut self, address: u64) -> Result<Instruction<I>, Self::Error> {
+        if self.virt {
+            self.guest.get_insn(address)
+        } else {
+            self.host.get_insn(address)
+        }
+    }
+
+    fn switch_context(&mut self, context: &Context) {
+        self.virt = matches!(
+            context.privilege,
+            Privilege::VirtUser | Privilege::VirtSupervisor
+        );
+        self.host.switch_context(context);
+        self.guest.switch_context(context);
+    }
+
+    fn base_set(&self) -> Option<base::Set> {
+        if self.virt {
+            self.guest.base_set()
+        } else {
+            self.host.base_set()
+        }
+    }
+}
@@ -0,0 +1,319 @@
+// Copyright (C) 2025, 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! [`Binary`] merged from multiple sources of code
+//!
+//! Code for a traced system is often scattered over multiple sources: one or
+//! more ELF files, raw memory dumps of bootroms and user supplied patches e.g.
+//! for code modified at runtime. This module provides a [`Builder`] for
+//! merging all those sources into a single [`Database`], which is a [`Binary`]
+//! backed by a sorted list of contiguous memory regions.
+//!
+//! Sources may overlap. Overlapping segments are expected to agree on the
+//! contents of the shared addresses. Any differing byte is reported as a
+//! [`Conflict`] by [`Builder::build`]. Patches, on the other hand, take
+//! precedence over segments and resolve any [`Conflict`] at addresses they
+//! cover.
+//!
+//! # Example
+//!
+//! ```
+//! use riscv_etrace_core::binary::{Binary, database};
+//! use riscv_etrace_core::instruction::{self, Instruction, base};
+//!
+//! let firmware = b"\x97\x02\x00\x00\x93\x82\x02\x00\x73\xa0\x52\x30";
+//! let dump = b"\x93\x82\x02\x00\x73\x00\x50\x10";
+//!
+//! let builder = database::Builder::new(base::Set::Rv32I)
+//!     .with_segment(0x80000000, firmware)
+//!     .with_segment(0x80000004, dump);
+//! let conflicts = builder.conflicts();
+//! assert_eq!(conflicts.len(), 3);
+//! assert!(conflicts.iter().all(|c| c.address >= 0x80000008));
+//!
+//! let mut binary = builder
+//!     .with_patch(0x80000008, b"\x73\x00\x50\x10")
+//!     .build()
+//!     .unwrap();
+//! let insn: Instruction = binary.get_insn(0x80000008).unwrap();
+//! assert_eq!(insn.info, Some(instruction::Kind::wfi));
+//! ```
+
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Range;
+
+use crate::instruction::{Instruction, base, decode, info};
+
+use super::{Binary, error};
+
+/// Builder for [`Database`]s
+///
+/// Sources are added via [`with_segment`][Self::with_segment] and
+/// [`with_patch`][Self::with_patch]. Segments are identified by the order in
+/// which they were added, starting at `0`.
+#[derive(Clone, Debug)]
+pub struct Builder<D> {
+    segments: Vec<(u64, Vec<u8>)>,
+    patches: Vec<(u64, Vec<u8>)>,
+    base: D,
+}
+
+impl<D> Builder<D> {
+    /// Create a new [`Builder`] for code of a given instruction base set
+    pub fn new(base: D) -> Self {
+        Self {
+            segments: Default::default(),
+            patches: Default::default(),
+            base,
+        }
+    }
+
+    /// Add a segment of (raw) code located at the given address
+    ///
+    /// Contents of the segment must agree with those of all other segments
+    /// overlapping it. Differing bytes are reported as [`Conflict`]s.
+    pub fn with_segment(mut self, address: u64, data: impl Into<Vec<u8>>) -> Self {
+        self.segments.push((address, data.into()));
+        self
+    }
+
+    /// Add multiple segments of (raw) code
+    ///
+    /// The segments are added in the order they are yielded, as if added via
+    /// [`with_segment`][Self::with_segment].
+    pub fn with_segments<T: Into<Vec<u8>>>(
+        self,
+        segments: impl IntoIterator<Item = (u64, T)>,
+    ) -> Self {
+        segments
+            .into_iter()
+            .fold(self, |b, (a, d)| b.with_segment(a, d))
+    }
+
+    /// Add a patch of (raw) code located at the given address
+    ///
+    /// The contents of a patch take precedence over those of any segment,
+    /// resolving [`Conflict`]s at all addresses covered. Later patches take
+    /// precedence over earlier ones.
+    pub fn with_patch(mut self, address: u64, data: impl Into<Vec<u8>>) -> Self {
+        self.patches.push((address, data.into()));
+        self
+    }
+
+    /// Retrieve all unresolved [`Conflict`]s between segments
+    ///
+    /// [`Conflict`]s are ordered by address.
+    pub fn conflicts(&self) -> Vec<Conflict> {
+        self.merge().1
+    }
+
+    /// Build a [`Database`] from all sources added
+    ///
+    /// Returns an error listing all unresolved [`Conflict`]s if there are any.
+    pub fn build(self) -> Result<Database<D>, Conflicts> {
+        let (regions, conflicts) = self.merge();
+        if conflicts.is_empty() {
+            Ok(Database {
+                regions,
+                last: 0,
+                base: self.base,
+            })
+        } else {
+            Err(Conflicts(conflicts))
+        }
+    }
+
+    /// Merge all sources into regions, collecting unresolved [`Conflict`]s
+    fn merge(&self) -> (Vec<(u64, Vec<u8>)>, Vec<Conflict>) {
+        let mut order: Vec<_> = (0..self.segments.len()).collect();
+        order.sort_by_key(|i| self.segments[*i].0);
+
+        let mut regions = Vec::new();
+        let mut conflicts = Vec::new();
+        for index in order {
+            let (address, data) = &self.segments[index];
+            for (address, existing, byte) in insert(&mut regions, *address, data, false) {
+                // Segments are inserted in order of their address and the byte
+                // of the first one covering the address is retained.
+                let first = self
+                    .segments
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, (a, d))| *a <= address && address - a < d.len() as u64)
+                    .min_by_key(|(_, (a, _))| *a)
+                    .map(|(i, _)| i)
+                    .unwrap_or(index);
+                conflicts.push(Conflict {
+                    address,
+                    first: (first, existing),
+                    second: (index, byte),
+                });
+            }
+        }
+
+        for (address, data) in &self.patches {
+            insert(&mut regions, *address, data, true);
+            let range = *address..address.saturating_add(data.len() as u64);
+            conflicts.retain(|c| !range.contains(&c.address));
+        }
+
+        conflicts.sort_by_key(|c| c.address);
+        (regions, conflicts)
+    }
+}
+
+/// Insert data into a sorted list of disjoint regions
+///
+/// Regions overlapping or adjacent to the new data are merged. If `replace` is
+/// `true`, the new data replaces existing contents. Otherwise, existing
+/// contents are retained and differing bytes are returned as triples of the
+/// address, the existing and the new byte.
+fn insert(
+    regions: &mut Vec<(u64, Vec<u8>)>,
+    address: u64,
+    data: &[u8],
+    replace: bool,
+) -> Vec<(u64, u8, u8)> {
+    let end = |(a, d): &(u64, Vec<u8>)| a.saturating_add(d.len() as u64);
+
+    let room = usize::try_from(u64::MAX - address).unwrap_or(usize::MAX);
+    let data = &data[..data.len().min(room)];
+    let data_end = address + data.len() as u64;
+    let first = regions.partition_point(|r| end(r) < address);
+    let last = regions.partition_point(|r| r.0 <= data_end);
+    let range = first..last.max(first);
+
+    let start = regions[range.clone()]
+        .first()
+        .map_or(address, |r| r.0.min(address));
+    let stop = regions[range.clone()]
+        .last()
+        .map_or(data_end, |r| end(r).max(data_end));
+
+    let mut buffer = alloc::vec![0; (stop - start) as usize];
+    let offset = (address - start) as usize;
+    buffer[offset..][..data.len()].copy_from_slice(data);
+
+    let mut mismatches = Vec::new();
+    for (region_start, region) in &regions[range.clone()] {
+        let region_offset = (region_start - start) as usize;
+        for (i, byte) in region.iter().enumerate() {
+            let pos = region_offset + i;
+            let Some(new) = pos.checked_sub(offset).and_then(|o| data.get(o)) else {
+                buffer[pos] = *byte;
+                continue;
+            };
+            if replace {
+                continue;
+            }
+            if new != byte {
+                mismatches.push((start + pos as u64, *byte, *new));
+            }
+            buffer[pos] = *byte;
+        }
+    }
+
+    regions.splice(range, [(start, buffer)]);
+    mismatches
+}
+
+/// [`Binary`] merged from multiple sources
+///
+/// A [`Database`] is created through a [`Builder`]. It holds a sorted list of
+/// disjoint, contiguous regions of code from which [`Instruction`]s are
+/// decoded on demand.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Database<D> {
+    regions: Vec<(u64, Vec<u8>)>,
+    last: usize,
+    base: D,
+}
+
+impl<D> Database<D> {
+    /// Retrieve the address ranges covered by this [`Database`]
+    pub fn ranges(&self) -> impl Iterator<Item = Range<u64>> + '_ {
+        self.regions
+            .iter()
+            .map(|(a, d)| *a..a.saturating_add(d.len() as u64))
+    }
+
+    /// Retrieve the raw code covering the given address
+    ///
+    /// Returns the data starting at the given address up to the end of the
+    /// region containing it.
+    pub fn data(&self, address: u64) -> Option<&[u8]> {
+        self.find(address).map(|(_, d)| d)
+    }
+
+    /// Find the region index and data at the given address
+    fn find(&self, address: u64) -> Option<(usize, &[u8])> {
+        let data_at = |index: usize| {
+            let (start, data) = self.regions.get(index)?;
+            let offset = address.checked_sub(*start)?.try_into().ok()?;
+            data.get(offset..).filter(|d| !d.is_empty())
+        };
+        data_at(self.last).map(|d| (self.last, d)).or_else(|| {
+            let index = self.regions.partition_point(|(a, _)| *a <= address);
+            let index = index.checked_sub(1)?;
+            data_at(index).map(|d| (index, d))
+        })
+    }
+}
+
+impl<D: decode::Decode<I>, I: info::Info> Binary<I> for Database<D> {
+    type Error = error::SegmentError;
+
+    fn get_insn(&mut self, address: u64) -> Result<Instruction<I>, Self::Error> {
+        let (index, data) = self
+            .find(address)
+            .ok_or(error::SegmentError::AddressNotCovered)?;
+        let res = Instruction::extract(data, &self.base)
+            .map(|(i, _)| i)
+            .ok_or(error::SegmentError::InvalidInstruction);
+        self.last = index;
+        res
+    }
+    fn base_set(&self) -> Option<base::Set> {
+        decode::Decode::<I>::base_set(&self.base)
+    }
+}
+
+/// Conflicting contents of two segments at a single address
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Conflict {
+    /// Address at which the segments disagree
+    pub address: u64,
+    /// Index and byte of the segment retained
+    ///
+    /// If multiple segments cover the address, the one starting at the lowest
+    /// address is retained.
+    pub first: (usize, u8),
+    /// Index and byte of the segment conflicting with the retained one
+    pub second: (usize, u8),
+}
+
+impl fmt::Display for Conflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Segments {} and {} disagree at {:#x} ({:#04x} vs. {:#04x})",
+            self.first.0, self.second.0, self.address, self.first.1, self.second.1,
+        )
+    }
+}
+
+/// Error listing unresolved [`Conflict`]s
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Conflicts(pub Vec<Conflict>);
+
+impl core::error::Error for Conflicts {}
+
+impl fmt::Display for Conflicts {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} conflicting byte(s) between segments", self.0.len())?;
+        if let Some(conflict) = self.0.first() {
+            write!(f, ", first: {conflict}")?;
+        }
+        Ok(())
+    }
+}
@@ -88,6 +88,7 @@ impl<T: MaybeMiss + core::error::Error + Sync + Send + ?Sized> MaybeMissError fo
 
 /// An error for single segments of encoded [`Instruction`][super::Instruction]s
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum SegmentError {
     /// The address was not covered
     AddressNotCovered,
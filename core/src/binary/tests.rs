@@ -0,0 +1,252 @@
+// Copyright (C) 2025, 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+use crate::instruction;
+
+use error::MaybeMiss;
+
+macro_rules! retrieval_test {
+    ($n:ident, $b:expr, $($a:literal $(=> $i:expr)?),*) => {
+        #[test]
+        fn $n() {
+            let mut binary = $b;
+            $(
+                retrieval_test!(binary, $a $(, $i)?);
+            )*
+        }
+    };
+    ($b:ident, $a:literal, $i:expr) => {
+        let res: Result<Instruction, _> = $b.get_insn($a);
+        assert_eq!(res, $i);
+        assert!(!res.is_miss());
+    };
+    ($b:ident, $a:literal) => {
+        let res: Result<Instruction, _> = $b.get_insn($a);
+        assert_eq!(res, Err(Miss::miss($a)));
+        assert!(res.is_miss());
+    };
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn boxed() {
+    let mut binary = from_sorted_map([
+        (0x1000, instruction::UNCOMPRESSED),
+        (0x1004, instruction::COMPRESSED),
+    ])
+    .boxed();
+    assert!(binary.get_insn(0x0).is_miss());
+    assert_eq!(
+        binary.get_insn(0x1000).expect("Could not get insn"),
+        instruction::UNCOMPRESSED
+    );
+}
+
+retrieval_test!(option, None::<Empty>, 0x0);
+
+retrieval_test!(
+    from_func,
+    from_fn(|a| {
+        match a {
+            0x1000 => Ok(instruction::UNCOMPRESSED),
+            _ => Err(error::NoInstruction),
+        }
+    }),
+    0x1000 => Ok(instruction::UNCOMPRESSED),
+    0x1004
+);
+
+retrieval_test!(
+    offset,
+    from_sorted_map([
+        (0x00, instruction::UNCOMPRESSED),
+        (0x04, instruction::COMPRESSED),
+    ]).with_offset(0x1000),
+    0x0,
+    0x1000 => Ok(instruction::UNCOMPRESSED),
+    0x1004 => Ok(instruction::COMPRESSED),
+    0x1008
+);
+
+retrieval_test!(
+    mirrored,
+    from_sorted_map([
+        (0x1000, instruction::UNCOMPRESSED),
+        (0x1004, instruction::COMPRESSED),
+    ]).mirrored(0x1000, 0x10, [0x8000, 0x9000]),
+    0x0,
+    0x1000 => Ok(instruction::UNCOMPRESSED),
+    0x8000 => Ok(instruction::UNCOMPRESSED),
+    0x9004 => Ok(instruction::COMPRESSED),
+    0x9010
+);
+
+retrieval_test!(
+    masked,
+    from_sorted_map([
+        (0x1000, instruction::UNCOMPRESSED),
+        (0x1004, instruction::COMPRESSED),
+    ]).with_mask(0xffff),
+    0x0,
+    0x1000 => Ok(instruction::UNCOMPRESSED),
+    0xff_0000_1004 => Ok(instruction::COMPRESSED),
+    0x8000_1008
+);
+
+retrieval_test!(
+    segment,
+    from_segment(
+        b"\x97\x02\x00\x00\xff\x00\x00\x00\x73\x25\x40\xf1\x83\xb2\x82\x01\x67\x80\x02\x00",
+        instruction::base::Set::Rv64I,
+    ),
+    0x00 => Ok(instruction::Kind::new_auipc(5, 0).into()),
+    0x04 => Err(error::SegmentError::InvalidInstruction),
+    0x08 => Ok(instruction::UNCOMPRESSED),
+    0x0c => Ok(instruction::UNCOMPRESSED),
+    0x10 => Ok(instruction::Kind::new_jalr(0, 5, 0).into()),
+    0x14
+);
+
+retrieval_test!(
+    segment_tuple,
+    (
+        from_segment(b"\xff\x00\x00\x00\x73\x25\x40\xf1", instruction::base::Set::Rv64I),
+        from_segment(b"\x97\x02\x00\x00", instruction::base::Set::Rv64I).with_offset(0x1000),
+    ),
+    0x0000 => Err(error::SegmentError::InvalidInstruction),
+    0x0004 => Ok(instruction::UNCOMPRESSED),
+    0x1000 => Ok(instruction::Kind::new_auipc(5, 0).into()),
+    0x1004
+);
+
+retrieval_test!(
+    simple_map,
+    from_sorted_map([
+        (0x1000, instruction::UNCOMPRESSED),
+        (0x1004, instruction::UNCOMPRESSED),
+    ]),
+    0x0,
+    0x1000 => Ok(instruction::UNCOMPRESSED),
+    0x1004 => Ok(instruction::UNCOMPRESSED),
+    0x1008
+);
+
+retrieval_test!(empty, Empty, 0x0);
+
+retrieval_test!(
+    multi,
+    Multi::new([
+        from_sorted_map([
+            (0x1000, instruction::UNCOMPRESSED),
+            (0x1004, instruction::UNCOMPRESSED),
+        ]),
+        from_sorted_map([
+            (0x2000, instruction::UNCOMPRESSED),
+            (0x2004, instruction::UNCOMPRESSED),
+        ]),
+    ]),
+    0x0,
+    0x1000 => Ok(instruction::UNCOMPRESSED),
+    0x1004 => Ok(instruction::UNCOMPRESSED),
+    0x2000 => Ok(instruction::UNCOMPRESSED),
+    0x2004 => Ok(instruction::UNCOMPRESSED),
+    0x1000 => Ok(instruction::UNCOMPRESSED),
+    0x1008
+);
+
+#[test]
+fn binary_from_sorted_map() {
+    from_sorted_map([
+        (0x1000, instruction::UNCOMPRESSED),
+        (0x1004, instruction::UNCOMPRESSED),
+    ])
+    .expect("Could not create binary");
+}
+
+#[test]
+fn binary_from_unsorted_map() {
+    assert_eq!(
+        from_sorted_map([
+            (0x1004, instruction::UNCOMPRESSED),
+            (0x1000, instruction::UNCOMPRESSED),
+        ]),
+        None,
+    );
+}
+
+#[test]
+fn binary_from_map() {
+    assert_eq!(
+        from_map([
+            (0x1004, instruction::UNCOMPRESSED),
+            (0x1000, instruction::UNCOMPRESSED),
+        ]),
+        from_sorted_map([
+            (0x1000, instruction::UNCOMPRESSED),
+            (0x1004, instruction::UNCOMPRESSED),
+        ])
+        .expect("Could not create binary"),
+    );
+}
+
+#[test]
+fn overlay() {
+    let banks = [
+        from_sorted_map([(0x1000, instruction::UNCOMPRESSED)]),
+        from_sorted_map([(0x1000, instruction::COMPRESSED)]),
+        from_sorted_map([(0x2000, instruction::COMPRESSED)]),
+    ];
+    let mut overlay = combinators::Overlay::new(banks).with_schedule([(10, 1), (20, 2)]);
+    assert_eq!(overlay.advance(5), 0);
+    assert_eq!(overlay.get_insn(0x1000), Ok(instruction::UNCOMPRESSED));
+    assert_eq!(overlay.advance(10), 1);
+    assert_eq!(overlay.get_insn(0x1000), Ok(instruction::COMPRESSED));
+    assert_eq!(overlay.advance(25), 2);
+    assert!(overlay.get_insn(0x1000).is_miss());
+    assert!(!overlay.select(3));
+    assert!(overlay.select(0));
+    assert_eq!(overlay.get_insn(0x1000), Ok(instruction::UNCOMPRESSED));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn database_conflicts() {
+    let builder = database::Builder::new(instruction::base::Set::Rv32I)
+        .with_segment(0x1004, b"\x67\x80\x02\x00".as_slice())
+        .with_segment(0x1000, b"\x97\x02\x00\x00\x67\x80\x02\x00".as_slice())
+        .with_segment(0x1006, b"\x02\x01\x67\x80\x02\x00".as_slice());
+    assert_eq!(
+        builder.conflicts(),
+        alloc::vec![database::Conflict {
+            address: 0x1007,
+            first: (1, 0x00),
+            second: (2, 0x01),
+        }],
+    );
+    assert!(builder.clone().build().is_err());
+
+    let mut binary = builder
+        .with_patch(0x1007, b"\x00".as_slice())
+        .build()
+        .expect("Unresolved conflicts");
+    let ranges: alloc::vec::Vec<_> = binary.ranges().collect();
+    assert_eq!(ranges, alloc::vec![0x1000..0x100c]);
+    retrieval_test!(
+        binary,
+        0x1000,
+        Ok(instruction::Kind::new_auipc(5, 0).into())
+    );
+    retrieval_test!(
+        binary,
+        0x1004,
+        Ok(instruction::Kind::new_jalr(0, 5, 0).into())
+    );
+    retrieval_test!(
+        binary,
+        0x1008,
+        Ok(instruction::Kind::new_jalr(0, 5, 0).into())
+    );
+    retrieval_test!(binary, 0x100c);
+}
@@ -0,0 +1,296 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Compact error codes
+//!
+//! Embedded users may not be able to render errors as text, e.g. due to the
+//! lack of an allocator or because failures need to be logged over a severely
+//! constrained channel. This module provides a compact [`Code`] representation
+//! of decoder and tracer errors consisting of a 16 bit code and a 64 bit
+//! payload. A [`Code`] can be logged as is or as [bytes][Code::to_bytes] and
+//! converted back to the rich error offline.
+//!
+//! The upper byte of the code identifies the [`Domain`] of the error, the lower
+//! byte the variant. Codes are stable, i.e. they do not change between
+//! versions of this library.
+//!
+//! # Example
+//!
+//! ```
+//! use riscv_etrace_core::binary::error::NoInstruction;
+//! use riscv_etrace_core::code::Code;
+//! use riscv_etrace_core::tracer::error::Error;
+//!
+//! let error: Error<NoInstruction> = Error::CannotGetInstruction(NoInstruction, 0x80000000);
+//! let bytes = Code::from(&error).to_bytes();
+//!
+//! // Offline
+//! let decoded: Error<NoInstruction> = Code::from_bytes(bytes).try_into().unwrap();
+//! assert_eq!(decoded, error);
+//! ```
+
+use core::fmt;
+use core::num::{NonZeroU8, NonZeroUsize};
+
+use crate::binary::error::Miss;
+use crate::packet;
+use crate::tracer;
+use crate::types::branch;
+
+/// Names of features which may be reported as unsupported
+///
+/// The payload of [`tracer::error::Error::UnsupportedFeature`] is the index
+/// into this list. New names must only ever be appended.
+const FEATURES: [&str; 1] = ["implicit return"];
+
+/// Name of features not found in [`FEATURES`]
+const UNKNOWN_FEATURE: &str = "unknown";
+
+/// Compact representation of an error
+///
+/// See the [module][self] documentation for details.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Code {
+    /// Code identifying the domain and variant of the error
+    pub code: u16,
+    /// Additional data associated with the error
+    pub payload: u64,
+}
+
+impl Code {
+    /// Size of a [`Code`] in bytes
+    pub const SIZE: usize = 10;
+
+    /// Create a new code for the given domain, variant and payload
+    pub const fn new(domain: Domain, variant: u8, payload: u64) -> Self {
+        Self {
+            code: ((domain as u16) << 8) | variant as u16,
+            payload,
+        }
+    }
+
+    /// Retrieve the [`Domain`] of this code
+    ///
+    /// Returns `None` if the domain is not known.
+    pub fn domain(self) -> Option<Domain> {
+        match self.code >> 8 {
+            1 => Some(Domain::Packet),
+            2 => Some(Domain::Tracer),
+            3 => Some(Domain::Embedded),
+            _ => None,
+        }
+    }
+
+    /// Retrieve the variant within the [`Domain`]
+    pub fn variant(self) -> u8 {
+        self.code as u8
+    }
+
+    /// Retrieve the little endian byte representation of this code
+    pub fn to_bytes(self) -> [u8; Self::SIZE] {
+        let mut res = [0; Self::SIZE];
+        res[..2].copy_from_slice(&self.code.to_le_bytes());
+        res[2..].copy_from_slice(&self.payload.to_le_bytes());
+        res
+    }
+
+    /// Create a code from its little endian byte representation
+    pub fn from_bytes(bytes: [u8; Self::SIZE]) -> Self {
+        let [c0, c1, payload @ ..] = bytes;
+        Self {
+            code: u16::from_le_bytes([c0, c1]),
+            payload: u64::from_le_bytes(payload),
+        }
+    }
+}
+
+impl fmt::Display for Code {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "E{:04x}:{:x}", self.code, self.payload)
+    }
+}
+
+/// Domain of an error [`Code`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Domain {
+    /// A [`packet::Error`]
+    Packet = 1,
+    /// A [`tracer::error::Error`]
+    Tracer = 2,
+    /// An error specific to embedded drivers
+    Embedded = 3,
+}
+
+impl From<&packet::Error> for Code {
+    fn from(error: &packet::Error) -> Self {
+        use packet::Error as E;
+
+        let (variant, payload) = match *error {
+            E::UnknownTraceType(t) => (1, t.into()),
+            E::UnknownFmt(f, None) => (2, f.into()),
+            E::UnknownFmt(f, Some(s)) => (2, u64::from(f) | 0x100 | (u64::from(s) << 16)),
+            E::BadBranchFmt => (3, 0),
+            E::InsufficientData(n) => (4, n.get() as u64),
+            E::BufferTooSmall => (5, 0),
+            E::PayloadTooBig(s) => (6, s as u64),
+            E::UnknownPrivilege(p) => (7, p.into()),
+            E::UnknownEncoderMode(m) => (8, m.into()),
+            E::InvalidDataLength(l) => (9, l.into()),
+            E::PlaceholderNonZero(z) => (10, z.into()),
+        };
+        Self::new(Domain::Packet, variant, payload)
+    }
+}
+
+impl From<packet::Error> for Code {
+    fn from(error: packet::Error) -> Self {
+        (&error).into()
+    }
+}
+
+impl TryFrom<Code> for packet::Error {
+    type Error = Code;
+
+    fn try_from(code: Code) -> Result<Self, Self::Error> {
+        if code.domain() != Some(Domain::Packet) {
+            return Err(code);
+        }
+
+        let payload = code.payload;
+        let byte = || u8::try_from(payload).map_err(|_| code);
+        let size = || usize::try_from(payload).map_err(|_| code);
+        match code.variant() {
+            1 => byte().map(Self::UnknownTraceType),
+            2 if payload & 0x100 == 0 => byte().map(|f| Self::UnknownFmt(f, None)),
+            2 => u8::try_from(payload >> 16)
+                .map(|s| Self::UnknownFmt(payload as u8, Some(s)))
+                .map_err(|_| code),
+            3 => Ok(Self::BadBranchFmt),
+            4 => size()
+                .ok()
+                .and_then(NonZeroUsize::new)
+                .map(Self::InsufficientData)
+                .ok_or(code),
+            5 => Ok(Self::BufferTooSmall),
+            6 => size().map(Self::PayloadTooBig),
+            7 => byte().map(Self::UnknownPrivilege),
+            8 => byte().map(Self::UnknownEncoderMode),
+            9 => byte().map(Self::InvalidDataLength),
+            10 => byte().map(Self::PlaceholderNonZero),
+            _ => Err(code),
+        }
+    }
+}
+
+/// Conversion of [`tracer::error::Error`]s
+///
+/// Errors reported by the [`Binary`][crate::binary::Binary] are not retained.
+/// For [`tracer::error::Error::CannotGetInstruction`], only the address is
+/// retained and a [`Miss`] for that address is substituted when converting
+/// back. Names of unsupported features not known to this library are
+/// converted back as `"unknown"`.
+impl<I> From<&tracer::error::Error<I>> for Code {
+    fn from(error: &tracer::error::Error<I>) -> Self {
+        use tracer::error::Error as E;
+
+        let (variant, payload) = match error {
+            E::StartOfTrace => (1, 0),
+            E::UnsupportedFeature(feature) => (
+                2,
+                FEATURES
+                    .iter()
+                    .position(|f| f == feature)
+                    .map(|p| p as u64)
+                    .unwrap_or(u64::MAX),
+            ),
+            E::UnprocessedInstructions => (3, 0),
+            E::CannotAddBranches(branch::Error::TooManyBranches) => (4, 0),
+            E::UnprocessedBranches(n) => (5, n.get().into()),
+            E::UnexpectedUninferableDiscon => (6, 0),
+            E::UnresolvableBranch => (7, 0),
+            E::CannotConstructIrStack(size) => (8, *size as u64),
+            E::CannotGetInstruction(_, address) => (9, *address),
+            E::InvalidSyncAddress(address) => (10, *address),
+            E::SpeculationLimit(limit) => (11, *limit as u64),
+        };
+        Self::new(Domain::Tracer, variant, payload)
+    }
+}
+
+impl<I> From<tracer::error::Error<I>> for Code {
+    fn from(error: tracer::error::Error<I>) -> Self {
+        (&error).into()
+    }
+}
+
+impl<I: Miss> TryFrom<Code> for tracer::error::Error<I> {
+    type Error = Code;
+
+    fn try_from(code: Code) -> Result<Self, Self::Error> {
+        if code.domain() != Some(Domain::Tracer) {
+            return Err(code);
+        }
+
+        let payload = code.payload;
+        match code.variant() {
+            1 => Ok(Self::StartOfTrace),
+            2 => Ok(Self::UnsupportedFeature(
+                usize::try_from(payload)
+                    .ok()
+                    .and_then(|p| FEATURES.get(p))
+                    .unwrap_or(&UNKNOWN_FEATURE),
+            )),
+            3 => Ok(Self::UnprocessedInstructions),
+            4 if payload == 0 => Ok(Self::CannotAddBranches(branch::Error::TooManyBranches)),
+            5 => u8::try_from(payload)
+                .ok()
+                .and_then(NonZeroU8::new)
+                .map(Self::UnprocessedBranches)
+                .ok_or(code),
+            6 => Ok(Self::UnexpectedUninferableDiscon),
+            7 => Ok(Self::UnresolvableBranch),
+            8 => usize::try_from(payload)
+                .map(Self::CannotConstructIrStack)
+                .map_err(|_| code),
+            9 => Ok(Self::CannotGetInstruction(I::miss(payload), payload)),
+            10 => Ok(Self::InvalidSyncAddress(payload)),
+            11 => usize::try_from(payload)
+                .map(Self::SpeculationLimit)
+                .map_err(|_| code),
+            _ => Err(code),
+        }
+    }
+}
+
+#[cfg(feature = "embedded")]
+impl<B> From<&crate::embedded::error::Error<B>> for Code {
+    fn from(error: &crate::embedded::error::Error<B>) -> Self {
+        use crate::embedded::error::Error as E;
+
+        match error {
+            E::Packet(inner) => inner.into(),
+            E::Tracer(inner) => inner.into(),
+            E::BufferOverflow => Self::new(Domain::Embedded, 1, 0),
+        }
+    }
+}
+
+#[cfg(feature = "embedded")]
+impl<B> From<crate::embedded::error::Error<B>> for Code {
+    fn from(error: crate::embedded::error::Error<B>) -> Self {
+        (&error).into()
+    }
+}
+
+#[cfg(feature = "embedded")]
+impl<B: Miss> TryFrom<Code> for crate::embedded::error::Error<B> {
+    type Error = Code;
+
+    fn try_from(code: Code) -> Result<Self, Self::Error> {
+        match code.domain() {
+            Some(Domain::Packet) => code.try_into().map(Self::Packet),
+            Some(Domain::Tracer) => code.try_into().map(Self::Tracer),
+            Some(Domain::Embedded) if code.variant() == 1 => Ok(Self::BufferOverflow),
+            _ => Err(code),
+        }
+    }
+}
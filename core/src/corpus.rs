@@ -0,0 +1,306 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Regression corpora of raw trace data
+//!
+//! Inputs found by fuzzing the decoder or tracer are best kept as permanent
+//! regression tests. This module provides a [`Checker`] which runs a corpus of
+//! raw trace data, given as [`Entry`]s, through decoding and tracing in a
+//! checked mode. Malformed input is expected and tolerated, but the following
+//! properties are checked for every entry:
+//! * decoding terminates, i.e. every packet decoded consumes data,
+//! * the tracer does not yield an excessive number of items for a single
+//!   payload and
+//! * processing is deterministic, i.e. processing the entry a second time
+//!   yields the same [`Summary`].
+//!
+//! Panics are obviously not caught but fail the surrounding test.
+//!
+//! Entries are usually embedded via [`include_bytes`]. With the `std` feature,
+//! the main crate additionally allows loading a corpus from a directory.
+//!
+//! # Example
+//!
+//! ```
+//! use riscv_etrace_core::binary::{self, Adaptable};
+//! use riscv_etrace_core::corpus::{Checker, Entry};
+//! use riscv_etrace_core::instruction::base;
+//! use riscv_etrace_core::{packet, tracer};
+//!
+//! static CORPUS: &[Entry] = &[
+//!     Entry::new("start", b"\x45\x73\x0a\x00\x00\x20\x41\x01"),
+//!     Entry::new("garbage", b"\xff\xff\x00\x13"),
+//! ];
+//!
+//! # let binary_data = b"\x14\x41\x11\x05\x94\xc1\x91\x05\xe3\xec\xc5\xfe\x82\x80";
+//! let binary = binary::from_segment(binary_data, base::Set::Rv32I)
+//!     .with_offset(0x80000028);
+//! let tracer = tracer::builder().with_binary(binary);
+//! let checker: Checker<_, _> = Checker::new(packet::builder(), tracer);
+//! checker.check_all(CORPUS.iter().copied()).unwrap();
+//! ```
+
+#[cfg(test)]
+mod tests;
+
+use core::fmt;
+
+use crate::binary::Binary;
+use crate::instruction::{self, info::Info};
+use crate::packet::{self, unit};
+use crate::tracer::{self, error::Error as TracerError};
+use crate::transport::{Framing, Transport};
+use crate::types::stack::{self, ReturnStack};
+
+/// Named raw trace data
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Entry<'a> {
+    /// Name of the entry, e.g. the name of the file it originates from
+    pub name: &'a str,
+    /// Raw trace data
+    pub data: &'a [u8],
+}
+
+impl<'a> Entry<'a> {
+    /// Create a new entry
+    pub const fn new(name: &'a str, data: &'a [u8]) -> Self {
+        Self { name, data }
+    }
+}
+
+/// Checked processing of raw trace data
+///
+/// The checker decodes packets via a [`Transport`], by default a [`Framing`],
+/// and feeds the payloads of all harts into a single [`Tracer`][tracer::Tracer]
+/// built for each run. Errors reported by the decoder or tracer are counted
+/// in the [`Summary`]. Decoding stops at the first decoding error.
+#[derive(Clone)]
+pub struct Checker<U, B, S = stack::NoStack, I = Option<instruction::Kind>, T = Framing> {
+    decoder: packet::Builder<U>,
+    tracer: tracer::Builder<B>,
+    transport: T,
+    max_items: u64,
+    phantom: core::marker::PhantomData<(S, I)>,
+}
+
+impl<U, B, S, I> Checker<U, B, S, I> {
+    /// Create a new checker using the given builders
+    ///
+    /// The checker uses [`Framing::Encap`] and tolerates up to
+    /// [`DEFAULT_MAX_ITEMS`] items per payload.
+    pub fn new(decoder: packet::Builder<U>, tracer: tracer::Builder<B>) -> Self {
+        Self {
+            decoder,
+            tracer,
+            transport: Default::default(),
+            max_items: DEFAULT_MAX_ITEMS,
+            phantom: Default::default(),
+        }
+    }
+}
+
+impl<U, B, S, I, T> Checker<U, B, S, I, T> {
+    /// Set the [`Transport`] for decoding packets
+    pub fn with_transport<N>(self, transport: N) -> Checker<U, B, S, I, N> {
+        Checker {
+            decoder: self.decoder,
+            tracer: self.tracer,
+            transport,
+            max_items: self.max_items,
+            phantom: self.phantom,
+        }
+    }
+
+    /// Set the maximum number of items tolerated for a single payload
+    pub fn with_max_items(self, max_items: u64) -> Self {
+        Self { max_items, ..self }
+    }
+}
+
+impl<U, B, S, I, T> Checker<U, B, S, I, T>
+where
+    U: unit::Unit + Clone,
+    B: Binary<I> + Clone,
+    S: ReturnStack,
+    I: Info + Clone,
+    T: Transport<U> + Clone,
+{
+    /// Check all given entries
+    ///
+    /// Returns the [merged][Summary::merge] [`Summary`] of all entries on
+    /// success. Checking stops at the first entry failing, which is reported
+    /// along with the [`Error`].
+    pub fn check_all<'a>(
+        &self,
+        entries: impl IntoIterator<Item = Entry<'a>>,
+    ) -> Result<Summary, Failure<'a, B::Error>> {
+        entries.into_iter().try_fold(Summary::default(), |acc, e| {
+            self.check(e.data)
+                .map(|s| acc.merge(&s))
+                .map_err(|error| Failure { entry: e, error })
+        })
+    }
+
+    /// Check a single piece of raw trace data
+    pub fn check(&self, data: &[u8]) -> Result<Summary, Error<B::Error>> {
+        let first = self.run(data)?;
+        let second = self.run(data)?;
+        if first != second {
+            return Err(Error::Nondeterministic { first, second });
+        }
+        Ok(first)
+    }
+
+    /// Process the raw trace data once
+    fn run(&self, data: &[u8]) -> Result<Summary, Error<B::Error>> {
+        let mut tracer: tracer::Tracer<B, S, I> =
+            self.tracer.clone().build().map_err(Error::Build)?;
+        let mut transport = self.transport.clone();
+        let mut decoder = self.decoder.clone().decoder(data);
+        let mut summary = Summary::default();
+        while decoder.bytes_left() > 0 {
+            let offset = data.len() - decoder.bytes_left();
+            let packet = match transport.next_packet(&mut decoder) {
+                Ok(packet) => packet,
+                Err(_) => {
+                    summary.decode_errors += 1;
+                    break;
+                }
+            };
+            if data.len() - decoder.bytes_left() <= offset {
+                return Err(Error::NoProgress { offset });
+            }
+            summary.packets += 1;
+            let Some(packet) = packet else {
+                continue;
+            };
+
+            if tracer.process_payload(&packet.payload).is_err() {
+                summary.tracer_errors += 1;
+                continue;
+            }
+            let mut items = 0;
+            for item in tracer.by_ref() {
+                let Ok(item) = item else {
+                    summary.tracer_errors += 1;
+                    break;
+                };
+                items += 1;
+                if items > self.max_items {
+                    return Err(Error::Runaway { offset });
+                }
+                summary.digest = (summary.digest ^ item.pc()).wrapping_mul(FNV_PRIME);
+            }
+            summary.items += items;
+        }
+        Ok(summary)
+    }
+}
+
+/// Default maximum number of items tolerated for a single payload
+pub const DEFAULT_MAX_ITEMS: u64 = 1 << 16;
+
+/// Prime of the 64 bit FNV hash used for the [`Summary::digest`]
+const FNV_PRIME: u64 = 0x100000001b3;
+
+/// Outcome of processing raw trace data
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Summary {
+    /// Number of packets decoded
+    pub packets: u64,
+    /// Number of decoding errors encountered
+    pub decode_errors: u64,
+    /// Number of errors reported by the tracer
+    pub tracer_errors: u64,
+    /// Number of items yielded by the tracer
+    pub items: u64,
+    /// Digest of the PCs of all items yielded
+    pub digest: u64,
+}
+
+impl Summary {
+    /// Combine this summary with another one
+    ///
+    /// The resulting [`digest`][Self::digest] depends on the order in which
+    /// summaries are merged.
+    pub fn merge(&self, other: &Self) -> Self {
+        Self {
+            packets: self.packets + other.packets,
+            decode_errors: self.decode_errors + other.decode_errors,
+            tracer_errors: self.tracer_errors + other.tracer_errors,
+            items: self.items + other.items,
+            digest: (self.digest ^ other.digest).wrapping_mul(FNV_PRIME),
+        }
+    }
+}
+
+/// A violation of the properties checked by a [`Checker`]
+#[non_exhaustive]
+#[derive(Debug, PartialEq)]
+pub enum Error<E> {
+    /// The tracer could not be built
+    Build(TracerError<E>),
+    /// A packet was decoded without consuming any data at the given offset
+    NoProgress {
+        /// Offset of the packet in the raw trace data
+        offset: usize,
+    },
+    /// The tracer yielded too many items for the packet at the given offset
+    Runaway {
+        /// Offset of the packet in the raw trace data
+        offset: usize,
+    },
+    /// Processing the same data twice yielded different outcomes
+    Nondeterministic {
+        /// Outcome of the first run
+        first: Summary,
+        /// Outcome of the second run
+        second: Summary,
+    },
+}
+
+impl<E: core::error::Error + 'static> core::error::Error for Error<E> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Build(inner) => Some(inner),
+            _ => None,
+        }
+    }
+}
+
+impl<E> fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Build(_) => write!(f, "could not build tracer"),
+            Self::NoProgress { offset } => {
+                write!(f, "decoding made no progress at offset {offset}")
+            }
+            Self::Runaway { offset } => {
+                write!(f, "too many items for packet at offset {offset}")
+            }
+            Self::Nondeterministic { first, second } => {
+                write!(f, "outcomes differ between runs: {first:?} vs. {second:?}")
+            }
+        }
+    }
+}
+
+/// An [`Error`] for a specific [`Entry`]
+#[derive(Debug, PartialEq)]
+pub struct Failure<'a, E> {
+    /// The entry which failed
+    pub entry: Entry<'a>,
+    /// The error encountered
+    pub error: Error<E>,
+}
+
+impl<E: core::error::Error + 'static> core::error::Error for Failure<'_, E> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl<E> fmt::Display for Failure<'_, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "corpus entry '{}' failed", self.entry.name)
+    }
+}
@@ -0,0 +1,68 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+use crate::binary::{self, Adaptable, error::SegmentError};
+use crate::instruction::base;
+use crate::packet::decoder::Decoder;
+use crate::transport::UnitPacket;
+
+/// Regression corpus
+static CORPUS: &[Entry] = &[
+    Entry::new("start", b"\x45\x73\x0a\x00\x00\x20\x41\x01"),
+    Entry::new("truncated", b"\x45\x73\x0a\x00"),
+    Entry::new("null", b"\x00\x00\x00\x00"),
+    Entry::new("garbage", b"\xff\xff\xff\xff\x13\x37"),
+];
+
+fn test_checker()
+-> Checker<unit::Reference, impl Binary<Option<instruction::Kind>, Error = SegmentError> + Clone> {
+    let binary = binary::from_segment(
+        b"\x14\x41\x11\x05\x94\xc1\x91\x05\xe3\xec\xc5\xfe\x82\x80",
+        base::Set::Rv32I,
+    )
+    .with_offset(0x80000028);
+    Checker::new(packet::builder(), tracer::builder().with_binary(binary))
+}
+
+#[test]
+fn corpus() {
+    let summary = test_checker()
+        .check_all(CORPUS.iter().copied())
+        .expect("Corpus check failed");
+    assert!(summary.packets >= 1);
+    assert!(summary.items >= 2);
+}
+
+#[test]
+fn runaway() {
+    let entry = CORPUS[0];
+    assert_eq!(
+        test_checker().with_max_items(1).check_all([entry]),
+        Err(Failure {
+            entry,
+            error: Error::Runaway { offset: 0 }
+        })
+    );
+}
+
+#[test]
+fn no_progress() {
+    #[derive(Copy, Clone)]
+    struct Stuck;
+
+    impl<U: unit::Unit> Transport<U> for Stuck {
+        fn next_packet(
+            &mut self,
+            _: &mut Decoder<'_, U>,
+        ) -> Result<Option<UnitPacket<U>>, packet::Error> {
+            Ok(None)
+        }
+    }
+
+    assert_eq!(
+        test_checker().with_transport(Stuck).check(CORPUS[0].data),
+        Err(Error::NoProgress { offset: 0 })
+    );
+}
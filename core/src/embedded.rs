@@ -0,0 +1,331 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Self-tracing on embedded targets
+//!
+//! This module provides a minimal [`Driver`] for decoding and tracing on the
+//! target being traced itself, e.g. as part of firmware which wants to report
+//! the recent control flow after a fault. The driver reads trace data from a
+//! [`Fifo`], usually a memory-mapped trace sink accessed via [`Registers`],
+//! and retains the most recent [`Item`]s in a [`History`].
+//!
+//! Trace data is expected to consist of [`encap::Packet`]s.
+//!
+//! For minimal code size, consider fixing the [`Parameters`] at compile time
+//! via a [`Profiled`] unit.
+//!
+//! [`encap::Packet`]: crate::packet::encap::Packet
+//! [`Parameters`]: crate::config::Parameters
+//! [`Profiled`]: crate::packet::unit::Profiled
+//! [`Item`]: crate::tracer::item::Item
+
+pub mod error;
+
+#[cfg(test)]
+mod tests;
+
+use crate::analysis::history::History;
+use crate::binary::Binary;
+use crate::instruction::{self, info::Info};
+use crate::packet::{self, unit};
+use crate::tracer::Tracer;
+use crate::types::stack::{self, ReturnStack};
+
+use error::Error;
+
+/// Source of trace data in units of 32 bit words
+pub trait Fifo {
+    /// Retrieve the next word, if one is available
+    ///
+    /// The bytes of the word are expected to be in little endian order.
+    fn pop(&mut self) -> Option<u32>;
+}
+
+impl<F: Fifo + ?Sized> Fifo for &mut F {
+    fn pop(&mut self) -> Option<u32> {
+        F::pop(self)
+    }
+}
+
+/// Access to memory-mapped, 32 bit registers
+pub trait Registers {
+    /// Read the register at the given byte offset
+    fn read(&mut self, offset: usize) -> u32;
+}
+
+/// [`Fifo`] accessed via [`Registers`]
+///
+/// The FIFO is considered empty if any bits of the status register selected
+/// by a mask are set. Otherwise, words are read from a data register.
+#[derive(Clone, Debug)]
+pub struct RegisterFifo<R> {
+    registers: R,
+    status: usize,
+    empty_mask: u32,
+    data: usize,
+}
+
+impl<R: Registers> RegisterFifo<R> {
+    /// Create a new FIFO with the given register offsets
+    ///
+    /// The FIFO will be empty if `status & empty_mask` is not zero, with
+    /// `status` being the value read from the `status` register. Words are
+    /// read from the `data` register.
+    pub fn new(registers: R, status: usize, empty_mask: u32, data: usize) -> Self {
+        Self {
+            registers,
+            status,
+            empty_mask,
+            data,
+        }
+    }
+
+    /// Retrieve the underlying [`Registers`]
+    pub fn registers(&mut self) -> &mut R {
+        &mut self.registers
+    }
+}
+
+impl<R: Registers> Fifo for RegisterFifo<R> {
+    fn pop(&mut self) -> Option<u32> {
+        (self.registers.read(self.status) & self.empty_mask == 0)
+            .then(|| self.registers.read(self.data))
+    }
+}
+
+/// Driver for tracing a single hart from a [`Fifo`]
+///
+/// The driver buffers up to `L` bytes of trace data read from its [`Fifo`].
+/// Packets for the hart traced are decoded and dispatched to a [`Tracer`]. The
+/// last `N` [`Item`]s generated are retained in a [`History`]. Packets for
+/// other harts are discarded.
+///
+/// # Example
+///
+/// ```
+/// use riscv_etrace_core::binary::{self, Adaptable};
+/// use riscv_etrace_core::embedded::{Driver, Fifo};
+/// use riscv_etrace_core::instruction::base;
+/// use riscv_etrace_core::packet;
+/// use riscv_etrace_core::tracer::{self, Tracer};
+///
+/// struct Sink(&'static [u32]);
+///
+/// impl Fifo for Sink {
+///     fn pop(&mut self) -> Option<u32> {
+///         let (word, rest) = self.0.split_first()?;
+///         self.0 = rest;
+///         Some(*word)
+///     }
+/// }
+///
+/// # let binary_data = b"\x14\x41\x11\x05\x94\xc1\x91\x05\xe3\xec\xc5\xfe\x82\x80";
+/// let binary = binary::from_segment(binary_data, base::Set::Rv32I)
+///     .with_offset(0x80000028);
+/// let tracer: Tracer<_> = tracer::builder().with_binary(binary).build().unwrap();
+/// let mut driver: Driver<_, _, _> = Driver::new(
+///     Sink(&[0x000a7345, 0x01412000]),
+///     packet::builder(),
+///     tracer,
+///     0,
+/// );
+///
+/// driver.poll().unwrap();
+/// driver.history().iter().for_each(|i| println!("PC: {:0x}", i.pc()));
+/// ```
+///
+/// [`Item`]: crate::tracer::item::Item
+pub struct Driver<
+    F,
+    U,
+    B,
+    S = stack::NoStack,
+    I = Option<instruction::Kind>,
+    const L: usize = 256,
+    const N: usize = 64,
+> where
+    B: Binary<I>,
+    S: ReturnStack,
+    I: Info + Clone,
+{
+    fifo: F,
+    decoder: packet::Builder<U>,
+    tracer: Tracer<B, S, I>,
+    hart: u64,
+    buffer: [u8; L],
+    fill: usize,
+    history: History<I, N>,
+}
+
+impl<F, U, B, S, I, const L: usize, const N: usize> Driver<F, U, B, S, I, L, N>
+where
+    F: Fifo,
+    U: unit::Unit + Clone,
+    B: Binary<I>,
+    S: ReturnStack,
+    I: Info + Clone,
+{
+    /// Create a new driver for the given hart
+    pub fn new(fifo: F, decoder: packet::Builder<U>, tracer: Tracer<B, S, I>, hart: u64) -> Self {
+        Self {
+            fifo,
+            decoder,
+            tracer,
+            hart,
+            buffer: [0; L],
+            fill: 0,
+            history: Default::default(),
+        }
+    }
+
+    /// Read and process all trace data currently available
+    ///
+    /// Reads words from the [`Fifo`] until it is empty, processing packets
+    /// whenever the internal buffer is full. Returns the number of [`Item`]s
+    /// generated. On error, the internal buffer is cleared.
+    ///
+    /// [`Item`]: crate::tracer::item::Item
+    pub fn poll(&mut self) -> Result<usize, Error<B::Error>> {
+        self.poll_with(&mut ())
+    }
+
+    /// Read and process all trace data currently available, with [`Metrics`]
+    ///
+    /// Behaves like [`poll`][Self::poll] but records the time spent for
+    /// decoding and tracing as well as the number of [`Item`]s generated in
+    /// the given [`Metrics`].
+    ///
+    /// [`Metrics`]: crate::metrics::Metrics
+    /// [`Item`]: crate::tracer::item::Item
+    #[cfg(feature = "metrics")]
+    pub fn poll_measured<C: crate::metrics::Clock>(
+        &mut self,
+        metrics: &mut crate::metrics::Metrics<C>,
+    ) -> Result<usize, Error<B::Error>> {
+        self.poll_with(metrics)
+    }
+
+    /// Read and process all trace data currently available using a [`Probe`]
+    fn poll_with(&mut self, probe: &mut impl Probe) -> Result<usize, Error<B::Error>> {
+        let mut items = 0;
+        while let Some(word) = self.fifo.pop() {
+            if L - self.fill < 4 {
+                items += self.process_buffer(probe)?;
+                if L - self.fill < 4 {
+                    self.fill = 0;
+                    return Err(Error::BufferOverflow);
+                }
+            }
+            self.buffer[self.fill..][..4].copy_from_slice(&word.to_le_bytes());
+            self.fill += 4;
+        }
+        self.process_buffer(probe).map(|n| items + n)
+    }
+
+    /// Retrieve the [`History`] of recent [`Item`]s
+    ///
+    /// [`Item`]: crate::tracer::item::Item
+    pub fn history(&self) -> &History<I, N> {
+        &self.history
+    }
+
+    /// Retrieve the [`Tracer`]
+    pub fn tracer(&self) -> &Tracer<B, S, I> {
+        &self.tracer
+    }
+
+    /// Retrieve the [`Fifo`]
+    pub fn fifo(&mut self) -> &mut F {
+        &mut self.fifo
+    }
+
+    /// Process all complete packets in the internal buffer
+    fn process_buffer(&mut self, probe: &mut impl Probe) -> Result<usize, Error<B::Error>> {
+        let res = self.process_packets(probe);
+        if res.is_err() {
+            self.fill = 0;
+        }
+        res
+    }
+
+    /// Decode and process packets in the internal buffer
+    fn process_packets(&mut self, probe: &mut impl Probe) -> Result<usize, Error<B::Error>> {
+        let data = &self.buffer[..self.fill];
+        let mut decoder = self.decoder.clone().decoder(data);
+        let mut items = 0;
+        let rest = loop {
+            let rest = decoder.remaining_data().len();
+            let packet = match probe.decode(|| decoder.decode_encap_packet()) {
+                Ok(packet) => packet,
+                Err(packet::Error::InsufficientData(_)) => break rest,
+                Err(e) => return Err(e.into()),
+            };
+            let Some(packet) = packet
+                .into_normal()
+                .filter(|p| u64::from(p.src_id()) == self.hart)
+            else {
+                continue;
+            };
+
+            let mut payloads = packet.payloads();
+            while let Some(payload) = probe.decode(|| payloads.next()) {
+                let payload = payload?;
+                let tracer = &mut self.tracer;
+                let history = &mut self.history;
+                let generated: usize = probe.trace(|| -> Result<_, Error<B::Error>> {
+                    tracer.process_payload(&payload).map_err(Error::Tracer)?;
+                    let mut generated = 0;
+                    for item in tracer.by_ref() {
+                        history.push(item.map_err(Error::Tracer)?);
+                        generated += 1;
+                    }
+                    Ok(generated)
+                })?;
+                probe.add_items(generated);
+                items += generated;
+            }
+        };
+
+        self.buffer.copy_within(self.fill - rest..self.fill, 0);
+        self.fill = rest;
+        Ok(items)
+    }
+}
+
+/// Hooks for measuring individual stages of a [`Driver`]
+trait Probe {
+    /// Execute `f`, which decodes a packet
+    fn decode<R>(&mut self, f: impl FnOnce() -> R) -> R;
+
+    /// Execute `f`, which processes a payload
+    fn trace<R>(&mut self, f: impl FnOnce() -> R) -> R;
+
+    /// Record the generation of the given number of items
+    fn add_items(&mut self, items: usize);
+}
+
+impl Probe for () {
+    fn decode<R>(&mut self, f: impl FnOnce() -> R) -> R {
+        f()
+    }
+
+    fn trace<R>(&mut self, f: impl FnOnce() -> R) -> R {
+        f()
+    }
+
+    fn add_items(&mut self, _: usize) {}
+}
+
+#[cfg(feature = "metrics")]
+impl<C: crate::metrics::Clock> Probe for crate::metrics::Metrics<C> {
+    fn decode<R>(&mut self, f: impl FnOnce() -> R) -> R {
+        self.measure(crate::metrics::Stage::Decode, f)
+    }
+
+    fn trace<R>(&mut self, f: impl FnOnce() -> R) -> R {
+        self.measure(crate::metrics::Stage::Trace, f)
+    }
+
+    fn add_items(&mut self, items: usize) {
+        self.add_items(items.try_into().unwrap_or(u64::MAX))
+    }
+}
@@ -0,0 +1,48 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Errors specific to embedded drivers
+
+use core::fmt;
+
+use crate::packet;
+use crate::tracer;
+
+/// Errors specific to embedded [`Driver`][super::Driver]s
+#[derive(Debug, PartialEq)]
+pub enum Error<B> {
+    /// A packet could not be decoded
+    Packet(packet::Error),
+    /// An error occured while tracing
+    Tracer(tracer::error::Error<B>),
+    /// The internal buffer is full but does not contain a complete packet
+    BufferOverflow,
+}
+
+impl<B> core::error::Error for Error<B>
+where
+    B: fmt::Debug + core::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Packet(inner) => Some(inner),
+            Self::Tracer(inner) => Some(inner),
+            Self::BufferOverflow => None,
+        }
+    }
+}
+
+impl<B> fmt::Display for Error<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Packet(_) => write!(f, "could not decode packet"),
+            Self::Tracer(_) => write!(f, "error while tracing"),
+            Self::BufferOverflow => write!(f, "buffer overflow"),
+        }
+    }
+}
+
+impl<B> From<packet::Error> for Error<B> {
+    fn from(inner: packet::Error) -> Self {
+        Self::Packet(inner)
+    }
+}
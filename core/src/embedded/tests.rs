@@ -0,0 +1,174 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+use crate::binary;
+use crate::instruction::UNCOMPRESSED;
+use crate::packet::{encap, payload, sync};
+use crate::tracer::item::Item;
+
+/// [`Registers`] of a simple trace sink
+///
+/// Register `0` is non-zero if the sink is empty, register `4` holds the next
+/// word.
+struct Sink<'a> {
+    words: &'a [u32],
+}
+
+impl Registers for Sink<'_> {
+    fn read(&mut self, offset: usize) -> u32 {
+        match offset {
+            0 => self.words.is_empty().into(),
+            4 => {
+                let (word, rest) = self.words.split_first().expect("Read from empty sink");
+                self.words = rest;
+                *word
+            }
+            _ => panic!("Unexpected offset {offset}"),
+        }
+    }
+}
+
+/// Encode start packets for the given `(hart, address)` pairs as words
+fn start_words(packets: &[(u16, u64)], words: &mut [u32]) -> usize {
+    let mut buffer = [0u8; 64];
+    let mut encoder = test_decoder().encoder(buffer.as_mut());
+    packets.iter().for_each(|(hart, address)| {
+        let payload: payload::InstructionTrace = sync::Start {
+            branch: true,
+            ctx: Default::default(),
+            address: *address,
+        }
+        .into();
+        let packet = encap::Normal::new(0, *hart, payload::Payload::from(payload));
+        encoder
+            .encode(&encap::Packet::from(packet))
+            .expect("Could not encode packet");
+    });
+    let uncommitted = encoder.uncommitted();
+    let len = buffer.len() - uncommitted;
+    buffer[..len]
+        .chunks(4)
+        .zip(words.iter_mut())
+        .map(|(c, w)| {
+            let mut bytes = [0; 4];
+            bytes[..c.len()].copy_from_slice(c);
+            *w = u32::from_le_bytes(bytes);
+        })
+        .count()
+}
+
+fn test_decoder() -> packet::Builder {
+    packet::builder().with_hart_index_width(8)
+}
+
+#[test]
+fn register_fifo() {
+    let words = [1, 2, 3];
+    let mut fifo = RegisterFifo::new(Sink { words: &words }, 0, 1, 4);
+    assert!(core::iter::from_fn(|| fifo.pop()).eq([1, 2, 3]));
+    assert_eq!(fifo.pop(), None);
+}
+
+#[test]
+fn driver_filter_hart() {
+    let mut words = [0; 16];
+    let len = start_words(&[(1, 0x80000000), (0, 0x80000010)], &mut words);
+
+    let tracer: Tracer<_> = crate::tracer::builder()
+        .with_binary(binary::from_fn(|_| {
+            Ok::<_, binary::error::NoInstruction>(UNCOMPRESSED)
+        }))
+        .build()
+        .expect("Could not build tracer");
+    let sink = Sink {
+        words: &words[..len],
+    };
+    let mut driver: Driver<_, _, _, _, _, 16, 4> =
+        Driver::new(RegisterFifo::new(sink, 0, 1, 4), test_decoder(), tracer, 0);
+    assert_eq!(driver.poll(), Ok(2));
+    assert!(
+        driver
+            .history()
+            .iter()
+            .map(Item::pc)
+            .eq([0x80000010, 0x80000010])
+    );
+}
+
+#[test]
+fn driver_multiple_payloads() {
+    let mut separate = [0; 4];
+    let len = start_words(&[(0, 0x80000000), (0, 0x80000010)], &mut separate);
+    let mut bytes = [0u8; 16];
+    separate
+        .iter()
+        .zip(bytes.chunks_mut(4))
+        .for_each(|(w, c)| c.copy_from_slice(&w.to_le_bytes()));
+
+    // Merge the payloads of both packets into a single packet
+    let first = usize::from(bytes[0] & 0x1f);
+    let second = usize::from(bytes[first + 2] & 0x1f);
+    let mut merged = [0u8; 16];
+    merged[0] = bytes[0] + (bytes[first + 2] & 0x1f);
+    merged[1] = bytes[1];
+    merged[2..][..first].copy_from_slice(&bytes[2..][..first]);
+    merged[2 + first..][..second].copy_from_slice(&bytes[first + 4..][..second]);
+    let mut words = [0; 4];
+    merged
+        .chunks(4)
+        .zip(words.iter_mut())
+        .for_each(|(c, w)| *w = u32::from_le_bytes([c[0], c[1], c[2], c[3]]));
+
+    let driver = |words| {
+        let tracer: Tracer<_> = crate::tracer::builder()
+            .with_binary(binary::from_fn(|_| {
+                Ok::<_, binary::error::NoInstruction>(UNCOMPRESSED)
+            }))
+            .build()
+            .expect("Could not build tracer");
+        let mut driver: Driver<_, _, _, _, _, 16, 8> = Driver::new(
+            RegisterFifo::new(Sink { words }, 0, 1, 4),
+            test_decoder(),
+            tracer,
+            0,
+        );
+        let items = driver.poll();
+        (items, driver)
+    };
+    let (expected, reference) = driver(&separate[..len]);
+    let (items, merged) = driver(&words[..(first + second + 2).div_ceil(4)]);
+    assert_eq!(items, expected);
+    assert!(merged.history().iter().eq(reference.history().iter()));
+}
+
+#[cfg(feature = "metrics")]
+#[test]
+fn driver_metrics() {
+    use crate::metrics::{Metrics, Stage};
+
+    let mut words = [0; 16];
+    let len = start_words(&[(1, 0x80000000), (0, 0x80000010)], &mut words);
+
+    let tracer: Tracer<_> = crate::tracer::builder()
+        .with_binary(binary::from_fn(|_| {
+            Ok::<_, binary::error::NoInstruction>(UNCOMPRESSED)
+        }))
+        .build()
+        .expect("Could not build tracer");
+    let sink = Sink {
+        words: &words[..len],
+    };
+    let mut driver: Driver<_, _, _, _, _, 16, 4> =
+        Driver::new(RegisterFifo::new(sink, 0, 1, 4), test_decoder(), tracer, 0);
+    let mut ticks = 0;
+    let mut metrics = Metrics::new(|| {
+        ticks += 1;
+        ticks
+    });
+    assert_eq!(driver.poll_measured(&mut metrics), Ok(2));
+    assert_eq!(metrics.items(), 2);
+    assert_eq!(metrics.counter(Stage::Trace).calls(), 1);
+    assert!(metrics.counter(Stage::Decode).calls() >= 2);
+}
@@ -0,0 +1,386 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Known-good programs and payloads for sanity-checking integrations
+//!
+//! Chapter 12 "Example code and packets" of the E-Trace specification
+//! (version 2.0.3) illustrates which payloads a trace encoder produces for
+//! specific execution paths. This module exposes those examples as
+//! [`Fixture`]s, each consisting of a program, a sequence of payloads and the
+//! [`Item`]s a correctly configured [`Tracer`][tracer::Tracer] yields for them.
+//!
+//! Fixtures allow checking an integration at runtime, e.g. whether a
+//! [`tracer::Builder`] is configured with [`config::Parameters`] matching the
+//! default ones assumed by the examples.
+//!
+//! # Example
+//!
+//! ```
+//! use riscv_etrace_core::fixtures;
+//! use riscv_etrace_core::tracer;
+//! use riscv_etrace_core::types::stack::StaticStack;
+//!
+//! for fixture in fixtures::ALL {
+//!     fixture.verify::<StaticStack<8>>(tracer::builder()).unwrap();
+//! }
+//! ```
+//!
+//! [`config::Parameters`]: crate::config::Parameters
+
+use core::fmt;
+
+use crate::binary::{self, basic::SimpleMap, error::NoInstruction};
+use crate::instruction::{COMPRESSED, Instruction, Kind, Size, UNCOMPRESSED};
+use crate::packet::{payload, sync};
+use crate::tracer::{self, item};
+use crate::types::{Context, Privilege, branch, stack::ReturnStack};
+
+use item::Item;
+use payload::InstructionTrace;
+
+/// [`Binary`][binary::Binary] of a [`Fixture`]'s program
+pub type Program = SimpleMap<&'static [(u64, Instruction)], Option<Kind>>;
+
+/// A known-good combination of program, payloads and tracing [`Item`]s
+#[derive(Copy, Clone, Debug)]
+pub struct Fixture {
+    /// Name of the fixture
+    pub name: &'static str,
+    /// Program as address-[`Instruction`] pairs sorted by address
+    pub program: &'static [(u64, Instruction)],
+    /// Payloads emitted by the encoder, in order
+    pub payloads: &'static [InstructionTrace],
+    /// [`Item`]s expected after processing each of the
+    /// [`payloads`][Self::payloads]
+    pub items: &'static [&'static [Item]],
+}
+
+impl Fixture {
+    /// Retrieve the [`program`][Self::program] as a [`Binary`][binary::Binary]
+    ///
+    /// The programs of all fixtures in this module are sorted. Hence, this fn
+    /// never returns [`None`] for them.
+    pub fn binary(&self) -> Option<Program> {
+        binary::from_sorted_map(self.program)
+    }
+
+    /// Verify that a tracer built from the given builder agrees with this
+    /// fixture
+    ///
+    /// The [`binary`][Self::binary] is set on the [`tracer::Builder`], which is
+    /// then used for building a [`Tracer`][tracer::Tracer]. All
+    /// [`payloads`][Self::payloads] are processed and the [`Item`]s yielded
+    /// compared to the expected [`items`][Self::items].
+    pub fn verify<S: ReturnStack>(&self, builder: tracer::Builder) -> Result<(), Error> {
+        let mut tracer: tracer::Tracer<_, S> = builder
+            .with_binary(self.binary())
+            .build()
+            .map_err(Error::Build)?;
+        self.payloads
+            .iter()
+            .zip(self.items)
+            .enumerate()
+            .try_for_each(|(index, (payload, expected))| {
+                tracer
+                    .process_te_inst(payload)
+                    .map_err(|e| Error::Tracer(index, e))?;
+                let mismatch = |expected: Option<&Item>, actual| Error::Mismatch {
+                    payload: index,
+                    expected: expected.copied(),
+                    actual,
+                };
+                for item in expected.iter() {
+                    match tracer.next().transpose() {
+                        Ok(Some(i)) if i == *item => (),
+                        Ok(i) => return Err(mismatch(Some(item), i)),
+                        Err(e) => return Err(Error::Tracer(index, e)),
+                    }
+                }
+                match tracer.next().transpose() {
+                    Ok(None) => Ok(()),
+                    Ok(i) => Err(mismatch(None, i)),
+                    Err(e) => Err(Error::Tracer(index, e)),
+                }
+            })
+    }
+}
+
+/// Errors resulting from [`Fixture::verify`]
+#[derive(Debug, PartialEq)]
+pub enum Error {
+    /// The tracer could not be built
+    Build(tracer::error::Error<NoInstruction>),
+    /// The tracer reported an error while processing the payload with the index
+    Tracer(usize, tracer::error::Error<NoInstruction>),
+    /// The tracer yielded an unexpected [`Item`]
+    Mismatch {
+        /// Index of the payload after which the mismatch occurred
+        payload: usize,
+        /// The expected [`Item`], if any
+        expected: Option<Item>,
+        /// The [`Item`] yielded by the tracer, if any
+        actual: Option<Item>,
+    },
+}
+
+impl core::error::Error for Error {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Build(inner) => Some(inner),
+            Self::Tracer(_, inner) => Some(inner),
+            Self::Mismatch { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Build(_) => write!(f, "could not build tracer"),
+            Self::Tracer(index, _) => write!(f, "could not process payload {index}"),
+            Self::Mismatch {
+                payload,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "expected {expected:?} but got {actual:?} after payload {payload}"
+            ),
+        }
+    }
+}
+
+/// All fixtures defined in this module
+pub static ALL: [&Fixture; 4] = [
+    &DEBUG_PRINTF,
+    &EXITING_FROM_FUNC_2,
+    &THREE_BRANCHES,
+    &COMPLEX,
+];
+
+/// Call of and return from `debug_printf` (examples 1 and 2)
+pub static DEBUG_PRINTF: Fixture = Fixture {
+    name: "debug_printf",
+    program: &[
+        // debug_printf:
+        (0x80001178, COMPRESSED),
+        (0x8000117a, COMPRESSED),
+        (0x8000117c, COMPRESSED),
+        (0x8000117e, COMPRESSED),
+        (0x80001180, compressed(Kind::new_c_jr(1))),
+        // main:
+        (0x80001a80, UNCOMPRESSED),
+        // Call debug_printf
+        (0x80001a84, normal(Kind::new_jal(1, -0x90c))),
+        (0x80001a88, UNCOMPRESSED),
+    ],
+    payloads: &[start(0x80001a80), address(0x80001a88 - 0x80001a80)],
+    items: &[
+        &[
+            context(0x80001a80),
+            Item::new(0x80001a80, regular(UNCOMPRESSED)),
+        ],
+        &[
+            Item::new(0x80001a84, regular(normal(Kind::new_jal(1, -0x90c)))),
+            Item::new(0x80001178, regular(COMPRESSED)),
+            Item::new(0x8000117a, regular(COMPRESSED)),
+            Item::new(0x8000117c, regular(COMPRESSED)),
+            Item::new(0x8000117e, regular(COMPRESSED)),
+            Item::new(0x80001180, regular(compressed(Kind::new_c_jr(1)))),
+            Item::new(0x80001a88, regular(UNCOMPRESSED)),
+        ],
+    ],
+};
+
+/// Exiting from `Func_2` (example 3)
+pub static EXITING_FROM_FUNC_2: Fixture = Fixture {
+    name: "exiting_from_func_2",
+    program: &[
+        // Func_2:
+        (0x800010da, COMPRESSED),
+        (0x800010dc, normal(Kind::new_bge(0, 10, 0x008))),
+        (0x800010e0, COMPRESSED),
+        (0x800010e2, COMPRESSED),
+        (0x800010e4, COMPRESSED),
+        (0x800010e6, COMPRESSED),
+        (0x800010e8, compressed(Kind::new_c_jr(1))),
+        // main:
+        (0x80001b8a, UNCOMPRESSED),
+    ],
+    payloads: &[start(0x800010da), branches(1, 0, 0xab0)],
+    items: &[
+        &[
+            context(0x800010da),
+            Item::new(0x800010da, regular(COMPRESSED)),
+        ],
+        &[
+            Item::new(0x800010dc, regular(normal(Kind::new_bge(0, 10, 0x008)))),
+            Item::new(0x800010e4, regular(COMPRESSED)),
+            Item::new(0x800010e6, regular(COMPRESSED)),
+            Item::new(0x800010e8, regular(compressed(Kind::new_c_jr(1)))),
+            Item::new(0x80001b8a, regular(UNCOMPRESSED)),
+        ],
+    ],
+};
+
+/// Three branches in `Proc_6` (example 4)
+pub static THREE_BRANCHES: Fixture = Fixture {
+    name: "three_branches",
+    program: &[
+        // Proc_6:
+        (0x80001110, COMPRESSED),
+        (0x80001112, COMPRESSED),
+        (0x80001114, COMPRESSED),
+        (0x80001116, normal(Kind::new_beq(8, 15, 0x028))),
+        (0x8000111a, compressed(Kind::new_c_beqz(8, 0x036))),
+        (0x8000111c, COMPRESSED),
+        (0x8000111e, normal(Kind::new_beq(8, 14, 0x040))),
+        (0x8000115e, COMPRESSED),
+        (0x80001160, COMPRESSED),
+        (0x80001162, compressed(Kind::new_c_jr(1))),
+        // Proc_1:
+        (0x80001258, UNCOMPRESSED),
+    ],
+    payloads: &[start(0x80001110), branches(3, 0b011, 0x148)],
+    items: &[
+        &[
+            context(0x80001110),
+            Item::new(0x80001110, regular(COMPRESSED)),
+        ],
+        &[
+            Item::new(0x80001112, regular(COMPRESSED)),
+            Item::new(0x80001114, regular(COMPRESSED)),
+            Item::new(0x80001116, regular(normal(Kind::new_beq(8, 15, 0x028)))),
+            Item::new(0x8000111a, regular(compressed(Kind::new_c_beqz(8, 0x036)))),
+            Item::new(0x8000111c, regular(COMPRESSED)),
+            Item::new(0x8000111e, regular(normal(Kind::new_beq(8, 14, 0x040)))),
+            Item::new(0x8000115e, regular(COMPRESSED)),
+            Item::new(0x80001160, regular(COMPRESSED)),
+            Item::new(0x80001162, regular(compressed(Kind::new_c_jr(1)))),
+            Item::new(0x80001258, regular(UNCOMPRESSED)),
+        ],
+    ],
+};
+
+/// Calls across `Proc_1`, `Proc_6` and `Func_3` (example 5)
+pub static COMPLEX: Fixture = Fixture {
+    name: "complex",
+    program: &[
+        // Func_3:
+        (0x800010f8, COMPRESSED),
+        (0x800010fa, UNCOMPRESSED),
+        (0x800010fe, compressed(Kind::new_c_jr(1))),
+        // Proc_6:
+        (0x80001100, COMPRESSED),
+        (0x80001102, COMPRESSED),
+        (0x80001104, COMPRESSED),
+        (0x80001106, COMPRESSED),
+        (0x80001108, COMPRESSED),
+        (0x8000110a, COMPRESSED),
+        // Call Func_3
+        (0x8000110c, normal(Kind::new_jal(1, -0x014))),
+        (0x80001110, compressed(Kind::new_c_beqz(10, 0x024))),
+        (0x80001112, COMPRESSED),
+        // Proc_1:
+        (0x8000121c, COMPRESSED),
+        (0x8000121e, compressed(Kind::new_c_beqz(15, 0x02c))),
+        (0x8000124a, COMPRESSED),
+        (0x8000124c, COMPRESSED),
+        (0x8000124e, UNCOMPRESSED),
+        (0x80001252, COMPRESSED),
+        // Call Proc_6
+        (0x80001254, normal(Kind::new_jal(1, -0x154))),
+    ],
+    payloads: &[start(0x8000121c), branches(2, 0b10, 0x1110 - 0x121c)],
+    items: &[
+        &[
+            context(0x8000121c),
+            Item::new(0x8000121c, regular(COMPRESSED)),
+        ],
+        &[
+            Item::new(0x8000121e, regular(compressed(Kind::new_c_beqz(15, 0x02c)))),
+            Item::new(0x8000124a, regular(COMPRESSED)),
+            Item::new(0x8000124c, regular(COMPRESSED)),
+            Item::new(0x8000124e, regular(UNCOMPRESSED)),
+            Item::new(0x80001252, regular(COMPRESSED)),
+            Item::new(0x80001254, regular(normal(Kind::new_jal(1, -0x154)))),
+            Item::new(0x80001100, regular(COMPRESSED)),
+            Item::new(0x80001102, regular(COMPRESSED)),
+            Item::new(0x80001104, regular(COMPRESSED)),
+            Item::new(0x80001106, regular(COMPRESSED)),
+            Item::new(0x80001108, regular(COMPRESSED)),
+            Item::new(0x8000110a, regular(COMPRESSED)),
+            Item::new(0x8000110c, regular(normal(Kind::new_jal(1, -0x014)))),
+            Item::new(0x800010f8, regular(COMPRESSED)),
+            Item::new(0x800010fa, regular(UNCOMPRESSED)),
+            Item::new(0x800010fe, regular(compressed(Kind::new_c_jr(1)))),
+            Item::new(0x80001110, regular(compressed(Kind::new_c_beqz(10, 0x024)))),
+        ],
+    ],
+};
+
+/// Create a 32bit [`Instruction`] from a [`Kind`]
+const fn normal(kind: Kind) -> Instruction {
+    Instruction {
+        size: Size::Normal,
+        info: Some(kind),
+    }
+}
+
+/// Create a 16bit [`Instruction`] from a [`Kind`]
+const fn compressed(kind: Kind) -> Instruction {
+    Instruction {
+        size: Size::Compressed,
+        info: Some(kind),
+    }
+}
+
+/// Create an [`item::Kind::Regular`] for the given [`Instruction`]
+const fn regular(insn: Instruction) -> item::Kind {
+    item::Kind::Regular(insn)
+}
+
+/// Create a context [`Item`] for the default [`Context`]
+const fn context(pc: u64) -> Item {
+    let ctx = Context {
+        privilege: Privilege::User,
+        context: 0,
+    };
+    Item::new(pc, item::Kind::Context(ctx))
+}
+
+/// Create a [`sync::Start`] payload for the given address
+const fn start(address: u64) -> InstructionTrace {
+    let start = sync::Start {
+        branch: true,
+        ctx: sync::Context {
+            privilege: Privilege::User,
+            time: None,
+            context: 0,
+        },
+        address,
+    };
+    InstructionTrace::Synchronization(sync::Synchronization::Start(start))
+}
+
+/// Create an address-only payload for the given differential address
+const fn address(address: i64) -> InstructionTrace {
+    InstructionTrace::Address(address_info(address))
+}
+
+/// Create a branch payload with the given branches and differential address
+const fn branches(count: u8, map: u32, address: i64) -> InstructionTrace {
+    InstructionTrace::Branch(payload::Branch {
+        branch_map: branch::Map::new(count, map),
+        address: Some(address_info(address)),
+    })
+}
+
+/// Create [`payload::AddressInfo`] for the given differential address
+const fn address_info(address: i64) -> payload::AddressInfo {
+    payload::AddressInfo {
+        address,
+        notify: false,
+        updiscon: false,
+        irdepth: None,
+    }
+}
@@ -7,6 +7,7 @@
 
 pub mod error;
 pub mod hart2enc;
+pub mod model;
 pub mod state;
 pub mod step;
 
@@ -1,9 +1,10 @@
-// Copyright (C) 2025 FZI Forschungszentrum Informatik
+// Copyright (C) 2025, 2026 FZI Forschungszentrum Informatik
 // SPDX-License-Identifier: Apache-2.0
 //! Payload generation errors
 
 use core::fmt;
 
+use crate::packet;
 use crate::types::branch;
 
 /// Errors that may be emitted during payload generation
@@ -17,12 +18,15 @@ pub enum Error {
     CannotAddBranches(branch::Error),
     /// The branch map is empty when it should not be
     BranchMapEmpty,
+    /// A payload could not be encoded
+    CannotEncode(packet::Error),
 }
 
 impl core::error::Error for Error {
     fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
         match self {
             Self::CannotAddBranches(inner) => Some(inner),
+            Self::CannotEncode(inner) => Some(inner),
             _ => None,
         }
     }
@@ -35,6 +39,7 @@ impl fmt::Display for Error {
             Self::UnsupportedFeature(feat) => write!(f, "feature \"{feat}\" not supported"),
             Self::CannotAddBranches(_) => write!(f, "cannot add branches to branch map"),
             Self::BranchMapEmpty => write!(f, "the branch map is unexpectedly empty"),
+            Self::CannotEncode(_) => write!(f, "cannot encode payload"),
         }
     }
 }
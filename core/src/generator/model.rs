@@ -0,0 +1,336 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Encoder reference model
+//!
+//! This module provides a [`Model`] of a complete trace encoder, which turns a
+//! stream of [`Step`]s into encoded packets. In addition to the payload
+//! generation performed by a [`Generator`], the model maintains a resync
+//! counter and encodes payloads into packets of a given [`Format`]. It is
+//! intended as a reference for diffing the output of hardware encoders
+//! packet-by-packet.
+
+use core::num::NonZeroU64;
+
+use crate::packet::encoder::Encode;
+use crate::packet::payload::{InstructionTrace, Payload};
+use crate::packet::{self, encap, smi, unit};
+
+use super::error::Error;
+use super::step::Step;
+use super::{Event, Generator};
+
+/// Maximum size of a single encoded packet
+const MAX_PACKET_SIZE: usize = 64;
+
+/// Model of a complete trace encoder
+///
+/// A model processes [`Step`]s via a [`Generator`]. Every payload generated is
+/// encoded into a packet of the configured [`Format`]. The payload and the
+/// encoded packet are then passed to a callback.
+///
+/// Synchronization is triggered periodically based on the [`Resync`] policy.
+///
+/// # Example
+///
+/// ```
+/// use riscv_etrace_core::generator::{self, model, step};
+/// use riscv_etrace_core::packet;
+/// # use riscv_etrace_core::generator::hart2enc::CType;
+/// # use riscv_etrace_core::instruction::Size;
+/// # use riscv_etrace_core::types::Context;
+///
+/// # #[derive(Copy, Clone)]
+/// # struct Retirement(u64);
+/// #
+/// # impl step::Step for Retirement {
+/// #     fn address(&self) -> u64 { self.0 }
+/// #     fn kind(&self) -> step::Kind {
+/// #         step::Kind::Retirement { insn_size: Size::Normal }
+/// #     }
+/// #     fn ctype(&self) -> CType { Default::default() }
+/// #     fn context(&self) -> Context { Default::default() }
+/// # }
+/// #
+/// let generator = generator::builder().build().unwrap();
+/// let mut model = model::Model::new(generator, packet::builder())
+///     .with_resync(model::Resync::Steps(2.try_into().unwrap()));
+///
+/// let mut packets = 0;
+/// let mut count = |_: &_, _: &[u8]| packets += 1;
+/// model
+///     .begin_qualification(Default::default(), Default::default(), &mut count)
+///     .unwrap();
+/// for i in 0..8 {
+///     let step = Retirement(0x80000000 + 4 * i);
+///     model.process_step(step, false, &mut count).unwrap();
+/// }
+/// model.end_qualification(true, &mut count).unwrap();
+/// assert!(packets > 2);
+/// ```
+pub struct Model<S, U = unit::Reference>
+where
+    S: Step,
+    U: unit::Unit,
+{
+    generator: Generator<S, U::IOptions, U::DOptions>,
+    packets: packet::Builder<U>,
+    format: Format,
+    resync: Resync,
+    counter: u64,
+    last_sync: Option<u64>,
+    held: Option<u64>,
+    pending: bool,
+}
+
+impl<S, U> Model<S, U>
+where
+    S: Step + Clone,
+    U: unit::Unit + Clone,
+    U::IOptions: Clone + for<'d> Encode<'d, U>,
+    U::DOptions: Clone + for<'d> Encode<'d, U>,
+{
+    /// Create a new model from a [`Generator`] and a [`packet::Builder`]
+    ///
+    /// New models emit [`Format::Encap`] packets for the hart `0` and never
+    /// trigger a resync.
+    pub fn new(
+        generator: Generator<S, U::IOptions, U::DOptions>,
+        packets: packet::Builder<U>,
+    ) -> Self {
+        Self {
+            generator,
+            packets,
+            format: Default::default(),
+            resync: Default::default(),
+            counter: 0,
+            last_sync: None,
+            held: None,
+            pending: false,
+        }
+    }
+
+    /// Emit packets of the given [`Format`]
+    pub fn with_format(self, format: Format) -> Self {
+        Self { format, ..self }
+    }
+
+    /// Trigger resyncs according to the given [`Resync`] policy
+    pub fn with_resync(self, resync: Resync) -> Self {
+        Self { resync, ..self }
+    }
+
+    /// Begin qualification
+    ///
+    /// Generates a [`sync::Support`][packet::sync::Support] payload via
+    /// [`Generator::begin_qualification`] and emits it.
+    pub fn begin_qualification(
+        &mut self,
+        ioptions: U::IOptions,
+        doptions: U::DOptions,
+        f: impl FnMut(&InstructionTrace<U::IOptions, U::DOptions>, &[u8]),
+    ) -> Result<(), Error> {
+        let payload = self
+            .generator
+            .begin_qualification(ioptions, doptions)?
+            .into();
+        self.emit(payload, None, f)
+    }
+
+    /// Process a single [`Step`]
+    ///
+    /// Emits the payloads generated for the given step. Notification is
+    /// requested for the step if `notify` is `true`. Returns the number of
+    /// payloads emitted.
+    pub fn process_step(
+        &mut self,
+        step: S,
+        notify: bool,
+        mut f: impl FnMut(&InstructionTrace<U::IOptions, U::DOptions>, &[u8]),
+    ) -> Result<usize, Error> {
+        // Payloads generated while processing a step relate to the previous one
+        let timestamp = self.held;
+        self.held = step.timestamp();
+        let resync = !self.pending
+            && match self.resync {
+                Resync::Never => false,
+                Resync::Steps(max) | Resync::Payloads(max) | Resync::Instructions(max) => {
+                    self.counter >= max.get()
+                }
+                Resync::Time(period) => Option::zip(self.held, self.last_sync)
+                    .is_some_and(|(t, l)| t.saturating_sub(l) >= period.get()),
+            };
+        let increment = match self.resync {
+            Resync::Steps(_) => 1,
+            Resync::Instructions(_) => step.instruction_count(),
+            _ => 0,
+        };
+
+        let event = if resync {
+            self.pending = true;
+            Some(Event::ReSync)
+        } else if notify {
+            Some(Event::Notify)
+        } else {
+            None
+        };
+
+        let mut emitted = 0;
+        let Self {
+            generator,
+            packets,
+            format,
+            ..
+        } = self;
+        let mut payloads = generator.process_step(step, event);
+        payloads.try_for_each(|p| {
+            let payload = p?;
+            if matches!(payload, InstructionTrace::Synchronization(_)) {
+                self.counter = 0;
+                self.pending = false;
+                self.last_sync = timestamp.or(self.last_sync);
+            }
+            emitted += 1;
+            emit(packets, *format, payload, timestamp, &mut f)
+        })?;
+
+        let increment = match self.resync {
+            Resync::Payloads(_) => emitted as u64,
+            _ => increment,
+        };
+        self.counter = self.counter.saturating_add(increment);
+        Ok(emitted)
+    }
+
+    /// End qualification
+    ///
+    /// Emits any pending payloads, including a [`sync::Support`] payload with
+    /// the given `ienable` value, via [`Generator::end_qualification`]. Returns
+    /// the number of payloads emitted.
+    ///
+    /// [`sync::Support`]: packet::sync::Support
+    pub fn end_qualification(
+        &mut self,
+        ienable: bool,
+        mut f: impl FnMut(&InstructionTrace<U::IOptions, U::DOptions>, &[u8]),
+    ) -> Result<usize, Error> {
+        let Self {
+            generator,
+            packets,
+            format,
+            ..
+        } = self;
+        let timestamp = self.held.take();
+        generator.end_qualification(ienable).try_fold(0, |n, p| {
+            emit(packets, *format, p?, timestamp, &mut f).map(|_| n + 1)
+        })
+    }
+
+    /// Retrieve the current value of the resync counter
+    ///
+    /// The counter is not used for [`Resync::Time`].
+    pub fn resync_counter(&self) -> u64 {
+        self.counter
+    }
+
+    /// Retrieve the [`Format`] of emitted packets
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Encode and emit a single payload
+    fn emit(
+        &self,
+        payload: InstructionTrace<U::IOptions, U::DOptions>,
+        timestamp: Option<u64>,
+        f: impl FnMut(&InstructionTrace<U::IOptions, U::DOptions>, &[u8]),
+    ) -> Result<(), Error> {
+        emit(&self.packets, self.format, payload, timestamp, f)
+    }
+}
+
+/// Encode a single payload as a packet and pass it to the callback
+fn emit<U>(
+    packets: &packet::Builder<U>,
+    format: Format,
+    payload: InstructionTrace<U::IOptions, U::DOptions>,
+    timestamp: Option<u64>,
+    mut f: impl FnMut(&InstructionTrace<U::IOptions, U::DOptions>, &[u8]),
+) -> Result<(), Error>
+where
+    U: unit::Unit + Clone,
+    U::IOptions: Clone + for<'d> Encode<'d, U>,
+    U::DOptions: Clone + for<'d> Encode<'d, U>,
+{
+    let mut buffer = [0u8; MAX_PACKET_SIZE];
+    let mut encoder = packets.clone().encoder(buffer.as_mut());
+    match format {
+        Format::Encap { flow, src_id } => {
+            let packet = encap::Normal::new(flow, src_id, Payload::from(payload.clone()));
+            let packet = match timestamp {
+                Some(timestamp) => packet.with_timestamp(timestamp),
+                None => packet,
+            };
+            encoder.encode(&encap::Packet::from(packet))
+        }
+        Format::Smi { trace_type, hart } => {
+            let packet = smi::Packet::new(trace_type, hart, payload.clone());
+            let packet = match timestamp {
+                Some(timestamp) => packet.with_time_tag(timestamp as u16),
+                None => packet,
+            };
+            encoder.encode(&packet)
+        }
+    }
+    .map_err(Error::CannotEncode)?;
+    let len = MAX_PACKET_SIZE - encoder.uncommitted();
+
+    f(&payload, &buffer[..len]);
+    Ok(())
+}
+
+/// Format of packets emitted by a [`Model`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Format {
+    /// [`encap::Packet`]s with the given flow and source id
+    ///
+    /// The timestamp of the [`Step`] is included if present.
+    Encap { flow: u8, src_id: u16 },
+    /// [`smi::Packet`]s with the given trace type and hart index
+    ///
+    /// The lower 16 bits of the timestamp of the [`Step`] are included as time
+    /// tag if present.
+    Smi { trace_type: u8, hart: u64 },
+}
+
+impl Default for Format {
+    fn default() -> Self {
+        Self::Encap { flow: 0, src_id: 0 }
+    }
+}
+
+/// Policy for triggering resyncs
+///
+/// For policies based on counting, the resync counter is reset whenever a
+/// synchronization payload is emitted. When it reaches the configured maximum,
+/// a resync is triggered for the next [`Step`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Resync {
+    /// Never trigger a resync
+    #[default]
+    Never,
+    /// Count [`Step`]s
+    Steps(NonZeroU64),
+    /// Count emitted payloads
+    Payloads(NonZeroU64),
+    /// Count retired instructions
+    ///
+    /// Instructions are counted via [`Step::instruction_count`].
+    Instructions(NonZeroU64),
+    /// Trigger a resync after the given period of time
+    ///
+    /// A resync is triggered for the first [`Step`] with a
+    /// [timestamp][Step::timestamp] at least the given period after that of
+    /// the step for which the last synchronization payload was emitted. This
+    /// policy has no effect for [`Step`]s without timestamps.
+    Time(NonZeroU64),
+}
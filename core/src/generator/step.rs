@@ -59,6 +59,19 @@ pub trait Step {
         self.last_offset() == 0
     }
 
+    /// Number of instructions retired in this step
+    ///
+    /// The default implementation assumes that steps retiring instructions
+    /// retire a single one. Implementations of blocks should override it.
+    fn instruction_count(&self) -> u64 {
+        match self.kind() {
+            Kind::Trap {
+                insn_size: None, ..
+            } => 0,
+            _ => 1,
+        }
+    }
+
     /// Refine this step's data with information from the next step
     fn refine(&mut self, _next: &Self) {}
 }
@@ -24,6 +24,8 @@ pub mod bits;
 pub mod decode;
 pub mod format;
 pub mod info;
+#[cfg(feature = "riscv-isa")]
+pub mod isa;
 
 #[cfg(test)]
 mod tests;
@@ -38,6 +40,7 @@ use info::Info;
 /// Specific [`Instruction`] kinds relevant for tracing
 #[allow(non_camel_case_types)]
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum Kind {
     // SYS (R)
     mret,
@@ -86,83 +89,83 @@ pub enum Kind {
 /// Construction
 impl Kind {
     /// Create a `beq` instruction
-    pub fn new_beq(rs1: Register, rs2: Register, imm: i16) -> Self {
+    pub const fn new_beq(rs1: Register, rs2: Register, imm: i16) -> Self {
         Self::beq(format::TypeB { rs1, rs2, imm })
     }
 
     /// Create a `bne` instruction
-    pub fn new_bne(rs1: Register, rs2: Register, imm: i16) -> Self {
+    pub const fn new_bne(rs1: Register, rs2: Register, imm: i16) -> Self {
         Self::bne(format::TypeB { rs1, rs2, imm })
     }
 
     /// Create a `blt` instruction
-    pub fn new_blt(rs1: Register, rs2: Register, imm: i16) -> Self {
+    pub const fn new_blt(rs1: Register, rs2: Register, imm: i16) -> Self {
         Self::blt(format::TypeB { rs1, rs2, imm })
     }
 
     /// Create a `bge` instruction
-    pub fn new_bge(rs1: Register, rs2: Register, imm: i16) -> Self {
+    pub const fn new_bge(rs1: Register, rs2: Register, imm: i16) -> Self {
         Self::bge(format::TypeB { rs1, rs2, imm })
     }
 
     /// Create a `bltu` instruction
-    pub fn new_bltu(rs1: Register, rs2: Register, imm: i16) -> Self {
+    pub const fn new_bltu(rs1: Register, rs2: Register, imm: i16) -> Self {
         Self::bltu(format::TypeB { rs1, rs2, imm })
     }
 
     /// Create a `bgeu` instruction
-    pub fn new_bgeu(rs1: Register, rs2: Register, imm: i16) -> Self {
+    pub const fn new_bgeu(rs1: Register, rs2: Register, imm: i16) -> Self {
         Self::bgeu(format::TypeB { rs1, rs2, imm })
     }
 
     /// Create an `auipc` instruction
-    pub fn new_auipc(rd: Register, imm: i32) -> Self {
+    pub const fn new_auipc(rd: Register, imm: i32) -> Self {
         Self::auipc(format::TypeU { rd, imm })
     }
 
     /// Create a `lui` instruction
-    pub fn new_lui(rd: Register, imm: i32) -> Self {
+    pub const fn new_lui(rd: Register, imm: i32) -> Self {
         Self::lui(format::TypeU { rd, imm })
     }
 
     /// Create a `c.beqz` instruction
-    pub fn new_c_beqz(rs1: Register, imm: i16) -> Self {
+    pub const fn new_c_beqz(rs1: Register, imm: i16) -> Self {
         Self::c_beqz(format::TypeB { rs1, rs2: 0, imm })
     }
 
     /// Create a `c.bnez` instruction
-    pub fn new_c_bnez(rs1: Register, imm: i16) -> Self {
+    pub const fn new_c_bnez(rs1: Register, imm: i16) -> Self {
         Self::c_bnez(format::TypeB { rs1, rs2: 0, imm })
     }
 
     /// Create a `jal` instruction
-    pub fn new_jal(rd: Register, imm: i32) -> Self {
+    pub const fn new_jal(rd: Register, imm: i32) -> Self {
         Self::jal(format::TypeJ { rd, imm })
     }
 
     /// Create a `c.j` instruction
-    pub fn new_c_j(rd: Register, imm: i16) -> Self {
+    pub const fn new_c_j(rd: Register, imm: i16) -> Self {
         Self::c_j(format::TypeJ {
             rd,
-            imm: imm.into(),
+            imm: imm as i32,
         })
     }
 
     /// Create a `c.jal` instruction
-    pub fn new_c_jal(rd: Register, imm: i16) -> Self {
+    pub const fn new_c_jal(rd: Register, imm: i16) -> Self {
         Self::c_jal(format::TypeJ {
             rd,
-            imm: imm.into(),
+            imm: imm as i32,
         })
     }
 
     /// Create a `c.lui` instruction
-    pub fn new_c_lui(rd: Register, imm: i32) -> Self {
+    pub const fn new_c_lui(rd: Register, imm: i32) -> Self {
         Self::c_lui(format::TypeU { rd, imm })
     }
 
     /// Create a `c.jr` instruction
-    pub fn new_c_jr(rd: Register) -> Self {
+    pub const fn new_c_jr(rd: Register) -> Self {
         Self::c_jr(format::TypeR {
             rd,
             rs1: rd,
@@ -171,7 +174,7 @@ impl Kind {
     }
 
     /// Create a `c.jalr` instruction
-    pub fn new_c_jalr(rd: Register) -> Self {
+    pub const fn new_c_jalr(rd: Register) -> Self {
         Self::c_jalr(format::TypeR {
             rd,
             rs1: rd,
@@ -180,11 +183,70 @@ impl Kind {
     }
 
     /// Create a `jalr` instruction
-    pub fn new_jalr(rd: Register, rs1: Register, imm: i16) -> Self {
+    pub const fn new_jalr(rd: Register, rs1: Register, imm: i16) -> Self {
         Self::jalr(format::TypeI { rd, rs1, imm })
     }
 }
 
+/// Operands
+///
+/// These fns report the operands of control transfer and upper immediate
+/// instructions, which are the only ones for which [`Kind`] retains operands.
+/// Operands which are implicit in compressed instructions are reported as if
+/// the instruction was expanded, e.g. `c.jal` reports `x1` as the destination
+/// register.
+impl Kind {
+    /// Retrieve the destination register, if any
+    pub fn rd(&self) -> Option<Register> {
+        match self {
+            Self::auipc(d) | Self::lui(d) | Self::c_lui(d) => Some(d.rd),
+            Self::jal(d) => Some(d.rd),
+            Self::jalr(d) => Some(d.rd),
+            Self::c_j(_) | Self::c_jr(_) => Some(0),
+            Self::c_jal(_) | Self::c_jalr(_) => Some(1),
+            _ => None,
+        }
+    }
+
+    /// Retrieve the first source register, if any
+    pub fn rs1(&self) -> Option<Register> {
+        match self {
+            Self::beq(d) | Self::bne(d) | Self::blt(d) | Self::bge(d) => Some(d.rs1),
+            Self::bltu(d) | Self::bgeu(d) | Self::c_beqz(d) | Self::c_bnez(d) => Some(d.rs1),
+            Self::jalr(d) => Some(d.rs1),
+            Self::c_jr(d) | Self::c_jalr(d) => Some(d.rs1),
+            _ => None,
+        }
+    }
+
+    /// Retrieve the second source register, if any
+    pub fn rs2(&self) -> Option<Register> {
+        match self {
+            Self::beq(d) | Self::bne(d) | Self::blt(d) | Self::bge(d) => Some(d.rs2),
+            Self::bltu(d) | Self::bgeu(d) => Some(d.rs2),
+            Self::c_beqz(_) | Self::c_bnez(_) => Some(0),
+            _ => None,
+        }
+    }
+
+    /// Retrieve the immediate, if any
+    ///
+    /// The immediate is reported in its effective form, i.e. sign-extended
+    /// and scaled. For branches and jumps, this is the offset. For `lui`,
+    /// `auipc` and `c.lui`, the value shifted into the upper bits is reported.
+    pub fn imm(&self) -> Option<i32> {
+        match self {
+            Self::beq(d) | Self::bne(d) | Self::blt(d) | Self::bge(d) => Some(d.imm.into()),
+            Self::bltu(d) | Self::bgeu(d) | Self::c_beqz(d) | Self::c_bnez(d) => Some(d.imm.into()),
+            Self::auipc(d) | Self::lui(d) | Self::c_lui(d) => Some(d.imm),
+            Self::jal(d) | Self::c_j(d) | Self::c_jal(d) => Some(d.imm),
+            Self::jalr(d) => Some(d.imm.into()),
+            Self::c_jr(_) | Self::c_jalr(_) => Some(0),
+            _ => None,
+        }
+    }
+}
+
 impl Info for Kind {
     type Register = Register;
 
@@ -259,6 +321,10 @@ impl Info for Kind {
         }
     }
 
+    fn is_wfi(&self) -> bool {
+        matches!(self, Self::wfi)
+    }
+
     fn ignored() -> Self {
         Self::nop
     }
@@ -349,6 +415,18 @@ impl<I: Info> Instruction<I> {
         Bits::extract(data).map(|(b, r)| (Self::decode(b, base), r))
     }
 
+    /// Extract an instruction from a raw byte slice
+    ///
+    /// Like [`extract`][Self::extract], but reports the number of bytes
+    /// missing if the slice ends in the middle of an instruction. See
+    /// [`Bits::try_extract`] for details.
+    pub fn try_extract<'d, D: Decode<I>>(
+        data: &'d [u8],
+        base: &D,
+    ) -> Result<(Self, &'d [u8]), bits::ExtractError> {
+        Bits::try_extract(data).map(|(b, r)| (Self::decode(b, base), r))
+    }
+
     /// Decode an instruction from the given [`Bits`]
     ///
     /// Decode the given [`Bits`] into an [`Instruction`] using the given
@@ -425,6 +503,14 @@ impl<I: Info> Info for Instruction<I> {
         self.info.is_return()
     }
 
+    fn is_unknown(&self) -> bool {
+        self.info.is_unknown()
+    }
+
+    fn is_wfi(&self) -> bool {
+        self.info.is_wfi()
+    }
+
     fn ignored() -> Self {
         Self {
             info: I::ignored(),
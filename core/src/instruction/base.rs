@@ -110,6 +110,10 @@ impl decode::Decode<Option<Kind>> for Set {
     fn decode_64(&self, _insn: u64) -> Option<Kind> {
         None
     }
+
+    fn base_set(&self) -> Option<Set> {
+        Some(*self)
+    }
 }
 
 impl decode::MakeDecode for Set {
@@ -0,0 +1,182 @@
+// Copyright (C) 2025, 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Utilities for dissecting a bunch of bytes into instruction [`Bits`]
+
+use core::fmt;
+use core::num::NonZeroUsize;
+
+use super::Size;
+
+/// Bits from which [`Instruction`][super::Instruction]s can be disassembled
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Bits {
+    Bit16(u16),
+    Bit32(u32),
+    Bit48(u64),
+    Bit64(u64),
+}
+
+impl Bits {
+    /// Extract [`Bits`] from a raw byte slice
+    ///
+    /// Try to extract [`Bits`] from the beginning of the given slice, honoring
+    /// the Base Instruction-Length Encoding specified in Section 1.5 of The
+    /// RISC-V Instruction Set Manual Volume I.
+    ///
+    /// Returns a tuple containing the [`Bits`] and the remaining part of the
+    /// slice if successful. Returns `None` if the beginning does not appear to
+    /// be either a 16 or 32 bit instruction, or if the slice does not contain
+    /// enough bytes. Use [`try_extract`][Self::try_extract] for
+    /// distinguishing the two.
+    pub fn extract(data: &[u8]) -> Option<(Self, &[u8])> {
+        Self::try_extract(data).ok()
+    }
+
+    /// Extract [`Bits`] from a raw byte slice
+    ///
+    /// Like [`extract`][Self::extract], but reports the number of bytes
+    /// missing if the slice ends in the middle of an instruction. This allows
+    /// fetching exactly the missing data, e.g. from a debug probe.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use riscv_etrace_core::instruction::bits::{Bits, ExtractError};
+    ///
+    /// let err = Bits::try_extract(&[0x97, 0x01]).unwrap_err();
+    /// assert_eq!(err, ExtractError::Truncated(2.try_into().unwrap()));
+    ///
+    /// let (bits, _) = Bits::try_extract(&[0x97, 0x01, 0x00, 0x00]).unwrap();
+    /// assert_eq!(bits, Bits::Bit32(0x197));
+    /// ```
+    pub fn try_extract(data: &[u8]) -> Result<(Self, &[u8]), ExtractError> {
+        let Some(first) = data.first() else {
+            return Err(ExtractError::Truncated(
+                const { NonZeroUsize::new(2).unwrap() },
+            ));
+        };
+        let len = Self::len_from_first_byte(*first).ok_or(ExtractError::UnsupportedLength)?;
+        let Some((insn, rest)) = data.split_at_checked(len) else {
+            let missing = NonZeroUsize::new(len - data.len());
+            return Err(ExtractError::Truncated(
+                missing.unwrap_or(NonZeroUsize::MIN),
+            ));
+        };
+        let mut bytes = [0u8; 8];
+        bytes[..len].copy_from_slice(insn);
+        let bits = match len {
+            2 => Self::Bit16(u16::from_le_bytes([bytes[0], bytes[1]])),
+            4 => Self::Bit32(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])),
+            6 => Self::Bit48(u64::from_le_bytes(bytes)),
+            _ => Self::Bit64(u64::from_le_bytes(bytes)),
+        };
+        Ok((bits, rest))
+    }
+
+    /// Determine the length in bytes of an instruction from its first byte
+    fn len_from_first_byte(byte: u8) -> Option<usize> {
+        if byte & 0b11 != 0b11 {
+            Some(2)
+        } else if byte & 0b11100 != 0b11100 {
+            Some(4)
+        } else if byte & 0x3f == 0x1f {
+            Some(6)
+        } else if byte & 0x7f == 0x3f {
+            Some(8)
+        } else {
+            None
+        }
+    }
+
+    /// Retrieve this instruction's [`Size`]
+    pub fn size(self) -> Size {
+        match self {
+            Self::Bit16(_) => Size::Compressed,
+            Self::Bit32(_) => Size::Normal,
+            Self::Bit48(_) => Size::Wide,
+            Self::Bit64(_) => Size::ExtraWide,
+        }
+    }
+}
+
+impl Default for Bits {
+    fn default() -> Self {
+        Self::Bit32(0)
+    }
+}
+
+impl TryFrom<u16> for Bits {
+    type Error = u16;
+
+    fn try_from(num: u16) -> Result<Self, Self::Error> {
+        if num & 0b11 != 0b11 {
+            Ok(Self::Bit16(num))
+        } else {
+            Err(num)
+        }
+    }
+}
+
+impl TryFrom<u32> for Bits {
+    type Error = u32;
+
+    fn try_from(num: u32) -> Result<Self, Self::Error> {
+        if num & 0b11 != 0b11 {
+            num.try_into().map(Self::Bit16).map_err(|_| num)
+        } else if num & 0b11100 != 0b11100 {
+            Ok(Self::Bit32(num))
+        } else {
+            Err(num)
+        }
+    }
+}
+
+impl TryFrom<u64> for Bits {
+    type Error = u64;
+
+    fn try_from(num: u64) -> Result<Self, Self::Error> {
+        if num & 0x3f == 0x1f && num >> 48 == 0 {
+            Ok(Self::Bit48(num))
+        } else if num & 0x7f == 0x3f {
+            Ok(Self::Bit64(num))
+        } else {
+            num.try_into()
+                .map_err(|_| num)
+                .and_then(|n: u32| n.try_into().map_err(Into::into))
+        }
+    }
+}
+
+impl fmt::Display for Bits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Bit16(v) => write!(f, "{v:04x}"),
+            Self::Bit32(v) => write!(f, "{v:08x}"),
+            Self::Bit48(v) => write!(f, "{v:012x}"),
+            Self::Bit64(v) => write!(f, "{v:016x}"),
+        }
+    }
+}
+
+/// Error returned by [`Bits::try_extract`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ExtractError {
+    /// The data ends before the end of the instruction
+    ///
+    /// The number of bytes missing is exact, except for empty data. For those,
+    /// the minimum size of an instruction is reported.
+    Truncated(NonZeroUsize),
+    /// The instruction's length is not supported
+    UnsupportedLength,
+}
+
+impl core::error::Error for ExtractError {}
+
+impl fmt::Display for ExtractError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Truncated(n) => write!(f, "instruction truncated, {n} more bytes needed"),
+            Self::UnsupportedLength => write!(f, "unsupported instruction length"),
+        }
+    }
+}
@@ -4,6 +4,7 @@
 
 use crate::config::Parameters;
 
+use super::base;
 use super::bits::Bits;
 use super::info::Info;
 
@@ -21,6 +22,13 @@ pub trait Decode<I: Info> {
     /// Decode a 64bit instruction [`Info`]
     fn decode_64(&self, insn: u64) -> I;
 
+    /// Retrieve the [`base::Set`] instructions are decoded for, if known
+    ///
+    /// The default implementation returns [`None`].
+    fn base_set(&self) -> Option<base::Set> {
+        None
+    }
+
     /// Decode instruction [`Info`] from [`Bits`]
     fn decode_bits(&self, bits: Bits) -> I {
         match bits {
@@ -53,6 +61,10 @@ where
     fn decode_64(&self, insn: u64) -> (I, Bits) {
         (self.decode_64(insn), Bits::Bit64(insn))
     }
+
+    fn base_set(&self) -> Option<base::Set> {
+        Decode::<I>::base_set(self)
+    }
 }
 
 #[cfg(feature = "riscv-isa")]
@@ -82,6 +94,10 @@ impl Decode<riscv_isa::Instruction> for riscv_isa::Target {
     fn decode_64(&self, _insn: u64) -> riscv_isa::Instruction {
         riscv_isa::Instruction::UNIMP
     }
+
+    fn base_set(&self) -> Option<base::Set> {
+        Some((*self).into())
+    }
 }
 
 #[cfg(feature = "riscv-isa")]
@@ -110,6 +126,10 @@ impl Decode<riscv_isa::Compressed> for riscv_isa::Target {
     fn decode_64(&self, _insn: u64) -> riscv_isa::Compressed {
         riscv_isa::Compressed::UNIMP
     }
+
+    fn base_set(&self) -> Option<base::Set> {
+        Some((*self).into())
+    }
 }
 
 #[cfg(all(feature = "either", feature = "riscv-isa"))]
@@ -141,6 +161,10 @@ impl Decode<either::Either<riscv_isa::Compressed, riscv_isa::Instruction>> for r
     ) -> either::Either<riscv_isa::Compressed, riscv_isa::Instruction> {
         either::Right(self.decode_64(insn))
     }
+
+    fn base_set(&self) -> Option<base::Set> {
+        Some((*self).into())
+    }
 }
 
 /// Make a [`Decode`]
@@ -127,6 +127,27 @@ pub trait Info: Send + Sync {
         self.is_uninferable_jump() || self.is_return_from_trap() || self.is_ecall_or_ebreak()
     }
 
+    /// Determine whether this instruction is unknown
+    ///
+    /// Returns `true` if [`Self`] refers to an instruction which could not be
+    /// decoded, e.g. because its opcode is not supported. The tracing algorithm
+    /// treats such instructions as if they did not affect control flow.
+    ///
+    /// The default implementation returns `false`.
+    fn is_unknown(&self) -> bool {
+        false
+    }
+
+    /// Determine whether this instruction is a `wfi`
+    ///
+    /// Returns `true` if [`Self`] refers to a "wait for interrupt" instruction,
+    /// which hints that the hart may enter a low-power state.
+    ///
+    /// The default implementation returns `false`.
+    fn is_wfi(&self) -> bool {
+        false
+    }
+
     /// Create an instruction that is to be ignored
     ///
     /// Create a valid instruction that does not have any effect on control flow
@@ -173,6 +194,14 @@ impl<T: Info> Info for Option<T> {
         self.as_ref().map(Info::is_return).unwrap_or(false)
     }
 
+    fn is_unknown(&self) -> bool {
+        self.as_ref().map(Info::is_unknown).unwrap_or(true)
+    }
+
+    fn is_wfi(&self) -> bool {
+        self.as_ref().map(Info::is_wfi).unwrap_or(false)
+    }
+
     fn ignored() -> Self {
         None
     }
@@ -218,6 +247,14 @@ where
         self.0.is_return()
     }
 
+    fn is_unknown(&self) -> bool {
+        self.0.is_unknown()
+    }
+
+    fn is_wfi(&self) -> bool {
+        self.0.is_wfi()
+    }
+
     fn ignored() -> Self {
         (Info::ignored(), Default::default())
     }
@@ -264,6 +301,14 @@ where
         either::for_both!(self, i => i.is_return())
     }
 
+    fn is_unknown(&self) -> bool {
+        either::for_both!(self, i => i.is_unknown())
+    }
+
+    fn is_wfi(&self) -> bool {
+        either::for_both!(self, i => i.is_wfi())
+    }
+
     fn ignored() -> Self {
         either::Left(Info::ignored())
     }
@@ -335,6 +380,14 @@ impl Info for riscv_isa::Instruction {
         }
     }
 
+    fn is_unknown(&self) -> bool {
+        matches!(self, Self::UNIMP)
+    }
+
+    fn is_wfi(&self) -> bool {
+        matches!(self, Self::WFI)
+    }
+
     fn ignored() -> Self {
         Self::UNIMP
     }
@@ -405,6 +458,10 @@ impl Info for riscv_isa::Compressed {
         }
     }
 
+    fn is_unknown(&self) -> bool {
+        matches!(self, Self::UNIMP)
+    }
+
     fn ignored() -> Self {
         Self::UNIMP
     }
@@ -0,0 +1,310 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Adapter for [`riscv_isa`] instructions
+//!
+//! This module provides [`Adapter`], a thin wrapper around a
+//! [`riscv_isa::Instruction`] implementing [`Info`] and [`Decode`]. In addition
+//! to the control flow information required for tracing, it provides access to
+//! an instruction's integer register operands, its immediate and its [`Class`].
+//! Compressed instructions are represented by their expanded equivalent.
+//!
+//! # Example
+//!
+//! ```
+//! use riscv_etrace_core::instruction::decode::{Decode, MakeDecode};
+//! use riscv_etrace_core::instruction::info::Info;
+//! use riscv_etrace_core::instruction::isa::{Adapter, Class};
+//!
+//! let target = riscv_isa::Target::rv64i_full();
+//! // jalr ra, 8(a0)
+//! let insn: Adapter = target.decode_32(0x008500e7);
+//! assert!(insn.is_call());
+//! assert_eq!(insn.class(), Class::Jump);
+//! assert_eq!((insn.rd(), insn.rs1(), insn.imm()), (Some(1), Some(10), Some(8)));
+//! ```
+
+use core::fmt;
+
+use riscv_isa::Instruction;
+
+use super::base;
+use super::decode::Decode;
+use super::info::Info;
+
+/// Extract a field shared by a number of [`Instruction`] variants
+macro_rules! field {
+    ($insn:expr, $field:ident: $($variant:ident),* $(,)?) => {
+        match $insn {
+            $(Instruction::$variant { $field, .. } => Some($field),)*
+            _ => None,
+        }
+    };
+}
+
+/// [`riscv_isa::Instruction`] adapter
+///
+/// Register operands are reported only if they refer to integer registers.
+/// Floating point registers are not reported.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Adapter(Instruction);
+
+impl Adapter {
+    /// Retrieve the wrapped [`riscv_isa::Instruction`]
+    pub fn inner(&self) -> &Instruction {
+        &self.0
+    }
+
+    /// Retrieve the destination register
+    pub fn rd(&self) -> Option<u32> {
+        field!(
+            self.0,
+            rd:
+            LUI, AUIPC, JAL, JALR, LB, LH, LW, LBU, LHU, ADDI, SLTI, SLTIU,
+            XORI, ORI, ANDI, SLLI, SRLI, SRAI, ADD, SUB, SLL, SLT, SLTU, XOR,
+            SRL, SRA, OR, AND, LWU, LD, ADDIW, SLLIW, SRLIW, SRAIW, ADDW, SUBW,
+            SLLW, SRLW, SRAW, CSRRW, CSRRS, CSRRC, CSRRWI, CSRRSI, CSRRCI, MUL,
+            MULH, MULHSU, MULHU, DIV, DIVU, REM, REMU, MULW, DIVW, DIVUW, REMW,
+            REMUW, LR_W, SC_W, AMOSWAP_W, AMOADD_W, AMOXOR_W, AMOAND_W,
+            AMOOR_W, AMOMIN_W, AMOMAX_W, AMOMINU_W, AMOMAXU_W, LR_D, SC_D,
+            AMOSWAP_D, AMOADD_D, AMOXOR_D, AMOAND_D, AMOOR_D, AMOMIN_D,
+            AMOMAX_D, AMOMINU_D, AMOMAXU_D, FCVT_W_S, FCVT_WU_S, FMV_X_W,
+            FEQ_S, FLT_S, FLE_S, FCLASS_S, FCVT_L_S, FCVT_LU_S, FEQ_D, FLT_D,
+            FLE_D, FCLASS_D, FCVT_W_D, FCVT_WU_D, FCVT_L_D, FCVT_LU_D, FMV_X_D,
+            FEQ_Q, FLT_Q, FLE_Q, FCLASS_Q, FCVT_W_Q, FCVT_WU_Q, FCVT_L_Q,
+            FCVT_LU_Q, FEQ_H, FLT_H, FLE_H, FCLASS_H, FCVT_W_H, FCVT_WU_H,
+            FCVT_L_H, FCVT_LU_H, SH1ADD, SH2ADD, SH3ADD, ADD_UW, SH1ADD_UW,
+            SH2ADD_UW, SH3ADD_UW, SLLI_UW, ANDN, ORN, XNOR, CLZ, CTZ, CPOP,
+            MAX, MAXU, MIN, MINU, SEXT_B, SEXT_H, ZEXT_H, CLZW, CTZW, CPOPW,
+            ROL, ROR, RORI, ORC_B, REV8, ROLW, RORIW, RORW, PACK, PACKH, BREV8,
+            ZIP, UNZIP, PACKW, CLMUL, CLMULH, CLMULR, BCLR, BCLRI, BEXT, BEXTI,
+            BINV, BINVI, BSET, BSETI,
+        )
+    }
+
+    /// Retrieve the first source register
+    pub fn rs1(&self) -> Option<u32> {
+        field!(
+            self.0,
+            rs1:
+            JALR, BEQ, BNE, BLT, BGE, BLTU, BGEU, LB, LH, LW, LBU, LHU, SB, SH,
+            SW, ADDI, SLTI, SLTIU, XORI, ORI, ANDI, SLLI, SRLI, SRAI, ADD, SUB,
+            SLL, SLT, SLTU, XOR, SRL, SRA, OR, AND, SFENCE_VMA, LWU, LD, SD,
+            ADDIW, SLLIW, SRLIW, SRAIW, ADDW, SUBW, SLLW, SRLW, SRAW, CSRRW,
+            CSRRS, CSRRC, MUL, MULH, MULHSU, MULHU, DIV, DIVU, REM, REMU, MULW,
+            DIVW, DIVUW, REMW, REMUW, LR_W, SC_W, AMOSWAP_W, AMOADD_W,
+            AMOXOR_W, AMOAND_W, AMOOR_W, AMOMIN_W, AMOMAX_W, AMOMINU_W,
+            AMOMAXU_W, LR_D, SC_D, AMOSWAP_D, AMOADD_D, AMOXOR_D, AMOAND_D,
+            AMOOR_D, AMOMIN_D, AMOMAX_D, AMOMINU_D, AMOMAXU_D, FLW, FSW,
+            FCVT_S_W, FCVT_S_WU, FMV_W_X, FCVT_S_L, FCVT_S_LU, FLD, FSD,
+            FCVT_D_W, FCVT_D_WU, FCVT_D_L, FCVT_D_LU, FMV_D_X, FLQ, FSQ,
+            FCVT_Q_W, FCVT_Q_WU, FCVT_Q_L, FCVT_Q_LU, FLH, FSH, FMV_X_H,
+            FCVT_H_W, FCVT_H_WU, FMV_H_X, FCVT_H_L, FCVT_H_LU, SH1ADD, SH2ADD,
+            SH3ADD, ADD_UW, SH1ADD_UW, SH2ADD_UW, SH3ADD_UW, SLLI_UW, ANDN,
+            ORN, XNOR, CLZ, CTZ, CPOP, MAX, MAXU, MIN, MINU, SEXT_B, SEXT_H,
+            ZEXT_H, CLZW, CTZW, CPOPW, ROL, ROR, RORI, ORC_B, REV8, ROLW,
+            RORIW, RORW, PACK, PACKH, BREV8, ZIP, UNZIP, PACKW, CLMUL, CLMULH,
+            CLMULR, BCLR, BCLRI, BEXT, BEXTI, BINV, BINVI, BSET, BSETI,
+        )
+    }
+
+    /// Retrieve the second source register
+    pub fn rs2(&self) -> Option<u32> {
+        field!(
+            self.0,
+            rs2:
+            BEQ, BNE, BLT, BGE, BLTU, BGEU, SB, SH, SW, ADD, SUB, SLL, SLT,
+            SLTU, XOR, SRL, SRA, OR, AND, SFENCE_VMA, SD, ADDW, SUBW, SLLW,
+            SRLW, SRAW, MUL, MULH, MULHSU, MULHU, DIV, DIVU, REM, REMU, MULW,
+            DIVW, DIVUW, REMW, REMUW, SC_W, AMOSWAP_W, AMOADD_W, AMOXOR_W,
+            AMOAND_W, AMOOR_W, AMOMIN_W, AMOMAX_W, AMOMINU_W, AMOMAXU_W, SC_D,
+            AMOSWAP_D, AMOADD_D, AMOXOR_D, AMOAND_D, AMOOR_D, AMOMIN_D,
+            AMOMAX_D, AMOMINU_D, AMOMAXU_D, SH1ADD, SH2ADD, SH3ADD, ADD_UW,
+            SH1ADD_UW, SH2ADD_UW, SH3ADD_UW, ANDN, ORN, XNOR, MAX, MAXU, MIN,
+            MINU, ROL, ROR, ROLW, RORW, PACK, PACKH, PACKW, CLMUL, CLMULH,
+            CLMULR, BCLR, BEXT, BINV, BSET,
+        )
+    }
+
+    /// Retrieve the immediate
+    ///
+    /// The immediate is reported in its effective form, i.e. sign-extended
+    /// and scaled. This includes offsets, shift amounts and the immediates of
+    /// CSR instructions. For `lui` and `auipc`, the value shifted into the
+    /// upper bits is reported.
+    pub fn imm(&self) -> Option<i64> {
+        if let Instruction::LUI { imm, .. } | Instruction::AUIPC { imm, .. } = self.0 {
+            return Some(((imm << 12) as i32).into());
+        }
+        let imm = field!(
+            self.0,
+            imm:
+            ADDI, SLTI, SLTIU, XORI, ORI, ANDI, ADDIW,
+        );
+        let offset = field!(
+            self.0,
+            offset:
+            JAL, JALR, BEQ, BNE, BLT, BGE, BLTU, BGEU, LB, LH, LW, LBU, LHU,
+            SB, SH, SW, LWU, LD, SD, FLW, FSW, FLD, FSD, FLQ, FSQ, FLH, FSH,
+        );
+        let shamt = field!(
+            self.0,
+            shamt:
+            SLLI, SRLI, SRAI, SLLIW, SRLIW, SRAIW, SLLI_UW, RORI, RORIW, BCLRI,
+            BEXTI, BINVI, BSETI,
+        );
+        let uimm = field!(
+            self.0,
+            uimm:
+            CSRRWI, CSRRSI, CSRRCI,
+        );
+        imm.or(offset)
+            .map(i64::from)
+            .or(shamt.or(uimm).map(i64::from))
+    }
+
+    /// Retrieve the CSR accessed by a CSR instruction
+    pub fn csr(&self) -> Option<u32> {
+        field!(
+            self.0,
+            csr:
+            CSRRW, CSRRS, CSRRC, CSRRWI, CSRRSI, CSRRCI,
+        )
+    }
+
+    /// Retrieve the [`Class`] of this instruction
+    pub fn class(&self) -> Class {
+        match self.0 {
+            Instruction::UNIMP => Class::Unknown,
+            Instruction::JAL { .. } | Instruction::JALR { .. } => Class::Jump,
+            insn if insn.branch() => Class::Branch,
+            Instruction::LR_W { .. }
+            | Instruction::LR_D { .. }
+            | Instruction::SC_W { .. }
+            | Instruction::SC_D { .. } => Class::Atomic,
+            insn if insn.load() && insn.store() => Class::Atomic,
+            insn if insn.load() => Class::Load,
+            insn if insn.store() => Class::Store,
+            Instruction::FENCE { .. }
+            | Instruction::FENCE_I
+            | Instruction::ECALL
+            | Instruction::EBREAK
+            | Instruction::SRET
+            | Instruction::MRET
+            | Instruction::WFI
+            | Instruction::SFENCE_VMA { .. }
+            | Instruction::WRS_NTO
+            | Instruction::WRS_STO => Class::System,
+            _ if self.csr().is_some() => Class::System,
+            _ => Class::Compute,
+        }
+    }
+}
+
+impl From<Instruction> for Adapter {
+    fn from(insn: Instruction) -> Self {
+        Self(insn)
+    }
+}
+
+impl From<Adapter> for Instruction {
+    fn from(adapter: Adapter) -> Self {
+        adapter.0
+    }
+}
+
+impl Info for Adapter {
+    type Register = u32;
+
+    fn branch_target(&self) -> Option<i16> {
+        self.0.branch_target()
+    }
+
+    fn inferable_jump_target(&self) -> Option<i32> {
+        self.0.inferable_jump_target()
+    }
+
+    fn uninferable_jump_target(&self) -> Option<(Self::Register, i16)> {
+        self.0.uninferable_jump_target()
+    }
+
+    fn upper_immediate(&self, pc: u64) -> Option<(Self::Register, u64)> {
+        self.0.upper_immediate(pc)
+    }
+
+    fn is_return_from_trap(&self) -> bool {
+        self.0.is_return_from_trap()
+    }
+
+    fn is_ecall_or_ebreak(&self) -> bool {
+        self.0.is_ecall_or_ebreak()
+    }
+
+    fn is_call(&self) -> bool {
+        self.0.is_call()
+    }
+
+    fn is_return(&self) -> bool {
+        self.0.is_return()
+    }
+
+    fn is_unknown(&self) -> bool {
+        self.0.is_unknown()
+    }
+
+    fn is_wfi(&self) -> bool {
+        self.0.is_wfi()
+    }
+
+    fn ignored() -> Self {
+        Self(Info::ignored())
+    }
+}
+
+impl Decode<Adapter> for riscv_isa::Target {
+    fn decode_16(&self, insn: u16) -> Adapter {
+        Adapter(self.decode_16(insn))
+    }
+
+    fn decode_32(&self, insn: u32) -> Adapter {
+        Adapter(self.decode_32(insn))
+    }
+
+    fn decode_48(&self, insn: u64) -> Adapter {
+        Adapter(self.decode_48(insn))
+    }
+
+    fn decode_64(&self, insn: u64) -> Adapter {
+        Adapter(self.decode_64(insn))
+    }
+
+    fn base_set(&self) -> Option<base::Set> {
+        Some((*self).into())
+    }
+}
+
+impl fmt::Display for Adapter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+/// Coarse class of an instruction
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Class {
+    /// A conditional branch
+    Branch,
+    /// An unconditional jump, including calls and returns
+    Jump,
+    /// A load from memory
+    Load,
+    /// A store to memory
+    Store,
+    /// An atomic memory operation, including LR/SC
+    Atomic,
+    /// A system instruction, including fences and CSR accesses
+    System,
+    /// Any other instruction, e.g. arithmetic or logic operations
+    Compute,
+    /// An unknown instruction
+    Unknown,
+}
@@ -0,0 +1,160 @@
+// Copyright (C) 2025, 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+
+extern crate alloc;
+
+mod compare;
+mod decode_tests;
+mod extract;
+mod fmt;
+mod insn_fmt;
+
+use super::*;
+
+use base::Set::{Rv32I, Rv64I};
+use decode::Decode;
+use info::Info;
+
+macro_rules! upper_immediate_test {
+    ($name:ident, $ctor:ident($rd:expr, $imm:expr), $expected:expr) => {
+        #[test]
+        fn $name() {
+            let kind = Kind::$ctor($rd, $imm);
+            let dummy_input = 0x500;
+            let result = kind.upper_immediate(dummy_input);
+
+            assert_eq!(result, $expected);
+        }
+    };
+}
+
+upper_immediate_test!(lui_ok, new_lui(5, 0x80010), Some((5, 0x80010)));
+
+// PC + imm for auipc
+upper_immediate_test!(auipc_ok, new_auipc(7, 0x55555), Some((7, (0x55A55))));
+upper_immediate_test!(c_lui_ok, new_c_lui(3, 0x20), Some((3, 0x20)));
+upper_immediate_test!(jal_ok, new_jal(3, 0x4359), None);
+
+#[test]
+fn is_call_test() {
+    let jalr = Kind::new_jalr(1, 3, 2792);
+    let lui = Kind::new_lui(4, 519603);
+    assert!(jalr.is_call());
+    assert!(!lui.is_call());
+}
+
+#[test]
+fn is_return_test() {
+    let jalr = Kind::new_jal(2, 2450);
+    assert!(!jalr.is_return());
+}
+
+macro_rules! from_kind_test {
+    ($name:ident, $kind:expr, $expected_size:expr) => {
+        #[test]
+        fn $name() {
+            let instruction: Instruction = $kind.into();
+            assert_eq!(instruction.size, $expected_size);
+        }
+    };
+}
+from_kind_test!(from_mret, Kind::mret, Size::Normal);
+from_kind_test!(from_fence, Kind::fence, Size::Normal);
+from_kind_test!(from_bltu, Kind::new_bltu(3, 5, 0b1001), Size::Normal);
+from_kind_test!(from_c_lui, Kind::new_c_lui(8, 0b11000), Size::Compressed);
+from_kind_test!(from_c_eabreak, Kind::c_ebreak, Size::Compressed);
+from_kind_test!(from_ebreak, Kind::ebreak, Size::Normal);
+from_kind_test!(
+    from_auipc,
+    Kind::new_auipc(12, 0b11110000101000101100),
+    Size::Normal
+);
+
+#[test]
+fn kind_operands() {
+    let operands = |k: Kind| (k.rd(), k.rs1(), k.rs2(), k.imm());
+
+    assert_eq!(
+        operands(Kind::new_bne(10, 11, -4)),
+        (None, Some(10), Some(11), Some(-4))
+    );
+    assert_eq!(
+        operands(Kind::new_c_beqz(8, 6)),
+        (None, Some(8), Some(0), Some(6))
+    );
+    assert_eq!(
+        operands(Kind::new_jal(1, 0x100)),
+        (Some(1), None, None, Some(0x100))
+    );
+    assert_eq!(
+        operands(Kind::new_c_jal(1, -2)),
+        (Some(1), None, None, Some(-2))
+    );
+    assert_eq!(
+        operands(Kind::new_jalr(0, 5, 8)),
+        (Some(0), Some(5), None, Some(8))
+    );
+    assert_eq!(
+        operands(Kind::new_c_jr(1)),
+        (Some(0), Some(1), None, Some(0))
+    );
+    assert_eq!(
+        operands(Kind::new_lui(5, 0x1000)),
+        (Some(5), None, None, Some(0x1000))
+    );
+    assert_eq!(operands(Kind::wfi), (None, None, None, None));
+}
+
+#[test]
+fn size_ord() {
+    assert!(Size::Compressed < Size::Normal);
+    assert!(Size::Normal < Size::Wide);
+    assert!(Size::Wide < Size::ExtraWide);
+}
+
+#[cfg(feature = "riscv-isa")]
+#[test]
+fn isa_adapter_operands() {
+    use decode::MakeDecode;
+    use isa::{Adapter, Class};
+
+    let target = riscv_isa::Target::rv64i_full();
+    let decode = |insn: u32| -> Adapter { target.decode_32(insn) };
+
+    // lui a5, 0xfffff
+    let lui = decode(0xfffff7b7);
+    assert_eq!(
+        (lui.rd(), lui.rs1(), lui.imm()),
+        (Some(15), None, Some(-0x1000))
+    );
+    assert_eq!(lui.class(), Class::Compute);
+    assert_eq!(lui.upper_immediate(0), Some((15, 0xfffffffffffff000)));
+
+    // sd ra, -8(sp)
+    let sd = decode(0xfe113c23);
+    assert_eq!((sd.rd(), sd.rs1(), sd.rs2()), (None, Some(2), Some(1)));
+    assert_eq!((sd.imm(), sd.class()), (Some(-8), Class::Store));
+
+    // beq a0, a1, -4
+    let beq = decode(0xfeb50ee3);
+    assert_eq!(
+        (beq.rs1(), beq.rs2(), beq.imm()),
+        (Some(10), Some(11), Some(-4))
+    );
+    assert_eq!(
+        (beq.class(), beq.branch_target()),
+        (Class::Branch, Some(-4))
+    );
+
+    // csrrw zero, mtvec, t0
+    let csrw = decode(0x30529073);
+    assert_eq!(
+        (csrw.csr(), csrw.rs1(), csrw.class()),
+        (Some(0x305), Some(5), Class::System)
+    );
+
+    // c.jr ra
+    let ret: Adapter = target.decode_16(0x8082);
+    assert_eq!((ret.class(), ret.rs1()), (Class::Jump, Some(1)));
+    assert!(ret.is_return());
+}
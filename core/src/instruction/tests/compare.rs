@@ -59,6 +59,11 @@ mod compare_riscv_isa {
         riscv_isa::Instruction,
         riscv_isa::Target::rv64i_full()
     );
+
+    #[test]
+    fn adapter_compressed() {
+        compare_infos_16::<_, isa::Adapter>(riscv_isa::Target::rv64i_full());
+    }
 }
 
 /// Compare [`Info`] outputs for a range of decoded insns against a reference
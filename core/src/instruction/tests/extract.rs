@@ -50,6 +50,29 @@ fn bits_extract_none() {
     assert_eq!(Bits::extract(&data), None,)
 }
 
+#[test]
+fn bits_extract_truncated() {
+    use bits::ExtractError;
+
+    let data = [0xBF, 0x2F, 0x15, 0x46, 0x52, 0x8C, 0x84, 0x23];
+    for len in 1..data.len() {
+        assert_eq!(
+            Bits::try_extract(&data[..len]),
+            Err(ExtractError::Truncated(
+                (data.len() - len).try_into().unwrap()
+            )),
+        );
+    }
+    assert_eq!(
+        Bits::try_extract(&[]),
+        Err(ExtractError::Truncated(2.try_into().unwrap())),
+    );
+    assert_eq!(
+        Bits::try_extract(&[0xFF, 0x82]),
+        Err(ExtractError::UnsupportedLength),
+    );
+}
+
 #[test]
 fn bits_extract_size() {
     for i in u8::MIN..=u8::MAX {
@@ -0,0 +1,184 @@
+// Copyright (C) 2024 - 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! # Decoder and tracer for RISC-V E-Traces
+//!
+//! This library provides a [packet] decoder and encoder a [tracer] as well as
+//! a payload [generator] for the instruction tracing defined in the [Efficient
+//! Trace for RISC-V][etrace] specification. Given trace packets previously
+//! retrieved from an encoder and the traced program, the [tracer] allows
+//! reconstructing the execution path. The [generator], on the other hand,
+//! serves as a software implementation for a trace encoder.
+//!
+//! This library also features a limited [instruction] database with decoding
+//! functionality. Currently, only decoding of RV32IC and RV64IC instructions
+//! are supported. However, tracing is not impacted by other instructions that
+//! do not influence the control flow ("control transfer instructions").
+//!
+//! # Tracing flow
+//!
+//! Raw trace data needs to be decoded via [`packet::decoder::Decoder`]s, which
+//! are constructed via a [`packet::Builder`]. A builder is usually configured
+//! for a trace [`Unit`][packet::unit::Unit] implementation with specific
+//! [`config::Parameters`].
+//!
+//! A decoded packet or [`Payload`][packet::payload::Payload] payload needs to
+//! be dispatched to the [`Tracer`][tracer::Tracer] for that RISC-V hart. It is
+//! the responsibility of the library user to do so.
+//!
+//! A [`Tracer`][tracer::Tracer] processes packets and generates a series of
+//! tracing [`Item`][tracer::item::Item]s. It is constructed via a
+//! [`tracer::Builder`], which is configured for the specific program
+//! being traced (in the form of a [`Binary`][binary::Binary]) and the same
+//! [`config::Parameters`] that the decoder was configured with.
+//!
+//! [`Binary`][binary::Binary] is a trait abstracting access to
+//! [`Instruction`][instruction::Instruction]s. This library provides a number
+//! of implementations and utilities for constructing one, including limited
+//! instruction decoding capabilities.
+//!
+//! The most commonly used items are re-exported in the [`prelude`]. With the
+//! `alloc` feature, a [`session::Session`] bundles decoding and tracing for
+//! multiple harts.
+//!
+//! # Relation to `riscv-etrace`
+//!
+//! This crate contains the decoder, the tracer and all other functionality
+//! that does not depend on `std` or on parsers for program files. It is
+//! intended for embedded users requiring a minimal dependency graph. The
+//! `riscv-etrace` crate re-exports all modules of this crate and adds tooling
+//! such as [`Binary`][binary::Binary]s for ELF files and exporters.
+//!
+//! The [`session`] module is part of this crate rather than `riscv-etrace`
+//! since [`session::Session`] requires allocation but not `std`. It is thus
+//! available on `no_std` targets providing a global allocator via the `alloc`
+//! feature.
+//!
+//! # E-Trace options
+//!
+//! The following [E-Trace][etrace] options are supported:
+//! * delta/full address mode
+//! * sequentially inferred jumps
+//! * implicit return ([tracer] only, known to be broken by specification)
+//!
+//! # Crate features
+//!
+//! Some functionality if controlled via crate features:
+//! * `alloc`: enables some features that require allocation
+//! * `either`: enables impls of various traits for [`either::Either`]
+//! * `embedded`: enables the [`embedded`] module providing a driver for
+//!   self-tracing on embedded targets
+//! * `fixtures`: enables the [`fixtures`] module providing known-good programs
+//!   and payloads for sanity-checking integrations
+//! * `metrics`: enables the [`metrics`] module for measuring the time spent
+//!   decoding and tracing
+//! * `riscv-isa`: enables support for decoding and tracing
+//!   [`riscv_isa::Instruction`]s instead of [`instruction::Kind`], including
+//!   the [`instruction::isa::Adapter`] providing operand access.
+//! * `self-check`: enables checking the [tracer]'s stop conditions against a
+//!   shadow implementation of the reference pseudo-code for every instruction,
+//!   panicking on divergence; intended for development only
+//! * `serde`: enables (de)serialization of configuration via [`serde`]
+//!
+//! # Panics
+//!
+//! Decoding packets, tracing and retrieving
+//! [`Instruction`][instruction::Instruction]s from the
+//! [`Binary`][binary::Binary]s provided by this library do not panic,
+//! regardless of the trace data or program binary processed. Malformed inputs
+//! are reported as errors instead. This guarantee only holds for
+//! [`config::Parameters`] within the limits defined by the specification and
+//! if the `self-check` feature is not enabled.
+//!
+//! # no_std
+//!
+//! This crate does not depend on `std` regardless of the features enabled and
+//! is thus suitable for `no_std` environments.
+//!
+//! # Example
+//!
+//! The following example demonstrates basic instruction tracing, with default
+//! [`config::Parameters`], a simple [`Binary`][binary::Binary] and tracing
+//! packets conforming to the [Unformatted Trace & Diagnostic Data Packet
+//! Encapsulation for RISC-V][encap] specification placed in a single buffer.
+//!
+//! ```
+//! use riscv_etrace_core::binary::{self, Adaptable};
+//! use riscv_etrace_core::packet;
+//! use riscv_etrace_core::instruction::{base, Instruction};
+//! use riscv_etrace_core::tracer::{self, Tracer};
+//!
+//! # let binary_data = b"\x14\x41\x11\x05\x94\xc1\x91\x05\xe3\xec\xc5\xfe\x82\x80";
+//! # let binary_offset = 0x80000028;
+//! # let trace_data = b"\x45\x73\x0a\x00\x00\x20\x41\x01";
+//! # let hart_to_trace = 0;
+//! let binary = binary::from_segment(binary_data, base::Set::Rv32I)
+//!     .with_offset(binary_offset);
+//!
+//! let parameters = Default::default();
+//! let mut decoder = packet::builder()
+//!     .with_params(&parameters)
+//!     .decoder(trace_data);
+//! let mut tracer: Tracer<_> = tracer::builder()
+//!     .with_binary(binary)
+//!     .with_params(&parameters)
+//!     .build()
+//!     .unwrap();
+//!
+//! while decoder.bytes_left() > 0 {
+//!     let packet = decoder.decode_encap_packet().unwrap().into_normal();
+//!     if let Some(packet) = packet.filter(|p| p.src_id() == hart_to_trace) {
+//!         let payload = packet.decode_payload().unwrap();
+//!         eprintln!("{payload:?}");
+//!         tracer.process_payload(&payload).unwrap();
+//!         tracer.by_ref().for_each(|i| {
+//!             let item = i.unwrap();
+//!             if let Some(info) = item.trap() {
+//!                 println!("Trap! EPC={:0x}, interrupt={}", item.pc(), info.is_interrupt());
+//!             } else {
+//!                 println!("PC: {:0x}", item.pc());
+//!             }
+//!         });
+//!     }
+//! }
+//! ```
+//!
+//! [etrace]: <https://github.com/riscv-non-isa/riscv-trace-spec/>
+//! [encap]: <https://github.com/riscv-non-isa/e-trace-encap/>
+#![no_std]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(test)]
+mod tests;
+
+pub mod analysis;
+pub mod binary;
+pub mod code;
+pub mod config;
+pub mod corpus;
+#[cfg(feature = "embedded")]
+pub mod embedded;
+#[cfg(feature = "fixtures")]
+pub mod fixtures;
+pub mod generator;
+pub mod instruction;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+pub mod packet;
+pub mod prelude;
+#[cfg(feature = "alloc")]
+pub mod session;
+pub mod tracer;
+pub mod transport;
+pub mod types;
+
+/// Sealing of traits
+///
+/// Traits with [`Sealed`][sealed::Sealed] as a supertrait can not be
+/// implemented outside of this crate. This allows adding items to them without
+/// breaking downstream users.
+mod sealed {
+    pub trait Sealed {}
+}
@@ -0,0 +1,173 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Self-measurement of decoding and tracing
+//!
+//! This module provides lightweight instrumentation for verifying real-time
+//! budgets of the decoding pipeline on the target hardware. [`Metrics`] record
+//! the time spent in individual [`Stage`]s as [`Counter`]s, using ticks of a
+//! user-provided [`Clock`], e.g. a cycle counter.
+//!
+//! When the `embedded` feature is enabled, a [`Driver`] can report its
+//! [`Metrics`] via [`poll_measured`].
+//!
+//! [`Driver`]: crate::embedded::Driver
+//! [`poll_measured`]: crate::embedded::Driver::poll_measured
+
+#[cfg(test)]
+mod tests;
+
+/// Source of monotonic ticks
+///
+/// Ticks may be in any unit, e.g. CPU cycles or timer ticks. The clock may
+/// wrap around, as long as no single measurement spans a wrap-around.
+pub trait Clock {
+    /// Retrieve the current tick
+    fn now(&mut self) -> u64;
+}
+
+impl<F: FnMut() -> u64> Clock for F {
+    fn now(&mut self) -> u64 {
+        self()
+    }
+}
+
+/// Stage of the decoding pipeline
+#[non_exhaustive]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Stage {
+    /// Decoding of a packet, including its payload
+    Decode,
+    /// Processing of a payload by a [`Tracer`], including the generation of
+    /// all resulting [`Item`]s
+    ///
+    /// [`Tracer`]: crate::tracer::Tracer
+    /// [`Item`]: crate::tracer::item::Item
+    Trace,
+}
+
+/// Accumulated measurements of a single [`Stage`]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Counter {
+    calls: u64,
+    total: u64,
+    max: u64,
+}
+
+impl Counter {
+    /// Record a single measurement of the given duration
+    pub fn record(&mut self, duration: u64) {
+        self.calls = self.calls.saturating_add(1);
+        self.total = self.total.saturating_add(duration);
+        self.max = self.max.max(duration);
+    }
+
+    /// Retrieve the number of measurements recorded
+    pub fn calls(&self) -> u64 {
+        self.calls
+    }
+
+    /// Retrieve the sum of all durations recorded
+    pub fn total(&self) -> u64 {
+        self.total
+    }
+
+    /// Retrieve the longest duration recorded
+    pub fn max(&self) -> u64 {
+        self.max
+    }
+
+    /// Retrieve the mean duration, if any measurement was recorded
+    pub fn mean(&self) -> Option<u64> {
+        self.total.checked_div(self.calls)
+    }
+}
+
+/// Measurements of all [`Stage`]s
+///
+/// # Example
+///
+/// ```
+/// use riscv_etrace_core::metrics::{Metrics, Stage};
+///
+/// let mut ticks = 0;
+/// let mut metrics = Metrics::new(|| {
+///     ticks += 10;
+///     ticks
+/// });
+/// let sum = metrics.measure(Stage::Decode, || 1 + 2);
+/// assert_eq!(sum, 3);
+/// assert_eq!(metrics.counter(Stage::Decode).calls(), 1);
+/// assert_eq!(metrics.counter(Stage::Decode).total(), 10);
+/// assert_eq!(metrics.counter(Stage::Trace).mean(), None);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Metrics<C: Clock> {
+    clock: C,
+    decode: Counter,
+    trace: Counter,
+    items: u64,
+}
+
+impl<C: Clock> Metrics<C> {
+    /// Create new, empty metrics using the given [`Clock`]
+    pub fn new(clock: C) -> Self {
+        Self {
+            clock,
+            decode: Default::default(),
+            trace: Default::default(),
+            items: 0,
+        }
+    }
+
+    /// Execute `f`, recording its duration for the given [`Stage`]
+    pub fn measure<R>(&mut self, stage: Stage, f: impl FnOnce() -> R) -> R {
+        let start = self.clock.now();
+        let res = f();
+        let duration = self.clock.now().wrapping_sub(start);
+        self.counter_mut(stage).record(duration);
+        res
+    }
+
+    /// Record the generation of the given number of items
+    pub fn add_items(&mut self, items: u64) {
+        self.items = self.items.saturating_add(items);
+    }
+
+    /// Retrieve the [`Counter`] for the given [`Stage`]
+    pub fn counter(&self, stage: Stage) -> &Counter {
+        match stage {
+            Stage::Decode => &self.decode,
+            Stage::Trace => &self.trace,
+        }
+    }
+
+    /// Retrieve the number of items generated
+    pub fn items(&self) -> u64 {
+        self.items
+    }
+
+    /// Retrieve the total number of ticks spent in all [`Stage`]s
+    pub fn total(&self) -> u64 {
+        self.decode.total().saturating_add(self.trace.total())
+    }
+
+    /// Retrieve the [`Clock`]
+    pub fn clock(&mut self) -> &mut C {
+        &mut self.clock
+    }
+
+    /// Clear all measurements
+    pub fn reset(&mut self) {
+        self.decode = Default::default();
+        self.trace = Default::default();
+        self.items = 0;
+    }
+
+    /// Retrieve the [`Counter`] for the given [`Stage`] mutably
+    fn counter_mut(&mut self, stage: Stage) -> &mut Counter {
+        match stage {
+            Stage::Decode => &mut self.decode,
+            Stage::Trace => &mut self.trace,
+        }
+    }
+}
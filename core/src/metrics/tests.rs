@@ -0,0 +1,25 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+#[test]
+fn counter() {
+    let mut counter = Counter::default();
+    assert_eq!(counter.mean(), None);
+    [3, 9, 6].into_iter().for_each(|d| counter.record(d));
+    assert_eq!(counter.calls(), 3);
+    assert_eq!(counter.total(), 18);
+    assert_eq!(counter.max(), 9);
+    assert_eq!(counter.mean(), Some(6));
+}
+
+#[test]
+fn metrics_wrapping_clock() {
+    let mut ticks = [u64::MAX - 1, 3].into_iter();
+    let mut metrics = Metrics::new(|| ticks.next().expect("Clock exhausted"));
+    metrics.measure(Stage::Trace, || ());
+    assert_eq!(metrics.counter(Stage::Trace).total(), 5);
+    assert_eq!(metrics.counter(Stage::Decode).calls(), 0);
+    assert_eq!(metrics.total(), 5);
+}
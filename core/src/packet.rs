@@ -5,19 +5,24 @@
 //! This module provides definitions for [payloads][payload] and packets as well
 //! as a [`decoder`] for decoding them from raw trace data.
 
+pub mod annotate;
 pub mod decoder;
+pub mod detect;
 pub mod encap;
 pub mod encoder;
 pub mod error;
 pub mod esp32;
 pub mod ext;
 pub mod payload;
+pub mod preset;
+pub mod roundtrip;
 pub mod smi;
+pub mod source;
 pub mod sync;
 pub mod truncate;
 pub mod unit;
 mod util;
-mod width;
+pub mod width;
 
 #[cfg(test)]
 mod tests;
@@ -41,11 +46,13 @@ pub fn builder() -> Builder<unit::Reference> {
 #[derive(Copy, Clone, Default)]
 pub struct Builder<U = unit::Reference> {
     field_widths: width::Widths,
+    address_shift: Option<u8>,
     unit: U,
     hart_index_width: u8,
     timestamp_width: u8,
     trace_type_width: u8,
     no_compress: bool,
+    strict: bool,
 }
 
 impl Builder<unit::Reference> {
@@ -64,15 +71,36 @@ impl<U> Builder<U> {
         }
     }
 
+    /// Override the shift applied to instruction addresses
+    ///
+    /// By default, addresses are shifted by the number of LSBs omitted
+    /// according to the [`config::Parameters`], i.e. `iaddress_lsb_p`. Some
+    /// encoders apply a different shift, e.g. by 2 bits even though
+    /// compressed instructions are supported. Setting a shift overrides the
+    /// one derived from the parameters, regardless of whether the parameters
+    /// are set before or after it. Passing [`None`] removes the override.
+    ///
+    /// The shift in effect is reflected in the
+    /// [`iaddress_lsb`][width::Widths::iaddress_lsb] of the widths reported by
+    /// [`Decoder`][decoder::Decoder]s and [`Encoder`][encoder::Encoder]s built.
+    pub fn with_address_shift(self, address_shift: Option<u8>) -> Self {
+        Self {
+            address_shift,
+            ..self
+        }
+    }
+
     /// Set the trace [`Unit`][unit::Unit] implementation
     pub fn for_unit<V>(self, unit: V) -> Builder<V> {
         Builder {
             field_widths: self.field_widths,
+            address_shift: self.address_shift,
             unit,
             hart_index_width: self.hart_index_width,
             timestamp_width: self.timestamp_width,
             trace_type_width: self.trace_type_width,
             no_compress: self.no_compress,
+            strict: self.strict,
         }
     }
 
@@ -127,23 +155,120 @@ impl<U> Builder<U> {
         }
     }
 
+    /// Activate or deactivate strict decoding
+    ///
+    /// In strict mode, [`Decoder`][decoder::Decoder]s built by this builder
+    /// reject values the [`Unit`][unit::Unit] does not know, such as unknown
+    /// [encoder modes][unit::Unit::decode_encoder_mode], rather than
+    /// preserving them as raw values. Strict decoding is deactivated by
+    /// default.
+    pub fn with_strict(self, strict: bool) -> Self {
+        Self { strict, ..self }
+    }
+
+    /// Apply the given [`Preset`][preset::Preset]
+    ///
+    /// All settings covered by the [`Preset`][preset::Preset] are replaced,
+    /// including the [`Unit`][unit::Unit]. Other settings are retained.
+    pub fn with_preset<V: Clone>(self, preset: &preset::Preset<V>) -> Builder<V> {
+        self.for_unit(preset.unit.clone())
+            .with_params(&preset.params)
+            .with_hart_index_width(preset.hart_index_width)
+            .with_timestamp_width(preset.timestamp_width)
+            .with_trace_type_width(preset.trace_type_width)
+            .with_strict(preset.strict)
+    }
+
+    /// Retrieve the width used for packet source index fields
+    #[cfg(feature = "alloc")]
+    pub(crate) fn hart_index_width(&self) -> u8 {
+        self.hart_index_width
+    }
+
+    /// Retrieve the width used for packet timestamps
+    #[cfg(feature = "alloc")]
+    pub(crate) fn timestamp_width(&self) -> u8 {
+        self.timestamp_width
+    }
+
+    /// Retrieve the width used for the trace type
+    #[cfg(feature = "alloc")]
+    pub(crate) fn trace_type_width(&self) -> u8 {
+        self.trace_type_width
+    }
+
+    /// Retrieve the address shift overriding `iaddress_lsb_p`, if any
+    #[cfg(feature = "alloc")]
+    pub(crate) fn address_shift(&self) -> Option<u8> {
+        self.address_shift
+    }
+
+    /// Retrieve the field widths for [`Decoder`]s and [`Encoder`]s
+    ///
+    /// Parameters [fixed][unit::Unit::fixed_parameters] by the unit take
+    /// precedence over the ones set via [`with_params`][Self::with_params].
+    ///
+    /// [`Decoder`]: decoder::Decoder
+    /// [`Encoder`]: encoder::Encoder
+    fn field_widths(&self) -> width::Widths
+    where
+        U: unit::Unit,
+    {
+        self.unit
+            .fixed_parameters()
+            .map(|p| width::Widths::new(&p))
+            .unwrap_or(self.field_widths)
+    }
+
     /// Build a [`Decoder`][decoder::Decoder] for the given data
-    pub fn decoder(self, data: &[u8]) -> decoder::Decoder<'_, U> {
+    pub fn decoder(self, data: &[u8]) -> decoder::Decoder<'_, U>
+    where
+        U: unit::Unit,
+    {
         let mut res = decoder::Decoder::new(
-            self.field_widths,
+            self.field_widths(),
+            self.address_shift,
             self.unit,
             self.hart_index_width,
             self.timestamp_width,
             self.trace_type_width,
+            self.strict,
         );
         res.reset(data);
         res
     }
 
+    /// Build a [`Decoder`][decoder::Decoder] for the given [`ByteSource`]
+    ///
+    /// [`ByteSource`]: source::ByteSource
+    pub fn decoder_for_source<'d>(
+        self,
+        source: &'d dyn source::ByteSource,
+    ) -> decoder::Decoder<'d, U>
+    where
+        U: unit::Unit,
+    {
+        let mut res = decoder::Decoder::new(
+            self.field_widths(),
+            self.address_shift,
+            self.unit,
+            self.hart_index_width,
+            self.timestamp_width,
+            self.trace_type_width,
+            self.strict,
+        );
+        res.reset_to_source(source);
+        res
+    }
+
     /// Build an [`Encoder`][encoder::Encoder] for this configuration
-    pub fn encoder(self, buffer: &mut [u8]) -> encoder::Encoder<'_, U> {
+    pub fn encoder(self, buffer: &mut [u8]) -> encoder::Encoder<'_, U>
+    where
+        U: unit::Unit,
+    {
         let mut res = encoder::Encoder::new(
-            self.field_widths,
+            self.field_widths(),
+            self.address_shift,
             self.unit,
             self.hart_index_width,
             self.timestamp_width,
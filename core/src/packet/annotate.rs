@@ -0,0 +1,161 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Bit-level annotation of decoded packets
+//!
+//! When comparing decoded packets against waveform dumps of an encoder, it is
+//! often necessary to know exactly which bits of the raw data make up which
+//! field. A [`Decoder`][super::decoder::Decoder] may be equipped with a
+//! [`Recorder`] via [`with_recorder`][super::decoder::Decoder::with_recorder],
+//! which is informed about every [`Field`] decoded.
+//!
+//! With the `alloc` feature, this module also provides a [`Recording`] which
+//! collects all [`Field`]s and can be rendered alongside the raw data via
+//! [`Annotated`].
+//!
+//! # Example
+//!
+//! ```
+//! # #[cfg(feature = "alloc")]
+//! # {
+//! use riscv_etrace_core::packet::{self, annotate};
+//!
+//! let data = b"\x45\x73\x0a\x00\x00\x20\x41\x01";
+//! let recording = annotate::Recording::new();
+//! let mut decoder = packet::builder().decoder(data).with_recorder(&recording);
+//! decoder.decode_encap_packet().unwrap();
+//!
+//! let fields = recording.take();
+//! assert_eq!(fields[0].name, "length");
+//! assert_eq!(fields[0].start, 0);
+//! assert_eq!(fields[0].width, 5);
+//! assert_eq!(fields[0].value, 5);
+//!
+//! let annotated = annotate::Annotated::new(data, &fields);
+//! println!("{annotated}");
+//! # }
+//! ```
+
+use core::fmt;
+
+/// A single field decoded from raw data
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Field {
+    /// Name of the field
+    ///
+    /// The name usually matches the one used in the specification.
+    pub name: &'static str,
+    /// Position of the first bit of the field
+    pub start: usize,
+    /// Width of the field in bits
+    pub width: u8,
+    /// Raw value of the field
+    ///
+    /// The value is not sign-extended and only contains the lower 64 bits of
+    /// fields wider than that.
+    pub value: u64,
+}
+
+impl Field {
+    /// Retrieve the position of the first bit after this field
+    pub fn end(&self) -> usize {
+        self.start.saturating_add(self.width.into())
+    }
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self.width.clamp(1, 64).into();
+        write!(
+            f,
+            "{:>5}..{:<5} {:<20} {:>2}: {:#x} ({:0width$b})",
+            self.start,
+            self.end(),
+            self.name,
+            self.width,
+            self.value,
+            self.value,
+        )
+    }
+}
+
+/// Receiver of decoded [`Field`]s
+///
+/// This trait allows observing the [`Field`]s decoded by a
+/// [`Decoder`][super::decoder::Decoder]. Since decoders may be cloned,
+/// [`record`][Self::record] only receives a shared reference and
+/// implementations need to rely on interior mutability.
+pub trait Recorder {
+    /// Record a single [`Field`]
+    fn record(&self, field: Field);
+}
+
+impl<R: Recorder> Recorder for &R {
+    fn record(&self, field: Field) {
+        R::record(self, field)
+    }
+}
+
+/// [`Recorder`] collecting all [`Field`]s
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, Default)]
+pub struct Recording {
+    fields: core::cell::RefCell<alloc::vec::Vec<Field>>,
+}
+
+#[cfg(feature = "alloc")]
+impl Recording {
+    /// Create a new, empty recording
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Take all [`Field`]s recorded so far, leaving the recording empty
+    pub fn take(&self) -> alloc::vec::Vec<Field> {
+        self.fields.take()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Recorder for Recording {
+    fn record(&self, field: Field) {
+        self.fields.borrow_mut().push(field)
+    }
+}
+
+/// Raw data annotated with [`Field`]s
+///
+/// The [`Display`][fmt::Display] impl renders the raw data as hex bytes,
+/// followed by one line per [`Field`] containing its bit range, name, width
+/// and value.
+#[derive(Copy, Clone, Debug)]
+pub struct Annotated<'a> {
+    data: &'a [u8],
+    fields: &'a [Field],
+}
+
+impl<'a> Annotated<'a> {
+    /// Create a new annotated view of the given data
+    pub fn new(data: &'a [u8], fields: &'a [Field]) -> Self {
+        Self { data, fields }
+    }
+}
+
+impl fmt::Display for Annotated<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let len = self
+            .fields
+            .iter()
+            .map(|f| f.end().div_ceil(8))
+            .max()
+            .unwrap_or_default()
+            .min(self.data.len());
+        let mut bytes = self.data.iter().take(len);
+        if let Some(byte) = bytes.next() {
+            write!(f, "{byte:02x}")?;
+        }
+        bytes.try_for_each(|b| write!(f, " {b:02x}"))?;
+        self.fields
+            .iter()
+            .try_for_each(|field| write!(f, "\n{field}"))
+    }
+}
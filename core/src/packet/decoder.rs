@@ -0,0 +1,810 @@
+// Copyright (C) 2024 - 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Packet decoder
+
+use core::num::NonZeroUsize;
+use core::ops;
+
+use crate::config;
+use crate::transport::Framing;
+
+use super::annotate::{Field, Recorder};
+use super::error::Error;
+use super::payload::InstructionTrace;
+use super::source::ByteSource;
+use super::truncate::TruncateNum;
+use super::unit::Unit;
+use super::width::Widths;
+use super::{encap, esp32, smi};
+
+/// A decoder for individual packets and/or [payloads][super::payload]
+///
+/// Use this decoder to decode [`encap::Packet`]s or [`smi::Packet`]s.
+///
+/// A decoder is created via a [`Builder`][super::Builder]. From the supplied
+/// data, packets of different formats may be decoded using the corresponding
+/// fns such as [`decode_encap_packet`][Self::decode_encap_packet] or
+/// [`decode_smi_packet`][Self::decode_smi_packet]. After assessing whether a
+/// packet is relevant or not, its [`Payload`][super::payload::Payload] may be
+/// decoded. Multiple packets from different harts may be sequentially decoded
+/// by a single decoder instance.
+///
+/// Decoders usually decode data from a single byte slice. Data which is not
+/// contiguous may be decoded from a [`ByteSource`] instead. However, some fns
+/// such as [`remaining_data`][Self::remaining_data] are of limited use in that
+/// case.
+///
+/// If a packet could not be decoded due to insufficient data, the decoder will
+/// report this by emitting an [`Error::InsufficientData`] error.
+/// Alternatively, the number of bytes left in the input can be queried via the
+/// fn [`bytes_left`][Self::bytes_left]. The bit-level position may be queried
+/// and adjusted via the [`Alignment`] trait.
+///
+/// # Example
+///
+/// The follwing example demonstrates decoding of trace data in chunks with one
+/// decoder per input, including the recovery from attempting to decode an
+/// incomplete packet. We do not need to clone the [`Builder`][super::Builder]
+/// since it happens to be [`Copy`] in this case.
+///
+/// ```
+/// use riscv_etrace_core::packet;
+///
+/// # let parameters = Default::default();
+/// # let trace_data = b"\x45\x73\x0a\x00";
+/// # let trace_data_next = b"\x45\x73\x0a\x00\x00\x20\x41\x01";
+/// let builder = packet::builder().with_params(&parameters);
+/// let mut decoder = builder.decoder(trace_data);
+/// loop {
+///     let packet = match decoder.decode_encap_packet() {
+///         Ok(packet) => packet,
+///         Err(packet::Error::InsufficientData(_)) => break,
+///         Err(e) => panic!("{e:?}"),
+///     };
+///     // ...
+/// }
+/// let mut decoder = builder.decoder(trace_data_next);
+/// loop {
+///     let packet = match decoder.decode_encap_packet() {
+///         Ok(packet) => packet,
+///         Err(packet::Error::InsufficientData(_)) => break,
+///         Err(e) => panic!("{e:?}"),
+///     };
+///     // ...
+/// }
+/// ```
+#[derive(Clone)]
+pub struct Decoder<'d, U> {
+    data: Data<'d>,
+    bit_pos: usize,
+    origin: usize,
+    recorder: Option<&'d dyn Recorder>,
+    field_widths: Widths,
+    address_shift: Option<u8>,
+    adjusted_widths: Option<Widths>,
+    unit: U,
+    hart_index_width: u8,
+    timestamp_width: u8,
+    trace_type_width: u8,
+    strict: bool,
+}
+
+impl<'d, U> Decoder<'d, U> {
+    /// Create a new decoder
+    pub(super) fn new(
+        field_widths: Widths,
+        address_shift: Option<u8>,
+        unit: U,
+        hart_index_width: u8,
+        timestamp_width: u8,
+        trace_type_width: u8,
+        strict: bool,
+    ) -> Self {
+        Self {
+            data: Data::Slice(&[]),
+            bit_pos: 0,
+            origin: 0,
+            recorder: None,
+            field_widths,
+            address_shift,
+            adjusted_widths: None,
+            unit,
+            hart_index_width,
+            timestamp_width,
+            trace_type_width,
+            strict,
+        }
+    }
+
+    /// Retrieve the number of bytes left in this decoder's data
+    ///
+    /// If the decoder is currently not at a byte boundary, the number returned
+    /// includes the partially decoded byte.
+    pub fn bytes_left(&self) -> usize {
+        self.data.len().saturating_sub(self.bit_pos >> 3)
+    }
+
+    /// Retrieve the current byte position
+    ///
+    /// Returns the zero-based position of the byte which is currently decoded.
+    /// If the current bit position is on a byte boundary, the position of the
+    /// byte after the boundary is returned.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use riscv_etrace_core::packet;
+    ///
+    /// # let trace_data = &[];
+    /// let mut decoder = packet::builder().decoder(trace_data);
+    /// assert_eq!(decoder.byte_pos(), 0);
+    /// ```
+    pub fn byte_pos(&self) -> usize {
+        self.bit_pos >> 3
+    }
+
+    /// Reset the inner data to the given byte slice
+    pub fn reset(&mut self, data: &'d [u8]) {
+        self.bit_pos = 0;
+        self.origin = 0;
+        self.data = Data::Slice(data);
+    }
+
+    /// Reset the inner data to the given [`ByteSource`]
+    pub fn reset_to_source(&mut self, source: &'d dyn ByteSource) {
+        self.bit_pos = 0;
+        self.origin = 0;
+        self.data = Data::Source {
+            source,
+            start: 0,
+            end: source.len(),
+        };
+    }
+
+    /// Set a [`Recorder`] for the fields decoded
+    ///
+    /// All fields decoded subsequently, including those decoded by
+    /// sub-decoders [split off][Self::split_off_to], will be reported to the
+    /// given [`Recorder`]. Bit positions reported are relative to the start of
+    /// the data set via [`reset`][Self::reset] or the [`Builder`].
+    ///
+    /// [`Builder`]: super::Builder
+    pub fn with_recorder(self, recorder: &'d dyn Recorder) -> Self {
+        Self {
+            recorder: Some(recorder),
+            ..self
+        }
+    }
+
+    /// Decode a single item
+    ///
+    /// Decodes a single item, consuming the associated data from the input and
+    /// sign-extending the input if neccessary. Depending on the item's type and
+    /// [`Decode`] implementation, the decoder may be left at a _bit_ boundary
+    /// following the item after successful operation. A failure may leave the
+    /// decoder in an unspecified state.
+    pub fn decode<T: Decode<'d, U>>(&mut self) -> Result<T, Error> {
+        Decode::decode(self)
+    }
+
+    /// Decode a single item, reporting the number of bits [`Consumed`]
+    ///
+    /// Like [`decode`][Self::decode], but additionally reports the exact number
+    /// of bits consumed while decoding the item as well as the padding to the
+    /// next byte boundary.
+    pub fn decode_counted<T: Decode<'d, U>>(&mut self) -> Result<(T, Consumed), Error> {
+        let start = self.bit_pos;
+        let item = Decode::decode(self)?;
+        let consumed = Consumed {
+            bits: self.bit_pos - start,
+            padding: ((8 - (self.bit_pos & 0x7)) & 0x7) as u8,
+        };
+        Ok((item, consumed))
+    }
+
+    /// Decode a single [`encap::Packet`]
+    ///
+    /// Decodes a single [`encap::Packet`], consuming the associated data from
+    /// the input. After successful operation, the decoder is left at the byte
+    /// boundary following the packet, ready to decode the next one. A failure
+    /// may leave the decoder in an unspecified state.
+    pub fn decode_encap_packet(&mut self) -> Result<encap::Packet<Self>, Error>
+    where
+        U: Clone,
+    {
+        Decode::decode(self)
+    }
+
+    /// Decode a single [`smi::Packet`] consisting of header and payload
+    ///
+    /// Decodes a single [`smi::Packet`], consuming the associated data from the
+    /// input. After successful operation, the decoder is left at the byte
+    /// boundary following the packet, ready to decode the next one. A failure
+    /// may leave the decoder in an unspecified state.
+    pub fn decode_smi_packet(&mut self) -> Result<smi::Packet<Self>, Error>
+    where
+        U: Clone,
+    {
+        Decode::decode(self)
+    }
+
+    /// Decode the header of a single [`smi::Packet`], skipping its payload
+    ///
+    /// Decodes the header of a single [`smi::Packet`] and skips the payload
+    /// without decoding it. The packet returned holds the length of the
+    /// payload in bytes. This allows fast scanning passes, e.g. for indexing,
+    /// which are only interested in packets' harts or time tags. After
+    /// successful operation, the decoder is left at the byte boundary
+    /// following the packet. A failure may leave the decoder in an unspecified
+    /// state.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use riscv_etrace_core::packet;
+    ///
+    /// let mut decoder = packet::builder().decoder(b"\x42\x00\x00\x41\x00");
+    /// let header = decoder.decode_smi_header_only().unwrap();
+    /// assert_eq!(*header.payload(), 2);
+    /// assert_eq!(decoder.bytes_left(), 2);
+    /// ```
+    pub fn decode_smi_header_only(&mut self) -> Result<smi::Packet<usize>, Error> {
+        Decode::decode(self)
+    }
+
+    pub fn decode_esp32_packet(&mut self) -> Result<esp32::Packet<Self>, Error>
+    where
+        U: Clone,
+    {
+        Decode::decode(self)
+    }
+
+    /// Decode a single, stand-alone [`InstructionTrace`] payload
+    ///
+    /// Decodes a single [`InstructionTrace`] payload, consuming the associated
+    /// data from the input and sign-extending the input if neccessary. After
+    /// successful operation, the decoder is left at the _bit_ boundary
+    /// following the payload. A failure may leave the decoder in an unspecified
+    /// state.
+    pub fn decode_payload(&mut self) -> Result<InstructionTrace<U::IOptions, U::DOptions>, Error>
+    where
+        U: Unit,
+    {
+        Decode::decode(self)
+    }
+
+    /// Decode a single, stand-alone payload, reporting the bits [`Consumed`]
+    ///
+    /// Like [`decode_payload`][Self::decode_payload], but additionally reports
+    /// the exact number of bits consumed by the payload as well as the padding
+    /// to the next byte boundary. This allows rewriting individual payloads in
+    /// place without re-encoding the entire trace.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use riscv_etrace_core::packet;
+    ///
+    /// let mut decoder = packet::builder().decoder(b"\x00\x20\x41\x01");
+    /// let (_, consumed) = decoder.decode_payload_counted().unwrap();
+    /// assert_eq!(consumed.bits + usize::from(consumed.padding), 8 * consumed.bytes());
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn decode_payload_counted(
+        &mut self,
+    ) -> Result<(InstructionTrace<U::IOptions, U::DOptions>, Consumed), Error>
+    where
+        U: Unit,
+    {
+        self.decode_counted()
+    }
+
+    /// Retrieve this decoder's [`Unit`]
+    pub fn unit(&self) -> &U {
+        &self.unit
+    }
+
+    /// Retrieve the payload field widths
+    ///
+    /// If the [`Unit`] has [fixed parameters][Unit::FIXED_PARAMETERS], widths
+    /// are derived from those at compile time. An
+    /// [address shift][super::Builder::with_address_shift] overriding
+    /// `iaddress_lsb_p` is applied in any case. While decoding a payload, the
+    /// widths [adjusted][Unit::adjust_widths] by the [`Unit`] are returned.
+    pub fn widths(&self) -> Widths
+    where
+        U: Unit,
+    {
+        if let Some(widths) = self.adjusted_widths {
+            return widths;
+        }
+        let fixed = const {
+            match U::FIXED_PARAMETERS {
+                Some(params) => Some(Widths::new(&params)),
+                None => None,
+            }
+        };
+        let mut widths = fixed.unwrap_or(self.field_widths);
+        if let Some(shift) = self.address_shift {
+            widths.iaddress_lsb = shift;
+        }
+        widths
+    }
+
+    /// Decode an item using widths adjusted for a payload of the given format
+    ///
+    /// The widths are adjusted via [`Unit::adjust_widths`] and remain in effect
+    /// only for the decoding of the item via `decode`.
+    pub(super) fn with_adjusted_widths<T>(
+        &mut self,
+        format: u8,
+        decode: impl FnOnce(&mut Self) -> Result<T, Error>,
+    ) -> Result<T, Error>
+    where
+        U: Unit,
+    {
+        let base = self.widths();
+        let mut widths = base;
+        U::adjust_widths(format, &mut widths, self)?;
+        self.adjusted_widths = Some(widths).filter(|w| *w != base);
+        let res = decode(self);
+        self.adjusted_widths = None;
+        res
+    }
+
+    /// Retrieve the hart index width
+    pub(super) fn hart_index_width(&self) -> u8 {
+        self.hart_index_width
+    }
+
+    /// Retrieve the width of the timestamp used in packet headers
+    pub(super) fn timestamp_width(&self) -> u8 {
+        self.timestamp_width
+    }
+
+    /// Retrieve the trace type width
+    pub(super) fn trace_type_width(&self) -> u8 {
+        self.trace_type_width
+    }
+
+    /// Determine whether this decoder is in strict mode
+    ///
+    /// See [`Builder::with_strict`][super::Builder::with_strict] for details.
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
+    /// Retrieve the remaining inner data, including the current byte
+    ///
+    /// If the current bit position is at a byte buondary, e.g. after successful
+    /// decoding of a packet, the returned buffer contains only undecoded data.
+    ///
+    /// For decoders decoding from a [`ByteSource`], an empty slice is returned.
+    /// Use [`bytes_left`][Self::bytes_left] for determining the amount of data
+    /// remaining instead.
+    pub fn remaining_data(&self) -> &'d [u8] {
+        match self.data {
+            Data::Slice(data) => {
+                data.split_at_checked(self.bit_pos >> 3)
+                    .unwrap_or_default()
+                    .1
+            }
+            Data::Source { .. } => &[],
+        }
+    }
+
+    /// Split off a sub-decoder covering the data to the given position
+    ///
+    /// On success, the inner data will be reset to the portion of the original
+    /// data starting at and including byte `pos` past the current
+    /// [byte position][Self::byte_pos]. A decoder with the original bit
+    /// position covering the first half of the buffer will be returned.
+    ///
+    /// The sub-decoder is restricted to its half of the buffer. Reads past its
+    /// end, including those of nested sub-decoders, are served exclusively via
+    /// sign-extension of its last byte and never yield data belonging to the
+    /// remaining buffer, i.e. to the next packet. In debug builds, this
+    /// invariant is checked via assertions.
+    pub fn split_off_to(&mut self, pos: usize) -> Result<Self, Error>
+    where
+        U: Clone,
+    {
+        let pos = self.byte_pos().saturating_add(pos);
+        if let Some((data, remaining)) = self.data.split_at(pos) {
+            let mut res = self.clone();
+            res.data = data;
+            self.data = remaining;
+            self.bit_pos = 0;
+            self.origin = self.origin.saturating_add(pos);
+            debug_assert_eq!(
+                res.origin.saturating_add(res.data.len()),
+                self.origin,
+                "sub-decoder overlaps remaining data",
+            );
+            Ok(res)
+        } else {
+            let need = pos
+                .checked_sub(self.data.len())
+                .and_then(NonZeroUsize::new)
+                .unwrap_or(NonZeroUsize::MIN);
+            Err(Error::InsufficientData(need))
+        }
+    }
+
+    /// Skip the given number of bytes past the current byte position
+    ///
+    /// On success, the decoder is left at the byte boundary following the
+    /// bytes skipped.
+    pub(super) fn skip_bytes(&mut self, len: usize) -> Result<(), Error> {
+        let pos = self.byte_pos().saturating_add(len);
+        if let Some((_, remaining)) = self.data.split_at(pos) {
+            self.data = remaining;
+            self.bit_pos = 0;
+            self.origin = self.origin.saturating_add(pos);
+            Ok(())
+        } else {
+            let need = pos
+                .checked_sub(self.data.len())
+                .and_then(NonZeroUsize::new)
+                .unwrap_or(NonZeroUsize::MIN);
+            Err(Error::InsufficientData(need))
+        }
+    }
+
+    /// Read an unsigned integer field of the given width
+    ///
+    /// This fn allows decoding unit specific payload data, e.g. in
+    /// [`Unit::decode_extension`]. Widths greater than `64` are treated as
+    /// `64`. The `name` is reported to the [`Recorder`], if any.
+    pub fn read_uint(&mut self, name: &'static str, width: u8) -> Result<u64, Error> {
+        self.read_bits(name, width.min(64))
+    }
+
+    /// Read a single bit
+    pub(super) fn read_bit(&mut self, name: &'static str) -> Result<bool, Error> {
+        let res = (self.get_byte(self.bit_pos >> 3)? >> (self.bit_pos & 0x07)) & 0x1;
+        self.record(name, 1, res.into());
+        self.bit_pos += 1;
+        Ok(res != 0)
+    }
+
+    /// Read a single differential bit
+    ///
+    /// The bit's value is considered to be [`true`] if it differs from the
+    /// previous bit and [`false`] if it doesn't. The previous bit needs to be
+    /// part of this decoder's data, i.e. of the same payload. Reading a
+    /// differential bit at the very start of the data fails with
+    /// [`Error::InsufficientData`], since there is no bit to refer to.
+    pub(super) fn read_differential_bit(&mut self, name: &'static str) -> Result<bool, Error> {
+        let reference_pos = self
+            .bit_pos
+            .checked_sub(1)
+            .ok_or(Error::InsufficientData(NonZeroUsize::MIN))?;
+        let reference_bit = (self.get_byte(reference_pos >> 3)? >> (reference_pos & 0x07)) & 0x1;
+        let raw_bit = (self.get_byte(self.bit_pos >> 3)? >> (self.bit_pos & 0x07)) & 0x1;
+        self.record(name, 1, raw_bit.into());
+        self.bit_pos += 1;
+        Ok(reference_bit ^ raw_bit != 0)
+    }
+
+    /// Read a number of bits as an integer
+    ///
+    /// Unsigned integers will be left-padded with zeroes, signed integers will
+    /// be sign-extended.
+    ///
+    /// # Safety
+    ///
+    /// May panic if `bit_count` is higher then the bit width of the target
+    /// integer.
+    pub(super) fn read_bits<T>(&mut self, name: &'static str, bit_count: u8) -> Result<T, Error>
+    where
+        T: From<u8>
+            + ops::Shl<usize, Output = T>
+            + ops::Shr<usize, Output = T>
+            + ops::BitOrAssign<T>
+            + TruncateNum,
+    {
+        let res = self.extract_bits(bit_count)?;
+        if self.recorder.is_some() {
+            let raw = self.extract_bits::<u64>(bit_count.min(64))?;
+            self.record(name, bit_count, raw);
+        }
+        self.bit_pos += usize::from(bit_count);
+        Ok(res)
+    }
+
+    /// Extract a number of bits at the current position as an integer
+    fn extract_bits<T>(&self, bit_count: u8) -> Result<T, Error>
+    where
+        T: From<u8>
+            + ops::Shl<usize, Output = T>
+            + ops::Shr<usize, Output = T>
+            + ops::BitOrAssign<T>
+            + TruncateNum,
+    {
+        let lowest_bits = self.bit_pos & 0x07;
+        let mut byte_pos = self.bit_pos >> 3;
+        let mut res = T::from(self.get_byte(byte_pos)?) >> lowest_bits;
+        let mut bits_extracted = 8 - lowest_bits;
+
+        while bits_extracted < bit_count.into() {
+            byte_pos += 1;
+            res |= T::from(self.get_byte(byte_pos)?) << bits_extracted;
+            bits_extracted += 8;
+        }
+
+        Ok(res.truncated(bit_count))
+    }
+
+    /// Report a field starting at the current position to the [`Recorder`]
+    ///
+    /// Fields of zero width are not reported.
+    fn record(&self, name: &'static str, width: u8, value: u64) {
+        if let Some(recorder) = self.recorder.filter(|_| width > 0) {
+            recorder.record(Field {
+                name,
+                start: self.origin.saturating_mul(8).saturating_add(self.bit_pos),
+                width,
+                value,
+            });
+        }
+    }
+
+    /// Get the byte at the given byte position
+    ///
+    /// If the byte position is past the end of the current data source, the
+    /// result of a decompression if returned. The sign is taken from the last
+    /// byte within the bounds of this decoder's data. A byte within bounds
+    /// which is not available, e.g. from a faulty [`ByteSource`], results in
+    /// an error rather than in a decompression.
+    fn get_byte(&self, pos: usize) -> Result<u8, Error> {
+        let byte = if pos < self.data.len() {
+            self.data.get(pos)
+        } else {
+            self.data
+                .len()
+                .checked_sub(1)
+                .and_then(|p| self.data.get(p))
+                .map(|b| if b & 0x80 != 0 { 0xFF } else { 0x00 })
+        };
+        byte.ok_or(Error::InsufficientData(NonZeroUsize::MIN))
+    }
+}
+
+/// Control over the bit-level position of a [`Decoder`]
+///
+/// Payloads are not neccessarily byte-aligned and a [`Decoder`] may be left at
+/// a bit boundary after decoding one. This trait allows implementors of custom
+/// [`Transport`]s or [`Unit`]s to query and adjust the position, e.g. for
+/// skipping padding following a header. The trait is sealed and only
+/// implemented for [`Decoder`].
+///
+/// # Example
+///
+/// ```
+/// use riscv_etrace_core::packet::{self, decoder::Alignment};
+///
+/// let mut decoder = packet::builder().decoder(b"\x05\x00\x00\x00\x07");
+/// decoder.read_uint("flags", 3).unwrap();
+/// assert_eq!(decoder.bit_offset(), 3);
+/// decoder.align_to(4).unwrap();
+/// assert!(decoder.is_aligned());
+/// assert_eq!(decoder.read_uint("value", 8), Ok(7));
+/// ```
+///
+/// [`Transport`]: crate::transport::Transport
+pub trait Alignment: crate::sealed::Sealed {
+    /// Retrieve the current bit position
+    ///
+    /// The position is relative to the start of the data decoded.
+    fn bit_pos(&self) -> usize;
+
+    /// Retrieve the current bit offset within the current byte
+    fn bit_offset(&self) -> u8 {
+        (self.bit_pos() & 0x7) as u8
+    }
+
+    /// Determine whether the current position is at a byte boundary
+    fn is_aligned(&self) -> bool {
+        self.bit_offset() == 0
+    }
+
+    /// Advance the position to the next byte boundary
+    ///
+    /// If the current position is at a byte boundary, it is not changed.
+    fn advance_to_byte(&mut self);
+
+    /// Advance the position to the next multiple of `bytes` bytes
+    ///
+    /// If the current position is at such a boundary, it is not changed. Fails
+    /// with [`Error::InsufficientData`] if the boundary lies past the end of
+    /// the data, in which case the position is not changed either. An
+    /// alignment of zero bytes is treated as an alignment of one byte.
+    fn align_to(&mut self, bytes: usize) -> Result<(), Error>;
+}
+
+impl<U> crate::sealed::Sealed for Decoder<'_, U> {}
+
+impl<U> Alignment for Decoder<'_, U> {
+    fn bit_pos(&self) -> usize {
+        self.bit_pos
+    }
+
+    fn advance_to_byte(&mut self) {
+        if self.bit_pos & 0x7 != 0 {
+            self.bit_pos = (self.bit_pos & !0x7usize) + 8;
+        }
+    }
+
+    fn align_to(&mut self, bytes: usize) -> Result<(), Error> {
+        let pos = self.bit_pos.div_ceil(8).next_multiple_of(bytes.max(1));
+        if let Some(need) = pos.checked_sub(self.data.len()).and_then(NonZeroUsize::new) {
+            return Err(Error::InsufficientData(need));
+        }
+        self.bit_pos = pos << 3;
+        Ok(())
+    }
+}
+
+/// Amount of data consumed while decoding a single item
+///
+/// See [`Decoder::decode_counted`] and [`Decoder::decode_payload_counted`].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Consumed {
+    /// Number of bits consumed by the item
+    pub bits: usize,
+    /// Number of padding bits between the item and the next byte boundary
+    pub padding: u8,
+}
+
+impl Consumed {
+    /// Retrieve the number of (partial) bytes consumed, including the padding
+    ///
+    /// The number returned only equals the number of bytes spanned by the item
+    /// if decoding started at a byte boundary.
+    pub fn bytes(&self) -> usize {
+        self.bits.div_ceil(8)
+    }
+}
+
+/// Retrieve the [`Capabilities`] of [`Decoder`]s in this build
+///
+/// # Example
+///
+/// ```
+/// use riscv_etrace_core::packet::decoder;
+///
+/// let params = Default::default();
+/// assert_eq!(decoder::capabilities().check_params(&params), Ok(()));
+/// ```
+pub fn capabilities() -> Capabilities {
+    Capabilities {
+        framings: &Framing::ALL,
+        units: &["reference", "pulp"],
+        described_units: cfg!(feature = "alloc"),
+        unit_descriptions: cfg!(all(feature = "alloc", feature = "serde")),
+        plugs: cfg!(feature = "alloc"),
+        branch_count: true,
+        jump_target_index: true,
+        data_trace: false,
+        max_field_width: 64,
+    }
+}
+
+/// Packet formats, units and payloads supported by [`Decoder`]s
+///
+/// Tooling may use this description for checking whether a trace encoder's
+/// output can be decoded before starting to decode it. Use [`capabilities`]
+/// for retrieving the capabilities of this build, which may depend on the
+/// cargo features enabled.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Capabilities {
+    /// [`Framing`]s of packets supported
+    pub framings: &'static [Framing],
+    /// Names of the [`Unit`]s provided by this library
+    pub units: &'static [&'static str],
+    /// Whether units may be described by data, i.e. `unit::Described`
+    pub described_units: bool,
+    /// Whether unit descriptions may be deserialized, e.g. from config files
+    pub unit_descriptions: bool,
+    /// Whether units may be selected at runtime via `unit::Plug`s
+    pub plugs: bool,
+    /// Whether [`BranchCount`][super::ext::BranchCount] payloads are decoded
+    pub branch_count: bool,
+    /// Whether [`JumpTargetIndex`][super::ext::JumpTargetIndex] payloads are
+    /// decoded
+    pub jump_target_index: bool,
+    /// Whether data trace payloads are decoded
+    pub data_trace: bool,
+    /// Maximum width of a field in bits
+    pub max_field_width: u8,
+}
+
+impl Capabilities {
+    /// Check whether the given [`config::Parameters`] are supported
+    ///
+    /// Returns the name of the first parameter exceeding the capabilities as
+    /// an error.
+    pub fn check_params(&self, params: &config::Parameters) -> Result<(), &'static str> {
+        let widths = [
+            (params.context_width_p.get(), "context_width_p"),
+            (params.time_width_p.get(), "time_width_p"),
+            (params.ecause_width_p.get(), "ecause_width_p"),
+            (params.iaddress_width_p.get(), "iaddress_width_p"),
+            (params.privilege_width_p.get(), "privilege_width_p"),
+        ];
+        widths
+            .into_iter()
+            .find(|(width, _)| *width > self.max_field_width)
+            .map_or(Ok(()), |(_, name)| Err(name))
+    }
+}
+
+/// Data decoded by a [`Decoder`]
+#[derive(Copy, Clone)]
+enum Data<'d> {
+    /// Contiguous data
+    Slice(&'d [u8]),
+    /// Window into a [`ByteSource`]
+    Source {
+        source: &'d dyn ByteSource,
+        start: usize,
+        end: usize,
+    },
+}
+
+impl<'d> Data<'d> {
+    /// Retrieve the number of bytes
+    fn len(&self) -> usize {
+        match self {
+            Self::Slice(data) => data.len(),
+            Self::Source { start, end, .. } => end - start,
+        }
+    }
+
+    /// Retrieve the byte at the given position
+    fn get(&self, pos: usize) -> Option<u8> {
+        match self {
+            Self::Slice(data) => <[u8]>::get(data, pos).copied(),
+            Self::Source { source, start, end } => {
+                debug_assert!(*end <= source.len(), "window exceeds source");
+                start
+                    .checked_add(pos)
+                    .filter(|p| p < end)
+                    .and_then(|p| source.get(p))
+            }
+        }
+    }
+
+    /// Split the data at the given position
+    fn split_at(self, pos: usize) -> Option<(Self, Self)> {
+        match self {
+            Self::Slice(data) => data
+                .split_at_checked(pos)
+                .map(|(l, r)| (Self::Slice(l), Self::Slice(r))),
+            Self::Source { source, start, end } => {
+                let mid = start.checked_add(pos).filter(|m| *m <= end)?;
+                let left = Self::Source {
+                    source,
+                    start,
+                    end: mid,
+                };
+                let right = Self::Source {
+                    source,
+                    start: mid,
+                    end,
+                };
+                Some((left, right))
+            }
+        }
+    }
+}
+
+/// Decodable item
+///
+/// Items implementing this trait may be decoded using an [`Decoder`].
+pub trait Decode<'d, U>: Sized {
+    /// Decode an item of this type
+    fn decode(decoder: &mut Decoder<'d, U>) -> Result<Self, Error>;
+}
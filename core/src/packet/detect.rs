@@ -0,0 +1,217 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Heuristic detection of encoder parameters
+//!
+//! Decoding trace data requires knowledge of the encoder's
+//! [`config::Parameters`], which may not always be available. This module
+//! provides a [`Detector`] which inspects the first RISC-V Encapsulation
+//! packets of a capture and guesses the [`iaddress_width_p`] and
+//! [`iaddress_lsb_p`] parameters, which affect most payloads. The resulting
+//! [`Guess`] is meant for bootstrapping a configuration, not for replacing one.
+//!
+//! For each candidate combination of parameters, the packets' payloads are
+//! decoded. A payload is considered consistent with the candidate if it
+//! decodes successfully and no whole bytes of the packet are left over. Since
+//! encapsulation drops trailing sign-extension bytes, overly wide addresses
+//! are often consistent with a capture. Among the candidates with the most
+//! consistent payloads, the first one is thus chosen, with candidate widths
+//! being tried in ascending order.
+//!
+//! The [protocol version][config::Version] can not be detected, since version 1
+//! and version 2 payloads share their layout.
+//!
+//! # Example
+//!
+//! ```
+//! use riscv_etrace_core::packet::{self, detect};
+//!
+//! let data = b"\x45\x73\x0a\x00\x00\x20\x41\x01";
+//! let guess = detect::Detector::default()
+//!     .detect(packet::builder(), data)
+//!     .unwrap();
+//! assert_eq!(guess.params.iaddress_width_p.get(), 32);
+//! ```
+//!
+//! [`iaddress_width_p`]: config::Parameters::iaddress_width_p
+//! [`iaddress_lsb_p`]: config::Parameters::iaddress_lsb_p
+
+use core::num::NonZeroU8;
+
+use crate::config;
+
+use super::Builder;
+use super::payload::InstructionTrace;
+use super::sync::Synchronization;
+use super::unit::Unit;
+
+/// Default candidates for [`iaddress_width_p`][width]
+///
+/// [width]: config::Parameters::iaddress_width_p
+pub const ADDRESS_WIDTHS: &[u8] = &[32, 39, 48, 57, 64];
+
+/// Default candidates for [`iaddress_lsb_p`][lsb]
+///
+/// The candidates are ordered by how common they are.
+///
+/// [lsb]: config::Parameters::iaddress_lsb_p
+pub const ADDRESS_LSBS: &[u8] = &[1, 0, 2];
+
+/// Heuristic detector for encoder parameters
+///
+/// Parameters not subject to detection are taken from the base parameters,
+/// which default to [`config::PARAMETERS`].
+#[derive(Copy, Clone, Debug)]
+pub struct Detector<'a> {
+    base: config::Parameters,
+    widths: &'a [u8],
+    lsbs: &'a [u8],
+    max_packets: usize,
+}
+
+impl<'a> Detector<'a> {
+    /// Set the base [`config::Parameters`]
+    pub fn with_params(self, params: &config::Parameters) -> Self {
+        Self {
+            base: *params,
+            ..self
+        }
+    }
+
+    /// Set the candidates for the address width
+    ///
+    /// Candidates should be given in ascending order.
+    pub fn with_address_widths(self, widths: &'a [u8]) -> Self {
+        Self { widths, ..self }
+    }
+
+    /// Set the candidates for the number of address LSBs omitted
+    pub fn with_address_lsbs(self, lsbs: &'a [u8]) -> Self {
+        Self { lsbs, ..self }
+    }
+
+    /// Set the maximum number of packets to inspect
+    pub fn with_max_packets(self, max_packets: usize) -> Self {
+        Self {
+            max_packets,
+            ..self
+        }
+    }
+
+    /// Guess parameters for the given trace data
+    ///
+    /// The packets in `data` are decoded using [`Decoder`]s created by the
+    /// given [`Builder`], with the candidate parameters applied. Returns
+    /// [`None`] if no packet with a payload could be decoded or if there is no
+    /// candidate.
+    ///
+    /// [`Decoder`]: super::decoder::Decoder
+    pub fn detect<U: Unit + Clone>(&self, builder: Builder<U>, data: &[u8]) -> Option<Guess> {
+        let mut best: Option<Guess> = None;
+        for width in self.widths.iter().filter_map(|w| NonZeroU8::new(*w)) {
+            for lsb in self.lsbs.iter().copied().filter(|l| *l < width.get()) {
+                let params = config::Parameters {
+                    iaddress_width_p: width,
+                    iaddress_lsb_p: lsb,
+                    ..self.base
+                };
+                let (packets, consistent) = self.check(builder.clone(), &params, data);
+                match best.as_mut() {
+                    Some(best) if best.consistent > consistent => (),
+                    Some(best) if best.consistent == consistent => best.ambiguous += 1,
+                    _ => {
+                        best = Some(Guess {
+                            params,
+                            packets,
+                            consistent,
+                            ambiguous: 0,
+                        })
+                    }
+                }
+            }
+        }
+        best.filter(|g| g.packets > 0)
+    }
+
+    /// Check the given parameters against trace data
+    ///
+    /// Returns the number of payloads inspected and the number of payloads
+    /// consistent with the parameters.
+    fn check<U: Unit + Clone>(
+        &self,
+        builder: Builder<U>,
+        params: &config::Parameters,
+        data: &[u8],
+    ) -> (usize, usize) {
+        let mut decoder = builder.with_params(params).decoder(data);
+        let mut packets = 0;
+        let mut consistent = 0;
+        while packets < self.max_packets {
+            let Ok(packet) = decoder.decode_encap_packet() else {
+                break;
+            };
+            let Some(normal) = packet.into_normal() else {
+                continue;
+            };
+            packets += 1;
+
+            let mut payload = normal.payload().clone();
+            let Ok(0) = payload.read_bits::<u8>("trace_type", payload.trace_type_width()) else {
+                continue;
+            };
+            let Ok(trace) = payload.decode_payload() else {
+                continue;
+            };
+            if payload.bytes_left() <= 1 && is_plausible(&trace) {
+                consistent += 1;
+            }
+        }
+        (packets, consistent)
+    }
+}
+
+impl Default for Detector<'_> {
+    fn default() -> Self {
+        Self {
+            base: config::PARAMETERS,
+            widths: ADDRESS_WIDTHS,
+            lsbs: ADDRESS_LSBS,
+            max_packets: 64,
+        }
+    }
+}
+
+/// Guessed encoder parameters
+#[derive(Copy, Clone, Debug)]
+pub struct Guess {
+    /// The guessed parameters
+    pub params: config::Parameters,
+    /// Number of packets with a payload inspected
+    pub packets: usize,
+    /// Number of payloads consistent with the guessed parameters
+    pub consistent: usize,
+    /// Number of other candidates as consistent as the guessed parameters
+    pub ambiguous: usize,
+}
+
+impl Guess {
+    /// Retrieve the confidence in this guess as a percentage
+    ///
+    /// The confidence is the share of consistent payloads, divided by the
+    /// number of candidates that are equally consistent.
+    pub fn confidence(&self) -> u8 {
+        let total = self.packets.max(1) * (self.ambiguous + 1);
+        (self.consistent * 100 / total) as u8
+    }
+}
+
+/// Check whether a successfully decoded payload is plausible
+///
+/// A synchronization payload reporting an address of zero is considered
+/// implausible, since such an address is usually not executable.
+fn is_plausible<I, D>(trace: &InstructionTrace<I, D>) -> bool {
+    match trace {
+        InstructionTrace::Synchronization(Synchronization::Start(start)) => start.address != 0,
+        InstructionTrace::Synchronization(Synchronization::Trap(trap)) => trap.address != 0,
+        _ => true,
+    }
+}
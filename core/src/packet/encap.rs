@@ -8,7 +8,7 @@
 //!
 //! [encap]: <https://github.com/riscv-non-isa/e-trace-encap/>
 
-use super::decoder::{Decode, Decoder};
+use super::decoder::{Alignment, Decode, Decoder};
 use super::encoder::{Encode, Encoder};
 use super::{Error, payload, unit};
 
@@ -90,9 +90,9 @@ impl<'d, U: Clone> Decode<'d, U> for Packet<Decoder<'d, U>> {
             // are taken care of by `Decoder::split_data`.
             return Err(Error::InsufficientData(core::num::NonZeroUsize::MIN));
         }
-        let length = decoder.read_bits(5)?;
-        let flow = decoder.read_bits(2)?;
-        let extend = decoder.read_bit()?;
+        let length = decoder.read_bits("length", 5)?;
+        let flow = decoder.read_bits("flow", 2)?;
+        let extend = decoder.read_bit("extend")?;
 
         match core::num::NonZeroU8::new(length) {
             Some(length) => {
@@ -103,9 +103,9 @@ impl<'d, U: Clone> Decode<'d, U> for Packet<Decoder<'d, U>> {
                     + usize::from(timestamp_width);
 
                 let mut payload = decoder.split_off_to(length)?;
-                let src_id = payload.read_bits(src_id_width)?;
+                let src_id = payload.read_bits("src_id", src_id_width)?;
                 let timestamp = extend
-                    .then(|| payload.read_bits(8 * timestamp_width))
+                    .then(|| payload.read_bits("timestamp", 8 * timestamp_width))
                     .transpose()?;
                 Ok(Normal {
                     flow,
@@ -194,6 +194,14 @@ impl<P> Normal<P> {
         self.timestamp
     }
 
+    /// Extend the packet's (outer) timestamp to an absolute one
+    ///
+    /// The timestamp, if present, is replaced by the one reconstructed by the
+    /// given [`Rollover`], which should be dedicated to the packet's source.
+    pub fn extend_timestamp(&mut self, rollover: &mut Rollover) {
+        self.timestamp = self.timestamp.map(|t| rollover.extend(t));
+    }
+
     /// Retrieve the packet's payload
     pub fn payload(&self) -> &P {
         &self.payload
@@ -208,15 +216,86 @@ impl<P> Normal<P> {
 impl<'d, U: unit::Unit> Normal<Decoder<'d, U>> {
     /// Decode the packet's E-Trace payload
     pub fn decode_payload(mut self) -> Result<payload::Payload<U::IOptions, U::DOptions>, Error> {
-        let width = self.payload.trace_type_width();
-        match self.payload.read_bits::<u8>(width)? {
-            0 => Decode::decode(&mut self.payload).map(payload::Payload::InstructionTrace),
-            1 => Ok(payload::Payload::DataTrace),
-            unknown => Err(Error::UnknownTraceType(unknown)),
+        decode_payload(&mut self.payload)
+    }
+
+    /// Iterate over all E-Trace payloads contained in the packet
+    ///
+    /// Some encoders pack multiple payloads into a single packet. Each payload
+    /// starts at the byte boundary following the previous one. Iteration ends
+    /// when no data is left or after the first error. Packets must not contain
+    /// any padding after the last payload.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use riscv_etrace_core::packet;
+    ///
+    /// let data = b"\x0a\x13\x00\x00\x00\xe0\x13\x00\x00\x00\xe0";
+    /// let mut decoder = packet::builder().decoder(data);
+    /// let packet = decoder.decode_encap_packet().unwrap().into_normal().unwrap();
+    /// let payloads: Vec<_> = packet.payloads().collect::<Result<_, _>>().unwrap();
+    /// assert_eq!(payloads.len(), 2);
+    /// assert_eq!(payloads[0], payloads[1]);
+    /// ```
+    pub fn payloads(self) -> Payloads<'d, U> {
+        Payloads {
+            decoder: Some(self.payload),
         }
     }
 }
 
+/// Iterator over the E-Trace payloads of a [`Normal`] packet
+///
+/// Created via [`Normal::payloads`].
+pub struct Payloads<'d, U> {
+    decoder: Option<Decoder<'d, U>>,
+}
+
+impl<U> Payloads<'_, U> {
+    /// Retrieve the number of bytes left after the payloads decoded so far
+    ///
+    /// Returns `0` once iteration ended, including after an error.
+    pub fn bytes_left(&self) -> usize {
+        self.decoder.as_ref().map_or(0, |d| {
+            let partial = d.bit_pos() % 8 != 0;
+            d.bytes_left().saturating_sub(partial.into())
+        })
+    }
+}
+
+impl<U: unit::Unit> Iterator for Payloads<'_, U> {
+    type Item = Result<payload::Payload<U::IOptions, U::DOptions>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let decoder = self.decoder.as_mut()?;
+        decoder.advance_to_byte();
+        if decoder.bytes_left() == 0 {
+            self.decoder = None;
+            return None;
+        }
+        let res = decode_payload(decoder);
+        if res.is_err() {
+            self.decoder = None;
+        }
+        Some(res)
+    }
+}
+
+impl<U: unit::Unit> core::iter::FusedIterator for Payloads<'_, U> {}
+
+/// Decode an E-Trace payload, including its trace type
+fn decode_payload<U: unit::Unit>(
+    decoder: &mut Decoder<'_, U>,
+) -> Result<payload::Payload<U::IOptions, U::DOptions>, Error> {
+    let width = decoder.trace_type_width();
+    match decoder.read_bits::<u8>("trace_type", width)? {
+        0 => Decode::decode(decoder).map(payload::Payload::InstructionTrace),
+        1 => Ok(payload::Payload::DataTrace),
+        unknown => Err(Error::UnknownTraceType(unknown)),
+    }
+}
+
 impl<'d, U> TryFrom<Normal<Decoder<'d, U>>> for Normal<payload::Payload<U::IOptions, U::DOptions>>
 where
     U: unit::Unit,
@@ -287,3 +366,76 @@ where
         Ok(())
     }
 }
+
+/// Reconstruction of absolute timestamps from truncated ones
+///
+/// Timestamps in [`Normal`] packets are usually truncated to only a few bytes
+/// and thus roll over frequently. A rollover tracks the timestamps of a single
+/// source and reconstructs monotonically increasing 64 bit timestamps from
+/// them, assuming that no more than one rollover occurs between two
+/// consecutive timestamps.
+///
+/// # Example
+///
+/// ```
+/// use riscv_etrace_core::packet::encap::Rollover;
+///
+/// let mut rollover = Rollover::new(8);
+/// assert_eq!(rollover.extend(0xf0), 0xf0);
+/// assert_eq!(rollover.extend(0x10), 0x110);
+/// assert_eq!(rollover.extend(0x20), 0x120);
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Rollover {
+    width: u8,
+    last: Option<u64>,
+}
+
+impl Rollover {
+    /// Create a new rollover for timestamps of the given width in bits
+    ///
+    /// For timestamps as decoded from [`Normal`] packets, the width is `8`
+    /// times the [timestamp width][super::Builder::with_timestamp_width].
+    pub fn new(width: u8) -> Self {
+        Self { width, last: None }
+    }
+
+    /// Create a rollover resuming after the given absolute timestamp
+    ///
+    /// The next timestamp will be reconstructed relative to the given one, as
+    /// if it was the last one reconstructed.
+    pub fn with_last(self, last: Option<u64>) -> Self {
+        Self { last, ..self }
+    }
+
+    /// Reconstruct the absolute timestamp for the given truncated one
+    ///
+    /// Bits of the given timestamp beyond the width are ignored. The first
+    /// timestamp is taken as is.
+    pub fn extend(&mut self, timestamp: u64) -> u64 {
+        let Some(period) = 1u64.checked_shl(self.width.into()) else {
+            self.last = Some(timestamp);
+            return timestamp;
+        };
+        let mask = period - 1;
+        let timestamp = timestamp & mask;
+        let res = match self.last {
+            Some(last) => {
+                let res = (last & !mask) | timestamp;
+                if res < last {
+                    res.wrapping_add(period)
+                } else {
+                    res
+                }
+            }
+            None => timestamp,
+        };
+        self.last = Some(res);
+        res
+    }
+
+    /// Retrieve the last absolute timestamp reconstructed, if any
+    pub fn last(&self) -> Option<u64> {
+        self.last
+    }
+}
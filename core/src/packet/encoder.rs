@@ -7,6 +7,7 @@ use core::ops;
 
 use super::error::Error;
 use super::truncate::TruncateNum;
+use super::unit::Unit;
 use super::width::Widths;
 
 /// Am encoder for packets and/or [payloads][super::payload]
@@ -15,6 +16,7 @@ pub struct Encoder<'d, U> {
     bit_pos: usize,
     bytes_committed: usize,
     field_widths: Widths,
+    address_shift: Option<u8>,
     unit: U,
     hart_index_width: u8,
     timestamp_width: u8,
@@ -26,6 +28,7 @@ impl<'d, U> Encoder<'d, U> {
     /// Create a new encoder
     pub(super) fn new(
         field_widths: Widths,
+        address_shift: Option<u8>,
         unit: U,
         hart_index_width: u8,
         timestamp_width: u8,
@@ -37,6 +40,7 @@ impl<'d, U> Encoder<'d, U> {
             bit_pos: 0,
             bytes_committed: 0,
             field_widths,
+            address_shift,
             unit,
             hart_index_width,
             timestamp_width,
@@ -68,8 +72,25 @@ impl<'d, U> Encoder<'d, U> {
     }
 
     /// Retrieve the payload field widths
-    pub(super) fn widths(&self) -> &Widths {
-        &self.field_widths
+    ///
+    /// If the [`Unit`] has [fixed parameters][Unit::FIXED_PARAMETERS], widths
+    /// are derived from those at compile time. An address shift overriding
+    /// `iaddress_lsb_p` is applied in any case.
+    pub(super) fn widths(&self) -> Widths
+    where
+        U: Unit,
+    {
+        let fixed = const {
+            match U::FIXED_PARAMETERS {
+                Some(params) => Some(Widths::new(&params)),
+                None => None,
+            }
+        };
+        let mut widths = fixed.unwrap_or(self.field_widths);
+        if let Some(shift) = self.address_shift {
+            widths.iaddress_lsb = shift;
+        }
+        widths
     }
 
     /// Retrieve the hart index width
@@ -106,6 +127,15 @@ impl<'d, U> Encoder<'d, U> {
         Ok(chunk)
     }
 
+    /// Write an unsigned integer field of the given width
+    ///
+    /// This fn allows encoding unit specific payload data, e.g. in
+    /// [`Unit::encode_extension`]. Widths
+    /// greater than `64` are treated as `64`.
+    pub fn write_uint(&mut self, value: u64, width: u8) -> Result<(), Error> {
+        self.write_bits(value, width.min(64))
+    }
+
     /// Write a single bit
     pub(super) fn write_bit(&mut self, bit: bool) -> Result<(), Error> {
         let byte_pos = self.bit_pos >> 3;
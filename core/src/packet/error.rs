@@ -6,7 +6,8 @@ use core::fmt;
 use core::num::NonZeroUsize;
 
 /// Packet decode/encode errors
-#[derive(Copy, Clone, Debug, PartialEq)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum Error {
     /// The trace type is not known to us
     UnknownTraceType(u8),
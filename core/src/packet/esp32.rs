@@ -75,9 +75,9 @@ impl<'d, U: Clone> Decode<'d, U> for Packet<Decoder<'d, U>> {
             // are taken care of by `Decoder::split_data`.
             return Err(Error::InsufficientData(core::num::NonZeroUsize::MIN));
         }
-        let length = decoder.read_bits(5)?;
-        let placeholder: u8 = decoder.read_bits(3)?; // placeholder 
-        let index = decoder.read_bits(16)?;
+        let length = decoder.read_bits("length", 5)?;
+        let placeholder: u8 = decoder.read_bits("placeholder", 3)?; // placeholder 
+        let index = decoder.read_bits("index", 16)?;
 
         if placeholder != 0 {
             return Err(Error::PlaceholderNonZero(placeholder));
@@ -0,0 +1,377 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Extension payloads
+
+use core::fmt;
+
+use crate::types::branch;
+
+use super::decoder::{Decode, Decoder};
+use super::encoder::{Encode, Encoder};
+use super::payload::AddressInfo;
+use super::unit::Unit;
+use super::{Error, util};
+
+/// Extension payload
+///
+/// Represents a format 0 packet. Subformats not defined by the specification
+/// are decoded by the [`Unit`] as [`Vendor`] payloads.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum Extension {
+    BranchCount(BranchCount),
+    JumpTargetIndex(JumpTargetIndex),
+    Vendor(Vendor),
+}
+
+impl Extension {
+    /// Retrieve the [`AddressInfo`] in this payload
+    ///
+    /// Returns a reference to the [`AddressInfo`] contained in this payload or
+    /// [`None`] if it does not contain one.
+    pub fn get_address_info(&self) -> Option<&AddressInfo> {
+        match self {
+            Self::BranchCount(b) => b.kind.address_info(),
+            Self::Vendor(v) => v.address.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Retrieve the implicit return depth
+    pub fn implicit_return_depth(&self) -> Option<usize> {
+        match self {
+            Self::BranchCount(b) => b.kind.address_info().and_then(|a| a.irdepth),
+            Self::JumpTargetIndex(j) => j.irdepth,
+            Self::Vendor(v) => v.address.and_then(|a| a.irdepth),
+        }
+    }
+
+    /// Retrieve the branches reported in this payload, if any
+    pub fn branch_map(&self) -> Option<branch::Map> {
+        match self {
+            Self::Vendor(v) => Some(v.branches),
+            _ => None,
+        }
+    }
+}
+
+impl<U: Unit> Decode<'_, U> for Extension {
+    fn decode(decoder: &mut Decoder<U>) -> Result<Self, Error> {
+        match decoder.read_bits("subformat", decoder.widths().format0_subformat)? {
+            0 => BranchCount::decode(decoder).map(Self::BranchCount),
+            1 => JumpTargetIndex::decode(decoder).map(Self::JumpTargetIndex),
+            s => U::decode_extension(s, decoder).map(Self::Vendor),
+        }
+    }
+}
+
+impl<U: Unit> Encode<'_, U> for Extension {
+    fn encode(&self, encoder: &mut Encoder<U>) -> Result<(), Error> {
+        let format0_subformat = encoder.widths().format0_subformat;
+        match self {
+            Self::BranchCount(branch) => {
+                encoder.write_bits(0u8, format0_subformat)?;
+                encoder.encode(branch)
+            }
+            Self::JumpTargetIndex(jti) => {
+                encoder.write_bits(1u8, format0_subformat)?;
+                encoder.encode(jti)
+            }
+            Self::Vendor(vendor) => {
+                encoder.write_bits(vendor.subformat, format0_subformat)?;
+                U::encode_extension(vendor, encoder)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Extension {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::BranchCount(b) => write!(f, "BRANCH COUNT {b}"),
+            Self::JumpTargetIndex(j) => write!(f, "JTI {j}"),
+            Self::Vendor(v) => write!(f, "VENDOR {v}"),
+        }
+    }
+}
+
+/// Unit specific extension payload
+///
+/// Represents a format 0 packet with a subformat not defined by the
+/// specification. Such payloads are decoded and encoded by the [`Unit`] via
+/// [`Unit::decode_extension`] and [`Unit::encode_extension`].
+///
+/// The [`Tracer`][crate::tracer::Tracer] processes this payload like a
+/// [`Branch`][super::payload::Branch] payload: the `branches` are appended to
+/// the branch map and the `address`, if present, is reported. Units are
+/// expected to map their subformats' semantics onto those fields. Additional
+/// data may be conveyed via `data`, which is not interpreted by this library.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct Vendor {
+    /// Subformat of the payload
+    pub(crate) subformat: u8,
+    /// Branches reported by this payload
+    pub(crate) branches: branch::Map,
+    /// Address reported by this payload, if any
+    pub(crate) address: Option<AddressInfo>,
+    /// Unit specific data
+    pub(crate) data: u64,
+}
+
+impl Vendor {
+    /// Create a new vendor payload of the given subformat
+    pub fn new(subformat: u8) -> Self {
+        Self {
+            subformat,
+            ..Default::default()
+        }
+    }
+
+    /// Set the branches reported
+    pub fn with_branches(self, branches: branch::Map) -> Self {
+        Self { branches, ..self }
+    }
+
+    /// Set the [`AddressInfo`] reported
+    pub fn with_address(self, address: AddressInfo) -> Self {
+        Self {
+            address: Some(address),
+            ..self
+        }
+    }
+
+    /// Set the unit specific data
+    pub fn with_data(self, data: u64) -> Self {
+        Self { data, ..self }
+    }
+
+    /// Retrieve the subformat
+    pub fn subformat(&self) -> u8 {
+        self.subformat
+    }
+
+    /// Retrieve the branches reported
+    pub fn branches(&self) -> branch::Map {
+        self.branches
+    }
+
+    /// Retrieve the [`AddressInfo`] reported, if any
+    pub fn address(&self) -> Option<AddressInfo> {
+        self.address
+    }
+
+    /// Retrieve the unit specific data
+    pub fn data(&self) -> u64 {
+        self.data
+    }
+}
+
+impl fmt::Display for Vendor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "subformat: {}, data: {:#x}, {}",
+            self.subformat, self.data, self.branches
+        )?;
+        if let Some(info) = self.address {
+            write!(f, ", {info}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Branch count payload
+///
+/// Represents a format 0, subformat 0 packet. It informs about the number of
+/// correctly predicted branches.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct BranchCount {
+    /// Count of the number of correctly predicted branches, minus 31.
+    pub(crate) branch_count: u32,
+    pub(crate) kind: BranchKind,
+}
+
+impl BranchCount {
+    /// Create a new branch count payload
+    pub fn new(branch_count: u32, kind: BranchKind) -> Self {
+        Self { branch_count, kind }
+    }
+
+    /// Retrieve the number of correctly predicted branches, minus 31
+    pub fn branch_count(&self) -> u32 {
+        self.branch_count
+    }
+
+    /// Retrieve the [`BranchKind`]
+    pub fn kind(&self) -> BranchKind {
+        self.kind
+    }
+}
+
+impl<U: Unit> Decode<'_, U> for BranchCount {
+    fn decode(decoder: &mut Decoder<U>) -> Result<Self, Error> {
+        let branch_count = decoder.read_bits("branch_count", 32)?;
+        let kind = BranchKind::decode(decoder)?;
+        Ok(BranchCount { branch_count, kind })
+    }
+}
+
+impl<U: Unit> Encode<'_, U> for BranchCount {
+    fn encode(&self, encoder: &mut Encoder<U>) -> Result<(), Error> {
+        encoder.write_bits(self.branch_count, 32)?;
+        encoder.encode(&self.kind)
+    }
+}
+
+impl fmt::Display for BranchCount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} branches predicted correctly", self.branch_count)?;
+        match self.kind {
+            BranchKind::NoAddr => Ok(()),
+            BranchKind::Addr(info) => write!(f, ", including branch at {info}"),
+            BranchKind::AddrFail(info) => write!(f, ", excluding branch at {info}"),
+        }
+    }
+}
+
+/// Determines the layout of [`BranchCount`].
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum BranchKind {
+    /// No address
+    ///
+    /// The packet does not contain an address, and the branch following the
+    /// last correct prediction failed.
+    NoAddr,
+    /// Address, success
+    ///
+    /// The packet contains an address. If this points to a branch instruction,
+    /// then the branch was predicted correctly.
+    Addr(AddressInfo),
+    /// Address, failure
+    ///
+    /// The packet contains an address that points to a branch instruction. The
+    /// prediction for that branch failed.
+    AddrFail(AddressInfo),
+}
+
+impl BranchKind {
+    /// Retrieve the [`AddressInfo`] in this branch kind
+    pub fn address_info(&self) -> Option<&AddressInfo> {
+        match self {
+            Self::Addr(a) => Some(a),
+            Self::AddrFail(a) => Some(a),
+            _ => None,
+        }
+    }
+}
+
+impl<U: Unit> Decode<'_, U> for BranchKind {
+    fn decode(decoder: &mut Decoder<U>) -> Result<Self, Error> {
+        match decoder.read_bits::<u8>("branch_fmt", 2)? {
+            0b00 => Ok(Self::NoAddr),
+            0b01 => Err(Error::BadBranchFmt),
+            0b10 => decoder.decode().map(Self::Addr),
+            0b11 => decoder.decode().map(Self::AddrFail),
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<U: Unit> Encode<'_, U> for BranchKind {
+    fn encode(&self, encoder: &mut Encoder<U>) -> Result<(), Error> {
+        match self {
+            Self::NoAddr => encoder.write_bits(0b00u8, 2),
+            Self::Addr(info) => {
+                encoder.write_bits(0b10u8, 2)?;
+                encoder.encode(info)
+            }
+            Self::AddrFail(info) => {
+                encoder.write_bits(0b11u8, 2)?;
+                encoder.encode(info)
+            }
+        }
+    }
+}
+
+/// Jump target index payload
+///
+/// Represents a format 0, subformat 1 packet.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub struct JumpTargetIndex {
+    /// Index of entry containing the jump's target address
+    pub(crate) index: usize,
+    pub(crate) branch_map: branch::Map,
+
+    /// Implicit return depth
+    pub(crate) irdepth: Option<usize>,
+}
+
+impl JumpTargetIndex {
+    /// Create a new jump target index payload
+    pub fn new(index: usize, branch_map: branch::Map) -> Self {
+        Self {
+            index,
+            branch_map,
+            irdepth: None,
+        }
+    }
+
+    /// Set the implicit return depth
+    pub fn with_irdepth(self, irdepth: usize) -> Self {
+        Self {
+            irdepth: Some(irdepth),
+            ..self
+        }
+    }
+
+    /// Retrieve the index of the entry containing the jump's target address
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Retrieve the branch map
+    pub fn branch_map(&self) -> branch::Map {
+        self.branch_map
+    }
+
+    /// Retrieve the implicit return depth, if reported
+    pub fn irdepth(&self) -> Option<usize> {
+        self.irdepth
+    }
+}
+
+impl<U: Unit> Decode<'_, U> for JumpTargetIndex {
+    fn decode(decoder: &mut Decoder<U>) -> Result<Self, Error> {
+        let index = decoder.read_bits("index", decoder.widths().cache_index)?;
+        let branch_map = util::BranchCount::decode(decoder)?.read_branch_map(decoder)?;
+        let irdepth = util::read_implicit_return(decoder)?;
+        Ok(JumpTargetIndex {
+            index,
+            branch_map,
+            irdepth,
+        })
+    }
+}
+
+impl<U: Unit> Encode<'_, U> for JumpTargetIndex {
+    fn encode(&self, encoder: &mut Encoder<U>) -> Result<(), Error> {
+        encoder.write_bits(self.index, encoder.widths().cache_index)?;
+        let count = util::BranchCount(self.branch_map.count());
+        encoder.encode(&count)?;
+        encoder.write_bits(self.branch_map.raw_map(), count.field_length())?;
+        util::write_implicit_return(encoder, self.irdepth)
+    }
+}
+
+impl fmt::Display for JumpTargetIndex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "index: {}, {}", self.index, self.branch_map)?;
+        if let Some(irdepth) = self.irdepth {
+            write!(f, ", irdepth: {irdepth}")?;
+        }
+        Ok(())
+    }
+}
@@ -12,6 +12,7 @@ use super::{Error, ext, sync, unit, util};
 
 /// An E-Trace payload
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[non_exhaustive]
 pub enum Payload<I = unit::ReferenceIOptions, D = unit::ReferenceDOptions> {
     /// An instruction trace payload
     InstructionTrace(InstructionTrace<I, D>),
@@ -68,6 +69,7 @@ impl<I: unit::IOptions, D> fmt::Display for Payload<I, D> {
 
 /// An instruction trace payload
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum InstructionTrace<I = unit::ReferenceIOptions, D = unit::ReferenceDOptions> {
     Extension(ext::Extension),
     Branch(Branch),
@@ -77,13 +79,14 @@ pub enum InstructionTrace<I = unit::ReferenceIOptions, D = unit::ReferenceDOptio
 
 impl<U: unit::Unit> Decode<'_, U> for InstructionTrace<U::IOptions, U::DOptions> {
     fn decode(decoder: &mut Decoder<U>) -> Result<Self, Error> {
-        match decoder.read_bits::<u8>(2)? {
+        let format = decoder.read_bits::<u8>("format", 2)?;
+        decoder.with_adjusted_widths(format, |decoder| match format {
             0b00 => ext::Extension::decode(decoder).map(Into::into),
             0b01 => Branch::decode(decoder).map(Into::into),
             0b10 => AddressInfo::decode(decoder).map(Into::into),
             0b11 => sync::Synchronization::decode(decoder).map(Into::into),
             _ => unreachable!(),
-        }
+        })
     }
 }
 
@@ -242,12 +245,41 @@ impl<I: unit::IOptions, D> fmt::Display for InstructionTrace<I, D> {
 /// instruction must be reported, and there has been at least one branch since
 /// the previous packet
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
 pub struct Branch {
-    pub branch_map: branch::Map,
-    pub address: Option<AddressInfo>,
+    pub(crate) branch_map: branch::Map,
+    pub(crate) address: Option<AddressInfo>,
 }
 
-impl<U> Decode<'_, U> for Branch {
+impl Branch {
+    /// Create a new branch payload for the given branch map without address
+    pub fn new(branch_map: branch::Map) -> Self {
+        Self {
+            branch_map,
+            address: None,
+        }
+    }
+
+    /// Set the [`AddressInfo`]
+    pub fn with_address(self, address: AddressInfo) -> Self {
+        Self {
+            address: Some(address),
+            ..self
+        }
+    }
+
+    /// Retrieve the branch map
+    pub fn branch_map(&self) -> branch::Map {
+        self.branch_map
+    }
+
+    /// Retrieve the [`AddressInfo`], if any
+    pub fn address(&self) -> Option<AddressInfo> {
+        self.address
+    }
+}
+
+impl<U: unit::Unit> Decode<'_, U> for Branch {
     fn decode(decoder: &mut Decoder<U>) -> Result<Self, Error> {
         use util::BranchCount;
 
@@ -269,7 +301,7 @@ impl<U> Decode<'_, U> for Branch {
     }
 }
 
-impl<U> Encode<'_, U> for Branch {
+impl<U: unit::Unit> Encode<'_, U> for Branch {
     fn encode(&self, encoder: &mut Encoder<U>) -> Result<(), Error> {
         if let Some(address) = self.address.as_ref() {
             let count = util::BranchCount(self.branch_map.count());
@@ -304,16 +336,17 @@ impl fmt::Display for Branch {
 /// Inaddition to being a payload on its own, it also is used as part of other
 /// payloads.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
 pub struct AddressInfo {
     /// Differential instruction address.
-    pub address: i64,
+    pub(crate) address: i64,
 
     /// A notification was requested by a trigger
     ///
     /// If `true`, this packet is reporting an instruction that is not the
     /// target of an uninferable discontinuity because a notification was
     /// requested via a trigger.
-    pub notify: bool,
+    pub(crate) notify: bool,
 
     /// An uninferable discontinuity occured before a sync event
     ///
@@ -321,17 +354,67 @@ pub struct AddressInfo {
     /// uninferable discontinuity and is also the instruction before an
     /// exception, privilege change or resync (i.e. it will be followed
     /// immediately by a format 3 packet).
-    pub updiscon: bool,
+    pub(crate) updiscon: bool,
 
     /// Implicit return depth
-    pub irdepth: Option<usize>,
+    pub(crate) irdepth: Option<usize>,
+}
+
+impl AddressInfo {
+    /// Create a new address info payload for the given differential address
+    pub fn new(address: i64) -> Self {
+        Self {
+            address,
+            notify: false,
+            updiscon: false,
+            irdepth: None,
+        }
+    }
+
+    /// Set whether a notification was requested by a trigger
+    pub fn with_notify(self, notify: bool) -> Self {
+        Self { notify, ..self }
+    }
+
+    /// Set whether an uninferable discontinuity preceded a sync event
+    pub fn with_updiscon(self, updiscon: bool) -> Self {
+        Self { updiscon, ..self }
+    }
+
+    /// Set the implicit return depth
+    pub fn with_irdepth(self, irdepth: usize) -> Self {
+        Self {
+            irdepth: Some(irdepth),
+            ..self
+        }
+    }
+
+    /// Retrieve the differential instruction address
+    pub fn address(&self) -> i64 {
+        self.address
+    }
+
+    /// Retrieve whether a notification was requested by a trigger
+    pub fn notify(&self) -> bool {
+        self.notify
+    }
+
+    /// Retrieve whether an uninferable discontinuity preceded a sync event
+    pub fn updiscon(&self) -> bool {
+        self.updiscon
+    }
+
+    /// Retrieve the implicit return depth, if reported
+    pub fn irdepth(&self) -> Option<usize> {
+        self.irdepth
+    }
 }
 
-impl<U> Decode<'_, U> for AddressInfo {
+impl<U: unit::Unit> Decode<'_, U> for AddressInfo {
     fn decode(decoder: &mut Decoder<U>) -> Result<Self, Error> {
         let address = util::read_address(decoder)?;
-        let notify = decoder.read_differential_bit()?;
-        let updiscon = decoder.read_differential_bit()?;
+        let notify = util::read_address_flag(decoder, "notify", address)?;
+        let updiscon = util::read_address_flag(decoder, "updiscon", address)?;
         let irdepth = util::read_implicit_return(decoder)?;
         Ok(AddressInfo {
             address,
@@ -342,11 +425,11 @@ impl<U> Decode<'_, U> for AddressInfo {
     }
 }
 
-impl<U> Encode<'_, U> for AddressInfo {
+impl<U: unit::Unit> Encode<'_, U> for AddressInfo {
     fn encode(&self, encoder: &mut Encoder<U>) -> Result<(), Error> {
         util::write_address(encoder, self.address)?;
-        encoder.write_differential_bit(self.notify)?;
-        encoder.write_differential_bit(self.updiscon)?;
+        util::write_address_flag(encoder, self.notify, self.address)?;
+        util::write_address_flag(encoder, self.updiscon, self.address)?;
         util::write_implicit_return(encoder, self.irdepth)
     }
 }
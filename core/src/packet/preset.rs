@@ -0,0 +1,143 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Configuration presets for known trace encoders
+//!
+//! Decoding a trace requires a [`Builder`] configured with the trace
+//! [unit][unit::Unit] and [`config::Parameters`] of the encoder that
+//! produced it as well as various field widths and flags. This module provides
+//! [`Preset`]s bundling such a configuration for known platforms, which may be
+//! applied to a [`Builder`] via [`Builder::with_preset`].
+//!
+//! Presets are plain data. New ones may be defined by users in the same way as
+//! the ones provided by this library. With the `alloc` feature, all presets
+//! provided by this library are listed in [`PRESETS`].
+//!
+//! # Example
+//!
+//! ```
+//! use riscv_etrace_core::packet::{self, preset::Preset};
+//!
+//! let decoder = packet::builder()
+//!     .with_preset(&Preset::REFERENCE_FLOW_64)
+//!     .decoder(&[]);
+//! assert_eq!(decoder.widths().iaddress.get(), 64);
+//! ```
+//!
+//! [`Builder`]: super::Builder
+//! [`Builder::with_preset`]: super::Builder::with_preset
+
+use crate::config;
+
+use super::unit;
+#[cfg(feature = "alloc")]
+use super::unit::Plug;
+
+/// Decoder configuration for a specific platform
+///
+/// See the [module][self] documentation for details.
+#[derive(Copy, Clone, Debug)]
+pub struct Preset<U> {
+    /// Short, unique name
+    pub name: &'static str,
+    /// Description of the platform
+    pub description: &'static str,
+    /// Trace [unit][unit::Unit]
+    pub unit: U,
+    /// Encoder parameters
+    pub params: config::Parameters,
+    /// Width of source index fields
+    ///
+    /// See [`with_hart_index_width`][super::Builder::with_hart_index_width].
+    pub hart_index_width: u8,
+    /// Width of timestamps in packet headers
+    ///
+    /// See [`with_timestamp_width`][super::Builder::with_timestamp_width].
+    pub timestamp_width: u8,
+    /// Width of trace type fields
+    ///
+    /// See [`with_trace_type_width`][super::Builder::with_trace_type_width].
+    pub trace_type_width: u8,
+    /// Whether to decode strictly
+    ///
+    /// See [`with_strict`][super::Builder::with_strict].
+    pub strict: bool,
+}
+
+impl Preset<unit::Reference> {
+    /// Reference flow's encoder model configured for RV32
+    pub const REFERENCE_FLOW_32: Self = Self {
+        name: "reference-flow-32",
+        description: "Reference flow's encoder model, RV32 configuration",
+        unit: unit::Reference,
+        params: config::PARAMETERS,
+        hart_index_width: 0,
+        timestamp_width: 0,
+        trace_type_width: 0,
+        strict: false,
+    };
+
+    /// Reference flow's encoder model configured for RV64
+    pub const REFERENCE_FLOW_64: Self = Self {
+        name: "reference-flow-64",
+        description: "Reference flow's encoder model, RV64 configuration",
+        params: config::Parameters {
+            iaddress_width_p: core::num::NonZeroU8::new(64).unwrap(),
+            ..config::PARAMETERS
+        },
+        ..Self::REFERENCE_FLOW_32
+    };
+}
+
+impl Preset<unit::PULP> {
+    /// PULP platform's rv_tracer
+    pub const PULP: Self = Self {
+        name: "pulp",
+        description: "PULP platform's rv_tracer",
+        unit: unit::PULP,
+        params: config::Parameters {
+            ecause_width_p: core::num::NonZeroU8::new(5).unwrap(),
+            ..config::PARAMETERS
+        },
+        hart_index_width: 0,
+        timestamp_width: 0,
+        trace_type_width: 0,
+        strict: false,
+    };
+}
+
+#[cfg(feature = "alloc")]
+impl<U: unit::Unit<Plug>> Preset<U>
+where
+    U::IOptions: core::fmt::Debug,
+    U::DOptions: core::fmt::Debug,
+{
+    /// Convert this preset into one using a [`Plug`]
+    pub fn plugged(&self) -> Preset<Plug> {
+        Preset {
+            name: self.name,
+            description: self.description,
+            unit: Plug::new(&self.unit),
+            params: self.params,
+            hart_index_width: self.hart_index_width,
+            timestamp_width: self.timestamp_width,
+            trace_type_width: self.trace_type_width,
+            strict: self.strict,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Preset<Plug> {
+    /// Find the preset in [`PRESETS`] with the given name
+    pub fn by_name(name: &str) -> Option<Self> {
+        PRESETS.iter().map(|p| p()).find(|p| p.name == name)
+    }
+}
+
+/// List of constructors for all [`Preset`]s provided by this library
+#[cfg(feature = "alloc")]
+pub const PRESETS: &[fn() -> Preset<Plug>] = &[
+    || Preset::REFERENCE_FLOW_32.plugged(),
+    || Preset::REFERENCE_FLOW_64.plugged(),
+    || Preset::PULP.plugged(),
+];
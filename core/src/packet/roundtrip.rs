@@ -0,0 +1,219 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Round-trip property checks for payload encoding and decoding
+//!
+//! This module provides utilities for checking that payloads survive a round
+//! trip through an [`Encoder`] and a [`Decoder`] unaltered. The checks are
+//! randomized: payloads are obtained by decoding pseudo-random bitstreams
+//! under pseudo-random [`config::Parameters`]. Each payload decoded
+//! successfully is then encoded and decoded again, and the result compared to
+//! the original payload.
+//!
+//! Since payloads are obtained via decoding, no knowledge about the structure
+//! of a unit's [`IOptions`][super::unit::Unit::IOptions] or
+//! [`DOptions`][super::unit::Unit::DOptions] is required. Hence, implementers
+//! of custom [`Unit`]s may use [`check_random`] for verifying that their
+//! option encodings round-trip.
+//!
+//! # Example
+//!
+//! ```
+//! use riscv_etrace_core::packet::{self, roundtrip};
+//!
+//! let mut rng = roundtrip::Rng::new(0x2545f4914f6cdd1d);
+//! let checked = roundtrip::check_random(packet::builder(), &mut rng, 64).unwrap();
+//! assert!(checked > 0);
+//! ```
+//!
+//! [`Encoder`]: super::encoder::Encoder
+//! [`Decoder`]: super::decoder::Decoder
+
+use core::fmt;
+use core::num::NonZeroU8;
+
+use crate::config;
+
+use super::Builder;
+use super::encoder::Encode;
+use super::error::Error;
+use super::payload::InstructionTrace;
+use super::unit::Unit;
+
+/// Maximum size of a payload in bytes supported by the checks
+const MAX_PAYLOAD_SIZE: usize = 64;
+
+/// Simple xorshift pseudo-random number generator
+///
+/// This generator is not suitable for anything but generating reproducible
+/// test inputs.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    /// Create a new generator from the given seed
+    ///
+    /// A seed of `0` is replaced by a non-zero value.
+    pub fn new(seed: u64) -> Self {
+        Self(if seed == 0 { 0x2545f4914f6cdd1d } else { seed })
+    }
+
+    /// Generate the next pseudo-random number
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// Generate a pseudo-random number in the given inclusive range
+    pub fn range(&mut self, min: u8, max: u8) -> u8 {
+        let span = u64::from(max.saturating_sub(min)) + 1;
+        min + (self.next_u64() % span) as u8
+    }
+
+    /// Generate a pseudo-random flag
+    pub fn flag(&mut self) -> bool {
+        self.next_u64() & 1 != 0
+    }
+
+    /// Fill the given buffer with pseudo-random bytes
+    pub fn fill(&mut self, buffer: &mut [u8]) {
+        buffer.iter_mut().for_each(|b| *b = self.next_u64() as u8);
+    }
+
+    /// Generate pseudo-random [`config::Parameters`]
+    ///
+    /// The parameters generated are within the limits supported by this
+    /// library.
+    pub fn parameters(&mut self) -> config::Parameters {
+        let iaddress_width_p = self.non_zero(64);
+        config::Parameters {
+            cache_size_p: self.range(0, 8),
+            call_counter_size_p: self.range(0, 8),
+            context_width_p: self.non_zero(64),
+            time_width_p: self.non_zero(64),
+            ecause_width_p: self.non_zero(16),
+            f0s_width_p: self.range(0, 2),
+            iaddress_lsb_p: self.range(0, (iaddress_width_p.get() - 1).min(2)),
+            iaddress_width_p,
+            nocontext_p: self.flag(),
+            notime_p: self.flag(),
+            privilege_width_p: self.non_zero(3),
+            return_stack_size_p: self.range(0, 8),
+            sijump_p: self.flag(),
+        }
+    }
+
+    /// Generate a pseudo-random non-zero number up to `max`
+    fn non_zero(&mut self, max: u8) -> NonZeroU8 {
+        NonZeroU8::new(self.range(1, max)).unwrap_or(NonZeroU8::MIN)
+    }
+}
+
+/// Result of a round-trip check for [`Unit`] `U`
+pub type Outcome<T, U> =
+    Result<T, Failure<InstructionTrace<<U as Unit>::IOptions, <U as Unit>::DOptions>>>;
+
+/// Failure of a round-trip check
+#[derive(Clone, Debug, PartialEq)]
+pub enum Failure<P> {
+    /// The payload could not be encoded
+    Encode(P, Error),
+    /// The payload could not be decoded after encoding it
+    Decode(P, Error),
+    /// The payload decoded differs from the original one
+    Mismatch {
+        /// The original payload
+        original: P,
+        /// The payload decoded after encoding the original one
+        decoded: P,
+    },
+}
+
+impl<P: fmt::Debug> core::error::Error for Failure<P> {
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Encode(_, inner) => Some(inner),
+            Self::Decode(_, inner) => Some(inner),
+            Self::Mismatch { .. } => None,
+        }
+    }
+}
+
+impl<P: fmt::Debug> fmt::Display for Failure<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Encode(p, _) => write!(f, "could not encode {p:?}"),
+            Self::Decode(p, _) => write!(f, "could not decode {p:?} after encoding"),
+            Self::Mismatch { original, decoded } => {
+                write!(f, "{original:?} was decoded as {decoded:?}")
+            }
+        }
+    }
+}
+
+/// Check that a single payload round-trips
+///
+/// Encodes the given payload using an [`Encoder`][super::encoder::Encoder]
+/// built by the given [`Builder`], decodes the result and compares it against
+/// the original payload.
+#[allow(clippy::result_large_err)]
+pub fn check_payload<U>(
+    builder: Builder<U>,
+    payload: &InstructionTrace<U::IOptions, U::DOptions>,
+) -> Outcome<(), U>
+where
+    U: Unit + Clone,
+    U::IOptions: Clone + PartialEq + for<'a> Encode<'a, U>,
+    U::DOptions: Clone + PartialEq + for<'a> Encode<'a, U>,
+{
+    let mut buffer = [0u8; MAX_PAYLOAD_SIZE];
+    let mut encoder = builder.clone().encoder(&mut buffer);
+    encoder
+        .encode(payload)
+        .map_err(|e| Failure::Encode(payload.clone(), e))?;
+    let len = MAX_PAYLOAD_SIZE - encoder.uncommitted();
+
+    let decoded = builder
+        .decoder(&buffer[..len])
+        .decode_payload()
+        .map_err(|e| Failure::Decode(payload.clone(), e))?;
+    if decoded == *payload {
+        Ok(())
+    } else {
+        Err(Failure::Mismatch {
+            original: payload.clone(),
+            decoded,
+        })
+    }
+}
+
+/// Check that payloads obtained from pseudo-random data round-trip
+///
+/// For each of the given number of `rounds`, pseudo-random
+/// [`config::Parameters`] are applied to the given [`Builder`] and a payload is
+/// decoded from pseudo-random data. If decoding succeeds, the payload is
+/// checked via [`check_payload`].
+///
+/// Returns the number of payloads checked. The first failure is reported as an
+/// error.
+#[allow(clippy::result_large_err)]
+pub fn check_random<U>(builder: Builder<U>, rng: &mut Rng, rounds: usize) -> Outcome<usize, U>
+where
+    U: Unit + Clone,
+    U::IOptions: Clone + PartialEq + for<'a> Encode<'a, U>,
+    U::DOptions: Clone + PartialEq + for<'a> Encode<'a, U>,
+{
+    let mut data = [0u8; MAX_PAYLOAD_SIZE / 2];
+    let mut checked = 0;
+    for _ in 0..rounds {
+        let builder = builder.clone().with_params(&rng.parameters());
+        rng.fill(&mut data);
+        let Ok(payload) = builder.clone().decoder(&data).decode_payload() else {
+            continue;
+        };
+        check_payload(builder, &payload)?;
+        checked += 1;
+    }
+    Ok(checked)
+}
@@ -4,7 +4,7 @@
 
 use core::fmt;
 
-use super::decoder::{Decode, Decoder};
+use super::decoder::{Alignment, Decode, Decoder};
 use super::encoder::{Encode, Encoder};
 use super::{Error, payload, unit};
 
@@ -132,23 +132,48 @@ where
 
 impl<'d, U: Clone> Decode<'d, U> for Packet<Decoder<'d, U>> {
     fn decode(decoder: &mut Decoder<'d, U>) -> Result<Self, Error> {
-        let payload_len: usize = decoder.read_bits(5)?;
-        let trace_type = decoder.read_bits::<u8>(2)?;
-        let time_tag = decoder
-            .read_bit()?
-            .then(|| decoder.read_bits(16))
-            .transpose()?;
-        let hart = decoder.read_bits(decoder.hart_index_width())?;
-        decoder.advance_to_byte();
-        decoder.split_off_to(payload_len).map(|payload| Self {
-            trace_type,
-            time_tag,
-            hart,
+        let header = decode_header(decoder)?;
+        decoder.split_off_to(header.payload).map(|payload| Self {
+            trace_type: header.trace_type,
+            time_tag: header.time_tag,
+            hart: header.hart,
             payload,
         })
     }
 }
 
+/// Decoding of a packet's header only
+///
+/// The payload is skipped. The packet's payload is the payload's length in
+/// bytes.
+impl<'d, U> Decode<'d, U> for Packet<usize> {
+    fn decode(decoder: &mut Decoder<'d, U>) -> Result<Self, Error> {
+        let header = decode_header(decoder)?;
+        decoder.skip_bytes(header.payload)?;
+        Ok(header)
+    }
+}
+
+/// Decode a packet header, leaving the decoder at the start of the payload
+///
+/// The packet returned holds the payload's length in bytes.
+fn decode_header<U>(decoder: &mut Decoder<U>) -> Result<Packet<usize>, Error> {
+    let payload_len = decoder.read_bits("payload_len", 5)?;
+    let trace_type = decoder.read_bits::<u8>("trace_type", 2)?;
+    let time_tag = decoder
+        .read_bit("time_tag_present")?
+        .then(|| decoder.read_bits("time_tag", 16))
+        .transpose()?;
+    let hart = decoder.read_bits("hart", decoder.hart_index_width())?;
+    decoder.advance_to_byte();
+    Ok(Packet {
+        trace_type,
+        time_tag,
+        hart,
+        payload: payload_len,
+    })
+}
+
 impl<'d, U, P: Encode<'d, U>> Encode<'d, U> for Packet<P> {
     fn encode(&self, encoder: &mut Encoder<'d, U>) -> Result<(), Error> {
         let head = &mut encoder.first_uncommitted_chunk::<1>()?[0];
@@ -0,0 +1,78 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Non-contiguous sources of trace data
+//!
+//! Trace data is not always available as a single, contiguous slice. Examples
+//! include chained DMA descriptors, ring buffers which wrap around or windows
+//! into memory which need to be accessed in a specific way. Rather than copying
+//! such data into a single buffer, a [`Decoder`][super::decoder::Decoder] may
+//! decode directly from a [`ByteSource`] via
+//! [`Builder::decoder_for_source`][super::Builder::decoder_for_source].
+//!
+//! # Example
+//!
+//! ```
+//! use riscv_etrace_core::packet;
+//!
+//! // A ring buffer that wrapped around in the middle of a packet
+//! let segments: &[&[u8]] = &[b"\x45\x73\x0a", b"\x00\x00\x20"];
+//! let mut decoder = packet::builder().decoder_for_source(&segments);
+//! let packet = decoder.decode_encap_packet().unwrap();
+//! assert_eq!(decoder.bytes_left(), 0);
+//! ```
+
+/// Random access source of bytes
+pub trait ByteSource {
+    /// Retrieve the number of bytes available
+    fn len(&self) -> usize;
+
+    /// Retrieve the byte at the given index
+    ///
+    /// Returns [`None`] if the index is not less than [`len`][Self::len].
+    fn get(&self, index: usize) -> Option<u8>;
+
+    /// Check whether this source is empty
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl ByteSource for [u8] {
+    fn len(&self) -> usize {
+        <[u8]>::len(self)
+    }
+
+    fn get(&self, index: usize) -> Option<u8> {
+        <[u8]>::get(self, index).copied()
+    }
+}
+
+impl<S: ByteSource + ?Sized> ByteSource for &S {
+    fn len(&self) -> usize {
+        S::len(self)
+    }
+
+    fn get(&self, index: usize) -> Option<u8> {
+        S::get(self, index)
+    }
+}
+
+/// Consecutive segments of data
+///
+/// Bytes are looked up by walking the segments. Hence, this impl is best
+/// suited for a small number of segments.
+impl ByteSource for [&[u8]] {
+    fn len(&self) -> usize {
+        self.iter().map(|s| s.len()).sum()
+    }
+
+    fn get(&self, mut index: usize) -> Option<u8> {
+        self.iter().find_map(|s| match <[u8]>::get(s, index) {
+            Some(byte) => Some(*byte),
+            None => {
+                index -= s.len();
+                None
+            }
+        })
+    }
+}
@@ -19,6 +19,7 @@ use super::{Error, util};
 ///
 /// Represents a format 3 packet.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum Synchronization<I = unit::ReferenceIOptions, D = unit::ReferenceDOptions> {
     Start(Start),
     Trap(Trap),
@@ -92,7 +93,7 @@ impl<I, D> From<Support<I, D>> for Synchronization<I, D> {
 
 impl<U: Unit> Decode<'_, U> for Synchronization<U::IOptions, U::DOptions> {
     fn decode(decoder: &mut Decoder<U>) -> Result<Self, Error> {
-        match decoder.read_bits::<u8>(2)? {
+        match decoder.read_bits::<u8>("subformat", 2)? {
             0b00 => Start::decode(decoder).map(Into::into),
             0b01 => Trap::decode(decoder).map(Into::into),
             0b10 => Context::decode(decoder).map(Into::into),
@@ -146,18 +147,58 @@ impl<I: unit::IOptions, D> fmt::Display for Synchronization<I, D> {
 /// Represents a format 3, subformat 0 packet. It is sent by the encoder for the
 /// first traced instruction or when resynchronization is necessary.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
 pub struct Start {
     /// False, if the address is a taken branch instruction. True, if the branch
     /// was not taken or the instruction is not a branch.
-    pub branch: bool,
-    pub ctx: Context,
+    pub(crate) branch: bool,
+    pub(crate) ctx: Context,
     /// Full address of the instruction.
-    pub address: u64,
+    pub(crate) address: u64,
 }
 
-impl<U> Decode<'_, U> for Start {
+impl Start {
+    /// Create a new start packet for the given full address
+    ///
+    /// The packet reports a branch that was not taken or a non-branch
+    /// instruction in the default [`Context`].
+    pub fn new(address: u64) -> Self {
+        Self {
+            branch: true,
+            ctx: Default::default(),
+            address,
+        }
+    }
+
+    /// Set whether the branch was not taken or the instruction is no branch
+    pub fn with_branch(self, branch: bool) -> Self {
+        Self { branch, ..self }
+    }
+
+    /// Set the [`Context`]
+    pub fn with_ctx(self, ctx: Context) -> Self {
+        Self { ctx, ..self }
+    }
+
+    /// Retrieve whether the branch was not taken or the instruction is no branch
+    pub fn branch(&self) -> bool {
+        self.branch
+    }
+
+    /// Retrieve the [`Context`]
+    pub fn ctx(&self) -> Context {
+        self.ctx
+    }
+
+    /// Retrieve the full address of the instruction
+    pub fn address(&self) -> u64 {
+        self.address
+    }
+}
+
+impl<U: Unit> Decode<'_, U> for Start {
     fn decode(decoder: &mut Decoder<U>) -> Result<Self, Error> {
-        let branch = decoder.read_bit()?;
+        let branch = decoder.read_bit("branch")?;
         let ctx = Context::decode(decoder)?;
         let address = util::read_address(decoder)?;
         Ok(Start {
@@ -168,7 +209,7 @@ impl<U> Decode<'_, U> for Start {
     }
 }
 
-impl<U> Encode<'_, U> for Start {
+impl<U: Unit> Encode<'_, U> for Start {
     fn encode(&self, encoder: &mut Encoder<U>) -> Result<(), Error> {
         encoder.write_bit(self.branch)?;
         encoder.encode(&self.ctx)?;
@@ -192,32 +233,89 @@ impl fmt::Display for Start {
 /// Represents a format 3, subformat 1 packet. It is sent by the encoder
 /// following an exception or interrupt.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[non_exhaustive]
 pub struct Trap {
     /// `false`, if the address is a taken branch instruction. `true`, if the
     /// branch was not taken or the instruction is not a branch.
-    pub branch: bool,
-    pub ctx: Context,
+    pub(crate) branch: bool,
+    pub(crate) ctx: Context,
     /// `true`, if the address points to the trap handler. `false`, if address
     /// points to the EPC for an exception at the target of an updiscon, and is
     /// undefined for other exceptions and interrupts.
-    pub thaddr: bool,
+    pub(crate) thaddr: bool,
     /// Full address of the instruction
-    pub address: u64,
-    pub info: trap::Info,
+    pub(crate) address: u64,
+    pub(crate) info: trap::Info,
 }
 
-impl<U> Decode<'_, U> for Trap {
+impl Trap {
+    /// Create a new trap packet for the given full address and trap info
+    ///
+    /// The packet reports a branch that was not taken or a non-branch
+    /// instruction at the trap handler's address in the default [`Context`].
+    pub fn new(address: u64, info: trap::Info) -> Self {
+        Self {
+            branch: true,
+            ctx: Default::default(),
+            thaddr: true,
+            address,
+            info,
+        }
+    }
+
+    /// Set whether the branch was not taken or the instruction is no branch
+    pub fn with_branch(self, branch: bool) -> Self {
+        Self { branch, ..self }
+    }
+
+    /// Set the [`Context`]
+    pub fn with_ctx(self, ctx: Context) -> Self {
+        Self { ctx, ..self }
+    }
+
+    /// Set whether the address points to the trap handler
+    pub fn with_thaddr(self, thaddr: bool) -> Self {
+        Self { thaddr, ..self }
+    }
+
+    /// Retrieve whether the branch was not taken or the instruction is no branch
+    pub fn branch(&self) -> bool {
+        self.branch
+    }
+
+    /// Retrieve the [`Context`]
+    pub fn ctx(&self) -> Context {
+        self.ctx
+    }
+
+    /// Retrieve whether the address points to the trap handler
+    pub fn thaddr(&self) -> bool {
+        self.thaddr
+    }
+
+    /// Retrieve the full address of the instruction
+    pub fn address(&self) -> u64 {
+        self.address
+    }
+
+    /// Retrieve the [`trap::Info`]
+    pub fn info(&self) -> trap::Info {
+        self.info
+    }
+}
+
+impl<U: Unit> Decode<'_, U> for Trap {
     fn decode(decoder: &mut Decoder<U>) -> Result<Self, Error> {
-        let branch = decoder.read_bit()?;
+        let branch = decoder.read_bit("branch")?;
         let ctx = Context::decode(decoder)?;
-        let ecause = decoder.read_bits(decoder.widths().ecause.get())?;
-        let interrupt = decoder.read_bit()?;
-        let thaddr = decoder.read_bit()?;
+        let ecause = decoder.read_bits("ecause", decoder.widths().ecause.get())?;
+        let interrupt = decoder.read_bit("interrupt")?;
+        let thaddr = decoder.read_bit("thaddr")?;
         let address = util::read_address(decoder)?;
         let tval = if interrupt {
             None
         } else {
-            Some(decoder.read_bits(decoder.widths().iaddress.get())?)
+            Some(decoder.read_bits("tval", decoder.widths().iaddress.get())?)
         };
         Ok(Trap {
             branch,
@@ -229,7 +327,7 @@ impl<U> Decode<'_, U> for Trap {
     }
 }
 
-impl<U> Encode<'_, U> for Trap {
+impl<U: Unit> Encode<'_, U> for Trap {
     fn encode(&self, encoder: &mut Encoder<U>) -> Result<(), Error> {
         encoder.write_bit(self.branch)?;
         encoder.encode(&self.ctx)?;
@@ -265,11 +363,47 @@ impl fmt::Display for Trap {
 /// Represents a format 3, subformat 2 packet. It informs about a changed
 /// context. It is also used as part of other payloads.
 #[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
 pub struct Context {
     /// The privilege level of the reported instruction.
-    pub privilege: Privilege,
-    pub time: Option<u64>,
-    pub context: u64,
+    pub(crate) privilege: Privilege,
+    pub(crate) time: Option<u64>,
+    pub(crate) context: u64,
+}
+
+impl Context {
+    /// Set the privilege level of the reported instruction
+    pub fn with_privilege(self, privilege: Privilege) -> Self {
+        Self { privilege, ..self }
+    }
+
+    /// Set the time
+    pub fn with_time(self, time: u64) -> Self {
+        Self {
+            time: Some(time),
+            ..self
+        }
+    }
+
+    /// Set the context
+    pub fn with_context(self, context: u64) -> Self {
+        Self { context, ..self }
+    }
+
+    /// Retrieve the privilege level of the reported instruction
+    pub fn privilege(&self) -> Privilege {
+        self.privilege
+    }
+
+    /// Retrieve the time, if reported
+    pub fn time(&self) -> Option<u64> {
+        self.time
+    }
+
+    /// Retrieve the context
+    pub fn context(&self) -> u64 {
+        self.context
+    }
 }
 
 impl From<&Context> for types::Context {
@@ -287,19 +421,20 @@ impl From<Context> for types::Context {
     }
 }
 
-impl<U> Decode<'_, U> for Context {
+impl<U: Unit> Decode<'_, U> for Context {
     fn decode(decoder: &mut Decoder<U>) -> Result<Self, Error> {
-        let privilege = decoder
-            .read_bits::<u8>(decoder.widths().privilege.get())?
-            .try_into()
-            .map_err(Error::UnknownPrivilege)?;
+        let privilege = decoder.read_bits::<u8>("privilege", decoder.widths().privilege.get())?;
+        let privilege = match privilege.try_into() {
+            Ok(privilege) => privilege,
+            Err(privilege) => U::decode_privilege(privilege, decoder)?,
+        };
         let time = decoder
             .widths()
             .time
-            .map(|w| decoder.read_bits(w.get()))
+            .map(|w| decoder.read_bits("time", w.get()))
             .transpose()?;
         let context_width = decoder.widths().context.map(Into::into).unwrap_or_default();
-        let context = decoder.read_bits(context_width)?;
+        let context = decoder.read_bits("context", context_width)?;
         Ok(Context {
             privilege,
             time,
@@ -308,9 +443,9 @@ impl<U> Decode<'_, U> for Context {
     }
 }
 
-impl<U> Encode<'_, U> for Context {
+impl<U: Unit> Encode<'_, U> for Context {
     fn encode(&self, encoder: &mut Encoder<U>) -> Result<(), Error> {
-        encoder.write_bits(u8::from(self.privilege), encoder.widths().privilege.get())?;
+        U::encode_privilege(self.privilege, encoder)?;
         if let Some(width) = encoder.widths().time {
             encoder.write_bits(self.time.unwrap_or_default(), width.get())?;
         }
@@ -335,27 +470,106 @@ impl fmt::Display for Context {
 ///
 /// Represents a format 3, subformat 3 packet.
 #[derive(Copy, Clone, Default, Debug, Eq, PartialEq)]
+#[non_exhaustive]
 pub struct Support<I = unit::ReferenceIOptions, D = unit::ReferenceDOptions> {
-    pub ienable: bool,
-    pub encoder_mode: EncoderMode,
-    pub qual_status: QualStatus,
-    pub ioptions: I,
-    pub denable: bool,
-    pub dloss: bool,
-    pub doptions: D,
+    pub(crate) ienable: bool,
+    pub(crate) encoder_mode: EncoderMode,
+    pub(crate) qual_status: QualStatus,
+    pub(crate) ioptions: I,
+    pub(crate) denable: bool,
+    pub(crate) dloss: bool,
+    pub(crate) doptions: D,
+}
+
+impl<I, D> Support<I, D> {
+    /// Set whether the instruction trace encoder is enabled
+    pub fn with_ienable(self, ienable: bool) -> Self {
+        Self { ienable, ..self }
+    }
+
+    /// Set the [`EncoderMode`]
+    pub fn with_encoder_mode(self, encoder_mode: EncoderMode) -> Self {
+        Self {
+            encoder_mode,
+            ..self
+        }
+    }
+
+    /// Set the [`QualStatus`]
+    pub fn with_qual_status(self, qual_status: QualStatus) -> Self {
+        Self {
+            qual_status,
+            ..self
+        }
+    }
+
+    /// Set the instruction trace options
+    pub fn with_ioptions(self, ioptions: I) -> Self {
+        Self { ioptions, ..self }
+    }
+
+    /// Set whether the data trace encoder is enabled
+    pub fn with_denable(self, denable: bool) -> Self {
+        Self { denable, ..self }
+    }
+
+    /// Set whether data trace packets were lost
+    pub fn with_dloss(self, dloss: bool) -> Self {
+        Self { dloss, ..self }
+    }
+
+    /// Set the data trace options
+    pub fn with_doptions(self, doptions: D) -> Self {
+        Self { doptions, ..self }
+    }
+
+    /// Retrieve whether the instruction trace encoder is enabled
+    pub fn ienable(&self) -> bool {
+        self.ienable
+    }
+
+    /// Retrieve the [`EncoderMode`]
+    pub fn encoder_mode(&self) -> EncoderMode {
+        self.encoder_mode
+    }
+
+    /// Retrieve the [`QualStatus`]
+    pub fn qual_status(&self) -> QualStatus {
+        self.qual_status
+    }
+
+    /// Retrieve the instruction trace options
+    pub fn ioptions(&self) -> &I {
+        &self.ioptions
+    }
+
+    /// Retrieve whether the data trace encoder is enabled
+    pub fn denable(&self) -> bool {
+        self.denable
+    }
+
+    /// Retrieve whether data trace packets were lost
+    pub fn dloss(&self) -> bool {
+        self.dloss
+    }
+
+    /// Retrieve the data trace options
+    pub fn doptions(&self) -> &D {
+        &self.doptions
+    }
 }
 
 impl<U: Unit> Decode<'_, U> for Support<U::IOptions, U::DOptions> {
     fn decode(decoder: &mut Decoder<U>) -> Result<Self, Error> {
-        let ienable = decoder.read_bit()?;
+        let ienable = decoder.read_bit("ienable")?;
         let encoder_mode = decoder
-            .read_bits::<u8>(decoder.unit().encoder_mode_width())?
+            .read_bits::<u8>("encoder_mode", decoder.unit().encoder_mode_width())?
             .try_into()
-            .map_err(Error::UnknownEncoderMode)?;
+            .or_else(|m| U::decode_encoder_mode(m, decoder))?;
         let qual_status = QualStatus::decode(decoder)?;
         let ioptions = U::decode_ioptions(decoder)?;
-        let denable = decoder.read_bit()?;
-        let dloss = decoder.read_bit()?;
+        let denable = decoder.read_bit("denable")?;
+        let dloss = decoder.read_bit("dloss")?;
         let doptions = U::decode_doptions(decoder)?;
         Ok(Support {
             ienable,
@@ -384,11 +598,8 @@ where
         encoder.encode(&self.qual_status)?;
         encoder.encode(&self.ioptions)?;
         encoder.write_bit(self.denable)?;
-        if self.denable {
-            encoder.write_bit(self.dloss)?;
-            encoder.encode(&self.doptions)?;
-        }
-        Ok(())
+        encoder.write_bit(self.dloss)?;
+        encoder.encode(&self.doptions)
     }
 }
 
@@ -425,6 +636,7 @@ impl<I: unit::IOptions, D> fmt::Display for Support<I, D> {
 
 /// Representation of a change to the filter qualification
 #[derive(Copy, Clone, Default, Debug, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum QualStatus {
     /// No change to filter qualification.
     #[default]
@@ -441,7 +653,7 @@ pub enum QualStatus {
 
 impl<U> Decode<'_, U> for QualStatus {
     fn decode(decoder: &mut Decoder<U>) -> Result<Self, Error> {
-        Ok(match decoder.read_bits::<u8>(2)? {
+        Ok(match decoder.read_bits::<u8>("qual_status", 2)? {
             0b00 => QualStatus::NoChange,
             0b01 => QualStatus::EndedRep,
             0b10 => QualStatus::TraceLost,
@@ -476,9 +688,14 @@ impl fmt::Display for QualStatus {
 
 /// Mode the encoder is operating in
 #[derive(Copy, Clone, Default, Debug, Eq, PartialEq)]
+#[non_exhaustive]
 pub enum EncoderMode {
     #[default]
     BranchTrace,
+    /// Mode not defined by the specification, e.g. a data trace mode
+    ///
+    /// See [`Unit::decode_encoder_mode`] for details.
+    Custom(u8),
 }
 
 impl TryFrom<u8> for EncoderMode {
@@ -496,6 +713,7 @@ impl From<EncoderMode> for u8 {
     fn from(mode: EncoderMode) -> Self {
         match mode {
             EncoderMode::BranchTrace => 0,
+            EncoderMode::Custom(mode) => mode,
         }
     }
 }
@@ -504,6 +722,7 @@ impl fmt::Display for EncoderMode {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             Self::BranchTrace => write!(f, "branch trace"),
+            Self::Custom(mode) => write!(f, "custom mode {mode}"),
         }
     }
 }
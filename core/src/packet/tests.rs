@@ -0,0 +1,1046 @@
+// Copyright (C) 2025, 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+
+extern crate alloc;
+
+#[macro_use]
+mod test_gen;
+
+mod basic;
+mod encap_tests;
+mod parts;
+
+use super::*;
+
+use core::num::{NonZeroU8, NonZeroUsize};
+
+use crate::types::{self, branch};
+
+use decoder::Decode;
+use payload::{AddressInfo, InstructionTrace};
+
+// `payload` related tests
+bitstream_test!(
+    extension_jti_1,
+    b"\x00\x7f\x05",
+    ext::JumpTargetIndex {
+        index: 768,
+        branch_map: branch::Map::new(31, 10),
+        irdepth: None
+    },
+    params { cache_size_p: 10 }
+);
+bitstream_test!(
+    extension_jti_2,
+    b"\xff\x03",
+    ext::JumpTargetIndex {
+        index: 1023,
+        branch_map: Default::default(),
+        irdepth: None
+    },
+    params { cache_size_p: 10 }
+);
+bitstream_test!(
+    branches,
+    b"\x47\x0b",
+    payload::Branch {
+        branch_map: branch::Map::new(7, 0b101_1010),
+        address: Some(AddressInfo {
+            address: 0,
+            notify: false,
+            updiscon: false,
+            irdepth: None
+        }),
+    },
+    params { cache_size_p: 10 }
+);
+bitstream_test!(
+    branch_with_zero_branches,
+    b"\x00\x04",
+    payload::Branch {
+        branch_map: branch::Map::new(31, 32),
+        address: None
+    }
+);
+bitstream_test!(
+    address_absolute,
+    b"\x01\x00\x00\x00\x00\x00\x00\xc0",
+    payload::AddressInfo {
+        address: 4,
+        notify: true,
+        updiscon: false,
+        irdepth: None
+    },
+    params {
+        iaddress_width_p: 64.try_into().unwrap(),
+        iaddress_lsb_p: 2.try_into().unwrap()
+    }
+);
+bitstream_test!(
+    address_differential,
+    b"\x01\x00\x00\x00\x00\x00\x00\x80",
+    payload::AddressInfo {
+        address: 4,
+        notify: false,
+        updiscon: true,
+        irdepth: None
+    },
+    params {
+        iaddress_width_p: 64.try_into().unwrap(),
+        iaddress_lsb_p: 2.try_into().unwrap()
+    }
+);
+bitstream_test!(
+    address_raw_flags_1,
+    b"\x01\x00\x00\x00\x00\x00\x00\xc0",
+    payload::AddressInfo {
+        address: 4,
+        notify: true,
+        updiscon: true,
+        irdepth: None
+    },
+    params {
+        iaddress_width_p: 64.try_into().unwrap(),
+        iaddress_lsb_p: 2.try_into().unwrap()
+    },
+    unit(RawFlags)
+);
+bitstream_test!(
+    address_raw_flags_2,
+    b"\x01\x00\x00\x00\x00\x00\x00\x40",
+    payload::AddressInfo {
+        address: 4,
+        notify: true,
+        updiscon: false,
+        irdepth: None
+    },
+    params {
+        iaddress_width_p: 64.try_into().unwrap(),
+        iaddress_lsb_p: 2.try_into().unwrap()
+    },
+    unit(RawFlags)
+);
+bitstream_test!(
+    address_msb_flags,
+    b"\xff\xff\xff\xff\xff\xff\xff\x3f",
+    payload::AddressInfo {
+        address: -4,
+        notify: true,
+        updiscon: true,
+        irdepth: None
+    },
+    params {
+        iaddress_width_p: 64.try_into().unwrap(),
+        iaddress_lsb_p: 2.try_into().unwrap()
+    },
+    unit(MsbFlags)
+);
+bitstream_test!(
+    address_previous_flags,
+    b"\xff\xff\xff\xff\xff\xff\xff\x3f",
+    payload::AddressInfo {
+        address: -4,
+        notify: true,
+        updiscon: false,
+        irdepth: None
+    },
+    params {
+        iaddress_width_p: 64.try_into().unwrap(),
+        iaddress_lsb_p: 2.try_into().unwrap()
+    }
+);
+bitstream_test!(
+    synchronization_start,
+    b"\xff",
+    sync::Start {
+        branch: true,
+        ctx: sync::Context {
+            privilege: types::Privilege::Machine,
+            time: None,
+            context: 0
+        },
+        address: 0xffff_ffff_ffff_fffe,
+    },
+    params {
+        iaddress_width_p: 64.try_into().unwrap(),
+        iaddress_lsb_p: 1.try_into().unwrap()
+    }
+);
+
+/*
+Decoded packet: Packet { trace_type: 2, time_tag: None, hart: 0, payload: [115, 0, 0, 0, 0, 25, 65, 0, 8], .. }
+Payload: InstructionTrace(Synchronization(Start(Start { branch: true, ctx: Context { privilege: Machine, time: None, context: Some(0) }, address: 536937572 })))
+*/
+bitstream_test!(
+    decode_instruction_trace_sync_payload,
+    b"\x73\x00\x00\x00\x00\x19\x41\x00\x08",
+    InstructionTrace::Synchronization(sync::Synchronization::Start(sync::Start {
+        branch: true,
+        ctx: sync::Context {
+            privilege: types::Privilege::Machine,
+            time: None,
+            context: 0
+        },
+        address: 536937572
+    })),
+    params(&PARAMS_32)
+);
+
+/*
+Decoded packet: Packet { trace_type: 2, time_tag: None, hart: 0, payload: [1, 128, 0], .. }
+Payload: InstructionTrace(Branch(Branch { branch_map: Map { count: 31, map: 256 }, address: None }))
+*/
+bitstream_test!(
+    decode_instruction_trace_branch_payload,
+    b"\x01\x80\x00",
+    InstructionTrace::Branch(payload::Branch {
+        branch_map: branch::Map::new(31, 256),
+        address: None
+    }),
+    params(&PARAMS_32)
+);
+/*
+Decoded packet: Packet { trace_type: 2, time_tag: None, hart: 0, payload: [250, 251], .. }
+Payload: InstructionTrace(Address(AddressInfo { address: 18446744073709551100, notify: false, updiscon: false, irdepth: None }))
+*/
+bitstream_test!(
+    decode_instruction_trace_address_payload,
+    b"\xFA\xFB",
+    InstructionTrace::Address(AddressInfo {
+        address: -0x204,
+        notify: false,
+        updiscon: false,
+        irdepth: None
+    }),
+    params(&PARAMS_64)
+);
+
+// Ok(Synchronization(Support(Support { ienable: true, encoder_mode: BranchTrace, qual_status: TraceLost, ioptions: ReferenceIOptions { implicit_return: false, implicit_exception: false, full_address: false, jump_target_cache: false, branch_prediction: false }, denable: false, dloss: false, doptions: ReferenceDOptions { no_address: false, no_data: false, full_address: false, full_data: false } })))
+bitstream_test!(
+    decode_qualstat_trace_lost,
+    b"\x9F\x00",
+    InstructionTrace::Synchronization(sync::Synchronization::Support(sync::Support {
+        ienable: true,
+        encoder_mode: sync::EncoderMode::BranchTrace,
+        qual_status: sync::QualStatus::TraceLost,
+        ioptions: unit::ReferenceIOptions {
+            implicit_return: false,
+            implicit_exception: false,
+            full_address: false,
+            jump_target_cache: false,
+            branch_prediction: false
+        },
+        denable: false,
+        dloss: false,
+        doptions: unit::ReferenceDOptions {
+            no_address: false,
+            no_data: false,
+            full_address: false,
+            full_data: false
+        }
+    })),
+    params(&PARAMS_32)
+);
+
+bitstream_test!(
+    decode_qualstat_ended_ntr,
+    b"\xDF\x00",
+    InstructionTrace::Synchronization(sync::Synchronization::Support(sync::Support {
+        ienable: true,
+        encoder_mode: sync::EncoderMode::BranchTrace,
+        qual_status: sync::QualStatus::EndedNtr,
+        ioptions: unit::ReferenceIOptions {
+            implicit_return: false,
+            implicit_exception: false,
+            full_address: false,
+            jump_target_cache: false,
+            branch_prediction: false
+        },
+        denable: false,
+        dloss: false,
+        doptions: unit::ReferenceDOptions {
+            no_address: false,
+            no_data: false,
+            full_address: false,
+            full_data: false
+        }
+    })),
+    params(&PARAMS_32)
+);
+
+bitstream_test!(
+    interrupt_ecause7,
+    b"\x77\x00\x00\x00\x80\x33\x36\x00\x00\x10",
+    InstructionTrace::Synchronization(
+        sync::Trap {
+            branch: true,
+            ctx: sync::Context {
+                privilege: types::Privilege::Machine,
+                time: None,
+                context: 0,
+            },
+            thaddr: true,
+            address: 0x800001b0,
+            info: types::trap::Info {
+                ecause: 7,
+                tval: None
+            },
+        }
+        .into()
+    ),
+    params(&PARAMS_64)
+);
+
+bitstream_test!(
+    exception_ecause2,
+    b"\x77\x00\x00\x00\x00\x41\x44\x00\x00\x10",
+    InstructionTrace::Synchronization(
+        sync::Trap {
+            branch: true,
+            ctx: sync::Context {
+                privilege: types::Privilege::Machine,
+                time: None,
+                context: 0,
+            },
+            thaddr: false,
+            address: 0x80000222,
+            info: types::trap::Info {
+                ecause: 2,
+                tval: Some(0)
+            }
+        }
+        .into()
+    ),
+    params(&PARAMS_64)
+);
+
+const PARAMS_32: config::Parameters = config::Parameters {
+    cache_size_p: 0,
+    call_counter_size_p: 0,
+    context_width_p: NonZeroU8::new(32).unwrap(),
+    time_width_p: NonZeroU8::new(1).unwrap(),
+    ecause_width_p: NonZeroU8::new(5).unwrap(),
+    f0s_width_p: 0,
+    iaddress_lsb_p: 1,
+    iaddress_width_p: NonZeroU8::new(32).unwrap(),
+    nocontext_p: false,
+    notime_p: true,
+    privilege_width_p: NonZeroU8::new(2).unwrap(),
+    return_stack_size_p: 0,
+    sijump_p: false,
+};
+
+const PARAMS_64: config::Parameters = config::Parameters {
+    cache_size_p: 0,
+    call_counter_size_p: 0,
+    context_width_p: NonZeroU8::new(32).unwrap(),
+    time_width_p: NonZeroU8::new(1).unwrap(),
+    ecause_width_p: NonZeroU8::new(5).unwrap(),
+    f0s_width_p: 0,
+    iaddress_lsb_p: 1,
+    iaddress_width_p: NonZeroU8::new(64).unwrap(),
+    nocontext_p: false,
+    notime_p: true,
+    privilege_width_p: NonZeroU8::new(2).unwrap(),
+    return_stack_size_p: 0,
+    sijump_p: false,
+};
+
+/// [`unit::Unit`] encoding address flags as raw bits
+#[derive(Copy, Clone, Debug, Default)]
+struct RawFlags;
+
+impl<U> unit::Unit<U> for RawFlags {
+    type IOptions = unit::ReferenceIOptions;
+    type DOptions = unit::ReferenceDOptions;
+
+    fn encoder_mode_width(&self) -> u8 {
+        1
+    }
+
+    fn differential_address_flags(&self) -> bool {
+        false
+    }
+
+    fn decode_ioptions(decoder: &mut decoder::Decoder<U>) -> Result<Self::IOptions, Error> {
+        Decode::decode(decoder)
+    }
+
+    fn decode_doptions(decoder: &mut decoder::Decoder<U>) -> Result<Self::DOptions, Error> {
+        Decode::decode(decoder)
+    }
+}
+
+/// [`unit::Unit`] encoding address flags relative to the address' MSB
+#[derive(Copy, Clone, Debug, Default)]
+struct MsbFlags;
+
+impl<U> unit::Unit<U> for MsbFlags {
+    type IOptions = unit::ReferenceIOptions;
+    type DOptions = unit::ReferenceDOptions;
+
+    fn encoder_mode_width(&self) -> u8 {
+        1
+    }
+
+    fn address_flag_reference(&self) -> unit::FlagReference {
+        unit::FlagReference::AddressMsb
+    }
+
+    fn decode_ioptions(decoder: &mut decoder::Decoder<U>) -> Result<Self::IOptions, Error> {
+        Decode::decode(decoder)
+    }
+
+    fn decode_doptions(decoder: &mut decoder::Decoder<U>) -> Result<Self::DOptions, Error> {
+        Decode::decode(decoder)
+    }
+}
+
+/// [`unit::Unit`] defining a format 0 subformat `2` carrying a byte of data
+#[derive(Copy, Clone, Debug, Default)]
+struct VendorExt;
+
+impl<U> unit::Unit<U> for VendorExt {
+    type IOptions = unit::ReferenceIOptions;
+    type DOptions = unit::ReferenceDOptions;
+
+    fn encoder_mode_width(&self) -> u8 {
+        1
+    }
+
+    fn decode_ioptions(decoder: &mut decoder::Decoder<U>) -> Result<Self::IOptions, Error> {
+        Decode::decode(decoder)
+    }
+
+    fn decode_doptions(decoder: &mut decoder::Decoder<U>) -> Result<Self::DOptions, Error> {
+        Decode::decode(decoder)
+    }
+
+    fn decode_extension(
+        subformat: u8,
+        decoder: &mut decoder::Decoder<U>,
+    ) -> Result<ext::Vendor, Error> {
+        if subformat != 2 {
+            return Err(Error::UnknownFmt(0, Some(subformat)));
+        }
+        let data = decoder.read_uint("data", 8)?;
+        Ok(ext::Vendor {
+            subformat,
+            data,
+            ..Default::default()
+        })
+    }
+
+    fn encode_extension(
+        extension: &ext::Vendor,
+        encoder: &mut encoder::Encoder<U>,
+    ) -> Result<(), Error> {
+        encoder.write_uint(extension.data, 8)
+    }
+}
+
+#[test]
+fn vendor_extension() {
+    let params = config::Parameters {
+        f0s_width_p: 2,
+        ..Default::default()
+    };
+    let expected: InstructionTrace = ext::Extension::Vendor(ext::Vendor {
+        subformat: 2,
+        data: 0xab,
+        ..Default::default()
+    })
+    .into();
+
+    let data = b"\xb8\x0a";
+    let builder = builder().with_params(&params).for_unit(VendorExt);
+    let payload = builder.decoder(data).decode_payload().unwrap();
+    assert_eq!(payload, expected);
+
+    let mut buffer = [0u8; 2];
+    let mut encoder = builder.encoder(&mut buffer);
+    encoder.encode(&expected).unwrap();
+    assert_eq!(buffer[0], data[0]);
+    let payload = builder.decoder(&buffer).decode_payload().unwrap();
+    assert_eq!(payload, expected);
+
+    let res = builder.decoder(b"\x0c\x00").decode_payload();
+    assert_eq!(res, Err(Error::UnknownFmt(0, Some(3))));
+}
+
+/// [`unit::Unit`] using the reserved privilege encoding `2` for debug mode
+#[derive(Copy, Clone, Debug, Default)]
+struct DebugPrivilege;
+
+impl<U> unit::Unit<U> for DebugPrivilege {
+    type IOptions = unit::ReferenceIOptions;
+    type DOptions = unit::ReferenceDOptions;
+
+    fn encoder_mode_width(&self) -> u8 {
+        1
+    }
+
+    fn decode_ioptions(decoder: &mut decoder::Decoder<U>) -> Result<Self::IOptions, Error> {
+        Decode::decode(decoder)
+    }
+
+    fn decode_doptions(decoder: &mut decoder::Decoder<U>) -> Result<Self::DOptions, Error> {
+        Decode::decode(decoder)
+    }
+
+    fn decode_privilege(
+        privilege: u8,
+        _decoder: &mut decoder::Decoder<U>,
+    ) -> Result<types::Privilege, Error> {
+        match privilege {
+            2 => Ok(types::Privilege::Custom(2)),
+            p => Err(Error::UnknownPrivilege(p)),
+        }
+    }
+}
+
+#[test]
+fn custom_privilege() {
+    let expected: InstructionTrace = sync::Start {
+        branch: true,
+        ctx: sync::Context {
+            privilege: types::Privilege::Custom(2),
+            ..Default::default()
+        },
+        address: 0x80000000,
+    }
+    .into();
+
+    let builder = builder().for_unit(DebugPrivilege);
+    let mut buffer = [0u8; 8];
+    let mut encoder = builder.encoder(&mut buffer);
+    encoder.encode(&expected).unwrap();
+    let payload = builder.decoder(&buffer).decode_payload().unwrap();
+    assert_eq!(payload, expected);
+
+    let res = super::builder().decoder(&buffer).decode_payload();
+    assert_eq!(res, Err(Error::UnknownPrivilege(2)));
+}
+
+/// [`unit::Unit`] signalling short addresses via an in-band flag
+#[derive(Copy, Clone, Debug, Default)]
+struct ShortAddress;
+
+impl<U> unit::Unit<U> for ShortAddress {
+    type IOptions = unit::ReferenceIOptions;
+    type DOptions = unit::ReferenceDOptions;
+
+    fn encoder_mode_width(&self) -> u8 {
+        1
+    }
+
+    fn decode_ioptions(decoder: &mut decoder::Decoder<U>) -> Result<Self::IOptions, Error> {
+        Decode::decode(decoder)
+    }
+
+    fn decode_doptions(decoder: &mut decoder::Decoder<U>) -> Result<Self::DOptions, Error> {
+        Decode::decode(decoder)
+    }
+
+    fn adjust_widths(
+        format: u8,
+        widths: &mut width::Widths,
+        decoder: &mut decoder::Decoder<U>,
+    ) -> Result<(), Error> {
+        if format == 0b10 && decoder.read_uint("short", 1)? != 0 {
+            widths.iaddress = NonZeroU8::new(16).unwrap();
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn adjusted_widths() {
+    let expected: InstructionTrace = AddressInfo {
+        address: 0x1234,
+        notify: false,
+        updiscon: false,
+        irdepth: None,
+    }
+    .into();
+
+    let mut decoder = builder().for_unit(ShortAddress).decoder(b"\xd6\x48\x00");
+    assert_eq!(decoder.decode_payload(), Ok(expected));
+    assert_eq!(decoder.widths(), width::Widths::default());
+
+    let payload = builder().decoder(b"\xd6\x48\x00").decode_payload();
+    assert_ne!(payload, Ok(expected));
+}
+
+#[test]
+fn annotate_fields() {
+    use annotate::{Field, Recorder};
+    use core::cell::RefCell;
+
+    struct Fields(RefCell<[Option<Field>; 16]>);
+
+    impl Recorder for Fields {
+        fn record(&self, field: Field) {
+            let mut fields = self.0.borrow_mut();
+            if let Some(slot) = fields.iter_mut().find(|f| f.is_none()) {
+                *slot = Some(field);
+            }
+        }
+    }
+
+    let fields = Fields(Default::default());
+    let data = b"\x45\x73\x0a\x00\x00\x20\x41\x01";
+    let packet = builder()
+        .decoder(data)
+        .with_recorder(&fields)
+        .decode_encap_packet()
+        .unwrap();
+    packet.into_normal().unwrap().decode_payload().unwrap();
+
+    let fields = fields.0.take();
+    let names = ["length", "flow", "extend", "format", "subformat", "branch"];
+    let names = names.into_iter().chain(["privilege", "address"]);
+    assert!(fields.iter().flatten().map(|f| f.name).eq(names));
+    let mut fields = fields.into_iter().flatten();
+    let first = fields.next().unwrap();
+    let last = fields.try_fold(first, |prev, f| (prev.end() == f.start).then_some(f));
+    assert_eq!(last.map(|f| (f.end(), f.value)), Some((46, 0x40000014)));
+}
+
+#[test]
+fn profiled_unit() {
+    struct Narrow;
+
+    impl unit::Profile for Narrow {
+        const PARAMETERS: config::Parameters = config::Parameters {
+            iaddress_width_p: NonZeroU8::new(16).unwrap(),
+            ..config::PARAMETERS
+        };
+    }
+
+    let data = b"\x45\x73\x0a\x00\x00\x20\x41\x01";
+    let expected = builder()
+        .with_params(&<Narrow as unit::Profile>::PARAMETERS)
+        .decoder(data)
+        .decode_encap_packet()
+        .unwrap()
+        .into_normal()
+        .unwrap()
+        .decode_payload()
+        .unwrap();
+
+    let unit: unit::Profiled<unit::Reference, Narrow> = Default::default();
+    let payload = builder()
+        .for_unit(unit)
+        .decoder(data)
+        .decode_encap_packet()
+        .unwrap()
+        .into_normal()
+        .unwrap()
+        .decode_payload()
+        .unwrap();
+    assert_eq!(payload, expected);
+
+    // Fixed parameters survive type erasure
+    #[cfg(feature = "alloc")]
+    {
+        let plug = unit::Plug::new(&unit);
+        let widths = builder().for_unit(plug).decoder(data).widths();
+        assert_eq!(widths.iaddress.get(), 16);
+    }
+}
+
+#[test]
+fn decode_from_source() {
+    let data = b"\x45\x73\x0a\x00\x00\x20\x41\x01\x45\x73\x0a\x00\x00\x20";
+    for split in 0..=data.len() {
+        let (head, tail) = data.split_at(split);
+        let segments: &[&[u8]] = &[head, &[], tail];
+        let mut decoder = builder().decoder_for_source(&segments);
+        let mut expected = builder().decoder(data);
+        while expected.bytes_left() > 0 {
+            let payload = decoder
+                .decode_encap_packet()
+                .map(|p| p.into_normal().unwrap().decode_payload().unwrap());
+            let expected_payload = expected
+                .decode_encap_packet()
+                .map(|p| p.into_normal().unwrap().decode_payload().unwrap());
+            assert_eq!(payload, expected_payload);
+            assert_eq!(decoder.bytes_left(), expected.bytes_left());
+        }
+        assert_eq!(decoder.bytes_left(), 0);
+        assert!(matches!(
+            decoder.decode_encap_packet(),
+            Err(Error::InsufficientData(_))
+        ));
+    }
+}
+
+#[test]
+fn roundtrip_random() {
+    let mut rng = roundtrip::Rng::new(0x9e3779b97f4a7c15);
+    let checked = roundtrip::check_random(builder(), &mut rng, 4096).unwrap();
+    assert!(checked > 1024, "only {checked} payloads checked");
+    let checked = roundtrip::check_random(builder().for_unit(unit::PULP), &mut rng, 4096).unwrap();
+    assert!(checked > 1024, "only {checked} payloads checked");
+}
+
+#[test]
+fn detect_address_width() {
+    let params = config::Parameters {
+        iaddress_width_p: NonZeroU8::new(48).unwrap(),
+        ..Default::default()
+    };
+    let builder = builder().with_params(&params);
+
+    let mut buffer = [0u8; 128];
+    let mut encoder = builder.encoder(&mut buffer);
+    for n in 0..4 {
+        let payload = sync::Trap {
+            branch: true,
+            ctx: Default::default(),
+            thaddr: false,
+            address: 0x7f12_3456_7000 + n * 0x10,
+            info: types::trap::Info {
+                ecause: 2,
+                tval: Some(0x7f00_0000_1234),
+            },
+        };
+        let payload = payload::Payload::InstructionTrace(payload.into());
+        let packet = encap::Packet::from(encap::Normal::new(0, 0, payload));
+        encoder.encode(&packet).unwrap();
+    }
+    let len = 128 - encoder.uncommitted();
+
+    let guess = detect::Detector::default()
+        .detect(builder, &buffer[..len])
+        .unwrap();
+    assert_eq!(guess.params.iaddress_width_p, params.iaddress_width_p);
+    assert_eq!(guess.params.iaddress_lsb_p, params.iaddress_lsb_p);
+    assert_eq!(guess.packets, 4);
+    assert_eq!(guess.consistent, 4);
+}
+
+#[test]
+fn restricted_decoding() {
+    let data = b"\x12\x84\x00\x7f";
+    let mut decoder = builder().decoder(data);
+    let mut outer = decoder.split_off_to(3).unwrap();
+    assert_eq!(outer.read_bits::<u8>("value", 8), Ok(0x12));
+    let mut inner = outer.split_off_to(1).unwrap();
+    assert_eq!(inner.read_bits::<u32>("value", 24), Ok(0xffff84));
+    assert_eq!(inner.read_bits::<u8>("value", 8), Ok(0xff));
+    assert_eq!(outer.bytes_left(), 1);
+    assert_eq!(outer.read_bits::<u16>("value", 16), Ok(0x0000));
+    assert_eq!(decoder.read_bits::<u16>("value", 16), Ok(0x007f));
+
+    struct Faulty;
+    impl source::ByteSource for Faulty {
+        fn len(&self) -> usize {
+            4
+        }
+
+        fn get(&self, index: usize) -> Option<u8> {
+            (index != 2).then_some(0x80)
+        }
+    }
+    let mut decoder = builder().decoder_for_source(&Faulty);
+    let mut payload = decoder.split_off_to(3).unwrap();
+    assert_eq!(payload.read_bits::<u16>("value", 16), Ok(0x8080));
+    assert_eq!(
+        payload.read_bits::<u8>("value", 8),
+        Err(Error::InsufficientData(NonZeroUsize::MIN))
+    );
+}
+
+#[test]
+fn decoder_alignment() {
+    use decoder::Alignment;
+
+    let mut decoder = builder().decoder(b"\x0f\x01\x02");
+    assert_eq!(decoder.read_uint("value", 4), Ok(0xf));
+    assert_eq!(decoder.bit_pos(), 4);
+    assert!(!decoder.is_aligned());
+    decoder.advance_to_byte();
+    assert_eq!(decoder.bit_pos(), 8);
+    decoder.advance_to_byte();
+    assert_eq!(decoder.bit_pos(), 8);
+    assert_eq!(
+        decoder.align_to(4),
+        Err(Error::InsufficientData(NonZeroUsize::MIN))
+    );
+    assert_eq!(decoder.bit_pos(), 8);
+    decoder.align_to(3).unwrap();
+    assert_eq!(decoder.bytes_left(), 0);
+}
+
+#[test]
+fn decode_counted() {
+    use decoder::Alignment;
+
+    let data = b"\x00\x20\x41\x01";
+    let mut decoder = builder().decoder(data);
+    let (payload, consumed) = decoder.decode_payload_counted().unwrap();
+    assert_eq!(Ok(payload), builder().decoder(data).decode_payload());
+    assert_eq!(consumed.bits, decoder.bit_pos());
+    assert_eq!(
+        consumed.bits + usize::from(consumed.padding),
+        8 * consumed.bytes()
+    );
+
+    let mut decoder = builder().decoder(b"\x0f\x01");
+    let (value, consumed) = decoder.decode_counted::<sync::QualStatus>().unwrap();
+    assert_eq!(value, sync::QualStatus::EndedNtr);
+    assert_eq!(
+        consumed,
+        decoder::Consumed {
+            bits: 2,
+            padding: 6
+        }
+    );
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn described_unit() {
+    use unit::IOptions;
+    use unit::described::{Described, IOption};
+
+    let support = sync::Support {
+        ienable: true,
+        encoder_mode: sync::EncoderMode::BranchTrace,
+        qual_status: sync::QualStatus::NoChange,
+        ioptions: unit::PULPIOptions {
+            delta_address: true,
+            full_address: false,
+            implicit_exception: true,
+            sijump: false,
+            implicit_return: true,
+            branch_prediction: false,
+            jump_target_cache: false,
+        },
+        denable: false,
+        dloss: false,
+        doptions: unit::NoOptions,
+    };
+    let expected: InstructionTrace<_, _> = sync::Synchronization::Support(support).into();
+    let mut buffer = [0u8; 3];
+    let pulp = builder().for_unit(unit::PULP);
+    pulp.encoder(&mut buffer).encode(&expected).unwrap();
+
+    let unit = Described {
+        ioptions: alloc::vec![
+            IOption::JumpTargetCache,
+            IOption::BranchPrediction,
+            IOption::ImplicitReturn,
+            IOption::Sijump,
+            IOption::ImplicitException,
+            IOption::FullAddress,
+            IOption::DeltaAddress,
+        ],
+        doptions_width: 0,
+        ..Default::default()
+    };
+    let described = unit.apply(builder());
+    let payload = described.clone().decoder(&buffer).decode_payload().unwrap();
+    let ioptions = &payload.as_support().unwrap().ioptions;
+    assert_eq!(ioptions.address_mode(), support.ioptions.address_mode());
+    assert_eq!(ioptions.implicit_return(), Some(true));
+    assert_eq!(ioptions.sequentially_inferred_jumps(), Some(false));
+    assert_eq!(ioptions.get(IOption::Reserved), None);
+
+    let mut reencoded = [0u8; 3];
+    described.encoder(&mut reencoded).encode(&payload).unwrap();
+    assert_eq!(reencoded, buffer);
+}
+
+#[test]
+fn wide_stack_depth() {
+    let payload = |irdepth| -> InstructionTrace {
+        AddressInfo {
+            address: 0x100,
+            notify: false,
+            updiscon: false,
+            irdepth: Some(irdepth),
+        }
+        .into()
+    };
+    for (return_stack_size_p, call_counter_size_p, irdepth) in
+        [(16, 0, 1 << 16), (255, 0, 1 << 16), (255, 255, usize::MAX)]
+    {
+        let params = config::Parameters {
+            return_stack_size_p,
+            call_counter_size_p,
+            ..Default::default()
+        };
+        let builder = builder().with_params(&params);
+        let mut buffer = [0u8; 128];
+        let mut encoder = builder.encoder(buffer.as_mut());
+        encoder
+            .encode(&payload(irdepth))
+            .expect("Could not encode payload");
+        let len = 128 - encoder.uncommitted();
+        assert_eq!(
+            builder.decoder(&buffer[..len]).decode_payload(),
+            Ok(payload(irdepth))
+        );
+    }
+
+    // Set a bit of `irdepth` beyond the lowest 64 bit
+    let params = config::Parameters {
+        return_stack_size_p: 100,
+        ..Default::default()
+    };
+    let builder = builder().with_params(&params);
+    let mut buffer = [0u8; 32];
+    builder
+        .encoder(buffer.as_mut())
+        .encode(&payload(0))
+        .expect("Could not encode payload");
+    buffer[14] |= 0x10;
+    let decoded = builder.decoder(&buffer).decode_payload();
+    assert_eq!(
+        decoded.map(|p| p.implicit_return_depth()),
+        Ok(Some(usize::MAX))
+    );
+}
+
+#[test]
+fn custom_encoder_mode() {
+    let payload: InstructionTrace = sync::Support {
+        encoder_mode: sync::EncoderMode::Custom(1),
+        ..Default::default()
+    }
+    .into();
+    let builder = builder();
+    let mut buffer = [0u8; 8];
+    let mut encoder = builder.encoder(buffer.as_mut());
+    encoder.encode(&payload).expect("Could not encode payload");
+    let len = 8 - encoder.uncommitted();
+
+    let data = &buffer[..len];
+    assert_eq!(builder.decoder(data).decode_payload(), Ok(payload));
+    assert_eq!(
+        builder.with_strict(true).decoder(data).decode_payload(),
+        Err(Error::UnknownEncoderMode(1))
+    );
+}
+
+#[test]
+fn presets() {
+    use preset::Preset;
+
+    let builder = builder()
+        .with_hart_index_width(3)
+        .with_preset(&Preset::PULP);
+    let decoder = builder.decoder(&[]);
+    assert_eq!(decoder.widths().ecause.get(), 5);
+    assert_eq!(decoder.unit(), &unit::PULP);
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn presets_by_name() {
+    use preset::{PRESETS, Preset};
+
+    assert!(PRESETS.iter().all(|p| Preset::by_name(p().name).is_some()));
+    let preset = Preset::by_name("reference-flow-64").expect("Missing preset");
+    let decoder = builder().with_preset(&preset).decoder(&[]);
+    assert_eq!(decoder.widths().iaddress.get(), 64);
+    assert!(Preset::by_name("unknown").is_none());
+}
+
+#[test]
+fn address_shift_override() {
+    let shifted = builder().with_address_shift(Some(2));
+    let builder = shifted.with_params(&PARAMS_32);
+    assert_eq!(builder.decoder(&[]).widths().iaddress_lsb, 2);
+    assert_eq!(
+        builder
+            .with_address_shift(None)
+            .decoder(&[])
+            .widths()
+            .iaddress_lsb,
+        1
+    );
+
+    let expected: InstructionTrace<_, _> = sync::Start {
+        branch: true,
+        ctx: Default::default(),
+        address: 0x1104,
+    }
+    .into();
+    let mut buffer = [0u8; 16];
+    builder.encoder(&mut buffer).encode(&expected).unwrap();
+    let payload = builder.decoder(&buffer).decode_payload().unwrap();
+    assert_eq!(payload, expected);
+
+    let payload = super::builder()
+        .with_params(&PARAMS_32)
+        .decoder(&buffer)
+        .decode_payload()
+        .unwrap();
+    let InstructionTrace::Synchronization(sync::Synchronization::Start(start)) = payload else {
+        panic!("Unexpected payload {payload:?}")
+    };
+    assert_eq!(start.address, 0x882);
+}
+
+#[test]
+fn smi_header_only() {
+    let builder = builder();
+    let payload: InstructionTrace = sync::Start {
+        branch: true,
+        ctx: Default::default(),
+        address: 0x80000000,
+    }
+    .into();
+    let mut buffer = [0u8; 64];
+    let mut encoder = builder.encoder(&mut buffer);
+    encoder.encode(&smi::Packet::new(0b10, 0, payload)).unwrap();
+    encoder
+        .encode(&smi::Packet::new(0b10, 0, payload).with_time_tag(0x1234))
+        .unwrap();
+    let len = 64 - encoder.uncommitted();
+    let data = &buffer[..len];
+
+    for split in 0..=data.len() {
+        let (head, tail) = data.split_at(split);
+        let segments: &[&[u8]] = &[head, tail];
+        let mut decoder = builder.decoder_for_source(&segments);
+        let mut expected = builder.decoder(data);
+        while expected.bytes_left() > 0 {
+            let header = decoder.decode_smi_header_only().unwrap();
+            let packet = expected.decode_smi_packet().unwrap();
+            assert_eq!(header.hart(), packet.hart());
+            assert_eq!(header.time_tag(), packet.time_tag());
+            assert_eq!(header.trace_type(), Some(smi::TraceType::Instruction));
+            assert_eq!(*header.payload(), packet.payload().bytes_left());
+            assert_eq!(decoder.bytes_left(), expected.bytes_left());
+        }
+    }
+
+    let mut decoder = builder.decoder(&data[..len - 1]);
+    decoder.decode_smi_header_only().unwrap();
+    assert!(matches!(
+        decoder.decode_smi_header_only(),
+        Err(Error::InsufficientData(_))
+    ));
+}
+
+#[test]
+fn decoder_capabilities() {
+    let capabilities = decoder::capabilities();
+    assert!(
+        capabilities
+            .framings
+            .contains(&crate::transport::Framing::Smi)
+    );
+    assert!(capabilities.units.contains(&"pulp"));
+    assert_eq!(capabilities.check_params(&PARAMS_32), Ok(()));
+    let params = config::Parameters {
+        iaddress_width_p: NonZeroU8::new(65).unwrap(),
+        ..PARAMS_32
+    };
+    assert_eq!(capabilities.check_params(&params), Err("iaddress_width_p"));
+}
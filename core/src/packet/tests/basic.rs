@@ -31,15 +31,15 @@ macro_rules! basic_test {
 
 macro_rules! decode_test {
     ($d:ident, (bits $i:literal, $v:expr $(, $p:literal)?)) => {
-        assert_eq!($d.read_bits($i), Ok($v));
+        assert_eq!($d.read_bits("test", $i), Ok($v));
         $(assert_eq!($d.byte_pos(), $p);)?
     };
     ($d:ident, (bit $v:expr $(, $p:literal)?)) => {
-        assert_eq!($d.read_bit(), Ok($v));
+        assert_eq!($d.read_bit("test"), Ok($v));
         $(assert_eq!($d.byte_pos(), $p);)?
     };
     ($d:ident, (diff $v:expr $(, $p:literal)?)) => {
-        assert_eq!($d.read_differential_bit(), Ok($v));
+        assert_eq!($d.read_differential_bit("test"), Ok($v));
         $(assert_eq!($d.byte_pos(), $p);)?
     };
 }
@@ -97,3 +97,40 @@ bitstream_test!(
     params(&PARAMS_32),
     timestamp_width(1)
 );
+
+#[test]
+fn multiple_payloads() {
+    let data = b"\x0b\x13\x00\x00\x00\xe0\x13\x00\x00\x00\xe0\x01";
+    let mut decoder = Builder::new().decoder(data);
+    let packet = decoder
+        .decode_encap_packet()
+        .unwrap()
+        .into_normal()
+        .unwrap();
+    let mut payloads = packet.payloads();
+    let first = payloads.next().unwrap().unwrap();
+    assert_eq!(payloads.next(), Some(Ok(first)));
+    assert!(matches!(
+        payloads.next(),
+        Some(Ok(payload::Payload::InstructionTrace(_)))
+    ));
+    assert_eq!(payloads.next(), None);
+    assert_eq!(decoder.bytes_left(), 0);
+}
+
+#[test]
+fn multi_payload_transport() {
+    use crate::transport::{MultiPayloadEncap, Transport};
+
+    let data = b"\x0a\x13\x00\x00\x00\xe0\x13\x00\x00\x00\xe0\x05\x13\x00\x00\x00\xe0";
+    let mut decoder = Builder::new().decoder(data);
+    let mut transport = MultiPayloadEncap::default();
+    let first = transport.next_packet(&mut decoder).unwrap().unwrap();
+    assert_eq!(decoder.bytes_left(), data.len());
+    let second = transport.next_packet(&mut decoder).unwrap().unwrap();
+    assert_eq!(second, first);
+    assert_eq!(decoder.bytes_left(), 6);
+    let third = transport.next_packet(&mut decoder).unwrap().unwrap();
+    assert_eq!(third, first);
+    assert_eq!(decoder.bytes_left(), 0);
+}
@@ -17,7 +17,7 @@ fn implicit_return_test_none() {
         })
         .decoder(data);
     decoder
-        .read_bits::<u8>(5)
+        .read_bits::<u8>("test", 5)
         .expect("Tried read bit, but failed");
     let result = read_implicit_return(&mut decoder);
     assert_eq!(result, Ok(Some(1)));
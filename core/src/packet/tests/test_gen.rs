@@ -26,6 +26,9 @@ macro_rules! bitstream_test {
     ($b:tt ($c:expr) trace_type_width ($w:expr) $($k:ident $v:tt)*) => {
         bitstream_test!($b ($c.with_trace_type_width($w)) $($k $v)*);
     };
+    ($b:tt ($c:expr) unit ($u:expr) $($k:ident $v:tt)*) => {
+        bitstream_test!($b ($c.for_unit($u)) $($k $v)*);
+    };
     ($b:tt ($c:expr) compression ($w:expr) $($k:ident $v:tt)*) => {
         bitstream_test!($b ($c.with_compression($w)) $($k $v)*);
     };
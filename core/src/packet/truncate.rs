@@ -3,7 +3,9 @@
 //! Utility for truncating fields of a specific bit-width
 
 /// Truncate a value to a given bit-width
-pub trait TruncateNum {
+///
+/// This trait is sealed and implemented for primitive integer types only.
+pub trait TruncateNum: crate::sealed::Sealed {
     /// Truncate a value to the given width `bit_count`
     ///
     /// For unsigned values, all upper bits will be zeroed. signed values will
@@ -17,6 +19,8 @@ pub trait TruncateNum {
 
 macro_rules! unsigned_truncate {
     ($t:ty) => {
+        impl crate::sealed::Sealed for $t {}
+
         impl TruncateNum for $t {
             fn truncated(self, bit_count: u8) -> Self {
                 self & !((!<$t>::MIN).checked_shl(bit_count.into()).unwrap_or(0))
@@ -35,6 +39,8 @@ unsigned_truncate!(u32);
 unsigned_truncate!(u64);
 unsigned_truncate!(usize);
 
+impl crate::sealed::Sealed for i64 {}
+
 impl TruncateNum for i64 {
     fn truncated(self, bit_count: u8) -> Self {
         let Some(ref_bit) = bit_count.checked_sub(1) else {
@@ -0,0 +1,972 @@
+// Copyright (C) 2025, 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Trace unit implementation specific definitions and utilities
+//!
+//! This module provides traits for capturing some specifics of trace unit
+//! implementations not captured by [`config::Parameters`], as well as
+//! implementations of those traits.
+
+#[cfg(feature = "alloc")]
+pub mod described;
+
+#[cfg(feature = "alloc")]
+use alloc::boxed::Box;
+use core::fmt;
+
+use crate::config;
+use crate::instruction::base;
+use crate::types::Privilege;
+
+use super::decoder::{Decode, Decoder};
+use super::encoder::{Encode, Encoder};
+use super::error::Error;
+use super::ext;
+use super::sync::EncoderMode;
+use super::width::Widths;
+
+use config::AddressMode;
+
+/// Specifics about a trace unit implementation
+pub trait Unit<U = Self> {
+    /// Instruction trace options
+    type IOptions: IOptions + 'static;
+
+    /// Data trace options
+    type DOptions: DOptions + 'static;
+
+    /// [`config::Parameters`] fixed at compile time
+    ///
+    /// If this is `Some`, [`Decoder`]s and [`Encoder`]s for this unit derive
+    /// field widths from these parameters rather than the ones set via
+    /// [`Builder::with_params`][super::Builder::with_params]. Since the widths
+    /// are then known at compile time, the compiler may prune code dealing
+    /// with other widths. The effect on code size depends on the unit, the
+    /// parameters and the compiler, and may be negligible. See [`Profiled`]
+    /// for a convenient way of fixing parameters for any existing unit.
+    ///
+    /// The default is `None`.
+    const FIXED_PARAMETERS: Option<config::Parameters> = None;
+
+    /// Retrieve the [`config::Parameters`] fixed for this unit, if any
+    ///
+    /// Units erasing the type of another unit, such as [`Plug`], can not
+    /// forward [`FIXED_PARAMETERS`][Self::FIXED_PARAMETERS] and override this
+    /// fn instead. Parameters returned take precedence over the ones set via
+    /// [`Builder::with_params`][super::Builder::with_params], but are only
+    /// known at runtime.
+    ///
+    /// The default implementation returns
+    /// [`FIXED_PARAMETERS`][Self::FIXED_PARAMETERS].
+    fn fixed_parameters(&self) -> Option<config::Parameters> {
+        Self::FIXED_PARAMETERS
+    }
+
+    /// Width of the encoder mode field
+    fn encoder_mode_width(&self) -> u8;
+
+    /// Determine whether address flags are encoded as differential bits
+    ///
+    /// The specification defines the `notify` and `updiscon` fields
+    /// accompanying addresses as differential bits, i.e. they are considered
+    /// set if they differ from the respective previous bit. Some units encode
+    /// them as raw bits instead, which is signalled by returning `false`.
+    ///
+    /// The default implementation returns `true`, in accordance with the
+    /// specification.
+    fn differential_address_flags(&self) -> bool {
+        true
+    }
+
+    /// Determine the reference of the flags accompanying addresses
+    ///
+    /// The `notify` and `updiscon` flags are read relative to the
+    /// [`FlagReference`] returned. Units deviating from the specification at
+    /// this boundary may select a different reference.
+    ///
+    /// The default implementation returns [`FlagReference::Previous`] if
+    /// [`differential_address_flags`][Self::differential_address_flags]
+    /// returns `true` and [`FlagReference::Raw`] otherwise.
+    fn address_flag_reference(&self) -> FlagReference {
+        if self.differential_address_flags() {
+            FlagReference::Previous
+        } else {
+            FlagReference::Raw
+        }
+    }
+
+    /// Decode instruction trace options
+    fn decode_ioptions(decoder: &mut Decoder<U>) -> Result<Self::IOptions, Error>;
+
+    /// Decode data trace options
+    fn decode_doptions(decoder: &mut Decoder<U>) -> Result<Self::DOptions, Error>;
+
+    /// Decode a unit specific format 0 subformat
+    ///
+    /// This fn is called for [`Extension`][ext::Extension] payloads with a
+    /// subformat not defined by the specification. The subformat itself was
+    /// already consumed from the `decoder`.
+    ///
+    /// The default implementation fails with [`Error::UnknownFmt`].
+    fn decode_extension(subformat: u8, decoder: &mut Decoder<U>) -> Result<ext::Vendor, Error> {
+        let _ = decoder;
+        Err(Error::UnknownFmt(0, Some(subformat)))
+    }
+
+    /// Encode a unit specific format 0 subformat
+    ///
+    /// This fn is called for encoding [`ext::Vendor`] payloads. The subformat
+    /// was already written to the `encoder`.
+    ///
+    /// The default implementation fails with [`Error::UnknownFmt`].
+    fn encode_extension(extension: &ext::Vendor, encoder: &mut Encoder<U>) -> Result<(), Error> {
+        let _ = encoder;
+        Err(Error::UnknownFmt(0, Some(extension.subformat)))
+    }
+
+    /// Decode a privilege encoding not defined by the specification
+    ///
+    /// This fn is called for privilege encodings reserved by the
+    /// specification, allowing units to map them to any [`Privilege`], e.g.
+    /// [`Privilege::Custom`]. The encoding itself was already consumed from
+    /// the `decoder`.
+    ///
+    /// The default implementation fails with [`Error::UnknownPrivilege`].
+    fn decode_privilege(privilege: u8, decoder: &mut Decoder<U>) -> Result<Privilege, Error> {
+        let _ = decoder;
+        Err(Error::UnknownPrivilege(privilege))
+    }
+
+    /// Decode an encoder mode not defined by the specification
+    ///
+    /// This fn is called for encoder modes other than the ones defined by the
+    /// specification, allowing units with wider mode fields to map them to any
+    /// [`EncoderMode`], e.g. [`EncoderMode::Custom`]. The mode itself was
+    /// already consumed from the `decoder`.
+    ///
+    /// The default implementation fails with [`Error::UnknownEncoderMode`] if
+    /// the `decoder` [is strict][Decoder::is_strict] and preserves the raw
+    /// mode as [`EncoderMode::Custom`] otherwise.
+    fn decode_encoder_mode(mode: u8, decoder: &mut Decoder<U>) -> Result<EncoderMode, Error> {
+        if decoder.is_strict() {
+            Err(Error::UnknownEncoderMode(mode))
+        } else {
+            Ok(EncoderMode::Custom(mode))
+        }
+    }
+
+    /// Encode a privilege
+    ///
+    /// The default implementation writes the encoding defined by the
+    /// specification or the raw encoding of a [`Privilege::Custom`].
+    fn encode_privilege(privilege: Privilege, encoder: &mut Encoder<U>) -> Result<(), Error>
+    where
+        U: Unit,
+    {
+        encoder.write_bits(u8::from(privilege), encoder.widths().privilege.get())
+    }
+
+    /// Adjust the field widths for a single payload
+    ///
+    /// This fn is called after the format of an [`InstructionTrace`] payload
+    /// was consumed from the `decoder`, with the `widths` that would otherwise
+    /// be used for decoding the payload. Units encoding fields with a variable
+    /// width, e.g. addresses with a length indicated in-band, may adjust the
+    /// `widths` accordingly and consume any in-band information from the
+    /// `decoder`. The adjusted widths are only used for the current payload.
+    ///
+    /// The default implementation leaves the widths unchanged.
+    ///
+    /// [`InstructionTrace`]: super::payload::InstructionTrace
+    fn adjust_widths(
+        format: u8,
+        widths: &mut Widths,
+        decoder: &mut Decoder<U>,
+    ) -> Result<(), Error> {
+        let _ = (format, widths, decoder);
+        Ok(())
+    }
+
+    /// Create a [`Plug`] for this unit
+    #[cfg(feature = "alloc")]
+    fn as_plug(&self) -> Plug
+    where
+        Self: Unit<Plug> + Sized,
+        <Self as Unit<Plug>>::IOptions: fmt::Debug,
+        <Self as Unit<Plug>>::DOptions: fmt::Debug,
+    {
+        Plug::new(self)
+    }
+}
+
+/// Reference of the flags accompanying addresses
+///
+/// The flags `notify` and `updiscon` following an address in a payload are
+/// usually encoded as differential bits, i.e. they are considered set if they
+/// differ from some reference bit. The reference bit is always part of the
+/// same payload.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum FlagReference {
+    /// Flags are encoded as raw bits
+    Raw,
+    /// Each flag is relative to the bit immediately preceeding it
+    ///
+    /// This is the encoding defined by the specification: `notify` is relative
+    /// to the most significant bit of the address and `updiscon` is relative
+    /// to `notify`.
+    #[default]
+    Previous,
+    /// All flags are relative to the most significant bit of the address
+    AddressMsb,
+}
+
+/// Instruction trace options that may be communicated via support packets
+///
+/// This trait features fns that return either [`Some`] value reflecting an
+/// option or [`None`] if the type does not contain any information on the
+/// specific option.
+pub trait IOptions: Send + Sync {
+    /// Retrieve the encoder's address mode
+    fn address_mode(&self) -> Option<AddressMode> {
+        None
+    }
+
+    /// Retrieve whether the encoder reports sequentially inferable jumps
+    ///
+    /// Returns `Some(true)` if the encoder signals that it does _not_ report
+    /// sequentially inferable jumps and `Some(false)` if it signals that it
+    /// _does_ report them.
+    fn sequentially_inferred_jumps(&self) -> Option<bool> {
+        None
+    }
+
+    /// Retrieve whether the encoder reports function return addresses
+    ///
+    /// Returns `Some(true)` if the encoder signals that it does _not_ report
+    /// function return addresses and `Some(false)` if it signals that it _does_
+    /// report them.
+    fn implicit_return(&self) -> Option<bool> {
+        None
+    }
+
+    /// Retrieve whether the encoder may omit trap vector addresses
+    ///
+    /// Returns `Some(true)` if the encoder signals that it omits addresses from
+    /// packets reporting traps if that address can be determined from `ecause`.
+    /// Returns `Some(false)` if the encoder signals that it always includes the
+    /// address.
+    fn implicit_exception(&self) -> Option<bool> {
+        None
+    }
+
+    /// Retrieve whether branch prediction is enabled
+    fn branch_prediction(&self) -> Option<bool> {
+        None
+    }
+
+    /// Retrieve whether jump target caching is enabled
+    fn jump_target_cache(&self) -> Option<bool> {
+        None
+    }
+
+    /// Retrieve the base instruction set advertised for the traced hart
+    ///
+    /// Some encoders advertise the hart's ISA in proprietary fields. A
+    /// [`Session`][crate::session::Session] reconciles the [`base::Set`]
+    /// advertised with the one of its [`Binary`][crate::binary::Binary].
+    fn base_set(&self) -> Option<base::Set> {
+        None
+    }
+
+    /// Retrieve whether the traced hart is advertised to support the `C`
+    /// extension
+    fn compressed(&self) -> Option<bool> {
+        None
+    }
+
+    /// Update the active [`Features`][config::Features] based on these ioptions
+    ///
+    /// On success, the given [`Features`][config::Features] reflect the
+    /// configuration conveyed by these ioptions. If any of the activated
+    /// options is not supported, the name of the feature is returned as an
+    /// error. It is the responsibility of the caller to wrap that `&str` into
+    /// an appropriate error type if neccessary.
+    fn update_features(&self, features: &mut config::Features) -> Result<(), &'static str> {
+        // Before touching any state, we need to assert no unsupported option is
+        // active.
+        if self.implicit_exception() == Some(true) {
+            return Err("implicit exceptions");
+        }
+        if self.branch_prediction() == Some(true) {
+            return Err("branch prediction");
+        }
+        if self.jump_target_cache() == Some(true) {
+            return Err("jump target cache");
+        }
+
+        if let Some(jumps) = self.sequentially_inferred_jumps() {
+            features.sequentially_inferred_jumps = jumps;
+        }
+        if let Some(returns) = self.implicit_return() {
+            features.implicit_returns = returns;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: IOptions + ?Sized> IOptions for Box<T> {
+    fn address_mode(&self) -> Option<AddressMode> {
+        T::address_mode(self.as_ref())
+    }
+
+    fn sequentially_inferred_jumps(&self) -> Option<bool> {
+        T::sequentially_inferred_jumps(self.as_ref())
+    }
+
+    fn implicit_return(&self) -> Option<bool> {
+        T::implicit_return(self.as_ref())
+    }
+
+    fn implicit_exception(&self) -> Option<bool> {
+        T::implicit_exception(self.as_ref())
+    }
+
+    fn branch_prediction(&self) -> Option<bool> {
+        T::branch_prediction(self.as_ref())
+    }
+
+    fn jump_target_cache(&self) -> Option<bool> {
+        T::jump_target_cache(self.as_ref())
+    }
+
+    fn base_set(&self) -> Option<base::Set> {
+        T::base_set(self.as_ref())
+    }
+
+    fn compressed(&self) -> Option<bool> {
+        T::compressed(self.as_ref())
+    }
+}
+
+#[cfg(feature = "either")]
+impl<L: IOptions, R: IOptions> IOptions for either::Either<L, R> {
+    fn address_mode(&self) -> Option<AddressMode> {
+        either::for_both!(self, o => o.address_mode())
+    }
+
+    fn sequentially_inferred_jumps(&self) -> Option<bool> {
+        either::for_both!(self, o => o.sequentially_inferred_jumps())
+    }
+
+    fn implicit_return(&self) -> Option<bool> {
+        either::for_both!(self, o => o.implicit_return())
+    }
+
+    fn implicit_exception(&self) -> Option<bool> {
+        either::for_both!(self, o => o.implicit_exception())
+    }
+
+    fn branch_prediction(&self) -> Option<bool> {
+        either::for_both!(self, o => o.branch_prediction())
+    }
+
+    fn jump_target_cache(&self) -> Option<bool> {
+        either::for_both!(self, o => o.jump_target_cache())
+    }
+
+    fn base_set(&self) -> Option<base::Set> {
+        either::for_both!(self, o => o.base_set())
+    }
+
+    fn compressed(&self) -> Option<bool> {
+        either::for_both!(self, o => o.compressed())
+    }
+}
+
+/// An [`IOptions`] that is [`Debug`][fmt::Debug]
+pub trait DebugIOptions: IOptions + fmt::Debug {}
+
+impl<T: IOptions + fmt::Debug> DebugIOptions for T {}
+
+/// Data trace options that may be communicated via support packets
+pub trait DOptions: Send + Sync {}
+
+#[cfg(feature = "alloc")]
+impl<T: DOptions + ?Sized> DOptions for Box<T> {}
+
+#[cfg(feature = "either")]
+impl<L: DOptions, R: DOptions> DOptions for either::Either<L, R> {}
+
+/// An `doptions` that are [`Debug`][fmt::Debug]
+pub trait DebugDOptions: DOptions + fmt::Debug {}
+
+impl<T: DOptions + fmt::Debug> DebugDOptions for T {}
+
+/// Reference trace [`Unit`]
+///
+/// This unit is used in the reference flow (in the form of a model).
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Reference;
+
+impl<U> Unit<U> for Reference {
+    type IOptions = ReferenceIOptions;
+    type DOptions = ReferenceDOptions;
+
+    fn encoder_mode_width(&self) -> u8 {
+        1
+    }
+
+    fn decode_ioptions(decoder: &mut Decoder<U>) -> Result<Self::IOptions, Error> {
+        Decode::decode(decoder)
+    }
+
+    fn decode_doptions(decoder: &mut Decoder<U>) -> Result<Self::DOptions, Error> {
+        Decode::decode(decoder)
+    }
+}
+
+/// [`IOptions`] for the [`Reference`] [`Unit`]
+#[derive(Copy, Clone, Default, Debug, PartialEq)]
+pub struct ReferenceIOptions {
+    pub implicit_return: bool,
+    pub implicit_exception: bool,
+    pub full_address: bool,
+    pub jump_target_cache: bool,
+    pub branch_prediction: bool,
+}
+
+impl<U> Decode<'_, U> for ReferenceIOptions {
+    fn decode(decoder: &mut Decoder<U>) -> Result<Self, Error> {
+        let implicit_return = decoder.read_bit("implicit_return")?;
+        let implicit_exception = decoder.read_bit("implicit_exception")?;
+        let full_address = decoder.read_bit("full_address")?;
+        let jump_target_cache = decoder.read_bit("jump_target_cache")?;
+        let branch_prediction = decoder.read_bit("branch_prediction")?;
+        Ok(Self {
+            implicit_return,
+            implicit_exception,
+            full_address,
+            jump_target_cache,
+            branch_prediction,
+        })
+    }
+}
+
+impl<U> Encode<'_, U> for ReferenceIOptions {
+    fn encode(&self, encoder: &mut Encoder<U>) -> Result<(), Error> {
+        encoder.write_bit(self.implicit_return)?;
+        encoder.write_bit(self.implicit_exception)?;
+        encoder.write_bit(self.full_address)?;
+        encoder.write_bit(self.jump_target_cache)?;
+        encoder.write_bit(self.branch_prediction)
+    }
+}
+
+impl IOptions for ReferenceIOptions {
+    fn address_mode(&self) -> Option<AddressMode> {
+        Some(AddressMode::from_full(self.full_address))
+    }
+
+    fn implicit_return(&self) -> Option<bool> {
+        Some(self.implicit_return)
+    }
+
+    fn implicit_exception(&self) -> Option<bool> {
+        Some(self.implicit_exception)
+    }
+
+    fn branch_prediction(&self) -> Option<bool> {
+        Some(self.branch_prediction)
+    }
+
+    fn jump_target_cache(&self) -> Option<bool> {
+        Some(self.jump_target_cache)
+    }
+}
+
+/// DOptions for the [`Reference`] [`Unit`]
+#[derive(Copy, Clone, Default, Debug, PartialEq)]
+pub struct ReferenceDOptions {
+    pub no_address: bool,
+    pub no_data: bool,
+    pub full_address: bool,
+    pub full_data: bool,
+}
+
+impl<U> Decode<'_, U> for ReferenceDOptions {
+    fn decode(decoder: &mut Decoder<U>) -> Result<Self, Error> {
+        let no_address = decoder.read_bit("no_address")?;
+        let no_data = decoder.read_bit("no_data")?;
+        let full_address = decoder.read_bit("full_address")?;
+        let full_data = decoder.read_bit("full_data")?;
+        Ok(Self {
+            no_address,
+            no_data,
+            full_address,
+            full_data,
+        })
+    }
+}
+
+impl<U> Encode<'_, U> for ReferenceDOptions {
+    fn encode(&self, encoder: &mut Encoder<U>) -> Result<(), Error> {
+        encoder.write_bit(self.no_address)?;
+        encoder.write_bit(self.no_data)?;
+        encoder.write_bit(self.full_address)?;
+        encoder.write_bit(self.full_data)
+    }
+}
+
+impl DOptions for ReferenceDOptions {}
+
+/// PULP trace [`Unit`]
+///
+/// Supports the [PULP rv tracer](https://github.com/pulp-platform/rv_tracer)
+/// and compatible trace units.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct PULP;
+
+impl<U> Unit<U> for PULP {
+    type IOptions = PULPIOptions;
+    type DOptions = NoOptions;
+
+    fn encoder_mode_width(&self) -> u8 {
+        1
+    }
+
+    fn decode_ioptions(decoder: &mut Decoder<U>) -> Result<Self::IOptions, Error> {
+        Decode::decode(decoder)
+    }
+
+    fn decode_doptions(decoder: &mut Decoder<U>) -> Result<Self::DOptions, Error> {
+        Decode::decode(decoder)
+    }
+}
+
+/// [`IOptions`] for the [`PULP`] [`Unit`]
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct PULPIOptions {
+    pub delta_address: bool,
+    pub full_address: bool,
+    pub implicit_exception: bool,
+    pub sijump: bool,
+    pub implicit_return: bool,
+    pub branch_prediction: bool,
+    pub jump_target_cache: bool,
+}
+
+impl<U> Decode<'_, U> for PULPIOptions {
+    fn decode(decoder: &mut Decoder<U>) -> Result<Self, Error> {
+        let jump_target_cache = decoder.read_bit("jump_target_cache")?;
+        let branch_prediction = decoder.read_bit("branch_prediction")?;
+        let implicit_return = decoder.read_bit("implicit_return")?;
+        let sijump = decoder.read_bit("sijump")?;
+        let implicit_exception = decoder.read_bit("implicit_exception")?;
+        let full_address = decoder.read_bit("full_address")?;
+        let delta_address = decoder.read_bit("delta_address")?;
+        Ok(Self {
+            delta_address,
+            full_address,
+            implicit_exception,
+            sijump,
+            implicit_return,
+            branch_prediction,
+            jump_target_cache,
+        })
+    }
+}
+
+impl<U> Encode<'_, U> for PULPIOptions {
+    fn encode(&self, encoder: &mut Encoder<U>) -> Result<(), Error> {
+        encoder.write_bit(self.jump_target_cache)?;
+        encoder.write_bit(self.branch_prediction)?;
+        encoder.write_bit(self.implicit_return)?;
+        encoder.write_bit(self.sijump)?;
+        encoder.write_bit(self.implicit_exception)?;
+        encoder.write_bit(self.full_address)?;
+        encoder.write_bit(self.delta_address)
+    }
+}
+
+impl IOptions for PULPIOptions {
+    fn address_mode(&self) -> Option<AddressMode> {
+        match (self.delta_address, self.full_address) {
+            (true, false) => Some(AddressMode::Delta),
+            (false, true) => Some(AddressMode::Full),
+            _ => None,
+        }
+    }
+
+    fn sequentially_inferred_jumps(&self) -> Option<bool> {
+        Some(self.sijump)
+    }
+
+    fn implicit_return(&self) -> Option<bool> {
+        Some(self.implicit_return)
+    }
+
+    fn implicit_exception(&self) -> Option<bool> {
+        Some(self.implicit_exception)
+    }
+
+    fn branch_prediction(&self) -> Option<bool> {
+        Some(self.branch_prediction)
+    }
+
+    fn jump_target_cache(&self) -> Option<bool> {
+        Some(self.jump_target_cache)
+    }
+}
+
+/// [`config::Parameters`] fixed at compile time
+///
+/// # Example
+///
+/// ```
+/// use core::num::NonZeroU8;
+///
+/// use riscv_etrace_core::config::{self, Parameters};
+/// use riscv_etrace_core::packet::{self, unit};
+///
+/// struct Tiny;
+///
+/// impl unit::Profile for Tiny {
+///     const PARAMETERS: Parameters = Parameters {
+///         iaddress_width_p: NonZeroU8::new(24).unwrap(),
+///         ecause_width_p: NonZeroU8::new(4).unwrap(),
+///         ..config::PARAMETERS
+///     };
+/// }
+///
+/// let unit: unit::Profiled<unit::Reference, Tiny> = Default::default();
+/// let decoder = packet::builder().for_unit(unit).decoder(&[]);
+/// ```
+pub trait Profile {
+    /// The parameters
+    const PARAMETERS: config::Parameters;
+}
+
+/// [`Unit`] with [`config::Parameters`] fixed at compile time
+///
+/// This unit behaves exactly like the wrapped [`Unit`] `U`, except that its
+/// [`FIXED_PARAMETERS`][Unit::FIXED_PARAMETERS] are those of the [`Profile`]
+/// `P`. Parameters set via [`Builder::with_params`][with_params] have no effect
+/// on [`Decoder`]s and [`Encoder`]s for this unit.
+///
+/// The fixed parameters survive conversion to a [`Plug`] via
+/// [`Unit::fixed_parameters`]. However, a [`Plug`] only applies them at
+/// runtime, and the compiler thus can not prune any code.
+///
+/// [with_params]: super::Builder::with_params
+pub struct Profiled<U, P> {
+    unit: U,
+    profile: core::marker::PhantomData<fn() -> P>,
+}
+
+impl<U, P> Profiled<U, P> {
+    /// Create a new profiled unit wrapping the given [`Unit`]
+    pub fn new(unit: U) -> Self {
+        Self {
+            unit,
+            profile: Default::default(),
+        }
+    }
+
+    /// Retrieve the wrapped [`Unit`]
+    pub fn inner(&self) -> &U {
+        &self.unit
+    }
+}
+
+impl<U: Copy, P> Copy for Profiled<U, P> {}
+
+impl<U: Clone, P> Clone for Profiled<U, P> {
+    fn clone(&self) -> Self {
+        Self::new(self.unit.clone())
+    }
+}
+
+impl<U: Default, P> Default for Profiled<U, P> {
+    fn default() -> Self {
+        Self::new(Default::default())
+    }
+}
+
+impl<U: fmt::Debug, P> fmt::Debug for Profiled<U, P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Profiled").field(&self.unit).finish()
+    }
+}
+
+impl<V, U: Unit<V>, P: Profile> Unit<V> for Profiled<U, P> {
+    type IOptions = U::IOptions;
+    type DOptions = U::DOptions;
+
+    const FIXED_PARAMETERS: Option<config::Parameters> = Some(P::PARAMETERS);
+
+    fn encoder_mode_width(&self) -> u8 {
+        self.unit.encoder_mode_width()
+    }
+
+    fn differential_address_flags(&self) -> bool {
+        self.unit.differential_address_flags()
+    }
+
+    fn address_flag_reference(&self) -> FlagReference {
+        self.unit.address_flag_reference()
+    }
+
+    fn decode_ioptions(decoder: &mut Decoder<V>) -> Result<Self::IOptions, Error> {
+        U::decode_ioptions(decoder)
+    }
+
+    fn decode_doptions(decoder: &mut Decoder<V>) -> Result<Self::DOptions, Error> {
+        U::decode_doptions(decoder)
+    }
+
+    fn decode_extension(subformat: u8, decoder: &mut Decoder<V>) -> Result<ext::Vendor, Error> {
+        U::decode_extension(subformat, decoder)
+    }
+
+    fn encode_extension(extension: &ext::Vendor, encoder: &mut Encoder<V>) -> Result<(), Error> {
+        U::encode_extension(extension, encoder)
+    }
+
+    fn decode_encoder_mode(mode: u8, decoder: &mut Decoder<V>) -> Result<EncoderMode, Error> {
+        U::decode_encoder_mode(mode, decoder)
+    }
+
+    fn decode_privilege(privilege: u8, decoder: &mut Decoder<V>) -> Result<Privilege, Error> {
+        U::decode_privilege(privilege, decoder)
+    }
+
+    fn encode_privilege(privilege: Privilege, encoder: &mut Encoder<V>) -> Result<(), Error>
+    where
+        V: Unit,
+    {
+        U::encode_privilege(privilege, encoder)
+    }
+
+    fn adjust_widths(
+        format: u8,
+        widths: &mut Widths,
+        decoder: &mut Decoder<V>,
+    ) -> Result<(), Error> {
+        U::adjust_widths(format, widths, decoder)
+    }
+}
+
+/// A [`Unit`] allowing plugging any [`Unit`] into a [`Decoder`]
+///
+/// [`Decoder`] is generic over its [`Unit`], and may thus be constructed with
+/// any [`Unit`]. However , this choice is reflected in the [`Decoder`]'s type.
+/// This helper allows erasing the type of the specific [`Unit`] used, serving
+/// as a "plug" for arbitrary [`Unit`]s.
+#[cfg(feature = "alloc")]
+#[allow(clippy::type_complexity)]
+#[derive(Copy, Clone, Debug)]
+pub struct Plug {
+    fixed_parameters: Option<config::Parameters>,
+    encoder_mode_width: u8,
+    differential_address_flags: bool,
+    address_flag_reference: FlagReference,
+    decode_ioptions: fn(&mut Decoder<Self>) -> Result<Box<dyn DebugIOptions>, Error>,
+    decode_doptions: fn(&mut Decoder<Self>) -> Result<Box<dyn DebugDOptions>, Error>,
+    decode_extension: fn(u8, &mut Decoder<Self>) -> Result<ext::Vendor, Error>,
+    encode_extension: fn(&ext::Vendor, &mut Encoder<Self>) -> Result<(), Error>,
+    decode_encoder_mode: fn(u8, &mut Decoder<Self>) -> Result<EncoderMode, Error>,
+    decode_privilege: fn(u8, &mut Decoder<Self>) -> Result<Privilege, Error>,
+    encode_privilege: fn(Privilege, &mut Encoder<Self>) -> Result<(), Error>,
+    adjust_widths: fn(u8, &mut Widths, &mut Decoder<Self>) -> Result<(), Error>,
+}
+
+#[cfg(feature = "alloc")]
+impl Plug {
+    /// Create a new plug for the given [`Unit`]
+    pub fn new<U>(inner: &U) -> Self
+    where
+        U: Unit<Self>,
+        U::IOptions: fmt::Debug,
+        U::DOptions: fmt::Debug,
+    {
+        fn decode_ioptions<U>(decoder: &mut Decoder<Plug>) -> Result<Box<dyn DebugIOptions>, Error>
+        where
+            U: Unit<Plug>,
+            U::IOptions: fmt::Debug,
+        {
+            U::decode_ioptions(decoder).map(|r| -> Box<dyn DebugIOptions> { Box::new(r) })
+        }
+
+        fn decode_doptions<U>(decoder: &mut Decoder<Plug>) -> Result<Box<dyn DebugDOptions>, Error>
+        where
+            U: Unit<Plug>,
+            U::DOptions: fmt::Debug,
+        {
+            U::decode_doptions(decoder).map(|r| -> Box<dyn DebugDOptions> { Box::new(r) })
+        }
+
+        Self {
+            fixed_parameters: inner.fixed_parameters(),
+            encoder_mode_width: inner.encoder_mode_width(),
+            differential_address_flags: inner.differential_address_flags(),
+            address_flag_reference: inner.address_flag_reference(),
+            decode_ioptions: decode_ioptions::<U>,
+            decode_doptions: decode_doptions::<U>,
+            decode_extension: U::decode_extension,
+            encode_extension: U::encode_extension,
+            decode_encoder_mode: U::decode_encoder_mode,
+            decode_privilege: U::decode_privilege,
+            encode_privilege: U::encode_privilege,
+            adjust_widths: U::adjust_widths,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Default for Plug {
+    fn default() -> Self {
+        Self::new(&Reference)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Unit for Plug {
+    type IOptions = Box<dyn DebugIOptions>;
+    type DOptions = Box<dyn DebugDOptions>;
+
+    fn fixed_parameters(&self) -> Option<config::Parameters> {
+        self.fixed_parameters
+    }
+
+    fn encoder_mode_width(&self) -> u8 {
+        self.encoder_mode_width
+    }
+
+    fn differential_address_flags(&self) -> bool {
+        self.differential_address_flags
+    }
+
+    fn address_flag_reference(&self) -> FlagReference {
+        self.address_flag_reference
+    }
+
+    fn decode_ioptions(decoder: &mut Decoder<Self>) -> Result<Self::IOptions, Error> {
+        (decoder.unit().decode_ioptions)(decoder)
+    }
+
+    fn decode_doptions(decoder: &mut Decoder<Self>) -> Result<Self::DOptions, Error> {
+        (decoder.unit().decode_doptions)(decoder)
+    }
+
+    fn decode_extension(subformat: u8, decoder: &mut Decoder<Self>) -> Result<ext::Vendor, Error> {
+        (decoder.unit().decode_extension)(subformat, decoder)
+    }
+
+    fn encode_extension(extension: &ext::Vendor, encoder: &mut Encoder<Self>) -> Result<(), Error> {
+        (encoder.unit().encode_extension)(extension, encoder)
+    }
+
+    fn decode_encoder_mode(mode: u8, decoder: &mut Decoder<Self>) -> Result<EncoderMode, Error> {
+        (decoder.unit().decode_encoder_mode)(mode, decoder)
+    }
+
+    fn decode_privilege(privilege: u8, decoder: &mut Decoder<Self>) -> Result<Privilege, Error> {
+        (decoder.unit().decode_privilege)(privilege, decoder)
+    }
+
+    fn encode_privilege(privilege: Privilege, encoder: &mut Encoder<Self>) -> Result<(), Error> {
+        (encoder.unit().encode_privilege)(privilege, encoder)
+    }
+
+    fn adjust_widths(
+        format: u8,
+        widths: &mut Widths,
+        decoder: &mut Decoder<Self>,
+    ) -> Result<(), Error> {
+        (decoder.unit().adjust_widths)(format, widths, decoder)
+    }
+}
+
+/// List of [`Plug`] constructors for all [`Unit`]s provided by this library
+#[cfg(feature = "alloc")]
+pub const PLUGS: &[PlugsEntry<'static>] = &[
+    PlugsEntry::new(
+        "reference",
+        "Reference flow's original encoder model",
+        || Plug::new(&Reference),
+    ),
+    PlugsEntry::new("pulp", "PULP plattform's rv_tracer", || Plug::new(&PULP)),
+];
+
+/// A single entry in a list of [`Plug`]s
+#[cfg(feature = "alloc")]
+#[derive(Copy, Clone, Debug)]
+pub struct PlugsEntry<'a> {
+    name: &'a str,
+    description: &'a str,
+    ctor: fn() -> Plug,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> PlugsEntry<'a> {
+    /// Create a new entry
+    pub const fn new(name: &'a str, description: &'a str, ctor: fn() -> Plug) -> Self {
+        Self {
+            name,
+            description,
+            ctor,
+        }
+    }
+
+    /// Retrieve the name associated to the [`Plug`]
+    pub fn name(&self) -> &'a str {
+        self.name
+    }
+
+    /// Retrieve a description of the [`Plug`]
+    pub fn description(&self) -> &'a str {
+        self.description
+    }
+
+    /// Create the [`Plug`] associated to this entry
+    pub fn plug(&self) -> Plug {
+        (self.ctor)()
+    }
+
+    /// Find the entry in [`PLUGS`] with the given name
+    pub fn by_name(name: &str) -> Option<Self> {
+        PLUGS.iter().find(|p| p.name == name).copied()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Default for PlugsEntry<'_> {
+    fn default() -> Self {
+        PLUGS[0]
+    }
+}
+
+/// Type representing an empty set, zero-bit wide set of options
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct NoOptions;
+
+impl<U> Decode<'_, U> for NoOptions {
+    fn decode(_decoder: &mut Decoder<U>) -> Result<Self, Error> {
+        Ok(Self)
+    }
+}
+
+impl<U> Encode<'_, U> for NoOptions {
+    fn encode(&self, _encoder: &mut Encoder<U>) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+impl IOptions for NoOptions {}
+impl DOptions for NoOptions {}
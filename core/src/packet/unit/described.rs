@@ -0,0 +1,277 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Trace units described by data
+//!
+//! Supporting a trace unit usually requires an implementation of [`Unit`].
+//! However, many units only differ from the [`Reference`][super::Reference]
+//! unit in a few details, such as the order of options in support packets or
+//! the encoding of address flags. This module provides [`Described`], a
+//! [`Unit`] defined entirely by data which may be constructed at runtime.
+//!
+//! # Serde
+//!
+//! If the `serde` feature is enabled, [`Described`] supports
+//! (de)serialization. This allows supporting new units by shipping a
+//! configuration file. Fields not present default to the values of the
+//! [`Reference`][super::Reference] unit.
+//!
+//! ```
+//! # #[cfg(feature = "serde")]
+//! # {
+//! use riscv_etrace_core::packet::unit::described::{Described, IOption};
+//!
+//! let unit: Described = toml::from_str(r#"
+//!     ioptions = ["full_address", "implicit_return"]
+//!     doptions_width = 0
+//!     address_flags = "raw"
+//! "#).unwrap();
+//! assert_eq!(unit.ioptions, [IOption::FullAddress, IOption::ImplicitReturn]);
+//! # }
+//! ```
+
+use alloc::vec::Vec;
+
+use crate::config::{self, AddressMode};
+use crate::instruction::base;
+use crate::packet::Builder;
+use crate::packet::decoder::{Decode, Decoder};
+use crate::packet::encoder::{Encode, Encoder};
+use crate::packet::error::Error;
+
+use super::{DOptions, FlagReference, IOptions, Unit};
+
+/// A [`Unit`] defined by data
+///
+/// Since decoding relies on the data held by the unit, this type only
+/// implements [`Unit<Described>`][Unit] and can thus not be used with a
+/// [`Plug`][super::Plug].
+#[derive(Clone, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct Described {
+    /// Width of the encoder mode field
+    pub encoder_mode_width: u8,
+    /// Reference of the flags accompanying addresses
+    pub address_flags: FlagReference,
+    /// Instruction trace options in the order they are transmitted
+    pub ioptions: Vec<IOption>,
+    /// Width of the data trace options
+    ///
+    /// Data trace options are not interpreted. Widths greater than `64` are
+    /// treated as `64`.
+    pub doptions_width: u8,
+    /// Parameters of the unit, if fixed
+    pub parameters: Option<config::Parameters>,
+}
+
+impl Described {
+    /// Apply this unit to the given [`Builder`]
+    ///
+    /// If this unit specifies [`parameters`][Self::parameters], they are also
+    /// applied.
+    pub fn apply<U>(&self, builder: Builder<U>) -> Builder<Self> {
+        let builder = builder.for_unit(self.clone());
+        match self.parameters.as_ref() {
+            Some(params) => builder.with_params(params),
+            None => builder,
+        }
+    }
+}
+
+impl Default for Described {
+    fn default() -> Self {
+        Self {
+            encoder_mode_width: 1,
+            address_flags: Default::default(),
+            ioptions: Vec::from([
+                IOption::ImplicitReturn,
+                IOption::ImplicitException,
+                IOption::FullAddress,
+                IOption::JumpTargetCache,
+                IOption::BranchPrediction,
+            ]),
+            doptions_width: 4,
+            parameters: None,
+        }
+    }
+}
+
+impl Unit for Described {
+    type IOptions = DescribedIOptions;
+    type DOptions = DescribedDOptions;
+
+    fn encoder_mode_width(&self) -> u8 {
+        self.encoder_mode_width
+    }
+
+    fn differential_address_flags(&self) -> bool {
+        self.address_flags != FlagReference::Raw
+    }
+
+    fn address_flag_reference(&self) -> FlagReference {
+        self.address_flags
+    }
+
+    fn decode_ioptions(decoder: &mut Decoder<Self>) -> Result<Self::IOptions, Error> {
+        Decode::decode(decoder)
+    }
+
+    fn decode_doptions(decoder: &mut Decoder<Self>) -> Result<Self::DOptions, Error> {
+        Decode::decode(decoder)
+    }
+}
+
+/// An instruction trace option
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum IOption {
+    /// Addresses are full addresses if set
+    FullAddress,
+    /// Addresses are differential addresses if set
+    DeltaAddress,
+    /// Trap vector addresses are omitted if set
+    ImplicitException,
+    /// Sequentially inferable jumps are not reported if set
+    Sijump,
+    /// Function return addresses are not reported if set
+    ImplicitReturn,
+    /// Branch prediction is enabled if set
+    BranchPrediction,
+    /// Jump target caching is enabled if set
+    JumpTargetCache,
+    /// The hart implements `RV64I` if set and `RV32I` otherwise
+    Rv64,
+    /// The hart supports the `C` extension if set
+    Compressed,
+    /// The bit is not interpreted
+    Reserved,
+}
+
+impl IOption {
+    /// Retrieve the name of the field holding this option
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::FullAddress => "full_address",
+            Self::DeltaAddress => "delta_address",
+            Self::ImplicitException => "implicit_exception",
+            Self::Sijump => "sijump",
+            Self::ImplicitReturn => "implicit_return",
+            Self::BranchPrediction => "branch_prediction",
+            Self::JumpTargetCache => "jump_target_cache",
+            Self::Rv64 => "rv64",
+            Self::Compressed => "compressed",
+            Self::Reserved => "reserved",
+        }
+    }
+}
+
+/// [`IOptions`] for the [`Described`] [`Unit`]
+///
+/// Options are held in the order they were decoded.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct DescribedIOptions {
+    /// Options along with their values
+    pub options: Vec<(IOption, bool)>,
+}
+
+impl DescribedIOptions {
+    /// Retrieve the value of the given option, if present
+    pub fn get(&self, option: IOption) -> Option<bool> {
+        self.options
+            .iter()
+            .find(|(o, _)| *o == option)
+            .map(|(_, v)| *v)
+    }
+}
+
+impl Decode<'_, Described> for DescribedIOptions {
+    fn decode(decoder: &mut Decoder<Described>) -> Result<Self, Error> {
+        let mut options = Vec::with_capacity(decoder.unit().ioptions.len());
+        for n in 0..decoder.unit().ioptions.len() {
+            let option = decoder.unit().ioptions[n];
+            options.push((option, decoder.read_bit(option.name())?));
+        }
+        Ok(Self { options })
+    }
+}
+
+impl Encode<'_, Described> for DescribedIOptions {
+    fn encode(&self, encoder: &mut Encoder<Described>) -> Result<(), Error> {
+        for n in 0..encoder.unit().ioptions.len() {
+            let option = encoder.unit().ioptions[n];
+            encoder.write_bit(self.get(option).unwrap_or(false))?;
+        }
+        Ok(())
+    }
+}
+
+impl IOptions for DescribedIOptions {
+    fn address_mode(&self) -> Option<AddressMode> {
+        self.get(IOption::FullAddress)
+            .or_else(|| self.get(IOption::DeltaAddress).map(|d| !d))
+            .map(AddressMode::from_full)
+    }
+
+    fn sequentially_inferred_jumps(&self) -> Option<bool> {
+        self.get(IOption::Sijump)
+    }
+
+    fn implicit_return(&self) -> Option<bool> {
+        self.get(IOption::ImplicitReturn)
+    }
+
+    fn implicit_exception(&self) -> Option<bool> {
+        self.get(IOption::ImplicitException)
+    }
+
+    fn branch_prediction(&self) -> Option<bool> {
+        self.get(IOption::BranchPrediction)
+    }
+
+    fn jump_target_cache(&self) -> Option<bool> {
+        self.get(IOption::JumpTargetCache)
+    }
+
+    fn base_set(&self) -> Option<base::Set> {
+        self.get(IOption::Rv64).map(|rv64| {
+            if rv64 {
+                base::Set::Rv64I
+            } else {
+                base::Set::Rv32I
+            }
+        })
+    }
+
+    fn compressed(&self) -> Option<bool> {
+        self.get(IOption::Compressed)
+    }
+}
+
+/// [`DOptions`] for the [`Described`] [`Unit`]
+///
+/// The options are held as raw bits.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct DescribedDOptions(pub u64);
+
+impl Decode<'_, Described> for DescribedDOptions {
+    fn decode(decoder: &mut Decoder<Described>) -> Result<Self, Error> {
+        let width = decoder.unit().doptions_width;
+        decoder.read_uint("doptions", width).map(Self)
+    }
+}
+
+impl Encode<'_, Described> for DescribedDOptions {
+    fn encode(&self, encoder: &mut Encoder<Described>) -> Result<(), Error> {
+        let width = encoder.unit().doptions_width;
+        encoder.write_uint(self.0, width)
+    }
+}
+
+impl DOptions for DescribedDOptions {}
@@ -0,0 +1,235 @@
+// Copyright (C) 2025, 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Utilities for decoding specific items of packets/payloads
+
+use core::{fmt, ops};
+
+use crate::types::branch;
+
+use super::decoder::{Decode, Decoder};
+use super::encoder::{Encode, Encoder};
+use super::unit::{FlagReference, Unit};
+use super::width::Widths;
+use super::{Error, truncate};
+
+/// Read an address
+///
+/// Read an address, honouring the address width and lsb offset specified in the
+/// [`Decoder`]'s protocol configuration.
+pub fn read_address<U: Unit, T>(decoder: &mut Decoder<U>) -> Result<T, Error>
+where
+    T: From<u8>
+        + ops::Shl<u8, Output = T>
+        + ops::Shl<usize, Output = T>
+        + ops::Shr<usize, Output = T>
+        + ops::BitOrAssign<T>
+        + truncate::TruncateNum,
+{
+    let widths = decoder.widths();
+    let lsb = widths.iaddress_lsb;
+    let width = widths.iaddress.get().saturating_sub(lsb);
+    decoder.read_bits::<T>("address", width).map(|v| v << lsb)
+}
+
+/// Write an address
+///
+/// Write an address, honouring the address width and lsb offset specified in the
+/// [`Encoder`]'s protocol configuration.
+pub fn write_address<U: Unit, T>(encoder: &mut Encoder<'_, U>, address: T) -> Result<(), Error>
+where
+    T: Copy
+        + ops::Shl<usize, Output = T>
+        + ops::Shr<usize, Output = T>
+        + ops::BitOrAssign<T>
+        + truncate::TruncateNum,
+{
+    let widths = encoder.widths();
+    let lsb = widths.iaddress_lsb;
+    let width = widths.iaddress.get().saturating_sub(lsb);
+    encoder.write_bits(address >> lsb.into(), width)
+}
+
+/// Read a flag accompanying an address
+///
+/// This fn reads a flag such as `notify` or `updiscon`, honouring the
+/// [`Unit`]'s choice of [reference][Unit::address_flag_reference]. The
+/// `address` is the one preceeding the flag. The `name` is reported to the
+/// decoder's [`Recorder`], if any.
+///
+/// [`Recorder`]: super::annotate::Recorder
+pub fn read_address_flag<U: Unit>(
+    decoder: &mut Decoder<U>,
+    name: &'static str,
+    address: i64,
+) -> Result<bool, Error> {
+    match decoder.unit().address_flag_reference() {
+        FlagReference::Raw => decoder.read_bit(name),
+        FlagReference::Previous => decoder.read_differential_bit(name),
+        FlagReference::AddressMsb => {
+            let msb = address_msb(decoder.widths(), address);
+            decoder.read_bit(name).map(|b| b != msb)
+        }
+    }
+}
+
+/// Write a flag accompanying an address
+///
+/// This fn writes a flag such as `notify` or `updiscon`, honouring the
+/// [`Unit`]'s choice of [reference][Unit::address_flag_reference]. The
+/// `address` is the one preceeding the flag.
+pub fn write_address_flag<U: Unit>(
+    encoder: &mut Encoder<U>,
+    flag: bool,
+    address: i64,
+) -> Result<(), Error> {
+    match encoder.unit().address_flag_reference() {
+        FlagReference::Raw => encoder.write_bit(flag),
+        FlagReference::Previous => encoder.write_differential_bit(flag),
+        FlagReference::AddressMsb => {
+            let msb = address_msb(encoder.widths(), address);
+            encoder.write_bit(flag != msb)
+        }
+    }
+}
+
+/// Determine the most significant bit of an address field
+fn address_msb(widths: Widths, address: i64) -> bool {
+    (address >> (widths.iaddress.get() - 1)) & 1 != 0
+}
+
+/// Read the `irreport` and `irdepth` fields
+///
+/// This fn reads the `irreport` and `irdepth` fields. The former is read
+/// differentially, and if the result is `true` this fn returns `irdepth`.
+/// Otherwise, `None` is returned.
+pub fn read_implicit_return<U: Unit>(decoder: &mut Decoder<U>) -> Result<Option<usize>, Error> {
+    // We intentionally read both the `irreport` and `irdepth` field
+    // unconditionally in order to keep the overall width read constant.
+    let report = decoder.read_differential_bit("irreport")?;
+    let depth = decoder
+        .widths()
+        .stack_depth
+        .map(|w| read_stack_depth(decoder, w.get()))
+        .transpose()?;
+    if report { Ok(depth) } else { Ok(None) }
+}
+
+/// Read an `irdepth` field of the given width
+///
+/// Fields wider than 64 bit are read in chunks. Depths not representable as a
+/// [`usize`] saturate.
+fn read_stack_depth<U: Unit>(decoder: &mut Decoder<U>, width: u16) -> Result<usize, Error> {
+    let chunk = width.min(64);
+    let depth: u64 = decoder.read_bits("irdepth", chunk as u8)?;
+    let mut saturated = false;
+    let mut remaining = width - chunk;
+    while remaining > 0 {
+        let chunk = remaining.min(64);
+        saturated |= decoder.read_bits::<u64>("irdepth", chunk as u8)? != 0;
+        remaining -= chunk;
+    }
+    Ok(usize::try_from(depth)
+        .ok()
+        .filter(|_| !saturated)
+        .unwrap_or(usize::MAX))
+}
+
+/// Write the `irreport` and `irdepth` fields
+///
+/// This fn reads the `irreport` and `irdepth` fields. The former is written
+/// differentially, and is `true` if `irdepth` is not `None`.
+pub fn write_implicit_return<U: Unit>(
+    encoder: &mut Encoder<U>,
+    irdepth: Option<usize>,
+) -> Result<(), Error> {
+    encoder.write_differential_bit(irdepth.is_some())?;
+    Option::zip(irdepth, encoder.widths().stack_depth)
+        .map(|(v, w)| write_stack_depth(encoder, v, w.get()))
+        .transpose()?;
+    Ok(())
+}
+
+/// Write an `irdepth` field of the given width
+///
+/// Fields wider than 64 bit are written in chunks.
+fn write_stack_depth<U: Unit>(
+    encoder: &mut Encoder<U>,
+    depth: usize,
+    width: u16,
+) -> Result<(), Error> {
+    let chunk = width.min(64);
+    let depth = u64::try_from(depth).unwrap_or(u64::MAX);
+    encoder.write_bits(depth, chunk as u8)?;
+    let mut remaining = width - chunk;
+    while remaining > 0 {
+        let chunk = remaining.min(64);
+        encoder.write_bits(0u64, chunk as u8)?;
+        remaining -= chunk;
+    }
+    Ok(())
+}
+
+/// Utility for decoding branch maps
+///
+/// Branch maps consist of a count and the map data, wtih a field length derived
+/// from the count. This utility allows reading a count, and then reading a
+/// [branch::Map], potentially with an altered count.
+#[derive(Copy, Clone, Debug)]
+pub struct BranchCount(pub u8);
+
+impl BranchCount {
+    /// Determine whether this count is zero
+    pub fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Read a branch map with this count
+    ///
+    /// Counts exceeding the capacity of a [branch::Map] are rejected.
+    pub fn read_branch_map<U>(self, decoder: &mut Decoder<U>) -> Result<branch::Map, Error> {
+        if u32::from(self.0) >= branch::Map::MAX_BRANCHES {
+            return Err(Error::BadBranchFmt);
+        }
+        let mut map = decoder.read_bits("branch_map", self.field_length())?;
+        map &= !0u32.checked_shl(self.0.into()).unwrap_or_default();
+        Ok(branch::Map::new(self.0, map))
+    }
+
+    /// Determine the field length
+    ///
+    /// Counts exceeding `31` yield the length of a [full][Self::FULL] map.
+    pub fn field_length(self) -> u8 {
+        core::iter::successors(Some(31), |l| (*l > 0).then_some(l >> 1))
+            .take_while(|l| *l >= self.0)
+            .last()
+            .unwrap_or(31)
+    }
+
+    /// Count for a full branch map
+    pub const FULL: Self = Self(31);
+}
+
+impl<U> Decode<'_, U> for BranchCount {
+    fn decode(decoder: &mut Decoder<U>) -> Result<Self, Error> {
+        decoder.read_bits("branches", 5).map(Self)
+    }
+}
+
+impl<U> Encode<'_, U> for BranchCount {
+    fn encode(&self, encoder: &mut Encoder<U>) -> Result<(), Error> {
+        encoder.write_bits(self.0, 5)
+    }
+}
+
+/// Utility for displaying a [`bool`] as "enabled" or "disabled"
+#[derive(Copy, Clone, Default, Debug)]
+pub struct Enabled(pub bool);
+
+impl fmt::Display for Enabled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            true => write!(f, "enabled"),
+            false => write!(f, "disabled"),
+        }
+    }
+}
@@ -0,0 +1,77 @@
+// Copyright (C) 2025 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Types and utilities related to widths
+
+use core::num::{NonZeroU8, NonZeroU16};
+
+use crate::config::Parameters;
+
+/// Widths of various payload fields
+///
+/// Widths are usually derived from [`Parameters`], but may be adjusted per
+/// payload by a [`Unit`][super::unit::Unit] via
+/// [`Unit::adjust_widths`][super::unit::Unit::adjust_widths].
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Widths {
+    /// Width of jump target cache indices
+    pub cache_index: u8,
+    /// Width of context fields, if present
+    pub context: Option<NonZeroU8>,
+    /// Width of time fields, if present
+    pub time: Option<NonZeroU8>,
+    /// Width of exception causes
+    pub ecause: NonZeroU8,
+    /// Width of format 0 subformats
+    pub format0_subformat: u8,
+    /// Number of address LSBs omitted
+    pub iaddress_lsb: u8,
+    /// Width of instruction addresses
+    pub iaddress: NonZeroU8,
+    /// Width of privilege fields
+    pub privilege: NonZeroU8,
+    /// Width of `irdepth` fields, if present
+    ///
+    /// For large `return_stack_size_p` and `call_counter_size_p`, this width
+    /// may exceed the width of any integer type.
+    pub stack_depth: Option<NonZeroU16>,
+}
+
+impl Default for Widths {
+    fn default() -> Self {
+        (&Parameters::default()).into()
+    }
+}
+
+impl Widths {
+    /// Derive widths from [`Parameters`]
+    pub const fn new(params: &Parameters) -> Self {
+        let return_stack = params.return_stack_size_p as u16;
+        let stack_depth =
+            return_stack + params.call_counter_size_p as u16 + if return_stack > 0 { 1 } else { 0 };
+        Self {
+            cache_index: params.cache_size_p,
+            context: if params.nocontext_p {
+                None
+            } else {
+                Some(params.context_width_p)
+            },
+            time: if params.notime_p {
+                None
+            } else {
+                Some(params.time_width_p)
+            },
+            ecause: params.ecause_width_p,
+            format0_subformat: params.f0s_width_p,
+            iaddress_lsb: params.iaddress_lsb_p,
+            iaddress: params.iaddress_width_p,
+            privilege: params.privilege_width_p,
+            stack_depth: NonZeroU16::new(stack_depth),
+        }
+    }
+}
+
+impl From<&Parameters> for Widths {
+    fn from(params: &Parameters) -> Self {
+        Self::new(params)
+    }
+}
@@ -0,0 +1,44 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Commonly used items
+//!
+//! This module re-exports the modules providing the builders for decoders,
+//! tracers and sessions as well as traits and types commonly needed for
+//! tracing. It is intended to be glob-imported.
+//!
+//! # Example
+//!
+//! ```
+//! use riscv_etrace_core::instruction::base;
+//! use riscv_etrace_core::prelude::*;
+//!
+//! # let binary_data = b"\x14\x41\x11\x05\x94\xc1\x91\x05\xe3\xec\xc5\xfe\x82\x80";
+//! # let trace_data = b"\x45\x73\x0a\x00\x00\x20\x41\x01";
+//! let binary = binary::from_segment(binary_data, base::Set::Rv32I)
+//!     .with_offset(0x80000028);
+//!
+//! let parameters = Parameters::default();
+//! let mut decoder = packet::builder()
+//!     .with_params(&parameters)
+//!     .decoder(trace_data);
+//! let mut tracer: Tracer<_> = tracer::builder()
+//!     .with_binary(binary)
+//!     .with_params(&parameters)
+//!     .build()
+//!     .unwrap();
+//!
+//! let packet = decoder.decode_encap_packet().unwrap().into_normal().unwrap();
+//! tracer.process_payload(&packet.decode_payload().unwrap()).unwrap();
+//! let items: Vec<Item> = tracer.map(Result::unwrap).collect();
+//! assert_eq!(items[0].pc(), 0x80000028);
+//! ```
+
+pub use crate::binary::{self, Adaptable, Binary};
+pub use crate::config::Parameters;
+pub use crate::instruction::info::Info;
+pub use crate::packet::{self, unit::IOptions};
+pub use crate::tracer::{self, Tracer, item::Item};
+pub use crate::types::stack::ReturnStack;
+
+#[cfg(feature = "alloc")]
+pub use crate::session::{self, Session};
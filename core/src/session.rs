@@ -0,0 +1,1412 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Tracing sessions covering multiple harts
+//!
+//! This module provides a [`Session`], which combines the decoding of packets
+//! with the dispatching of their payloads to one [`Tracer`] per hart. Sessions
+//! are intended for applications which simply want to process all tracing
+//! [`Item`]s contained in some trace data with minimal effort.
+//!
+//! Sessions are constructed using a [`Builder`].
+
+pub mod error;
+pub mod index;
+pub mod isa;
+pub mod lifecycle;
+pub mod postmortem;
+pub mod repro;
+pub mod segment;
+pub mod span;
+pub mod timestamping;
+pub mod topology;
+pub mod validate;
+
+#[cfg(test)]
+mod tests;
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::ops::{ControlFlow, Range};
+
+use crate::binary::{self, Binary};
+use crate::config;
+use crate::instruction::{self, base, info::Info};
+use crate::packet::{self, encap, payload, unit};
+use crate::tracer::item::{Confidence, Item, Stamped, Stamper};
+use crate::tracer::{self, Tracer, filter::Filter};
+use crate::transport::{Transport, UnitPacket};
+use crate::types::stack::{self, ReturnStack};
+
+pub use crate::transport::{Framing, Packet};
+
+use error::Error;
+use index::{Index, Occurrence, Slice};
+use postmortem::Window;
+use timestamping::Timestamping;
+
+/// Tracing session covering multiple harts
+///
+/// A session decodes packets from raw trace data via a [`Transport`], usually
+/// a [`Framing`], and dispatches their payloads to the [`Tracer`] for the hart
+/// identified in the packet. [`Tracer`]s are created on demand from the
+/// [`tracer::Builder`] the session was built with.
+///
+/// # Example
+///
+/// The following example demonstrates tracing all harts with a callback which
+/// is invoked for every [`Item`].
+///
+/// ```
+/// use riscv_etrace_core::binary::{self, Adaptable};
+/// use riscv_etrace_core::instruction::base;
+/// use riscv_etrace_core::session;
+///
+/// # let binary_data = b"\x14\x41\x11\x05\x94\xc1\x91\x05\xe3\xec\xc5\xfe\x82\x80";
+/// # let trace_data = b"\x45\x73\x0a\x00\x00\x20\x41\x01";
+/// let binary = binary::from_segment(binary_data, base::Set::Rv32I)
+///     .with_offset(0x80000028);
+/// let mut session: session::Session<_, _> = session::builder()
+///     .with_params(&Default::default())
+///     .with_binary(binary)
+///     .build();
+///
+/// session
+///     .for_each_item(trace_data, |hart, item| {
+///         println!("Hart {hart}, PC: {:0x}", item.pc());
+///     })
+///     .unwrap();
+/// ```
+pub struct Session<U, B, S = stack::NoStack, I = Option<instruction::Kind>, T = Framing>
+where
+    B: Binary<I>,
+    S: ReturnStack,
+    I: Info + Clone,
+{
+    decoder: packet::Builder<U>,
+    tracer: tracer::Builder<B>,
+    transport: T,
+    harts: BTreeMap<u64, Tracer<B, S, I>>,
+    region_bases: BTreeMap<u64, u64>,
+    topology: Option<topology::Topology>,
+    source: u64,
+    carry: BTreeMap<u64, Vec<u8>>,
+    suspect: BTreeSet<u64>,
+    lifecycle: BTreeMap<u64, lifecycle::State>,
+    transitions: Vec<lifecycle::Transition>,
+    wfi: BTreeSet<u64>,
+    last_seen: BTreeMap<u64, u64>,
+    sleep_threshold: Option<u64>,
+    drowsy: BTreeMap<u64, (Option<u64>, lifecycle::SleepCause)>,
+    sleeps: Vec<lifecycle::Sleep>,
+    advertised: BTreeMap<u64, base::Set>,
+    isa_mismatches: Vec<isa::Mismatch>,
+    timestamping: Option<Arc<dyn Timestamping + Send + Sync>>,
+    timestamps: BTreeMap<u64, u64>,
+    filter: Option<Filter>,
+    repro: Option<repro::Buffer>,
+}
+
+impl<U, B, S, I, T> Session<U, B, S, I, T>
+where
+    U: unit::Unit + Clone,
+    B: Binary<I> + Clone,
+    S: ReturnStack,
+    I: Info + Clone,
+    T: Transport<U> + Clone,
+{
+    /// Decode all [`Packet`]s in the given trace data
+    ///
+    /// Null packets are skipped. The trace data is expected to end at a packet
+    /// boundary. The hart indices of the packets returned are not mapped via
+    /// the session's [`Topology`][topology::Topology].
+    pub fn decode_packets(&mut self, data: &[u8]) -> Result<Vec<UnitPacket<U>>, packet::Error> {
+        let mut decoder = self.decoder.clone().decoder(data);
+        let mut packets = Vec::new();
+        while decoder.bytes_left() > 0 {
+            if let Some(packet) = self.transport.next_packet(&mut decoder)? {
+                packets.push(packet);
+            }
+        }
+        Ok(packets)
+    }
+
+    /// Process a single [`Packet`]
+    ///
+    /// The packet's payload is dispatched to the [`Tracer`] for the packet's
+    /// hart, which is expected to be a global hart id. The given callback is
+    /// invoked for each resulting [`Item`].
+    ///
+    /// The hart's [`lifecycle::State`] is updated according to the payload and,
+    /// if the last instruction retired was a `wfi`, the time passed since the
+    /// hart's previous packet. If the payload is a support packet advertising
+    /// a [`base::Set`] other than the one of the hart's [`Binary`], an
+    /// [`isa::Mismatch`] is recorded. If the session was built with a
+    /// [`Filter`], items not matching it are dropped.
+    pub fn process_packet(
+        &mut self,
+        packet: &Packet<U::IOptions, U::DOptions>,
+        mut f: impl FnMut(u64, Item<I>),
+    ) -> Result<(), Error<B::Error>> {
+        let filter = self.filter;
+        self.dispatch_packet(packet, |h, i| {
+            if filter.is_none_or(|filter| filter.matches(h, &i)) {
+                f(h, i)
+            }
+        })
+    }
+
+    /// Dispatch a single [`Packet`] to its hart's [`Tracer`]
+    ///
+    /// This fn behaves like [`process_packet`][Self::process_packet], except
+    /// that items are not filtered.
+    fn dispatch_packet(
+        &mut self,
+        packet: &Packet<U::IOptions, U::DOptions>,
+        mut f: impl FnMut(u64, Item<I>),
+    ) -> Result<(), Error<B::Error>> {
+        let hart = packet.hart;
+        let timestamp = packet.timestamp;
+        let wfi = self.wfi.contains(&hart);
+        let last = timestamp.and_then(|t| self.last_seen.insert(hart, t));
+        if let (true, Some(threshold), Some(now), Some(last)) =
+            (wfi, self.sleep_threshold, timestamp, last)
+        {
+            let silent = now.checked_sub(last).is_some_and(|d| d >= threshold);
+            if self.lifecycle(hart) == lifecycle::State::Active && silent {
+                self.fall_asleep(hart, Some(last), lifecycle::SleepCause::Silence);
+            }
+        }
+        let current = self.lifecycle(hart);
+        let state = match current.next(&packet.payload) {
+            lifecycle::State::Paused if current == lifecycle::State::Asleep => current,
+            lifecycle::State::Paused if wfi => {
+                self.fall_asleep(hart, timestamp, lifecycle::SleepCause::Unqualified);
+                lifecycle::State::Asleep
+            }
+            state => state,
+        };
+        self.transition(hart, timestamp, state);
+        let support = packet.payload.as_support();
+        if let Some(set) = support.and_then(|s| unit::IOptions::base_set(&s.ioptions)) {
+            self.reconcile_isa(hart, packet.timestamp, set)?;
+        }
+        let tracer = self.tracer_mut(hart)?;
+        tracer
+            .process_payload(&packet.payload)
+            .map_err(|e| Error::Tracer(hart, e))?;
+        let mut wfi = None;
+        let mut observe = |i: Item<I>| {
+            if let Some(insn) = i.instruction() {
+                wfi = Some(insn.info.is_wfi());
+            }
+            f(hart, i)
+        };
+        tracer.try_for_each(|i| i.map(&mut observe).map_err(|e| Error::Tracer(hart, e)))?;
+        match wfi {
+            Some(true) => self.wfi.insert(hart),
+            Some(false) => self.wfi.remove(&hart),
+            None => false,
+        };
+        Ok(())
+    }
+
+    /// Decode all [`Packet`]s in the given trace data for processing
+    ///
+    /// The packets are decoded like by [`decode_packets`][Self::decode_packets].
+    /// Their hart indices are mapped to hart ids and their timestamps are
+    /// extended like for [`for_each_item`][Self::for_each_item]. The packets
+    /// are returned [sorted by time][sort_by_time], ready for being passed to
+    /// [`process_packet`][Self::process_packet] in order.
+    pub fn decode_sorted_packets(
+        &mut self,
+        data: &[u8],
+    ) -> Result<Vec<UnitPacket<U>>, Error<B::Error>> {
+        let mut packets = self.decode_packets(data)?;
+        self.map_harts(&mut packets)?;
+        self.extend_timestamps(&mut packets);
+        sort_by_time(&mut packets);
+        Ok(packets)
+    }
+
+    /// Process all [`Item`]s in the given trace data
+    ///
+    /// All packets are decoded first. They are then [sorted by
+    /// time][sort_by_time] and processed in that order. The given callback is
+    /// invoked for each resulting [`Item`] with the index of the hart it
+    /// belongs to.
+    ///
+    /// If the session was built with a [`Topology`][topology::Topology], the
+    /// data is considered to originate from the current
+    /// [source][Self::set_source] and hart indices are mapped to global hart
+    /// ids. Packets for unmapped hart indices result in an error.
+    ///
+    /// If the session was built with a
+    /// [repro window][Builder::with_repro_window], the raw data of each packet
+    /// is retained in the session's [`repro::Buffer`] before it is processed.
+    pub fn for_each_item(
+        &mut self,
+        data: &[u8],
+        mut f: impl FnMut(u64, Item<I>),
+    ) -> Result<(), Error<B::Error>> {
+        if self.repro.is_some() {
+            return self.for_each_item_retained(data, f);
+        }
+        self.decode_sorted_packets(data)?
+            .iter()
+            .try_for_each(|p| self.process_packet(p, &mut f))
+    }
+
+    /// Process all [`Item`]s in the given trace data as [`Stamped`] items
+    ///
+    /// This fn behaves like [`for_each_item`][Self::for_each_item], except
+    /// that the callback is invoked with [`Stamped`] items. Their key carries
+    /// the hart, the timestamp of the packet the item was generated from, if
+    /// any, and a sequence number reflecting the order in which items were
+    /// generated. Raw packets are not retained, regardless of the
+    /// [repro window][Builder::with_repro_window].
+    pub fn for_each_stamped_item(
+        &mut self,
+        data: &[u8],
+        mut f: impl FnMut(Stamped<I>),
+    ) -> Result<(), Error<B::Error>> {
+        let mut stamper = Stamper::default();
+        self.decode_sorted_packets(data)?
+            .iter()
+            .try_for_each(|p| self.process_packet(p, |h, i| f(stamper.stamp(h, p.timestamp, i))))
+    }
+
+    /// Process all [`Item`]s in the given trace data, retaining raw packets
+    ///
+    /// This fn behaves like [`for_each_item`][Self::for_each_item], except
+    /// that the raw data of each packet is pushed to the session's
+    /// [`repro::Buffer`] before the packet is processed.
+    fn for_each_item_retained(
+        &mut self,
+        data: &[u8],
+        mut f: impl FnMut(u64, Item<I>),
+    ) -> Result<(), Error<B::Error>> {
+        let mut decoder = self.decoder.clone().decoder(data);
+        let mut packets = Vec::new();
+        while decoder.bytes_left() > 0 {
+            let start = data.len() - decoder.bytes_left();
+            let packet = self.transport.next_packet(&mut decoder)?;
+            let end = data.len() - decoder.bytes_left();
+            packets.extend(packet.map(|p| (start..end, p)));
+        }
+        packets.iter_mut().try_for_each(|(_, p)| {
+            self.map_harts(core::slice::from_mut(p))?;
+            self.extend_timestamps(core::slice::from_mut(p));
+            Ok::<_, Error<B::Error>>(())
+        })?;
+        sort_keyed_by_time(&mut packets, |(_, p)| p.hart, |(_, p)| p.timestamp);
+
+        packets.into_iter().try_for_each(|(range, packet)| {
+            if let Some(repro) = self.repro.as_mut() {
+                repro.push(packet.hart, &data[range]);
+            }
+            self.process_packet(&packet, &mut f)
+        })
+    }
+
+    /// Process the [`Item`]s in a [`span::Span`] of the given trace data
+    ///
+    /// All packets are decoded and [sorted by time][sort_by_time] like for
+    /// [`for_each_item`][Self::for_each_item]. For each hart, tracing starts
+    /// at the last packet before the `span` from which tracing can be
+    /// (re)started, i.e. a [`sync::Start`] or a [`sync::Trap`] with a trap
+    /// handler address. Earlier packets are skipped. Harts without such a
+    /// packet before the `span` are synchronized within the `span`, skipping
+    /// packets until then. Tracing stops at the end of the `span`.
+    ///
+    /// The given callback is only invoked for [`Item`]s generated from packets
+    /// within the `span`. Any existing [`Tracer`] for a hart in the trace data
+    /// is discarded, since the [`Tracer`]'s state is unlikely to match the
+    /// packets preceeding the `span`.
+    ///
+    /// [`sync::Start`]: packet::sync::Start
+    /// [`sync::Trap`]: packet::sync::Trap
+    pub fn for_each_item_in(
+        &mut self,
+        data: &[u8],
+        span: &span::Span,
+        mut f: impl FnMut(u64, Item<I>),
+    ) -> Result<span::Report, Error<B::Error>> {
+        let packets = self.decode_sorted_packets(data)?;
+
+        let mut report = span::Report {
+            packets: packets.len(),
+            ..Default::default()
+        };
+        let mut time = None;
+        let times: Vec<_> = packets
+            .iter()
+            .map(|p| {
+                time = p.timestamp.or(time);
+                time
+            })
+            .collect();
+        let first = (0..packets.len())
+            .find(|n| span.contains(*n, times[*n]) || span.precedes(*n, times[*n]))
+            .unwrap_or(packets.len());
+        let end = (first..packets.len())
+            .find(|n| span.precedes(*n, times[*n]))
+            .unwrap_or(packets.len());
+
+        let mut starts = BTreeMap::new();
+        packets.iter().enumerate().for_each(|(n, p)| {
+            self.harts.remove(&p.hart);
+            if n < first && is_resync_point(&p.payload) {
+                starts.insert(p.hart, n);
+            }
+        });
+
+        for (n, packet) in packets.iter().enumerate().take(end) {
+            let within = n >= first;
+            let hart = packet.hart;
+            if !within && starts.get(&hart).is_none_or(|s| n < *s) {
+                report.skipped += 1;
+                continue;
+            }
+            if !is_resync_point(&packet.payload) && !self.harts.contains_key(&hart) {
+                report.unsynced += 1;
+                continue;
+            }
+
+            if within {
+                report.traced += 1;
+                self.process_packet(packet, &mut f)?;
+            } else {
+                report.warmup += 1;
+                self.dispatch_packet(packet, |_, _| ())?;
+            }
+        }
+        report.skipped += packets.len() - end;
+        Ok(report)
+    }
+
+    /// Process a segment of trace data split across multiple segments
+    ///
+    /// The segment is processed in relation to the segments previously passed
+    /// to this fn according to the given [`segment::Boundary`]. A packet cut
+    /// at the end of the segment is carried over to the next segment. The
+    /// packets in the segment are then [sorted by time][sort_by_time] and
+    /// processed in that order.
+    ///
+    /// The given callback is invoked for each resulting [`Item`] with the
+    /// index of the hart it belongs to and a flag indicating whether the item
+    /// is potentially unreliable. Items of a hart are potentially unreliable
+    /// if they are generated after a [`segment::Boundary::Lossy`] and before
+    /// or from the hart's next resynchronization. Items generated before the
+    /// resynchronization are also marked as [`Confidence::Speculative`] unless
+    /// they already carry a different [`Confidence`]. The first item generated
+    /// from the resynchronization is marked as [`Confidence::Gap`]. If tracing
+    /// fails for such a hart, its [`Tracer`] is discarded.
+    ///
+    /// After a [`segment::Boundary::Lossy`], data at the start of the segment
+    /// is skipped up to the first position from which a number of packets,
+    /// at least one of which carries a payload, can be decoded without error.
+    /// That number is [`segment::RESYNC_LOOKAHEAD`], or less if the segment
+    /// ends before. This heuristic may be fooled by data which happens to
+    /// decode to packets. Decoding errors after that position are reported
+    /// like for a [`segment::Boundary::Contiguous`] segment.
+    ///
+    /// Packets for harts without a [`Tracer`] are skipped unless tracing can
+    /// be (re)started from them, i.e. if they are a [`sync::Start`] or a
+    /// [`sync::Trap`] with a trap handler address.
+    ///
+    /// Segments are associated to the current [source][Self::set_source]. Hart
+    /// indices are mapped like for [`for_each_item`][Self::for_each_item].
+    /// Packets cut at the end of a segment are carried over per source, and a
+    /// [`segment::Boundary::Lossy`] only affects harts of the current source.
+    ///
+    /// [`sync::Start`]: packet::sync::Start
+    /// [`sync::Trap`]: packet::sync::Trap
+    pub fn process_segment(
+        &mut self,
+        data: &[u8],
+        boundary: segment::Boundary,
+        mut f: impl FnMut(u64, Item<I>, bool),
+    ) -> Result<segment::Report, Error<B::Error>> {
+        let mut report = segment::Report::default();
+        let mut carry = self.carry.remove(&self.source).unwrap_or_default();
+        let data = match boundary {
+            segment::Boundary::Contiguous if carry.is_empty() => data,
+            segment::Boundary::Contiguous => {
+                carry.extend_from_slice(data);
+                carry.as_slice()
+            }
+            segment::Boundary::Lossy => {
+                report.discarded = carry.len();
+                let harts: Vec<_> = self.harts().filter(|h| self.is_from_source(*h)).collect();
+                harts.iter().for_each(|h| {
+                    if matches!(
+                        self.lifecycle(*h),
+                        lifecycle::State::Active
+                            | lifecycle::State::Paused
+                            | lifecycle::State::Asleep
+                    ) {
+                        self.transition(*h, None, lifecycle::State::Lost);
+                    }
+                });
+                self.suspect.extend(harts);
+                report.skipped = (0..data.len())
+                    .find(|o| is_sync_offset(&self.decoder, self.transport.clone(), &data[*o..]))
+                    .unwrap_or(data.len());
+                &data[report.skipped..]
+            }
+        };
+
+        let (mut packets, rest) = decode_segment(&self.decoder, &mut self.transport, data)?;
+        report.cut = rest.len();
+        if !rest.is_empty() {
+            self.carry.insert(self.source, rest.into());
+        }
+        self.map_harts(&mut packets)?;
+        self.extend_timestamps(&mut packets);
+        sort_by_time(&mut packets);
+
+        for packet in packets {
+            let hart = packet.hart;
+            let resync = is_resync_point(&packet.payload);
+            if !resync && !self.harts.contains_key(&hart) {
+                report.unsynced += 1;
+                continue;
+            }
+
+            let lossy = boundary == segment::Boundary::Lossy;
+            let suspect =
+                self.suspect.contains(&hart) || (lossy && !self.harts.contains_key(&hart));
+            let mut resumed = suspect && resync;
+            let mut annotate = |i: Item<I>| match i.confidence() {
+                _ if core::mem::take(&mut resumed) => i.with_confidence(Confidence::Gap),
+                Confidence::Certain if suspect && !resync => {
+                    i.with_confidence(Confidence::Speculative)
+                }
+                _ => i,
+            };
+            let filter = self.filter;
+            let res = self.dispatch_packet(&packet, |h, i| {
+                let i = annotate(i);
+                if filter.is_none_or(|filter| filter.matches(h, &i)) {
+                    f(h, i, suspect)
+                }
+            });
+            match res {
+                Ok(()) if resync => {
+                    self.suspect.remove(&hart);
+                }
+                Ok(()) => (),
+                Err(Error::Tracer(_, _)) if suspect => {
+                    self.harts.remove(&hart);
+                    self.suspect.remove(&hart);
+                    report.resets += 1;
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(report)
+    }
+
+    /// Validate the given trace data, collecting all problems encountered
+    ///
+    /// Unlike [`for_each_item`][Self::for_each_item], this fn does not stop at
+    /// the first error. Instead, errors are recorded along with their
+    /// provenance in a [`validate::Report`] and processing continues:
+    ///
+    /// * after a decoding error, decoding resumes at the first subsequent byte
+    ///   from which a packet can be decoded, recording only a single problem
+    ///   for the bytes skipped,
+    /// * after a tracing error, the hart's [`Tracer`] is discarded and packets
+    ///   for the hart are skipped until tracing can be restarted, i.e. until
+    ///   a [`sync::Start`] or a [`sync::Trap`] with a trap handler address.
+    ///
+    /// Validation stops once `limit` problems were recorded. Packets are
+    /// processed like by [`for_each_item`][Self::for_each_item], with offsets
+    /// reported being relative to the start of `data`.
+    ///
+    /// [`sync::Start`]: packet::sync::Start
+    /// [`sync::Trap`]: packet::sync::Trap
+    pub fn validate(&mut self, data: &[u8], limit: usize) -> validate::Report<B::Error> {
+        use validate::Problem;
+
+        let mut report = validate::Report::new();
+        if limit == 0 {
+            return report;
+        }
+
+        let mut decoder = self.decoder.clone().decoder(data);
+        let mut failed = false;
+        let mut packets = Vec::new();
+        while decoder.bytes_left() > 0 {
+            let offset = data.len() - decoder.bytes_left();
+            match self.transport.next_packet(&mut decoder) {
+                Ok(packet) => {
+                    failed = false;
+                    packets.extend(packet.map(|p| (offset, p)));
+                }
+                Err(e) => {
+                    let problem = Problem {
+                        offset,
+                        hart: None,
+                        timestamp: None,
+                        error: e.into(),
+                    };
+                    if !core::mem::replace(&mut failed, true) && !report.record(problem, limit) {
+                        return report;
+                    }
+                    decoder.reset(&data[offset + 1..]);
+                }
+            }
+        }
+        report.packets = packets.len();
+
+        let mut mapped = Vec::with_capacity(packets.len());
+        for (offset, mut packet) in packets {
+            if let Err(error) = self.map_harts(core::slice::from_mut(&mut packet)) {
+                let problem = Problem {
+                    offset,
+                    hart: None,
+                    timestamp: packet.timestamp,
+                    error,
+                };
+                if !report.record(problem, limit) {
+                    return report;
+                }
+                continue;
+            }
+            self.extend_timestamps(core::slice::from_mut(&mut packet));
+            mapped.push((offset, packet));
+        }
+
+        sort_keyed_by_time(&mut mapped, |(_, p)| p.hart, |(_, p)| p.timestamp);
+        for (offset, packet) in mapped {
+            let hart = packet.hart;
+            if !is_resync_point(&packet.payload) && !self.harts.contains_key(&hart) {
+                report.unsynced += 1;
+                continue;
+            }
+
+            let items = &mut report.items;
+            if let Err(error) = self.process_packet(&packet, |_, _| *items += 1) {
+                self.harts.remove(&hart);
+                let problem = Problem {
+                    offset,
+                    hart: Some(hart),
+                    timestamp: packet.timestamp,
+                    error,
+                };
+                if !report.record(problem, limit) {
+                    return report;
+                }
+            }
+        }
+        report
+    }
+
+    /// Re-trace the packets of a hart preceeding a failure
+    ///
+    /// Searches the last packet before the one at `index` for the same hart
+    /// from which tracing can be (re)started, i.e. a [`sync::Start`] or a
+    /// [`sync::Trap`] with a trap handler address. The packets for that hart
+    /// are then processed with a new [`Tracer`], starting at that packet, up
+    /// to the packet at `index` or the first failure. The `packets` are
+    /// expected in the order in which they were processed originally, e.g.
+    /// [sorted by time][sort_by_time].
+    ///
+    /// Returns `None` if `index` is out of bounds or if no packet suitable for
+    /// starting tracing was found.
+    ///
+    /// [`sync::Start`]: packet::sync::Start
+    /// [`sync::Trap`]: packet::sync::Trap
+    #[allow(clippy::type_complexity)]
+    pub fn post_mortem(
+        &self,
+        packets: &[UnitPacket<U>],
+        index: usize,
+    ) -> Result<Option<Window<B, S, I>>, Error<B::Error>>
+    where
+        S: Clone,
+    {
+        let Some(hart) = packets.get(index).map(|p| p.hart) else {
+            return Ok(None);
+        };
+        let Some(start) = packets[..index]
+            .iter()
+            .rposition(|p| p.hart == hart && is_resync_point(&p.payload))
+        else {
+            return Ok(None);
+        };
+
+        let mut tracer = self.build_tracer(hart)?;
+        let mut items = Vec::new();
+        let mut process = |tracer: &mut Tracer<B, S, I>, packet: &UnitPacket<U>| {
+            tracer.process_payload(&packet.payload)?;
+            tracer.try_for_each(|i| i.map(|i| items.push(i)))
+        };
+
+        let mut end = index;
+        let mut before = None;
+        let mut error = None;
+        for n in (start..=index).filter(|n| packets[*n].hart == hart) {
+            before = Some(tracer.clone());
+            if let Err(e) = process(&mut tracer, &packets[n]) {
+                end = n;
+                error = Some(e);
+                break;
+            }
+        }
+
+        Ok(Some(Window {
+            hart,
+            start,
+            end,
+            items,
+            tracer: before.unwrap_or(tracer),
+            error,
+        }))
+    }
+
+    /// Determine the PC of a hart at the given time
+    ///
+    /// Re-traces the packets of the given hart with a new [`Tracer`], starting
+    /// at the last sync point in the [`Index`] at or before `timestamp`. The
+    /// last [`Item`] generated from a packet with a timestamp not later than
+    /// `timestamp` is returned. Since packets usually report instructions
+    /// retired some time before, this is an approximation. The `packets` are
+    /// expected to be the ones the [`Index`] was created from.
+    ///
+    /// Returns `None` if there is no sync point at or before `timestamp` or
+    /// if no [`Item`] was generated up to that time.
+    pub fn pc_at(
+        &self,
+        packets: &[UnitPacket<U>],
+        index: &Index,
+        hart: u64,
+        timestamp: u64,
+    ) -> Result<Option<Occurrence<I>>, Error<B::Error>> {
+        let Some(start) = index.before(hart, timestamp) else {
+            return Ok(None);
+        };
+        let mut last = None;
+        self.retrace(packets, hart, start, Some(timestamp), |o| {
+            last = Some(o);
+            ControlFlow::Continue(())
+        })?;
+        Ok(last)
+    }
+
+    /// Find all executions of an address by a hart within a time range
+    ///
+    /// Re-traces the packets of the given hart with a new [`Tracer`], starting
+    /// at the last sync point in the [`Index`] at or before the start of the
+    /// `range` or at the hart's first sync point. All [`Item`]s signalling the
+    /// retirement of the instruction at `pc` generated from packets with a
+    /// timestamp within the `range` are returned in the order in which they
+    /// were generated. The `packets` are expected to be the ones the [`Index`]
+    /// was created from.
+    pub fn occurrences_of(
+        &self,
+        packets: &[UnitPacket<U>],
+        index: &Index,
+        hart: u64,
+        pc: u64,
+        range: Range<u64>,
+    ) -> Result<Vec<Occurrence<I>>, Error<B::Error>> {
+        let start = index
+            .before(hart, range.start)
+            .or_else(|| index.points(hart).first().copied());
+        let mut res = Vec::new();
+        let Some(start) = start.filter(|p| p.timestamp < Some(range.end)) else {
+            return Ok(res);
+        };
+        let end = range.end.checked_sub(1);
+        self.retrace(packets, hart, start, end, |o| {
+            let hit = o.item.pc() == pc && o.item.instruction().is_some();
+            if hit && o.timestamp.is_some_and(|t| range.contains(&t)) {
+                res.push(o)
+            }
+            ControlFlow::Continue(())
+        })?;
+        Ok(res)
+    }
+
+    /// Extract all executions of a function by a hart within a time range
+    ///
+    /// Re-traces the packets of the given hart like [`occurrences_of`]. Each
+    /// entry into the `function`, given as an address range, at a time within
+    /// the `range` results in a [`Slice`] containing the items within the
+    /// function as well as up to `context` items before the entry and after
+    /// the exit. The `packets` are expected to be the ones the [`Index`] was
+    /// created from.
+    ///
+    /// [`occurrences_of`]: Self::occurrences_of
+    pub fn slices(
+        &self,
+        packets: &[UnitPacket<U>],
+        index: &Index,
+        hart: u64,
+        function: Range<u64>,
+        context: usize,
+        range: Range<u64>,
+    ) -> Result<Vec<Slice<I>>, Error<B::Error>> {
+        let start = index
+            .before(hart, range.start)
+            .or_else(|| index.points(hart).first().copied());
+        let Some(start) = start.filter(|p| p.timestamp < Some(range.end)) else {
+            return Ok(Vec::new());
+        };
+        let mut slicer = index::Slicer::new(function, context);
+        self.retrace(packets, hart, start, Some(u64::MAX), |o| {
+            let entry = o.timestamp.is_some_and(|t| range.contains(&t));
+            let late = o.timestamp.is_some_and(|t| t >= range.end);
+            slicer.process(o, entry);
+            if late && slicer.is_idle() {
+                ControlFlow::Break(())
+            } else {
+                ControlFlow::Continue(())
+            }
+        })?;
+        Ok(slicer.into_slices())
+    }
+
+    /// Re-trace the packets of a hart from the given sync point
+    ///
+    /// Packets are processed up to and including the last one with a timestamp
+    /// not later than `end`. The callback is invoked for every [`Item`].
+    /// Re-tracing stops early after the packet for which the callback returned
+    /// [`ControlFlow::Break`].
+    fn retrace(
+        &self,
+        packets: &[UnitPacket<U>],
+        hart: u64,
+        start: index::Point,
+        end: Option<u64>,
+        mut f: impl FnMut(Occurrence<I>) -> ControlFlow<()>,
+    ) -> Result<(), Error<B::Error>> {
+        let mut tracer = self.build_tracer(hart)?;
+        let mut time = start.timestamp;
+        for (packet, p) in packets.iter().enumerate().skip(start.packet) {
+            time = p.timestamp.or(time);
+            if time > end {
+                break;
+            }
+            if p.hart != hart {
+                continue;
+            }
+            tracer
+                .process_payload(&p.payload)
+                .map_err(|e| Error::Tracer(hart, e))?;
+            let mut flow = ControlFlow::Continue(());
+            tracer.try_for_each(|i| {
+                i.map(|item| {
+                    let occurrence = Occurrence {
+                        packet,
+                        timestamp: time,
+                        item,
+                    };
+                    if f(occurrence).is_break() {
+                        flow = ControlFlow::Break(());
+                    }
+                })
+                .map_err(|e| Error::Tracer(hart, e))
+            })?;
+            if flow.is_break() {
+                break;
+            }
+        }
+        Ok(())
+    }
+
+    /// Set the source of trace data subsequently processed
+    ///
+    /// The source identifies e.g. the funnel from which trace data originates.
+    /// It is used for mapping hart indices via the session's
+    /// [`Topology`][topology::Topology]. The initial source is `0`.
+    pub fn set_source(&mut self, source: u64) {
+        self.source = source;
+    }
+
+    /// Retrieve the current source of trace data
+    pub fn source(&self) -> u64 {
+        self.source
+    }
+
+    /// Retrieve the [`Topology`][topology::Topology], if any
+    pub fn topology(&self) -> Option<&topology::Topology> {
+        self.topology.as_ref()
+    }
+
+    /// Map the hart indices of the given packets to global hart ids
+    fn map_harts(&self, packets: &mut [UnitPacket<U>]) -> Result<(), Error<B::Error>> {
+        let Some(topology) = self.topology.as_ref() else {
+            return Ok(());
+        };
+        packets.iter_mut().try_for_each(|p| {
+            p.hart = topology
+                .global(self.source, p.hart)
+                .ok_or(Error::UnmappedHart(self.source, p.hart))?;
+            Ok(())
+        })
+    }
+
+    /// Normalize the timestamps of the given packets
+    ///
+    /// Timestamps are only normalized if the session was built with a
+    /// [`Timestamping`][Builder::with_timestamping].
+    fn extend_timestamps(&mut self, packets: &mut [UnitPacket<U>]) {
+        let Some(timestamping) = self.timestamping.as_ref() else {
+            return;
+        };
+        packets.iter_mut().for_each(|p| {
+            if let Some(timestamp) = p.timestamp {
+                let last = self.timestamps.get(&p.hart).copied();
+                let timestamp = timestamping.normalize(p.hart, last, timestamp);
+                self.timestamps.insert(p.hart, timestamp);
+                p.timestamp = Some(timestamp);
+            }
+        });
+    }
+
+    /// Determine whether the given global hart id belongs to the current source
+    fn is_from_source(&self, hart: u64) -> bool {
+        self.topology
+            .as_ref()
+            .is_none_or(|t| t.local(hart).is_some_and(|(s, _)| s == self.source))
+    }
+
+    /// Retrieve the [`Tracer`] for the given hart, if one exists
+    pub fn tracer(&self, hart: u64) -> Option<&Tracer<B, S, I>> {
+        self.harts.get(&hart)
+    }
+
+    /// Retrieve the [`Tracer`] for the given hart, creating it if necessary
+    pub fn tracer_mut(&mut self, hart: u64) -> Result<&mut Tracer<B, S, I>, Error<B::Error>> {
+        use alloc::collections::btree_map::Entry;
+
+        match self.harts.entry(hart) {
+            Entry::Occupied(e) => Ok(e.into_mut()),
+            Entry::Vacant(e) => tracer_builder(&self.tracer, &self.region_bases, hart)
+                .build()
+                .map(|t| e.insert(t))
+                .map_err(|e| Error::Tracer(hart, e)),
+        }
+    }
+
+    /// Build a new [`Tracer`] for the given hart
+    fn build_tracer(&self, hart: u64) -> Result<Tracer<B, S, I>, Error<B::Error>> {
+        tracer_builder(&self.tracer, &self.region_bases, hart)
+            .build()
+            .map_err(|e| Error::Tracer(hart, e))
+    }
+
+    /// Set the region base applied to absolute addresses of the given hart
+    ///
+    /// The base applies to all packets of the hart processed subsequently,
+    /// including those re-traced e.g. via [`post_mortem`][Self::post_mortem].
+    /// See [`tracer::Builder::with_region_base`] for details.
+    pub fn set_region_base(&mut self, hart: u64, base: u64) {
+        self.region_bases.insert(hart, base);
+        if let Some(tracer) = self.harts.get_mut(&hart) {
+            tracer.set_region_base(base);
+        }
+    }
+
+    /// Retrieve the region base set for the given hart, if any
+    ///
+    /// Harts for which no base was [set][Self::set_region_base] use the base
+    /// of the [`tracer::Builder`] the session was built with.
+    pub fn region_base(&self, hart: u64) -> Option<u64> {
+        self.region_bases.get(&hart).copied()
+    }
+
+    /// Retrieve the [`lifecycle::State`] of the given hart
+    pub fn lifecycle(&self, hart: u64) -> lifecycle::State {
+        self.lifecycle.get(&hart).copied().unwrap_or_default()
+    }
+
+    /// Retrieve and clear the [`lifecycle::Transition`]s recorded so far
+    ///
+    /// Transitions are recorded in the order in which they occured while
+    /// processing packets and segments.
+    pub fn take_transitions(&mut self) -> Vec<lifecycle::Transition> {
+        core::mem::take(&mut self.transitions)
+    }
+
+    /// Record a transition of the given hart to the given state
+    ///
+    /// Nothing is recorded if the hart is already in the given state.
+    fn transition(&mut self, hart: u64, timestamp: Option<u64>, to: lifecycle::State) {
+        let from = self.lifecycle.insert(hart, to).unwrap_or_default();
+        if from == lifecycle::State::Asleep
+            && to != from
+            && let Some((start, cause)) = self.drowsy.remove(&hart)
+        {
+            self.sleeps.push(lifecycle::Sleep {
+                hart,
+                start,
+                end: timestamp,
+                cause,
+            });
+        }
+        if from != to {
+            self.transitions.push(lifecycle::Transition {
+                hart,
+                timestamp,
+                from,
+                to,
+            });
+        }
+    }
+
+    /// Let the given hart fall asleep at the given time
+    ///
+    /// The hart transitions to [`lifecycle::State::Asleep`] and a
+    /// [`lifecycle::Sleep`] is opened, which is recorded once the hart leaves
+    /// that state.
+    fn fall_asleep(&mut self, hart: u64, timestamp: Option<u64>, cause: lifecycle::SleepCause) {
+        self.drowsy.insert(hart, (timestamp, cause));
+        self.transition(hart, timestamp, lifecycle::State::Asleep);
+    }
+
+    /// Retrieve and clear the [`lifecycle::Sleep`] intervals recorded so far
+    ///
+    /// Intervals are recorded once the hart wakes up, i.e. leaves the
+    /// [`lifecycle::State::Asleep`]. Harts still asleep are not reported.
+    pub fn take_sleeps(&mut self) -> Vec<lifecycle::Sleep> {
+        core::mem::take(&mut self.sleeps)
+    }
+
+    /// Retrieve and clear the [`isa::Mismatch`]es recorded so far
+    ///
+    /// A mismatch is recorded at most once for every change of the
+    /// [`base::Set`] advertised for a hart.
+    pub fn take_isa_mismatches(&mut self) -> Vec<isa::Mismatch> {
+        core::mem::take(&mut self.isa_mismatches)
+    }
+
+    /// Compare the [`base::Set`] advertised for a hart with its [`Binary`]'s
+    ///
+    /// An [`isa::Mismatch`] is recorded if the sets differ and the advertised
+    /// set changed since the last support packet for the hart.
+    fn reconcile_isa(
+        &mut self,
+        hart: u64,
+        timestamp: Option<u64>,
+        advertised: base::Set,
+    ) -> Result<(), Error<B::Error>> {
+        if self.advertised.insert(hart, advertised) == Some(advertised) {
+            return Ok(());
+        }
+        let binary = self.tracer_mut(hart)?.binary().base_set();
+        if let Some(binary) = binary.filter(|b| *b != advertised) {
+            self.isa_mismatches.push(isa::Mismatch {
+                hart,
+                timestamp,
+                advertised,
+                binary,
+            });
+        }
+        Ok(())
+    }
+
+    /// Retrieve the indices of all harts encountered so far
+    pub fn harts(&self) -> impl Iterator<Item = u64> + '_ {
+        self.harts.keys().copied()
+    }
+
+    /// Retrieve the [`Transport`] of packets
+    pub fn transport(&self) -> &T {
+        &self.transport
+    }
+
+    /// Retrieve the [`repro::Buffer`] retaining raw packets, if any
+    ///
+    /// A buffer is only present if the session was built with a
+    /// [repro window][Builder::with_repro_window].
+    pub fn repro_buffer(&self) -> Option<&repro::Buffer> {
+        self.repro.as_ref()
+    }
+}
+
+impl<U, B, S, I> Session<U, B, S, I, Framing>
+where
+    B: Binary<I>,
+    S: ReturnStack,
+    I: Info + Clone,
+{
+    /// Retrieve the [`Framing`] of packets
+    pub fn framing(&self) -> Framing {
+        self.transport
+    }
+
+    /// Create a [`repro::Repro`] for the given hart
+    ///
+    /// The [`repro::Repro`] holds the raw packets retained for the hart along
+    /// with the given [`config::Parameters`] and the decoder configuration of
+    /// this session. The parameters are expected to be the ones the session
+    /// was built with.
+    ///
+    /// Returns `None` if the session was not built with a
+    /// [repro window][Builder::with_repro_window] or if no packets were
+    /// retained for the hart.
+    pub fn repro(&self, hart: u64, params: &config::Parameters) -> Option<repro::Repro> {
+        let data = self.repro.as_ref()?.data(hart);
+        if data.is_empty() {
+            return None;
+        }
+        Some(repro::Repro {
+            params: *params,
+            framing: self.transport,
+            hart_index_width: self.decoder.hart_index_width(),
+            timestamp_width: self.decoder.timestamp_width(),
+            trace_type_width: self.decoder.trace_type_width(),
+            address_shift: self.decoder.address_shift(),
+            hart,
+            data,
+        })
+    }
+}
+
+/// Create a new [`Builder`] for [`Session`]s
+pub fn builder() -> Builder {
+    Default::default()
+}
+
+/// Builder for [`Session`]s
+#[derive(Clone, Default)]
+pub struct Builder<U = unit::Reference, B = binary::Empty, T = Framing> {
+    decoder: packet::Builder<U>,
+    tracer: tracer::Builder<B>,
+    transport: T,
+    topology: Option<topology::Topology>,
+    timestamping: Option<Arc<dyn Timestamping + Send + Sync>>,
+    filter: Option<Filter>,
+    repro_window: Option<usize>,
+    sleep_threshold: Option<u64>,
+}
+
+impl Builder {
+    /// Create a new builder for a [`Session`]
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl<U, B, T> Builder<U, B, T> {
+    /// Build the [`Session`] for encoders with the given [`config::Parameters`]
+    ///
+    /// The parameters are applied to both the [`packet::Builder`] and the
+    /// [`tracer::Builder`] used by the [`Session`].
+    pub fn with_params(self, params: &config::Parameters) -> Self {
+        Self {
+            decoder: self.decoder.with_params(params),
+            tracer: self.tracer.with_params(params),
+            ..self
+        }
+    }
+
+    /// Build the [`Session`] with the given [`packet::Builder`]
+    ///
+    /// New builders carry a [`Default`] [`packet::Builder`].
+    pub fn with_decoder<V>(self, decoder: packet::Builder<V>) -> Builder<V, B, T> {
+        Builder {
+            decoder,
+            tracer: self.tracer,
+            transport: self.transport,
+            topology: self.topology,
+            timestamping: self.timestamping,
+            filter: self.filter,
+            repro_window: self.repro_window,
+            sleep_threshold: self.sleep_threshold,
+        }
+    }
+
+    /// Build the [`Session`] with the given [`tracer::Builder`]
+    ///
+    /// New builders carry a [`Default`] [`tracer::Builder`].
+    pub fn with_tracer<C>(self, tracer: tracer::Builder<C>) -> Builder<U, C, T> {
+        Builder {
+            decoder: self.decoder,
+            tracer,
+            transport: self.transport,
+            topology: self.topology,
+            timestamping: self.timestamping,
+            filter: self.filter,
+            repro_window: self.repro_window,
+            sleep_threshold: self.sleep_threshold,
+        }
+    }
+
+    /// Build the [`Session`] with the given [`Binary`]
+    ///
+    /// The [`Binary`] is cloned for every [`Tracer`] created by the
+    /// [`Session`].
+    pub fn with_binary<C>(self, binary: C) -> Builder<U, C, T> {
+        Builder {
+            decoder: self.decoder,
+            tracer: self.tracer.with_binary(binary),
+            transport: self.transport,
+            topology: self.topology,
+            timestamping: self.timestamping,
+            filter: self.filter,
+            repro_window: self.repro_window,
+            sleep_threshold: self.sleep_threshold,
+        }
+    }
+
+    /// Build the [`Session`] for the given [`Framing`]
+    ///
+    /// New builders are configured for [`Framing::Encap`].
+    pub fn with_framing(self, framing: Framing) -> Builder<U, B> {
+        self.with_transport(framing)
+    }
+
+    /// Build the [`Session`] with the given [`Transport`]
+    ///
+    /// This fn allows using framings not covered by [`Framing`].
+    pub fn with_transport<V>(self, transport: V) -> Builder<U, B, V> {
+        Builder {
+            decoder: self.decoder,
+            tracer: self.tracer,
+            transport,
+            topology: self.topology,
+            timestamping: self.timestamping,
+            filter: self.filter,
+            repro_window: self.repro_window,
+            sleep_threshold: self.sleep_threshold,
+        }
+    }
+
+    /// Build the [`Session`] with the given [`Topology`][topology::Topology]
+    ///
+    /// New builders carry no topology, i.e. hart indices in packets are used
+    /// as hart ids directly.
+    pub fn with_topology(self, topology: topology::Topology) -> Self {
+        Self {
+            topology: Some(topology),
+            ..self
+        }
+    }
+
+    /// Build the [`Session`] with timestamp rollover of the given width
+    ///
+    /// If set, timestamps of packets are assumed to be truncated to the given
+    /// width in bits. Before packets are [sorted by time][sort_by_time], their
+    /// timestamps are extended to absolute ones via an [`encap::Rollover`]
+    /// per hart. This is a shorthand for [`with_timestamping`] with an
+    /// [`encap::Rollover`].
+    ///
+    /// [`with_timestamping`]: Self::with_timestamping
+    pub fn with_timestamp_rollover(self, width: u8) -> Self {
+        self.with_timestamping(encap::Rollover::new(width))
+    }
+
+    /// Build the [`Session`] with the given [`Timestamping`]
+    ///
+    /// If set, timestamps of packets are normalized via the [`Timestamping`]
+    /// before packets are [sorted by time][sort_by_time] or otherwise compared.
+    /// New builders are configured for no [`Timestamping`], i.e. timestamps are
+    /// used as is.
+    pub fn with_timestamping(
+        self,
+        timestamping: impl Timestamping + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            timestamping: Some(Arc::new(timestamping)),
+            ..self
+        }
+    }
+
+    /// Build the [`Session`] with the given [`Filter`]
+    ///
+    /// If set, only [`Item`]s matching the filter are passed to callbacks.
+    /// New builders carry no filter.
+    pub fn with_filter(self, filter: Filter) -> Self {
+        Self {
+            filter: Some(filter),
+            ..self
+        }
+    }
+
+    /// Build the [`Session`] inferring sleep from silence after a `wfi`
+    ///
+    /// If set, a hart whose last retired instruction was a `wfi` is considered
+    /// [asleep][lifecycle::State::Asleep] between two of its packets at least
+    /// `ticks` apart. New builders only infer sleep from the end of
+    /// qualification after a `wfi`.
+    pub fn with_sleep_threshold(self, ticks: u64) -> Self {
+        Self {
+            sleep_threshold: Some(ticks),
+            ..self
+        }
+    }
+
+    /// Build the [`Session`] retaining raw packets for reproducing failures
+    ///
+    /// If set, the session retains the raw data of the last `packets` packets
+    /// of each hart processed via [`Session::for_each_item`] in a
+    /// [`repro::Buffer`]. After a failure, the packets retained may be dumped
+    /// via [`Session::repro`]. New builders do not retain any packets.
+    pub fn with_repro_window(self, packets: usize) -> Self {
+        Self {
+            repro_window: Some(packets),
+            ..self
+        }
+    }
+
+    /// Build the [`Session`]
+    pub fn build<S, I>(self) -> Session<U, B, S, I, T>
+    where
+        B: Binary<I>,
+        S: ReturnStack,
+        I: Info + Clone,
+    {
+        Session {
+            decoder: self.decoder,
+            tracer: self.tracer,
+            transport: self.transport,
+            harts: Default::default(),
+            region_bases: Default::default(),
+            topology: self.topology,
+            source: 0,
+            carry: Default::default(),
+            suspect: Default::default(),
+            lifecycle: Default::default(),
+            transitions: Default::default(),
+            wfi: Default::default(),
+            last_seen: Default::default(),
+            sleep_threshold: self.sleep_threshold,
+            drowsy: Default::default(),
+            sleeps: Default::default(),
+            advertised: Default::default(),
+            isa_mismatches: Default::default(),
+            timestamping: self.timestamping,
+            timestamps: Default::default(),
+            filter: self.filter,
+            repro: self.repro_window.map(repro::Buffer::new),
+        }
+    }
+}
+
+/// Create a [`tracer::Builder`] for the given hart
+///
+/// The region base set for the hart, if any, overrides the one of the given
+/// builder.
+fn tracer_builder<B: Clone>(
+    builder: &tracer::Builder<B>,
+    region_bases: &BTreeMap<u64, u64>,
+    hart: u64,
+) -> tracer::Builder<B> {
+    let builder = builder.clone();
+    match region_bases.get(&hart) {
+        Some(base) => builder.with_region_base(*base),
+        None => builder,
+    }
+}
+
+/// Decode [`Packet`]s from a segment of trace data
+///
+/// Returns the decoded packets and the data of a trailing packet cut at the
+/// end of the segment.
+fn decode_segment<'d, U: unit::Unit + Clone>(
+    decoder: &packet::Builder<U>,
+    transport: &mut impl Transport<U>,
+    data: &'d [u8],
+) -> Result<(Vec<UnitPacket<U>>, &'d [u8]), packet::Error> {
+    let mut decoder = decoder.clone().decoder(data);
+    let mut packets = Vec::new();
+    while decoder.bytes_left() > 0 {
+        let rest = decoder.remaining_data();
+        match transport.next_packet(&mut decoder) {
+            Ok(Some(packet)) => packets.push(packet),
+            Ok(None) => (),
+            Err(packet::Error::InsufficientData(_)) => return Ok((packets, rest)),
+            Err(e) => return Err(e),
+        }
+    }
+    Ok((packets, Default::default()))
+}
+
+/// Determine whether decoding may be resynchronized at the start of `data`
+///
+/// Returns `true` if [`segment::RESYNC_LOOKAHEAD`] consecutive packets, or
+/// all packets up to the end of `data`, decode without error and at least one
+/// of them carries a payload.
+fn is_sync_offset<U: unit::Unit + Clone>(
+    decoder: &packet::Builder<U>,
+    mut transport: impl Transport<U>,
+    data: &[u8],
+) -> bool {
+    let mut decoder = decoder.clone().decoder(data);
+    let mut payload = false;
+    for _ in 0..segment::RESYNC_LOOKAHEAD {
+        if decoder.bytes_left() == 0 {
+            break;
+        }
+        match transport.next_packet(&mut decoder) {
+            Ok(packet) => payload |= packet.is_some(),
+            Err(packet::Error::InsufficientData(_)) => break,
+            Err(_) => return false,
+        }
+    }
+    payload
+}
+
+/// Determine whether tracing may be (re)started from the given payload
+fn is_resync_point<I, D>(payload: &payload::Payload<I, D>) -> bool {
+    use packet::sync::Synchronization;
+
+    match payload.as_instruction_trace() {
+        Some(payload::InstructionTrace::Synchronization(Synchronization::Start(_))) => true,
+        Some(payload::InstructionTrace::Synchronization(Synchronization::Trap(t))) => t.thaddr,
+        _ => false,
+    }
+}
+
+/// Sort [`Packet`]s by time
+///
+/// Packets are ordered by their timestamp, with packets without a timestamp
+/// being considered to share the timestamp of the closest preceeding packet
+/// with a timestamp. Packets of the same hart are never reordered: the packets
+/// of each hart are kept in a queue in their original order and the queues are
+/// merged by the timestamp of their first packet. Thus, timestamps wrapping
+/// around or otherwise decreasing only affect the interleaving of harts. The
+/// order of packets with the same timestamp is preserved.
+pub fn sort_by_time<I, D>(packets: &mut Vec<Packet<I, D>>) {
+    sort_keyed_by_time(packets, |p| p.hart, |p| p.timestamp)
+}
+
+/// Sort values by time
+///
+/// Values are sorted like by [`sort_by_time`], with the given fns retrieving
+/// the hart and the timestamp of a value.
+fn sort_keyed_by_time<T>(
+    values: &mut Vec<T>,
+    hart: impl Fn(&T) -> u64,
+    timestamp: impl Fn(&T) -> Option<u64>,
+) {
+    use alloc::collections::{BinaryHeap, VecDeque};
+    use core::cmp::Reverse;
+
+    let mut time = None;
+    let mut queues: BTreeMap<u64, VecDeque<_>> = BTreeMap::new();
+    values.drain(..).enumerate().for_each(|(n, v)| {
+        time = timestamp(&v).or(time);
+        queues.entry(hart(&v)).or_default().push_back((time, n, v));
+    });
+
+    let mut heads: BinaryHeap<_> = queues
+        .iter()
+        .filter_map(|(h, q)| q.front().map(|(t, n, _)| Reverse((*t, *n, *h))))
+        .collect();
+    while let Some(Reverse((_, _, h))) = heads.pop() {
+        let Some(queue) = queues.get_mut(&h) else {
+            continue;
+        };
+        values.extend(queue.pop_front().map(|(_, _, v)| v));
+        if let Some((t, n, _)) = queue.front() {
+            heads.push(Reverse((*t, *n, h)));
+        }
+    }
+}
@@ -0,0 +1,55 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Session specific errors
+
+use core::fmt;
+
+use crate::packet;
+use crate::tracer;
+
+/// Session specific errors
+#[derive(Debug, PartialEq)]
+#[non_exhaustive]
+pub enum Error<B> {
+    /// A packet could not be decoded
+    Packet(packet::Error),
+    /// An error occured while tracing the given hart
+    Tracer(u64, tracer::error::Error<B>),
+    /// The hart index of the given source is not mapped to a hart id
+    UnmappedHart(u64, u64),
+    /// The [`Binary`][crate::binary::Binary] could not be loaded
+    Binary(B),
+}
+
+impl<B> core::error::Error for Error<B>
+where
+    B: fmt::Debug + core::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Packet(inner) => Some(inner),
+            Self::Tracer(_, inner) => Some(inner),
+            Self::UnmappedHart(_, _) => None,
+            Self::Binary(inner) => Some(inner),
+        }
+    }
+}
+
+impl<B> fmt::Display for Error<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Packet(_) => write!(f, "could not decode packet"),
+            Self::Tracer(hart, _) => write!(f, "error while tracing hart {hart}"),
+            Self::UnmappedHart(source, hart) => {
+                write!(f, "hart {hart} of source {source} is not mapped")
+            }
+            Self::Binary(_) => write!(f, "could not load binary"),
+        }
+    }
+}
+
+impl<B> From<packet::Error> for Error<B> {
+    fn from(inner: packet::Error) -> Self {
+        Self::Packet(inner)
+    }
+}
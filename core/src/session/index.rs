@@ -0,0 +1,208 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Time-based queries on decoded trace data
+//!
+//! Interactive debugging tools often need to answer questions such as "where
+//! was the hart at time T?" or "when was this address executed?" without
+//! re-tracing an entire capture. An [`Index`] records, for each hart, the
+//! packets from which tracing can be (re)started along with their timestamps.
+//! [`Session::pc_at`] and [`Session::occurrences_of`] use it for re-tracing
+//! only the packets between the closest preceeding sync point and the time of
+//! interest. [`Session::slices`] extracts [`Slice`]s of the execution of a
+//! specific function for sharing minimal reproductions of misbehavior.
+//!
+//! Like for [`Session::post_mortem`], packets are expected in the order in
+//! which they would be processed, e.g. [sorted by time][super::sort_by_time].
+//! Packets without a timestamp are considered to share the timestamp of the
+//! closest preceeding packet with a timestamp.
+//!
+//! [`Session::pc_at`]: super::Session::pc_at
+//! [`Session::occurrences_of`]: super::Session::occurrences_of
+//! [`Session::slices`]: super::Session::slices
+//! [`Session::post_mortem`]: super::Session::post_mortem
+
+use alloc::collections::{BTreeMap, VecDeque};
+use alloc::vec::Vec;
+use core::ops::Range;
+
+use crate::instruction::{self, info::Info};
+use crate::tracer::item::Item;
+use crate::transport::Packet;
+
+/// Index of the sync points in a sequence of [`Packet`]s
+#[derive(Clone, Debug, Default)]
+pub struct Index {
+    points: BTreeMap<u64, Vec<Point>>,
+}
+
+impl Index {
+    /// Create an index for the given packets
+    pub fn new<I, D>(packets: &[Packet<I, D>]) -> Self {
+        let mut points: BTreeMap<u64, Vec<Point>> = BTreeMap::new();
+        let mut time = None;
+        for (packet, p) in packets.iter().enumerate() {
+            time = p.timestamp.or(time);
+            if super::is_resync_point(&p.payload) {
+                points.entry(p.hart).or_default().push(Point {
+                    packet,
+                    timestamp: time,
+                });
+            }
+        }
+        Self { points }
+    }
+
+    /// Retrieve all sync points of the given hart
+    pub fn points(&self, hart: u64) -> &[Point] {
+        self.points
+            .get(&hart)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Retrieve the last sync point of the given hart at or before a time
+    pub fn before(&self, hart: u64, timestamp: u64) -> Option<Point> {
+        let points = self.points(hart);
+        let n = points.partition_point(|p| p.timestamp <= Some(timestamp));
+        n.checked_sub(1).map(|n| points[n])
+    }
+
+    /// Retrieve the indices of all harts with at least one sync point
+    pub fn harts(&self) -> impl Iterator<Item = u64> + '_ {
+        self.points.keys().copied()
+    }
+}
+
+/// A point from which tracing can be (re)started
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Point {
+    /// Index of the packet
+    pub packet: usize,
+    /// Timestamp of the packet, if known
+    pub timestamp: Option<u64>,
+}
+
+/// An [`Item`] found by a query
+#[derive(Clone, Debug, PartialEq)]
+pub struct Occurrence<I: Info = Option<instruction::Kind>> {
+    /// Index of the packet from which the item was generated
+    pub packet: usize,
+    /// Timestamp of the packet from which the item was generated, if known
+    pub timestamp: Option<u64>,
+    /// The item itself
+    pub item: Item<I>,
+}
+
+/// Items of a single invocation of a function, with surrounding context
+///
+/// Created via [`Session::slices`][super::Session::slices]. The
+/// [`items`][Self::items] consist of [`before`][Self::before] items preceeding
+/// the entry into the function, all items within the function and
+/// [`after`][Self::after] items following the exit from the function. Items
+/// of callees located outside the function are not included.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Slice<I: Info = Option<instruction::Kind>> {
+    /// Items, including context
+    pub items: Vec<Occurrence<I>>,
+    /// Number of context items preceeding the entry into the function
+    pub before: usize,
+    /// Number of context items following the exit from the function
+    pub after: usize,
+}
+
+/// Generator of [`Slice`]s from a stream of [`Occurrence`]s
+///
+/// A function is considered exited when a return instruction within its
+/// address range is followed by an item outside of it.
+#[derive(Clone, Debug)]
+pub(super) struct Slicer<I: Info> {
+    function: Range<u64>,
+    context: usize,
+    recent: VecDeque<Occurrence<I>>,
+    current: Option<Slice<I>>,
+    exited: bool,
+    returning: bool,
+    slices: Vec<Slice<I>>,
+}
+
+impl<I: Info + Clone> Slicer<I> {
+    /// Create a new slicer for the given function and number of context items
+    pub fn new(function: Range<u64>, context: usize) -> Self {
+        Self {
+            function,
+            context,
+            recent: VecDeque::with_capacity(context),
+            current: None,
+            exited: false,
+            returning: false,
+            slices: Vec::new(),
+        }
+    }
+
+    /// Process an [`Occurrence`]
+    ///
+    /// Only items with `entry` set may start a new [`Slice`].
+    pub fn process(&mut self, occurrence: Occurrence<I>, entry: bool) {
+        let inside = self.function.contains(&occurrence.item.pc());
+        if let Some(slice) = self.current.as_mut() {
+            if (self.exited && inside) || (!inside && self.returning && self.context == 0) {
+                self.finish();
+            } else if self.exited || (!inside && self.returning) {
+                self.exited = true;
+                slice.items.push(occurrence.clone());
+                slice.after += 1;
+                if slice.after >= self.context {
+                    self.finish();
+                }
+            } else if inside {
+                self.returning = occurrence
+                    .item
+                    .instruction()
+                    .is_some_and(|i| i.info.is_return());
+                slice.items.push(occurrence.clone());
+            }
+        }
+
+        if self.current.is_none() && inside && entry {
+            let mut items: Vec<_> = self.recent.iter().cloned().collect();
+            let before = items.len();
+            self.returning = occurrence
+                .item
+                .instruction()
+                .is_some_and(|i| i.info.is_return());
+            items.push(occurrence.clone());
+            self.current = Some(Slice {
+                items,
+                before,
+                after: 0,
+            });
+        }
+
+        if self.context > 0 {
+            if self.recent.len() >= self.context {
+                self.recent.pop_front();
+            }
+            self.recent.push_back(occurrence);
+        }
+    }
+
+    /// Check whether no [`Slice`] is currently being generated
+    pub fn is_idle(&self) -> bool {
+        self.current.is_none()
+    }
+
+    /// Finish slicing, returning all [`Slice`]s
+    ///
+    /// A [`Slice`] which is not yet finished is included.
+    pub fn into_slices(mut self) -> Vec<Slice<I>> {
+        self.finish();
+        self.slices
+    }
+
+    /// Finish the current [`Slice`], if any
+    fn finish(&mut self) {
+        self.slices.extend(self.current.take());
+        self.exited = false;
+        self.returning = false;
+    }
+}
@@ -0,0 +1,54 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Reconciliation of advertised instruction sets
+//!
+//! Some trace units advertise the ISA of the traced hart in proprietary fields
+//! of support packets, exposed via [`IOptions::base_set`]. Tracing with a
+//! [`Binary`] decoding instructions for a different [`base::Set`] usually
+//! results in subtly wrong instruction sizes and branch targets rather than in
+//! an outright error. A [`Session`][super::Session] thus compares the
+//! [`base::Set`] advertised with the one of the hart's [`Binary`] and records
+//! a [`Mismatch`] if they disagree. Mismatches may be retrieved via
+//! [`Session::take_isa_mismatches`][super::Session::take_isa_mismatches].
+//!
+//! [`IOptions::base_set`]: crate::packet::unit::IOptions::base_set
+//! [`Binary`]: crate::binary::Binary
+
+use core::fmt;
+
+use crate::instruction::base;
+
+/// Disagreement between the advertised and the [`Binary`][b]'s [`base::Set`]
+///
+/// [b]: crate::binary::Binary
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Mismatch {
+    /// Id of the hart
+    pub hart: u64,
+    /// Timestamp of the support packet advertising the set, if present
+    pub timestamp: Option<u64>,
+    /// [`base::Set`] advertised by the encoder
+    pub advertised: base::Set,
+    /// [`base::Set`] of the [`Binary`][crate::binary::Binary]
+    pub binary: base::Set,
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "hart {} advertised as {}, but binary is {}",
+            self.hart,
+            name(self.advertised),
+            name(self.binary),
+        )
+    }
+}
+
+/// Retrieve the conventional name of the given [`base::Set`]
+fn name(set: base::Set) -> &'static str {
+    match set {
+        base::Set::Rv32I => "RV32I",
+        base::Set::Rv64I => "RV64I",
+    }
+}
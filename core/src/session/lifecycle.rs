@@ -0,0 +1,156 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Lifecycle of traced harts
+//!
+//! Over the course of a trace, a hart may become traced, be filtered, lose
+//! trace data and stop being traced. This module provides the [`State`]
+//! modelling this lifecycle and the [`Transition`]s between states, which are
+//! recorded by a [`Session`][super::Session] and may be retrieved via
+//! [`Session::take_transitions`][super::Session::take_transitions]. They allow
+//! e.g. rendering per-hart activity lanes.
+//!
+//! Harts executing a `wfi` usually stop retiring instructions until they are
+//! woken by an interrupt. Encoders then either end qualification or simply
+//! stop emitting packets. A [`Session`][super::Session] combines these hints
+//! into [`Sleep`] intervals, which may be retrieved via
+//! [`Session::take_sleeps`][super::Session::take_sleeps].
+
+use core::fmt;
+
+use crate::packet::payload::{InstructionTrace, Payload};
+use crate::packet::sync::{QualStatus, Synchronization};
+
+/// Lifecycle state of a single hart
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum State {
+    /// No packet from which tracing can be started was received yet
+    #[default]
+    NotTraced,
+    /// The hart is being traced
+    Active,
+    /// Tracing is paused because the hart's execution is filtered
+    ///
+    /// This state is entered if the encoder reports that qualification ended.
+    Paused,
+    /// Trace data of the hart was lost
+    ///
+    /// This state is entered if the encoder reports a loss of trace or if
+    /// trace data was lost between two segments.
+    Lost,
+    /// Tracing was disabled by the encoder
+    Ended,
+    /// The hart is presumably sleeping after executing a `wfi`
+    ///
+    /// This state is entered if the last instruction retired by the hart was a
+    /// `wfi` and either qualification ended or no packet was received for the
+    /// hart for some time. See [`SleepCause`] for details.
+    Asleep,
+}
+
+impl State {
+    /// Determine the state following this one after the given payload
+    ///
+    /// A hart [`Asleep`][Self::Asleep] becomes [`Active`][Self::Active] with
+    /// any instruction trace payload other than a support payload. Falling
+    /// asleep depends on the instructions retired and is not covered by this
+    /// fn.
+    pub fn next<I, D>(self, payload: &Payload<I, D>) -> Self {
+        match payload.as_instruction_trace() {
+            Some(InstructionTrace::Synchronization(sync)) => match sync {
+                Synchronization::Start(_) => Self::Active,
+                Synchronization::Trap(trap) if trap.thaddr => Self::Active,
+                Synchronization::Support(support) if !support.ienable => Self::Ended,
+                Synchronization::Support(support) => match support.qual_status {
+                    QualStatus::NoChange => self,
+                    QualStatus::TraceLost => Self::Lost,
+                    QualStatus::EndedRep | QualStatus::EndedNtr => Self::Paused,
+                },
+                _ => self,
+            },
+            Some(_) if self == Self::Asleep => Self::Active,
+            _ => self,
+        }
+    }
+}
+
+impl fmt::Display for State {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotTraced => write!(f, "not traced"),
+            Self::Active => write!(f, "active"),
+            Self::Paused => write!(f, "paused"),
+            Self::Lost => write!(f, "lost"),
+            Self::Ended => write!(f, "ended"),
+            Self::Asleep => write!(f, "asleep"),
+        }
+    }
+}
+
+/// Transition of a hart from one [`State`] to another
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Transition {
+    /// Id of the hart
+    pub hart: u64,
+    /// Timestamp of the packet causing the transition, if present
+    pub timestamp: Option<u64>,
+    /// State before the transition
+    pub from: State,
+    /// State after the transition
+    pub to: State,
+}
+
+/// Interval in which a hart was presumably sleeping
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Sleep {
+    /// Id of the hart
+    pub hart: u64,
+    /// Time at which the hart fell asleep, if known
+    pub start: Option<u64>,
+    /// Time at which the hart woke up, if known
+    pub end: Option<u64>,
+    /// [`SleepCause`] from which the interval was inferred
+    pub cause: SleepCause,
+}
+
+impl Sleep {
+    /// Retrieve the duration of this interval
+    ///
+    /// Returns `None` if either the start or end time is unknown or if the end
+    /// lies before the start.
+    pub fn duration(&self) -> Option<u64> {
+        self.end?.checked_sub(self.start?)
+    }
+}
+
+impl fmt::Display for Sleep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "hart {} asleep ({})", self.hart, self.cause)?;
+        if let Some(duration) = self.duration() {
+            write!(f, " for {duration}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Hint from which a [`Sleep`] interval was inferred
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum SleepCause {
+    /// Qualification ended after a `wfi`
+    ///
+    /// The interval starts with the support packet reporting the end of
+    /// qualification.
+    Unqualified,
+    /// No packet was received for the hart for some time after a `wfi`
+    ///
+    /// The interval starts with the last packet before the silence.
+    Silence,
+}
+
+impl fmt::Display for SleepCause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Unqualified => write!(f, "wfi, qualification ended"),
+            Self::Silence => write!(f, "wfi, no packets"),
+        }
+    }
+}
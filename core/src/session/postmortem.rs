@@ -0,0 +1,56 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Post-mortem analysis of tracing failures
+//!
+//! When tracing fails at some packet, the most common debugging approach is to
+//! re-trace the packets of the affected hart starting at the last point from
+//! which tracing could be (re)started, and to inspect the [`Item`]s and the
+//! [`Tracer`]'s state right before the failure. A [`Window`], created via
+//! [`Session::post_mortem`][super::Session::post_mortem], captures the result
+//! of that process.
+
+use alloc::vec::Vec;
+
+use crate::binary::Binary;
+use crate::instruction::{self, info::Info};
+use crate::tracer::{Tracer, error::Error, item::Item};
+use crate::types::stack::{self, ReturnStack};
+
+/// Result of re-tracing the packets preceeding a failure
+pub struct Window<B, S = stack::NoStack, I = Option<instruction::Kind>>
+where
+    B: Binary<I>,
+    S: ReturnStack,
+    I: Info,
+{
+    /// Hart traced
+    pub hart: u64,
+    /// Index of the packet from which tracing was started
+    pub start: usize,
+    /// Index of the packet at which tracing failed
+    ///
+    /// If tracing fails before reaching the packet for which the window was
+    /// requested, this index will differ from that packet's index. If tracing
+    /// does not fail at all, the index of that packet is reported.
+    pub end: usize,
+    /// [`Item`]s generated from the packet at [`start`][Self::start] up to the
+    /// failure
+    pub items: Vec<Item<I>>,
+    /// State of the [`Tracer`] before processing the packet at
+    /// [`end`][Self::end]
+    pub tracer: Tracer<B, S, I>,
+    /// Error encountered at the packet at [`end`][Self::end], if any
+    pub error: Option<Error<B::Error>>,
+}
+
+impl<B, S, I> Window<B, S, I>
+where
+    B: Binary<I>,
+    S: ReturnStack,
+    I: Info,
+{
+    /// Retrieve the PC of the last [`Item`] generated before the failure
+    pub fn last_pc(&self) -> Option<u64> {
+        self.items.last().map(Item::pc)
+    }
+}
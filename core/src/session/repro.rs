@@ -0,0 +1,336 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Reproduction files for tracing failures
+//!
+//! Tracing failures reported by users are notoriously hard to reproduce, since
+//! the trace data leading up to them is usually large or confidential. A
+//! [`Session`] built with a [repro window][super::Builder::with_repro_window]
+//! retains the raw data of the last few packets of each hart in a [`Buffer`].
+//! When tracing fails, [`Session::repro`] bundles the packets retained for the
+//! affected hart with the configuration required for decoding them into a
+//! [`Repro`]. A [`Repro`] may be [written][Repro::to_bytes] to a standalone
+//! file and later [loaded][Repro::from_bytes] for replaying the failure.
+//!
+//! The [`Binary`][crate::binary::Binary] and the trace
+//! [`Unit`][crate::packet::unit::Unit] are not part of a [`Repro`] and need to
+//! be supplied separately when replaying.
+//!
+//! # File format
+//!
+//! A repro file consists of the following fields, with multi-byte values
+//! being encoded little endian:
+//!
+//! | Size | Content                                                        |
+//! |------|----------------------------------------------------------------|
+//! | 8    | Magic bytes [`MAGIC`]                                          |
+//! | 1    | Format [`VERSION`]                                             |
+//! | 1    | Index of the [`Framing`] in [`Framing::ALL`]                   |
+//! | 13   | [`config::Parameters`], one byte per field, in order           |
+//! | 3    | Hart index, timestamp and trace type widths                    |
+//! | 2    | `1` and the address shift if overridden, `0` and `0` otherwise |
+//! | 8    | Hart id                                                        |
+//! | 8    | Length of the trace data                                       |
+//! | *    | Trace data                                                     |
+//!
+//! # Example
+//!
+//! ```
+//! use riscv_etrace_core::session::repro::Repro;
+//!
+//! let repro = Repro {
+//!     hart: 1,
+//!     data: vec![0x45, 0x73, 0x0a, 0x00, 0x00, 0x20, 0x41, 0x01],
+//!     ..Default::default()
+//! };
+//! let loaded = Repro::from_bytes(&repro.to_bytes()).unwrap();
+//! assert_eq!(loaded.hart, 1);
+//! assert_eq!(loaded.data, repro.data);
+//! ```
+//!
+//! [`Session`]: super::Session
+//! [`Session::repro`]: super::Session::repro
+
+use alloc::collections::{BTreeMap, VecDeque};
+use alloc::vec::Vec;
+use core::fmt;
+use core::num::NonZeroU8;
+
+use crate::config;
+use crate::packet;
+use crate::transport::Framing;
+
+/// Magic bytes at the start of every repro file
+pub const MAGIC: [u8; 8] = *b"RVETREPR";
+
+/// Version of the repro file format
+pub const VERSION: u8 = 1;
+
+/// Sliding window of raw packets per hart
+///
+/// The buffer retains the raw data of up to a fixed number of packets per
+/// hart, discarding the oldest packet when a new one is pushed.
+#[derive(Clone, Debug, Default)]
+pub struct Buffer {
+    capacity: usize,
+    harts: BTreeMap<u64, VecDeque<Vec<u8>>>,
+}
+
+impl Buffer {
+    /// Create a new buffer retaining up to `capacity` packets per hart
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            harts: Default::default(),
+        }
+    }
+
+    /// Retrieve the number of packets retained per hart
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Push the raw data of a packet for the given hart
+    pub fn push(&mut self, hart: u64, packet: &[u8]) {
+        if self.capacity == 0 {
+            return;
+        }
+        let packets = self.harts.entry(hart).or_default();
+        if packets.len() >= self.capacity {
+            packets.pop_front();
+        }
+        packets.push_back(packet.into());
+    }
+
+    /// Iterate over the raw packets retained for the given hart
+    ///
+    /// Packets are yielded in the order in which they were pushed.
+    pub fn packets(&self, hart: u64) -> impl Iterator<Item = &[u8]> + '_ {
+        self.harts
+            .get(&hart)
+            .into_iter()
+            .flatten()
+            .map(Vec::as_slice)
+    }
+
+    /// Retrieve the concatenated raw data retained for the given hart
+    pub fn data(&self, hart: u64) -> Vec<u8> {
+        self.packets(hart).flatten().copied().collect()
+    }
+
+    /// Discard all packets retained
+    pub fn clear(&mut self) {
+        self.harts.clear();
+    }
+}
+
+/// Standalone reproduction of a tracing failure
+///
+/// See the [module][self] documentation for details.
+#[derive(Clone, Debug, Default)]
+pub struct Repro {
+    /// Parameters of the encoder
+    pub params: config::Parameters,
+    /// Framing of the packets in [`data`][Self::data]
+    pub framing: Framing,
+    /// Width of packet source index fields
+    pub hart_index_width: u8,
+    /// Width of timestamps in packet headers
+    pub timestamp_width: u8,
+    /// Width of trace type fields
+    pub trace_type_width: u8,
+    /// Address shift overriding `iaddress_lsb_p`, if any
+    pub address_shift: Option<u8>,
+    /// Id of the hart the packets belong to
+    ///
+    /// If the session the packets were retained by was built with a
+    /// [`Topology`][super::topology::Topology], this is a global hart id. The
+    /// packets themselves carry the hart's local index.
+    pub hart: u64,
+    /// Raw trace data
+    pub data: Vec<u8>,
+}
+
+impl Repro {
+    /// Create a [`packet::Builder`] for decoding the [`data`][Self::data]
+    ///
+    /// The builder uses the [`Reference`][packet::unit::Reference] unit. Other
+    /// units need to be set via [`packet::Builder::for_unit`].
+    pub fn decoder(&self) -> packet::Builder {
+        packet::builder()
+            .with_params(&self.params)
+            .with_hart_index_width(self.hart_index_width)
+            .with_timestamp_width(self.timestamp_width)
+            .with_trace_type_width(self.trace_type_width)
+            .with_address_shift(self.address_shift)
+    }
+
+    /// Create a [`Builder`][super::Builder] for replaying the failure
+    ///
+    /// The builder is configured with the [`decoder`][Self::decoder], the
+    /// [`params`][Self::params] and the [`framing`][Self::framing]. The
+    /// [`Binary`][crate::binary::Binary] needs to be supplied by the caller.
+    pub fn session_builder(&self) -> super::Builder {
+        super::builder()
+            .with_decoder(self.decoder())
+            .with_params(&self.params)
+            .with_framing(self.framing)
+    }
+
+    /// Encode this repro as the contents of a repro file
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let params = &self.params;
+        let framing = Framing::ALL
+            .iter()
+            .position(|f| *f == self.framing)
+            .unwrap_or_default() as u8;
+        let mut res = Vec::with_capacity(44 + self.data.len());
+        res.extend_from_slice(&MAGIC);
+        res.extend_from_slice(&[
+            VERSION,
+            framing,
+            params.cache_size_p,
+            params.call_counter_size_p,
+            params.context_width_p.get(),
+            params.time_width_p.get(),
+            params.ecause_width_p.get(),
+            params.f0s_width_p,
+            params.iaddress_lsb_p,
+            params.iaddress_width_p.get(),
+            params.nocontext_p.into(),
+            params.notime_p.into(),
+            params.privilege_width_p.get(),
+            params.return_stack_size_p,
+            params.sijump_p.into(),
+            self.hart_index_width,
+            self.timestamp_width,
+            self.trace_type_width,
+            self.address_shift.is_some().into(),
+            self.address_shift.unwrap_or_default(),
+        ]);
+        res.extend_from_slice(&self.hart.to_le_bytes());
+        res.extend_from_slice(&(self.data.len() as u64).to_le_bytes());
+        res.extend_from_slice(&self.data);
+        res
+    }
+
+    /// Decode a repro from the contents of a repro file
+    pub fn from_bytes(data: &[u8]) -> Result<Self, Error> {
+        let mut reader = Reader(data);
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err(Error::Magic);
+        }
+        let version = reader.byte()?;
+        if version != VERSION {
+            return Err(Error::Version(version));
+        }
+        let framing = reader.byte()?;
+        let framing = *Framing::ALL
+            .get(usize::from(framing))
+            .ok_or(Error::Framing(framing))?;
+        let params = config::Parameters {
+            cache_size_p: reader.byte()?,
+            call_counter_size_p: reader.byte()?,
+            context_width_p: reader.non_zero("context_width_p")?,
+            time_width_p: reader.non_zero("time_width_p")?,
+            ecause_width_p: reader.non_zero("ecause_width_p")?,
+            f0s_width_p: reader.byte()?,
+            iaddress_lsb_p: reader.byte()?,
+            iaddress_width_p: reader.non_zero("iaddress_width_p")?,
+            nocontext_p: reader.flag("nocontext_p")?,
+            notime_p: reader.flag("notime_p")?,
+            privilege_width_p: reader.non_zero("privilege_width_p")?,
+            return_stack_size_p: reader.byte()?,
+            sijump_p: reader.flag("sijump_p")?,
+        };
+        let hart_index_width = reader.byte()?;
+        let timestamp_width = reader.byte()?;
+        let trace_type_width = reader.byte()?;
+        let has_shift = reader.flag("address_shift")?;
+        let address_shift = Some(reader.byte()?).filter(|_| has_shift);
+        let hart = reader.u64()?;
+        let len = usize::try_from(reader.u64()?).map_err(|_| Error::Truncated)?;
+        let trace = reader.take(len)?.into();
+        if !reader.0.is_empty() {
+            return Err(Error::TrailingData(reader.0.len()));
+        }
+        Ok(Self {
+            params,
+            framing,
+            hart_index_width,
+            timestamp_width,
+            trace_type_width,
+            address_shift,
+            hart,
+            data: trace,
+        })
+    }
+}
+
+/// Reader for the fields of a repro file
+struct Reader<'d>(&'d [u8]);
+
+impl<'d> Reader<'d> {
+    /// Take the given number of bytes
+    fn take(&mut self, len: usize) -> Result<&'d [u8], Error> {
+        let (res, rest) = self.0.split_at_checked(len).ok_or(Error::Truncated)?;
+        self.0 = rest;
+        Ok(res)
+    }
+
+    /// Take a single byte
+    fn byte(&mut self) -> Result<u8, Error> {
+        self.take(1).map(|b| b[0])
+    }
+
+    /// Take a single non-zero byte
+    fn non_zero(&mut self, name: &'static str) -> Result<NonZeroU8, Error> {
+        NonZeroU8::new(self.byte()?).ok_or(Error::Field(name))
+    }
+
+    /// Take a single byte holding either `0` or `1`
+    fn flag(&mut self, name: &'static str) -> Result<bool, Error> {
+        match self.byte()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(Error::Field(name)),
+        }
+    }
+
+    /// Take a little endian `u64`
+    fn u64(&mut self) -> Result<u64, Error> {
+        let bytes = self.take(8)?.try_into().map_err(|_| Error::Truncated)?;
+        Ok(u64::from_le_bytes(bytes))
+    }
+}
+
+/// Errors encountered when loading a [`Repro`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The data does not start with the [`MAGIC`] bytes
+    Magic,
+    /// The file format version is not supported
+    Version(u8),
+    /// The data ends prematurely
+    Truncated,
+    /// The framing index is unknown
+    Framing(u8),
+    /// The given field holds an invalid value
+    Field(&'static str),
+    /// The given number of bytes follow the trace data
+    TrailingData(usize),
+}
+
+impl core::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Magic => write!(f, "not a repro file"),
+            Self::Version(v) => write!(f, "unsupported repro file version {v}"),
+            Self::Truncated => write!(f, "repro file is truncated"),
+            Self::Framing(i) => write!(f, "unknown framing {i}"),
+            Self::Field(name) => write!(f, "invalid value for {name}"),
+            Self::TrailingData(n) => write!(f, "{n} bytes of trailing data"),
+        }
+    }
+}
@@ -0,0 +1,51 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Utilities for trace data split across multiple segments
+//!
+//! Captures are often split across multiple segments, e.g. files of a rotating
+//! capture. A packet may be cut at the end of one segment and continue in the
+//! next one. If trace data may have been lost between two segments, a
+//! [`Session`][super::Session] needs to resynchronize both the decoding of
+//! packets and tracing.
+
+/// Number of packets decoded for confirming a resynchronization point
+///
+/// After a [`Boundary::Lossy`], a [`Session`][super::Session] skips data at
+/// the start of a segment until this number of packets can be decoded without
+/// error.
+pub const RESYNC_LOOKAHEAD: usize = 8;
+
+/// Relation between a segment and the segment preceeding it
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Boundary {
+    /// The segment directly continues the previous one
+    ///
+    /// A packet cut at the end of the previous segment continues at the start
+    /// of this segment.
+    #[default]
+    Contiguous,
+    /// Trace data may have been lost between the previous segment and this one
+    ///
+    /// Any partial packet at the end of the previous segment is discarded. The
+    /// segment may start in the middle of a packet.
+    Lossy,
+}
+
+/// Report on the processing of a single segment
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Report {
+    /// Number of bytes of a partial packet discarded at the boundary
+    ///
+    /// These bytes were carried over from the end of the previous segment.
+    pub discarded: usize,
+    /// Number of bytes skipped at the start of the segment for resynchronizing
+    pub skipped: usize,
+    /// Number of packets skipped because their hart was not synchronized
+    pub unsynced: usize,
+    /// Number of harts whose tracing was reset due to an error
+    pub resets: usize,
+    /// Number of bytes of a partial packet at the end of the segment
+    ///
+    /// These bytes are carried over to the next segment.
+    pub cut: usize,
+}
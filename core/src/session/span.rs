@@ -0,0 +1,64 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Utilities for tracing only a portion of some trace data
+//!
+//! When investigating an incident in a long capture, only the [`Item`]s around
+//! the time of the incident are of interest. Tracing everything up to that
+//! point is wasteful. [`Session::for_each_item_in`] only traces the packets in
+//! a given [`Span`] and the minimal number of packets before it necessary for
+//! synchronizing each hart.
+//!
+//! [`Item`]: crate::tracer::item::Item
+//! [`Session::for_each_item_in`]: super::Session::for_each_item_in
+
+use core::ops::Range;
+
+/// Portion of trace data to trace
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Span {
+    /// Packets with a timestamp within the given range
+    ///
+    /// Packets without a timestamp are considered to carry the timestamp of
+    /// the last packet with one, if any.
+    Time(Range<u64>),
+    /// Packets with an index within the given range
+    ///
+    /// Packets are indexed in the order in which they are processed, i.e.
+    /// [sorted by time][super::sort_by_time].
+    Packets(Range<usize>),
+}
+
+impl Span {
+    /// Determine whether a packet with the given index and timestamp is covered
+    pub fn contains(&self, index: usize, timestamp: Option<u64>) -> bool {
+        match self {
+            Self::Time(range) => timestamp.is_some_and(|t| range.contains(&t)),
+            Self::Packets(range) => range.contains(&index),
+        }
+    }
+
+    /// Determine whether a packet with the given index and timestamp follows
+    /// this span
+    pub fn precedes(&self, index: usize, timestamp: Option<u64>) -> bool {
+        match self {
+            Self::Time(range) => timestamp.is_some_and(|t| t >= range.end),
+            Self::Packets(range) => index >= range.end,
+        }
+    }
+}
+
+/// Report on the tracing of a [`Span`]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Report {
+    /// Number of packets decoded
+    pub packets: usize,
+    /// Number of packets skipped without tracing them
+    pub skipped: usize,
+    /// Number of packets traced for synchronizing harts before the [`Span`]
+    pub warmup: usize,
+    /// Number of packets traced within the [`Span`]
+    pub traced: usize,
+    /// Number of packets within the [`Span`] skipped because their hart was
+    /// not synchronized
+    pub unsynced: usize,
+}
@@ -0,0 +1,971 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+use crate::instruction::{COMPRESSED, UNCOMPRESSED};
+use crate::packet::{encap, sync};
+use crate::types::trap;
+
+/// Encode the given `(hart, timestamp, address)` triplets as start packets
+fn start_packets(packets: &[(u16, u64, u64)]) -> Vec<u8> {
+    let packets: Vec<_> = packets
+        .iter()
+        .map(|(hart, timestamp, address)| {
+            let payload = sync::Start {
+                branch: true,
+                ctx: Default::default(),
+                address: *address,
+            };
+            (*hart, *timestamp, payload.into())
+        })
+        .collect();
+    encode_packets(&packets)
+}
+
+/// Encode the given `(hart, timestamp, payload)` triplets
+fn encode_packets(packets: &[(u16, u64, payload::InstructionTrace)]) -> Vec<u8> {
+    let builder = test_decoder();
+    let mut buffer = alloc::vec![0; 128];
+    let mut encoder = builder.encoder(buffer.as_mut());
+    packets.iter().for_each(|(hart, timestamp, payload)| {
+        let packet = encap::Normal::new(0, *hart, payload::Payload::from(*payload))
+            .with_timestamp(*timestamp);
+        encoder
+            .encode(&encap::Packet::from(packet))
+            .expect("Could not encode packet");
+    });
+    let uncommitted = encoder.uncommitted();
+    buffer.truncate(buffer.len() - uncommitted);
+    buffer
+}
+
+fn test_decoder() -> packet::Builder {
+    packet::builder()
+        .with_hart_index_width(8)
+        .with_timestamp_width(1)
+        .with_compression(false)
+}
+
+fn test_bin()
+-> binary::basic::SimpleMap<[(u64, instruction::Instruction); 2], Option<instruction::Kind>> {
+    binary::from_sorted_map([(0x80000000, UNCOMPRESSED), (0x80000100, COMPRESSED)])
+        .expect("Could not construct binary")
+}
+
+#[test]
+fn time_sorted_items() {
+    let data = start_packets(&[
+        (1, 20, 0x80000100),
+        (0, 10, 0x80000000),
+        (2, 15, 0x80000000),
+    ]);
+
+    let mut session: Session<_, _> = builder()
+        .with_decoder(test_decoder())
+        .with_binary(test_bin())
+        .build();
+
+    let mut items = Vec::new();
+    session
+        .for_each_item(data.as_ref(), |h, i| {
+            items.push((h, i.pc(), i.instruction().is_some()))
+        })
+        .expect("Could not trace");
+    assert_eq!(
+        items,
+        [
+            (0, 0x80000000, false),
+            (0, 0x80000000, true),
+            (2, 0x80000000, false),
+            (2, 0x80000000, true),
+            (1, 0x80000100, false),
+            (1, 0x80000100, true),
+        ],
+    );
+    assert!(session.harts().eq([0, 1, 2]));
+}
+
+/// [`Transport`] attributing encap packets to harts in reverse order
+#[derive(Copy, Clone, Default)]
+struct Reversed(Framing);
+
+impl<U: unit::Unit + Clone> Transport<U> for Reversed {
+    fn next_packet(
+        &mut self,
+        decoder: &mut packet::decoder::Decoder<'_, U>,
+    ) -> Result<Option<UnitPacket<U>>, packet::Error> {
+        self.0.next_packet(decoder).map(|p| {
+            p.map(|p| Packet {
+                hart: 2 - p.hart,
+                ..p
+            })
+        })
+    }
+}
+
+#[test]
+fn custom_transport() {
+    let data = start_packets(&[(0, 10, 0x80000000), (2, 20, 0x80000100)]);
+
+    let mut session: Session<_, _, stack::NoStack, _, _> = builder()
+        .with_decoder(test_decoder())
+        .with_binary(test_bin())
+        .with_transport(Reversed::default())
+        .build();
+
+    let mut items = Vec::new();
+    session
+        .for_each_item(data.as_ref(), |h, i| items.push((h, i.pc())))
+        .expect("Could not trace");
+    assert_eq!(
+        items,
+        [
+            (2, 0x80000000),
+            (2, 0x80000000),
+            (0, 0x80000100),
+            (0, 0x80000100),
+        ],
+    );
+}
+
+#[test]
+fn multi_payload_transport() {
+    let data = start_packets(&[(1, 10, 0x80000000), (1, 10, 0x80000010)]);
+
+    // Merge the payloads of both packets, each preceded by a three byte header
+    let first = usize::from(data[0] & 0x1f);
+    let mut merged = data[..3 + first].to_vec();
+    merged[0] += data[3 + first] & 0x1f;
+    merged.extend_from_slice(&data[6 + first..]);
+
+    let bin = binary::from_fn(|_| Ok::<_, binary::error::NoInstruction>(UNCOMPRESSED));
+    let mut expected = Vec::new();
+    let mut session: Session<_, _> = builder()
+        .with_decoder(test_decoder())
+        .with_binary(bin)
+        .build();
+    session
+        .for_each_item(data.as_ref(), |h, i| expected.push((h, i.pc())))
+        .expect("Could not trace");
+
+    let mut items = Vec::new();
+    let mut session: Session<_, _, stack::NoStack, _, _> = builder()
+        .with_decoder(test_decoder())
+        .with_binary(bin)
+        .with_transport(crate::transport::MultiPayloadEncap::default())
+        .build();
+    session
+        .for_each_item(merged.as_ref(), |h, i| items.push((h, i.pc())))
+        .expect("Could not trace");
+    assert_eq!(items, expected);
+    assert!(expected.contains(&(1, 0x80000010)));
+}
+
+#[test]
+fn sort_untimed() {
+    let packet = |hart, timestamp| Packet {
+        hart,
+        timestamp,
+        payload: payload::Payload::<unit::ReferenceIOptions>::DataTrace,
+    };
+    let mut packets = alloc::vec![
+        packet(0, None),
+        packet(1, Some(5)),
+        packet(2, None),
+        packet(3, Some(2)),
+        packet(4, None),
+    ];
+    sort_by_time(&mut packets);
+    assert!(packets.iter().map(|p| p.hart).eq([0, 3, 4, 1, 2]));
+}
+
+#[test]
+fn sort_wrapped() {
+    let packet = |hart, timestamp| Packet {
+        hart,
+        timestamp: Some(timestamp),
+        payload: payload::Payload::<unit::ReferenceIOptions>::DataTrace,
+    };
+    let mut packets = alloc::vec![
+        packet(0, 0xf0),
+        packet(1, 0x80),
+        packet(0, 0x10),
+        packet(1, 0x90),
+    ];
+    sort_by_time(&mut packets);
+    let order: Vec<_> = packets.iter().map(|p| (p.hart, p.timestamp)).collect();
+    assert_eq!(
+        order,
+        [
+            (1, Some(0x80)),
+            (1, Some(0x90)),
+            (0, Some(0xf0)),
+            (0, Some(0x10))
+        ]
+    );
+}
+
+#[test]
+fn wrapped_timestamps_keep_hart_order() {
+    let data = start_packets(&[
+        (0, 0xf0, 0x80000000),
+        (1, 0x80, 0x80000100),
+        (0, 0x10, 0x80000004),
+    ]);
+    let binary = binary::from_sorted_map([
+        (0x80000000, UNCOMPRESSED),
+        (0x80000004, UNCOMPRESSED),
+        (0x80000100, COMPRESSED),
+    ])
+    .expect("Could not construct binary");
+
+    let mut session: Session<_, _> = builder()
+        .with_decoder(test_decoder())
+        .with_binary(binary)
+        .build();
+    let mut pcs = Vec::new();
+    session
+        .for_each_item(data.as_ref(), |h, i| {
+            if h == 0 && i.instruction().is_some() {
+                pcs.push(i.pc())
+            }
+        })
+        .expect("Could not trace");
+    assert_eq!(pcs, [0x80000000, 0x80000004]);
+}
+
+/// Encode trap packets for the given `(hart, timestamp, address)` triplets
+fn trap_packets(packets: &[(u16, u64, u64)]) -> Vec<u8> {
+    let packets: Vec<_> = packets
+        .iter()
+        .map(|(hart, timestamp, address)| {
+            let payload = sync::Trap {
+                branch: true,
+                ctx: Default::default(),
+                thaddr: true,
+                address: *address,
+                info: trap::Info {
+                    ecause: 7,
+                    tval: None,
+                },
+            };
+            (*hart, *timestamp, payload.into())
+        })
+        .collect();
+    encode_packets(&packets)
+}
+
+/// Trace the given segments, collecting `(hart, pc, suspect)` triplets
+fn trace_segments(
+    segments: &[(&[u8], segment::Boundary)],
+) -> (Vec<(u64, u64, bool)>, Vec<segment::Report>) {
+    let mut session: Session<_, _> = builder()
+        .with_decoder(test_decoder())
+        .with_binary(test_bin())
+        .build();
+
+    let mut items = Vec::new();
+    let reports = segments
+        .iter()
+        .map(|(data, boundary)| {
+            session
+                .process_segment(data, *boundary, |h, i, s| {
+                    if i.instruction().is_some() {
+                        items.push((h, i.pc(), s))
+                    }
+                })
+                .expect("Could not trace")
+        })
+        .collect();
+    (items, reports)
+}
+
+#[test]
+fn segment_contiguous_cut() {
+    let data = trap_packets(&[(0, 1, 0x80000000), (0, 2, 0x80000100)]);
+    let (head, tail) = data.split_at(data.len() - 3);
+
+    let (items, reports) = trace_segments(&[
+        (head, segment::Boundary::Contiguous),
+        (tail, segment::Boundary::Contiguous),
+    ]);
+    assert_eq!(items, [(0, 0x80000000, false), (0, 0x80000100, false)]);
+    assert_eq!(reports[0].cut, data.len() / 2 - 3);
+    assert_eq!(reports[1], Default::default());
+}
+
+#[test]
+fn segment_lossy() {
+    let data = trap_packets(&[(0, 1, 0x80000000), (0, 2, 0x80000100), (0, 3, 0x80000000)]);
+    let len = data.len() / 3;
+    let head = &data[..len + 3];
+    let mut tail = alloc::vec![0x00, 0xff];
+    tail.extend_from_slice(&data[2 * len..]);
+
+    let (items, reports) = trace_segments(&[
+        (head, segment::Boundary::Contiguous),
+        (&tail, segment::Boundary::Lossy),
+    ]);
+    assert_eq!(items, [(0, 0x80000000, false), (0, 0x80000000, true)]);
+    assert_eq!(reports[0].cut, 3);
+    assert_eq!(reports[1].discarded, 3);
+    assert_eq!(reports[1].skipped, 2);
+    assert_eq!(reports[1].cut, 0);
+}
+
+#[test]
+fn segment_lossy_garbage() {
+    // Null packets all the way up to a corrupt byte, which decoding every
+    // suffix up to the end of the segment would visit quadratically often.
+    let mut data = alloc::vec![0x00; 1 << 16];
+    data.push(0xff);
+    let garbage = data.len();
+    data.extend(trap_packets(&[(0, 1, 0x80000000), (0, 2, 0x80000100)]));
+
+    let (items, reports) = trace_segments(&[(&data, segment::Boundary::Lossy)]);
+    assert_eq!(items, [(0, 0x80000000, true), (0, 0x80000100, false)]);
+    assert_eq!(reports[0].skipped, garbage);
+}
+
+#[test]
+fn segment_unsynced() {
+    let mut data = encode_packets(&[(
+        1,
+        1,
+        payload::InstructionTrace::Branch(payload::Branch {
+            branch_map: Default::default(),
+            address: None,
+        }),
+    )]);
+    data.extend(start_packets(&[(0, 2, 0x80000000)]));
+
+    let (items, reports) = trace_segments(&[(&data, segment::Boundary::Lossy)]);
+    assert_eq!(items, [(0, 0x80000000, true)]);
+    assert_eq!(reports[0].skipped, 0);
+    assert_eq!(reports[0].unsynced, 1);
+}
+
+#[test]
+fn topology_sources() {
+    let topology = topology::Topology::new()
+        .with_cluster(0, 2, 0)
+        .and_then(|t| t.with_cluster(1, 2, 2))
+        .expect("Could not build topology");
+    let mut session: Session<_, _> = builder()
+        .with_decoder(test_decoder())
+        .with_binary(test_bin())
+        .with_topology(topology)
+        .build();
+
+    let mut items = Vec::new();
+    session
+        .for_each_item(start_packets(&[(1, 10, 0x80000000)]).as_ref(), |h, i| {
+            items.push((h, i.pc()))
+        })
+        .expect("Could not trace");
+    session.set_source(1);
+    session
+        .for_each_item(start_packets(&[(1, 20, 0x80000100)]).as_ref(), |h, i| {
+            items.push((h, i.pc()))
+        })
+        .expect("Could not trace");
+    assert_eq!(
+        items,
+        [
+            (1, 0x80000000),
+            (1, 0x80000000),
+            (3, 0x80000100),
+            (3, 0x80000100),
+        ],
+    );
+
+    let res = session.for_each_item(start_packets(&[(2, 30, 0x80000000)]).as_ref(), |_, _| ());
+    assert_eq!(res, Err(Error::UnmappedHart(1, 2)));
+}
+
+#[test]
+fn topology_collision() {
+    use topology::{Error, Topology};
+
+    let topology = Topology::new().with_cluster(0, 4, 0);
+    assert_eq!(
+        topology.clone().and_then(|t| t.with_cluster(1, 2, 3)),
+        Err(Error::DuplicateGlobal {
+            global: 3,
+            source: 0,
+            local: 3
+        }),
+    );
+    assert_eq!(
+        topology.and_then(|t| t.with_hart(0, 1, 8)),
+        Err(Error::DuplicateLocal {
+            source: 0,
+            local: 1
+        }),
+    );
+}
+
+#[test]
+fn post_mortem_window() {
+    let data = start_packets(&[
+        (0, 10, 0x80000000),
+        (1, 20, 0x80000100),
+        (0, 30, 0x80000000),
+        (0, 40, 0x90000000),
+    ]);
+
+    let mut session: Session<_, _> = builder()
+        .with_decoder(test_decoder())
+        .with_binary(test_bin())
+        .build();
+    let packets = session.decode_packets(&data).expect("Could not decode");
+    assert!(session.for_each_item(&data, |_, _| ()).is_err());
+
+    let window = session
+        .post_mortem(&packets, 3)
+        .expect("Could not re-trace")
+        .expect("No window");
+    assert_eq!(window.hart, 0);
+    assert_eq!(window.start, 2);
+    assert_eq!(window.end, 3);
+    assert_eq!(window.last_pc(), Some(0x80000000));
+    assert_eq!(window.items.len(), 2);
+    assert!(window.error.is_some());
+    assert!(window.tracer.is_tracing());
+
+    assert!(session.post_mortem(&packets, 1).expect("Error").is_none());
+    assert!(session.post_mortem(&packets, 4).expect("Error").is_none());
+}
+
+#[test]
+fn lifecycle_transitions() {
+    use lifecycle::{State, Transition};
+
+    let start = |address| {
+        payload::InstructionTrace::from(sync::Start {
+            branch: true,
+            ctx: Default::default(),
+            address,
+        })
+    };
+    let support = |ienable, qual_status| {
+        payload::InstructionTrace::from(sync::Support {
+            ienable,
+            qual_status,
+            ..Default::default()
+        })
+    };
+    let data = encode_packets(&[
+        (0, 1, start(0x80000000)),
+        (1, 2, start(0x80000100)),
+        (0, 3, support(true, sync::QualStatus::TraceLost)),
+        (1, 4, support(false, sync::QualStatus::NoChange)),
+        (0, 5, start(0x80000100)),
+    ]);
+
+    let mut session: Session<_, _> = builder()
+        .with_decoder(test_decoder())
+        .with_binary(test_bin())
+        .build();
+    session
+        .for_each_item(data.as_ref(), |_, _| ())
+        .expect("Could not trace");
+
+    let transition = |hart, timestamp, from, to| Transition {
+        hart,
+        timestamp: Some(timestamp),
+        from,
+        to,
+    };
+    assert_eq!(
+        session.take_transitions(),
+        [
+            transition(0, 1, State::NotTraced, State::Active),
+            transition(1, 2, State::NotTraced, State::Active),
+            transition(0, 3, State::Active, State::Lost),
+            transition(1, 4, State::Active, State::Ended),
+            transition(0, 5, State::Lost, State::Active),
+        ],
+    );
+    assert_eq!(session.take_transitions(), []);
+    assert_eq!(session.lifecycle(1), State::Ended);
+    assert_eq!(session.lifecycle(2), State::NotTraced);
+}
+
+#[test]
+fn sleep_after_wfi() {
+    use lifecycle::{Sleep, SleepCause, State};
+
+    let start = |address| {
+        payload::InstructionTrace::from(sync::Start {
+            branch: true,
+            ctx: Default::default(),
+            address,
+        })
+    };
+    let interrupt = |address| {
+        payload::InstructionTrace::from(sync::Trap {
+            branch: true,
+            ctx: Default::default(),
+            thaddr: true,
+            address,
+            info: trap::Info {
+                ecause: 7,
+                tval: None,
+            },
+        })
+    };
+    let ended = payload::InstructionTrace::from(sync::Support {
+        ienable: true,
+        qual_status: sync::QualStatus::EndedRep,
+        ..Default::default()
+    });
+    let data = encode_packets(&[
+        (0, 1, start(0x80000000)),
+        (1, 2, start(0x80000000)),
+        (0, 3, ended),
+        (0, 10, start(0x80000100)),
+        (1, 50, interrupt(0x80000100)),
+        (1, 60, interrupt(0x80000000)),
+    ]);
+
+    let binary = binary::from_sorted_map([
+        (0x80000000, instruction::Kind::wfi.into()),
+        (0x80000100, COMPRESSED),
+    ])
+    .expect("Could not construct binary");
+    let mut session: Session<_, _> = builder()
+        .with_decoder(test_decoder())
+        .with_binary(binary)
+        .with_sleep_threshold(20)
+        .build();
+    session
+        .for_each_item(data.as_ref(), |_, _| ())
+        .expect("Could not trace");
+
+    let sleeps = session.take_sleeps();
+    assert_eq!(
+        sleeps,
+        [
+            Sleep {
+                hart: 0,
+                start: Some(3),
+                end: Some(10),
+                cause: SleepCause::Unqualified,
+            },
+            Sleep {
+                hart: 1,
+                start: Some(2),
+                end: Some(50),
+                cause: SleepCause::Silence,
+            },
+        ],
+    );
+    assert_eq!(sleeps[1].duration(), Some(48));
+    assert!(
+        session
+            .take_transitions()
+            .iter()
+            .any(|t| t.hart == 0 && t.timestamp == Some(3) && t.to == State::Asleep)
+    );
+    assert_eq!(session.lifecycle(1), State::Active);
+}
+
+#[test]
+fn timestamp_rollover() {
+    let data = trap_packets(&[
+        (0, 0xf0, 0x80000000),
+        (1, 0xf8, 0x80000100),
+        (0, 0x10, 0x80000000),
+        (1, 0x08, 0x80000100),
+    ]);
+
+    let mut session: Session<_, _> = builder()
+        .with_decoder(test_decoder())
+        .with_binary(test_bin())
+        .with_timestamp_rollover(8)
+        .build();
+    let mut harts = Vec::new();
+    session
+        .for_each_item(data.as_ref(), |h, i| {
+            if i.instruction().is_some() {
+                harts.push(h)
+            }
+        })
+        .expect("Could not trace");
+    assert_eq!(harts, [0, 1, 1, 0]);
+}
+
+#[test]
+fn custom_timestamping() {
+    let data = trap_packets(&[(0, 10, 0x80000000), (1, 6, 0x80000100), (0, 11, 0x80000000)]);
+
+    let mut session: Session<_, _> = builder()
+        .with_decoder(test_decoder())
+        .with_binary(test_bin())
+        .with_timestamping(|h, _, t| if h == 1 { t * 2 } else { t })
+        .build();
+    let mut harts = Vec::new();
+    session
+        .for_each_item(data.as_ref(), |h, i| {
+            if i.instruction().is_some() {
+                harts.push(h)
+            }
+        })
+        .expect("Could not trace");
+    assert_eq!(harts, [0, 0, 1]);
+}
+
+#[test]
+fn item_filter() {
+    let data = trap_packets(&[(0, 1, 0x80000000), (1, 2, 0x80000100)]);
+
+    let filter = "hart == 1 && kind == trap || pc in [0x80000000..=0x80000000]";
+    let mut session: Session<_, _> = builder()
+        .with_decoder(test_decoder())
+        .with_binary(test_bin())
+        .with_filter(filter.parse().unwrap())
+        .build();
+    let mut items = Vec::new();
+    session
+        .for_each_item(data.as_ref(), |h, i| {
+            items.push((h, i.pc(), i.instruction().is_some()))
+        })
+        .expect("Could not trace");
+    assert_eq!(
+        items,
+        [(0, 0x80000000, false), (0, 0x80000000, true), (1, 0, false)]
+    );
+}
+
+#[test]
+fn time_travel_queries() {
+    let data = start_packets(&[
+        (0, 10, 0x80000000),
+        (1, 15, 0x80000100),
+        (0, 20, 0x80000004),
+        (0, 30, 0x80000000),
+    ]);
+    let binary = binary::from_sorted_map([
+        (0x80000000, UNCOMPRESSED),
+        (0x80000004, instruction::Kind::new_jal(0, -4).into()),
+        (0x80000100, COMPRESSED),
+    ])
+    .expect("Could not construct binary");
+
+    let mut session: Session<_, _> = builder()
+        .with_decoder(test_decoder())
+        .with_binary(binary)
+        .build();
+    let packets = session
+        .decode_packets(data.as_ref())
+        .expect("Could not decode");
+    let index = index::Index::new(&packets);
+    assert_eq!(index.points(0).len(), 3);
+    assert_eq!(index.harts().collect::<Vec<_>>(), [0, 1]);
+
+    let at = |t| {
+        session
+            .pc_at(&packets, &index, 0, t)
+            .expect("Could not trace")
+            .map(|o| (o.packet, o.item.pc()))
+    };
+    assert_eq!(at(5), None);
+    assert_eq!(at(10), Some((0, 0x80000000)));
+    assert_eq!(at(25), Some((2, 0x80000004)));
+    assert_eq!(at(100), Some((3, 0x80000000)));
+
+    let hits = session
+        .occurrences_of(&packets, &index, 0, 0x80000000, 0..100)
+        .expect("Could not trace");
+    let hits: Vec<_> = hits.iter().map(|o| (o.packet, o.timestamp)).collect();
+    assert_eq!(hits, [(0, Some(10)), (3, Some(30))]);
+
+    let hits = session
+        .occurrences_of(&packets, &index, 0, 0x80000000, 11..30)
+        .expect("Could not trace");
+    assert!(hits.is_empty());
+}
+
+#[test]
+fn function_slices() {
+    let data = start_packets(&[
+        (0, 10, 0x80000000),
+        (0, 50, 0x80000000),
+        (0, 70, 0x80000100),
+    ]);
+    let binary = binary::from_sorted_map([
+        (0x80000000, UNCOMPRESSED),
+        (0x80000004, instruction::Kind::new_jal(1, 0xfc).into()),
+        (0x80000100, COMPRESSED),
+        (0x80000102, instruction::Kind::new_c_jr(1).into()),
+    ])
+    .expect("Could not construct binary");
+
+    let mut session: Session<_, _> = builder()
+        .with_decoder(test_decoder())
+        .with_binary(binary)
+        .build();
+    let packets = session
+        .decode_packets(data.as_ref())
+        .expect("Could not decode");
+    let index = index::Index::new(&packets);
+
+    let slices = session
+        .slices(&packets, &index, 0, 0x80000100..0x80000104, 1, 0..60)
+        .expect("Could not trace");
+    assert_eq!(slices.len(), 1);
+    let pcs: Vec<_> = slices[0].items.iter().map(|o| o.item.pc()).collect();
+    assert_eq!(pcs, [0x80000004, 0x80000100, 0x80000102, 0x80000000]);
+    assert_eq!((slices[0].before, slices[0].after), (1, 1));
+
+    let slices = session
+        .slices(&packets, &index, 0, 0x80000100..0x80000104, 0, 0..100)
+        .expect("Could not trace");
+    let pcs: Vec<Vec<_>> = slices
+        .iter()
+        .map(|s| s.items.iter().map(|o| o.item.pc()).collect())
+        .collect();
+    // The second slice starts with the context item reported by the sync
+    assert_eq!(
+        pcs,
+        [
+            alloc::vec![0x80000100, 0x80000102],
+            alloc::vec![0x80000100, 0x80000100],
+        ]
+    );
+}
+
+#[test]
+fn region_bases() {
+    let data = start_packets(&[(0, 10, 0x100), (1, 20, 0x80000100)]);
+
+    let mut session: Session<_, _> = builder()
+        .with_decoder(test_decoder())
+        .with_binary(test_bin())
+        .build();
+    session.set_region_base(0, 0x80000000);
+    assert_eq!(session.region_base(0), Some(0x80000000));
+    assert_eq!(session.region_base(1), None);
+
+    let mut items = Vec::new();
+    session
+        .for_each_item(data.as_ref(), |h, i| items.push((h, i.pc())))
+        .expect("Could not trace");
+    assert_eq!(
+        items,
+        [
+            (0, 0x80000100),
+            (0, 0x80000100),
+            (1, 0x80000100),
+            (1, 0x80000100),
+        ],
+    );
+
+    session.set_region_base(1, 0x80000000);
+    assert_eq!(session.tracer(1).map(|t| t.region_base()), Some(0x80000000));
+}
+
+#[test]
+fn validate_trace() {
+    let first = start_packets(&[(0, 10, 0x80000000)]);
+    let mut data = start_packets(&[(0, 10, 0x80000000), (1, 20, 0x1234), (1, 30, 0x80000100)]);
+    let end = data.len();
+    data.push(0x1f);
+
+    let mut session: Session<_, _> = builder()
+        .with_decoder(test_decoder())
+        .with_binary(test_bin())
+        .build();
+    let report = session.validate(&data, 8);
+    assert!(!report.is_ok());
+    assert!(!report.truncated);
+    assert_eq!(report.packets, 3);
+    assert_eq!(report.items, 4);
+    assert_eq!(report.problems.len(), 2);
+    assert_eq!(report.problems[0].offset, end);
+    assert!(matches!(report.problems[0].error, Error::Packet(_)));
+    assert_eq!(report.problems[1].offset, first.len());
+    assert_eq!(report.problems[1].hart, Some(1));
+    assert_eq!(report.problems[1].timestamp, Some(20));
+    assert!(matches!(report.problems[1].error, Error::Tracer(1, _)));
+    assert!(session.tracer(1).is_some());
+
+    let report = session.validate(&data, 1);
+    assert!(report.truncated);
+    assert_eq!(report.problems.len(), 1);
+    assert_eq!(report.items, 0);
+}
+
+#[test]
+fn validate_rollover() {
+    // Hart 0's second packet rolls over, so hart 1's packets precede it
+    let data = trap_packets(&[
+        (0, 0x70, 0x80000000),
+        (1, 0x80, 0x80000000),
+        (0, 0x10, 0x90000000),
+        (1, 0x90, 0x90000000),
+    ]);
+
+    let mut session: Session<_, _> = builder()
+        .with_decoder(test_decoder())
+        .with_binary(test_bin())
+        .with_timestamp_rollover(8)
+        .build();
+    let report = session.validate(&data, 1);
+    assert!(report.truncated);
+    assert_eq!(report.problems.len(), 1);
+    assert_eq!(report.problems[0].hart, Some(1));
+    assert_eq!(report.problems[0].timestamp, Some(0x90));
+}
+
+#[test]
+fn trace_span() {
+    use span::{Report, Span};
+
+    let data = start_packets(&[
+        (0, 10, 0x80000000),
+        (1, 15, 0x80000100),
+        (0, 20, 0x80000004),
+        (0, 30, 0x80000000),
+        (1, 40, 0x80000102),
+    ]);
+    let binary = binary::from_sorted_map([
+        (0x80000000, UNCOMPRESSED),
+        (0x80000004, instruction::Kind::new_jal(0, -4).into()),
+        (0x80000100, COMPRESSED),
+        (0x80000102, COMPRESSED),
+    ])
+    .expect("Could not construct binary");
+    let mut session: Session<_, _> = builder()
+        .with_decoder(test_decoder())
+        .with_binary(binary)
+        .build();
+
+    let mut items = Vec::new();
+    let report = session
+        .for_each_item_in(data.as_ref(), &Span::Time(20..35), |h, i| {
+            items.push((h, i.pc(), i.instruction().is_some()))
+        })
+        .expect("Could not trace");
+    assert_eq!(
+        report,
+        Report {
+            packets: 5,
+            skipped: 1,
+            warmup: 2,
+            traced: 2,
+            unsynced: 0,
+        }
+    );
+    assert_eq!(
+        items,
+        [
+            (0, 0x80000004, false),
+            (0, 0x80000004, true),
+            (0, 0x80000000, false),
+            (0, 0x80000000, true),
+        ],
+    );
+
+    let mut items = Vec::new();
+    let report = session
+        .for_each_item_in(data.as_ref(), &Span::Packets(3..5), |h, i| {
+            items.push((h, i.pc()))
+        })
+        .expect("Could not trace");
+    assert_eq!((report.skipped, report.warmup, report.traced), (1, 2, 2));
+    assert_eq!(
+        items,
+        [
+            (0, 0x80000000),
+            (0, 0x80000000),
+            (1, 0x80000102),
+            (1, 0x80000102),
+        ],
+    );
+}
+
+#[test]
+fn repro_window() {
+    let data = start_packets(&[(1, 10, 0x80000100), (0, 15, 0x80000000), (1, 20, 0x1234)]);
+    let mut session: Session<_, _> = builder()
+        .with_decoder(test_decoder())
+        .with_binary(test_bin())
+        .with_repro_window(1)
+        .build();
+    let res = session.for_each_item(&data, |_, _| ());
+    assert!(matches!(res, Err(Error::Tracer(1, _))));
+
+    let params = Default::default();
+    let repro = session.repro(1, &params).expect("No repro");
+    assert_eq!(repro.data, start_packets(&[(1, 20, 0x1234)]));
+    assert_eq!(
+        session.repro_buffer().map(|b| b.packets(0).count()),
+        Some(1)
+    );
+    assert!(session.repro(2, &params).is_none());
+
+    let loaded = repro::Repro::from_bytes(&repro.to_bytes()).expect("Could not load repro");
+    assert_eq!(loaded.hart, 1);
+    assert_eq!(loaded.hart_index_width, 8);
+    assert_eq!(loaded.timestamp_width, 1);
+    let mut replay: Session<_, _> = loaded
+        .session_builder()
+        .with_decoder(loaded.decoder().with_compression(false))
+        .with_binary(test_bin())
+        .build();
+    let res = replay.for_each_item(&loaded.data, |_, _| ());
+    assert!(matches!(res, Err(Error::Tracer(1, _))));
+
+    let mut bytes = repro.to_bytes();
+    assert_eq!(
+        repro::Repro::from_bytes(&bytes[..20]).err(),
+        Some(repro::Error::Truncated)
+    );
+    bytes[0] = 0;
+    assert_eq!(
+        repro::Repro::from_bytes(&bytes).err(),
+        Some(repro::Error::Magic)
+    );
+}
+
+#[test]
+fn isa_mismatch() {
+    use crate::packet::unit::described::{Described, DescribedIOptions, IOption};
+
+    let support = |timestamp, rv64| Packet {
+        hart: 1,
+        timestamp: Some(timestamp),
+        payload: payload::InstructionTrace::from(sync::Support {
+            ienable: true,
+            ioptions: DescribedIOptions {
+                options: alloc::vec![(IOption::Rv64, rv64)],
+            },
+            ..Default::default()
+        })
+        .into(),
+    };
+    let mut session: Session<_, _> = builder()
+        .with_decoder(Described::default().apply(test_decoder()))
+        .with_binary(binary::from_segment(b"\x01\x00", base::Set::Rv32I))
+        .build();
+    [(1, false), (2, true), (3, true), (4, false), (5, true)]
+        .into_iter()
+        .try_for_each(|(t, rv64)| session.process_packet(&support(t, rv64), |_, _| ()))
+        .expect("Could not process packets");
+
+    let mismatch = |timestamp| isa::Mismatch {
+        hart: 1,
+        timestamp: Some(timestamp),
+        advertised: base::Set::Rv64I,
+        binary: base::Set::Rv32I,
+    };
+    assert_eq!(session.take_isa_mismatches(), [mismatch(2), mismatch(5)]);
+    assert!(session.take_isa_mismatches().is_empty());
+    assert_eq!(
+        alloc::string::ToString::to_string(&mismatch(2)),
+        "hart 1 advertised as RV64I, but binary is RV32I"
+    );
+}
@@ -0,0 +1,72 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Normalization of timestamps
+//!
+//! A [`Session`][super::Session] compares timestamps of packets, e.g. when
+//! [sorting them by time][super::sort_by_time]. This requires timestamps which
+//! are absolute and monotonic. Many encoders, however, report timestamps which
+//! are truncated, encoded or scaled in some way. This module defines the
+//! [`Timestamping`] trait for normalizing such timestamps.
+//!
+//! # Example
+//!
+//! The following normalizes gray-coded 16 bit counters which may roll over.
+//!
+//! ```
+//! use riscv_etrace_core::packet::encap::Rollover;
+//! use riscv_etrace_core::session::{self, timestamping::Timestamping};
+//!
+//! #[derive(Copy, Clone)]
+//! struct Gray;
+//!
+//! impl Timestamping for Gray {
+//!     fn normalize(&self, _: u64, last: Option<u64>, timestamp: u64) -> u64 {
+//!         let mut binary = timestamp;
+//!         let mut shift = timestamp >> 1;
+//!         while shift != 0 {
+//!             binary ^= shift;
+//!             shift >>= 1;
+//!         }
+//!         Rollover::new(16).normalize(0, last, binary)
+//!     }
+//! }
+//!
+//! assert_eq!(Gray.normalize(0, Some(0x1fffe), 0b11), 0x20002);
+//!
+//! let session = session::builder().with_timestamping(Gray);
+//! ```
+
+use crate::packet::encap::Rollover;
+
+/// Normalization of timestamps reported by encoders
+///
+/// Implementations map the timestamps reported in packets to absolute,
+/// monotonic ones. A [`Session`][super::Session] tracks the last normalized
+/// timestamp for each hart and supplies it to [`normalize`][Self::normalize],
+/// which allows reconstructing timestamps truncated by the encoder.
+pub trait Timestamping {
+    /// Normalize the timestamp reported for the given hart
+    ///
+    /// The `last` timestamp is the last one normalized for the same hart, if
+    /// any. The `hart` is the global hart id.
+    fn normalize(&self, hart: u64, last: Option<u64>, timestamp: u64) -> u64;
+}
+
+/// [`Timestamping`] for timestamps truncated to the [`Rollover`]'s width
+///
+/// The [`Rollover`]'s own state is not used or altered. Each hart's timestamps
+/// are extended based on the hart's `last` timestamp instead.
+impl Timestamping for Rollover {
+    fn normalize(&self, _: u64, last: Option<u64>, timestamp: u64) -> u64 {
+        self.with_last(last).extend(timestamp)
+    }
+}
+
+/// [`Timestamping`] defined by a fn
+///
+/// The fn is called with the same arguments as [`Timestamping::normalize`].
+impl<F: Fn(u64, Option<u64>, u64) -> u64> Timestamping for F {
+    fn normalize(&self, hart: u64, last: Option<u64>, timestamp: u64) -> u64 {
+        self(hart, last, timestamp)
+    }
+}
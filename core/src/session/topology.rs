@@ -0,0 +1,120 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Topology of trace sources
+//!
+//! On SoCs with multiple clusters, trace data of each cluster is usually
+//! gathered by a dedicated funnel. The hart indices within packets are local
+//! to the cluster and thus repeat across trace sources. A [`Topology`] maps
+//! pairs of a trace source and a local hart index onto global hart ids, which
+//! are then used by the [`Session`][super::Session].
+
+use alloc::collections::BTreeMap;
+use core::fmt;
+
+/// Mapping of local hart indices of trace sources onto global hart ids
+///
+/// # Example
+///
+/// ```
+/// use riscv_etrace_core::session::topology::Topology;
+///
+/// let topology = Topology::new()
+///     .with_cluster(0, 4, 0)
+///     .and_then(|t| t.with_cluster(1, 2, 4))
+///     .unwrap();
+/// assert_eq!(topology.global(1, 1), Some(5));
+/// assert_eq!(topology.local(2), Some((0, 2)));
+/// assert_eq!(topology.global(1, 2), None);
+/// assert!(topology.clone().with_hart(2, 0, 5).is_err());
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Topology {
+    globals: BTreeMap<(u64, u64), u64>,
+    locals: BTreeMap<u64, (u64, u64)>,
+}
+
+impl Topology {
+    /// Create a new, empty topology
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Map the given source's local hart index onto the given global hart id
+    ///
+    /// Returns an error if either the local hart index or the global hart id
+    /// is already mapped.
+    pub fn with_hart(mut self, source: u64, local: u64, global: u64) -> Result<Self, Error> {
+        if self.globals.contains_key(&(source, local)) {
+            return Err(Error::DuplicateLocal { source, local });
+        }
+        if let Some((source, local)) = self.locals.get(&global) {
+            return Err(Error::DuplicateGlobal {
+                global,
+                source: *source,
+                local: *local,
+            });
+        }
+        self.globals.insert((source, local), global);
+        self.locals.insert(global, (source, local));
+        Ok(self)
+    }
+
+    /// Map a cluster of harts of the given source onto consecutive global ids
+    ///
+    /// The local hart indices `0` to `harts - 1` are mapped onto global hart
+    /// ids starting at `base`. Returns an error if any of the local hart
+    /// indices or global hart ids is already mapped.
+    pub fn with_cluster(self, source: u64, harts: u64, base: u64) -> Result<Self, Error> {
+        (0..harts).try_fold(self, |t, h| t.with_hart(source, h, base.wrapping_add(h)))
+    }
+
+    /// Retrieve the global hart id for the given source and local hart index
+    pub fn global(&self, source: u64, local: u64) -> Option<u64> {
+        self.globals.get(&(source, local)).copied()
+    }
+
+    /// Retrieve the source and local hart index for the given global hart id
+    pub fn local(&self, global: u64) -> Option<(u64, u64)> {
+        self.locals.get(&global).copied()
+    }
+
+    /// Retrieve an [`Iterator`] over all mappings
+    ///
+    /// The [`Iterator`] yields `((source, local), global)` pairs.
+    pub fn iter(&self) -> impl Iterator<Item = ((u64, u64), u64)> + '_ {
+        self.globals.iter().map(|(l, g)| (*l, *g))
+    }
+}
+
+/// Errors due to colliding mappings in a [`Topology`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum Error {
+    /// The local hart index of the source is already mapped
+    DuplicateLocal { source: u64, local: u64 },
+    /// The global hart id is already mapped to the given source and local index
+    DuplicateGlobal {
+        global: u64,
+        source: u64,
+        local: u64,
+    },
+}
+
+impl core::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DuplicateLocal { source, local } => {
+                write!(f, "hart {local} of source {source} is already mapped")
+            }
+            Self::DuplicateGlobal {
+                global,
+                source,
+                local,
+            } => write!(
+                f,
+                "hart id {global} is already mapped to hart {local} of source {source}"
+            ),
+        }
+    }
+}
@@ -0,0 +1,67 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Utilities for validating entire traces
+//!
+//! When validating a trace, e.g. in a conformance test, a report of all the
+//! problems found is usually more useful than the first error encountered.
+//! [`Session::validate`][super::Session::validate] continues processing after
+//! errors and collects them, along with their provenance, in a [`Report`].
+
+use alloc::vec::Vec;
+
+use super::error::Error;
+
+/// A problem encountered while validating a trace
+#[derive(Debug, PartialEq)]
+pub struct Problem<B> {
+    /// Byte offset of the offending packet in the trace data
+    pub offset: usize,
+    /// Global id of the hart the offending packet belongs to, if known
+    pub hart: Option<u64>,
+    /// Timestamp of the offending packet, if known
+    pub timestamp: Option<u64>,
+    /// The error encountered
+    pub error: Error<B>,
+}
+
+/// Report on the validation of a trace
+#[derive(Debug, PartialEq)]
+pub struct Report<B> {
+    /// Number of packets decoded
+    pub packets: usize,
+    /// Number of [`Item`][crate::tracer::item::Item]s generated
+    pub items: usize,
+    /// Number of packets skipped because their hart was not synchronized
+    pub unsynced: usize,
+    /// Problems encountered, in the order in which they were encountered
+    pub problems: Vec<Problem<B>>,
+    /// Whether the limit of problems was reached, stopping validation
+    pub truncated: bool,
+}
+
+impl<B> Report<B> {
+    /// Create a new, empty report
+    pub(super) fn new() -> Self {
+        Self {
+            packets: 0,
+            items: 0,
+            unsynced: 0,
+            problems: Vec::new(),
+            truncated: false,
+        }
+    }
+
+    /// Determine whether no problems were encountered
+    pub fn is_ok(&self) -> bool {
+        self.problems.is_empty()
+    }
+
+    /// Record a [`Problem`], respecting the given limit
+    ///
+    /// Returns `false` if the limit was reached and validation should stop.
+    pub(super) fn record(&mut self, problem: Problem<B>, limit: usize) -> bool {
+        self.problems.push(problem);
+        self.truncated = self.problems.len() >= limit;
+        !self.truncated
+    }
+}
@@ -0,0 +1,741 @@
+// Copyright (C) 2025, 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+
+#[macro_use]
+mod test_gen;
+
+mod chapter12;
+mod jumps;
+mod model;
+mod robustness;
+mod section762;
+mod traps;
+
+use crate::binary;
+use crate::config;
+use crate::generator;
+use crate::instruction;
+use crate::packet::{payload, sync};
+use crate::tracer;
+use crate::types::{Context, Privilege, branch, stack, trap};
+
+use instruction::{COMPRESSED, Kind, UNCOMPRESSED};
+use test_gen::{ItemConverter, ItemHints, TestStep};
+use tracer::item::Item;
+
+trace_test!(
+    full_branch_map,
+    test_bin_1(),
+    start_packet(0x80000010) => {
+        (0x80000010, Context::default()),
+        (0x80000010, UNCOMPRESSED)
+    }
+    payload::Branch {
+        branch_map: branch::Map::new(31, 1 << 30),
+        address: None,
+    } => {
+        [
+            (0x80000014, COMPRESSED),
+            (0x80000016, COMPRESSED),
+            (0x80000018, COMPRESSED),
+            (0x8000001a, COMPRESSED),
+            (0x8000001c, Kind::new_bltu(11, 12, -8));
+            31
+        ]
+    }
+    payload::AddressInfo {
+        address: 0x20 - 0x10,
+        notify: false,
+        updiscon: false,
+        irdepth: None,
+    } => {
+        (0x80000020, Kind::fence_i)
+    }
+);
+
+trace_test!(
+    full_address,
+    test_bin_1(),
+    @address_mode Full
+    sync::Start {
+        branch: true,
+        ctx: Default::default(),
+        address: 0x80000000,
+    } => {
+        (0x80000000, Context::default()),
+        (0x80000000, Kind::new_auipc(13, 0x0))
+    }
+    payload::Branch {
+        branch_map: branch::Map::new(1, 1),
+        address: Some(payload::AddressInfo {
+            address: 0x80000026,
+            notify: false,
+            updiscon: false,
+            irdepth: None,
+        }),
+    } => {
+        (0x80000004, UNCOMPRESSED),
+        (0x80000008, UNCOMPRESSED),
+        (0x8000000c, Kind::new_auipc(1, 0x0)),
+        (0x80000010, UNCOMPRESSED),
+        (0x80000014, COMPRESSED),
+        (0x80000016, COMPRESSED),
+        (0x80000018, COMPRESSED),
+        (0x8000001a, COMPRESSED),
+        (0x8000001c, Kind::new_bltu(11, 12, -8)),
+        (0x80000020, Kind::fence_i),
+        (0x80000024, Kind::new_c_jr(1)),
+        (0x80000026, UNCOMPRESSED)
+    }
+    payload::AddressInfo {
+        address: 0x80000034,
+        notify: false,
+        updiscon: false,
+        irdepth: None,
+    } => {
+        (0x8000002a, UNCOMPRESSED),
+        (0x8000002e, COMPRESSED),
+        (0x80000030, Kind::wfi),
+        (0x80000034, Kind::new_c_j(0, -4))
+    }
+);
+
+trace_test!(
+    resync,
+    test_bin_1(),
+    start_packet(0x80000000) => {
+        (0x80000000, Context::default()),
+        (0x80000000, Kind::new_auipc(13, 0x0))
+    }
+    start_packet(0x80000010) => {
+        (0x80000004, UNCOMPRESSED),
+        (0x80000008, UNCOMPRESSED),
+        (0x8000000c, Kind::new_auipc(1, 0x0)),
+        (0x80000010, Context::default()),
+        (0x80000010, UNCOMPRESSED, sync)
+    }
+);
+
+trace_test!(
+    sync_with_branches,
+    test_bin_1(),
+    start_packet(0x80000010) => {
+        (0x80000010, Context::default()),
+        (0x80000010, UNCOMPRESSED)
+    }
+    payload::Branch {
+        branch_map: branch::Map::new(3, 1 << 2),
+        address: Some(payload::AddressInfo {
+            address: 0x20 - 0x10,
+            notify: false,
+            updiscon: false,
+            irdepth: None,
+        }),
+    } => {
+        [
+            (0x80000014, COMPRESSED),
+            (0x80000016, COMPRESSED),
+            (0x80000018, COMPRESSED),
+            (0x8000001a, COMPRESSED),
+            (0x8000001c, Kind::new_bltu(11, 12, -8));
+            3
+        ],
+        (0x80000020, Kind::fence_i)
+    }
+    start_packet(0x80000024) => {
+        (0x80000024, Context::default()),
+        (0x80000024, Kind::new_c_jr(1), sync)
+    }
+);
+
+trace_test!(
+    ended_ntr,
+    test_bin_1(),
+    start_packet(0x80000014) => {
+        (0x80000014, Context::default()),
+        (0x80000014, COMPRESSED)
+    }
+    sync::Support {
+        ienable: true,
+        qual_status: sync::QualStatus::EndedNtr,
+        ..Default::default()
+    } => {}
+);
+
+trace_test!(
+    trace_notify,
+    test_bin_1(),
+    start_packet(0x80000000) => {
+        (0x80000000, Context::default()),
+        (0x80000000, Kind::new_auipc(13, 0x0))
+    }
+    payload::AddressInfo {
+        address: 0x14,
+        notify: true,
+        updiscon: false,
+        irdepth: None,
+    } => {
+        (0x80000004, UNCOMPRESSED),
+        (0x80000008, UNCOMPRESSED),
+        (0x8000000c, Kind::new_auipc(1, 0x0)),
+        (0x80000010, UNCOMPRESSED),
+        (0x80000014, COMPRESSED, notify),
+        (0x80000014, tracer::item::Kind::Notify)
+    }
+);
+
+#[test]
+fn sync_address_policy() {
+    use tracer::{SyncAddressPolicy, error::Error};
+
+    let build = |policy| {
+        let binary = binary::from_sorted_map(test_bin_1()).expect("Could not build binary");
+        tracer::builder()
+            .with_binary(binary)
+            .with_sync_address_policy(policy)
+            .build::<stack::NoStack, _>()
+            .expect("Could not build tracer")
+    };
+
+    let mut tracer = build(SyncAddressPolicy::Error);
+    assert_eq!(
+        tracer.process_te_inst(&start_packet(0)),
+        Err(Error::InvalidSyncAddress(0)),
+    );
+    assert_eq!(
+        tracer.process_te_inst(&start_packet(0x90000000)),
+        Err(Error::InvalidSyncAddress(0x90000000)),
+    );
+    assert!(!tracer.is_recovering());
+    tracer
+        .process_te_inst(&start_packet(0x80000010))
+        .expect("Could not process payload");
+    assert_eq!(tracer.count(), 2);
+
+    let mut tracer = build(SyncAddressPolicy::Warn);
+    tracer
+        .process_te_inst(&start_packet(0x80000010))
+        .expect("Could not process payload");
+    assert_eq!(tracer.take_address_warning(), None);
+    assert_eq!(tracer.by_ref().count(), 2);
+    assert!(tracer.process_te_inst(&start_packet(0x90000000)).is_err());
+    assert_eq!(tracer.take_address_warning(), Some(0x90000000));
+    assert_eq!(tracer.take_address_warning(), None);
+}
+
+#[test]
+fn branch_policy() {
+    use tracer::{BranchPolicy, Confidence, error::Error};
+
+    let build = |policy| {
+        let binary = binary::from_sorted_map(test_bin_1()).expect("Could not build binary");
+        tracer::builder()
+            .with_binary(binary)
+            .with_branch_policy(policy)
+            .build::<stack::NoStack, _>()
+            .expect("Could not build tracer")
+    };
+    let branch: payload::InstructionTrace = payload::Branch {
+        branch_map: branch::Map::new(1, 0),
+        address: Some(payload::AddressInfo {
+            address: 0x20 - 0x10,
+            notify: false,
+            updiscon: false,
+            irdepth: None,
+        }),
+    }
+    .into();
+
+    let mut tracer = build(BranchPolicy::Error);
+    tracer
+        .process_te_inst(&start_packet(0x80000010))
+        .expect("Could not process payload");
+    assert_eq!(tracer.by_ref().count(), 2);
+    tracer
+        .process_te_inst(&branch)
+        .expect("Could not process payload");
+    assert_eq!(
+        tracer.by_ref().find_map(Result::err),
+        Some(Error::UnresolvableBranch)
+    );
+    assert!(!tracer.is_speculative());
+
+    let mut tracer = build(BranchPolicy::AssumeNotTaken);
+    tracer
+        .process_te_inst(&start_packet(0x80000010))
+        .expect("Could not process payload");
+    assert_eq!(tracer.by_ref().count(), 2);
+    tracer
+        .process_te_inst(&branch)
+        .expect("Could not process payload");
+    let item = tracer.by_ref().map(Result::unwrap).last();
+    assert_eq!(item.map(|i| i.pc()), Some(0x80000020));
+    assert_eq!(item.map(|i| i.confidence()), Some(Confidence::Speculative));
+    assert!(tracer.is_speculative());
+    tracer
+        .process_te_inst(&start_packet(0x80000026))
+        .expect("Could not process payload");
+    let item = tracer.by_ref().map(Result::unwrap).last();
+    assert_eq!(item.map(|i| i.pc()), Some(0x80000026));
+    assert_eq!(item.map(|i| i.confidence()), Some(Confidence::Certain));
+    assert!(!tracer.is_speculative());
+}
+
+#[test]
+fn gap_confidence() {
+    use tracer::Confidence;
+
+    let binary = binary::from_sorted_map(test_bin_1()).expect("Could not build binary");
+    let mut tracer: tracer::Tracer<_> = tracer::builder()
+        .with_binary(binary)
+        .build()
+        .expect("Could not build tracer");
+    tracer
+        .process_te_inst(&start_packet(0x80000010))
+        .expect("Could not process payload");
+    assert!(tracer.all(|i| i.unwrap().confidence() == Confidence::Certain));
+
+    let lost: payload::InstructionTrace = sync::Support {
+        ienable: true,
+        qual_status: sync::QualStatus::TraceLost,
+        ..Default::default()
+    }
+    .into();
+    tracer
+        .process_te_inst(&lost)
+        .expect("Could not process payload");
+    tracer.by_ref().for_each(|i| assert!(i.is_ok()));
+    tracer
+        .process_te_inst(&start_packet(0x80000014))
+        .expect("Could not process payload");
+    let confidences = tracer.by_ref().map(|i| i.unwrap().confidence());
+    assert!(confidences.eq([Confidence::Gap, Confidence::Certain]));
+}
+
+#[test]
+fn speculation_limit() {
+    use tracer::{BranchPolicy, error::Error};
+
+    let build = |limit| {
+        let binary = binary::from_sorted_map(test_bin_1()).expect("Could not build binary");
+        tracer::builder()
+            .with_binary(binary)
+            .with_branch_policy(BranchPolicy::AssumeTaken)
+            .with_speculation_limit(limit)
+            .build::<stack::NoStack, _>()
+            .expect("Could not build tracer")
+    };
+    // The loop closed by the `bltu` at 0x8000001c is never left if the branch
+    // is assumed taken, so the address is never reached.
+    let branch: payload::InstructionTrace = payload::Branch {
+        branch_map: branch::Map::new(0, 0),
+        address: Some(payload::AddressInfo {
+            address: 0x20 - 0x14,
+            notify: false,
+            updiscon: false,
+            irdepth: None,
+        }),
+    }
+    .into();
+
+    let mut tracer = build(16);
+    tracer
+        .process_te_inst(&start_packet(0x80000014))
+        .expect("Could not process payload");
+    assert_eq!(tracer.by_ref().count(), 2);
+    tracer
+        .process_te_inst(&branch)
+        .expect("Could not process payload");
+    let (count, last) = tracer
+        .by_ref()
+        .fold((0, None), |(n, _), r| (n + 1, Some(r)));
+    assert_eq!(
+        last.map(|r| r.err()),
+        Some(Some(Error::SpeculationLimit(16)))
+    );
+    assert!(count <= 5 + 16 + 1);
+    assert_eq!(tracer.next(), None);
+
+    // The default limit is reached regardless of the number of branches
+    let mut tracer = build(1024);
+    tracer
+        .process_te_inst(&start_packet(0x80000014))
+        .expect("Could not process payload");
+    assert_eq!(tracer.by_ref().count(), 2);
+    tracer
+        .process_te_inst(&branch)
+        .expect("Could not process payload");
+    assert_eq!(
+        tracer.by_ref().find_map(Result::err),
+        Some(Error::SpeculationLimit(1024))
+    );
+    assert_eq!(tracer.next(), None);
+    assert_eq!(tracer.consumed_branches().count(), 0);
+
+    // Tracing continues with the next sync
+    tracer
+        .process_te_inst(&start_packet(0x80000014))
+        .expect("Could not process payload");
+    assert_eq!(tracer.by_ref().count(), 2);
+    assert!(!tracer.is_speculative());
+}
+
+#[test]
+fn predict_sync() {
+    let binary = binary::from_sorted_map(test_bin_1()).expect("Could not build binary");
+    let mut tracer: tracer::Tracer<_> = tracer::builder()
+        .with_binary(binary)
+        .build()
+        .expect("Could not build tracer");
+    assert_eq!(tracer.predict_sync(), None);
+
+    tracer
+        .process_te_inst(&start_packet(0x80000010))
+        .expect("Could not process payload");
+    assert_eq!(tracer.predict_sync(), None);
+    assert_eq!(tracer.by_ref().count(), 2);
+    assert_eq!(tracer.predict_sync(), Some((0x80000014, Privilege::User)));
+
+    // The branch is reported as not taken
+    tracer
+        .process_te_inst(&start_packet(0x8000001c))
+        .expect("Could not process payload");
+    tracer.by_ref().for_each(|i| assert!(i.is_ok()));
+    assert_eq!(tracer.predict_sync(), Some((0x80000020, Privilege::User)));
+
+    // `c.jr` is an uninferable jump
+    tracer
+        .process_te_inst(&start_packet(0x80000024))
+        .expect("Could not process payload");
+    tracer.by_ref().for_each(|i| assert!(i.is_ok()));
+    assert_eq!(tracer.predict_sync(), None);
+}
+
+#[test]
+fn seeded_tracer() {
+    let binary = binary::from_sorted_map(test_bin_1()).expect("Could not build binary");
+    let builder = tracer::builder()
+        .with_binary(binary)
+        .with_params(&config::Parameters {
+            return_stack_size_p: 2,
+            ..Default::default()
+        });
+    let context = Context {
+        privilege: Privilege::Machine,
+        context: 0,
+    };
+    let seed = tracer::Seed {
+        pc: 0x80000010,
+        context,
+        return_stack: &[0x80000026, 0x80000030],
+        ..Default::default()
+    };
+    let mut tracer: tracer::Tracer<_, stack::StaticStack<4>> =
+        builder.build_seeded(&seed).expect("Could not build tracer");
+    assert!(tracer.is_tracing());
+    assert_eq!(stack::ReturnStack::depth(tracer.return_stack()), 2);
+    let mut items = tracer.by_ref().map(Result::unwrap);
+    assert_eq!(items.next().map(|i| *i.kind()), Some(context.into()));
+    assert_eq!(items.last().map(|i| i.pc()), Some(0x80000010));
+    assert_eq!(
+        tracer.predict_sync(),
+        Some((0x80000014, Privilege::Machine))
+    );
+
+    tracer
+        .process_te_inst(&start_packet(0x80000018))
+        .expect("Could not process payload");
+    let pcs = tracer.by_ref().map(|i| i.unwrap().pc());
+    assert!(pcs.eq([0x80000014, 0x80000016, 0x80000018, 0x80000018]));
+
+    let seed = tracer::Seed {
+        return_stack: &[0; 5],
+        ..seed
+    };
+    let res: Result<tracer::Tracer<_, stack::StaticStack<4>>, _> = builder.build_seeded(&seed);
+    assert_eq!(
+        res.err(),
+        Some(tracer::error::Error::CannotConstructIrStack(5))
+    );
+
+    let seed = tracer::Seed {
+        pc: 0x1000,
+        return_stack: &[],
+        ..seed
+    };
+    let res: Result<tracer::Tracer<_, stack::StaticStack<4>>, _> = builder.build_seeded(&seed);
+    assert!(matches!(
+        res.err(),
+        Some(tracer::error::Error::CannotGetInstruction(_, 0x1000))
+    ));
+}
+
+#[test]
+fn fault_instructions() {
+    let binary = binary::from_sorted_map([
+        (0x80000000, UNCOMPRESSED),
+        (0x80000004, Kind::ecall.into()),
+        (0x80000100, COMPRESSED),
+    ])
+    .expect("Could not build binary");
+    let mut tracer: tracer::Tracer<_> = tracer::builder()
+        .with_binary(binary)
+        .with_fault_instructions(true)
+        .build()
+        .expect("Could not build tracer");
+    tracer
+        .process_te_inst(&start_packet(0x80000000))
+        .expect("Could not process payload");
+    tracer
+        .by_ref()
+        .for_each(|i| assert!(i.unwrap().fault().is_none()));
+
+    let trap: payload::InstructionTrace = sync::Trap {
+        branch: true,
+        ctx: Default::default(),
+        thaddr: true,
+        address: 0x80000100,
+        info: trap::Info {
+            ecause: 11,
+            tval: Some(0),
+        },
+    }
+    .into();
+    tracer
+        .process_te_inst(&trap)
+        .expect("Could not process payload");
+    let item = tracer.find_map(|i| i.ok().filter(|i| i.trap().is_some()));
+    let item = item.expect("No trap item");
+    assert_eq!(item.pc(), 0x80000004);
+    assert_eq!(item.fault(), Some(&Kind::ecall.into()));
+}
+
+#[test]
+fn stamped_item_order() {
+    use tracer::item::{Key, Stamper};
+
+    let mut stamper = Stamper::new(5);
+    let items = [
+        stamper.stamp(0, Some(20), Item::new(0x10, COMPRESSED.into())),
+        stamper.stamp(0, None, Item::new(0x12, COMPRESSED.into())),
+        stamper.stamp(1, Some(10), Item::new(0x20, COMPRESSED.into())),
+        stamper.stamp(0, Some(10), Item::new(0x14, COMPRESSED.into())),
+    ];
+    assert_eq!(stamper.next_sequence(), 9);
+    let mut sorted = items;
+    sorted.sort();
+    assert_eq!(sorted.map(|s| s.item.pc()), [0x12, 0x14, 0x20, 0x10]);
+    assert_eq!(
+        sorted[1].key,
+        Key {
+            timestamp: Some(10),
+            hart: 0,
+            sequence: 8,
+        }
+    );
+    assert_ne!(items[0], items[1]);
+    assert_eq!(items[0], items[0]);
+}
+
+#[test]
+fn virtualized_binary() {
+    let host = binary::from_sorted_map([(0x80000000, COMPRESSED)]).unwrap();
+    let guest = binary::from_sorted_map([(0x80000000, UNCOMPRESSED)]).unwrap();
+    let mut tracer: tracer::Tracer<_> = tracer::builder()
+        .with_binary(binary::Virtualized::new(host, guest))
+        .build()
+        .unwrap();
+
+    let ctx = sync::Context {
+        privilege: Privilege::VirtSupervisor,
+        ..Default::default()
+    };
+    let start: payload::InstructionTrace = sync::Start {
+        branch: true,
+        ctx,
+        address: 0x80000000,
+    }
+    .into();
+    tracer.process_te_inst(&start).unwrap();
+    let item = tracer.by_ref().map(Result::unwrap).last();
+    assert_eq!(
+        item.as_ref().and_then(Item::instruction),
+        Some(&UNCOMPRESSED)
+    );
+    assert!(tracer.binary().is_virtualized());
+
+    let ctx = sync::Context {
+        privilege: Privilege::Machine,
+        ..Default::default()
+    };
+    let payload: payload::InstructionTrace = sync::Synchronization::Context(ctx).into();
+    tracer.process_te_inst(&payload).unwrap();
+    assert_eq!(tracer.by_ref().count(), 1);
+    assert!(!tracer.binary().is_virtualized());
+}
+
+fn start_packet(address: u64) -> payload::InstructionTrace {
+    sync::Start {
+        branch: true,
+        ctx: Default::default(),
+        address,
+    }
+    .into()
+}
+
+fn test_bin_1() -> [(u64, instruction::Instruction); 17] {
+    [
+        (0x80000000, Kind::new_auipc(13, 0x0).into()),
+        (0x80000004, UNCOMPRESSED),
+        (0x80000008, UNCOMPRESSED),
+        (0x8000000c, Kind::new_auipc(1, 0x0).into()),
+        (0x80000010, UNCOMPRESSED),
+        // _copy_code
+        (0x80000014, COMPRESSED),
+        (0x80000016, COMPRESSED),
+        (0x80000018, COMPRESSED),
+        (0x8000001a, COMPRESSED),
+        (0x8000001c, Kind::new_bltu(11, 12, -8).into()),
+        (0x80000020, Kind::fence_i.into()),
+        (0x80000024, Kind::new_c_jr(1).into()),
+        // _entry
+        (0x80000026, UNCOMPRESSED),
+        (0x8000002a, UNCOMPRESSED),
+        (0x8000002e, COMPRESSED),
+        // _die
+        (0x80000030, Kind::wfi.into()),
+        (0x80000034, Kind::new_c_j(0, -4).into()),
+    ]
+}
+
+#[test]
+fn error_code_roundtrip() {
+    use crate::binary::error::NoInstruction;
+    use crate::code::{Code, Domain};
+    use crate::packet;
+    use core::num::{NonZeroU8, NonZeroUsize};
+
+    let packet_errors = [
+        packet::Error::UnknownFmt(0, Some(3)),
+        packet::Error::UnknownFmt(2, None),
+        packet::Error::InsufficientData(NonZeroUsize::new(7).unwrap()),
+        packet::Error::PayloadTooBig(300),
+    ];
+    for error in packet_errors {
+        let code = Code::from_bytes(Code::from(error).to_bytes());
+        assert_eq!(code.domain(), Some(Domain::Packet));
+        assert_eq!(packet::Error::try_from(code), Ok(error));
+    }
+
+    let tracer_errors: [tracer::error::Error<NoInstruction>; 5] = [
+        tracer::error::Error::UnsupportedFeature("implicit return"),
+        tracer::error::Error::UnprocessedBranches(NonZeroU8::new(3).unwrap()),
+        tracer::error::Error::CannotGetInstruction(NoInstruction, 0x80000010),
+        tracer::error::Error::InvalidSyncAddress(0x90000000),
+        tracer::error::Error::SpeculationLimit(1024),
+    ];
+    for error in tracer_errors {
+        let code = Code::from(&error);
+        assert_eq!(code.domain(), Some(Domain::Tracer));
+        assert_eq!(code.try_into(), Ok(error));
+    }
+
+    let unknown: tracer::error::Error<NoInstruction> =
+        tracer::error::Error::UnsupportedFeature("time travel");
+    assert_eq!(
+        Code::from(unknown).try_into(),
+        Ok(tracer::error::Error::<NoInstruction>::UnsupportedFeature(
+            "unknown"
+        ))
+    );
+
+    let foreign = Code::from(packet::Error::BadBranchFmt);
+    assert_eq!(
+        tracer::error::Error::<NoInstruction>::try_from(foreign),
+        Err(foreign)
+    );
+}
+
+#[test]
+fn filter_expressions() {
+    use tracer::filter::{Error, Filter};
+
+    let info = trap::Info {
+        ecause: 2,
+        tval: Some(0),
+    };
+    let trap: Item = Item::new(0x80000010, info.into());
+    let ctx: Item = Item::new(
+        0x80000010,
+        Context {
+            privilege: Privilege::Supervisor,
+            ..Default::default()
+        }
+        .into(),
+    );
+    let nop: Item = Item::new(0x80000010, Kind::c_nop.into());
+    let gap = nop.with_confidence(tracer::item::Confidence::Gap);
+
+    let matches = |expr: &str| {
+        let filter: Filter = expr.parse().unwrap();
+        [trap, ctx, nop, gap].map(|i| filter.matches(3, &i))
+    };
+    assert_eq!(matches("kind == trap"), [true, false, false, false]);
+    assert_eq!(matches("!(kind == trap)"), [false, true, true, true]);
+    assert_eq!(matches("privilege == s"), [false, true, false, false]);
+    assert_eq!(matches("ecause != 1"), [true, false, false, false]);
+    assert_eq!(
+        matches("confidence == gap || hart < 2"),
+        [false, false, false, true]
+    );
+    assert_eq!(
+        matches("kind == insn && pc in [0x8000_0000..0x8000_0010] || kind == context"),
+        [false, true, false, false]
+    );
+    assert_eq!(matches("pc >= 2147483664 && hart == 0x3"), [true; 4]);
+
+    assert_eq!("pc ==".parse::<Filter>(), Err(Error::UnexpectedEnd));
+    assert_eq!("ip == 1".parse::<Filter>(), Err(Error::UnknownField(0)));
+    assert_eq!("kind == nop".parse::<Filter>(), Err(Error::InvalidValue(8)));
+    assert_eq!("pc = 1".parse::<Filter>(), Err(Error::UnexpectedToken(3)));
+    assert_eq!("(pc == 1".parse::<Filter>(), Err(Error::UnexpectedEnd));
+    assert_eq!(
+        "pc == 1 pc".parse::<Filter>(),
+        Err(Error::UnexpectedToken(8))
+    );
+    assert_eq!(
+        "pc == 1 || pc == 2 || pc == 3".parse::<Filter<4>>(),
+        Err(Error::TooComplex)
+    );
+    assert!("((pc == 1))".parse::<Filter<4>>().is_ok());
+    assert_eq!("!!!!pc == 1".parse::<Filter<4>>(), Err(Error::TooComplex));
+
+    static NESTED: [u8; 1 << 16] = [b'('; 1 << 16];
+    let nested = core::str::from_utf8(&NESTED).unwrap();
+    assert_eq!(nested.parse::<Filter>(), Err(Error::TooComplex));
+}
+
+#[test]
+fn tracer_capabilities() {
+    use crate::packet::unit::ReferenceIOptions;
+
+    let capabilities = tracer::capabilities();
+    assert!(capabilities.versions.contains(&config::Version::V2));
+    let ioptions = ReferenceIOptions {
+        implicit_return: true,
+        ..Default::default()
+    };
+    assert_eq!(capabilities.check_ioptions(&ioptions), Ok(()));
+    let ioptions = ReferenceIOptions {
+        branch_prediction: true,
+        ..Default::default()
+    };
+    assert_eq!(
+        capabilities.check_ioptions(&ioptions),
+        Err("branch prediction")
+    );
+    assert_eq!(
+        capabilities.check_ioptions(&crate::packet::unit::NoOptions),
+        Ok(())
+    );
+}
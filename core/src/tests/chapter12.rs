@@ -186,3 +186,17 @@ trace_test!(
         (0x80001110, Kind::new_c_beqz(10, 0x024))
     }
 );
+
+#[cfg(feature = "fixtures")]
+#[test]
+fn fixtures() {
+    use crate::fixtures;
+
+    for fixture in fixtures::ALL {
+        fixture
+            .verify::<stack::StaticStack<8>>(tracer::builder())
+            .unwrap_or_else(|e| panic!("{}: {e}", fixture.name));
+        let builder = tracer::builder().with_address_mode(config::AddressMode::Full);
+        assert!(fixture.verify::<stack::StaticStack<8>>(builder).is_err());
+    }
+}
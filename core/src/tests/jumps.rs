@@ -100,7 +100,7 @@ trace_test!(
         irdepth: None,
     } => {
         (0x80000012, Kind::new_jalr(1, 13, 0x12)),
-        (0x80000020, COMPRESSED),
+        (0x80000020, COMPRESSED, sequential_jump),
         (0x80000022, Kind::new_c_jr(1)),
         (0x80000016, Kind::new_lui(13,0x80000000u32 as i32))
     }
@@ -111,7 +111,7 @@ trace_test!(
         irdepth: None,
     } => {
         (0x8000001a, Kind::new_jalr(1, 13, 0x20)),
-        (0x80000020, COMPRESSED),
+        (0x80000020, COMPRESSED, sequential_jump),
         (0x80000022, Kind::new_c_jr(1)),
         (0x8000001e, Kind::new_c_j(0, 0x6))
     }
@@ -155,11 +155,11 @@ trace_test!(
         irdepth: None,
     } => {
         (0x80000022, Kind::new_c_jr(1)),
-        (0x8000000e, Kind::new_auipc(13, 0)),
+        (0x8000000e, Kind::new_auipc(13, 0), implicit_return),
         (0x80000012, Kind::new_jalr(1, 13, 0x12)),
         (0x80000020, COMPRESSED),
         (0x80000022, Kind::new_c_jr(1)),
-        (0x80000016, Kind::new_lui(13,0x80000000u32 as i32)),
+        (0x80000016, Kind::new_lui(13,0x80000000u32 as i32), implicit_return),
         (0x8000001a, Kind::new_jalr(1, 13, 0x20)),
         (0x80000020, COMPRESSED)
     }
@@ -170,11 +170,50 @@ trace_test!(
         irdepth: None,
     } => {
         (0x80000022, Kind::new_c_jr(1)),
-        (0x8000001e, Kind::new_c_j(0, 0x6)),
+        (0x8000001e, Kind::new_c_j(0, 0x6), implicit_return),
         (0x80000024, Kind::wfi)
     }
 );
 
+#[test]
+fn irdepth_depth_only() {
+    let params = config::Parameters {
+        return_stack_size_p: 2,
+        ..Default::default()
+    };
+    let mut tracer: tracer::Tracer<_, stack::DepthOnly> = tracer::builder()
+        .with_binary(binary::from_sorted_map(test_bin_fncalls()))
+        .with_params(&params)
+        .with_implicit_return(true)
+        .build()
+        .expect("Could not build tracer");
+    let mut process = |payload: payload::InstructionTrace| {
+        tracer
+            .process_te_inst(&payload)
+            .expect("Could not process packet");
+        tracer
+            .by_ref()
+            .map(|i| i.expect("Could not trace").pc())
+            .last()
+    };
+
+    assert_eq!(process(start_packet(0x80000000)), Some(0x80000000));
+    let payload = payload::AddressInfo {
+        address: 0x20,
+        notify: false,
+        updiscon: false,
+        irdepth: Some(1),
+    };
+    assert_eq!(process(payload.into()), Some(0x80000020));
+    let payload = payload::AddressInfo {
+        address: 0x0e - 0x20,
+        notify: false,
+        updiscon: false,
+        irdepth: Some(0),
+    };
+    assert_eq!(process(payload.into()), Some(0x8000000e));
+}
+
 fn test_bin_fncalls() -> [(u64, instruction::Instruction); 13] {
     [
         (0x80000000, Kind::new_auipc(13, 0).into()),
@@ -0,0 +1,129 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+use generator::{model, step};
+use payload::InstructionTrace;
+
+/// [`TestStep`] with a timestamp
+#[derive(Copy, Clone, Debug)]
+struct Timed(TestStep, u64);
+
+impl step::Step for Timed {
+    fn address(&self) -> u64 {
+        self.0.address()
+    }
+
+    fn last_offset(&self) -> u64 {
+        self.0.last_offset()
+    }
+
+    fn kind(&self) -> step::Kind {
+        self.0.kind()
+    }
+
+    fn ctype(&self) -> generator::hart2enc::CType {
+        self.0.ctype()
+    }
+
+    fn context(&self) -> Context {
+        self.0.context()
+    }
+
+    fn timestamp(&self) -> Option<u64> {
+        Some(self.1)
+    }
+}
+
+/// Run a [`model::Model`] on straight-line code, collecting sync addresses
+///
+/// Steps have timestamps `10` apart. Returns the number of syncs and their
+/// addresses.
+fn sync_addresses(resync: model::Resync) -> (usize, [u64; 8]) {
+    use step::Step;
+
+    let mut converter = ItemConverter::default();
+    let mut steps = (0..16).filter_map(|i| {
+        let kind = UNCOMPRESSED.into();
+        converter
+            .feed_item(0x80000000 + 4 * i, kind, Default::default())
+            .map(|(s, _)| Timed(s, 10 * i))
+    });
+
+    let params = config::Parameters {
+        notime_p: false,
+        time_width_p: 8.try_into().unwrap(),
+        ..Default::default()
+    };
+    let builder = crate::packet::builder()
+        .with_params(&params)
+        .with_hart_index_width(8);
+    let generator = generator::builder()
+        .with_params(&params)
+        .build()
+        .expect("Could not build generator");
+    let mut model = model::Model::new(generator, builder)
+        .with_format(model::Format::Encap { flow: 0, src_id: 3 })
+        .with_resync(resync);
+
+    let mut syncs = (0, [0; 8]);
+    let mut check = |payload: &InstructionTrace, packet: &[u8]| {
+        let decoded = builder
+            .decoder(packet)
+            .decode_encap_packet()
+            .expect("Could not decode packet")
+            .into_normal()
+            .expect("Unexpected null packet");
+        assert_eq!(decoded.src_id(), 3);
+        let decoded = decoded.decode_payload().expect("Could not decode payload");
+        assert_eq!(decoded.as_instruction_trace(), Some(payload));
+        if let InstructionTrace::Synchronization(sync::Synchronization::Start(start)) = payload {
+            syncs.1[syncs.0] = start.address;
+            syncs.0 += 1;
+        }
+    };
+    model
+        .begin_qualification(Default::default(), Default::default(), &mut check)
+        .expect("Could not begin qualification");
+    steps
+        .try_for_each(|s| {
+            assert_eq!(s.instruction_count(), 1);
+            model.process_step(s, false, &mut check).map(|_| ())
+        })
+        .expect("Could not process step");
+    model
+        .end_qualification(true, &mut check)
+        .expect("Could not end qualification");
+    syncs
+}
+
+#[test]
+fn model_resync_never() {
+    assert_eq!(sync_addresses(model::Resync::Never).0, 1);
+}
+
+#[test]
+fn model_resync_steps() {
+    let (n, syncs) = sync_addresses(model::Resync::Steps(4.try_into().unwrap()));
+    assert_eq!(syncs[..n], [0x80000000, 0x80000014, 0x80000028, 0x8000003c]);
+}
+
+#[test]
+fn model_resync_instructions() {
+    let (n, syncs) = sync_addresses(model::Resync::Instructions(4.try_into().unwrap()));
+    assert_eq!(syncs[..n], [0x80000000, 0x80000014, 0x80000028, 0x8000003c]);
+}
+
+#[test]
+fn model_resync_payloads() {
+    // Straight-line code does not generate any payloads besides syncs
+    let (n, syncs) = sync_addresses(model::Resync::Payloads(2.try_into().unwrap()));
+    assert_eq!(syncs[..n], [0x80000000]);
+}
+
+#[test]
+fn model_resync_time() {
+    let (n, syncs) = sync_addresses(model::Resync::Time(35.try_into().unwrap()));
+    assert_eq!(syncs[..n], [0x80000000, 0x80000010, 0x80000020, 0x80000030]);
+}
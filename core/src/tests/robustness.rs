@@ -0,0 +1,132 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+use crate::packet::{self, payload::Payload};
+use binary::Adaptable;
+use config::AddressMode;
+use instruction::base;
+
+/// Number of random traces to process
+#[cfg(not(debug_assertions))]
+const ROUNDS: usize = 1024;
+#[cfg(debug_assertions)]
+const ROUNDS: usize = 128;
+
+/// Size of the random code region
+#[cfg(not(debug_assertions))]
+const CODE_SIZE: usize = 0x4000;
+#[cfg(debug_assertions)]
+const CODE_SIZE: usize = 0x1000;
+
+/// Maximum number of items to extract per payload
+const MAX_ITEMS: usize = 256;
+
+/// Simple xorshift generator for reproducible pseudo-random data
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn fill(&mut self, buffer: &mut [u8]) {
+        buffer.iter_mut().for_each(|b| *b = self.next() as u8);
+    }
+}
+
+#[test]
+fn random_input_does_not_panic() {
+    let mut rng = Rng(0x2545f4914f6cdd1d);
+    let mut code = [0u8; CODE_SIZE];
+    let mut trace = [0u8; 96];
+
+    for round in 0..ROUNDS {
+        rng.fill(&mut code);
+        rng.fill(&mut trace);
+
+        let params = config::Parameters {
+            iaddress_width_p: core::num::NonZeroU8::new(12 + (round % 3) as u8).unwrap(),
+            return_stack_size_p: (round % 3) as u8,
+            sijump_p: round % 5 == 0,
+            ..Default::default()
+        };
+        let mode = if round % 2 == 0 {
+            AddressMode::Delta
+        } else {
+            AddressMode::Full
+        };
+        let base = [base::Set::Rv32I, base::Set::Rv64I][(round / 2) % 2];
+        let tracer_builder = tracer::builder()
+            .with_params(&params)
+            .with_address_mode(mode)
+            .with_implicit_return(round % 3 == 0);
+        let decoder_builder = packet::builder().with_params(&params);
+
+        let mut tracer: tracer::Tracer<_, stack::StaticStack<4>> = tracer_builder
+            .with_binary(binary::from_segment(&code[..], base).with_offset(0))
+            .build()
+            .unwrap();
+        let mut feed = |payload: Payload<_, _>| {
+            if tracer.process_payload(&payload).is_ok() {
+                tracer.by_ref().take(MAX_ITEMS).for_each(drop);
+            }
+        };
+
+        let mut decoder = decoder_builder.decoder(&trace);
+        while let Ok(packet) = decoder.decode_encap_packet() {
+            if let Some(payload) = packet.into_normal().and_then(|p| p.decode_payload().ok()) {
+                feed(payload);
+            }
+        }
+
+        let mut decoder = decoder_builder.decoder(&trace);
+        while let Ok(packet) = decoder.decode_smi_packet() {
+            if let Ok(payload) = packet.decode_payload() {
+                feed(payload);
+            }
+        }
+
+        let mut decoder = decoder_builder.decoder(&trace);
+        while let Ok(packet) = decoder.decode_esp32_packet() {
+            if let Some(payload) = packet.into_normal().and_then(|p| p.decode_payload().ok()) {
+                feed(payload);
+            }
+        }
+
+        let mut decoder = packet::builder().with_params(&params).decoder(&trace);
+        while decoder.bytes_left() > 0 {
+            let Ok(payload) = decoder.decode_payload() else {
+                break;
+            };
+            if tracer.process_te_inst(&payload).is_ok() {
+                tracer.by_ref().take(MAX_ITEMS).for_each(drop);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "riscv-isa")]
+#[test]
+fn random_binary_riscv_isa_does_not_panic() {
+    use binary::Binary;
+
+    let mut rng = Rng(0x9e3779b97f4a7c15);
+    let mut code = [0u8; 0x400];
+    for target in ["RV32IMAC", "RV64IMAFDC"] {
+        let target: riscv_isa::Target = target.parse().unwrap();
+        for _ in 0..16 {
+            rng.fill(&mut code);
+            let mut bin = binary::from_segment(&code[..], target);
+            (0..code.len() as u64).step_by(2).for_each(|a| {
+                let _: Result<instruction::Instruction<riscv_isa::Instruction>, _> =
+                    bin.get_insn(a);
+                let _: Result<instruction::Instruction<riscv_isa::Compressed>, _> = bin.get_insn(a);
+            });
+        }
+    }
+}
@@ -276,7 +276,8 @@ trace_test!(
         (0x80000014, Kind::new_auipc(11, 0)),
         (0x80000018, UNCOMPRESSED),
         (0x8000001c, COMPRESSED),
-        (0x8000001e, UNCOMPRESSED, notify)
+        (0x8000001e, UNCOMPRESSED, notify),
+        (0x8000001e, tracer::item::Kind::Notify)
     }
     payload::AddressInfo {
         address: 0,
@@ -313,7 +314,8 @@ trace_test!(
         (0x80000014, Kind::new_auipc(11, 0)),
         (0x80000018, UNCOMPRESSED),
         (0x8000001c, COMPRESSED),
-        (0x8000001e, UNCOMPRESSED, notify)
+        (0x8000001e, UNCOMPRESSED, notify),
+        (0x8000001e, tracer::item::Kind::Notify)
     }
     sync::Support {
         ienable: true,
@@ -339,7 +341,8 @@ trace_test!(
         (0x80000014, Kind::new_auipc(11, 0)),
         (0x80000018, UNCOMPRESSED),
         (0x8000001c, COMPRESSED),
-        (0x8000001e, UNCOMPRESSED, notify)
+        (0x8000001e, UNCOMPRESSED, notify),
+        (0x8000001e, tracer::item::Kind::Notify)
     }
     sync::Support {
         ienable: true,
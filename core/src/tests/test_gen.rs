@@ -97,7 +97,11 @@ macro_rules! trace_test_helper {
 
 macro_rules! trace_check_def {
     ($t:ident, ($a:literal, $i:expr $(, $h:ident)*)) => {
-        assert_eq!($t.next(), Some(Ok(Item::new($a, $i.into()))));
+        let hints = ItemHints {
+            $($h: true,)*
+            ..Default::default()
+        };
+        assert_eq!($t.next(), Some(Ok(hints.apply(Item::new($a, $i.into())))));
     };
     ($t:ident, [$($i:tt),*; $n:literal]) => {
         (0..$n).for_each(|_| {
@@ -224,6 +228,23 @@ pub struct ItemHints {
     pub notify: bool,
     /// This branch was taken
     pub branch_taken: bool,
+    /// This item's PC is the target of a sequentially inferred jump
+    pub sequential_jump: bool,
+    /// This item's PC is the target of an implicit return
+    pub implicit_return: bool,
+}
+
+impl ItemHints {
+    /// Apply the hints relevant for tracing to the given [`item::Item`]
+    pub fn apply(self, item: item::Item) -> item::Item {
+        if self.sequential_jump {
+            item.with_inference(item::Inference::SequentialJump)
+        } else if self.implicit_return {
+            item.with_inference(item::Inference::ImplicitReturn)
+        } else {
+            item
+        }
+    }
 }
 
 /// Helper for constructing [`TestStep`]s from trace item definitions
@@ -322,6 +343,12 @@ impl ItemConverter {
                 };
                 Some((step, event))
             }
+            Kind::Notify => {
+                // Notifications are communicated via hints
+                self.ctype = ctype;
+                self.upper_immediate = prev_upper_immediate;
+                None
+            }
             Kind::Context(new_context) => {
                 let context = self.context;
                 self.context = new_context;
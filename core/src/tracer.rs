@@ -6,10 +6,11 @@
 //! [`InstructionTrace`] payloads and generates streams of tracing [`Item`]s.
 
 pub mod error;
+pub mod filter;
 pub mod item;
 mod state;
 
-pub use item::Item;
+pub use item::{Confidence, Inference, Item};
 
 use crate::binary::{self, Binary};
 use crate::config::{self, AddressMode, Features, Version};
@@ -38,7 +39,10 @@ use stack::ReturnStack;
 /// tracer. Feeding a payload while the items generated from the last payload
 /// are not exhaused results in an error.
 ///
-/// Tracers are constructed using a [`Builder`].
+/// Tracers are constructed using a [`Builder`]. Usually, tracing starts with a
+/// [`sync::Start`] payload. In controlled situations, e.g. when resuming from
+/// a checkpoint, a tracer may instead be [seeded][Builder::build_seeded] with
+/// a known initial state.
 ///
 /// # Recovery from failures
 ///
@@ -59,10 +63,10 @@ use stack::ReturnStack;
 /// iterating over the generated [`Item`]s.
 ///
 /// ```
-/// use riscv_etrace::tracer;
+/// use riscv_etrace_core::tracer;
 ///
-/// # use riscv_etrace::instruction::COMPRESSED;
-/// # let code = riscv_etrace::binary::from_sorted_map([(0x28, COMPRESSED)]);
+/// # use riscv_etrace_core::instruction::COMPRESSED;
+/// # let code = riscv_etrace_core::binary::from_sorted_map([(0x28, COMPRESSED)]);
 /// let parameters = Default::default();
 /// let mut tracer: tracer::Tracer<_> = tracer::builder()
 ///     .with_binary(code)
@@ -70,21 +74,16 @@ use stack::ReturnStack;
 ///     .build()
 ///     .unwrap();
 ///
-/// # use riscv_etrace::packet;
+/// # use riscv_etrace_core::packet;
 /// # use packet::payload::{InstructionTrace, Payload};
-/// # let payload: Payload = InstructionTrace::from(
-/// #   packet::sync::Start {
-/// #       branch: false,
-/// #       ctx: Default::default(),
-/// #       address: 0x28,
-/// #   }
-/// # )
-/// # .into();
+/// # let payload: Payload =
+/// #     InstructionTrace::from(packet::sync::Start::new(0x28).with_branch(false)).into();
 /// tracer.process_payload(&payload).unwrap();
 /// tracer.by_ref().for_each(|i| {
 ///     println!("PC: {:0x}", i.unwrap().pc());
 /// });
 /// ```
+#[derive(Clone)]
 pub struct Tracer<B, S = stack::NoStack, I = Option<instruction::Kind>>
 where
     B: Binary<I>,
@@ -96,6 +95,11 @@ where
     previous: Option<Event>,
     binary: B,
     address_mode: AddressMode,
+    region_base: u64,
+    address_policy: SyncAddressPolicy,
+    address_warning: Option<u64>,
+    fault_instructions: bool,
+    gap: bool,
     phantom: core::marker::PhantomData<I>,
 }
 
@@ -105,6 +109,41 @@ impl<B: Binary<I>, S: ReturnStack, I: Info + Clone> Tracer<B, S, I> {
         self.state.features()
     }
 
+    /// Get a reference of the [`ReturnStack`] used by this tracer
+    ///
+    /// Usage statistics of the stack may be recorded by using a
+    /// [`stack::Monitored`] stack.
+    pub fn return_stack(&self) -> &S {
+        self.state.return_stack()
+    }
+
+    /// Get the branches consumed since the last payload was processed
+    ///
+    /// The returned [`branch::Map`][types::branch::Map] holds the outcome of
+    /// all conditional branches for which [`Item`]s were yielded since the
+    /// last call to any of the `process_*` fns, oldest first. This allows
+    /// recovering the raw branch outcome stream without re-examining the
+    /// instructions. Outcomes assumed according to the [`BranchPolicy`] are
+    /// not included.
+    pub fn consumed_branches(&self) -> types::branch::Map {
+        self.state.consumed_branches()
+    }
+
+    /// Retrieve the region base applied to absolute addresses
+    ///
+    /// See [`Builder::with_region_base`] for details.
+    pub fn region_base(&self) -> u64 {
+        self.region_base
+    }
+
+    /// Set the region base applied to absolute addresses
+    ///
+    /// The new base applies to all payloads processed subsequently. See
+    /// [`Builder::with_region_base`] for details.
+    pub fn set_region_base(&mut self, base: u64) {
+        self.region_base = base;
+    }
+
     /// Get a reference of the [`Binary`] used by this tracer
     pub fn binary(&self) -> &B {
         &self.binary
@@ -155,22 +194,30 @@ impl<B: Binary<I>, S: ReturnStack, I: Info + Clone> Tracer<B, S, I> {
             initer.set_stack_depth(payload.implicit_return_depth());
             *(initer.get_branch_map_mut()) = Default::default();
             match self.address_mode {
-                AddressMode::Full => initer.set_address(0u64.wrapping_add_signed(info.address)),
+                AddressMode::Full => {
+                    initer.set_address(self.region_base.wrapping_add_signed(info.address))
+                }
                 AddressMode::Delta => initer.set_rel_address(info.address),
             }
             self.iter_state.handle_result(initer.reset_to_address())?;
             self.iter_state = IterationState::SingleItem;
+            self.gap = true;
 
             Ok(())
         } else {
             let previous = self.previous.take();
-            let updiscon_prev = self.state.previous_insn().is_uninferable_discon();
+            let updiscon_prev = self.state.previous_is_uninferable_discon();
 
             let mut initer = self.state.initializer(&mut self.binary)?;
             initer.set_stack_depth(payload.implicit_return_depth());
 
-            if let InstructionTrace::Branch(branch) = payload {
-                let res = initer.get_branch_map_mut().append(branch.branch_map);
+            let branch_map = match payload {
+                InstructionTrace::Branch(branch) => Some(branch.branch_map),
+                InstructionTrace::Extension(ext) => ext.branch_map(),
+                _ => None,
+            };
+            if let Some(branch_map) = branch_map {
+                let res = initer.get_branch_map_mut().append(branch_map);
                 self.iter_state
                     .handle_result(res)
                     .map_err(Error::CannotAddBranches)?;
@@ -179,7 +226,9 @@ impl<B: Binary<I>, S: ReturnStack, I: Info + Clone> Tracer<B, S, I> {
                 let notify = info.notify;
                 self.previous = Some(Event::Address { notify });
                 match self.address_mode {
-                    AddressMode::Full => initer.set_address(0u64.wrapping_add_signed(info.address)),
+                    AddressMode::Full => {
+                        initer.set_address(self.region_base.wrapping_add_signed(info.address))
+                    }
                     AddressMode::Delta => initer.set_rel_address(info.address),
                 }
 
@@ -210,12 +259,29 @@ impl<B: Binary<I>, S: ReturnStack, I: Info + Clone> Tracer<B, S, I> {
     ) -> Result<(), Error<B::Error>> {
         use sync::Synchronization;
 
+        let base = self.region_base;
+        match sync {
+            Synchronization::Start(start) => {
+                self.check_sync_address(base.wrapping_add(start.address))?
+            }
+            Synchronization::Trap(trap) => {
+                self.check_sync_address(base.wrapping_add(trap.address))?
+            }
+            _ => (),
+        }
+
+        // Tracing resumes after a gap if we recover from an error or if the
+        // encoder reported a loss of trace.
+        let after_gap =
+            self.is_recovering() || self.qual_status() == Some(sync::QualStatus::TraceLost);
+
         let previous = self.previous.take();
         match sync {
             Synchronization::Start(start) => {
                 let is_tracing = self.is_tracing() && !self.is_recovering();
 
-                let mut initer = self.sync_init(start.address, !is_tracing, !start.branch)?;
+                let address = base.wrapping_add(start.address);
+                let mut initer = self.sync_init(address, !is_tracing, !start.branch)?;
                 if is_tracing && previous != Some(Event::Trap { thaddr: false }) {
                     initer.set_condition(state::StopCondition::Sync {
                         context: start.ctx.into(),
@@ -229,10 +295,12 @@ impl<B: Binary<I>, S: ReturnStack, I: Info + Clone> Tracer<B, S, I> {
                         context: start.ctx.into(),
                         follow_up: true,
                     };
+                    self.gap = after_gap;
                 }
             }
             Synchronization::Trap(trap) => {
                 let thaddr = trap.thaddr;
+                let address = base.wrapping_add(trap.address);
                 self.previous = Some(Event::Trap { thaddr });
 
                 let epc = if self.is_recovering() {
@@ -243,7 +311,7 @@ impl<B: Binary<I>, S: ReturnStack, I: Info + Clone> Tracer<B, S, I> {
                 } else if trap.info.is_exception()
                     && previous != Some(Event::Trap { thaddr: false })
                 {
-                    let epc = (!trap.thaddr).then_some(trap.address);
+                    let epc = (!trap.thaddr).then_some(address);
                     self.state.exception_address(&mut self.binary, epc)?
                 } else {
                     self.state.current_pc()
@@ -251,10 +319,10 @@ impl<B: Binary<I>, S: ReturnStack, I: Info + Clone> Tracer<B, S, I> {
                 let res = if !thaddr {
                     let mut initer = self.state.initializer(&mut self.binary)?;
                     initer.set_stack_depth(None);
-                    initer.set_address(trap.address);
+                    initer.set_address(address);
                     initer.reset_to_address()
                 } else {
-                    let mut initer = self.sync_init(trap.address, false, !trap.branch)?;
+                    let mut initer = self.sync_init(address, false, !trap.branch)?;
                     initer.set_context(trap.ctx.into());
                     initer.reset_to_address()
                 };
@@ -265,6 +333,7 @@ impl<B: Binary<I>, S: ReturnStack, I: Info + Clone> Tracer<B, S, I> {
                     context: trap.ctx.into(),
                     follow_up: thaddr,
                 };
+                self.gap = after_gap;
             }
             Synchronization::Context(ctx) => {
                 let mut initer = self.state.initializer(&mut self.binary)?;
@@ -345,6 +414,88 @@ impl<B: Binary<I>, S: ReturnStack, I: Info + Clone> Tracer<B, S, I> {
         }
     }
 
+    /// Determine whether the items yielded may be based on assumed branches
+    ///
+    /// If the tracer was built with a [`BranchPolicy`] other than
+    /// [`BranchPolicy::Error`], it may assume outcomes for branches not
+    /// covered by a packet. This fn returns `true` if an outcome was assumed
+    /// since the tracer was last resynchronized, e.g. via a [`sync::Start`].
+    /// [`Item`]s yielded in the meantime carry [`Confidence::Speculative`].
+    pub fn is_speculative(&self) -> bool {
+        self.state.is_speculative()
+    }
+
+    /// Predict the address and privilege a following [`sync::Start`] reports
+    ///
+    /// If the next [`sync::Start`] is emitted for the instruction directly
+    /// following the last one yielded, it needs to report the address and
+    /// [`Privilege`][types::Privilege] returned by this fn for the trace to be
+    /// consistent. Co-debug environments may compare this prediction against
+    /// the encoder's actual output for localizing divergences.
+    ///
+    /// Returns [`None`] if the tracer is not tracing, is recovering, has items
+    /// left to yield or if the next address can not be determined from the
+    /// tracer's state alone, e.g. after an uninferable jump.
+    pub fn predict_sync(&self) -> Option<(u64, types::Privilege)> {
+        let depleted = matches!(self.iter_state, IterationState::FollowExec);
+        if !depleted || !self.state.is_fused() {
+            return None;
+        }
+        self.state
+            .predict_next_pc()
+            .map(|pc| (pc, self.state.privilege()))
+    }
+
+    /// Retrieve and clear the last suspicious address reported in a sync
+    ///
+    /// If the tracer was built with [`SyncAddressPolicy::Warn`], suspicious
+    /// addresses in [`sync::Start`] and [`sync::Trap`] payloads are recorded
+    /// rather than rejected. This fn returns the last address recorded, if
+    /// any, and clears it.
+    pub fn take_address_warning(&mut self) -> Option<u64> {
+        self.address_warning.take()
+    }
+
+    /// Validate an address reported in a [`sync::Synchronization`]
+    ///
+    /// An address is considered suspicious if it is zero or if no instruction
+    /// can be retrieved from the [`Binary`] at that address.
+    fn check_sync_address(&mut self, address: u64) -> Result<(), Error<B::Error>> {
+        if self.address_policy == SyncAddressPolicy::Allow {
+            return Ok(());
+        }
+
+        if address == 0 || self.binary.get_insn(address).is_err() {
+            match self.address_policy {
+                SyncAddressPolicy::Error => return Err(Error::InvalidSyncAddress(address)),
+                _ => self.address_warning = Some(address),
+            }
+        }
+        Ok(())
+    }
+
+    /// Initialize this tracer from a [`Seed`]
+    fn seed(&mut self, seed: &Seed<'_>) -> Result<(), Error<B::Error>> {
+        let depth = seed.return_stack.len();
+        if depth > self.state.return_stack().max_depth() {
+            return Err(Error::CannotConstructIrStack(depth));
+        }
+        self.check_sync_address(seed.pc)?;
+
+        let mut initer = self.sync_init(seed.pc, true, seed.branch_taken)?;
+        initer.set_context(seed.context);
+        let stack = initer.get_return_stack_mut();
+        seed.return_stack.iter().for_each(|a| stack.push(*a));
+        let res = initer.reset_to_address();
+        self.iter_state.handle_result(res)?;
+        self.iter_state = IterationState::ContextItem {
+            pc: None,
+            context: seed.context,
+            follow_up: true,
+        };
+        Ok(())
+    }
+
     /// Create a [`state::Initializer`] for [`sync::Synchronization`] variants
     fn sync_init(
         &mut self,
@@ -377,20 +528,22 @@ impl<B: Binary<I>, S: ReturnStack, I: Info + Clone> Tracer<B, S, I> {
 
         Ok(initer)
     }
-}
 
-impl<B: Binary<I>, S: ReturnStack, I: Info + Clone> Iterator for Tracer<B, S, I> {
-    type Item = Result<Item<I>, Error<B::Error>>;
-
-    fn next(&mut self) -> Option<Self::Item> {
+    /// Retrieve the next [`Item`] without annotating its [`Confidence`]
+    fn next_raw(&mut self) -> Option<Result<Item<I>, Error<B::Error>>> {
         match self.iter_state {
             IterationState::SingleItem => {
                 self.iter_state = IterationState::FollowExec;
 
-                Some(Ok(Item::new(
-                    self.state.current_pc(),
-                    self.state.current_insn().into(),
-                )))
+                // The state only retains a summary of the current instruction,
+                // so we need to retrieve the full one from the binary.
+                let pc = self.state.current_pc();
+                let res = self
+                    .binary
+                    .get_insn(pc)
+                    .map(|i| Item::new(pc, i.into()))
+                    .map_err(|e| Error::CannotGetInstruction(e, pc));
+                Some(self.iter_state.handle_result(res))
             }
             IterationState::TrapItem {
                 epc,
@@ -405,7 +558,14 @@ impl<B: Binary<I>, S: ReturnStack, I: Info + Clone> Iterator for Tracer<B, S, I>
                     follow_up,
                 };
 
-                Some(Ok(Item::new(epc, info.into())))
+                let item = Item::new(epc, info.into());
+                let fault = (self.fault_instructions && info.is_exception())
+                    .then(|| self.binary.get_insn(epc).ok())
+                    .flatten();
+                Some(Ok(match fault {
+                    Some(fault) => item.with_fault(fault),
+                    None => item,
+                }))
             }
             IterationState::ContextItem {
                 pc,
@@ -421,6 +581,10 @@ impl<B: Binary<I>, S: ReturnStack, I: Info + Clone> Iterator for Tracer<B, S, I>
                 let pc = pc.unwrap_or(self.state.current_pc());
                 Some(Ok(Item::new(pc, context.into())))
             }
+            IterationState::NotifyItem { pc } => {
+                self.iter_state = IterationState::FollowExec;
+                Some(Ok(Item::new(pc, item::Kind::Notify)))
+            }
             IterationState::FollowExec | IterationState::Depleting { .. } => {
                 let res = self
                     .state
@@ -430,6 +594,11 @@ impl<B: Binary<I>, S: ReturnStack, I: Info + Clone> Iterator for Tracer<B, S, I>
                         if let Some(ctx) = c {
                             self.iter_state = IterationState::SingleItem;
                             Item::new(p, ctx.into())
+                        } else if self.state.take_notified() {
+                            self.iter_state = IterationState::NotifyItem { pc: p };
+                            Item::new(p, i.into())
+                        } else if let Some(inference) = self.state.inference() {
+                            Item::new(p, i.into()).with_inference(inference)
                         } else {
                             Item::new(p, i.into())
                         }
@@ -439,6 +608,22 @@ impl<B: Binary<I>, S: ReturnStack, I: Info + Clone> Iterator for Tracer<B, S, I>
             IterationState::Recovering => None,
         }
     }
+}
+
+impl<B: Binary<I>, S: ReturnStack, I: Info + Clone> Iterator for Tracer<B, S, I> {
+    type Item = Result<Item<I>, Error<B::Error>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.next_raw()?;
+        let confidence = if core::mem::take(&mut self.gap) {
+            Confidence::Gap
+        } else if self.state.is_speculative() {
+            Confidence::Speculative
+        } else {
+            Confidence::Certain
+        };
+        Some(item.map(|i| i.with_confidence(confidence)))
+    }
 
     fn size_hint(&self) -> (usize, Option<usize>) {
         match self.iter_state {
@@ -454,6 +639,7 @@ impl<B: Binary<I>, S: ReturnStack, I: Info + Clone> Iterator for Tracer<B, S, I>
 
             // Single Item
             IterationState::SingleItem => (1, Some(1)),
+            IterationState::NotifyItem { .. } => (1, Some(1)),
 
             // Minimum 1 item, but could also be infinite
             IterationState::FollowExec => (0, None),
@@ -468,6 +654,118 @@ pub fn builder() -> Builder<binary::Empty> {
     Default::default()
 }
 
+/// Retrieve the [`Capabilities`] of [`Tracer`]s in this build
+///
+/// # Example
+///
+/// ```
+/// use riscv_etrace_core::packet::unit::ReferenceIOptions;
+/// use riscv_etrace_core::tracer;
+///
+/// let ioptions = ReferenceIOptions {
+///     implicit_return: true,
+///     ..Default::default()
+/// };
+/// assert_eq!(tracer::capabilities().check_ioptions(&ioptions), Ok(()));
+/// ```
+pub fn capabilities() -> Capabilities {
+    Capabilities {
+        full_address: true,
+        delta_address: true,
+        sequentially_inferred_jumps: true,
+        implicit_returns: true,
+        implicit_exceptions: false,
+        branch_prediction: false,
+        jump_target_cache: false,
+        versions: &[Version::V1, Version::V2],
+        unbounded_return_stack: cfg!(feature = "alloc"),
+        riscv_isa: cfg!(feature = "riscv-isa"),
+    }
+}
+
+/// Options and modes supported by [`Tracer`]s
+///
+/// Tooling may use this description for checking whether a trace encoder's
+/// configuration is supported before starting to trace, rather than
+/// encountering an [`Error::UnsupportedFeature`] mid-trace. Use
+/// [`capabilities`] for retrieving the capabilities of this build, which may
+/// depend on the cargo features enabled.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct Capabilities {
+    /// Whether addresses may be full addresses
+    pub full_address: bool,
+    /// Whether addresses may be differential addresses
+    pub delta_address: bool,
+    /// Whether sequentially inferable jumps may be omitted by the encoder
+    pub sequentially_inferred_jumps: bool,
+    /// Whether function return addresses may be omitted by the encoder
+    pub implicit_returns: bool,
+    /// Whether trap vector addresses may be omitted by the encoder
+    pub implicit_exceptions: bool,
+    /// Whether branch prediction may be enabled in the encoder
+    pub branch_prediction: bool,
+    /// Whether jump target caching may be enabled in the encoder
+    pub jump_target_cache: bool,
+    /// Trace protocol [`Version`]s supported
+    pub versions: &'static [Version],
+    /// Whether a [`ReturnStack`] without fixed capacity is available
+    pub unbounded_return_stack: bool,
+    /// Whether instructions may be decoded via the `riscv-isa` crate
+    pub riscv_isa: bool,
+}
+
+impl Capabilities {
+    /// Check whether the given [`IOptions`] are supported
+    ///
+    /// Returns the name of the first option which is active but not
+    /// supported as an error. Options on which the [`IOptions`] do not carry
+    /// any information are considered supported.
+    pub fn check_ioptions(&self, ioptions: &impl IOptions) -> Result<(), &'static str> {
+        let checks = [
+            (
+                ioptions.address_mode() == Some(AddressMode::Full),
+                self.full_address,
+                "full address",
+            ),
+            (
+                ioptions.address_mode() == Some(AddressMode::Delta),
+                self.delta_address,
+                "delta address",
+            ),
+            (
+                ioptions.sequentially_inferred_jumps() == Some(true),
+                self.sequentially_inferred_jumps,
+                "sequentially inferred jumps",
+            ),
+            (
+                ioptions.implicit_return() == Some(true),
+                self.implicit_returns,
+                "implicit returns",
+            ),
+            (
+                ioptions.implicit_exception() == Some(true),
+                self.implicit_exceptions,
+                "implicit exceptions",
+            ),
+            (
+                ioptions.branch_prediction() == Some(true),
+                self.branch_prediction,
+                "branch prediction",
+            ),
+            (
+                ioptions.jump_target_cache() == Some(true),
+                self.jump_target_cache,
+                "jump target cache",
+            ),
+        ];
+        checks
+            .into_iter()
+            .find(|(active, supported, _)| *active && !supported)
+            .map_or(Ok(()), |(_, _, name)| Err(name))
+    }
+}
+
 /// Builder for [`Tracer`]
 ///
 /// A builder will build a single [`Tracer`] for a single RISC-V hart.
@@ -481,8 +779,13 @@ pub struct Builder<B = binary::Empty> {
     max_stack_depth: usize,
     features: Features,
     address_mode: AddressMode,
+    region_base: u64,
     address_width: core::num::NonZeroU8,
     version: Version,
+    address_policy: SyncAddressPolicy,
+    branch_policy: BranchPolicy,
+    speculation_limit: usize,
+    fault_instructions: bool,
 }
 
 impl Builder<binary::Empty> {
@@ -497,13 +800,11 @@ impl<B> Builder<B> {
     ///
     /// New builders assume [`Default`] parameters.
     pub fn with_params(self, config: &config::Parameters) -> Self {
-        let max_stack_depth = if config.return_stack_size_p > 0 {
-            1 << config.return_stack_size_p
-        } else if config.call_counter_size_p > 0 {
-            1 << config.call_counter_size_p
-        } else {
-            0
-        };
+        let max_stack_depth = [config.return_stack_size_p, config.call_counter_size_p]
+            .into_iter()
+            .find(|s| *s > 0)
+            .map(|s| 1usize.checked_shl(s.into()).unwrap_or(usize::MAX))
+            .unwrap_or(0);
         Self {
             max_stack_depth,
             address_width: config.iaddress_width_p,
@@ -524,9 +825,14 @@ impl<B> Builder<B> {
             binary,
             max_stack_depth: self.max_stack_depth,
             address_mode: self.address_mode,
+            region_base: self.region_base,
             address_width: self.address_width,
             features: self.features,
             version: self.version,
+            address_policy: self.address_policy,
+            branch_policy: self.branch_policy,
+            speculation_limit: self.speculation_limit,
+            fault_instructions: self.fault_instructions,
         }
     }
 
@@ -540,6 +846,23 @@ impl<B> Builder<B> {
         }
     }
 
+    /// Build a [`Tracer`] applying the given region base to absolute addresses
+    ///
+    /// Some encoders report addresses relative to a programmed base rather
+    /// than absolute addresses. The region base is added to all addresses
+    /// reported in [`sync::Start`] and [`sync::Trap`] payloads as well as to
+    /// addresses reported in other payloads in [`AddressMode::Full`]. It does
+    /// not affect differential addresses. The base may be changed at runtime
+    /// via [`Tracer::set_region_base`].
+    ///
+    /// New builders are configured for a region base of `0`.
+    pub fn with_region_base(self, base: u64) -> Self {
+        Self {
+            region_base: base,
+            ..self
+        }
+    }
+
     /// Build a [`Tracer`] with implicit return enabled or disabled
     ///
     /// New builders are configured for no implicit return. The option in a
@@ -563,6 +886,58 @@ impl<B> Builder<B> {
         Self { version, ..self }
     }
 
+    /// Build a [`Tracer`] with the given [`SyncAddressPolicy`]
+    ///
+    /// New builders are configured for [`SyncAddressPolicy::Allow`].
+    pub fn with_sync_address_policy(self, policy: SyncAddressPolicy) -> Self {
+        Self {
+            address_policy: policy,
+            ..self
+        }
+    }
+
+    /// Build a [`Tracer`] with the given [`BranchPolicy`]
+    ///
+    /// New builders are configured for [`BranchPolicy::Error`].
+    pub fn with_branch_policy(self, policy: BranchPolicy) -> Self {
+        Self {
+            branch_policy: policy,
+            ..self
+        }
+    }
+
+    /// Build a [`Tracer`] speculating for at most `limit` instructions
+    ///
+    /// After assuming a branch outcome according to the [`BranchPolicy`], the
+    /// [`Tracer`] yields at most `limit` speculative [`Item`]s per payload.
+    /// Exceeding the limit, e.g. when following a loop whose closing branch is
+    /// assumed taken, results in an [`Error::SpeculationLimit`] and the
+    /// [`Tracer`] stops yielding [`Item`]s until the next payload. New
+    /// builders are configured for a limit of `1024` instructions.
+    pub fn with_speculation_limit(self, limit: usize) -> Self {
+        Self {
+            speculation_limit: limit,
+            ..self
+        }
+    }
+
+    /// Build a [`Tracer`] attaching faulting instructions to exceptions
+    ///
+    /// If enabled, [`Item`]s signalling an exception carry the [`Instruction`]
+    /// at the EPC, which is retrieved via [`Item::fault`]. This spares users
+    /// from retrieving it from the [`Binary`] themselves, e.g. for displaying
+    /// the instruction that caused the exception.
+    ///
+    /// New builders are configured for no faulting instructions.
+    ///
+    /// [`Instruction`]: instruction::Instruction
+    pub fn with_fault_instructions(self, fault_instructions: bool) -> Self {
+        Self {
+            fault_instructions,
+            ..self
+        }
+    }
+
     /// Build the [`Tracer`]
     pub fn build<S, I>(self) -> Result<Tracer<B, S, I>, Error<B::Error>>
     where
@@ -575,6 +950,8 @@ impl<B> Builder<B> {
                 .ok_or(Error::CannotConstructIrStack(self.max_stack_depth))?,
             self.address_width,
             self.features,
+            self.branch_policy,
+            self.speculation_limit,
         );
         Ok(Tracer {
             state,
@@ -582,9 +959,43 @@ impl<B> Builder<B> {
             previous: Default::default(),
             binary: self.binary,
             address_mode: self.address_mode,
+            region_base: self.region_base,
+            address_policy: self.address_policy,
+            address_warning: None,
+            fault_instructions: self.fault_instructions,
+            gap: false,
             phantom: Default::default(),
         })
     }
+
+    /// Build a [`Tracer`] seeded with a known initial state
+    ///
+    /// Usually, a [`Tracer`] requires a [`sync::Start`] before it is able to
+    /// process other payloads. A seeded [`Tracer`] behaves as if it already
+    /// processed a [`sync::Start`] for the [`Seed`]'s PC and context, and its
+    /// return stack holds the [`Seed`]'s return addresses. It yields the
+    /// corresponding [`Item`]s right away and may be fed any payload
+    /// afterwards.
+    ///
+    /// Seeding is only sound if the [`Seed`] matches the state of the hart at
+    /// the point the encoder starts emitting payloads, e.g. if taken from a
+    /// checkpoint or a debugger which halted the hart. Otherwise, the
+    /// [`Tracer`] will silently yield bogus [`Item`]s.
+    ///
+    /// The [`Seed`]'s PC is validated according to the configured
+    /// [`SyncAddressPolicy`] and needs to refer to an instruction in the
+    /// [`Binary`]. The number of return addresses must not exceed the return
+    /// stack's maximum depth.
+    pub fn build_seeded<S, I>(self, seed: &Seed<'_>) -> Result<Tracer<B, S, I>, Error<B::Error>>
+    where
+        B: Binary<I>,
+        S: ReturnStack,
+        I: Info + Clone,
+    {
+        let mut tracer = self.build()?;
+        tracer.seed(seed)?;
+        Ok(tracer)
+    }
 }
 
 impl<B: Default> Default for Builder<B> {
@@ -594,13 +1005,79 @@ impl<B: Default> Default for Builder<B> {
             max_stack_depth: Default::default(),
             features: Default::default(),
             address_mode: Default::default(),
+            region_base: 0,
             address_width: core::num::NonZeroU8::MIN,
             version: Default::default(),
+            address_policy: Default::default(),
+            branch_policy: Default::default(),
+            speculation_limit: 1024,
+            fault_instructions: false,
         }
         .with_params(&Default::default())
     }
 }
 
+/// Known state of a hart from which a [`Tracer`] starts tracing
+///
+/// See [`Builder::build_seeded`] for details.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub struct Seed<'a> {
+    /// Address of the first instruction traced
+    pub pc: u64,
+    /// Execution context at the first instruction traced
+    pub context: types::Context,
+    /// Whether the first instruction traced was taken, if it is a branch
+    pub branch_taken: bool,
+    /// Return addresses on the hart's return stack, bottom first
+    pub return_stack: &'a [u64],
+}
+
+/// Policy for addresses reported in [`sync::Start`] and [`sync::Trap`]s
+///
+/// On most systems, a sync payload reporting the address `0` or an address at
+/// which no instruction can be retrieved from the [`Binary`] indicates a
+/// misconfiguration of the encoder.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum SyncAddressPolicy {
+    /// Do not validate addresses
+    #[default]
+    Allow,
+    /// Record suspicious addresses but process the payload regardless
+    ///
+    /// Recorded addresses are retrieved via [`Tracer::take_address_warning`].
+    Warn,
+    /// Reject payloads with suspicious addresses
+    ///
+    /// The payload is rejected with an [`Error::InvalidSyncAddress`] without
+    /// affecting the [`Tracer`]'s state.
+    Error,
+}
+
+/// Policy for branches not covered by the current branch map
+///
+/// A [`Tracer`] may encounter a branch for which the current packet does not
+/// provide an outcome, e.g. due to a corrupted or lost packet. By default,
+/// this is reported as an [`Error::UnresolvableBranch`]. Alternatively, the
+/// [`Tracer`] may assume an outcome and continue tracing. [`Item`]s yielded
+/// after such an assumption are [speculative][Confidence::Speculative] until
+/// the tracer is resynchronized, e.g. via a [`sync::Start`]. Speculation may
+/// also be detected via [`Tracer::is_speculative`]. The number of speculative
+/// [`Item`]s per payload is [limited][Builder::with_speculation_limit].
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum BranchPolicy {
+    /// Report an [`Error::UnresolvableBranch`]
+    #[default]
+    Error,
+    /// Assume that the branch was not taken
+    AssumeNotTaken,
+    /// Assume that the branch was taken
+    ///
+    /// Note that assuming backward branches to be taken may cause the
+    /// [`Tracer`] to follow a loop until the
+    /// [speculation limit][Builder::with_speculation_limit] is reached.
+    AssumeTaken,
+}
+
 /// [`Tracer`] iteration states
 #[derive(Copy, Clone, Debug)]
 enum IterationState {
@@ -619,6 +1096,8 @@ enum IterationState {
         context: types::Context,
         follow_up: bool,
     },
+    /// We report a notification
+    NotifyItem { pc: u64 },
     /// We follow the execution path based on the current packet's data
     FollowExec,
     /// We follow the execution path as long as it's inferable
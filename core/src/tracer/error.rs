@@ -8,6 +8,7 @@ use crate::types::branch;
 
 /// Tracing specific errors
 #[derive(Debug, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum Error<I> {
     /// Invalid input at start of trace
     ///
@@ -32,10 +33,20 @@ pub enum Error<I> {
     ///
     /// The tracer has exhausted all availible branch information.
     UnresolvableBranch,
+    /// The tracer speculated beyond the given number of instructions
+    ///
+    /// See [`Builder::with_speculation_limit`][limit] for details.
+    ///
+    /// [limit]: super::Builder::with_speculation_limit
+    SpeculationLimit(usize),
     /// The IR stack cannot be constructed for the given size
     CannotConstructIrStack(usize),
     /// We could not fetch an `Instruction` from a given address
     CannotGetInstruction(I, u64),
+    /// A sync payload reported a suspicious address
+    ///
+    /// See [`SyncAddressPolicy`][super::SyncAddressPolicy] for details.
+    InvalidSyncAddress(u64),
 }
 
 impl<I> core::error::Error for Error<I>
@@ -61,12 +72,16 @@ impl<I> fmt::Display for Error<I> {
             Self::UnprocessedBranches(c) => write!(f, "{c} unprocessed branches"),
             Self::UnexpectedUninferableDiscon => write!(f, "unexpected uninferable discontinuity"),
             Self::UnresolvableBranch => write!(f, "unresolvable branch"),
+            Self::SpeculationLimit(limit) => {
+                write!(f, "speculated beyond {limit} instructions")
+            }
             Self::CannotConstructIrStack(size) => {
                 write!(f, "Cannot construct return stack of size {size}")
             }
             Self::CannotGetInstruction(_, addr) => {
                 write!(f, "Cannot get the instruction at {addr:#0x}")
             }
+            Self::InvalidSyncAddress(addr) => write!(f, "suspicious sync address {addr:#0x}"),
         }
     }
 }
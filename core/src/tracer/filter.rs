@@ -0,0 +1,460 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Filter expressions for tracing items
+//!
+//! This module provides a [`Filter`], a predicate over [`Item`]s compiled from
+//! a small expression language. It allows filtering streams of [`Item`]s based
+//! on expressions supplied at runtime, e.g. by a user of a CLI or GUI.
+//!
+//! # Syntax
+//!
+//! An expression consists of conditions combined via `&&`, `||` and `!`, and
+//! grouped via parentheses. `&&` binds stronger than `||`. A condition compares
+//! a field to a value via one of `==`, `!=`, `<`, `<=`, `>` and `>=`, or checks
+//! whether a field's value lies in a range via `in [start..end]` (excluding
+//! `end`) or `in [start..=end]` (including `end`). Numbers may be given in
+//! decimal or hexadecimal notation with a `0x` prefix and may contain `_`.
+//!
+//! The following fields are available:
+//!
+//! * `pc`: the [`Item`]'s PC,
+//! * `hart`: the hart the [`Item`] was generated for,
+//! * `kind`: the [`Kind`] of the [`Item`], one of `insn`, `trap`, `context` or
+//!   `notify`,
+//! * `confidence`: the [`Confidence`] of the [`Item`], one of `certain`,
+//!   `speculative` or `gap`,
+//! * `ecause`: the exception or interrupt cause for traps and
+//! * `privilege`: the [`Privilege`] for context updates, one of `u`, `s`, `m`,
+//!   `d`, `vu` or `vs`.
+//!
+//! Conditions on fields an [`Item`] does not carry, such as `ecause` for a
+//! retired instruction, never hold.
+//!
+//! # Example
+//!
+//! ```
+//! use riscv_etrace_core::instruction::Kind;
+//! use riscv_etrace_core::tracer::filter::Filter;
+//! use riscv_etrace_core::tracer::item::Item;
+//! use riscv_etrace_core::types::trap;
+//!
+//! let filter: Filter = "pc in [0x8000_0000..0x8001_0000] && kind == trap"
+//!     .parse()
+//!     .unwrap();
+//! let info = trap::Info {
+//!     ecause: 2,
+//!     tval: None,
+//! };
+//! let trap: Item = Item::new(0x8000_0100, info.into());
+//! assert!(filter.matches(0, &trap));
+//! assert!(!filter.matches(0, &Item::new(0x8000_0100, Kind::c_nop.into())));
+//! ```
+//!
+//! [`Kind`]: item::Kind
+//! [`Confidence`]: item::Confidence
+//! [`Privilege`]: crate::types::Privilege
+
+use core::fmt;
+use core::str::FromStr;
+
+use crate::instruction::info::Info;
+use crate::types::Privilege;
+
+use super::item::{self, Item};
+
+/// Compiled filter expression
+///
+/// A filter holds up to `N` operations, with each condition and each `&&`,
+/// `||` and `!` occupying one operation.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Filter<const N: usize = 16> {
+    ops: [Op; N],
+    len: usize,
+}
+
+impl<const N: usize> Filter<N> {
+    /// Compile the given expression
+    pub fn new(expr: &str) -> Result<Self, Error> {
+        let mut parser = Parser {
+            input: expr.as_bytes(),
+            pos: 0,
+            depth: 0,
+            filter: Self {
+                ops: [Op::Not; N],
+                len: 0,
+            },
+        };
+        parser.or()?;
+        parser.skip_whitespace();
+        if parser.pos < parser.input.len() {
+            return Err(Error::UnexpectedToken(parser.pos));
+        }
+        Ok(parser.filter)
+    }
+
+    /// Check whether the given [`Item`] of the given hart matches this filter
+    pub fn matches<I: Info>(&self, hart: u64, item: &Item<I>) -> bool {
+        let mut stack = [false; N];
+        let mut depth = 0;
+        for op in &self.ops[..self.len] {
+            let value = match op {
+                Op::Cond(cond) => cond.holds(hart, item),
+                Op::Not => !stack[depth - 1],
+                Op::And => stack[depth - 2] && stack[depth - 1],
+                Op::Or => stack[depth - 2] || stack[depth - 1],
+            };
+            depth -= op.arity();
+            stack[depth] = value;
+            depth += 1;
+        }
+        stack[0]
+    }
+}
+
+impl<const N: usize> FromStr for Filter<N> {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::new(s)
+    }
+}
+
+/// Errors that may occur when compiling a [`Filter`]
+///
+/// Positions are byte offsets into the expression.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Error {
+    /// An unexpected token was encountered at the given position
+    UnexpectedToken(usize),
+    /// The expression ended unexpectedly
+    UnexpectedEnd,
+    /// The field at the given position is not known
+    UnknownField(usize),
+    /// The value at the given position is not valid for its field
+    InvalidValue(usize),
+    /// The expression requires more operations than the [`Filter`] can hold
+    /// or is nested deeper than that number of operations
+    TooComplex,
+}
+
+impl core::error::Error for Error {}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedToken(p) => write!(f, "unexpected token at {p}"),
+            Self::UnexpectedEnd => write!(f, "unexpected end of expression"),
+            Self::UnknownField(p) => write!(f, "unknown field at {p}"),
+            Self::InvalidValue(p) => write!(f, "invalid value at {p}"),
+            Self::TooComplex => write!(f, "expression too complex"),
+        }
+    }
+}
+
+/// Single operation of a compiled [`Filter`]
+///
+/// Operations are stored in postfix order.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Op {
+    Cond(Cond),
+    Not,
+    And,
+    Or,
+}
+
+impl Op {
+    /// Number of values this operation consumes
+    fn arity(&self) -> usize {
+        match self {
+            Self::Cond(_) => 0,
+            Self::Not => 1,
+            Self::And | Self::Or => 2,
+        }
+    }
+}
+
+/// Condition on a single [`Field`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+struct Cond {
+    field: Field,
+    cmp: Cmp,
+    value: u64,
+}
+
+impl Cond {
+    /// Check whether this condition holds for the given [`Item`]
+    fn holds<I: Info>(&self, hart: u64, item: &Item<I>) -> bool {
+        let Some(actual) = self.field.value(hart, item) else {
+            return false;
+        };
+        match self.cmp {
+            Cmp::Eq => actual == self.value,
+            Cmp::Ne => actual != self.value,
+            Cmp::Lt => actual < self.value,
+            Cmp::Le => actual <= self.value,
+            Cmp::Gt => actual > self.value,
+            Cmp::Ge => actual >= self.value,
+            Cmp::In(end) => (self.value..end).contains(&actual),
+        }
+    }
+}
+
+/// Comparison of a [`Cond`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Cmp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    /// Range check, with the (exclusive) end of the range
+    In(u64),
+}
+
+/// Field of an [`Item`] a [`Cond`] refers to
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Field {
+    Pc,
+    Hart,
+    Kind,
+    Confidence,
+    Ecause,
+    Privilege,
+}
+
+impl Field {
+    /// Parse a field name
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "pc" => Some(Self::Pc),
+            "hart" => Some(Self::Hart),
+            "kind" => Some(Self::Kind),
+            "confidence" => Some(Self::Confidence),
+            "ecause" => Some(Self::Ecause),
+            "privilege" => Some(Self::Privilege),
+            _ => None,
+        }
+    }
+
+    /// Parse a symbolic value for this field
+    fn symbol(self, name: &str) -> Option<u64> {
+        let value = match (self, name) {
+            (Self::Kind, "insn") => 0,
+            (Self::Kind, "trap") => 1,
+            (Self::Kind, "context") => 2,
+            (Self::Kind, "notify") => 3,
+            (Self::Confidence, "certain") => item::Confidence::Certain as u64,
+            (Self::Confidence, "speculative") => item::Confidence::Speculative as u64,
+            (Self::Confidence, "gap") => item::Confidence::Gap as u64,
+            (Self::Privilege, "u") => u8::from(Privilege::User).into(),
+            (Self::Privilege, "s") => u8::from(Privilege::Supervisor).into(),
+            (Self::Privilege, "m") => u8::from(Privilege::Machine).into(),
+            (Self::Privilege, "d") => u8::from(Privilege::Debug).into(),
+            (Self::Privilege, "vu") => u8::from(Privilege::VirtUser).into(),
+            (Self::Privilege, "vs") => u8::from(Privilege::VirtSupervisor).into(),
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    /// Retrieve the value of this field for the given [`Item`]
+    fn value<I: Info>(self, hart: u64, item: &Item<I>) -> Option<u64> {
+        match (self, item.kind()) {
+            (Self::Pc, _) => Some(item.pc()),
+            (Self::Hart, _) => Some(hart),
+            (Self::Kind, item::Kind::Regular(_)) => Some(0),
+            (Self::Kind, item::Kind::Trap(_)) => Some(1),
+            (Self::Kind, item::Kind::Context(_)) => Some(2),
+            (Self::Kind, item::Kind::Notify) => Some(3),
+            (Self::Confidence, _) => Some(item.confidence() as u64),
+            (Self::Ecause, item::Kind::Trap(info)) => Some(info.ecause.into()),
+            (Self::Privilege, item::Kind::Context(ctx)) => Some(u8::from(ctx.privilege).into()),
+            _ => None,
+        }
+    }
+}
+
+/// Recursive descent parser emitting [`Op`]s in postfix order
+struct Parser<'a, const N: usize> {
+    input: &'a [u8],
+    pos: usize,
+    depth: usize,
+    filter: Filter<N>,
+}
+
+impl<const N: usize> Parser<'_, N> {
+    /// Parse a disjunction
+    fn or(&mut self) -> Result<(), Error> {
+        self.and()?;
+        while self.eat("||") {
+            self.and()?;
+            self.emit(Op::Or)?;
+        }
+        Ok(())
+    }
+
+    /// Parse a conjunction
+    fn and(&mut self) -> Result<(), Error> {
+        self.unary()?;
+        while self.eat("&&") {
+            self.unary()?;
+            self.emit(Op::And)?;
+        }
+        Ok(())
+    }
+
+    /// Parse a negation, a parenthesized expression or a condition
+    fn unary(&mut self) -> Result<(), Error> {
+        // Limit recursion since expressions may be supplied by users
+        if self.depth >= N {
+            return Err(Error::TooComplex);
+        }
+        self.depth += 1;
+        let res = if self.eat("!") {
+            self.unary().and_then(|_| self.emit(Op::Not))
+        } else if self.eat("(") {
+            self.or().and_then(|_| self.expect(")"))
+        } else {
+            self.cond()
+        };
+        self.depth -= 1;
+        res
+    }
+
+    /// Parse a condition
+    fn cond(&mut self) -> Result<(), Error> {
+        let pos = self.peek_pos()?;
+        let field = Field::parse(self.word()).ok_or(Error::UnknownField(pos))?;
+        let cond = if self.eat_word("in") {
+            self.expect("[")?;
+            let start = self.value(field)?;
+            self.expect("..")?;
+            let inclusive = self.eat("=");
+            let pos = self.peek_pos()?;
+            let end = self.value(field)?;
+            let end = if inclusive {
+                end.checked_add(1).ok_or(Error::InvalidValue(pos))?
+            } else {
+                end
+            };
+            self.expect("]")?;
+            Cond {
+                field,
+                cmp: Cmp::In(end),
+                value: start,
+            }
+        } else {
+            let cmp = [
+                ("==", Cmp::Eq),
+                ("!=", Cmp::Ne),
+                ("<=", Cmp::Le),
+                (">=", Cmp::Ge),
+                ("<", Cmp::Lt),
+                (">", Cmp::Gt),
+            ]
+            .into_iter()
+            .find_map(|(t, c)| self.eat(t).then_some(c));
+            let cmp = cmp.ok_or(Error::UnexpectedToken(self.pos))?;
+            let value = self.value(field)?;
+            Cond { field, cmp, value }
+        };
+        self.emit(Op::Cond(cond))
+    }
+
+    /// Parse a value for the given field
+    fn value(&mut self, field: Field) -> Result<u64, Error> {
+        let pos = self.peek_pos()?;
+        let word = self.word();
+        let digits = |s: &str, radix| {
+            let mut digits = s.bytes().filter(|b| *b != b'_').peekable();
+            digits.peek()?;
+            digits.try_fold(0u64, |v, d| {
+                let d = char::from(d).to_digit(radix)?;
+                v.checked_mul(radix.into())?.checked_add(d.into())
+            })
+        };
+        match word.strip_prefix("0x") {
+            Some(hex) => digits(hex, 16),
+            None if word.starts_with(|c: char| c.is_ascii_digit()) => digits(word, 10),
+            None => field.symbol(word),
+        }
+        .ok_or(Error::InvalidValue(pos))
+    }
+
+    /// Append an operation to the filter
+    fn emit(&mut self, op: Op) -> Result<(), Error> {
+        let slot = self
+            .filter
+            .ops
+            .get_mut(self.filter.len)
+            .ok_or(Error::TooComplex)?;
+        *slot = op;
+        self.filter.len += 1;
+        Ok(())
+    }
+
+    /// Consume a word consisting of alphanumeric characters and `_`
+    ///
+    /// A word may be empty.
+    fn word(&mut self) -> &str {
+        self.skip_whitespace();
+        let start = self.pos;
+        let len = self.input[start..]
+            .iter()
+            .take_while(|b| b.is_ascii_alphanumeric() || **b == b'_')
+            .count();
+        self.pos += len;
+        core::str::from_utf8(&self.input[start..self.pos]).unwrap_or_default()
+    }
+
+    /// Consume the given keyword if it is next in the input
+    fn eat_word(&mut self, keyword: &str) -> bool {
+        let pos = self.pos;
+        if self.word() == keyword {
+            true
+        } else {
+            self.pos = pos;
+            false
+        }
+    }
+
+    /// Consume the given token if it is next in the input
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_whitespace();
+        let found = self.input[self.pos..].starts_with(token.as_bytes());
+        if found {
+            self.pos += token.len();
+        }
+        found
+    }
+
+    /// Consume the given token or fail
+    fn expect(&mut self, token: &str) -> Result<(), Error> {
+        let pos = self.peek_pos()?;
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(Error::UnexpectedToken(pos))
+        }
+    }
+
+    /// Retrieve the position of the next token
+    fn peek_pos(&mut self) -> Result<usize, Error> {
+        self.skip_whitespace();
+        if self.pos < self.input.len() {
+            Ok(self.pos)
+        } else {
+            Err(Error::UnexpectedEnd)
+        }
+    }
+
+    /// Skip any whitespace
+    fn skip_whitespace(&mut self) {
+        self.pos += self.input[self.pos..]
+            .iter()
+            .take_while(|b| b.is_ascii_whitespace())
+            .count();
+    }
+}
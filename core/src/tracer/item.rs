@@ -0,0 +1,314 @@
+// Copyright (C) 2025, 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Tracing item
+
+use crate::instruction::{self, Instruction, info};
+use crate::types::{Context, trap};
+
+/// Tracing item
+///
+/// A tracing item corresponds to either a traced, retired [`Instruction`] or
+/// some other noteworthy event such as a trap.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Item<I: info::Info = Option<instruction::Kind>> {
+    pc: u64,
+    kind: Kind<I>,
+    confidence: Confidence,
+    inference: Option<Inference>,
+    fault: Option<Instruction<I>>,
+}
+
+impl<I: info::Info> Item<I> {
+    /// Create a new item
+    ///
+    /// The item's [`Confidence`] is [`Confidence::Certain`] and its PC is not
+    /// [inferred][Self::inference].
+    pub const fn new(pc: u64, kind: Kind<I>) -> Self {
+        Self {
+            pc,
+            kind,
+            confidence: Confidence::Certain,
+            inference: None,
+            fault: None,
+        }
+    }
+
+    /// Set the [`Confidence`] of this item
+    pub fn with_confidence(self, confidence: Confidence) -> Self {
+        Self { confidence, ..self }
+    }
+
+    /// Set the [`Inference`] of this item's PC
+    pub fn with_inference(self, inference: Inference) -> Self {
+        Self {
+            inference: Some(inference),
+            ..self
+        }
+    }
+
+    /// Set the faulting [`Instruction`] of this item
+    ///
+    /// See [`fault`][Self::fault] for details.
+    pub fn with_fault(self, insn: Instruction<I>) -> Self {
+        Self {
+            fault: Some(insn),
+            ..self
+        }
+    }
+
+    /// Retrieve the PC
+    ///
+    /// For items signalling a retired [`Instruction`], this fn will return its
+    /// address. For exceptions, it will return the EPC. For interrupts, it will
+    /// return the PC of the address of the last retired [`Instruction`].
+    pub fn pc(&self) -> u64 {
+        self.pc
+    }
+
+    /// Retrieve the item's [`Kind`]
+    pub fn kind(&self) -> &Kind<I> {
+        &self.kind
+    }
+
+    /// Retrieve the [`Confidence`] in this item's reconstruction
+    pub fn confidence(&self) -> Confidence {
+        self.confidence
+    }
+
+    /// Retrieve the [`Inference`] by which this item's PC was determined
+    ///
+    /// Returns [`Some`] if the item follows a discontinuity whose target was
+    /// inferred by the tracer rather than reported by the encoder. Returns
+    /// [`None`] for all other items.
+    pub fn inference(&self) -> Option<Inference> {
+        self.inference
+    }
+
+    /// Retrieve the (retired) [`Instruction`]
+    pub fn instruction(&self) -> Option<&Instruction<I>> {
+        match &self.kind {
+            Kind::Regular(insn) => Some(insn),
+            _ => None,
+        }
+    }
+
+    /// Retrieve the [`trap::Info`] assocaited to this item
+    ///
+    /// If this item signals a trap, this fn returns the associated
+    /// [`trap::Info`]. Otherwise, `None` is returned.
+    pub fn trap(&self) -> Option<&trap::Info> {
+        match &self.kind {
+            Kind::Trap(info) => Some(info),
+            _ => None,
+        }
+    }
+
+    /// Retrieve the faulting [`Instruction`] of an exception
+    ///
+    /// If this item signals an exception and the [`Tracer`] was built with
+    /// [fault instructions][super::Builder::with_fault_instructions] enabled,
+    /// this fn returns the [`Instruction`] at the EPC, i.e. the instruction
+    /// that caused the exception, if it could be retrieved. Otherwise, `None`
+    /// is returned.
+    ///
+    /// [`Tracer`]: super::Tracer
+    pub fn fault(&self) -> Option<&Instruction<I>> {
+        self.fault.as_ref()
+    }
+}
+
+/// Confidence in the reconstruction of an [`Item`]
+///
+/// Items are usually reconstructed from trace data alone. However, some
+/// recovery mechanisms rely on assumptions or resume tracing after trace data
+/// was lost. Consumers may use an item's confidence for weighting or
+/// excluding uncertain regions of a trace.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Confidence {
+    /// The item was reconstructed from trace data
+    #[default]
+    Certain,
+    /// The item may be the result of an assumption
+    ///
+    /// Examples include assumed branch outcomes (see
+    /// [`BranchPolicy`][super::BranchPolicy]) or items generated after trace
+    /// data was potentially lost but before tracing was resynchronized.
+    Speculative,
+    /// The item is the first one after trace data was lost
+    ///
+    /// Items preceeding this one may be missing, e.g. because the tracer
+    /// resumed after an error or after the encoder reported a loss of trace.
+    Gap,
+}
+
+/// Means by which the tracer inferred the target of a discontinuity
+///
+/// Targets of uninferable discontinuities are usually reported by the encoder.
+/// With some optional [features][crate::config::Features], the encoder omits
+/// them and the tracer infers the target instead. Tracking the means of
+/// inference helps distinguishing encoder bugs from tracer bugs.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Inference {
+    /// The target was inferred from a preceeding upper immediate instruction
+    /// as a sequentially inferred jump
+    SequentialJump,
+    /// The target was taken from the return stack as an implicit return
+    ImplicitReturn,
+}
+
+/// Kind of a tracing [`Item`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum Kind<I: info::Info = Option<instruction::Kind>> {
+    /// Signals the retiring of the [`Instruction`] at the [`Item`]'s PC
+    Regular(Instruction<I>),
+    /// Signals a trap
+    ///
+    /// In the case of an exception, the [`Item`]'s PC indicated the EPC. In the
+    /// case of an interrupt, the PC will point at the last [`Instruction`]
+    /// retired before the interrut.
+    Trap(trap::Info),
+    /// Signals an updated execution context
+    ///
+    /// The [`Context`] may or may not differ from the last communicated one.
+    /// The [`Item`]'s PC is the PC of the first instruction executed (and
+    /// retired) after the update, i.e. the PC of the following [`Item`].
+    Context(Context),
+    /// Signals a notification by the encoder
+    ///
+    /// Encoders emit notifications e.g. in response to a trigger. The
+    /// [`Item`]'s PC is the PC reported along with the notification, i.e. the
+    /// PC of the preceeding [`Item`].
+    Notify,
+}
+
+impl<I: info::Info> From<Instruction<I>> for Kind<I> {
+    fn from(insn: Instruction<I>) -> Self {
+        Self::Regular(insn)
+    }
+}
+
+impl From<instruction::Kind> for Kind<Option<instruction::Kind>> {
+    fn from(insn: instruction::Kind) -> Self {
+        Self::Regular(insn.into())
+    }
+}
+
+impl<I: info::Info> From<trap::Info> for Kind<I> {
+    fn from(info: trap::Info) -> Self {
+        Self::Trap(info)
+    }
+}
+
+impl<I: info::Info> From<Context> for Kind<I> {
+    fn from(context: Context) -> Self {
+        Self::Context(context)
+    }
+}
+
+/// [`Item`] along with a key for ordering items of multiple harts
+///
+/// Stamped items are ordered by their [`Key`], which allows merging streams of
+/// items from multiple harts or sources (e.g. via a k-way merge) and removing
+/// duplicates in a well-defined way. Stamped items are usually created via a
+/// [`Stamper`].
+///
+/// Two stamped items are considered equal if their [`Key`]s are equal. As
+/// sequence numbers assigned by a [`Stamper`] are unique, this is only the
+/// case for stamped items originating from the same one.
+#[derive(Copy, Clone, Debug)]
+pub struct Stamped<I: info::Info = Option<instruction::Kind>> {
+    /// Ordering key of the [`Item`]
+    pub key: Key,
+    /// The [`Item`] itself
+    pub item: Item<I>,
+}
+
+impl<I: info::Info> PartialEq for Stamped<I> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<I: info::Info> Eq for Stamped<I> {}
+
+impl<I: info::Info> PartialOrd for Stamped<I> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<I: info::Info> Ord for Stamped<I> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+/// Ordering key of a [`Stamped`] [`Item`]
+///
+/// Keys are ordered by timestamp first, then by hart and finally by sequence
+/// number. Keys without a timestamp are ordered before keys with one.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Key {
+    /// Timestamp associated with the [`Item`], e.g. of the packet it was
+    /// generated from
+    pub timestamp: Option<u64>,
+    /// Hart the [`Item`] was generated for
+    pub hart: u64,
+    /// Sequence number of the [`Item`]
+    pub sequence: u64,
+}
+
+/// Utility for creating [`Stamped`] [`Item`]s
+///
+/// A stamper assigns consecutive sequence numbers to the [`Item`]s it stamps.
+///
+/// # Example
+///
+/// ```
+/// use riscv_etrace_core::instruction::Kind;
+/// use riscv_etrace_core::tracer::item::{Item, Stamper};
+///
+/// let mut stamper = Stamper::default();
+/// let a = stamper.stamp(1, Some(10), Item::new(0x1000, Kind::c_nop.into()));
+/// let b = stamper.stamp(0, Some(10), Item::new(0x2000, Kind::c_nop.into()));
+/// let c = stamper.stamp(1, Some(10), Item::new(0x1002, Kind::c_nop.into()));
+/// let mut items = [c, a, b];
+/// items.sort();
+/// assert_eq!(items.map(|s| s.item.pc()), [0x2000, 0x1000, 0x1002]);
+/// ```
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Stamper {
+    next: u64,
+}
+
+impl Stamper {
+    /// Create a new stamper starting at the given sequence number
+    pub fn new(start: u64) -> Self {
+        Self { next: start }
+    }
+
+    /// Stamp an [`Item`] of the given hart with the given timestamp
+    pub fn stamp<I: info::Info>(
+        &mut self,
+        hart: u64,
+        timestamp: Option<u64>,
+        item: Item<I>,
+    ) -> Stamped<I> {
+        let sequence = self.next;
+        self.next = self.next.wrapping_add(1);
+        Stamped {
+            key: Key {
+                timestamp,
+                hart,
+                sequence,
+            },
+            item,
+        }
+    }
+
+    /// Retrieve the sequence number assigned to the next [`Item`]
+    pub fn next_sequence(&self) -> u64 {
+        self.next
+    }
+}
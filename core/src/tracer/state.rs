@@ -2,6 +2,9 @@
 // SPDX-License-Identifier: Apache-2.0
 //! Execution tracing utilities
 
+#[cfg(feature = "self-check")]
+mod check;
+
 use core::num::NonZeroU8;
 
 use crate::binary::Binary;
@@ -9,7 +12,9 @@ use crate::config::Features;
 use crate::instruction::{self, Instruction};
 use crate::types::{Context, Privilege, branch};
 
+use super::BranchPolicy;
 use super::error::Error;
+use super::item::Inference;
 use super::stack::ReturnStack;
 
 use instruction::info::Info;
@@ -21,13 +26,13 @@ pub struct State<S: ReturnStack, I: Info> {
     pc: u64,
 
     /// Current instruction
-    insn: Instruction<I>,
+    insn: Summary<I::Register>,
 
     /// Previous program counter
     last_pc: u64,
 
     /// Previous instruction
-    last_insn: Instruction<I>,
+    last_insn: Summary<I::Register>,
 
     /// Address reconstructed from the latest packet
     address: u64,
@@ -35,6 +40,9 @@ pub struct State<S: ReturnStack, I: Info> {
     /// Sequence of future branches
     branch_map: branch::Map,
 
+    /// Sequence of branches consumed since the last packet
+    consumed_branches: branch::Map,
+
     /// Stop condition for the current packet
     stop_condition: StopCondition,
 
@@ -55,18 +63,43 @@ pub struct State<S: ReturnStack, I: Info> {
 
     /// Feature selection
     features: Features,
+
+    /// Policy for branches not covered by the branch map
+    branch_policy: BranchPolicy,
+
+    /// Whether a branch outcome was assumed since the last sync
+    speculative: bool,
+
+    /// Maximum number of speculative items per packet
+    speculation_limit: usize,
+
+    /// Number of speculative items yielded for the current packet
+    speculated: usize,
+
+    /// Means by which the current PC was inferred, if any
+    inference: Option<Inference>,
+
+    /// Whether the current PC was reported with `notify` set
+    notified: bool,
 }
 
 impl<S: ReturnStack, I: Info + Clone> State<S, I> {
     /// Create a new, initial state for tracing
-    pub fn new(return_stack: S, address_width: NonZeroU8, features: Features) -> Self {
+    pub fn new(
+        return_stack: S,
+        address_width: NonZeroU8,
+        features: Features,
+        branch_policy: BranchPolicy,
+        speculation_limit: usize,
+    ) -> Self {
         Self {
             pc: 0,
-            insn: Info::ignored(),
+            insn: Summary::ignored(),
             last_pc: 0,
-            last_insn: Info::ignored(),
+            last_insn: Summary::ignored(),
             address: 0,
             branch_map: Default::default(),
+            consumed_branches: Default::default(),
             stop_condition: Default::default(),
             inferred_address: Default::default(),
             privilege: Default::default(),
@@ -74,6 +107,12 @@ impl<S: ReturnStack, I: Info + Clone> State<S, I> {
             stack_depth: Default::default(),
             address_width,
             features,
+            branch_policy,
+            speculative: false,
+            speculation_limit,
+            speculated: 0,
+            inference: None,
+            notified: false,
         }
     }
 
@@ -87,14 +126,44 @@ impl<S: ReturnStack, I: Info + Clone> State<S, I> {
         self.pc
     }
 
-    /// Retrieve the current [`Instruction`] without advancing the state
-    pub fn current_insn(&self) -> Instruction<I> {
-        self.insn.clone()
+    /// Retrieve the current [`Privilege`] level
+    pub fn privilege(&self) -> Privilege {
+        self.privilege
+    }
+
+    /// Predict the PC following the current one without advancing the state
+    ///
+    /// Returns [`None`] if the next PC can not be determined from the current
+    /// instruction and the information within the state alone, e.g. for
+    /// uninferable jumps or branches not covered by the branch map.
+    pub fn predict_next_pc(&self) -> Option<u64> {
+        let insn = &self.insn;
+        let next_pc = if let Some((target, _)) = self.inferable_jump_target(insn) {
+            target
+        } else if let Some(target) = self.sequential_jump_target(insn) {
+            target
+        } else if insn.is_uninferable_discon {
+            return None;
+        } else if let Some(target) = insn.branch_target {
+            let mut branch_map = self.branch_map;
+            if branch_map.pop_taken()? {
+                self.pc.wrapping_add_signed(target.into())
+            } else {
+                self.pc.wrapping_add(insn.size.into())
+            }
+        } else {
+            self.pc.wrapping_add(insn.size.into())
+        };
+
+        let mask = u64::MAX
+            .checked_shl(self.address_width.get().into())
+            .unwrap_or(0);
+        Some(next_pc & !mask)
     }
 
-    /// Retrieve the previous [`Instruction`] without advancing the state
-    pub fn previous_insn(&self) -> &Instruction<I> {
-        &self.last_insn
+    /// Check whether the previous instruction was an uninferable discontinuity
+    pub fn previous_is_uninferable_discon(&self) -> bool {
+        self.last_insn.is_uninferable_discon
     }
 
     /// Determine next [`ProtoItem`]
@@ -114,6 +183,14 @@ impl<S: ReturnStack, I: Info + Clone> State<S, I> {
             return Ok(None);
         }
 
+        if self.speculative {
+            if self.speculated >= self.speculation_limit {
+                self.stop_condition = StopCondition::Fused;
+                return Err(Error::SpeculationLimit(self.speculation_limit));
+            }
+            self.speculated += 1;
+        }
+
         if let Some(address) = self.inferred_address {
             let (pc, insn, end) = self
                 .next_pc(binary, address)
@@ -127,11 +204,14 @@ impl<S: ReturnStack, I: Info + Clone> State<S, I> {
             self.stop_condition = StopCondition::Fused;
             Ok(None)
         } else {
-            let (pc, insn, end) = self
+            let (pc, mut insn, end) = self
                 .next_pc(binary, self.address)
                 .inspect_err(|_| self.stop_condition = StopCondition::Fused)?;
 
-            let is_branch = self.insn.is_branch();
+            #[cfg(feature = "self-check")]
+            let expected = self.reference_outcome(end);
+
+            let is_branch = self.insn.branch_target.is_some();
             let branch_limit = if is_branch { 1 } else { 0 };
             let hit_address_and_branch =
                 self.pc == self.address && self.branch_map.count() == branch_limit;
@@ -142,13 +222,14 @@ impl<S: ReturnStack, I: Info + Clone> State<S, I> {
                 }
                 StopCondition::Address { notify: true, .. } if hit_address_and_branch => {
                     self.stop_condition = StopCondition::Fused;
+                    self.notified = true;
                     None
                 }
                 StopCondition::Address {
                     notify: false,
                     not_updiscon: true,
                 } if hit_address_and_branch
-                    && !self.last_insn.is_uninferable_discon()
+                    && !self.last_insn.is_uninferable_discon
                     && self.stack_depth_matches() =>
                 {
                     self.inferred_address = Some(self.pc);
@@ -158,6 +239,15 @@ impl<S: ReturnStack, I: Info + Clone> State<S, I> {
                 StopCondition::Sync { context } if hit_address_and_branch => {
                     self.privilege = context.privilege;
                     self.stop_condition = StopCondition::Fused;
+                    self.speculative = false;
+
+                    // The instruction at the sync address is executed in the
+                    // new context, which may be backed by different code.
+                    binary.switch_context(&context);
+                    insn = binary
+                        .get_insn(pc)
+                        .map_err(|e| Error::CannotGetInstruction(e, pc))?;
+                    self.insn = Summary::new(&insn, pc);
                     Some(context)
                 }
                 _ if end => {
@@ -165,6 +255,8 @@ impl<S: ReturnStack, I: Info + Clone> State<S, I> {
                     if let Some(n) = core::num::NonZeroU8::new(self.branch_map.count())
                         .filter(|n| n.get() > branch_limit)
                     {
+                        #[cfg(feature = "self-check")]
+                        check::assert_outcome(expected, check::Outcome::Unprocessed, pc);
                         return Err(Error::UnprocessedBranches(n));
                     }
                     None
@@ -172,6 +264,9 @@ impl<S: ReturnStack, I: Info + Clone> State<S, I> {
                 _ => None,
             };
 
+            #[cfg(feature = "self-check")]
+            check::assert_outcome(expected, self.actual_outcome(ctx.is_some()), pc);
+
             Ok(Some((pc, insn, ctx)))
         }
     }
@@ -185,13 +280,13 @@ impl<S: ReturnStack, I: Info + Clone> State<S, I> {
         binary: &mut B,
         packet_epc: Option<u64>,
     ) -> Result<u64, Error<B::Error>> {
-        if self.insn.is_uninferable_discon()
+        if self.insn.is_uninferable_discon
             && let Some(epc) = packet_epc
         {
             return Ok(epc);
         }
 
-        if self.insn.is_ecall_or_ebreak() {
+        if self.insn.is_ecall_or_ebreak {
             return Ok(self.pc);
         }
 
@@ -210,12 +305,15 @@ impl<S: ReturnStack, I: Info + Clone> State<S, I> {
         &'a mut self,
         binary: &'a mut B,
     ) -> Result<Initializer<'a, S, B, I>, Error<B::Error>> {
-        self.is_fused()
-            .then_some(Initializer {
-                state: self,
-                binary,
-            })
-            .ok_or(Error::UnprocessedInstructions)
+        if !self.is_fused() {
+            return Err(Error::UnprocessedInstructions);
+        }
+        self.consumed_branches = Default::default();
+        self.speculated = 0;
+        Ok(Initializer {
+            state: self,
+            binary,
+        })
     }
 
     /// Retrieve the current selection of optional [Features]
@@ -223,6 +321,37 @@ impl<S: ReturnStack, I: Info + Clone> State<S, I> {
         self.features
     }
 
+    /// Check whether a branch outcome was assumed since the last sync
+    pub fn is_speculative(&self) -> bool {
+        self.speculative
+    }
+
+    /// Retrieve the [`Inference`] by which the current PC was determined
+    ///
+    /// Returns [`None`] if the current PC was not the target of an inferred
+    /// discontinuity.
+    pub fn inference(&self) -> Option<Inference> {
+        self.inference
+    }
+
+    /// Check and clear whether the current PC was reported with `notify` set
+    ///
+    /// Returns `true` once after the state was fused due to reaching an
+    /// address reported with `notify` set.
+    pub fn take_notified(&mut self) -> bool {
+        core::mem::take(&mut self.notified)
+    }
+
+    /// Retrieve the branches consumed since the last packet
+    pub fn consumed_branches(&self) -> branch::Map {
+        self.consumed_branches
+    }
+
+    /// Retrieve the [`ReturnStack`]
+    pub fn return_stack(&self) -> &S {
+        &self.return_stack
+    }
+
     /// Determine the next PC
     ///
     /// Determines the next PC based on the given address as well as information
@@ -240,14 +369,24 @@ impl<S: ReturnStack, I: Info + Clone> State<S, I> {
         // The PC right after the current instruction
         let after_pc = self.pc.wrapping_add(self.insn.size.into());
 
-        let info = self.insn.info.clone();
-        let (mut next_pc, end) = self
+        let info = self.insn.clone();
+        let inferred = self
             .inferable_jump_target(&info)
-            .or_else(|| self.sequential_jump_target(&info).map(|t| (t, false)))
-            .or_else(|| self.implicit_return_address(&info).map(|t| (t, false)))
+            .map(|t| (t, None))
+            .or_else(|| {
+                let target = self.sequential_jump_target(&info)?;
+                Some(((target, false), Some(Inference::SequentialJump)))
+            })
+            .or_else(|| {
+                let target = self.implicit_return_address(&info)?;
+                Some(((target, false), Some(Inference::ImplicitReturn)))
+            });
+        self.inference = inferred.and_then(|(_, i)| i);
+        let (mut next_pc, end) = inferred
+            .map(|(t, _)| t)
             .map(Ok)
             .or_else(|| {
-                info.is_uninferable_discon().then(|| {
+                info.is_uninferable_discon.then(|| {
                     (!matches!(self.stop_condition, StopCondition::LastBranch))
                         .then_some((address, true))
                         .ok_or(Error::UnexpectedUninferableDiscon)
@@ -261,18 +400,16 @@ impl<S: ReturnStack, I: Info + Clone> State<S, I> {
             .checked_shl(self.address_width.get().into())
             .unwrap_or(0));
 
-        if self.features.implicit_returns && self.insn.is_call() {
+        if self.features.implicit_returns && info.is_call {
             self.return_stack.push(after_pc);
         }
 
-        self.last_pc = self.pc;
-        self.last_insn = self.insn.clone();
-
         let insn = binary
             .get_insn(next_pc)
             .map_err(|e| Error::CannotGetInstruction(e, next_pc))?;
+        self.last_pc = self.pc;
+        self.last_insn = core::mem::replace(&mut self.insn, Summary::new(&insn, next_pc));
         self.pc = next_pc;
-        self.insn = insn.clone();
 
         Ok((next_pc, insn, end))
     }
@@ -282,8 +419,8 @@ impl<S: ReturnStack, I: Info + Clone> State<S, I> {
     /// Computes and returns the absolute jump target along side a flag
     /// indicating whether the _relative_ target is zero if the given
     /// instruction an inferable jump instruction.
-    fn inferable_jump_target(&self, insn: &I) -> Option<(u64, bool)> {
-        insn.inferable_jump_target()
+    fn inferable_jump_target(&self, insn: &Summary<I::Register>) -> Option<(u64, bool)> {
+        insn.inferable_jump_target
             .map(|t| (self.pc.wrapping_add_signed(t.into()), t == 0))
     }
 
@@ -291,24 +428,24 @@ impl<S: ReturnStack, I: Info + Clone> State<S, I> {
     ///
     /// This roughly corresponds to a combination of `is_sequential_jump` and
     /// `sequential_jump_target` of the reference implementation.
-    fn sequential_jump_target(&self, insn: &I) -> Option<u64> {
+    fn sequential_jump_target(&self, insn: &Summary<I::Register>) -> Option<u64> {
         if !self.features.sequentially_inferred_jumps {
             return None;
         }
 
-        let (reg, target) = self.last_insn.upper_immediate(self.last_pc)?;
-        let (dep, off) = insn.uninferable_jump_target()?;
+        let (reg, target) = self.last_insn.upper_immediate.as_ref()?;
+        let (dep, off) = insn.uninferable_jump_target.as_ref()?;
 
-        (dep == reg).then_some(target.wrapping_add_signed(off.into()))
+        (dep == reg).then_some(target.wrapping_add_signed((*off).into()))
     }
 
     /// If the given instruction is a function return, try to find the return address
     ///
     /// This roughly corresponds to a combination of `is_implicit_return` and
     /// `pop_return_stack` of the reference implementation.
-    fn implicit_return_address(&mut self, insn: &I) -> Option<u64> {
+    fn implicit_return_address(&mut self, insn: &Summary<I::Register>) -> Option<u64> {
         if self.features.implicit_returns
-            && insn.is_return()
+            && insn.is_return
             && self.stack_depth != Some(self.return_stack.depth())
         {
             self.return_stack.pop()
@@ -327,16 +464,30 @@ impl<S: ReturnStack, I: Info + Clone> State<S, I> {
     ///
     /// This roughly corresponds to a combination of `is_taken_branch` of the
     /// reference implementation.
-    fn taken_branch_target<E>(&mut self, insn: &I) -> Result<Option<(u64, bool)>, Error<E>> {
-        let Some(target) = insn.branch_target() else {
+    fn taken_branch_target<E>(
+        &mut self,
+        insn: &Summary<I::Register>,
+    ) -> Result<Option<(u64, bool)>, Error<E>> {
+        let Some(target) = insn.branch_target else {
             // Not a branch instruction
             return Ok(None);
         };
-        let res = self
-            .branch_map
-            .pop_taken()
-            .ok_or(Error::UnresolvableBranch)?
-            .then_some((self.pc.wrapping_add_signed(target.into()), target == 0));
+        let taken = match (self.branch_map.pop_taken(), self.branch_policy) {
+            (Some(taken), _) => {
+                // The number of branches consumed is bounded by the branch
+                // map's capacity, so this will not fail.
+                let _ = self.consumed_branches.push_branch_taken(taken);
+                taken
+            }
+            (None, BranchPolicy::Error) => return Err(Error::UnresolvableBranch),
+            (None, policy) => {
+                // Assumed outcomes are not part of the reported outcomes and
+                // not bounded by the branch map's capacity.
+                self.speculative = true;
+                policy == BranchPolicy::AssumeTaken
+            }
+        };
+        let res = taken.then_some((self.pc.wrapping_add_signed(target.into()), target == 0));
         Ok(res)
     }
 
@@ -351,6 +502,60 @@ impl<S: ReturnStack, I: Info + Clone> State<S, I> {
     }
 }
 
+/// Control flow relevant information about an [`Instruction`]
+///
+/// Tracing only requires a few properties of the current and previous
+/// instruction. We extract those once per instruction rather than keeping
+/// (and cloning) potentially rich [`Info`].
+#[derive(Clone, Debug)]
+struct Summary<R> {
+    size: instruction::Size,
+    branch_target: Option<i16>,
+    inferable_jump_target: Option<i32>,
+    uninferable_jump_target: Option<(R, i16)>,
+    /// Upper immediate, already computed for the instruction's address
+    upper_immediate: Option<(R, u64)>,
+    is_uninferable_discon: bool,
+    is_ecall_or_ebreak: bool,
+    is_call: bool,
+    is_return: bool,
+}
+
+impl<R> Summary<R> {
+    /// Extract a summary from an [`Instruction`] located at `pc`
+    fn new<I: Info<Register = R>>(insn: &Instruction<I>, pc: u64) -> Self {
+        let info = &insn.info;
+        Self {
+            size: insn.size,
+            branch_target: info.branch_target(),
+            inferable_jump_target: info.inferable_jump_target(),
+            uninferable_jump_target: info.uninferable_jump_target(),
+            upper_immediate: info.upper_immediate(pc),
+            is_uninferable_discon: info.is_uninferable_discon(),
+            is_ecall_or_ebreak: info.is_ecall_or_ebreak(),
+            is_call: info.is_call(),
+            is_return: info.is_return(),
+        }
+    }
+
+    /// Create a summary of an instruction that is to be ignored
+    ///
+    /// See [`Info::ignored`] for details.
+    fn ignored() -> Self {
+        Self {
+            size: Default::default(),
+            branch_target: None,
+            inferable_jump_target: None,
+            uninferable_jump_target: None,
+            upper_immediate: None,
+            is_uninferable_discon: false,
+            is_ecall_or_ebreak: false,
+            is_call: false,
+            is_return: false,
+        }
+    }
+}
+
 /// A precursor to a tracer item
 ///
 /// This expands to a regular tracer item, optionally preceeded by a context
@@ -407,8 +612,16 @@ impl<S: ReturnStack, B: Binary<I>, I: Info> Initializer<'_, S, B, I> {
     }
 
     /// Set the execution context
+    ///
+    /// The [`Binary`] is [switched][Binary::switch_context] to the context.
     pub fn set_context(&mut self, context: Context) {
         self.state.privilege = context.privilege;
+        self.binary.switch_context(&context);
+    }
+
+    /// Get a mutable reference to the [`State`]'s [`ReturnStack`]
+    pub fn get_return_stack_mut(&mut self) -> &mut S {
+        &mut self.state.return_stack
     }
 
     /// Set the stack depth
@@ -443,9 +656,11 @@ impl<S: ReturnStack, B: Binary<I>, I: Info> Initializer<'_, S, B, I> {
             .map_err(|e| Error::CannotGetInstruction(e, address))?;
 
         self.state.pc = address;
-        self.state.insn = insn;
+        self.state.insn = Summary::new(&insn, address);
         self.state.last_pc = address;
-        self.state.last_insn = Info::ignored();
+        self.state.last_insn = Summary::ignored();
+        self.state.speculative = false;
+        self.state.inference = None;
 
         Ok(())
     }
@@ -0,0 +1,101 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Self-check of stop conditions
+//!
+//! The [`StopCondition`] logic in [`State::next_item`] is a condensed version
+//! of the conditions for breaking the tracing loop in `follow_execution_path`
+//! of the reference implementation. This module provides a shadow
+//! implementation following the reference pseudo-code more closely. With the
+//! `self-check` feature enabled, both are evaluated for every step and are
+//! asserted to agree.
+
+use super::{Info, ReturnStack, State, StopCondition};
+
+/// Outcome of a single tracing step
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(super) enum Outcome {
+    /// Tracing continues with the next instruction
+    Continue,
+    /// Tracing stops for the current packet
+    Stop,
+    /// Tracing stops and the state becomes inferred
+    Inferred,
+    /// Tracing stops at the synchronization point
+    Sync,
+    /// Tracing stops with branches left unprocessed
+    Unprocessed,
+}
+
+impl<S: ReturnStack, I: Info + Clone> State<S, I> {
+    /// Determine the [`Outcome`] of the current step as per the reference
+    ///
+    /// This fn needs to be called after the next PC was determined but before
+    /// the [`StopCondition`] was evaluated. `end` signals whether no further
+    /// instructions can be traced based on the current address.
+    pub(super) fn reference_outcome(&self, end: bool) -> Outcome {
+        let (stop_at_last_branch, notify, updiscon, sync) = match self.stop_condition {
+            StopCondition::LastBranch => (true, false, false, false),
+            StopCondition::Address {
+                notify,
+                not_updiscon,
+            } => (false, notify, !not_updiscon, false),
+            StopCondition::Sync { .. } => (false, false, false, true),
+            _ => (false, false, false, false),
+        };
+        let branches = self.branch_map.count();
+        let is_branch = self.insn.branch_target.is_some();
+        let limit = u8::from(is_branch);
+
+        if branches == 1 && is_branch && stop_at_last_branch {
+            return Outcome::Stop;
+        }
+        if self.pc == self.address && branches == limit {
+            if notify {
+                return Outcome::Stop;
+            }
+            if sync {
+                return Outcome::Sync;
+            }
+            let depth_matches = self
+                .stack_depth
+                .is_none_or(|d| d == self.return_stack.depth());
+            if !stop_at_last_branch
+                && !updiscon
+                && !self.last_insn.is_uninferable_discon
+                && depth_matches
+            {
+                return Outcome::Inferred;
+            }
+        }
+        match (end, branches > limit) {
+            (false, _) => Outcome::Continue,
+            (true, false) => Outcome::Stop,
+            (true, true) => Outcome::Unprocessed,
+        }
+    }
+
+    /// Determine the [`Outcome`] of the current step from the state
+    ///
+    /// This fn needs to be called after the [`StopCondition`] was evaluated.
+    /// `sync` signals whether a synchronization point was reached.
+    pub(super) fn actual_outcome(&self, sync: bool) -> Outcome {
+        if sync {
+            Outcome::Sync
+        } else if self.inferred_address.is_some() {
+            Outcome::Inferred
+        } else if self.is_fused() {
+            Outcome::Stop
+        } else {
+            Outcome::Continue
+        }
+    }
+}
+
+/// Assert that the `actual` [`Outcome`] matches the `expected` one
+#[track_caller]
+pub(super) fn assert_outcome(expected: Outcome, actual: Outcome, pc: u64) {
+    assert_eq!(
+        actual, expected,
+        "stop condition diverges from reference at {pc:#x}"
+    );
+}
@@ -0,0 +1,238 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Transport of packets within raw trace data
+//!
+//! This module defines the [`Transport`] trait, which abstracts over the
+//! framing of packets within raw trace data. A transport decodes [`Packet`]s,
+//! which identify the hart they originate from, an optional timestamp and the
+//! [`Payload`][payload::Payload] carried.
+//!
+//! The [`Framing`]s defined in the specification and supported by this library
+//! are available as a [`Transport`] implementation. Additional, e.g.
+//! proprietary, framings may be supported by implementing [`Transport`] on top
+//! of the public [`Decoder`] API.
+
+use crate::packet::decoder::Decoder;
+use crate::packet::{self, payload, unit};
+
+/// Transport of [`Packet`]s within raw trace data
+///
+/// # Example
+///
+/// The following example shows a transport for a framing with a two byte
+/// header, which consists of the hart index and the length of the payload.
+///
+/// ```
+/// use riscv_etrace_core::packet::{self, decoder::Decoder, unit};
+/// use riscv_etrace_core::transport::{Packet, Transport, UnitPacket};
+///
+/// struct Simple;
+///
+/// impl<U: unit::Unit + Clone> Transport<U> for Simple {
+///     fn next_packet(
+///         &mut self,
+///         decoder: &mut Decoder<'_, U>,
+///     ) -> Result<Option<UnitPacket<U>>, packet::Error> {
+///         let header = decoder.split_off_to(2)?.remaining_data();
+///         let (hart, len) = (header[0], header[1]);
+///         let payload = decoder.split_off_to(len.into())?.decode_payload()?;
+///         Ok(Some(Packet {
+///             hart: hart.into(),
+///             timestamp: None,
+///             payload: payload.into(),
+///         }))
+///     }
+/// }
+///
+/// let trace_data = b"\x02\x05\x13\x0a\x00\x00\xe0";
+/// let mut decoder = packet::builder().decoder(trace_data);
+/// let packet = Simple.next_packet(&mut decoder).unwrap().unwrap();
+/// assert_eq!(packet.hart, 2);
+/// assert_eq!(decoder.bytes_left(), 0);
+/// ```
+pub trait Transport<U: unit::Unit> {
+    /// Decode the next [`Packet`]
+    ///
+    /// Decodes a single packet, consuming the associated data from the
+    /// `decoder`. After successful operation, the decoder is expected to be
+    /// left at the byte boundary following the packet. Returns `None` if the
+    /// packet decoded does not carry a payload, e.g. if it is a null packet.
+    fn next_packet(
+        &mut self,
+        decoder: &mut Decoder<'_, U>,
+    ) -> Result<Option<UnitPacket<U>>, packet::Error>;
+}
+
+impl<U: unit::Unit, T: Transport<U> + ?Sized> Transport<U> for &mut T {
+    fn next_packet(
+        &mut self,
+        decoder: &mut Decoder<'_, U>,
+    ) -> Result<Option<UnitPacket<U>>, packet::Error> {
+        T::next_packet(self, decoder)
+    }
+}
+
+/// Framing of packets within raw trace data
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Framing {
+    /// Packets are [`packet::encap::Packet`]s
+    ///
+    /// Only the first payload of each packet is decoded. Use
+    /// [`MultiPayloadEncap`] for packets carrying multiple payloads.
+    #[default]
+    Encap,
+    /// Packets are [`packet::smi::Packet`]s
+    Smi,
+    /// Packets are [`packet::esp32::Packet`]s
+    ///
+    /// As the ESP32-C6 only features a single hart, all packets are attributed
+    /// to the hart with index `0`.
+    Esp32,
+}
+
+impl Framing {
+    /// All framings supported
+    pub const ALL: [Self; 3] = [Self::Encap, Self::Smi, Self::Esp32];
+
+    /// Retrieve a short name for this framing
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Encap => "encap",
+            Self::Smi => "smi",
+            Self::Esp32 => "esp32",
+        }
+    }
+
+    /// Retrieve the framing with the given [name][Self::name]
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|f| f.name() == name)
+    }
+}
+
+impl<U: unit::Unit + Clone> Transport<U> for Framing {
+    fn next_packet(
+        &mut self,
+        decoder: &mut Decoder<'_, U>,
+    ) -> Result<Option<UnitPacket<U>>, packet::Error> {
+        match self {
+            Self::Encap => decoder
+                .decode_encap_packet()?
+                .into_normal()
+                .map(|p| {
+                    let hart = p.src_id().into();
+                    let timestamp = p.timestamp();
+                    p.decode_payload().map(|payload| Packet {
+                        hart,
+                        timestamp,
+                        payload,
+                    })
+                })
+                .transpose(),
+            Self::Smi => {
+                let packet = decoder.decode_smi_packet()?;
+                let hart = packet.hart();
+                let timestamp = packet.time_tag().map(Into::into);
+                packet.decode_payload().map(|payload| {
+                    Some(Packet {
+                        hart,
+                        timestamp,
+                        payload,
+                    })
+                })
+            }
+            Self::Esp32 => decoder
+                .decode_esp32_packet()?
+                .into_normal()
+                .map(|p| {
+                    p.decode_payload().map(|payload| Packet {
+                        hart: 0,
+                        timestamp: None,
+                        payload,
+                    })
+                })
+                .transpose(),
+        }
+    }
+}
+
+/// [`Transport`] for [`packet::encap::Packet`]s carrying multiple payloads
+///
+/// Some encoders pack multiple payloads into a single encapsulated packet.
+/// While [`Framing::Encap`] only decodes the first payload of each packet,
+/// this transport yields every payload as an individual [`Packet`], in the
+/// order given by [`Normal::payloads`]. All of those [`Packet`]s share the
+/// encapsulated packet's source and timestamp.
+///
+/// The transport keeps track of the payloads already yielded. It leaves the
+/// decoder at the start of an encapsulated packet until its last payload was
+/// decoded or decoding a payload failed.
+///
+/// # Example
+///
+/// ```
+/// use riscv_etrace_core::packet;
+/// use riscv_etrace_core::transport::{MultiPayloadEncap, Transport};
+///
+/// let data = b"\x0a\x13\x00\x00\x00\xe0\x13\x00\x00\x00\xe0";
+/// let mut decoder = packet::builder().decoder(data);
+/// let mut transport = MultiPayloadEncap::default();
+/// assert!(transport.next_packet(&mut decoder).unwrap().is_some());
+/// assert_eq!(decoder.bytes_left(), data.len());
+/// assert!(transport.next_packet(&mut decoder).unwrap().is_some());
+/// assert_eq!(decoder.bytes_left(), 0);
+/// ```
+///
+/// [`Normal::payloads`]: packet::encap::Normal::payloads
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct MultiPayloadEncap {
+    offset: usize,
+}
+
+impl<U: unit::Unit + Clone> Transport<U> for MultiPayloadEncap {
+    fn next_packet(
+        &mut self,
+        decoder: &mut Decoder<'_, U>,
+    ) -> Result<Option<UnitPacket<U>>, packet::Error> {
+        let mut outer = decoder.clone();
+        let Some(mut normal) = outer.decode_encap_packet()?.into_normal() else {
+            *decoder = outer;
+            self.offset = 0;
+            return Ok(None);
+        };
+        let hart = normal.src_id().into();
+        let timestamp = normal.timestamp();
+
+        normal.payload_mut().split_off_to(self.offset)?;
+        let len = normal.payload().bytes_left();
+        let mut payloads = normal.payloads();
+        let res = payloads.next().transpose();
+        let left = payloads.bytes_left();
+        if res.is_ok() && left > 0 {
+            self.offset += len - left;
+        } else {
+            *decoder = outer;
+            self.offset = 0;
+        }
+        res.map(|p| {
+            p.map(|payload| Packet {
+                hart,
+                timestamp,
+                payload,
+            })
+        })
+    }
+}
+
+/// A decoded packet, independent of its [`Transport`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Packet<I = unit::ReferenceIOptions, D = unit::ReferenceDOptions> {
+    /// Index of the hart the packet originates from
+    pub hart: u64,
+    /// Timestamp of the packet, if present
+    pub timestamp: Option<u64>,
+    /// The packet's [`Payload`][payload::Payload]
+    pub payload: payload::Payload<I, D>,
+}
+
+/// [`Packet`] with the options of a given [`Unit`][unit::Unit]
+pub type UnitPacket<U> = Packet<<U as unit::Unit>::IOptions, <U as unit::Unit>::DOptions>;
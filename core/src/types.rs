@@ -4,6 +4,7 @@
 
 pub mod branch;
 pub mod stack;
+pub mod time;
 pub mod trap;
 
 #[cfg(test)]
@@ -21,6 +22,13 @@ pub enum Privilege {
     Debug,
     VirtUser,
     VirtSupervisor,
+    /// Privilege level defined by a specific trace unit
+    ///
+    /// The value is the privilege's raw encoding. Units may decode reserved
+    /// encodings to this variant via [`Unit::decode_privilege`].
+    ///
+    /// [`Unit::decode_privilege`]: crate::packet::unit::Unit::decode_privilege
+    Custom(u8),
 }
 
 impl TryFrom<u8> for Privilege {
@@ -48,6 +56,7 @@ impl From<Privilege> for u8 {
             Privilege::Debug => 4,
             Privilege::VirtUser => 5,
             Privilege::VirtSupervisor => 6,
+            Privilege::Custom(p) => p,
         }
     }
 }
@@ -61,6 +70,7 @@ impl fmt::Display for Privilege {
             Privilege::Debug => write!(f, "D"),
             Privilege::VirtUser => write!(f, "VU"),
             Privilege::VirtSupervisor => write!(f, "VS"),
+            Privilege::Custom(p) => write!(f, "X{p}"),
         }
     }
 }
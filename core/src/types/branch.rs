@@ -20,10 +20,10 @@ impl Map {
     /// # Note
     ///
     /// Panics if `count` is greater than [`MAX_BRANCHES`][Self::MAX_BRANCHES].
-    pub(crate) fn new(count: u8, map: u32) -> Self {
+    pub(crate) const fn new(count: u8, map: u32) -> Self {
         assert!(
-            u32::from(count) < Self::MAX_BRANCHES,
-            "Attempt to create a branch map with {count} branches",
+            (count as u32) < Self::MAX_BRANCHES,
+            "Attempt to create a branch map with too many branches",
         );
         Self { count, map }
     }
@@ -39,6 +39,14 @@ impl Map {
         Some(res)
     }
 
+    /// Remove up to `count` of the oldest branches and return them
+    ///
+    /// The returned [`Iter`] yields `true` for every taken branch, starting
+    /// with the oldest one.
+    pub fn pop_many(&mut self, count: u8) -> Iter {
+        self.take(count).into_iter()
+    }
+
     /// Transfer a maximum number of branches to a new branch map
     pub fn take(&mut self, mut count: u8) -> Self {
         if count > self.count {
@@ -46,7 +54,7 @@ impl Map {
         }
 
         let map = self.map;
-        self.map >>= count;
+        self.map = self.map.checked_shr(count.into()).unwrap_or_default();
         self.count -= count;
         Self { count, map }
     }
@@ -76,7 +84,7 @@ impl Map {
             .checked_add(other.count)
             .filter(|c| u32::from(*c) <= Self::MAX_BRANCHES)
             .ok_or(Error::TooManyBranches)?;
-        self.map |= other.map << u32::from(self.count);
+        self.map |= other.map.checked_shl(self.count.into()).unwrap_or_default();
         self.count = total;
         Ok(())
     }
@@ -93,6 +101,44 @@ impl Map {
     pub fn raw_map(&self) -> u32 {
         self.map
     }
+
+    /// Retrieve the branches in the map as a bit vector of taken branches
+    ///
+    /// The lowest valued bit corresponds to the oldest branch. Set bits
+    /// represent taken branches. Bits beyond [`count`][Self::count] are unset.
+    pub fn taken_bits(&self) -> u32 {
+        let mask = 1u32
+            .checked_shl(self.count.into())
+            .map(|b| b.wrapping_sub(1))
+            .unwrap_or(u32::MAX);
+        !self.map & mask
+    }
+
+    /// Retrieve an [`Iter`] over the branches in the map
+    ///
+    /// The iterator yields `true` for every taken branch, starting with the
+    /// oldest one. The map itself is not altered.
+    pub fn iter(&self) -> Iter {
+        Iter(*self)
+    }
+}
+
+impl IntoIterator for Map {
+    type Item = bool;
+    type IntoIter = Iter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Iter(self)
+    }
+}
+
+impl IntoIterator for &Map {
+    type Item = bool;
+    type IntoIter = Iter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
 }
 
 impl fmt::Display for Map {
@@ -102,8 +148,7 @@ impl fmt::Display for Map {
         write!(f, "{} branches", self.count())?;
         if self.count() > 0 {
             write!(f, ": ")?;
-            let mut map = *self;
-            core::iter::from_fn(|| map.pop_taken()).try_for_each(|t| match t {
+            self.iter().try_for_each(|t| match t {
                 true => f.write_char('t'),
                 false => f.write_char('_'),
             })?;
@@ -112,6 +157,29 @@ impl fmt::Display for Map {
     }
 }
 
+/// [`Iterator`] over the branches of a [`Map`]
+///
+/// Yields `true` for every taken branch, starting with the oldest one.
+#[derive(Clone, Debug)]
+pub struct Iter(Map);
+
+impl Iterator for Iter {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.pop_taken()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let count = self.0.count().into();
+        (count, Some(count))
+    }
+}
+
+impl ExactSizeIterator for Iter {}
+
+impl core::iter::FusedIterator for Iter {}
+
 /// Errors produced by [`Map`]
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub enum Error {
@@ -0,0 +1,575 @@
+// Copyright (C) 2025, 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Return stack utiltities for infering function returns
+
+/// Return stack
+///
+/// A stack of return addresses with a predefined maximal depth.
+pub trait ReturnStack: Sized {
+    /// Create a new return stack with the given maximum depth
+    ///
+    /// Returns [`None`] if `max_depth` is greater than the value supported by
+    /// the implementaiton, or if the stack could not be created due to some
+    /// other reason.
+    fn new(max_depth: usize) -> Option<Self>;
+
+    /// Push a new return address on the stack
+    ///
+    /// If the maximal depth is reached, the bottom address will be evicted from
+    /// the stack and thus no longer be obtainable via a [`pop`][Self::pop].
+    fn push(&mut self, addr: u64);
+
+    /// Retrieve and remove the topmost return address
+    fn pop(&mut self) -> Option<u64>;
+
+    /// Get the current stack depth
+    fn depth(&self) -> usize;
+
+    /// Get the maximum stack depth
+    fn max_depth(&self) -> usize;
+}
+
+/// Statically allocated [`ReturnStack`]
+///
+/// This [`ReturnStack`] keeps data in an array of size `N`. It supports maximum
+/// depths up to that size.
+#[derive(Clone, Debug)]
+pub struct StaticStack<const N: usize> {
+    data: [u64; N],
+    max_depth: usize,
+    depth: usize,
+    base: usize,
+}
+
+impl<const N: usize> ReturnStack for StaticStack<N> {
+    fn new(max_depth: usize) -> Option<Self> {
+        if max_depth > N {
+            None
+        } else {
+            Some(Self {
+                data: [0; N],
+                max_depth,
+                depth: 0,
+                base: 0,
+            })
+        }
+    }
+
+    fn push(&mut self, addr: u64) {
+        let depth = self.depth;
+        self.data[(self.base + depth) % N] = addr;
+
+        if depth < self.max_depth {
+            self.depth = depth.saturating_add(1);
+        } else {
+            let base = self.base + 1;
+            if base < N {
+                self.base = base;
+            } else {
+                self.base = 0;
+            }
+        }
+    }
+
+    fn pop(&mut self) -> Option<u64> {
+        let depth = self.depth.checked_sub(1)?;
+        self.depth = depth;
+        Some(self.data[(self.base + depth) % N])
+    }
+
+    fn depth(&self) -> usize {
+        self.depth
+    }
+
+    fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+}
+
+/// Dummy [`ReturnStack`] with zero depth
+///
+/// This [`ReturnStack`] does not hold any data. It only supports a maximum
+/// depth of zero. Hence, any non-zero `irdepth` reported by an encoder never
+/// matches its depth. Consider using a [`DepthOnly`] stack if return addresses
+/// are not needed but the encoder reports `irdepth`.
+#[derive(Copy, Clone, Debug)]
+pub struct NoStack;
+
+impl ReturnStack for NoStack {
+    fn new(max_depth: usize) -> Option<Self> {
+        (max_depth == 0).then_some(Self)
+    }
+
+    fn push(&mut self, _: u64) {}
+
+    fn pop(&mut self) -> Option<u64> {
+        None
+    }
+
+    fn depth(&self) -> usize {
+        0
+    }
+
+    fn max_depth(&self) -> usize {
+        0
+    }
+}
+
+/// [`ReturnStack`] tracking only its depth
+///
+/// This [`ReturnStack`] does not hold any return addresses but tracks the depth
+/// a stack of the same maximum depth would have, for any maximum depth. Popping
+/// always yields [`None`]. Thus, implicit returns are never resolved from the
+/// stack, but an `irdepth` reported by the encoder is still compared against
+/// the depth, e.g. when determining whether the tracer may stop at an address
+/// reported by the encoder.
+///
+/// # Example
+///
+/// ```
+/// use riscv_etrace_core::types::stack::{DepthOnly, ReturnStack};
+///
+/// let mut stack = DepthOnly::new(2).unwrap();
+/// stack.push(0x10);
+/// stack.push(0x20);
+/// stack.push(0x30);
+/// assert_eq!(stack.depth(), 2);
+/// assert_eq!(stack.pop(), None);
+/// assert_eq!(stack.depth(), 1);
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct DepthOnly {
+    depth: usize,
+    max_depth: usize,
+}
+
+impl ReturnStack for DepthOnly {
+    fn new(max_depth: usize) -> Option<Self> {
+        Some(Self {
+            depth: 0,
+            max_depth,
+        })
+    }
+
+    fn push(&mut self, _: u64) {
+        self.depth = self.depth.saturating_add(1).min(self.max_depth);
+    }
+
+    fn pop(&mut self) -> Option<u64> {
+        self.depth = self.depth.saturating_sub(1);
+        None
+    }
+
+    fn depth(&self) -> usize {
+        self.depth
+    }
+
+    fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+}
+
+/// [`ReturnStack`] wrapper recording usage statistics
+///
+/// This [`ReturnStack`] forwards all operations to an inner stack and records
+/// [`Stats`] about its usage, such as the maximum depth observed. This allows
+/// sizing the return stack of an encoder (via `return_stack_size_p`) based on
+/// real workloads.
+///
+/// # Example
+///
+/// ```
+/// use riscv_etrace_core::types::stack::{Monitored, ReturnStack, StaticStack};
+///
+/// let mut stack = Monitored::<StaticStack<2>>::new(2).unwrap();
+/// stack.push(0x10);
+/// stack.push(0x20);
+/// stack.push(0x30);
+/// assert_eq!(stack.pop(), Some(0x30));
+/// assert_eq!(stack.stats().max_depth, 2);
+/// assert_eq!(stack.stats().overflows, 1);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Monitored<S> {
+    inner: S,
+    stats: Stats,
+}
+
+impl<S: ReturnStack> Monitored<S> {
+    /// Retrieve the [`Stats`] recorded so far
+    pub fn stats(&self) -> Stats {
+        self.stats
+    }
+
+    /// Reset the [`Stats`] recorded so far
+    pub fn reset_stats(&mut self) {
+        self.stats = Default::default();
+    }
+
+    /// Retrieve the inner [`ReturnStack`]
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+}
+
+impl<S: ReturnStack> ReturnStack for Monitored<S> {
+    fn new(max_depth: usize) -> Option<Self> {
+        S::new(max_depth).map(|inner| Self {
+            inner,
+            stats: Default::default(),
+        })
+    }
+
+    fn push(&mut self, addr: u64) {
+        if self.inner.depth() >= self.inner.max_depth() {
+            self.stats.overflows = self.stats.overflows.saturating_add(1);
+        }
+        self.inner.push(addr);
+        self.stats.pushes = self.stats.pushes.saturating_add(1);
+        self.stats.max_depth = self.stats.max_depth.max(self.inner.depth());
+    }
+
+    fn pop(&mut self) -> Option<u64> {
+        let res = self.inner.pop();
+        if res.is_some() {
+            self.stats.pops = self.stats.pops.saturating_add(1);
+        } else {
+            self.stats.underflows = self.stats.underflows.saturating_add(1);
+        }
+        res
+    }
+
+    fn depth(&self) -> usize {
+        self.inner.depth()
+    }
+
+    fn max_depth(&self) -> usize {
+        self.inner.max_depth()
+    }
+}
+
+/// Storage for return addresses spilled from a [`Spilling`] stack
+///
+/// Implementations may store addresses in arbitrary user-provided memory.
+/// Addresses are spilled and filled in LIFO order.
+pub trait Spill {
+    /// Store an address
+    ///
+    /// Returns `false` if the address could not be stored, e.g. because the
+    /// storage is exhausted.
+    fn spill(&mut self, addr: u64) -> bool;
+
+    /// Retrieve and remove the address stored most recently
+    fn fill(&mut self) -> Option<u64>;
+}
+
+impl<P: Spill + ?Sized> Spill for &mut P {
+    fn spill(&mut self, addr: u64) -> bool {
+        P::spill(self, addr)
+    }
+
+    fn fill(&mut self) -> Option<u64> {
+        P::fill(self)
+    }
+}
+
+/// [`Spill`] storage discarding all addresses
+///
+/// A [`Spilling`] stack with this storage truncates the stack to its window,
+/// accounting for addresses lost.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Discard;
+
+impl Spill for Discard {
+    fn spill(&mut self, _: u64) -> bool {
+        false
+    }
+
+    fn fill(&mut self) -> Option<u64> {
+        None
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl Spill for alloc::vec::Vec<u64> {
+    fn spill(&mut self, addr: u64) -> bool {
+        self.push(addr);
+        true
+    }
+
+    fn fill(&mut self) -> Option<u64> {
+        self.pop()
+    }
+}
+
+/// [`ReturnStack`] spilling to [`Spill`] storage
+///
+/// This stack keeps the `N` most recent return addresses in a window held
+/// inline. Addresses evicted from the window are spilled to a [`Spill`]
+/// storage and retrieved from it when the window runs empty. This allows
+/// supporting deep call stacks, e.g. for encoders using a large
+/// `call_counter_size_p`, with only a small, fixed memory footprint within
+/// the tracer itself.
+///
+/// Addresses which could not be spilled are lost. They are still accounted
+/// for in the stack's [`depth`][ReturnStack::depth], which hence matches the
+/// depth of the encoder's stack. Popping a lost address yields [`None`]. The
+/// total number of addresses lost is reported by [`losses`][Self::losses].
+///
+/// Stacks are created with [`Default`] storage. Storage in user-provided
+/// memory may be realized via a [`Default`] handle to that memory.
+///
+/// # Example
+///
+/// ```
+/// use riscv_etrace_core::types::stack::{Discard, ReturnStack, Spilling};
+///
+/// let mut stack = Spilling::<2, Discard>::new(8).unwrap();
+/// stack.push(0x10);
+/// stack.push(0x20);
+/// stack.push(0x30);
+/// assert_eq!(stack.depth(), 3);
+/// assert_eq!(stack.pop(), Some(0x30));
+/// assert_eq!(stack.pop(), Some(0x20));
+/// assert_eq!(stack.pop(), None);
+/// assert_eq!(stack.losses(), 1);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Spilling<const N: usize, P> {
+    window: StaticStack<N>,
+    storage: P,
+    max_depth: usize,
+    spilled: usize,
+    dead: usize,
+    lost: usize,
+    losses: u64,
+}
+
+impl<const N: usize, P: Spill> Spilling<N, P> {
+    /// Retrieve the [`Spill`] storage
+    pub fn storage(&self) -> &P {
+        &self.storage
+    }
+
+    /// Retrieve the total number of addresses that could not be spilled
+    pub fn losses(&self) -> u64 {
+        self.losses
+    }
+
+    /// Evict the oldest address
+    fn evict(&mut self) {
+        if let Some(spilled) = self.spilled.checked_sub(1) {
+            // The oldest address is at the bottom of the storage, which we
+            // cannot access. We drain it once all others have been filled.
+            self.spilled = spilled;
+            self.dead += 1;
+        } else if let Some(lost) = self.lost.checked_sub(1) {
+            self.lost = lost;
+        } else if let Some(depth) = self.window.depth.checked_sub(1) {
+            self.window.depth = depth;
+            self.window.base = (self.window.base + 1) % N;
+        }
+    }
+}
+
+impl<const N: usize, P: Spill + Default> ReturnStack for Spilling<N, P> {
+    fn new(max_depth: usize) -> Option<Self> {
+        if N == 0 && max_depth > 0 {
+            return None;
+        }
+        Some(Self {
+            window: StaticStack::new(N)?,
+            storage: Default::default(),
+            max_depth,
+            spilled: 0,
+            dead: 0,
+            lost: 0,
+            losses: 0,
+        })
+    }
+
+    fn push(&mut self, addr: u64) {
+        if self.max_depth == 0 {
+            return;
+        }
+        if self.depth() >= self.max_depth {
+            self.evict();
+        }
+        if self.window.depth >= N {
+            let bottom = self.window.data[self.window.base];
+            self.window.depth -= 1;
+            self.window.base = (self.window.base + 1) % N;
+            // Once an address is lost, we must not spill newer ones.
+            if self.lost == 0 && self.storage.spill(bottom) {
+                self.spilled += 1;
+            } else {
+                self.lost += 1;
+                self.losses = self.losses.saturating_add(1);
+            }
+        }
+        self.window.push(addr);
+    }
+
+    fn pop(&mut self) -> Option<u64> {
+        if self.window.depth > 0 {
+            return self.window.pop();
+        }
+        if let Some(lost) = self.lost.checked_sub(1) {
+            self.lost = lost;
+            return None;
+        }
+        let spilled = self.spilled.checked_sub(1)?;
+        self.spilled = spilled;
+        let res = self.storage.fill();
+        if spilled == 0 {
+            (0..core::mem::take(&mut self.dead)).for_each(|_| {
+                self.storage.fill();
+            });
+        }
+        res
+    }
+
+    fn depth(&self) -> usize {
+        self.window.depth + self.spilled + self.lost
+    }
+
+    fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+}
+
+/// Usage statistics of a [`ReturnStack`]
+///
+/// Statistics are recorded by a [`Monitored`] stack.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// Maximum depth observed
+    pub max_depth: usize,
+    /// Number of return addresses pushed
+    pub pushes: u64,
+    /// Number of return addresses popped
+    pub pops: u64,
+    /// Number of pushes evicting the bottom address due to the stack being full
+    pub overflows: u64,
+    /// Number of pops on an empty stack
+    pub underflows: u64,
+}
+
+#[cfg(feature = "alloc")]
+use alloc::collections::VecDeque;
+#[derive(Clone, Debug)]
+#[cfg(feature = "alloc")]
+pub struct VecStack {
+    data: VecDeque<u64>,
+    max_depth: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl ReturnStack for VecStack {
+    fn new(max_size: usize) -> Option<Self> {
+        Some(Self {
+            data: VecDeque::with_capacity(max_size),
+            max_depth: max_size,
+        })
+    }
+
+    fn depth(&self) -> usize {
+        self.data.len()
+    }
+
+    // ReturnStack Aliases
+    fn push(&mut self, addr: u64) {
+        self.push_back(addr);
+    }
+    fn pop(&mut self) -> Option<u64> {
+        self.pop_back()
+    }
+    fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl VecStack {
+    // Pushes to top of stack, like push in vector
+    pub fn push_back(&mut self, addr: u64) {
+        if self.max_depth == 0 {
+            return;
+        }
+        if self.data.len() == self.max_depth {
+            self.data.pop_front();
+        }
+        self.data.push_back(addr);
+    }
+
+    // Pushes to bottom of stack
+    pub fn push_front(&mut self, addr: u64) {
+        if self.max_depth == 0 {
+            return;
+        }
+        if self.data.len() == self.max_depth {
+            self.data.pop_back();
+        }
+        self.data.push_front(addr);
+    }
+
+    // Pops top element of stack
+    pub fn pop_back(&mut self) -> Option<u64> {
+        self.data.pop_back()
+    }
+
+    // Pops most bottom element of stack
+    pub fn pop_front(&mut self) -> Option<u64> {
+        self.data.pop_front()
+    }
+}
+
+#[cfg(feature = "alloc")]
+use alloc::{boxed::Box, vec};
+#[derive(Clone, Debug)]
+#[cfg(feature = "alloc")]
+pub struct BoxStack {
+    data: Box<[u64]>,
+    depth: usize,
+    base: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl ReturnStack for BoxStack {
+    fn new(max_depth: usize) -> Option<Self> {
+        if max_depth == 0 {
+            return None;
+        }
+        Some(Self {
+            data: vec![0u64; max_depth].into_boxed_slice(),
+            depth: 0,
+            base: 0,
+        })
+    }
+
+    fn push(&mut self, addr: u64) {
+        let max_len = self.data.len();
+        let index = (self.depth + self.base) % max_len;
+        self.data[index] = addr;
+
+        if self.depth < max_len {
+            self.depth += 1;
+        } else {
+            self.base = (self.base + 1) % max_len;
+        }
+    }
+
+    fn pop(&mut self) -> Option<u64> {
+        let depth = self.depth.checked_sub(1)?;
+        self.depth = depth;
+        Some(self.data[(self.base + depth) % self.data.len()])
+    }
+
+    fn depth(&self) -> usize {
+        self.depth
+    }
+
+    fn max_depth(&self) -> usize {
+        self.data.len()
+    }
+}
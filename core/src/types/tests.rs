@@ -0,0 +1,306 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Tests for ReturnStack for VecStack
+
+use crate::types::branch;
+use crate::types::stack::ReturnStack;
+use crate::types::stack::StaticStack;
+use crate::types::stack::{DepthOnly, Discard, Spill, Spilling};
+use crate::types::stack::{Monitored, Stats};
+use crate::types::time;
+use crate::types::trap;
+
+macro_rules! underflow_test {
+    ($n:ident, $stack_type:ty) => {
+        #[test]
+        fn $n() {
+            let mut s = <$stack_type>::new(2).unwrap();
+            assert_eq!(s.pop(), None);
+            assert_eq!(s.depth(), 0);
+
+            s.push(10);
+            s.pop();
+            assert_eq!(s.pop(), None);
+        }
+    };
+}
+
+macro_rules! return_stack_implementation {
+    ($n:ident, $stack_type:ty) => {
+        #[test]
+        fn $n() {
+            let mut s = <$stack_type>::new(3).unwrap();
+            s.push(1);
+            s.push(2);
+            s.push(3);
+            assert_eq!(s.depth(), 3);
+            assert_eq!(s.max_depth(), 3);
+
+            s.push(4);
+            assert_eq!(s.depth(), 3);
+            assert_eq!(s.pop(), Some(4));
+            assert_eq!(s.pop(), Some(3));
+            assert_eq!(s.pop(), Some(2));
+            assert_eq!(s.pop(), None);
+        }
+    };
+}
+
+underflow_test!(static_stack_under, StaticStack<2>);
+return_stack_implementation!(static_implementation, StaticStack<3>);
+underflow_test!(monitored_stack_under, Monitored<StaticStack<2>>);
+return_stack_implementation!(monitored_implementation, Monitored<StaticStack<3>>);
+underflow_test!(depth_only_under, DepthOnly);
+
+/// [`Spill`] storage with a capacity of four addresses
+#[derive(Default)]
+struct Limited {
+    data: [u64; 4],
+    len: usize,
+}
+
+impl Spill for Limited {
+    fn spill(&mut self, addr: u64) -> bool {
+        let Some(slot) = self.data.get_mut(self.len) else {
+            return false;
+        };
+        *slot = addr;
+        self.len += 1;
+        true
+    }
+
+    fn fill(&mut self) -> Option<u64> {
+        self.len = self.len.checked_sub(1)?;
+        Some(self.data[self.len])
+    }
+}
+
+underflow_test!(spilling_stack_under, Spilling<1, Limited>);
+return_stack_implementation!(spilling_implementation, Spilling<1, Limited>);
+
+#[test]
+fn spilling_losses() {
+    let mut s = Spilling::<2, Limited>::new(16).unwrap();
+    (1..=8).for_each(|a| s.push(a));
+    assert_eq!(s.depth(), 8);
+    assert_eq!(s.storage().len, 4);
+    assert_eq!(s.losses(), 2);
+    assert_eq!(s.pop(), Some(8));
+    assert_eq!(s.pop(), Some(7));
+    assert_eq!(s.pop(), None);
+    assert_eq!(s.pop(), None);
+    assert_eq!(s.pop(), Some(4));
+    s.push(9);
+    assert_eq!(s.depth(), 4);
+    assert_eq!(s.pop(), Some(9));
+    (0..3).for_each(|a| assert_eq!(s.pop(), Some(3 - a)));
+    assert_eq!(s.pop(), None);
+    assert_eq!(s.depth(), 0);
+
+    let mut s = Spilling::<2, Discard>::new(3).unwrap();
+    (1..=4).for_each(|a| s.push(a));
+    assert_eq!(s.depth(), 3);
+    assert_eq!(s.losses(), 2);
+    assert_eq!(s.pop(), Some(4));
+    assert_eq!(s.pop(), Some(3));
+    assert_eq!(s.pop(), None);
+    assert_eq!(s.depth(), 0);
+}
+
+#[test]
+fn depth_only() {
+    let mut s = DepthOnly::new(3).unwrap();
+    (1..=4).for_each(|a| s.push(a));
+    assert_eq!(s.depth(), 3);
+    assert_eq!(s.max_depth(), 3);
+    (0..4).for_each(|_| assert_eq!(s.pop(), None));
+    assert_eq!(s.depth(), 0);
+}
+
+#[test]
+fn monitored_stats() {
+    let mut s = Monitored::<StaticStack<3>>::new(3).unwrap();
+    s.push(1);
+    s.push(2);
+    assert_eq!(s.pop(), Some(2));
+    (3..6).for_each(|a| s.push(a));
+    (0..4).for_each(|_| {
+        s.pop();
+    });
+    assert_eq!(
+        s.stats(),
+        Stats {
+            max_depth: 3,
+            pushes: 5,
+            pops: 4,
+            overflows: 1,
+            underflows: 1,
+        }
+    );
+}
+
+#[cfg(feature = "alloc")]
+mod box_stack_test {
+    use super::*;
+    use crate::types::stack::BoxStack;
+
+    return_stack_implementation!(boxstack_implementation, BoxStack);
+    underflow_test!(boxstack_stack_under, BoxStack);
+    #[test]
+    fn test_box_return_stack() {
+        let mut box_stack = BoxStack::new(3).unwrap();
+        assert!(box_stack.pop().is_none(), "Expected box to be empty");
+        box_stack.push(10);
+        box_stack.push(555);
+        box_stack.push(10000);
+
+        assert_eq!(box_stack.pop(), Some(10000));
+        assert_eq!(box_stack.pop(), Some(555));
+
+        box_stack.push(35);
+        assert_eq!(box_stack.pop(), Some(35));
+        assert_eq!(box_stack.pop(), Some(10));
+        assert_eq!(box_stack.pop(), None);
+        assert_eq!(box_stack.pop(), None);
+    }
+
+    #[test]
+    fn test_boxstack_zero_size() {
+        let box_stack = BoxStack::new(0);
+        assert!(box_stack.is_none());
+    }
+}
+
+#[cfg(feature = "alloc")]
+mod vec_stack_test {
+    use super::*;
+    use crate::types::stack::VecStack;
+
+    #[test]
+    fn test_aliases_vec_return_stack() {
+        let mut vec_stack = VecStack::new(4).unwrap();
+        vec_stack.push(5);
+        vec_stack.push(10);
+        vec_stack.push(15);
+
+        assert_eq!(vec_stack.pop(), Some(15));
+        assert_eq!(vec_stack.max_depth(), 4)
+    }
+
+    #[test]
+    fn test_vec_push_overflow() {
+        let mut vec_stack = VecStack::new(3).unwrap();
+        vec_stack.push_back(5);
+        vec_stack.push_back(10);
+        vec_stack.push_back(15);
+        vec_stack.push_front(0);
+
+        assert_eq!(vec_stack.pop_back(), Some(10));
+        assert_eq!(vec_stack.pop_front(), Some(0));
+        assert_eq!(vec_stack.depth(), 1);
+        assert_eq!(vec_stack.pop_back(), Some(5));
+        assert_eq!(vec_stack.pop_back(), None);
+    }
+
+    #[test]
+    fn test_vec_stack_reusability() {
+        let mut vec_stack = VecStack::new(2).unwrap();
+        vec_stack.push(1);
+        vec_stack.push(2);
+        vec_stack.pop();
+        vec_stack.pop();
+        vec_stack.push(3);
+        assert_eq!(vec_stack.pop(), Some(3));
+        assert_eq!(vec_stack.depth(), 0)
+    }
+
+    #[test]
+    fn test_vec_zero_cap() {
+        let mut vec_stack = VecStack::new(0).unwrap();
+        vec_stack.push(1);
+        assert_eq!(vec_stack.depth(), 0);
+        assert_eq!(vec_stack.pop(), None);
+    }
+}
+
+#[test]
+fn none_return_static() {
+    let s = StaticStack::<2>::new(3);
+    assert!(s.is_none());
+}
+
+#[test]
+fn branch_map_full() {
+    let mut map = branch::Map::default();
+    (0..32).for_each(|_| map.push_branch_taken(true).unwrap());
+    assert_eq!(
+        map.push_branch_taken(true),
+        Err(branch::Error::TooManyBranches)
+    );
+    assert_eq!(map.append(Default::default()), Ok(()));
+    assert_eq!(map.count(), 32);
+    assert_eq!(map.take(32).count(), 32);
+    assert_eq!(map.count(), 0);
+}
+
+#[test]
+fn branch_map_bulk() {
+    let mut map = branch::Map::default();
+    [true, false, false, true, true]
+        .into_iter()
+        .for_each(|t| map.push_branch_taken(t).unwrap());
+    assert_eq!(map.taken_bits(), 0b11001);
+    assert_eq!(map.iter().len(), 5);
+    assert!(map.pop_many(3).eq([true, false, false]));
+    assert!(map.into_iter().eq([true, true]));
+    assert_eq!(map.pop_many(4).len(), 2);
+    assert_eq!(map.taken_bits(), 0);
+
+    (0..32).for_each(|_| map.push_branch_taken(true).unwrap());
+    assert_eq!(map.taken_bits(), u32::MAX);
+}
+
+#[test]
+fn clock_anchors() {
+    let mut clock = time::Clock::new(core::num::NonZeroU64::new(1_000_000).unwrap());
+    assert_eq!(clock.to_nanos(3), 3_000);
+
+    clock.add_anchor(time::Anchor::new(1_000, 2_000_000));
+    assert_eq!(clock.to_nanos(1_500), 3_000_000);
+    assert_eq!(clock.to_nanos(0), 0);
+
+    // Anchors going back in time only replace the reference point
+    clock.add_anchor(time::Anchor::new(500, 3_000_000));
+    assert_eq!(clock.rate().0, 2_000_000);
+    assert_eq!(clock.to_nanos(400), 2_800_000);
+    assert_eq!(clock.to_nanos(u64::MAX), u64::MAX);
+}
+
+#[test]
+fn trap_cause() {
+    use trap::{Cause, Exception, Interrupt};
+
+    let info = trap::Info {
+        ecause: 13,
+        tval: Some(0x1000),
+    };
+    assert_eq!(info.cause(), Cause::Exception(Exception::LoadPageFault));
+    let info = trap::Info {
+        ecause: 13,
+        tval: None,
+    };
+    assert_eq!(info.cause(), Cause::Interrupt(Interrupt::CounterOverflow));
+
+    (0..=64).for_each(|code| {
+        assert_eq!(u16::from(Exception::from(code)), code);
+        assert_eq!(u16::from(Interrupt::from(code)), code);
+    });
+    assert_eq!(Exception::from(14), Exception::Reserved(14));
+    assert_eq!(Exception::from(50), Exception::Custom(50));
+    assert_eq!(Interrupt::from(4), Interrupt::Reserved(4));
+    assert_eq!(Interrupt::from(17), Interrupt::Platform(17));
+    assert_eq!(
+        Interrupt::from(6).privilege(),
+        Some(crate::types::Privilege::VirtSupervisor)
+    );
+}
@@ -0,0 +1,116 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Timestamp domain conversion
+//!
+//! Timestamps reported by an encoder are in units of encoder specific ticks.
+//! This module provides a [`Clock`] for converting those into nanoseconds,
+//! e.g. for aligning trace data with other system logs.
+
+use core::num::NonZeroU64;
+
+/// Nanoseconds per second
+const NANOS_PER_SEC: u64 = 1_000_000_000;
+
+/// Point in time known in both ticks and nanoseconds
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Anchor {
+    /// Timestamp in encoder ticks
+    pub ticks: u64,
+    /// Corresponding time in nanoseconds
+    pub nanos: u64,
+}
+
+impl Anchor {
+    /// Create a new anchor
+    pub fn new(ticks: u64, nanos: u64) -> Self {
+        Self { ticks, nanos }
+    }
+}
+
+/// Conversion of encoder ticks to nanoseconds
+///
+/// A clock is created for a nominal tick rate. The time corresponding to tick
+/// `0` is `0` unless set via [`with_offset`][Self::with_offset].
+///
+/// Encoder clocks may drift relative to the clock used for other logs. For
+/// correcting such drift, [`Anchor`]s may be supplied periodically via
+/// [`add_anchor`][Self::add_anchor]. The rate is then derived from the two
+/// most recent anchors and timestamps are converted relative to the latest
+/// one.
+///
+/// # Example
+///
+/// ```
+/// use core::num::NonZeroU64;
+/// use riscv_etrace_core::types::time::{Anchor, Clock};
+///
+/// let mut clock = Clock::new(NonZeroU64::new(100_000_000).unwrap()).with_offset(5_000);
+/// assert_eq!(clock.to_nanos(100), 6_000);
+///
+/// // The encoder's clock turns out to be slightly slow
+/// clock.add_anchor(Anchor::new(1_000_000, 10_105_000));
+/// assert_eq!(clock.to_nanos(2_000_000), 20_205_000);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Clock {
+    anchor: Anchor,
+    nanos: u64,
+    ticks: NonZeroU64,
+}
+
+impl Clock {
+    /// Create a new clock with the given nominal rate in ticks per second
+    pub fn new(rate: NonZeroU64) -> Self {
+        Self {
+            anchor: Default::default(),
+            nanos: NANOS_PER_SEC,
+            ticks: rate,
+        }
+    }
+
+    /// Set the time in nanoseconds corresponding to tick `0`
+    ///
+    /// This replaces any [`Anchor`] previously added.
+    pub fn with_offset(self, nanos: u64) -> Self {
+        Self {
+            anchor: Anchor::new(0, nanos),
+            ..self
+        }
+    }
+
+    /// Add an [`Anchor`] for drift correction
+    ///
+    /// If the anchor lies after the latest anchor in both ticks and
+    /// nanoseconds, the rate is updated to the one observed between the two.
+    /// Otherwise, only the reference point is replaced and the current rate is
+    /// retained.
+    pub fn add_anchor(&mut self, anchor: Anchor) {
+        let ticks = anchor.ticks.checked_sub(self.anchor.ticks);
+        let nanos = anchor.nanos.checked_sub(self.anchor.nanos);
+        if let (Some(ticks), Some(nanos)) = (ticks.and_then(NonZeroU64::new), nanos) {
+            self.ticks = ticks;
+            self.nanos = nanos;
+        }
+        self.anchor = anchor;
+    }
+
+    /// Retrieve the latest [`Anchor`]
+    pub fn anchor(&self) -> Anchor {
+        self.anchor
+    }
+
+    /// Retrieve the current rate as nanoseconds per number of ticks
+    pub fn rate(&self) -> (u64, NonZeroU64) {
+        (self.nanos, self.ticks)
+    }
+
+    /// Convert a timestamp in ticks to nanoseconds
+    ///
+    /// Results not representable as `u64` saturate.
+    pub fn to_nanos(&self, ticks: u64) -> u64 {
+        let delta = i128::from(ticks) - i128::from(self.anchor.ticks);
+        let delta = delta.saturating_mul(self.nanos.into()) / i128::from(self.ticks.get());
+        let nanos = i128::from(self.anchor.nanos).saturating_add(delta);
+        nanos.clamp(0, u64::MAX.into()) as u64
+    }
+}
@@ -0,0 +1,344 @@
+// Copyright (C) 2025, 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Trap related types and utilities
+
+use core::fmt;
+
+use super::Privilege;
+
+/// Information about a trap
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Info {
+    /// Cause of the trap or interrupt
+    pub ecause: u16,
+    /// Appropriate `utval`/`stval`/`vstval`/`mtval`
+    ///
+    /// This field also indicates whether the trap is an interrupt (`None`) or
+    /// exception (`Some`).
+    pub tval: Option<u64>,
+}
+
+impl Info {
+    /// This info refers to an interrupt
+    pub fn is_interrupt(&self) -> bool {
+        self.tval.is_none()
+    }
+
+    /// This info refers to an exception
+    pub fn is_exception(&self) -> bool {
+        self.tval.is_some()
+    }
+
+    /// Decode the [`ecause`][Self::ecause] into a [`Cause`]
+    pub fn cause(&self) -> Cause {
+        if self.is_interrupt() {
+            Cause::Interrupt(self.ecause.into())
+        } else {
+            Cause::Exception(self.ecause.into())
+        }
+    }
+}
+
+impl fmt::Display for Info {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let ecause = self.ecause;
+        match self.tval {
+            Some(tval) => write!(f, "exception (ecause: {ecause}, tval: {tval:0x})"),
+            None => write!(f, "interrupt (ecause: {ecause})"),
+        }
+    }
+}
+
+/// Decoded cause of a trap
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Cause {
+    /// The trap is an exception
+    Exception(Exception),
+    /// The trap is an interrupt
+    Interrupt(Interrupt),
+}
+
+impl Cause {
+    /// Retrieve the raw exception or interrupt code
+    pub fn code(self) -> u16 {
+        match self {
+            Self::Exception(e) => e.into(),
+            Self::Interrupt(i) => i.into(),
+        }
+    }
+}
+
+impl fmt::Display for Cause {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Exception(e) => fmt::Display::fmt(e, f),
+            Self::Interrupt(i) => fmt::Display::fmt(i, f),
+        }
+    }
+}
+
+/// Exception codes as defined by the RISC-V privileged specification
+///
+/// Codes designated for custom use are preserved as [`Exception::Custom`].
+/// Codes reserved by the specification are preserved as
+/// [`Exception::Reserved`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Exception {
+    /// Instruction address misaligned (code 0)
+    InstructionAddressMisaligned,
+    /// Instruction access fault (code 1)
+    InstructionAccessFault,
+    /// Illegal instruction (code 2)
+    IllegalInstruction,
+    /// Breakpoint (code 3)
+    Breakpoint,
+    /// Load address misaligned (code 4)
+    LoadAddressMisaligned,
+    /// Load access fault (code 5)
+    LoadAccessFault,
+    /// Store/AMO address misaligned (code 6)
+    StoreAddressMisaligned,
+    /// Store/AMO access fault (code 7)
+    StoreAccessFault,
+    /// Environment call from U-mode or VU-mode (code 8)
+    UserEcall,
+    /// Environment call from HS-mode (code 9)
+    SupervisorEcall,
+    /// Environment call from VS-mode (code 10)
+    VirtualSupervisorEcall,
+    /// Environment call from M-mode (code 11)
+    MachineEcall,
+    /// Instruction page fault (code 12)
+    InstructionPageFault,
+    /// Load page fault (code 13)
+    LoadPageFault,
+    /// Store/AMO page fault (code 15)
+    StorePageFault,
+    /// Double trap (code 16)
+    DoubleTrap,
+    /// Software check (code 18)
+    SoftwareCheck,
+    /// Hardware error (code 19)
+    HardwareError,
+    /// Instruction guest-page fault (code 20)
+    InstructionGuestPageFault,
+    /// Load guest-page fault (code 21)
+    LoadGuestPageFault,
+    /// Virtual instruction (code 22)
+    VirtualInstruction,
+    /// Store/AMO guest-page fault (code 23)
+    StoreGuestPageFault,
+    /// Code designated for custom use (codes 24-31 and 48-63)
+    Custom(u16),
+    /// Reserved code
+    Reserved(u16),
+}
+
+impl From<u16> for Exception {
+    fn from(code: u16) -> Self {
+        match code {
+            0 => Self::InstructionAddressMisaligned,
+            1 => Self::InstructionAccessFault,
+            2 => Self::IllegalInstruction,
+            3 => Self::Breakpoint,
+            4 => Self::LoadAddressMisaligned,
+            5 => Self::LoadAccessFault,
+            6 => Self::StoreAddressMisaligned,
+            7 => Self::StoreAccessFault,
+            8 => Self::UserEcall,
+            9 => Self::SupervisorEcall,
+            10 => Self::VirtualSupervisorEcall,
+            11 => Self::MachineEcall,
+            12 => Self::InstructionPageFault,
+            13 => Self::LoadPageFault,
+            15 => Self::StorePageFault,
+            16 => Self::DoubleTrap,
+            18 => Self::SoftwareCheck,
+            19 => Self::HardwareError,
+            20 => Self::InstructionGuestPageFault,
+            21 => Self::LoadGuestPageFault,
+            22 => Self::VirtualInstruction,
+            23 => Self::StoreGuestPageFault,
+            24..=31 | 48..=63 => Self::Custom(code),
+            _ => Self::Reserved(code),
+        }
+    }
+}
+
+impl From<Exception> for u16 {
+    fn from(exception: Exception) -> Self {
+        match exception {
+            Exception::InstructionAddressMisaligned => 0,
+            Exception::InstructionAccessFault => 1,
+            Exception::IllegalInstruction => 2,
+            Exception::Breakpoint => 3,
+            Exception::LoadAddressMisaligned => 4,
+            Exception::LoadAccessFault => 5,
+            Exception::StoreAddressMisaligned => 6,
+            Exception::StoreAccessFault => 7,
+            Exception::UserEcall => 8,
+            Exception::SupervisorEcall => 9,
+            Exception::VirtualSupervisorEcall => 10,
+            Exception::MachineEcall => 11,
+            Exception::InstructionPageFault => 12,
+            Exception::LoadPageFault => 13,
+            Exception::StorePageFault => 15,
+            Exception::DoubleTrap => 16,
+            Exception::SoftwareCheck => 18,
+            Exception::HardwareError => 19,
+            Exception::InstructionGuestPageFault => 20,
+            Exception::LoadGuestPageFault => 21,
+            Exception::VirtualInstruction => 22,
+            Exception::StoreGuestPageFault => 23,
+            Exception::Custom(code) | Exception::Reserved(code) => code,
+        }
+    }
+}
+
+impl fmt::Display for Exception {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InstructionAddressMisaligned => write!(f, "instruction address misaligned"),
+            Self::InstructionAccessFault => write!(f, "instruction access fault"),
+            Self::IllegalInstruction => write!(f, "illegal instruction"),
+            Self::Breakpoint => write!(f, "breakpoint"),
+            Self::LoadAddressMisaligned => write!(f, "load address misaligned"),
+            Self::LoadAccessFault => write!(f, "load access fault"),
+            Self::StoreAddressMisaligned => write!(f, "store/AMO address misaligned"),
+            Self::StoreAccessFault => write!(f, "store/AMO access fault"),
+            Self::UserEcall => write!(f, "environment call from U-mode"),
+            Self::SupervisorEcall => write!(f, "environment call from S-mode"),
+            Self::VirtualSupervisorEcall => write!(f, "environment call from VS-mode"),
+            Self::MachineEcall => write!(f, "environment call from M-mode"),
+            Self::InstructionPageFault => write!(f, "instruction page fault"),
+            Self::LoadPageFault => write!(f, "load page fault"),
+            Self::StorePageFault => write!(f, "store/AMO page fault"),
+            Self::DoubleTrap => write!(f, "double trap"),
+            Self::SoftwareCheck => write!(f, "software check"),
+            Self::HardwareError => write!(f, "hardware error"),
+            Self::InstructionGuestPageFault => write!(f, "instruction guest-page fault"),
+            Self::LoadGuestPageFault => write!(f, "load guest-page fault"),
+            Self::VirtualInstruction => write!(f, "virtual instruction"),
+            Self::StoreGuestPageFault => write!(f, "store/AMO guest-page fault"),
+            Self::Custom(code) => write!(f, "custom exception {code}"),
+            Self::Reserved(code) => write!(f, "reserved exception {code}"),
+        }
+    }
+}
+
+/// Interrupt codes as defined by the RISC-V privileged specification
+///
+/// Codes designated for platform or custom use are preserved as
+/// [`Interrupt::Platform`]. Codes reserved by the specification are preserved
+/// as [`Interrupt::Reserved`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Interrupt {
+    /// Supervisor software interrupt (code 1)
+    SupervisorSoftware,
+    /// Virtual supervisor software interrupt (code 2)
+    VirtualSupervisorSoftware,
+    /// Machine software interrupt (code 3)
+    MachineSoftware,
+    /// Supervisor timer interrupt (code 5)
+    SupervisorTimer,
+    /// Virtual supervisor timer interrupt (code 6)
+    VirtualSupervisorTimer,
+    /// Machine timer interrupt (code 7)
+    MachineTimer,
+    /// Supervisor external interrupt (code 9)
+    SupervisorExternal,
+    /// Virtual supervisor external interrupt (code 10)
+    VirtualSupervisorExternal,
+    /// Machine external interrupt (code 11)
+    MachineExternal,
+    /// Supervisor guest external interrupt (code 12)
+    SupervisorGuestExternal,
+    /// Local counter overflow interrupt (code 13)
+    CounterOverflow,
+    /// Code designated for platform or custom use (codes 16 and above)
+    Platform(u16),
+    /// Reserved code
+    Reserved(u16),
+}
+
+impl Interrupt {
+    /// Retrieve the [`Privilege`] level the interrupt is targeted at
+    ///
+    /// Returns [`None`] for interrupts not associated to a specific level,
+    /// e.g. [`Interrupt::Platform`] ones.
+    pub fn privilege(self) -> Option<Privilege> {
+        match self {
+            Self::SupervisorSoftware
+            | Self::SupervisorTimer
+            | Self::SupervisorExternal
+            | Self::SupervisorGuestExternal
+            | Self::CounterOverflow => Some(Privilege::Supervisor),
+            Self::VirtualSupervisorSoftware
+            | Self::VirtualSupervisorTimer
+            | Self::VirtualSupervisorExternal => Some(Privilege::VirtSupervisor),
+            Self::MachineSoftware | Self::MachineTimer | Self::MachineExternal => {
+                Some(Privilege::Machine)
+            }
+            Self::Platform(_) | Self::Reserved(_) => None,
+        }
+    }
+}
+
+impl From<u16> for Interrupt {
+    fn from(code: u16) -> Self {
+        match code {
+            1 => Self::SupervisorSoftware,
+            2 => Self::VirtualSupervisorSoftware,
+            3 => Self::MachineSoftware,
+            5 => Self::SupervisorTimer,
+            6 => Self::VirtualSupervisorTimer,
+            7 => Self::MachineTimer,
+            9 => Self::SupervisorExternal,
+            10 => Self::VirtualSupervisorExternal,
+            11 => Self::MachineExternal,
+            12 => Self::SupervisorGuestExternal,
+            13 => Self::CounterOverflow,
+            16.. => Self::Platform(code),
+            _ => Self::Reserved(code),
+        }
+    }
+}
+
+impl From<Interrupt> for u16 {
+    fn from(interrupt: Interrupt) -> Self {
+        match interrupt {
+            Interrupt::SupervisorSoftware => 1,
+            Interrupt::VirtualSupervisorSoftware => 2,
+            Interrupt::MachineSoftware => 3,
+            Interrupt::SupervisorTimer => 5,
+            Interrupt::VirtualSupervisorTimer => 6,
+            Interrupt::MachineTimer => 7,
+            Interrupt::SupervisorExternal => 9,
+            Interrupt::VirtualSupervisorExternal => 10,
+            Interrupt::MachineExternal => 11,
+            Interrupt::SupervisorGuestExternal => 12,
+            Interrupt::CounterOverflow => 13,
+            Interrupt::Platform(code) | Interrupt::Reserved(code) => code,
+        }
+    }
+}
+
+impl fmt::Display for Interrupt {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::SupervisorSoftware => write!(f, "supervisor software interrupt"),
+            Self::VirtualSupervisorSoftware => write!(f, "virtual supervisor software interrupt"),
+            Self::MachineSoftware => write!(f, "machine software interrupt"),
+            Self::SupervisorTimer => write!(f, "supervisor timer interrupt"),
+            Self::VirtualSupervisorTimer => write!(f, "virtual supervisor timer interrupt"),
+            Self::MachineTimer => write!(f, "machine timer interrupt"),
+            Self::SupervisorExternal => write!(f, "supervisor external interrupt"),
+            Self::VirtualSupervisorExternal => write!(f, "virtual supervisor external interrupt"),
+            Self::MachineExternal => write!(f, "machine external interrupt"),
+            Self::SupervisorGuestExternal => write!(f, "supervisor guest external interrupt"),
+            Self::CounterOverflow => write!(f, "counter overflow interrupt"),
+            Self::Platform(code) => write!(f, "platform interrupt {code}"),
+            Self::Reserved(code) => write!(f, "reserved interrupt {code}"),
+        }
+    }
+}
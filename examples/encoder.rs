@@ -10,7 +10,7 @@
 //! is specified via the `--max-sync` option, a resync is triggered after that
 //! many CSV lines.
 
-use std::num::NonZeroUsize;
+use std::num::NonZeroU64;
 use std::path::PathBuf;
 
 use riscv_etrace::{generator, instruction, types};
@@ -38,7 +38,7 @@ fn main() {
         )
         .arg(
             clap::arg!(--"max-sync" <NUM> "Maximum value for the sync counter")
-                .value_parser(clap::value_parser!(NonZeroUsize)),
+                .value_parser(clap::value_parser!(NonZeroU64)),
         )
         .arg(
             clap::arg!(-d --debug "Enable additional debug output")
@@ -97,36 +97,38 @@ fn main() {
         .map(|w| packet::builder().with_hart_index_width(*w))
         .unwrap_or_default()
         .with_params(&params);
-    let mut encode = |payload: packet::payload::InstructionTrace| {
+
+    let generator = generator::builder()
+        .with_params(&params)
+        .build()
+        .expect("Could not set up payload generator");
+    let resync = matches
+        .get_one::<NonZeroU64>("max-sync")
+        .map(|m| generator::model::Resync::Steps(*m))
+        .unwrap_or_default();
+    let mut model: generator::model::Model<CSVLine> =
+        generator::model::Model::new(generator, builder)
+            .with_format(generator::model::Format::Smi {
+                trace_type: 0b10,
+                hart: 0,
+            })
+            .with_resync(resync);
+
+    let mut emit = |payload: &packet::payload::InstructionTrace, packet: &[u8]| {
         use std::io::Write;
 
         if debug {
             eprintln!("Encoding payload: {payload:?}");
         }
-
-        let mut buffer = [0; 40];
-        let mut encoder = builder.encoder(buffer.as_mut());
-        encoder
-            .encode(&packet::smi::Packet::new(0b10, 0, payload))
-            .expect("Could not encode packet");
-        let uncommitted = encoder.uncommitted();
         output
-            .write_all(buffer.split_at(buffer.len() - uncommitted).0)
+            .write_all(packet)
             .expect("Could not write trace to file");
     };
 
-    let mut generator: generator::Generator<CSVLine> = generator::builder()
-        .with_params(&params)
-        .build()
-        .expect("Could not set up payload generator");
-
-    let packet = generator
-        .begin_qualification(Default::default(), Default::default())
+    model
+        .begin_qualification(Default::default(), Default::default(), &mut emit)
         .expect("Could not start qualification");
-    encode(packet.into());
 
-    let max_sync = matches.get_one::<NonZeroUsize>("max-sync").cloned();
-    let mut sync_counter: usize = 0;
     input
         .map(|l| {
             l.expect("Could not read line")
@@ -134,20 +136,14 @@ fn main() {
                 .expect("Could not parse line")
         })
         .for_each(|s| {
-            let sync = Some(sync_counter) == max_sync.map(NonZeroUsize::get);
-            if sync {
-                sync_counter = 0;
-            } else {
-                sync_counter += 1;
-            }
-            generator
-                .process_step(s, sync.then_some(generator::Event::ReSync))
-                .for_each(|p| encode(p.expect("Could not generate packet")));
+            model
+                .process_step(s, false, &mut emit)
+                .expect("Could not generate packet");
         });
 
-    generator
-        .end_qualification(true)
-        .for_each(|p| encode(p.expect("Could not generate packet")));
+    model
+        .end_qualification(true, &mut emit)
+        .expect("Could not generate packet");
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -185,6 +185,7 @@ fn main() {
                     item::Kind::Regular(insn) => println!("{pc:0x}\t{insn}"),
                     item::Kind::Trap(info) => println!("Trap! {info}"),
                     item::Kind::Context(ctx) => println!("Context! priv: {:?}", ctx.privilege),
+                    kind => println!("Unknown item: {kind:?}"),
                 }
 
                 if let Some(reference) = reference.as_mut()
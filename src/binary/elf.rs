@@ -4,6 +4,7 @@
 
 use core::borrow::Borrow;
 use core::fmt;
+use core::ops::Range;
 
 use elf::ElfBytes;
 use elf::endian::EndianParse;
@@ -70,6 +71,49 @@ where
     }
 }
 
+impl<'d, E, P, D> Elf<'d, E, P, D>
+where
+    E: Borrow<ElfBytes<'d, P>>,
+    P: EndianParse,
+{
+    /// Retrieve the address and data of all executable `LOAD` segments
+    ///
+    /// The segments may be fed into a [`database::Builder`][db] e.g. for
+    /// merging this ELF with other sources of code.
+    ///
+    /// [db]: super::database::Builder
+    pub fn exec_segments(&self) -> impl Iterator<Item = Result<(u64, &'d [u8]), Error>> + '_ {
+        let elf = self.elf.borrow();
+        elf.segments()
+            .into_iter()
+            .flat_map(|s| s.iter())
+            .filter(|s| s.p_type == elf::abi::PT_LOAD && s.p_flags & elf::abi::PF_X != 0)
+            .map(move |s| {
+                elf.segment_data(&s)
+                    .map(|d| (s.p_vaddr, d))
+                    .map_err(Error::CouldNotRetrieveData)
+            })
+    }
+
+    /// Compute a [`SegmentHint`] for the given address
+    pub fn hint(&self, address: u64) -> SegmentHint {
+        let segments = self
+            .elf
+            .borrow()
+            .segments()
+            .into_iter()
+            .flat_map(|s| s.iter())
+            .filter(|s| s.p_type == elf::abi::PT_LOAD);
+        SegmentHint::new(
+            address,
+            segments.map(|s| {
+                let range = s.p_vaddr..s.p_vaddr.saturating_add(s.p_memsz);
+                (range, s.p_flags & elf::abi::PF_X != 0)
+            }),
+        )
+    }
+}
+
 impl<'d, E, P, D, I> Binary<I> for Elf<'d, E, P, D>
 where
     E: Borrow<ElfBytes<'d, P>>,
@@ -79,6 +123,10 @@ where
 {
     type Error = Error;
 
+    fn base_set(&self) -> Option<base::Set> {
+        decode::Decode::<I>::base_set(&self.base)
+    }
+
     fn get_insn(&mut self, address: u64) -> Result<Instruction<I>, Self::Error> {
         // Iterator over all relevant segments' offset and data
         let segments = self
@@ -116,7 +164,7 @@ where
                 Ok(res)
             })
             .find_map(Result::transpose)
-            .ok_or(Error::NoSegmentFound)??;
+            .ok_or_else(|| Error::NoSegmentFound(Some(self.hint(address))))??;
 
         self.last_segment = segment;
         Instruction::extract(insn_data, &self.base)
@@ -125,11 +173,242 @@ where
     }
 }
 
+/// Lazily loaded static ELF [`Binary`]
+///
+/// Like [`Elf`], this [`Binary`] retrieves [`Instruction`]s from executable
+/// `LOAD` segments. However, only the file and program headers are read on
+/// construction. Segment contents are read from the underlying reader on
+/// demand, one page at a time, and only the most recently used page is kept in
+/// memory. Hence, resident memory is bounded by the page size rather than the
+/// size of the ELF file, which is useful for hosts handling many (large)
+/// binaries concurrently.
+///
+/// Using a reader with an internal buffer such as a [`BufReader`] is not
+/// required, as pages are read in one go.
+///
+/// # Example
+///
+/// ```no_run
+/// use riscv_etrace::binary::{Binary, elf::Lazy};
+/// use riscv_etrace::instruction::{Instruction, base};
+///
+/// let file = std::fs::File::open("firmware.elf").unwrap();
+/// let mut binary: Lazy<_> = Lazy::new(file).unwrap().with_page_size(0x1000);
+/// let insn: Instruction = binary.get_insn(0x80000000).unwrap();
+/// ```
+///
+/// [`BufReader`]: std::io::BufReader
+#[cfg(feature = "std")]
+pub struct Lazy<R, D = base::Set> {
+    reader: R,
+    segments: alloc::vec::Vec<Segment>,
+    data_segments: alloc::vec::Vec<Range<u64>>,
+    last_segment: usize,
+    page: (u64, alloc::vec::Vec<u8>),
+    page_size: u64,
+    base: D,
+}
+
+#[cfg(feature = "std")]
+impl<R, D> Lazy<R, D>
+where
+    R: std::io::Read + std::io::Seek,
+    D: decode::MakeDecode,
+{
+    /// Create a new lazily loaded ELF [`Binary`]
+    ///
+    /// This fn reads the file and program headers from the given reader.
+    pub fn new(mut reader: R) -> Result<Self, Error> {
+        use elf::endian::AnyEndian;
+        use elf::file::{self, Class, FileHeader};
+        use elf::{abi, segment::SegmentTable};
+
+        let mut ident = [0; abi::EI_NIDENT];
+        read_at(&mut reader, 0, &mut ident)?;
+        let ident = file::parse_ident::<AnyEndian>(&ident).map_err(Error::CouldNotRetrieveData)?;
+        let mut tail = alloc::vec![0; match ident.1 {
+            Class::ELF32 => file::ELF32_EHDR_TAILSIZE,
+            Class::ELF64 => file::ELF64_EHDR_TAILSIZE,
+        }];
+        read_at(&mut reader, abi::EI_NIDENT as u64, &mut tail)?;
+        let hdr = FileHeader::parse_tail(ident, &tail).map_err(Error::CouldNotRetrieveData)?;
+
+        if hdr.e_machine != abi::EM_RISCV {
+            return Err(Error::UnsupportedArchitecture);
+        } else if !hdr.endianness.is_little() {
+            return Err(Error::UnsupportedEndianess);
+        }
+        let base = match hdr.class {
+            Class::ELF32 => decode::MakeDecode::rv32i_full(),
+            Class::ELF64 => decode::MakeDecode::rv64i_full(),
+        };
+
+        let table_size = usize::from(hdr.e_phentsize) * usize::from(hdr.e_phnum);
+        let mut table = alloc::vec![0; table_size];
+        read_at(&mut reader, hdr.e_phoff, &mut table)?;
+        let (segments, data_segments) = SegmentTable::new(hdr.endianness, hdr.class, &table)
+            .iter()
+            .filter(|s| s.p_type == abi::PT_LOAD)
+            .partition::<alloc::vec::Vec<_>, _>(|s| s.p_flags & abi::PF_X != 0);
+        let segments = segments
+            .into_iter()
+            .map(|s| Segment {
+                vaddr: s.p_vaddr,
+                offset: s.p_offset,
+                size: s.p_filesz,
+            })
+            .collect();
+        let data_segments = data_segments
+            .into_iter()
+            .map(|s| s.p_vaddr..s.p_vaddr.saturating_add(s.p_memsz))
+            .collect();
+
+        Ok(Self {
+            reader,
+            segments,
+            data_segments,
+            last_segment: 0,
+            page: (u64::MAX, Default::default()),
+            page_size: 0x1000,
+            base,
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R, D> Lazy<R, D> {
+    /// Set the size of the pages read from the underlying reader
+    ///
+    /// By default, pages of 4KiB are read. Pages are extended by a few bytes
+    /// in order to accomodate instructions crossing page boundaries.
+    pub fn with_page_size(self, page_size: u64) -> Self {
+        Self {
+            page_size: page_size.max(1),
+            page: (u64::MAX, Default::default()),
+            ..self
+        }
+    }
+
+    /// Retrieve the [`base::Set`] of the instruction in this ELF
+    pub fn base_set(&self) -> &D {
+        &self.base
+    }
+
+    /// Retrieve the underlying reader
+    pub fn into_inner(self) -> R {
+        self.reader
+    }
+
+    /// Compute a [`SegmentHint`] for the given address
+    pub fn hint(&self, address: u64) -> SegmentHint {
+        let exec = self
+            .segments
+            .iter()
+            .map(|s| (s.vaddr..s.vaddr + s.size, true));
+        let data = self.data_segments.iter().map(|r| (r.clone(), false));
+        SegmentHint::new(address, exec.chain(data))
+    }
+}
+
+#[cfg(feature = "std")]
+impl<R, D, I> Binary<I> for Lazy<R, D>
+where
+    R: std::io::Read + std::io::Seek,
+    I: info::Info,
+    D: decode::Decode<I>,
+{
+    type Error = Error;
+
+    fn base_set(&self) -> Option<base::Set> {
+        decode::Decode::<I>::base_set(&self.base)
+    }
+
+    fn get_insn(&mut self, address: u64) -> Result<Instruction<I>, Self::Error> {
+        /// Number of bytes by which pages are extended
+        const OVERLAP: u64 = 8;
+
+        let (start, page) = &self.page;
+        let cached = address
+            .checked_sub(*start)
+            .and_then(|o| o.try_into().ok())
+            .and_then(|o| page.get(o..))
+            .filter(|d| address - start < self.page_size && !d.is_empty());
+        let data = if let Some(data) = cached {
+            data
+        } else {
+            let index = core::iter::once(self.last_segment)
+                .chain(0..self.segments.len())
+                .find(|i| self.segments.get(*i).is_some_and(|s| s.contains(address)))
+                .ok_or_else(|| Error::NoSegmentFound(Some(self.hint(address))))?;
+            self.last_segment = index;
+            let segment = self.segments[index];
+
+            let offset = address - segment.vaddr;
+            let page_offset = offset - offset % self.page_size;
+            let len = self
+                .page_size
+                .saturating_add(OVERLAP)
+                .min(segment.size - page_offset);
+            let len = len.try_into().map_err(Error::ExceededHostUSize)?;
+
+            let mut page = core::mem::take(&mut self.page.1);
+            page.resize(len, 0);
+            let file_offset = segment.offset.saturating_add(page_offset);
+            read_at(&mut self.reader, file_offset, &mut page)?;
+            self.page = (segment.vaddr + page_offset, page);
+
+            let offset = (offset - page_offset)
+                .try_into()
+                .map_err(Error::ExceededHostUSize)?;
+            &self.page.1[offset..]
+        };
+
+        Instruction::extract(data, &self.base)
+            .map(|(i, _)| i)
+            .ok_or(Error::InvalidInstruction)
+    }
+}
+
+/// Executable segment of a [`Lazy`] ELF
+#[cfg(feature = "std")]
+#[derive(Copy, Clone, Debug)]
+struct Segment {
+    vaddr: u64,
+    offset: u64,
+    size: u64,
+}
+
+#[cfg(feature = "std")]
+impl Segment {
+    /// Determine whether the given address is within this segment
+    fn contains(&self, address: u64) -> bool {
+        address
+            .checked_sub(self.vaddr)
+            .is_some_and(|o| o < self.size)
+    }
+}
+
+/// Read data at the given offset, filling the entire buffer
+#[cfg(feature = "std")]
+fn read_at(
+    reader: &mut (impl std::io::Read + std::io::Seek),
+    offset: u64,
+    buf: &mut [u8],
+) -> Result<(), Error> {
+    reader
+        .seek(std::io::SeekFrom::Start(offset))
+        .map_err(Error::Io)?;
+    reader.read_exact(buf).map_err(Error::Io)
+}
+
 /// ELF specific error type
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Error {
     /// No segment was found containing the address
-    NoSegmentFound,
+    ///
+    /// The error may carry a [`SegmentHint`] for diagnosing the cause.
+    NoSegmentFound(Option<SegmentHint>),
     /// The data for a segment could not be retrieved
     CouldNotRetrieveData(elf::parse::ParseError),
     /// Could not use an address or offset because it is too big for the host
@@ -140,17 +419,20 @@ pub enum Error {
     UnsupportedArchitecture,
     /// The ELF file is not little endian
     UnsupportedEndianess,
+    /// The ELF file could not be read
+    #[cfg(feature = "std")]
+    Io(std::io::Error),
 }
 
 impl error::Miss for Error {
     fn miss(_: u64) -> Self {
-        Self::NoSegmentFound
+        Self::NoSegmentFound(None)
     }
 }
 
 impl error::MaybeMiss for Error {
     fn is_miss(&self) -> bool {
-        matches!(self, Self::NoSegmentFound)
+        matches!(self, Self::NoSegmentFound(_))
     }
 }
 
@@ -159,6 +441,8 @@ impl core::error::Error for Error {
         match self {
             Self::CouldNotRetrieveData(e) => Some(e),
             Self::ExceededHostUSize(e) => Some(e),
+            #[cfg(feature = "std")]
+            Self::Io(e) => Some(e),
             _ => None,
         }
     }
@@ -167,7 +451,10 @@ impl core::error::Error for Error {
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::NoSegmentFound => write!(f, "Could not find segment for address"),
+            Self::NoSegmentFound(None) => write!(f, "Could not find segment for address"),
+            Self::NoSegmentFound(Some(hint)) => {
+                write!(f, "Could not find segment for address ({hint})")
+            }
             Self::CouldNotRetrieveData(_) => write!(f, "Could not retrieve data for segment"),
             Self::ExceededHostUSize(_) => write!(
                 f,
@@ -176,6 +463,8 @@ impl fmt::Display for Error {
             Self::InvalidInstruction => write!(f, "No valid instruction at address"),
             Self::UnsupportedArchitecture => write!(f, "The target architecture is not supported"),
             Self::UnsupportedEndianess => write!(f, "The target is not little endian"),
+            #[cfg(feature = "std")]
+            Self::Io(_) => write!(f, "Could not read ELF file"),
         }
     }
 }
@@ -183,13 +472,108 @@ impl fmt::Display for Error {
 impl PartialEq for Error {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
-            (Self::NoSegmentFound, Self::NoSegmentFound) => true,
+            (Self::NoSegmentFound(_), Self::NoSegmentFound(_)) => true,
             (Self::CouldNotRetrieveData(_), Self::CouldNotRetrieveData(_)) => true,
             (Self::ExceededHostUSize(l), Self::ExceededHostUSize(r)) => l == r,
             (Self::InvalidInstruction, Self::InvalidInstruction) => true,
             (Self::UnsupportedArchitecture, Self::UnsupportedArchitecture) => true,
             (Self::UnsupportedEndianess, Self::UnsupportedEndianess) => true,
+            #[cfg(feature = "std")]
+            (Self::Io(l), Self::Io(r)) => l.kind() == r.kind(),
             _ => false,
         }
     }
 }
+
+/// Hints for diagnosing a failure to find an executable segment
+///
+/// A hint names the executable segments closest to an address for which no
+/// executable segment could be found, and the non-executable segment containing
+/// the address, if any. Those may help diagnosing the use of a wrong binary or
+/// a wrong offset.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SegmentHint {
+    /// Address for which no executable segment was found
+    pub address: u64,
+    /// Nearest executable segment below the address
+    pub below: Option<Range<u64>>,
+    /// Nearest executable segment above the address
+    pub above: Option<Range<u64>>,
+    /// Non-executable segment containing the address
+    pub non_executable: Option<Range<u64>>,
+}
+
+impl SegmentHint {
+    /// Create a new hint from the given `LOAD` segments
+    ///
+    /// The segments are given as pairs of address ranges and a flag indicating
+    /// whether the segment is executable.
+    pub fn new(address: u64, segments: impl IntoIterator<Item = (Range<u64>, bool)>) -> Self {
+        segments.into_iter().filter(|(r, _)| !r.is_empty()).fold(
+            Self {
+                address,
+                ..Default::default()
+            },
+            |hint, (range, executable)| {
+                if !executable {
+                    let non_executable = hint
+                        .non_executable
+                        .or_else(|| range.contains(&address).then_some(range));
+                    Self {
+                        non_executable,
+                        ..hint
+                    }
+                } else if range.end <= address {
+                    let below = match hint.below {
+                        Some(b) if b.end >= range.end => Some(b),
+                        _ => Some(range),
+                    };
+                    Self { below, ..hint }
+                } else if range.start > address {
+                    let above = match hint.above {
+                        Some(a) if a.start <= range.start => Some(a),
+                        _ => Some(range),
+                    };
+                    Self { above, ..hint }
+                } else {
+                    hint
+                }
+            },
+        )
+    }
+
+    /// Retrieve the nearest executable segment and its distance
+    ///
+    /// The distance is measured from the address to the nearest end of the
+    /// segment.
+    pub fn nearest(&self) -> Option<(&Range<u64>, u64)> {
+        let below = self.below.as_ref().map(|r| (r, self.address - (r.end - 1)));
+        let above = self.above.as_ref().map(|r| (r, r.start - self.address));
+        match (below, above) {
+            (Some(b), Some(a)) if a.1 < b.1 => Some(a),
+            (Some(b), _) => Some(b),
+            (None, a) => a,
+        }
+    }
+}
+
+impl fmt::Display for SegmentHint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:#x}", self.address)?;
+        if let Some(segment) = &self.non_executable {
+            write!(
+                f,
+                " is in non-executable segment {:#x}..{:#x}",
+                segment.start, segment.end
+            )?;
+        }
+        match self.nearest() {
+            Some((segment, distance)) => write!(
+                f,
+                ", nearest executable segment {:#x}..{:#x} is {distance:#x} bytes away",
+                segment.start, segment.end
+            ),
+            None => write!(f, ", no executable segments"),
+        }
+    }
+}
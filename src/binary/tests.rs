@@ -3,9 +3,9 @@
 
 use super::*;
 
-use crate::instruction;
+use crate::instruction::{self, Instruction};
 
-use error::MaybeMiss;
+use error::{MaybeMiss, Miss};
 
 macro_rules! retrieval_test {
     ($n:ident, $b:expr, $($a:literal $(=> $i:expr)?),*) => {
@@ -29,108 +29,6 @@ macro_rules! retrieval_test {
     };
 }
 
-#[cfg(feature = "alloc")]
-#[test]
-fn boxed() {
-    let mut binary = from_sorted_map([
-        (0x1000, instruction::UNCOMPRESSED),
-        (0x1004, instruction::COMPRESSED),
-    ])
-    .boxed();
-    assert!(binary.get_insn(0x0).is_miss());
-    assert_eq!(
-        binary.get_insn(0x1000).expect("Could not get insn"),
-        instruction::UNCOMPRESSED
-    );
-}
-
-retrieval_test!(option, None::<Empty>, 0x0);
-
-retrieval_test!(
-    from_func,
-    from_fn(|a| {
-        match a {
-            0x1000 => Ok(instruction::UNCOMPRESSED),
-            _ => Err(error::NoInstruction),
-        }
-    }),
-    0x1000 => Ok(instruction::UNCOMPRESSED),
-    0x1004
-);
-
-retrieval_test!(
-    offset,
-    from_sorted_map([
-        (0x00, instruction::UNCOMPRESSED),
-        (0x04, instruction::COMPRESSED),
-    ]).with_offset(0x1000),
-    0x0,
-    0x1000 => Ok(instruction::UNCOMPRESSED),
-    0x1004 => Ok(instruction::COMPRESSED),
-    0x1008
-);
-
-retrieval_test!(
-    segment,
-    from_segment(
-        b"\x97\x02\x00\x00\xff\x00\x00\x00\x73\x25\x40\xf1\x83\xb2\x82\x01\x67\x80\x02\x00",
-        instruction::base::Set::Rv64I,
-    ),
-    0x00 => Ok(instruction::Kind::new_auipc(5, 0).into()),
-    0x04 => Err(error::SegmentError::InvalidInstruction),
-    0x08 => Ok(instruction::UNCOMPRESSED),
-    0x0c => Ok(instruction::UNCOMPRESSED),
-    0x10 => Ok(instruction::Kind::new_jalr(0, 5, 0).into()),
-    0x14
-);
-
-retrieval_test!(
-    segment_tuple,
-    (
-        from_segment(b"\xff\x00\x00\x00\x73\x25\x40\xf1", instruction::base::Set::Rv64I),
-        from_segment(b"\x97\x02\x00\x00", instruction::base::Set::Rv64I).with_offset(0x1000),
-    ),
-    0x0000 => Err(error::SegmentError::InvalidInstruction),
-    0x0004 => Ok(instruction::UNCOMPRESSED),
-    0x1000 => Ok(instruction::Kind::new_auipc(5, 0).into()),
-    0x1004
-);
-
-retrieval_test!(
-    simple_map,
-    from_sorted_map([
-        (0x1000, instruction::UNCOMPRESSED),
-        (0x1004, instruction::UNCOMPRESSED),
-    ]),
-    0x0,
-    0x1000 => Ok(instruction::UNCOMPRESSED),
-    0x1004 => Ok(instruction::UNCOMPRESSED),
-    0x1008
-);
-
-retrieval_test!(empty, Empty, 0x0);
-
-retrieval_test!(
-    multi,
-    Multi::new([
-        from_sorted_map([
-            (0x1000, instruction::UNCOMPRESSED),
-            (0x1004, instruction::UNCOMPRESSED),
-        ]),
-        from_sorted_map([
-            (0x2000, instruction::UNCOMPRESSED),
-            (0x2004, instruction::UNCOMPRESSED),
-        ]),
-    ]),
-    0x0,
-    0x1000 => Ok(instruction::UNCOMPRESSED),
-    0x1004 => Ok(instruction::UNCOMPRESSED),
-    0x2000 => Ok(instruction::UNCOMPRESSED),
-    0x2004 => Ok(instruction::UNCOMPRESSED),
-    0x1000 => Ok(instruction::UNCOMPRESSED),
-    0x1008
-);
-
 #[cfg(feature = "elf")]
 retrieval_test!(
     elf,
@@ -154,37 +52,61 @@ retrieval_test!(
     0xa000001e
 );
 
-#[test]
-fn binary_from_sorted_map() {
-    from_sorted_map([
-        (0x1000, instruction::UNCOMPRESSED),
-        (0x1004, instruction::UNCOMPRESSED),
-    ])
-    .expect("Could not create binary");
-}
+#[cfg(all(feature = "elf", feature = "std"))]
+retrieval_test!(
+    elf_lazy,
+    {
+        let elf = std::io::Cursor::new(include_bytes!("testfile.elf"));
+        elf::Lazy::<_>::new(elf)
+            .expect("Could not construct binary from ELF file")
+            .with_page_size(0x10)
+    },
+    0x0,
+    0xa0000000 => Ok(instruction::Kind::new_auipc(13, 0).into()),
+    0xa0000004 => Ok(instruction::UNCOMPRESSED),
+    0xa0000008 => Ok(instruction::UNCOMPRESSED),
+    0xa000000c => Ok(instruction::Kind::new_jal(0, 10).into()),
+    0xa0000010 => Ok(instruction::Kind::wfi.into()),
+    0xa0000014 => Ok(instruction::Kind::new_c_j(0, -4).into()),
+    0xa0000016 => Ok(instruction::Kind::wfi.into()),
+    0xa000001a => Ok(instruction::Kind::new_jal(0, -4).into()),
+    0xa0000000 => Ok(instruction::Kind::new_auipc(13, 0).into()),
+    0xa000001e
+);
 
+#[cfg(feature = "elf")]
 #[test]
-fn binary_from_unsorted_map() {
-    assert_eq!(
-        from_sorted_map([
-            (0x1004, instruction::UNCOMPRESSED),
-            (0x1000, instruction::UNCOMPRESSED),
-        ]),
-        None,
-    );
+fn elf_segment_hint() {
+    let elf = include_bytes!("testfile.elf");
+    let elf = ::elf::ElfBytes::<::elf::endian::LittleEndian>::minimal_parse(elf)
+        .expect("Coult not parse ELF file");
+    let mut binary = elf::Elf::<_, _, instruction::base::Set>::new(elf)
+        .expect("Could not construct binary from ELF file");
+    let res: Result<Instruction, _> = binary.get_insn(0xa0000100);
+    let Err(elf::Error::NoSegmentFound(Some(hint))) = res else {
+        panic!("Unexpected result: {res:?}");
+    };
+    assert_eq!(hint.below, Some(0xa0000000..0xa000001e));
+    assert_eq!(hint.above, None);
+    assert_eq!(hint.non_executable, None);
+    assert_eq!(hint.nearest(), Some((&(0xa0000000..0xa000001e), 0xe3)));
 }
 
+#[cfg(feature = "elf")]
 #[test]
-fn binary_from_map() {
-    assert_eq!(
-        from_map([
-            (0x1004, instruction::UNCOMPRESSED),
-            (0x1000, instruction::UNCOMPRESSED),
-        ]),
-        from_sorted_map([
-            (0x1000, instruction::UNCOMPRESSED),
-            (0x1004, instruction::UNCOMPRESSED),
-        ])
-        .expect("Could not create binary"),
+fn segment_hint() {
+    let hint = elf::SegmentHint::new(
+        0x1800,
+        [
+            (0x0..0x100, true),
+            (0x1000..0x1100, true),
+            (0x1800..0x1900, false),
+            (0x2000..0x2100, true),
+            (0x4000..0x4100, true),
+        ],
     );
+    assert_eq!(hint.below, Some(0x1000..0x1100));
+    assert_eq!(hint.above, Some(0x2000..0x2100));
+    assert_eq!(hint.non_executable, Some(0x1800..0x1900));
+    assert_eq!(hint.nearest(), Some((&(0x1000..0x1100), 0x701)));
 }
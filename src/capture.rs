@@ -0,0 +1,258 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Reading of capture files
+//!
+//! Captures of trace data are often stored compressed, as they easily reach
+//! multiple gigabytes. This module provides a [`Reader`] which transparently
+//! decompresses captures while reading them, detecting the [`Compression`]
+//! from the capture's first bytes. Uncompressed captures are read as is.
+//!
+//! Decompression of [Zstandard][Compression::Zstd] and [LZ4][Compression::Lz4]
+//! frames requires the `zstd` and `lz4` features respectively. Captures are
+//! read in [segments][Reader::segments] suitable for
+//! [`Session::process_segment`], so that they never need to be held in memory
+//! or on disk in their entirety.
+//!
+//! # Example
+//!
+//! ```no_run
+//! use riscv_etrace::capture::Reader;
+//! use riscv_etrace::session::{self, segment::Boundary};
+//!
+//! let mut session: session::Session<_, _> = session::builder()
+//!     .with_params(&Default::default())
+//!     .build();
+//! let mut reader = Reader::open("capture.bin.zst").unwrap();
+//! for segment in reader.segments(1 << 20) {
+//!     let segment = segment.unwrap();
+//!     session
+//!         .process_segment(&segment, Boundary::Contiguous, |hart, item, _| {
+//!             println!("Hart {hart}, PC: {:0x}", item.pc());
+//!         })
+//!         .unwrap();
+//! }
+//! ```
+//!
+//! [`Session::process_segment`]: crate::session::Session::process_segment
+
+#[cfg(test)]
+mod tests;
+
+use std::boxed::Box;
+use std::fs;
+use std::io::{self, Read};
+use std::path::Path;
+use std::vec::Vec;
+
+/// Magic bytes of a Zstandard frame
+const ZSTD_MAGIC: [u8; 4] = [0x28, 0xb5, 0x2f, 0xfd];
+
+/// Magic bytes of an LZ4 frame
+const LZ4_MAGIC: [u8; 4] = [0x04, 0x22, 0x4d, 0x18];
+
+/// Compression of a capture
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum Compression {
+    /// Uncompressed raw trace data
+    #[default]
+    None,
+    /// One or more Zstandard frames
+    Zstd,
+    /// One or more LZ4 frames
+    Lz4,
+}
+
+impl Compression {
+    /// Detect the compression from the first bytes of a capture
+    ///
+    /// Data not starting with the magic bytes of a known compression format is
+    /// considered uncompressed.
+    pub fn detect(data: &[u8]) -> Self {
+        match data.get(..4) {
+            Some(magic) if magic == ZSTD_MAGIC => Self::Zstd,
+            Some(magic) if magic == LZ4_MAGIC => Self::Lz4,
+            _ => Self::None,
+        }
+    }
+
+    /// Retrieve a short name for this compression
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Zstd => "zstd",
+            Self::Lz4 => "lz4",
+        }
+    }
+}
+
+/// Reader for possibly compressed captures
+///
+/// See the [module][self] documentation for details.
+pub struct Reader<'r> {
+    inner: Box<dyn Read + 'r>,
+    compression: Compression,
+}
+
+impl Reader<'static> {
+    /// Open the capture file at the given path
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::new(io::BufReader::new(fs::File::open(path)?))
+    }
+}
+
+impl<'r> Reader<'r> {
+    /// Create a new reader for the capture read from the given [`Read`]
+    ///
+    /// The first bytes are read for detecting the [`Compression`]. An error of
+    /// kind [`io::ErrorKind::Unsupported`] is returned if the capture is
+    /// compressed but support for the [`Compression`] is not enabled.
+    pub fn new(mut inner: impl Read + 'r) -> io::Result<Self> {
+        let mut magic = Vec::with_capacity(4);
+        inner.by_ref().take(4).read_to_end(&mut magic)?;
+        let compression = Compression::detect(&magic);
+        let inner = io::Cursor::new(magic).chain(inner);
+        let inner: Box<dyn Read + 'r> = match compression {
+            Compression::None => Box::new(inner),
+            #[cfg(feature = "zstd")]
+            Compression::Zstd => Box::new(zstd::Frames::new(inner)),
+            #[cfg(feature = "lz4")]
+            Compression::Lz4 => Box::new(lz4_flex::frame::FrameDecoder::new(inner)),
+            #[allow(unreachable_patterns)]
+            compression => {
+                return Err(io::Error::new(
+                    io::ErrorKind::Unsupported,
+                    std::format!(
+                        "{} compressed captures require the `{}` feature",
+                        compression.name(),
+                        compression.name(),
+                    ),
+                ));
+            }
+        };
+        Ok(Self { inner, compression })
+    }
+
+    /// Retrieve the [`Compression`] detected
+    pub fn compression(&self) -> Compression {
+        self.compression
+    }
+
+    /// Iterate over segments of the decompressed capture
+    ///
+    /// Each segment holds up to `size` bytes. Segments are contiguous, i.e.
+    /// packets may be cut at the end of a segment and continue in the next.
+    pub fn segments(&mut self, size: usize) -> Segments<'_, 'r> {
+        Segments {
+            reader: self,
+            size: size.max(1),
+        }
+    }
+}
+
+impl Read for Reader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+/// Iterator over segments of a capture
+///
+/// Created via [`Reader::segments`].
+pub struct Segments<'a, 'r> {
+    reader: &'a mut Reader<'r>,
+    size: usize,
+}
+
+impl Iterator for Segments<'_, '_> {
+    type Item = io::Result<Vec<u8>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut segment = Vec::with_capacity(self.size);
+        let res = self
+            .reader
+            .by_ref()
+            .take(self.size as u64)
+            .read_to_end(&mut segment);
+        match res {
+            Ok(0) => None,
+            Ok(_) => Some(Ok(segment)),
+            Err(e) => Some(Err(e)),
+        }
+    }
+}
+
+#[cfg(feature = "zstd")]
+mod zstd {
+    use std::io::{self, BufRead, Read};
+
+    use ruzstd::decoding::errors::{FrameDecoderError, ReadFrameHeaderError};
+    use ruzstd::decoding::{BlockDecodingStrategy, FrameDecoder};
+
+    /// Decoder for a sequence of Zstandard frames
+    ///
+    /// Skippable frames are skipped.
+    pub struct Frames<R> {
+        inner: io::BufReader<R>,
+        decoder: FrameDecoder,
+        in_frame: bool,
+    }
+
+    impl<R: Read> Frames<R> {
+        /// Create a new decoder for the frames read from the given [`Read`]
+        pub fn new(inner: R) -> Self {
+            Self {
+                inner: io::BufReader::new(inner),
+                decoder: FrameDecoder::new(),
+                in_frame: false,
+            }
+        }
+
+        /// Start decoding the next frame, skipping skippable frames
+        ///
+        /// Returns `false` if no data is left.
+        fn next_frame(&mut self) -> io::Result<bool> {
+            loop {
+                if self.inner.fill_buf()?.is_empty() {
+                    return Ok(false);
+                }
+                match self.decoder.reset(&mut self.inner) {
+                    Ok(()) => return Ok(true),
+                    Err(FrameDecoderError::ReadFrameHeaderError(
+                        ReadFrameHeaderError::SkipFrame { length, .. },
+                    )) => {
+                        let mut skipped = self.inner.by_ref().take(length.into());
+                        io::copy(&mut skipped, &mut io::sink())?;
+                    }
+                    Err(e) => return Err(io::Error::new(io::ErrorKind::InvalidData, e)),
+                }
+            }
+        }
+    }
+
+    impl<R: Read> Read for Frames<R> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            loop {
+                if self.in_frame {
+                    let decoder = &mut self.decoder;
+                    while decoder.can_collect() < buf.len() && !decoder.is_finished() {
+                        let needed = buf.len() - decoder.can_collect();
+                        decoder
+                            .decode_blocks(
+                                &mut self.inner,
+                                BlockDecodingStrategy::UptoBytes(needed),
+                            )
+                            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+                    }
+                    let len = decoder.read(buf)?;
+                    if len > 0 || buf.is_empty() {
+                        return Ok(len);
+                    }
+                }
+                self.in_frame = self.next_frame()?;
+                if !self.in_frame {
+                    return Ok(0);
+                }
+            }
+        }
+    }
+}
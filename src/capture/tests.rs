@@ -0,0 +1,68 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+/// Raw trace data used in tests
+const DATA: &[u8] = b"\x45\x73\x0a\x00\x00\x20\x41\x01";
+
+#[test]
+fn uncompressed_capture() {
+    let mut reader = Reader::new(DATA).expect("Could not create reader");
+    assert_eq!(reader.compression(), Compression::None);
+    let segments: Vec<_> = reader
+        .segments(3)
+        .collect::<io::Result<_>>()
+        .expect("Could not read segments");
+    assert_eq!(segments.concat(), DATA);
+    assert_eq!(segments.len(), 3);
+
+    let mut reader = Reader::new(&DATA[..2]).expect("Could not create reader");
+    assert_eq!(reader.segments(8).count(), 1);
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn zstd_capture() {
+    use ruzstd::encoding::{CompressionLevel, compress_to_vec};
+
+    let mut data = compress_to_vec(DATA, CompressionLevel::Fastest);
+    data.extend_from_slice(&[0x50, 0x2a, 0x4d, 0x18, 2, 0, 0, 0, 0xff, 0xff]);
+    data.extend(compress_to_vec(DATA, CompressionLevel::Fastest));
+
+    let mut reader = Reader::new(data.as_slice()).expect("Could not create reader");
+    assert_eq!(reader.compression(), Compression::Zstd);
+    let mut decompressed = Vec::new();
+    reader
+        .read_to_end(&mut decompressed)
+        .expect("Could not decompress");
+    assert_eq!(decompressed, [DATA, DATA].concat());
+}
+
+#[cfg(feature = "lz4")]
+#[test]
+fn lz4_capture() {
+    use std::io::Write;
+
+    let mut encoder = lz4_flex::frame::FrameEncoder::new(Vec::new());
+    encoder.write_all(DATA).expect("Could not compress");
+    let data = encoder.finish().expect("Could not compress");
+
+    let mut reader = Reader::new(data.as_slice()).expect("Could not create reader");
+    assert_eq!(reader.compression(), Compression::Lz4);
+    let segments: Vec<_> = reader
+        .segments(4)
+        .collect::<io::Result<_>>()
+        .expect("Could not read segments");
+    assert_eq!(segments.concat(), DATA);
+}
+
+#[cfg(not(feature = "zstd"))]
+#[test]
+fn unsupported_compression() {
+    let res = Reader::new([0x28, 0xb5, 0x2f, 0xfd, 0x00].as_slice());
+    assert_eq!(
+        res.err().map(|e| e.kind()),
+        Some(io::ErrorKind::Unsupported)
+    );
+}
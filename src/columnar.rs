@@ -0,0 +1,214 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Bulk tracing into columnar buffers
+//!
+//! Tracing a large capture item by item results in one [`Item`] value per
+//! retired instruction, most of which is discarded by analyses only looking at
+//! PCs. This module provides [`trace`] for tracing an entire capture into
+//! [`Columns`], a set of parallel arrays with one entry per [`Item`]. The
+//! arrays may be handed off directly to columnar formats or numerical
+//! libraries, e.g. Arrow or NumPy, without further conversion.
+//!
+//! # Example
+//!
+//! ```
+//! use riscv_etrace::columnar::{Columns, Kind};
+//! use riscv_etrace::instruction::Kind as InsnKind;
+//! use riscv_etrace::tracer::item::Item;
+//!
+//! let mut columns = Columns::default();
+//! columns.push(0, Some(10), &Item::new(0x1000, InsnKind::c_nop.into()));
+//! columns.push(1, None, &Item::new(0x2000, InsnKind::c_nop.into()));
+//! columns.push(0, None, &Item::new(0x1002, InsnKind::c_nop.into()));
+//! assert_eq!(columns.len(), 3);
+//! assert_eq!(columns.pcs, [0x1000, 0x2000, 0x1002]);
+//! assert_eq!(columns.kinds, [Kind::Regular as u8; 3]);
+//! assert_eq!(columns.timestamps, [Some(10), None, Some(10)]);
+//! ```
+//!
+//! [`Item`]: crate::tracer::item::Item
+
+#[cfg(test)]
+mod tests;
+
+use std::collections::BTreeMap;
+use std::vec::Vec;
+
+use crate::binary::Binary;
+use crate::instruction::info::Info;
+use crate::packet::unit;
+use crate::session::{Session, error::Error};
+use crate::tracer::item::{self, Item};
+use crate::transport::Transport;
+use crate::types::stack::ReturnStack;
+
+/// Trace all harts in the given trace data into [`Columns`]
+///
+/// All [`Item`]s are processed like by [`Session::for_each_item`], and pushed
+/// to the [`Columns`] in the order in which they are generated. Items are
+/// associated with the timestamp of the packet they were generated from as
+/// described for [`Columns::push`].
+///
+/// # Example
+///
+/// ```no_run
+/// use riscv_etrace::session::{self, Session};
+///
+/// # let trace_data = &[];
+/// let mut session: Session<_, _> = session::builder().build();
+/// let columns = riscv_etrace::columnar::trace(&mut session, trace_data).unwrap();
+/// println!("Traced {} items", columns.len());
+/// ```
+pub fn trace<U, B, S, I, T>(
+    session: &mut Session<U, B, S, I, T>,
+    data: &[u8],
+) -> Result<Columns, Error<B::Error>>
+where
+    U: unit::Unit + Clone,
+    B: Binary<I> + Clone,
+    S: ReturnStack,
+    I: Info + Clone,
+    T: Transport<U> + Clone,
+{
+    let mut columns = Columns::default();
+    session.for_each_stamped_item(data, |s| columns.push(s.key.hart, s.key.timestamp, &s.item))?;
+    Ok(columns)
+}
+
+/// Columnar buffer of [`Item`]s
+///
+/// The buffer consists of parallel arrays holding one entry per [`Item`]. The
+/// entry at a given index in every array belongs to the same [`Item`]. Only
+/// the information most analyses need is retained: the hart, the PC, the
+/// [`Kind`] and the timestamp of each [`Item`].
+///
+/// Two buffers compare equal if their arrays are equal, regardless of the
+/// timestamps [carried][Self::push] for each hart.
+#[derive(Clone, Debug, Default)]
+pub struct Columns {
+    /// Id of the hart of each [`Item`]
+    pub harts: Vec<u64>,
+    /// PC of each [`Item`]
+    pub pcs: Vec<u64>,
+    /// [`Kind`] of each [`Item`], as [`u8`]
+    pub kinds: Vec<u8>,
+    /// Timestamp of each [`Item`], if known
+    pub timestamps: Vec<Option<u64>>,
+    /// Last timestamp of each hart
+    last: BTreeMap<u64, u64>,
+}
+
+impl Columns {
+    /// Create a new, empty buffer with space for the given number of items
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            harts: Vec::with_capacity(capacity),
+            pcs: Vec::with_capacity(capacity),
+            kinds: Vec::with_capacity(capacity),
+            timestamps: Vec::with_capacity(capacity),
+            last: Default::default(),
+        }
+    }
+
+    /// Push a single [`Item`] of the given hart
+    ///
+    /// Items without a timestamp inherit the timestamp of the previous item
+    /// of the same hart. If there is no such item with a timestamp, the
+    /// timestamp is recorded as [`None`].
+    pub fn push<I: Info>(&mut self, hart: u64, timestamp: Option<u64>, item: &Item<I>) {
+        let timestamp = timestamp.or_else(|| self.last.get(&hart).copied());
+        if let Some(timestamp) = timestamp {
+            self.last.insert(hart, timestamp);
+        }
+        self.harts.push(hart);
+        self.pcs.push(item.pc());
+        self.kinds.push(Kind::of(item).into());
+        self.timestamps.push(timestamp);
+    }
+
+    /// Retrieve the number of items in this buffer
+    pub fn len(&self) -> usize {
+        self.pcs.len()
+    }
+
+    /// Determine whether this buffer is empty
+    pub fn is_empty(&self) -> bool {
+        self.pcs.is_empty()
+    }
+
+    /// Remove all items from this buffer, retaining the allocated space
+    ///
+    /// The timestamps carried for each hart are retained, allowing the buffer
+    /// to be reused for the next chunk of the same trace.
+    pub fn clear(&mut self) {
+        self.harts.clear();
+        self.pcs.clear();
+        self.kinds.clear();
+        self.timestamps.clear();
+    }
+}
+
+impl PartialEq for Columns {
+    fn eq(&self, other: &Self) -> bool {
+        self.harts == other.harts
+            && self.pcs == other.pcs
+            && self.kinds == other.kinds
+            && self.timestamps == other.timestamps
+    }
+}
+
+impl Eq for Columns {}
+
+/// Kind of an [`Item`] as stored in [`Columns::kinds`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[repr(u8)]
+pub enum Kind {
+    /// The [`Item`] signals the retiring of an instruction
+    Regular = 0,
+    /// The [`Item`] signals an exception
+    Exception = 1,
+    /// The [`Item`] signals an interrupt
+    Interrupt = 2,
+    /// The [`Item`] signals an updated execution context
+    Context = 3,
+    /// The [`Item`] signals a notification by the encoder
+    Notify = 4,
+    /// The [`Item`] is of some other kind
+    Other = 0xff,
+}
+
+impl Kind {
+    /// Determine the [`Kind`] of the given [`Item`]
+    pub fn of<I: Info>(item: &Item<I>) -> Self {
+        match item.kind() {
+            item::Kind::Regular(_) => Self::Regular,
+            item::Kind::Trap(info) if info.is_interrupt() => Self::Interrupt,
+            item::Kind::Trap(_) => Self::Exception,
+            item::Kind::Context(_) => Self::Context,
+            item::Kind::Notify => Self::Notify,
+            _ => Self::Other,
+        }
+    }
+}
+
+impl From<Kind> for u8 {
+    fn from(kind: Kind) -> Self {
+        kind as u8
+    }
+}
+
+impl TryFrom<u8> for Kind {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Regular),
+            1 => Ok(Self::Exception),
+            2 => Ok(Self::Interrupt),
+            3 => Ok(Self::Context),
+            4 => Ok(Self::Notify),
+            0xff => Ok(Self::Other),
+            v => Err(v),
+        }
+    }
+}
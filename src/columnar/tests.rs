@@ -0,0 +1,77 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+use crate::instruction::{self, COMPRESSED};
+use crate::packet::{self, encap, payload, sync};
+use crate::session;
+use crate::types::trap;
+
+#[test]
+fn trace_capture() {
+    let start = sync::Start::new(0x80000000);
+    let trap = sync::Trap::new(
+        0x80000100,
+        trap::Info {
+            ecause: 7,
+            tval: None,
+        },
+    );
+    let packets: [(u16, u64, payload::InstructionTrace); 3] = [
+        (1, 20, start.into()),
+        (0, 10, start.into()),
+        (1, 30, trap.into()),
+    ];
+
+    let decoder = packet::builder()
+        .with_hart_index_width(8)
+        .with_timestamp_width(1)
+        .with_compression(false);
+    let mut data = std::vec![0; 64];
+    let mut encoder = decoder.encoder(data.as_mut());
+    packets.iter().for_each(|(hart, timestamp, payload)| {
+        let packet = encap::Normal::new(0, *hart, payload::Payload::from(*payload))
+            .with_timestamp(*timestamp);
+        encoder
+            .encode(&encap::Packet::from(packet))
+            .expect("Could not encode packet");
+    });
+    let uncommitted = encoder.uncommitted();
+    data.truncate(data.len() - uncommitted);
+
+    let binary: crate::binary::basic::SimpleMap<_, Option<instruction::Kind>> =
+        crate::binary::from_sorted_map([(0x80000000, COMPRESSED), (0x80000100, COMPRESSED)])
+            .expect("Could not construct binary");
+    let mut session: Session<_, _> = session::builder()
+        .with_decoder(decoder)
+        .with_binary(binary)
+        .build();
+    let columns = trace(&mut session, data.as_ref()).expect("Could not trace");
+
+    let (r, c) = (Kind::Regular as u8, Kind::Context as u8);
+    assert_eq!(columns.len(), 7);
+    assert_eq!(columns.harts, [0, 0, 1, 1, 1, 1, 1]);
+    assert_eq!(columns.pcs[4..], [0x80000000, 0x80000100, 0x80000100]);
+    assert_eq!(columns.kinds, [c, r, c, r, Kind::Interrupt as u8, c, r]);
+    assert_eq!(Kind::try_from(columns.kinds[4]), Ok(Kind::Interrupt));
+    let timestamps = [10, 10, 20, 20, 30, 30, 30].map(Some);
+    assert_eq!(columns.timestamps, timestamps);
+}
+
+#[test]
+fn push_per_hart_timestamps() {
+    let item: Item = Item::new(0x1000, instruction::Kind::c_nop.into());
+    let mut columns = Columns::default();
+    columns.push(1, None, &item);
+    columns.push(0, Some(10), &item);
+    columns.push(1, None, &item);
+    columns.push(1, Some(20), &item);
+    columns.push(0, None, &item);
+    let timestamps = [None, Some(10), None, Some(20), Some(10)];
+    assert_eq!(columns.timestamps, timestamps);
+
+    columns.clear();
+    columns.push(1, None, &item);
+    assert_eq!(columns.timestamps, [Some(20)]);
+}
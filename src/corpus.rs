@@ -0,0 +1,79 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Regression corpora of raw trace data
+//!
+//! This module re-exports the [corpus checking][riscv_etrace_core::corpus] of
+//! the core crate. With the `std` feature, it additionally provides a
+//! [`Corpus`] which may be loaded from a directory, e.g. the directory a fuzzer
+//! stores its findings in.
+
+pub use riscv_etrace_core::corpus::*;
+
+#[cfg(all(test, feature = "std"))]
+mod tests;
+
+#[cfg(feature = "std")]
+use std::{fs, io, path::Path, string::String, vec::Vec};
+
+/// Corpus of named raw trace data owned in memory
+///
+/// # Example
+///
+/// ```no_run
+/// use riscv_etrace::corpus::{Checker, Corpus};
+/// use riscv_etrace::{binary, instruction, packet, tracer};
+///
+/// let corpus = Corpus::load_dir("fuzz/corpus").unwrap();
+/// let binary = binary::from_fn(|_| {
+///     Ok::<_, binary::error::NoInstruction>(instruction::COMPRESSED)
+/// });
+/// let tracer = tracer::builder().with_binary(binary);
+/// let checker: Checker<_, _> = Checker::new(packet::builder(), tracer);
+/// checker.check_all(corpus.entries()).unwrap();
+/// ```
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Corpus {
+    entries: Vec<(String, Vec<u8>)>,
+}
+
+#[cfg(feature = "std")]
+impl Corpus {
+    /// Load all files in the given directory
+    ///
+    /// Each regular file becomes one [`Entry`] named after the file. Entries
+    /// are sorted by name. Subdirectories are not visited.
+    pub fn load_dir(path: impl AsRef<Path>) -> io::Result<Self> {
+        let mut entries = Vec::new();
+        for entry in fs::read_dir(path)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_file() {
+                continue;
+            }
+            let name = entry.file_name().to_string_lossy().into_owned();
+            entries.push((name, fs::read(entry.path())?));
+        }
+        entries.sort();
+        Ok(Self { entries })
+    }
+
+    /// Add an entry
+    pub fn push(&mut self, name: impl Into<String>, data: impl Into<Vec<u8>>) {
+        self.entries.push((name.into(), data.into()));
+    }
+
+    /// Retrieve all [`Entry`]s
+    pub fn entries(&self) -> impl Iterator<Item = Entry<'_>> + '_ {
+        self.entries.iter().map(|(n, d)| Entry::new(n, d))
+    }
+
+    /// Retrieve the number of entries
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Determine whether the corpus is empty
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
@@ -0,0 +1,21 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+#[test]
+fn load_dir() {
+    let dir = std::env::temp_dir().join(std::format!("riscv-etrace-corpus-{}", std::process::id()));
+    fs::create_dir_all(dir.join("sub")).expect("Could not create directory");
+    fs::write(dir.join("b"), b"\x00\x00").expect("Could not write file");
+    fs::write(dir.join("a"), b"\x45\x73").expect("Could not write file");
+
+    let corpus = Corpus::load_dir(&dir);
+    fs::remove_dir_all(&dir).expect("Could not remove directory");
+    let corpus = corpus.expect("Could not load corpus");
+    assert!(
+        corpus
+            .entries()
+            .eq([Entry::new("a", b"\x45\x73"), Entry::new("b", b"\x00\x00"),])
+    );
+}
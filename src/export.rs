@@ -0,0 +1,124 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Export of tracing data
+//!
+//! This module provides writers for exporting decoded [`Packet`]s and tracing
+//! [`Item`]s in formats suitable for processing with other tools, such as
+//! [JSON Lines][jsonl] or [Perfetto][perfetto] traces, or for inspection as
+//! [plain text][text]. In addition, a function-level [call graph][callgraph]
+//! may be derived from [`Item`]s and exported. The [branch] outcomes may be
+//! exported for trace-driven branch predictor simulation. With the `arrow` and
+//! `parquet` features, [`Columns`] may be exported as Arrow IPC or Parquet
+//! files for loading into data-science tooling.
+//!
+//! For the common case of exporting all of a [`Session`]'s trace data, the
+//! [`jsonl`], [`perfetto`] and [`text`] modules each provide an `export` fn.
+//! Those report failures as an [`Error`].
+//!
+//! [`Packet`]: crate::transport::Packet
+//! [`Item`]: crate::tracer::item::Item
+//! [`Columns`]: crate::columnar::Columns
+//! [`Session`]: crate::session::Session
+
+#[cfg(feature = "arrow")]
+pub mod arrow;
+pub mod branch;
+pub mod callgraph;
+pub mod jsonl;
+#[cfg(feature = "parquet")]
+pub mod parquet;
+pub mod perfetto;
+pub mod text;
+
+#[cfg(test)]
+mod tests;
+
+use core::fmt;
+use std::io;
+
+use crate::session;
+
+/// Error that may occur while exporting a [`Session`]'s trace data
+///
+/// [`Session`]: session::Session
+#[derive(Debug)]
+pub enum Error<B> {
+    /// The trace data could not be processed
+    Session(session::error::Error<B>),
+    /// The export could not be written
+    Io(io::Error),
+}
+
+impl<B> core::error::Error for Error<B>
+where
+    B: fmt::Debug + core::error::Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn core::error::Error + 'static)> {
+        match self {
+            Self::Session(inner) => Some(inner),
+            Self::Io(inner) => Some(inner),
+        }
+    }
+}
+
+impl<B> fmt::Display for Error<B> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Session(_) => write!(f, "could not process trace data"),
+            Self::Io(_) => write!(f, "could not write export"),
+        }
+    }
+}
+
+impl<B> From<session::error::Error<B>> for Error<B> {
+    fn from(inner: session::error::Error<B>) -> Self {
+        Self::Session(inner)
+    }
+}
+
+impl<B> From<io::Error> for Error<B> {
+    fn from(inner: io::Error) -> Self {
+        Self::Io(inner)
+    }
+}
+
+/// [`fmt::Display`] wrapper for displaying a value as a JSON string
+struct JsonStr<T>(T);
+
+impl<T: fmt::Display> fmt::Display for JsonStr<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use fmt::Write;
+
+        struct Escaped<'a, 'b>(&'a mut fmt::Formatter<'b>);
+
+        impl fmt::Write for Escaped<'_, '_> {
+            fn write_str(&mut self, s: &str) -> fmt::Result {
+                s.chars().try_for_each(|c| match c {
+                    '"' => self.0.write_str("\\\""),
+                    '\\' => self.0.write_str("\\\\"),
+                    '\n' => self.0.write_str("\\n"),
+                    c if c.is_control() => write!(self.0, "\\u{:04x}", c as u32),
+                    c => self.0.write_char(c),
+                })
+            }
+        }
+
+        f.write_char('"')?;
+        write!(Escaped(f), "{}", self.0)?;
+        f.write_char('"')
+    }
+}
+
+/// [`fmt::Display`] wrapper for displaying an [`Option`] as JSON value
+///
+/// `None` is displayed as `null`.
+struct JsonOpt<T>(Option<T>);
+
+impl<T: fmt::Display> fmt::Display for JsonOpt<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            Some(v) => fmt::Display::fmt(v, f),
+            None => f.write_str("null"),
+        }
+    }
+}
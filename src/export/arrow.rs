@@ -0,0 +1,113 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Arrow IPC export
+//!
+//! This module provides a [`Writer`] emitting [`Columns`] as an [Arrow IPC
+//! file][ipc], which may be loaded by data-science tooling such as pandas,
+//! Polars, DataFusion or DuckDB. Each [`Columns`] written becomes one record
+//! batch following the [`schema`]:
+//!
+//! | Column      | Type    | Content                                       |
+//! |-------------|---------|-----------------------------------------------|
+//! | `hart`      | `u64`   | Id of the hart                                |
+//! | `pc`        | `u64`   | PC of the [`Item`][crate::tracer::item::Item] |
+//! | `kind`      | `u8`    | [`Kind`][crate::columnar::Kind] code          |
+//! | `timestamp` | `u64`   | Timestamp, null if unknown                    |
+//!
+//! # Example
+//!
+//! ```
+//! use riscv_etrace::columnar::Columns;
+//! use riscv_etrace::export::arrow::Writer;
+//! use riscv_etrace::instruction::Kind;
+//! use riscv_etrace::tracer::item::Item;
+//!
+//! let mut columns = Columns::default();
+//! columns.push(0, Some(10), &Item::new(0x1000, Kind::c_nop.into()));
+//!
+//! let mut writer = Writer::new(Vec::new()).unwrap();
+//! writer.write_columns(&columns).unwrap();
+//! let data = writer.finish().unwrap();
+//! assert_eq!(&data[..6], b"ARROW1");
+//! ```
+//!
+//! [ipc]: <https://arrow.apache.org/docs/format/Columnar.html#ipc-file-format>
+
+use std::io;
+use std::sync::Arc;
+
+use arrow_array::{ArrayRef, RecordBatch, UInt8Array, UInt64Array};
+use arrow_ipc::writer::FileWriter;
+use arrow_schema::{ArrowError, DataType, Field, Schema, SchemaRef};
+
+use crate::columnar::Columns;
+
+/// Writer for Arrow IPC files
+pub struct Writer<W: io::Write> {
+    inner: FileWriter<W>,
+}
+
+impl<W: io::Write> Writer<W> {
+    /// Create a new writer writing to the given [`io::Write`]
+    ///
+    /// The file header and [`schema`] are written immediately.
+    pub fn new(inner: W) -> io::Result<Self> {
+        let inner = FileWriter::try_new(inner, &schema()).map_err(into_io)?;
+        Ok(Self { inner })
+    }
+
+    /// Write the given [`Columns`] as a single record batch
+    ///
+    /// Empty [`Columns`] are skipped.
+    pub fn write_columns(&mut self, columns: &Columns) -> io::Result<()> {
+        if columns.is_empty() {
+            return Ok(());
+        }
+        let batch = record_batch(columns).map_err(into_io)?;
+        self.inner.write(&batch).map_err(into_io)
+    }
+
+    /// Finish writing, flushing and returning the underlying [`io::Write`]
+    pub fn finish(mut self) -> io::Result<W> {
+        self.inner.finish().map_err(into_io)?;
+        self.inner.into_inner().map_err(into_io)
+    }
+}
+
+impl<W: io::Write> core::fmt::Debug for Writer<W> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Writer").finish_non_exhaustive()
+    }
+}
+
+/// Retrieve the Arrow schema of exported [`Columns`]
+pub fn schema() -> SchemaRef {
+    Arc::new(Schema::new(std::vec![
+        Field::new("hart", DataType::UInt64, false),
+        Field::new("pc", DataType::UInt64, false),
+        Field::new("kind", DataType::UInt8, false),
+        Field::new("timestamp", DataType::UInt64, true),
+    ]))
+}
+
+/// Convert the given [`Columns`] into a [`RecordBatch`]
+///
+/// Each array of the [`Columns`] is copied into the batch once. Unknown
+/// timestamps become nulls.
+pub fn record_batch(columns: &Columns) -> Result<RecordBatch, ArrowError> {
+    let arrays: [ArrayRef; 4] = [
+        Arc::new(UInt64Array::from_iter_values(columns.harts.iter().copied())),
+        Arc::new(UInt64Array::from_iter_values(columns.pcs.iter().copied())),
+        Arc::new(UInt8Array::from_iter_values(columns.kinds.iter().copied())),
+        Arc::new(columns.timestamps.iter().collect::<UInt64Array>()),
+    ];
+    RecordBatch::try_new(schema(), arrays.into())
+}
+
+/// Convert an [`ArrowError`] into an [`io::Error`]
+pub(super) fn into_io(error: ArrowError) -> io::Error {
+    match error {
+        ArrowError::IoError(_, error) => error,
+        error => io::Error::other(error),
+    }
+}
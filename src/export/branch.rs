@@ -0,0 +1,205 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Branch outcome export
+//!
+//! This module provides a [`Writer`] emitting the outcome of every conditional
+//! branch in retirement order, suitable for trace-driven branch predictor
+//! simulation. The output consists of [`Record`]s without any header. Each
+//! [`Record`] occupies [`Record::SIZE`] bytes:
+//!
+//! | Offset | Size | Content                                          |
+//! |--------|------|--------------------------------------------------|
+//! | 0      | 8    | PC of the branch, little endian                  |
+//! | 8      | 8    | Target of the branch, little endian              |
+//! | 16     | 1    | `1` if the branch was taken, `0` otherwise       |
+//!
+//! The target is reported regardless of whether the branch was taken.
+//!
+//! A branch's outcome is only known once the next instruction was retired or
+//! an exception was raised. Branches whose outcome cannot be determined, e.g.
+//! because they are followed by a [gap][item::Confidence::Gap] or the end of
+//! the trace, are not exported but [counted][Writer::unresolved].
+//!
+//! # Example
+//!
+//! ```
+//! use riscv_etrace::export::branch::{Record, Writer};
+//! use riscv_etrace::instruction::{Instruction, Kind};
+//! use riscv_etrace::tracer::item::Item;
+//!
+//! let beqz = Instruction::from(Kind::new_c_beqz(10, 0x10));
+//! let nop = Instruction::from(Kind::c_nop);
+//! let items: [Item; 3] = [
+//!     Item::new(0x80000000, beqz.into()),
+//!     Item::new(0x80000010, beqz.into()),
+//!     Item::new(0x80000012, nop.into()),
+//! ];
+//!
+//! let mut writer = Writer::new(Vec::new());
+//! items.iter().try_for_each(|i| writer.write_item(i)).unwrap();
+//! let data = writer.finish().unwrap();
+//!
+//! let records: Vec<_> = data.chunks(Record::SIZE).map(Record::from_bytes).collect();
+//! assert_eq!(
+//!     records,
+//!     [
+//!         Some(Record { pc: 0x80000000, target: 0x80000010, taken: true }),
+//!         Some(Record { pc: 0x80000010, target: 0x80000020, taken: false }),
+//!     ],
+//! );
+//! ```
+
+use std::io;
+
+use crate::instruction::info::Info;
+use crate::tracer::item::{self, Item};
+
+/// Writer for branch outcomes of a single hart
+///
+/// See the [module][self] documentation for a description of the format.
+/// [`Item`]s must be supplied in the order in which they were generated and
+/// must all belong to the same hart. Traces of multiple harts require one
+/// writer per hart.
+#[derive(Debug)]
+pub struct Writer<W> {
+    inner: W,
+    pending: Option<Pending>,
+    records: u64,
+    unresolved: u64,
+}
+
+impl<W: io::Write> Writer<W> {
+    /// Create a new writer writing to the given [`io::Write`]
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            pending: None,
+            records: 0,
+            unresolved: 0,
+        }
+    }
+
+    /// Process a single [`Item`]
+    ///
+    /// Writes the [`Record`] for the previous branch if its outcome is now
+    /// known.
+    pub fn write_item<I: Info>(&mut self, item: &Item<I>) -> io::Result<()> {
+        if item.confidence() == item::Confidence::Gap {
+            self.drop_pending();
+        }
+
+        match item.kind() {
+            item::Kind::Regular(insn) => {
+                self.resolve(item.pc())?;
+                self.pending = insn.info.branch_target().map(|offset| Pending {
+                    pc: item.pc(),
+                    target: item.pc().wrapping_add_signed(offset.into()),
+                    next: item.pc().wrapping_add(insn.size.into()),
+                });
+            }
+            item::Kind::Trap(info) if info.is_exception() => self.resolve(item.pc())?,
+            _ => (),
+        }
+        Ok(())
+    }
+
+    /// Retrieve the number of [`Record`]s written so far
+    pub fn records(&self) -> u64 {
+        self.records
+    }
+
+    /// Retrieve the number of branches whose outcome could not be determined
+    pub fn unresolved(&self) -> u64 {
+        self.unresolved
+    }
+
+    /// Finish writing, flushing and returning the underlying [`io::Write`]
+    ///
+    /// A trailing branch whose outcome is unknown is counted as unresolved.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.drop_pending();
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+
+    /// Resolve the pending branch given the PC of the next instruction
+    fn resolve(&mut self, pc: u64) -> io::Result<()> {
+        let Some(pending) = self.pending.take() else {
+            return Ok(());
+        };
+        let taken = if pc == pending.target {
+            true
+        } else if pc == pending.next {
+            false
+        } else {
+            self.unresolved += 1;
+            return Ok(());
+        };
+        let record = Record {
+            pc: pending.pc,
+            target: pending.target,
+            taken,
+        };
+        self.inner.write_all(&record.to_bytes())?;
+        self.records += 1;
+        Ok(())
+    }
+
+    /// Drop the pending branch, if any, counting it as unresolved
+    fn drop_pending(&mut self) {
+        if self.pending.take().is_some() {
+            self.unresolved += 1;
+        }
+    }
+}
+
+/// A branch whose outcome is not yet known
+#[derive(Copy, Clone, Debug)]
+struct Pending {
+    pc: u64,
+    target: u64,
+    next: u64,
+}
+
+/// Outcome of a single conditional branch
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Record {
+    /// PC of the branch instruction
+    pub pc: u64,
+    /// Target of the branch
+    pub target: u64,
+    /// Whether the branch was taken
+    pub taken: bool,
+}
+
+impl Record {
+    /// Size of a single encoded [`Record`] in bytes
+    pub const SIZE: usize = 17;
+
+    /// Encode this record
+    pub fn to_bytes(&self) -> [u8; Self::SIZE] {
+        let mut res = [0; Self::SIZE];
+        res[..8].copy_from_slice(&self.pc.to_le_bytes());
+        res[8..16].copy_from_slice(&self.target.to_le_bytes());
+        res[16] = self.taken.into();
+        res
+    }
+
+    /// Decode a record
+    ///
+    /// Returns [`None`] if `data` does not hold exactly [`Record::SIZE`] bytes
+    /// or the outcome is neither `0` nor `1`.
+    pub fn from_bytes(data: &[u8]) -> Option<Self> {
+        let data: &[u8; Self::SIZE] = data.try_into().ok()?;
+        let taken = match data[16] {
+            0 => false,
+            1 => true,
+            _ => return None,
+        };
+        Some(Self {
+            pc: u64::from_le_bytes(data[..8].try_into().ok()?),
+            target: u64::from_le_bytes(data[8..16].try_into().ok()?),
+            taken,
+        })
+    }
+}
@@ -0,0 +1,375 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Function-level call graph export
+//!
+//! This module provides a [`Builder`] for deriving a [`CallGraph`] from the
+//! [`Item`]s actually traced. Items are attributed to functions through a
+//! [`Symbolize`] implementation such as [`Symbols`]. Edges between functions
+//! are established for calls, as classified by [`Info::is_call`], and for tail
+//! calls, i.e. transfers to another function by an instruction which is
+//! neither a call nor a return. Each edge carries the number of times it was
+//! taken.
+//!
+//! A [`CallGraph`] may be exported in [DOT][dot] format for rendering with
+//! Graphviz or as a JSON object.
+//!
+//! # Example
+//!
+//! ```
+//! use riscv_etrace::export::callgraph::{Builder, Symbols};
+//! use riscv_etrace::instruction::{Instruction, Kind};
+//! use riscv_etrace::tracer::item::Item;
+//!
+//! let symbols = Symbols::new()
+//!     .with_function(0x1000..0x1010, "main")
+//!     .with_function(0x2000..0x2010, "helper");
+//! let mut builder = Builder::new(symbols);
+//! let items: [Item; 3] = [
+//!     Item::new(0x1000, Instruction::from(Kind::new_jal(1, 0x1000)).into()),
+//!     Item::new(0x2000, Instruction::from(Kind::new_c_jr(1)).into()),
+//!     Item::new(0x1004, Instruction::from(Kind::wfi).into()),
+//! ];
+//! items.iter().for_each(|i| builder.process_item(0, i));
+//! let graph = builder.finish();
+//! assert_eq!(graph.edge(0x1000, 0x2000).map(|e| e.calls), Some(1));
+//!
+//! let mut dot = Vec::new();
+//! graph.write_dot(&mut dot).unwrap();
+//! let dot = String::from_utf8(dot).unwrap();
+//! assert!(dot.contains("f1000 -> f2000 [label=\"1\"]"));
+//! ```
+//!
+//! [dot]: <https://graphviz.org/doc/info/lang.html>
+
+use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::ops::Range;
+use std::io;
+
+use crate::instruction::info::Info;
+use crate::tracer::item::{self, Item};
+
+use super::JsonStr;
+
+/// A function an address was attributed to
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Symbol<'a> {
+    /// Start address of the function
+    pub address: u64,
+    /// Name of the function
+    pub name: Cow<'a, str>,
+    /// Whether the function is known to be inlined at some call sites
+    ///
+    /// Such hints may be derived from debug information, e.g. from DWARF's
+    /// `DW_AT_inline` attribute. Calls to such functions may be missing from
+    /// the trace.
+    pub inline: bool,
+}
+
+/// Attribution of addresses to functions
+pub trait Symbolize {
+    /// Retrieve the function containing the given address, if known
+    fn symbolize(&self, address: u64) -> Option<Symbol<'_>>;
+}
+
+/// Table of functions
+///
+/// This [`Symbolize`] implementation attributes addresses to functions based
+/// on a table of functions' address ranges. If ranges overlap, the function
+/// starting last before the address is chosen.
+#[derive(Clone, Debug, Default)]
+pub struct Symbols {
+    functions: BTreeMap<u64, (u64, String, bool)>,
+}
+
+impl Symbols {
+    /// Create a new, empty table of functions
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Add a function covering the given address range
+    pub fn with_function(mut self, range: Range<u64>, name: impl Into<String>) -> Self {
+        self.functions
+            .insert(range.start, (range.end, name.into(), false));
+        self
+    }
+
+    /// Mark the function starting at the given address as inlined
+    ///
+    /// See [`Symbol::inline`] for details.
+    pub fn with_inline_hint(mut self, address: u64) -> Self {
+        if let Some(function) = self.functions.get_mut(&address) {
+            function.2 = true;
+        }
+        self
+    }
+
+    /// Create a table of functions from the symbol table of an ELF file
+    ///
+    /// All symbols of type `STT_FUNC` with a non-zero size are added.
+    #[cfg(feature = "elf")]
+    pub fn from_elf<P: elf::endian::EndianParse>(
+        elf: &elf::ElfBytes<'_, P>,
+    ) -> Result<Self, elf::ParseError> {
+        let Some((symbols, names)) = elf.symbol_table()? else {
+            return Ok(Self::new());
+        };
+        symbols
+            .iter()
+            .filter(|s| s.st_symtype() == elf::abi::STT_FUNC && s.st_size > 0)
+            .try_fold(Self::new(), |table, s| {
+                let name = names.get(s.st_name as usize)?;
+                let end = s.st_value.saturating_add(s.st_size);
+                Ok(table.with_function(s.st_value..end, name))
+            })
+    }
+}
+
+impl Symbolize for Symbols {
+    fn symbolize(&self, address: u64) -> Option<Symbol<'_>> {
+        let (start, (end, name, inline)) = self.functions.range(..=address).next_back()?;
+        (address < *end).then(|| Symbol {
+            address: *start,
+            name: Cow::Borrowed(name),
+            inline: *inline,
+        })
+    }
+}
+
+/// A function in a [`CallGraph`]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Node {
+    /// Name of the function, if it could be symbolized
+    pub name: Option<String>,
+    /// Whether the function is known to be inlined at some call sites
+    pub inline: bool,
+    /// Number of times the function was entered via a call or tail call
+    pub entries: u64,
+}
+
+/// An edge between two functions in a [`CallGraph`]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Edge {
+    /// Number of calls from the caller to the callee
+    pub calls: u64,
+    /// Number of tail calls from the caller to the callee
+    pub tail_calls: u64,
+}
+
+/// Function-level call graph
+///
+/// Functions are identified by their start address. See [`Builder`] for how
+/// code which could not be symbolized is attributed.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CallGraph {
+    nodes: BTreeMap<u64, Node>,
+    edges: BTreeMap<(u64, u64), Edge>,
+}
+
+impl CallGraph {
+    /// Retrieve all functions, ordered by address
+    pub fn nodes(&self) -> impl Iterator<Item = (u64, &Node)> + '_ {
+        self.nodes.iter().map(|(a, n)| (*a, n))
+    }
+
+    /// Retrieve all edges as caller, callee and [`Edge`]
+    pub fn edges(&self) -> impl Iterator<Item = (u64, u64, &Edge)> + '_ {
+        self.edges.iter().map(|((f, t), e)| (*f, *t, e))
+    }
+
+    /// Retrieve the edge between the given caller and callee, if any
+    pub fn edge(&self, caller: u64, callee: u64) -> Option<&Edge> {
+        self.edges.get(&(caller, callee))
+    }
+
+    /// Write this graph in DOT format
+    ///
+    /// Nodes are labeled with the function name or, for functions which could
+    /// not be symbolized, their address. Functions with an inlining hint are
+    /// drawn dashed. Edges are labeled with the number of calls, tail calls
+    /// are drawn dashed.
+    pub fn write_dot(&self, mut w: impl io::Write) -> io::Result<()> {
+        writeln!(w, "digraph calls {{")?;
+        for (address, node) in &self.nodes {
+            let label = node
+                .name
+                .clone()
+                .unwrap_or_else(|| alloc::format!("{address:#x}"));
+            let style = if node.inline { ", style=dashed" } else { "" };
+            writeln!(w, "  f{address:x} [label={}{style}];", JsonStr(label))?;
+        }
+        for ((caller, callee), edge) in &self.edges {
+            if edge.calls > 0 {
+                writeln!(
+                    w,
+                    "  f{caller:x} -> f{callee:x} [label=\"{}\"];",
+                    edge.calls
+                )?;
+            }
+            if edge.tail_calls > 0 {
+                writeln!(
+                    w,
+                    "  f{caller:x} -> f{callee:x} [label=\"{}\", style=dashed];",
+                    edge.tail_calls,
+                )?;
+            }
+        }
+        writeln!(w, "}}")
+    }
+
+    /// Write this graph as a JSON object
+    ///
+    /// The object has a `nodes` field holding an array of objects with the
+    /// `address`, `name` (or `null`), `inline` and `entries` of each function,
+    /// and an `edges` field holding an array of objects with the `caller`,
+    /// `callee`, `calls` and `tail_calls` of each edge.
+    pub fn write_json(&self, mut w: impl io::Write) -> io::Result<()> {
+        write!(w, "{{\"nodes\":[")?;
+        for (n, (address, node)) in self.nodes.iter().enumerate() {
+            let sep = if n > 0 { "," } else { "" };
+            let name = super::JsonOpt(node.name.as_ref().map(JsonStr));
+            write!(
+                w,
+                "{sep}{{\"address\":{address},\"name\":{name},\"inline\":{},\"entries\":{}}}",
+                node.inline, node.entries,
+            )?;
+        }
+        write!(w, "],\"edges\":[")?;
+        for (n, ((caller, callee), edge)) in self.edges.iter().enumerate() {
+            let sep = if n > 0 { "," } else { "" };
+            write!(
+                w,
+                "{sep}{{\"caller\":{caller},\"callee\":{callee},\"calls\":{},\"tail_calls\":{}}}",
+                edge.calls, edge.tail_calls,
+            )?;
+        }
+        writeln!(w, "]}}")
+    }
+}
+
+/// Builder for [`CallGraph`]s
+///
+/// [`Item`]s are fed to the builder via [`process_item`][Self::process_item]
+/// in the order in which they were traced. Items of different harts may be
+/// interleaved.
+///
+/// Code which could not be symbolized is attributed to the address at which
+/// it was entered, i.e. the target of a call or tail call. The builder keeps
+/// a stack of callers for each hart for attributing such code after returns.
+#[derive(Clone, Debug)]
+pub struct Builder<S> {
+    symbols: S,
+    graph: CallGraph,
+    harts: BTreeMap<u64, Hart>,
+}
+
+impl<S: Symbolize> Builder<S> {
+    /// Create a new builder attributing addresses via the given [`Symbolize`]
+    pub fn new(symbols: S) -> Self {
+        Self {
+            symbols,
+            graph: Default::default(),
+            harts: Default::default(),
+        }
+    }
+
+    /// Process a single [`Item`] of the given hart
+    ///
+    /// Traps and context changes interrupt the control flow of the hart. No
+    /// edge is recorded for the transfer to the next instruction following
+    /// such an item.
+    pub fn process_item<I: Info>(&mut self, hart: u64, item: &Item<I>) {
+        let state = self.harts.entry(hart).or_default();
+        let item::Kind::Regular(insn) = item.kind() else {
+            state.previous = None;
+            return;
+        };
+
+        let pc = item.pc();
+        let symbol = self.symbols.symbolize(pc);
+        let function = match (&symbol, state.previous) {
+            (Some(symbol), _) => symbol.address,
+            (None, Some(p)) if p.transfer == Transfer::Return => state.stack.pop().unwrap_or(pc),
+            (None, Some(p)) if p.transfer == Transfer::Other && !p.symbolized => p.function,
+            (None, _) => pc,
+        };
+        self.graph.nodes.entry(function).or_insert_with(|| Node {
+            name: symbol.as_ref().map(|s| s.name.clone().into_owned()),
+            inline: symbol.as_ref().is_some_and(|s| s.inline),
+            entries: 0,
+        });
+
+        let transfer = if insn.info.is_call() {
+            Transfer::Call
+        } else if insn.info.is_return() {
+            Transfer::Return
+        } else {
+            Transfer::Other
+        };
+        let previous = state.previous.replace(Previous {
+            function,
+            transfer,
+            symbolized: symbol.is_some(),
+        });
+
+        let Some(previous) = previous else {
+            return;
+        };
+        let edge = (previous.function, function);
+        match previous.transfer {
+            Transfer::Call => {
+                state.stack.push(previous.function);
+                self.graph.edges.entry(edge).or_default().calls += 1;
+            }
+            Transfer::Return if symbol.is_some() => {
+                state.stack.pop();
+                return;
+            }
+            Transfer::Other if previous.function != function => {
+                self.graph.edges.entry(edge).or_default().tail_calls += 1;
+            }
+            _ => return,
+        }
+        if let Some(node) = self.graph.nodes.get_mut(&function) {
+            node.entries += 1;
+        }
+    }
+
+    /// Finish building, returning the [`CallGraph`]
+    pub fn finish(self) -> CallGraph {
+        self.graph
+    }
+}
+
+/// Call graph related state of a hart
+#[derive(Clone, Debug, Default)]
+struct Hart {
+    previous: Option<Previous>,
+    stack: Vec<u64>,
+}
+
+/// The previous instruction of a hart
+#[derive(Copy, Clone, Debug)]
+struct Previous {
+    function: u64,
+    transfer: Transfer,
+    symbolized: bool,
+}
+
+/// Classification of an instruction's control transfer
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Transfer {
+    Call,
+    Return,
+    Other,
+}
+
+/// [`Symbolize`] implementation attributing no address to any function
+impl Symbolize for () {
+    fn symbolize(&self, _: u64) -> Option<Symbol<'_>> {
+        None
+    }
+}
@@ -0,0 +1,235 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! JSON Lines export
+//!
+//! This module provides a [`Writer`] emitting one JSON object per line for
+//! every [`Packet`] and [`Item`] written. Every object has a `type` field,
+//! which is either `"packet"` or `"item"`.
+//!
+//! Packet objects carry a sequential `id`, the `hart`, the `timestamp` (or
+//! `null`), the payload `format` and a textual representation of the
+//! `payload`. Item objects carry the `hart`, the `id` of the `packet` from
+//! which they were generated (or `null`), the `pc` and the `kind` of the
+//! item, which is one of `"instruction"`, `"trap"` and `"context"`, together
+//! with fields specific to that kind.
+//!
+//! # Example
+//!
+//! ```
+//! use riscv_etrace::export::jsonl::Writer;
+//! use riscv_etrace::packet::{payload, sync};
+//! use riscv_etrace::tracer::item::{Item, Kind};
+//! use riscv_etrace::transport::Packet;
+//!
+//! let mut writer = Writer::new(Vec::new());
+//! let packet: Packet = Packet {
+//!     hart: 0,
+//!     timestamp: Some(42),
+//!     payload: payload::InstructionTrace::from(sync::Start::new(0x80000000))
+//!     .into(),
+//! };
+//! let id = writer.write_packet(&packet).unwrap();
+//! let item: Item = Item::new(0x80000000, Kind::Context(Default::default()));
+//! writer.write_item(0, Some(id), &item).unwrap();
+//!
+//! let output = String::from_utf8(writer.into_inner()).unwrap();
+//! let mut lines = output.lines();
+//! assert!(lines.next().unwrap().starts_with(r#"{"type":"packet","id":0,"hart":0"#));
+//! assert!(lines.next().unwrap().starts_with(r#"{"type":"item","hart":0,"packet":0"#));
+//! ```
+
+use core::fmt;
+use std::io;
+
+use crate::binary::Binary;
+use crate::instruction::info::Info;
+use crate::packet::payload::{InstructionTrace, Payload};
+use crate::packet::unit;
+use crate::session::Session;
+use crate::tracer::item::{self, Item};
+use crate::transport::{Packet, Transport};
+use crate::types::stack::ReturnStack;
+
+use super::{Error, JsonOpt, JsonStr};
+
+/// Export all [`Packet`]s and [`Item`]s in the given trace data
+///
+/// The packets are [decoded][Session::decode_sorted_packets] and processed in
+/// order. Every packet is written, followed by the [`Item`]s generated from
+/// it, which refer to the packet via its `id`. Thus, the output is equivalent
+/// to the one of [`Session::for_each_item`], with the packets interleaved.
+/// The underlying [`io::Write`] is flushed and returned.
+///
+/// # Example
+///
+/// ```no_run
+/// use riscv_etrace::session::{self, Session};
+///
+/// # let trace_data = &[];
+/// let mut session: Session<_, _> = session::builder().build();
+/// riscv_etrace::export::jsonl::export(&mut session, trace_data, std::io::stdout()).unwrap();
+/// ```
+pub fn export<U, B, S, I, T, W>(
+    session: &mut Session<U, B, S, I, T>,
+    data: &[u8],
+    inner: W,
+) -> Result<W, Error<B::Error>>
+where
+    U: unit::Unit + Clone,
+    B: Binary<I> + Clone,
+    S: ReturnStack,
+    I: Info + Clone + fmt::Debug,
+    T: Transport<U> + Clone,
+    W: io::Write,
+{
+    let mut writer = Writer::new(inner);
+    for packet in session.decode_sorted_packets(data)? {
+        let id = writer.write_packet(&packet)?;
+        let mut res = Ok(());
+        session.process_packet(&packet, |hart, item| {
+            if res.is_ok() {
+                res = writer.write_item(hart, Some(id), &item);
+            }
+        })?;
+        res?;
+    }
+    writer.flush()?;
+    Ok(writer.into_inner())
+}
+
+/// Export all [`Packet`]s in the given trace data without tracing them
+///
+/// The packets are [decoded][Session::decode_packets] and written in the order
+/// in which they occur in the trace data. The underlying [`io::Write`] is
+/// flushed and returned.
+pub fn export_packets<U, B, S, I, T, W>(
+    session: &mut Session<U, B, S, I, T>,
+    data: &[u8],
+    inner: W,
+) -> Result<W, Error<B::Error>>
+where
+    U: unit::Unit + Clone,
+    B: Binary<I> + Clone,
+    S: ReturnStack,
+    I: Info + Clone,
+    T: Transport<U> + Clone,
+    W: io::Write,
+{
+    let mut writer = Writer::new(inner);
+    session
+        .decode_packets(data)
+        .map_err(crate::session::error::Error::Packet)?
+        .iter()
+        .try_for_each(|p| writer.write_packet(p).map(drop))?;
+    writer.flush()?;
+    Ok(writer.into_inner())
+}
+
+/// Writer for [`Packet`]s and [`Item`]s in JSON Lines format
+///
+/// See the [module][self] documentation for a description of the format.
+#[derive(Debug)]
+pub struct Writer<W> {
+    inner: W,
+    next_id: u64,
+}
+
+impl<W: io::Write> Writer<W> {
+    /// Create a new writer writing to the given [`io::Write`]
+    pub fn new(inner: W) -> Self {
+        Self { inner, next_id: 0 }
+    }
+
+    /// Write a single [`Packet`]
+    ///
+    /// Returns the `id` assigned to the packet, which may be used for linking
+    /// [`Item`]s to the packet.
+    pub fn write_packet<I: unit::IOptions, D>(&mut self, packet: &Packet<I, D>) -> io::Result<u64> {
+        let id = self.next_id;
+        let format = match &packet.payload {
+            Payload::InstructionTrace(InstructionTrace::Extension(_)) => "extension",
+            Payload::InstructionTrace(InstructionTrace::Branch(_)) => "branch",
+            Payload::InstructionTrace(InstructionTrace::Address(_)) => "address",
+            Payload::InstructionTrace(InstructionTrace::Synchronization(_)) => "sync",
+            Payload::DataTrace => "data",
+            _ => "unknown",
+        };
+        writeln!(
+            self.inner,
+            r#"{{"type":"packet","id":{id},"hart":{},"timestamp":{},"format":"{format}","payload":{}}}"#,
+            packet.hart,
+            JsonOpt(packet.timestamp),
+            JsonStr(&packet.payload),
+        )?;
+        self.next_id += 1;
+        Ok(id)
+    }
+
+    /// Write a single [`Item`] of the given hart
+    ///
+    /// The `packet` is the `id` of the [`Packet`] from which the [`Item`] was
+    /// generated, if known.
+    pub fn write_item<I>(
+        &mut self,
+        hart: u64,
+        packet: Option<u64>,
+        item: &Item<I>,
+    ) -> io::Result<()>
+    where
+        I: Info + fmt::Debug,
+    {
+        write!(
+            self.inner,
+            r#"{{"type":"item","hart":{hart},"packet":{},"pc":{}"#,
+            JsonOpt(packet),
+            item.pc(),
+        )?;
+        match item.kind() {
+            item::Kind::Regular(insn) => write!(
+                self.inner,
+                r#","kind":"instruction","size":{},"info":{}"#,
+                u64::from(insn.size),
+                JsonStr(DebugStr(&insn.info)),
+            )?,
+            item::Kind::Trap(info) => write!(
+                self.inner,
+                r#","kind":"trap","interrupt":{},"ecause":{},"tval":{}"#,
+                info.is_interrupt(),
+                info.ecause,
+                JsonOpt(info.tval),
+            )?,
+            item::Kind::Context(ctx) => write!(
+                self.inner,
+                r#","kind":"context","privilege":"{}","context":{}"#,
+                ctx.privilege, ctx.context,
+            )?,
+            item::Kind::Notify => write!(self.inner, r#","kind":"notify""#)?,
+            _ => write!(self.inner, r#","kind":"unknown""#)?,
+        }
+        writeln!(self.inner, "}}")
+    }
+
+    /// Retrieve the number of [`Packet`]s written so far
+    pub fn packets(&self) -> u64 {
+        self.next_id
+    }
+
+    /// Flush the underlying [`io::Write`]
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+
+    /// Retrieve the underlying [`io::Write`]
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+/// [`fmt::Display`] wrapper for values implementing [`fmt::Debug`]
+struct DebugStr<T>(T);
+
+impl<T: fmt::Debug> fmt::Display for DebugStr<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
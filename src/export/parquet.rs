@@ -0,0 +1,87 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Parquet export
+//!
+//! This module provides a [`Writer`] emitting [`Columns`] as a [Parquet][pq]
+//! file following the same [schema] as the [Arrow IPC export][super::arrow].
+//! Parquet files may be queried directly by e.g. DuckDB or DataFusion. Each
+//! [`Columns`] written becomes at least one row group. Data is not compressed.
+//!
+//! # Example
+//!
+//! ```
+//! use riscv_etrace::columnar::Columns;
+//! use riscv_etrace::export::parquet::Writer;
+//! use riscv_etrace::instruction::Kind;
+//! use riscv_etrace::tracer::item::Item;
+//!
+//! let mut columns = Columns::default();
+//! columns.push(0, Some(10), &Item::new(0x1000, Kind::c_nop.into()));
+//!
+//! let mut writer = Writer::new(Vec::new()).unwrap();
+//! writer.write_columns(&columns).unwrap();
+//! let data = writer.finish().unwrap();
+//! assert_eq!(&data[..4], b"PAR1");
+//! ```
+//!
+//! [pq]: <https://parquet.apache.org/docs/file-format/>
+
+use std::io;
+
+use parquet::arrow::ArrowWriter;
+use parquet::errors::ParquetError;
+
+use crate::columnar::Columns;
+
+use super::arrow::{into_io, record_batch, schema};
+
+/// Writer for Parquet files
+pub struct Writer<W: io::Write + Send> {
+    inner: ArrowWriter<W>,
+}
+
+impl<W: io::Write + Send> Writer<W> {
+    /// Create a new writer writing to the given [`io::Write`]
+    pub fn new(inner: W) -> io::Result<Self> {
+        let inner = ArrowWriter::try_new(inner, schema(), None).map_err(from_parquet)?;
+        Ok(Self { inner })
+    }
+
+    /// Write the given [`Columns`]
+    ///
+    /// Empty [`Columns`] are skipped.
+    pub fn write_columns(&mut self, columns: &Columns) -> io::Result<()> {
+        if columns.is_empty() {
+            return Ok(());
+        }
+        let batch = record_batch(columns).map_err(into_io)?;
+        self.inner.write(&batch).map_err(from_parquet)?;
+        self.inner.flush().map_err(from_parquet)
+    }
+
+    /// Finish writing, flushing and returning the underlying [`io::Write`]
+    ///
+    /// This writes the file's footer.
+    pub fn finish(self) -> io::Result<W> {
+        let mut inner = self.inner.into_inner().map_err(from_parquet)?;
+        inner.flush()?;
+        Ok(inner)
+    }
+}
+
+impl<W: io::Write + Send> core::fmt::Debug for Writer<W> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Writer").finish_non_exhaustive()
+    }
+}
+
+/// Convert a [`ParquetError`] into an [`io::Error`]
+fn from_parquet(error: ParquetError) -> io::Error {
+    match error {
+        ParquetError::External(error) => match error.downcast::<io::Error>() {
+            Ok(error) => *error,
+            Err(error) => io::Error::other(error),
+        },
+        error => io::Error::other(error),
+    }
+}
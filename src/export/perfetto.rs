@@ -0,0 +1,287 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Perfetto trace export
+//!
+//! This module provides a [`Writer`] emitting [Perfetto] track events in the
+//! protobuf based trace format, which can be explored in the Perfetto UI or
+//! processed with its trace processor.
+//!
+//! The writer creates one track per hart. Functions are detected via calls and
+//! returns and exported as slices named after the function's entry address.
+//! Traps are exported as instant events. [`Item`]s do not carry timestamps, so
+//! the caller supplies one for every [`Item`], e.g. derived from packet
+//! timestamps or simply the number of [`Item`]s processed so far. [`export`]
+//! uses the timestamps of the packets the [`Item`]s were generated from.
+//!
+//! # Example
+//!
+//! ```
+//! use riscv_etrace::export::perfetto::Writer;
+//! use riscv_etrace::instruction;
+//! use riscv_etrace::tracer::item::Item;
+//!
+//! let mut writer = Writer::new(Vec::new());
+//! let nop = instruction::Instruction::from(instruction::Kind::c_nop);
+//! for i in 0..4 {
+//!     let item: Item = Item::new(0x80000000 + 2 * i, nop.into());
+//!     writer.write_item(0, i, &item).unwrap();
+//! }
+//! let trace = writer.finish().unwrap();
+//! assert!(!trace.is_empty());
+//! ```
+//!
+//! [Perfetto]: https://perfetto.dev/
+
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use std::io;
+
+use crate::binary::Binary;
+use crate::instruction::info::Info;
+use crate::packet::unit;
+use crate::session::Session;
+use crate::tracer::item::{self, Item};
+use crate::transport::Transport;
+use crate::types::stack::ReturnStack;
+
+use super::Error;
+
+/// Export all [`Item`]s in the given trace data
+///
+/// All [`Item`]s are processed like by [`Session::for_each_item`] and written
+/// with the timestamp of the packet they were generated from. Items from
+/// packets without a timestamp inherit the timestamp of the previous item of
+/// the same hart, or `0` if there is none. The export is
+/// [finished][Writer::finish] and the underlying [`io::Write`] returned.
+///
+/// # Example
+///
+/// ```no_run
+/// use riscv_etrace::session::{self, Session};
+///
+/// # let trace_data = &[];
+/// let mut session: Session<_, _> = session::builder().build();
+/// let file = std::fs::File::create("trace.pftrace").unwrap();
+/// riscv_etrace::export::perfetto::export(&mut session, trace_data, file).unwrap();
+/// ```
+pub fn export<U, B, S, I, T, W>(
+    session: &mut Session<U, B, S, I, T>,
+    data: &[u8],
+    inner: W,
+) -> Result<W, Error<B::Error>>
+where
+    U: unit::Unit + Clone,
+    B: Binary<I> + Clone,
+    S: ReturnStack,
+    I: Info + Clone,
+    T: Transport<U> + Clone,
+    W: io::Write,
+{
+    let mut writer = Writer::new(inner);
+    for packet in session.decode_sorted_packets(data)? {
+        let mut res = Ok(());
+        session.process_packet(&packet, |hart, item| {
+            let timestamp = packet
+                .timestamp
+                .or_else(|| writer.harts.get(&hart).map(|h| h.timestamp))
+                .unwrap_or_default();
+            if res.is_ok() {
+                res = writer.write_item(hart, timestamp, &item);
+            }
+        })?;
+        res?;
+    }
+    Ok(writer.finish()?)
+}
+
+/// Sequence id used for all packets emitted
+const SEQUENCE_ID: u64 = 1;
+
+/// Writer for [`Item`]s as Perfetto track events
+///
+/// See the [module][self] documentation for a description of the output.
+#[derive(Debug)]
+pub struct Writer<W: io::Write> {
+    inner: W,
+    harts: BTreeMap<u64, Hart>,
+    buffer: Vec<u8>,
+}
+
+impl<W: io::Write> Writer<W> {
+    /// Create a new writer writing to the given [`io::Write`]
+    pub fn new(inner: W) -> Self {
+        Self {
+            inner,
+            harts: Default::default(),
+            buffer: Default::default(),
+        }
+    }
+
+    /// Write a single [`Item`] of the given hart with the given timestamp
+    ///
+    /// Timestamps are interpreted as nanoseconds by Perfetto and must not
+    /// decrease for any given hart.
+    pub fn write_item<I: Info>(
+        &mut self,
+        hart: u64,
+        timestamp: u64,
+        item: &Item<I>,
+    ) -> io::Result<()> {
+        if !self.harts.contains_key(&hart) {
+            self.write_track(hart)?;
+        }
+        let state = self.harts.entry(hart).or_default();
+        state.timestamp = timestamp;
+
+        if core::mem::take(&mut state.call_pending) {
+            state.depth += 1;
+            let name = std::format!("{:#x}", item.pc());
+            self.write_event(hart, timestamp, EventType::SliceBegin, Some(&name))?;
+        }
+        self.handle_kind(hart, timestamp, item)
+    }
+
+    /// Finish the export
+    ///
+    /// Ends all slices which are still open at the last timestamp of their
+    /// respective hart and returns the underlying [`io::Write`].
+    pub fn finish(mut self) -> io::Result<W> {
+        let harts: Vec<_> = self
+            .harts
+            .iter()
+            .map(|(h, s)| (*h, s.timestamp, s.depth))
+            .collect();
+        for (hart, timestamp, depth) in harts {
+            (0..depth)
+                .try_for_each(|_| self.write_event(hart, timestamp, EventType::SliceEnd, None))?;
+        }
+        self.inner.flush()?;
+        Ok(self.inner)
+    }
+
+    /// Update the hart's state and emit events for the [`Item`]'s kind
+    fn handle_kind<I: Info>(
+        &mut self,
+        hart: u64,
+        timestamp: u64,
+        item: &Item<I>,
+    ) -> io::Result<()> {
+        let state = self.harts.entry(hart).or_default();
+        match item.kind() {
+            item::Kind::Regular(insn) if insn.is_call() => {
+                state.call_pending = true;
+                Ok(())
+            }
+            item::Kind::Regular(insn) if insn.is_return() && state.depth > 0 => {
+                state.depth -= 1;
+                self.write_event(hart, timestamp, EventType::SliceEnd, None)
+            }
+            item::Kind::Regular(_) => Ok(()),
+            item::Kind::Trap(info) => {
+                let name = if info.is_interrupt() {
+                    std::format!("interrupt {}", info.ecause)
+                } else {
+                    std::format!("exception {}", info.ecause)
+                };
+                self.write_event(hart, timestamp, EventType::Instant, Some(&name))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Write a track descriptor for the given hart
+    fn write_track(&mut self, hart: u64) -> io::Result<()> {
+        let mut descriptor = Vec::new();
+        write_varint_field(&mut descriptor, 1, track_uuid(hart));
+        write_bytes_field(&mut descriptor, 2, std::format!("hart {hart}").as_bytes());
+
+        self.buffer.clear();
+        write_varint_field(&mut self.buffer, 10, SEQUENCE_ID);
+        write_bytes_field(&mut self.buffer, 60, &descriptor);
+        self.flush_packet()
+    }
+
+    /// Write a track event for the given hart
+    fn write_event(
+        &mut self,
+        hart: u64,
+        timestamp: u64,
+        kind: EventType,
+        name: Option<&str>,
+    ) -> io::Result<()> {
+        let mut event = Vec::new();
+        write_varint_field(&mut event, 9, kind as u64);
+        write_varint_field(&mut event, 11, track_uuid(hart));
+        if let Some(name) = name {
+            write_bytes_field(&mut event, 23, name.as_bytes());
+        }
+
+        self.buffer.clear();
+        write_varint_field(&mut self.buffer, 8, timestamp);
+        write_varint_field(&mut self.buffer, 10, SEQUENCE_ID);
+        write_bytes_field(&mut self.buffer, 11, &event);
+        self.flush_packet()
+    }
+
+    /// Write the buffered `TracePacket` as an entry of the `Trace`
+    fn flush_packet(&mut self) -> io::Result<()> {
+        let mut header = Vec::new();
+        write_tag(&mut header, 1, WireType::Len);
+        write_varint(&mut header, self.buffer.len() as u64);
+        self.inner.write_all(&header)?;
+        self.inner.write_all(&self.buffer)
+    }
+}
+
+/// Per-hart export state
+#[derive(Copy, Clone, Debug, Default)]
+struct Hart {
+    timestamp: u64,
+    depth: u64,
+    call_pending: bool,
+}
+
+/// Type of a track event
+#[derive(Copy, Clone, Debug)]
+enum EventType {
+    SliceBegin = 1,
+    SliceEnd = 2,
+    Instant = 3,
+}
+
+/// Protobuf wire type
+#[derive(Copy, Clone, Debug)]
+enum WireType {
+    Varint = 0,
+    Len = 2,
+}
+
+/// Compute the uuid of the track for the given hart
+///
+/// The uuid must not be zero.
+fn track_uuid(hart: u64) -> u64 {
+    hart.wrapping_add(1)
+}
+
+fn write_tag(buffer: &mut Vec<u8>, field: u64, wire_type: WireType) {
+    write_varint(buffer, (field << 3) | wire_type as u64);
+}
+
+fn write_varint(buffer: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        buffer.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    buffer.push(value as u8);
+}
+
+fn write_varint_field(buffer: &mut Vec<u8>, field: u64, value: u64) {
+    write_tag(buffer, field, WireType::Varint);
+    write_varint(buffer, value);
+}
+
+fn write_bytes_field(buffer: &mut Vec<u8>, field: u64, value: &[u8]) {
+    write_tag(buffer, field, WireType::Len);
+    write_varint(buffer, value.len() as u64);
+    buffer.extend_from_slice(value);
+}
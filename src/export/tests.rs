@@ -0,0 +1,422 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+
+use super::*;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use std::format;
+
+use crate::instruction;
+use crate::packet::{self, encap, payload, sync};
+use crate::tracer::item::{Item, Kind};
+use crate::transport::Packet;
+use crate::types::{Context, Privilege, trap};
+
+#[test]
+fn json_str_escape() {
+    let escaped = format!("{}", JsonStr("a\"b\\c\nd\u{1}"));
+    assert_eq!(escaped, r#""a\"b\\c\nd\u0001""#);
+}
+
+#[test]
+fn jsonl_packets_and_items() {
+    let mut writer = jsonl::Writer::new(Vec::new());
+    let packet: Packet = Packet {
+        hart: 1,
+        timestamp: None,
+        payload: payload::InstructionTrace::from(sync::Start::new(0x80000000)).into(),
+    };
+    assert_eq!(writer.write_packet(&packet).unwrap(), 0);
+    assert_eq!(writer.write_packet(&packet).unwrap(), 1);
+    assert_eq!(writer.packets(), 2);
+
+    let insn = instruction::Instruction::from(instruction::Kind::c_nop);
+    let items: [Item; 3] = [
+        Item::new(0x80000000, insn.into()),
+        Item::new(
+            0x80000002,
+            Kind::Trap(trap::Info {
+                ecause: 2,
+                tval: Some(0),
+            }),
+        ),
+        Item::new(
+            0x80000100,
+            Kind::Context(Context {
+                privilege: Privilege::Machine,
+                context: 0,
+            }),
+        ),
+    ];
+    items
+        .iter()
+        .try_for_each(|i| writer.write_item(1, Some(1), i))
+        .unwrap();
+    writer.write_item(1, None, &items[0]).unwrap();
+
+    let output = String::from_utf8(writer.into_inner()).unwrap();
+    let lines: Vec<_> = output.lines().collect();
+    assert_eq!(lines.len(), 6);
+    assert!(lines[0].starts_with(
+        r#"{"type":"packet","id":0,"hart":1,"timestamp":null,"format":"sync","payload":""#
+    ));
+    assert!(lines[2].starts_with(
+        r#"{"type":"item","hart":1,"packet":1,"pc":2147483648,"kind":"instruction","size":2,"info":""#
+    ));
+    assert_eq!(
+        lines[3],
+        r#"{"type":"item","hart":1,"packet":1,"pc":2147483650,"kind":"trap","interrupt":false,"ecause":2,"tval":0}"#
+    );
+    assert_eq!(
+        lines[4],
+        r#"{"type":"item","hart":1,"packet":1,"pc":2147483904,"kind":"context","privilege":"M","context":0}"#
+    );
+    assert!(lines[5].starts_with(r#"{"type":"item","hart":1,"packet":null,"#));
+    assert!(lines.iter().all(|l| l.ends_with('}')));
+}
+
+/// Extract the track event types from a Perfetto trace
+///
+/// Only the fields emitted by [`perfetto::Writer`] are supported.
+fn perfetto_events(mut data: &[u8]) -> Vec<(u64, u64)> {
+    fn varint(data: &mut &[u8]) -> u64 {
+        let mut value = 0;
+        for shift in (0..).step_by(7) {
+            let (byte, rest) = data.split_first().unwrap();
+            *data = rest;
+            value |= u64::from(byte & 0x7f) << shift;
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+        value
+    }
+
+    fn fields(mut data: &[u8]) -> Vec<(u64, u64, &[u8])> {
+        let mut res = Vec::new();
+        while !data.is_empty() {
+            let tag = varint(&mut data);
+            if tag & 7 == 2 {
+                let len = varint(&mut data) as usize;
+                let (value, rest) = data.split_at(len);
+                data = rest;
+                res.push((tag >> 3, 0, value));
+            } else {
+                res.push((tag >> 3, varint(&mut data), &[][..]));
+            }
+        }
+        res
+    }
+
+    let mut res = Vec::new();
+    while !data.is_empty() {
+        assert_eq!(varint(&mut data), 0x0a);
+        let len = varint(&mut data) as usize;
+        let (packet, rest) = data.split_at(len);
+        data = rest;
+        let packet = fields(packet);
+        let event = packet.iter().find(|(f, ..)| *f == 11);
+        if let Some((_, _, event)) = event {
+            let timestamp = packet.iter().find(|(f, ..)| *f == 8).unwrap().1;
+            let kind = fields(event).iter().find(|(f, ..)| *f == 9).unwrap().1;
+            res.push((timestamp, kind));
+        } else {
+            assert!(packet.iter().any(|(f, ..)| *f == 60));
+        }
+    }
+    res
+}
+
+#[test]
+fn perfetto_slices() {
+    let call = instruction::Instruction::from(instruction::Kind::new_jal(1, 8));
+    let ret = instruction::Instruction::from(instruction::Kind::new_jalr(0, 1, 0));
+    let nop = instruction::Instruction::from(instruction::Kind::c_nop);
+    let items: [Item; 6] = [
+        Item::new(0x80000000, call.into()),
+        Item::new(0x80000008, nop.into()),
+        Item::new(0x8000000a, call.into()),
+        Item::new(0x80000012, ret.into()),
+        Item::new(
+            0x80000014,
+            Kind::Trap(trap::Info {
+                ecause: 11,
+                tval: None,
+            }),
+        ),
+        Item::new(0x80000004, nop.into()),
+    ];
+
+    let mut writer = perfetto::Writer::new(Vec::new());
+    items
+        .iter()
+        .zip(0..)
+        .try_for_each(|(i, t)| writer.write_item(0, t, i))
+        .unwrap();
+    let trace = writer.finish().unwrap();
+    assert_eq!(
+        perfetto_events(&trace),
+        [(1, 1), (3, 1), (3, 2), (4, 3), (5, 2)]
+    );
+}
+
+#[test]
+fn call_graph() {
+    use callgraph::{Builder, Edge, Symbols};
+
+    let symbols = Symbols::new()
+        .with_function(0x1000..0x1010, "main")
+        .with_function(0x2000..0x2010, "helper")
+        .with_inline_hint(0x2000);
+    let mut builder = Builder::new(symbols);
+    let insn = |k: instruction::Kind| Kind::Regular(k.into());
+    let items: [Item; 8] = [
+        Item::new(0x1000, insn(instruction::Kind::new_jal(1, 0x1000))),
+        Item::new(0x2000, insn(instruction::Kind::new_jal(0, 0x1000))),
+        Item::new(0x3000, insn(instruction::Kind::c_nop)),
+        Item::new(0x3002, insn(instruction::Kind::new_c_jr(1))),
+        Item::new(0x1004, insn(instruction::Kind::new_jal(1, 0x1ffc))),
+        Item::new(0x3000, insn(instruction::Kind::new_c_jr(1))),
+        Item::new(0x1008, insn(instruction::Kind::c_nop)),
+        Item::new(0x100a, Kind::Context(Default::default())),
+    ];
+    items.iter().for_each(|i| builder.process_item(0, i));
+    let graph = builder.finish();
+
+    let edges: Vec<_> = graph.edges().map(|(f, t, e)| (f, t, *e)).collect();
+    assert_eq!(
+        edges,
+        [
+            (
+                0x1000,
+                0x2000,
+                Edge {
+                    calls: 1,
+                    tail_calls: 0
+                }
+            ),
+            (
+                0x1000,
+                0x3000,
+                Edge {
+                    calls: 1,
+                    tail_calls: 0
+                }
+            ),
+            (
+                0x2000,
+                0x3000,
+                Edge {
+                    calls: 0,
+                    tail_calls: 1
+                }
+            ),
+        ],
+    );
+    let nodes: Vec<_> = graph
+        .nodes()
+        .map(|(a, n)| (a, n.inline, n.entries))
+        .collect();
+    assert_eq!(
+        nodes,
+        [(0x1000, false, 0), (0x2000, true, 1), (0x3000, false, 2)]
+    );
+
+    let mut json = Vec::new();
+    graph.write_json(&mut json).unwrap();
+    let json = String::from_utf8(json).unwrap();
+    assert!(json.starts_with(r#"{"nodes":[{"address":4096,"name":"main","inline":false"#));
+    assert!(json.contains(r#"{"address":12288,"name":null,"inline":false,"entries":2}"#));
+    assert!(json.contains(r#"{"caller":8192,"callee":12288,"calls":0,"tail_calls":1}"#));
+
+    let mut dot = Vec::new();
+    graph.write_dot(&mut dot).unwrap();
+    let dot = String::from_utf8(dot).unwrap();
+    assert!(dot.contains("f2000 [label=\"helper\", style=dashed];"));
+    assert!(dot.contains("f3000 [label=\"0x3000\"];"));
+    assert!(dot.contains("f2000 -> f3000 [label=\"1\", style=dashed];"));
+}
+
+#[test]
+fn branch_outcomes() {
+    use branch::{Record, Writer};
+
+    let bne = instruction::Instruction::from(instruction::Kind::new_bne(10, 11, -8));
+    let beqz = instruction::Instruction::from(instruction::Kind::new_c_beqz(10, 0x8));
+    let nop = instruction::Instruction::from(instruction::Kind::nop);
+    let exception = trap::Info {
+        ecause: 2,
+        tval: Some(0),
+    };
+    let items: [Item; 7] = [
+        Item::new(0x1000, nop.into()),
+        Item::new(0x1004, bne.into()),
+        Item::new(0x0ffc, bne.into()),
+        Item::new(0x1000, Kind::Trap(exception)),
+        Item::new(0x100, beqz.into()),
+        Item::new(0x200, nop.into()).with_confidence(crate::tracer::item::Confidence::Gap),
+        Item::new(0x204, beqz.into()),
+    ];
+    let mut writer = Writer::new(Vec::new());
+    items.iter().try_for_each(|i| writer.write_item(i)).unwrap();
+    assert_eq!(writer.records(), 2);
+    let data = writer.finish().unwrap();
+
+    let records: Vec<_> = data.chunks(Record::SIZE).map(Record::from_bytes).collect();
+    let expected = [
+        Record {
+            pc: 0x1004,
+            target: 0x0ffc,
+            taken: true,
+        },
+        Record {
+            pc: 0x0ffc,
+            target: 0x0ff4,
+            taken: false,
+        },
+    ];
+    assert_eq!(records, expected.map(Some));
+    assert_eq!(Record::from_bytes(&[0; 16]), None);
+}
+
+/// Create [`Columns`][crate::columnar::Columns] holding a few items
+#[cfg(feature = "arrow")]
+fn test_columns() -> crate::columnar::Columns {
+    let nop = instruction::Instruction::from(instruction::Kind::c_nop);
+    let interrupt = trap::Info {
+        ecause: 7,
+        tval: None,
+    };
+    let trap: Item = Item::new(0x2000, Kind::Trap(interrupt));
+    let mut columns = crate::columnar::Columns::default();
+    columns.push(0, Some(10), &Item::new(0x1000, nop.into()));
+    columns.push(1, Some(12), &trap);
+    columns.push(1, None, &Item::new(0x2002, nop.into()));
+    columns.push(2, None, &Item::new(0x3000, nop.into()));
+    columns
+}
+
+#[cfg(feature = "arrow")]
+#[test]
+fn arrow_ipc_roundtrip() {
+    let columns = test_columns();
+    let mut writer = arrow::Writer::new(Vec::new()).unwrap();
+    writer.write_columns(&columns).unwrap();
+    writer.write_columns(&Default::default()).unwrap();
+    let data = writer.finish().unwrap();
+
+    let reader = arrow_ipc::reader::FileReader::try_new(std::io::Cursor::new(data), None)
+        .expect("Could not read file");
+    assert_eq!(reader.schema(), arrow::schema());
+    let batches: Vec<_> = reader.map(|b| b.expect("Could not read batch")).collect();
+    assert_eq!(batches, [arrow::record_batch(&columns).unwrap()]);
+    assert_eq!(batches[0].column(3).null_count(), 1);
+}
+
+#[cfg(feature = "parquet")]
+#[test]
+fn parquet_roundtrip() {
+    use ::parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    let columns = test_columns();
+    let mut writer = parquet::Writer::new(Vec::new()).unwrap();
+    writer.write_columns(&columns).unwrap();
+    let data = writer.finish().unwrap();
+
+    let path = std::env::temp_dir().join(std::format!(
+        "riscv-etrace-export-{}.parquet",
+        std::process::id()
+    ));
+    std::fs::write(&path, data).expect("Could not write file");
+    let file = std::fs::File::open(&path).expect("Could not open file");
+    std::fs::remove_file(&path).expect("Could not remove file");
+    let reader = ParquetRecordBatchReaderBuilder::try_new(file)
+        .and_then(|b| b.build())
+        .expect("Could not read file");
+    let batches: Vec<_> = reader.map(|b| b.expect("Could not read batch")).collect();
+    assert_eq!(batches, [arrow::record_batch(&columns).unwrap()]);
+    assert_eq!(batches[0].column(3).null_count(), 1);
+}
+
+/// [`Session`][crate::session::Session] used for testing session exports
+type TestSession = crate::session::Session<
+    packet::unit::Reference,
+    crate::binary::basic::SimpleMap<
+        [(u64, instruction::Instruction); 2],
+        Option<instruction::Kind>,
+    >,
+>;
+
+/// Encode a few packets for multiple harts and build a session for them
+fn test_session() -> (Vec<u8>, TestSession) {
+    let start = sync::Start::new(0x80000000);
+    let trap = sync::Trap::new(
+        0x80000100,
+        trap::Info {
+            ecause: 7,
+            tval: None,
+        },
+    );
+    let packets: [(u16, u64, payload::InstructionTrace); 3] = [
+        (1, 20, start.into()),
+        (0, 10, start.into()),
+        (1, 30, trap.into()),
+    ];
+
+    let decoder = packet::builder()
+        .with_hart_index_width(8)
+        .with_timestamp_width(1)
+        .with_compression(false);
+    let mut data = std::vec![0; 64];
+    let mut encoder = decoder.encoder(data.as_mut());
+    packets.iter().for_each(|(hart, timestamp, payload)| {
+        let packet = encap::Normal::new(0, *hart, payload::Payload::from(*payload))
+            .with_timestamp(*timestamp);
+        encoder
+            .encode(&encap::Packet::from(packet))
+            .expect("Could not encode packet");
+    });
+    let uncommitted = encoder.uncommitted();
+    data.truncate(data.len() - uncommitted);
+
+    let compressed = instruction::COMPRESSED;
+    let binary =
+        crate::binary::from_sorted_map([(0x80000000, compressed), (0x80000100, compressed)])
+            .expect("Could not construct binary");
+    let session = crate::session::builder()
+        .with_decoder(decoder)
+        .with_binary(binary)
+        .build();
+    (data, session)
+}
+
+#[test]
+fn jsonl_session_export() {
+    let (data, mut session) = test_session();
+    let output = jsonl::export(&mut session, &data, Vec::new()).expect("Could not export");
+    let output = String::from_utf8(output).unwrap();
+    let lines: Vec<_> = output.lines().collect();
+    assert_eq!(lines.len(), 10);
+    assert!(lines[0].starts_with(r#"{"type":"packet","id":0,"hart":0,"timestamp":10,"#));
+    assert!(lines[1].starts_with(r#"{"type":"item","hart":0,"packet":0,"#));
+    assert!(lines[3].starts_with(r#"{"type":"packet","id":1,"hart":1,"timestamp":20,"#));
+    assert!(lines[6].starts_with(r#"{"type":"packet","id":2,"hart":1,"timestamp":30,"#));
+    assert!(lines[9].starts_with(r#"{"type":"item","hart":1,"packet":2,"#));
+}
+
+#[test]
+fn perfetto_session_export() {
+    let (data, mut session) = test_session();
+    let trace = perfetto::export(&mut session, &data, Vec::new()).expect("Could not export");
+    assert_eq!(perfetto_events(&trace), [(30, 3)]);
+}
+
+#[test]
+fn text_session_export() {
+    let (data, mut session) = test_session();
+    let output = text::export(&mut session, &data, Vec::new()).expect("Could not export");
+    let output = String::from_utf8(output).unwrap();
+    assert_eq!(output.lines().count(), 7);
+    assert!(output.starts_with("[0]         80000000:\t<context"));
+}
@@ -0,0 +1,128 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Plain text export
+//!
+//! This module provides a [`Writer`] emitting one line per [`Item`] in a
+//! format resembling the disassembly output of `objdump`. Every line starts
+//! with the hart in brackets and the PC, followed by the [`Instruction`] for
+//! regular items. Traps and context changes are written in angle brackets.
+//! The [faulting instruction][Item::fault] of an exception is included if
+//! available.
+//!
+//! # Example
+//!
+//! ```
+//! use riscv_etrace::export::text::Writer;
+//! use riscv_etrace::instruction::{Instruction, Kind};
+//! use riscv_etrace::tracer::item::Item;
+//!
+//! let mut writer = Writer::new(Vec::new());
+//! let insn = Instruction::from(Kind::new_c_jr(1));
+//! let item: Item = Item::new(0x80000000, insn.into());
+//! writer.write_item(0, &item).unwrap();
+//!
+//! let output = String::from_utf8(writer.into_inner()).unwrap();
+//! assert_eq!(output, "[0]         80000000:\tc.jr x1\n");
+//! ```
+
+use core::fmt;
+use std::io;
+
+use crate::binary::Binary;
+use crate::instruction::{Instruction, info::Info};
+use crate::packet::unit;
+use crate::session::Session;
+use crate::tracer::item::{self, Item};
+use crate::transport::Transport;
+use crate::types::stack::ReturnStack;
+
+use super::Error;
+
+/// Export all [`Item`]s in the given trace data
+///
+/// All [`Item`]s are processed like by [`Session::for_each_item`] and written
+/// in the order in which they are generated. The underlying [`io::Write`] is
+/// flushed and returned.
+///
+/// # Example
+///
+/// ```no_run
+/// use riscv_etrace::session::{self, Session};
+///
+/// # let trace_data = &[];
+/// let mut session: Session<_, _> = session::builder().build();
+/// riscv_etrace::export::text::export(&mut session, trace_data, std::io::stdout()).unwrap();
+/// ```
+pub fn export<U, B, S, I, T, W>(
+    session: &mut Session<U, B, S, I, T>,
+    data: &[u8],
+    inner: W,
+) -> Result<W, Error<B::Error>>
+where
+    U: unit::Unit + Clone,
+    B: Binary<I> + Clone,
+    S: ReturnStack,
+    I: Info + Clone,
+    Instruction<I>: fmt::Display,
+    T: Transport<U> + Clone,
+    W: io::Write,
+{
+    let mut writer = Writer::new(inner);
+    let mut res = Ok(());
+    session.for_each_item(data, |hart, item| {
+        if res.is_ok() {
+            res = writer.write_item(hart, &item);
+        }
+    })?;
+    res?;
+    writer.flush()?;
+    Ok(writer.into_inner())
+}
+
+/// Writer for [`Item`]s in a plain text format
+///
+/// See the [module][self] documentation for a description of the format.
+#[derive(Debug)]
+pub struct Writer<W> {
+    inner: W,
+}
+
+impl<W: io::Write> Writer<W> {
+    /// Create a new writer writing to the given [`io::Write`]
+    pub fn new(inner: W) -> Self {
+        Self { inner }
+    }
+
+    /// Write a single [`Item`] of the given hart
+    pub fn write_item<I>(&mut self, hart: u64, item: &Item<I>) -> io::Result<()>
+    where
+        I: Info,
+        Instruction<I>: fmt::Display,
+    {
+        write!(self.inner, "[{hart}] {:>16x}:\t", item.pc())?;
+        match item.kind() {
+            item::Kind::Regular(insn) => writeln!(self.inner, "{insn}"),
+            item::Kind::Trap(info) => match item.fault() {
+                Some(insn) => writeln!(self.inner, "<{info} at {insn}>"),
+                None => writeln!(self.inner, "<{info}>"),
+            },
+            item::Kind::Context(ctx) => writeln!(
+                self.inner,
+                "<context (privilege: {}, context: {:#x})>",
+                ctx.privilege, ctx.context,
+            ),
+            item::Kind::Notify => writeln!(self.inner, "<notify>"),
+            _ => writeln!(self.inner, "<unknown>"),
+        }
+    }
+
+    /// Flush the underlying [`io::Write`]
+    pub fn flush(&mut self) -> io::Result<()> {
+        self.inner.flush()
+    }
+
+    /// Retrieve the underlying [`io::Write`]
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
@@ -36,6 +36,25 @@
 //! of implementations and utilities for constructing one, including limited
 //! instruction decoding capabilities.
 //!
+//! The most commonly used items are re-exported in the [`prelude`]. With the
+//! `alloc` feature, a [`session::Session`] bundles decoding and tracing for
+//! multiple harts.
+//!
+//! # Crate structure
+//!
+//! The decoder, the tracer and all other functionality not depending on `std`
+//! or on parsers for program files live in the [`riscv_etrace_core`] crate,
+//! which is intended for embedded users requiring a minimal dependency graph.
+//! This crate re-exports all of its modules and adds tooling such as
+//! [`Binary`][binary::Binary]s for ELF files, [`export`]ers and [`columnar`]
+//! bulk tracing.
+//!
+//! The [`session`] module lives in the core crate behind its `alloc` feature
+//! since [`session::Session`] requires allocation but not `std`, and is thus
+//! usable on `no_std` targets providing a global allocator. This crate's
+//! [`session`] module re-exports it, adding functionality relying on tooling
+//! such as tracing ELF files.
+//!
 //! # E-Trace options
 //!
 //! The following [E-Trace][etrace] options are supported:
@@ -47,17 +66,51 @@
 //!
 //! Some functionality if controlled via crate features:
 //! * `alloc`: enables some features that require allocation
-//! * `either`: enables impls of various traits for [`either::Either`]
+//! * `arrow`: enables the [`export::arrow`] module for exporting
+//!   [`Columns`][columnar::Columns] as Arrow IPC files, implies `std`
+//! * `either`: enables impls of various traits for `either::Either`
 //! * `elf`: enables the [`binary::elf`] module providing a
 //!   [`Binary`][binary::Binary] for static ELF files using the [`elf`] crate
+//! * `embedded`: enables the [`embedded`] module providing a driver for
+//!   self-tracing on embedded targets
+//! * `fixtures`: enables the [`fixtures`] module providing known-good programs
+//!   and payloads for sanity-checking integrations
+//! * `lz4`: enables decompression of LZ4 compressed [capture]s, implies `std`
+//! * `metrics`: enables the [`metrics`] module for measuring the time spent
+//!   decoding and tracing
+//! * `parquet`: enables the [`export::parquet`] module for exporting
+//!   [`Columns`][columnar::Columns] as Parquet files, implies `arrow`
 //! * `riscv-isa`: enables support for decoding and tracing
-//!   [`riscv_isa::Instruction`]s instead of [`instruction::Kind`].
-//! * `serde`: enables (de)serialization of configuration via [`serde`]
+//!   `riscv_isa::Instruction`s instead of [`instruction::Kind`], including
+//!   the [`instruction::isa::Adapter`] providing operand access.
+//! * `self-check`: enables checking the [tracer]'s stop conditions against a
+//!   shadow implementation of the reference pseudo-code for every instruction,
+//!   panicking on divergence; intended for development only
+//! * `serde`: enables (de)serialization of configuration via `serde`
+//! * `std`: enables the [`export`] module providing writers for exporting
+//!   tracing data, reading of [capture] files, loading of
+//!   [corpora][corpus::Corpus] from directories and, together with `elf`,
+//!   lazily loaded ELF files, implies `alloc`
+//! * `zstd`: enables decompression of Zstandard compressed [capture]s,
+//!   implies `std`
+//!
+//! All features other than `arrow`, `elf`, `lz4`, `parquet`, `std` and `zstd`
+//! are forwarded to the core crate.
+//!
+//! # Panics
+//!
+//! Decoding packets, tracing and retrieving
+//! [`Instruction`][instruction::Instruction]s from the
+//! [`Binary`][binary::Binary]s provided by this library do not panic,
+//! regardless of the trace data or program binary processed. Malformed inputs
+//! are reported as errors instead. This guarantee only holds for
+//! [`config::Parameters`] within the limits defined by the specification and
+//! if the `self-check` feature is not enabled.
 //!
 //! # no_std
 //!
-//! This crate does not dependent on `std` and is thus suitable for `no_std`
-//! environments.
+//! Unless the `std` feature is enabled, this crate does not depend on `std`
+//! and is thus suitable for `no_std` environments.
 //!
 //! # Example
 //!
@@ -114,14 +167,27 @@
 
 #[cfg(feature = "alloc")]
 extern crate alloc;
-
-#[cfg(test)]
-mod tests;
+#[cfg(feature = "std")]
+extern crate std;
 
 pub mod binary;
-pub mod config;
-pub mod generator;
-pub mod instruction;
-pub mod packet;
-pub mod tracer;
-pub mod types;
+#[cfg(feature = "std")]
+pub mod capture;
+#[cfg(feature = "std")]
+pub mod columnar;
+pub mod corpus;
+#[cfg(feature = "std")]
+pub mod export;
+pub mod prelude;
+#[cfg(feature = "alloc")]
+pub mod session;
+
+#[cfg(feature = "embedded")]
+pub use riscv_etrace_core::embedded;
+#[cfg(feature = "fixtures")]
+pub use riscv_etrace_core::fixtures;
+#[cfg(feature = "metrics")]
+pub use riscv_etrace_core::metrics;
+pub use riscv_etrace_core::{
+    analysis, code, config, generator, instruction, packet, tracer, transport, types,
+};
@@ -0,0 +1,17 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Commonly used items
+//!
+//! This module re-exports the [prelude][riscv_etrace_core::prelude] of the
+//! core crate along with the tooling provided by this crate. It is intended to
+//! be glob-imported.
+
+pub use riscv_etrace_core::prelude::*;
+
+pub use crate::binary;
+
+#[cfg(feature = "alloc")]
+pub use crate::session;
+
+#[cfg(all(feature = "alloc", feature = "elf"))]
+pub use crate::session::trace_elf;
@@ -0,0 +1,61 @@
+// Copyright (C) 2026 FZI Forschungszentrum Informatik
+// SPDX-License-Identifier: Apache-2.0
+//! Tracing sessions covering multiple harts
+//!
+//! This module re-exports the [sessions][riscv_etrace_core::session] of the
+//! core crate. With the `elf` feature, it additionally provides [`trace_elf`]
+//! for tracing a program given as an ELF file with minimal effort.
+
+pub use riscv_etrace_core::session::*;
+
+#[cfg(feature = "elf")]
+use crate::binary::elf;
+#[cfg(feature = "elf")]
+use crate::config;
+#[cfg(feature = "elf")]
+use crate::tracer::item::Item;
+#[cfg(feature = "elf")]
+use error::Error;
+
+/// Trace all harts in the given trace data for the given ELF file
+///
+/// This fn parses the ELF file, builds a [`Session`] for encoders with the
+/// given [`config::Parameters`] and the [`Reference`] unit
+/// and processes all [`Item`]s in the trace data like
+/// [`for_each_item`][Session::for_each_item]. Packets are expected to be
+/// [`Framing::Encap`]sulated. The items are returned along with the index of
+/// the hart they belong to.
+///
+/// # Example
+///
+/// ```no_run
+/// # let elf_data = &[];
+/// # let trace_data = &[];
+/// for (hart, item) in riscv_etrace::session::trace_elf(elf_data, &Default::default(), trace_data)
+///     .unwrap()
+/// {
+///     println!("Hart {hart}, PC: {:0x}", item.pc());
+/// }
+/// ```
+///
+/// [`Reference`]: crate::packet::unit::Reference
+#[cfg(feature = "elf")]
+pub fn trace_elf(
+    elf: &[u8],
+    params: &config::Parameters,
+    data: &[u8],
+) -> Result<alloc::vec::IntoIter<(u64, Item)>, Error<elf::Error>> {
+    use ::elf::{ElfBytes, endian::LittleEndian};
+
+    use crate::types::stack;
+
+    let elf = ElfBytes::<LittleEndian>::minimal_parse(elf)
+        .map_err(|e| Error::Binary(elf::Error::CouldNotRetrieveData(e)))?;
+    let binary: elf::Elf<_, _> = elf::Elf::new(alloc::rc::Rc::new(elf)).map_err(Error::Binary)?;
+    let mut session: Session<_, _, stack::VecStack> =
+        builder().with_params(params).with_binary(binary).build();
+
+    let mut items = alloc::vec::Vec::new();
+    session.for_each_item(data, |hart, item| items.push((hart, item)))?;
+    Ok(items.into_iter())
+}